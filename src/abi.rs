@@ -1,5 +1,6 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use ethers_core::abi::Abi;
+use std::collections::BTreeMap;
 
 pub const INKAN_ABI_JSON: &str = r#"[{
   "type":"function","name":"createDelegationEvent","stateMutability":"nonpayable",
@@ -87,3 +88,64 @@ pub fn load_abi() -> Result<Abi> {
     Ok(serde_json::from_str::<Abi>(INKAN_ABI_JSON)?)
 }
 
+
+/// One function of an ABI: its 4-byte selector as `0x` hex, name and canonical signature.
+pub struct SelectorRow {
+    pub selector: String,
+    pub name: String,
+    pub signature: String,
+}
+
+/// Every function of `abi`, by name (overloads in declaration order).
+pub fn selector_rows(abi: &Abi) -> Vec<SelectorRow> {
+    abi.functions()
+        .map(|f| SelectorRow {
+            selector: format!("0x{}", hex::encode(f.short_signature())),
+            name: f.name.clone(),
+            signature: f.signature(),
+        })
+        .collect()
+}
+
+/// Parse an ABI from JSON: a bare ABI array, or a build artifact (Foundry, Hardhat) carrying
+/// one in its `abi` member.
+pub fn parse_abi_json(text: &str) -> Result<Abi> {
+    let value: serde_json::Value = serde_json::from_str(text).context("not JSON")?;
+    let abi = match value {
+        serde_json::Value::Array(_) => value,
+        serde_json::Value::Object(mut map) => match map.remove("abi") {
+            Some(abi) => abi,
+            None => bail!("neither an ABI array nor an artifact with an \"abi\" member"),
+        },
+        _ => bail!("neither an ABI array nor an artifact with an \"abi\" member"),
+    };
+    serde_json::from_value(abi).context("not a valid ABI")
+}
+
+/// Selectors shared by functions with different signatures across `abis` (each named for the
+/// message), one line per function involved. A contract cannot hold two of them, and a call
+/// built against one ABI would run the other's function on a contract built from the other.
+pub fn selector_collisions(abis: &[(&str, &Abi)]) -> Vec<String> {
+    let mut by_selector: BTreeMap<String, Vec<(String, &str)>> = BTreeMap::new();
+    for (label, abi) in abis {
+        for row in selector_rows(abi) {
+            let seen = by_selector.entry(row.selector).or_default();
+            if !seen.iter().any(|(sig, _)| *sig == row.signature) {
+                seen.push((row.signature, label));
+            }
+        }
+    }
+    let mut lines = Vec::new();
+    for (selector, funcs) in by_selector.into_iter().filter(|(_, f)| f.len() > 1) {
+        lines.push(format!("{selector} is shared by:"));
+        lines.extend(funcs.iter().map(|(sig, label)| format!("  {sig} ({label})")));
+    }
+    lines
+}
+
+/// Signatures of `expected` that `other` lacks, e.g. Inkan functions a contract build no longer
+/// exposes as this utility encodes them.
+pub fn missing_signatures(expected: &Abi, other: &Abi) -> Vec<String> {
+    let theirs: Vec<String> = other.functions().map(|f| f.signature()).collect();
+    expected.functions().map(|f| f.signature()).filter(|sig| !theirs.contains(sig)).collect()
+}
@@ -3,19 +3,38 @@ use async_trait::async_trait;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear as TermClear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
     layout::Rect,
     prelude::Frame,
-    widgets::Clear,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
     Terminal,
 };
 use std::collections::HashMap;
 use std::io;
+use std::path::PathBuf;
 
-use crate::screens::ConfirmQuitScreen;
+use crate::app_state::{AppEvent, Form, HandoffState};
+use crate::commands::recovery::{output_dirs, scan};
+use crate::screens::{AfterOk, ConfirmOkScreen, ConfirmQuitScreen, SandboxOverrideScreen};
+use crate::screens::hardening_report::HardeningReportScreen;
+use crate::screens::ceremony_checklist::CeremonyChecklistScreen;
+use crate::ui::scrub::scrub_terminal;
+use crate::key_usage::{load_key_usage, KeyUsage};
+use crate::migrate::migrate_stores;
+use crate::profile::{profile_enabled, ProfileKey};
+use crate::event_stream::EventStream;
+use crate::settings::{load_settings, Settings};
+use crate::vfs::{FsRef, Sandbox, SandboxFs};
+use crate::staging::{Staging, StagingFs};
+use crate::ceremony_checklist::Ceremony;
+use crate::workspace;
+use crate::memory_hygiene;
+use crate::hardening;
 
 pub enum Transition {
     Stay,
@@ -25,6 +44,8 @@ pub enum Transition {
     Quit,
     // NEW: pop multiple screens at once
     PopN(usize),
+    /// Clear the whole stack and start over from this screen (a workspace switch).
+    Reset(Box<dyn ScreenWidget>),
 }
 
 /// Small handoff bucket used when loading a delegation/revocation/redelegation/permanent-invalidation info file.
@@ -34,43 +55,230 @@ pub enum Transition {
 #[derive(Debug, Clone, Default)]
 pub struct DelegationPrefill {
     pub map: HashMap<String, String>,
+    /// SHA-256 (hex) of the info file the map was loaded from (and its includes).
+    pub source_sha256: Option<String>,
+    /// Files pulled in with `INCLUDE=`, in the order read.
+    pub includes: Vec<PathBuf>,
+}
+
+/// A private key decrypted from a key file for one field of a Create form (see
+/// `screens::load_signing_key`). The form takes it once; the hex is zeroized when dropped.
+#[derive(Clone, zeroize::ZeroizeOnDrop)]
+pub struct LoadedSigningKey {
+    /// Index of the form field the key goes into.
+    #[zeroize(skip)]
+    pub field: usize,
+    /// `0x` hex of the secret key.
+    pub privkey_hex: String,
+    /// Where it came from, shown in place of the key: file name, key label and fingerprint.
+    #[zeroize(skip)]
+    pub source: String,
+}
+
+/// Where a "Load From File" directory picker and its file list were last left,
+/// so re-opening the flow resumes there instead of at the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct PickerMemory {
+    pub dir: Option<String>,
+    pub selected: Option<PathBuf>,
 }
 
 #[derive(Default)]
 pub struct AppCtx {
-    pub result_text: String,
+    /// Prefills, form drafts and picker positions; changed only by `dispatch`.
+    pub state: HandoffState,
+
+    /// User settings loaded at startup (see `settings.rs`).
+    pub settings: Settings,
+
+    /// Session key of the master-password profile, once unlocked (see `profile.rs`).
+    /// Set through `dispatch`, like `key_usage`.
+    pub profile_key: Option<ProfileKey>,
 
-    /// If set, contains key/value pairs loaded from a delegation info file.
-    /// The Delegation Input screen should `take()` and apply these once.
-    pub pending_delegation_prefill: Option<DelegationPrefill>,
+    /// Signing statistics and retired keys, loaded with the settings (see `key_usage.rs`).
+    pub key_usage: KeyUsage,
 
-    /// If set, contains key/value pairs loaded from a revocation info file.
-    /// The Revocation Input screen should `take()` and apply these once.
-    pub pending_revocation_prefill: Option<DelegationPrefill>,
+    /// Filesystem the screens read and write through (the real disk unless replaced),
+    /// confined to `sandbox` and staged through `staging` once set up with `set_fs`.
+    pub fs: FsRef,
 
-    /// If set, contains key/value pairs loaded from a re-delegation info file.
-    /// The Re-Delegation Input screen should `take()` and apply these once.
-    pub pending_redelegation_prefill: Option<DelegationPrefill>,
+    /// Directories `fs` may touch; follows `settings.sandbox_roots` via `set_settings`.
+    pub sandbox: Sandbox,
 
-    /// If set, contains key/value pairs loaded from a permanent invalidation info file.
-    /// The Permanent Invalidation Input screen should `take()` and apply these once.
-    pub pending_permanent_invalidation_prefill: Option<DelegationPrefill>,
+    /// Per-run ephemeral directory that `fs` stages writes in; follows
+    /// `settings.ephemeral_run_dir` via `set_settings` (see `staging.rs`).
+    pub staging: Staging,
+
+    /// Progress events for external monitoring; follows `settings.event_stream` via `set_settings`.
+    pub events: EventStream,
+
+    /// Ceremony checklist gating the signing screens, with this session's ticks; follows
+    /// `settings.ceremony_checklist` via `set_settings` (see `ceremony_checklist.rs`).
+    pub ceremony: Option<Ceremony>,
+}
+
+impl AppCtx {
+    /// Use `fs` for all screen file access, behind this context's sandbox and staging.
+    pub fn set_fs(&mut self, fs: FsRef) {
+        let staged = FsRef(std::sync::Arc::new(StagingFs::new(fs, self.staging.clone())));
+        self.fs = FsRef(std::sync::Arc::new(SandboxFs::new(staged, self.sandbox.clone())));
+    }
+
+    /// Replace the session settings, keeping the sandbox roots, staging, event stream and
+    /// ceremony checklist in step. The checklist keeps its ticks unless its path changed.
+    pub fn set_settings(&mut self, settings: Settings) {
+        self.sandbox.set_roots(&settings.sandbox_roots);
+        self.staging.set_enabled(settings.ephemeral_run_dir);
+        self.events.set_target(&settings.event_stream);
+        let checklist = settings.ceremony_checklist.trim();
+        if self.ceremony.as_ref().map(|c| c.path.as_str()) != Some(checklist).filter(|p| !p.is_empty()) {
+            self.ceremony = (!checklist.is_empty()).then(|| Ceremony::load(checklist));
+        }
+        self.settings = settings;
+    }
+
+    /// Apply `event` from the screen named `source` (its breadcrumb) and log it.
+    pub fn dispatch(&mut self, source: &str, event: AppEvent) {
+        self.state.record(source, &event);
+        if let AppEvent::WorkspaceOpened(_) = event {
+            // Nothing unlocked or loaded in the previous workspace carries over
+            self.profile_key = None;
+            self.key_usage = KeyUsage::default();
+        }
+        match event {
+            AppEvent::ProfileUnlocked { key, usage } => {
+                self.key_usage = usage;
+                self.profile_key = Some(key);
+            }
+            AppEvent::ProfileKeyChanged(key) => self.profile_key = key,
+            AppEvent::KeyUsageChanged(usage) => self.key_usage = usage,
+            other => self.state.reduce(other),
+        }
+    }
+
+    /// The pending prefill for `form`, consumed by this call.
+    pub fn take_prefill(&mut self, source: &str, form: Form) -> Option<DelegationPrefill> {
+        let prefill = self.state.prefill(form)?.clone();
+        self.dispatch(source, AppEvent::PrefillTaken(form));
+        Some(prefill)
+    }
+
+    /// The key loaded for `form` from an encrypted key file, consumed by this call.
+    pub fn take_signing_key(&mut self, source: &str, form: Form) -> Option<LoadedSigningKey> {
+        let key = self.state.signing_key(form)?.clone();
+        self.dispatch(source, AppEvent::SigningKeyTaken(form));
+        Some(key)
+    }
+
+    /// The draft `form` was last left with, consumed by this call.
+    pub fn take_draft(&mut self, source: &str, form: Form) -> Option<HashMap<String, String>> {
+        let draft = self.state.draft(form)?.clone();
+        self.dispatch(source, AppEvent::DraftTaken(form));
+        Some(draft)
+    }
 }
 
 #[async_trait]
-pub trait ScreenWidget {
+pub trait ScreenWidget: Send {
     fn title(&self) -> &str { "Inkan" }
+
+    /// Name of this screen in the breadcrumb header; empty for dialogs, which are left out.
+    fn crumb(&self) -> &str { "" }
     fn draw(&self, f: &mut Frame<'_>, area: Rect, ctx: &AppCtx);
 
     /// Called before each draw when this screen is on top.
     /// Use this to apply any pending prefill immediately upon returning.
     fn apply_prefill(&mut self, _ctx: &mut AppCtx) {}
 
+    /// True for screens that render secret material (passwords, private keys).
+    /// When such a screen leaves the stack the terminal and its scrollback are scrubbed.
+    fn shows_secrets(&self) -> bool { false }
+
+    /// Describes work still running on this screen (e.g. a signing batch), if any.
+    /// Quit confirmation shows it and offers to save what is complete.
+    fn busy(&self) -> Option<String> { None }
+
+    /// True for screens that sign transactions. While a ceremony checklist is configured and
+    /// not complete, the checklist is put over them as they open.
+    fn signs(&self) -> bool { false }
+
+    /// Advance background work by one step. Called every loop turn while this
+    /// screen is on top and `busy()`; keys are still handled between steps.
+    async fn tick(&mut self, _ctx: &mut AppCtx) -> Result<Transition> { Ok(Transition::Stay) }
+
+    /// The app is quitting while this screen is busy: stop at a clean point and flush
+    /// whatever is complete. The returned note is printed after the terminal is restored.
+    fn finish_for_quit(&mut self, _ctx: &AppCtx) -> Option<String> { None }
+
     async fn on_key(&mut self, key: KeyEvent, ctx: &mut AppCtx) -> Result<Transition>;
 }
 
+/// Apply a transition to the stack. Returns `true` to quit; sets `scrub` when a
+/// secret-bearing screen was popped.
+fn apply_transition(stack: &mut Vec<Box<dyn ScreenWidget>>, transition: Transition, scrub: &mut bool) -> bool {
+    let mut pop = |stack: &mut Vec<Box<dyn ScreenWidget>>| match stack.pop() {
+        Some(s) => { *scrub |= s.shows_secrets(); true }
+        None => false,
+    };
+    match transition {
+        Transition::Stay => {}
+        Transition::Push(s) => stack.push(s),
+        Transition::Pop => {
+            pop(stack);
+        }
+        Transition::Replace(s) => {
+            pop(stack);
+            stack.push(s);
+        }
+        Transition::Quit => return true,
+        // pop multiple levels
+        Transition::PopN(n) => {
+            for _ in 0..n {
+                if !pop(stack) { break; }
+            }
+        }
+        Transition::Reset(s) => {
+            while pop(stack) {}
+            stack.push(s);
+        }
+    }
+    stack.is_empty()
+}
+
+/// Put the terminal back to cooked mode on the main screen. Safe to call more than once.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+}
+
+/// Restores the terminal if `run_menu` exits early (an `?` error or a panic unwinding),
+/// so a failure mid-ceremony never leaves the shell in raw mode on the alternate screen.
+struct TerminalGuard {
+    armed: bool,
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            restore_terminal();
+        }
+    }
+}
+
+/// Restore the terminal before the default panic hook prints, so the message lands
+/// on the normal screen instead of vanishing with the alternate one.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+}
+
 pub async fn run_menu() -> Result<()> {
     // terminal init
+    install_panic_hook();
+    let mut guard = TerminalGuard { armed: true };
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -78,8 +286,19 @@ pub async fn run_menu() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?; // clean start
 
-    let mut ctx = AppCtx::default();
-    let mut stack: Vec<Box<dyn ScreenWidget>> = vec![Box::new(crate::screens::MainMenuScreen::default())];
+    let (mut ctx, mut stack) = initial_state();
+    // Risky surroundings (recorded terminal, plain swap, open output dirs, core dumps) come first
+    let findings = hardening::check();
+    if hardening::any_warning(&findings) {
+        stack.push(Box::new(HardeningReportScreen::at_startup(hardening::report_lines(&findings))));
+    }
+
+    // F12 "panic" blanking: nothing is drawn until the next key press.
+    let mut blanked = false;
+    // Set once any secret-bearing screen has been shown; the exit path then scrubs too.
+    let mut secrets_shown = false;
+    // Printed after the terminal is restored (e.g. where a partial batch was saved).
+    let mut exit_notes: Vec<String> = Vec::new();
 
     loop {
         // Allow the top screen to apply any pending prefill before rendering.
@@ -88,49 +307,59 @@ pub async fn run_menu() -> Result<()> {
         }
 
         terminal.draw(|f| {
-            let size = f.size();
-            if let Some(top) = stack.last() {
-                top.draw(f, size, &ctx);
+            if blanked {
+                f.render_widget(Clear, f.size());
             } else {
-                // just in case—clear remaining area
-                f.render_widget(Clear, size);
+                draw_stack(f, &stack, &ctx);
             }
         })?;
 
-        if event::poll(std::time::Duration::from_millis(250))? {
+        // Busy screens get stepped whenever no key is waiting.
+        let busy = stack.last().is_some_and(|s| s.busy().is_some());
+        let wait = std::time::Duration::from_millis(if busy { 0 } else { 250 });
+        if !event::poll(wait)? {
+            if busy {
+                let mut scrub = false;
+                if tick_top(&mut stack, &mut ctx, &mut scrub).await? {
+                    finish_busy(&mut stack, &ctx, &mut exit_notes);
+                    break;
+                }
+                if scrub {
+                    secrets_shown = true;
+                    scrub_terminal(&mut terminal)?;
+                }
+            }
+        } else {
             match event::read()? {
                 Event::Key(k) if k.kind == KeyEventKind::Press => {
-                    // GLOBAL HOTKEY: Ctrl+Q shows confirm quit from anywhere
-                    if k.modifiers.contains(KeyModifiers::CONTROL) && matches!(k.code, KeyCode::Char('q' | 'Q')) {
-                        stack.push(Box::new(ConfirmQuitScreen::new()));
+                    // While blanked, any key only restores the screen.
+                    if blanked {
+                        blanked = false;
                         continue;
                     }
 
-                    if let Some(top) = stack.last_mut() {
-                        match top.on_key(k, &mut ctx).await? {
-                            Transition::Stay => {}
-                            Transition::Push(s) => stack.push(s),
-                            Transition::Pop => {
-                                stack.pop();
-                                if stack.is_empty() {
-                                    break;
-                                }
-                            }
-                            Transition::Replace(s) => {
-                                stack.pop();
-                                stack.push(s);
-                            }
-                            Transition::Quit => break,
-                            // pop multiple levels
-                            Transition::PopN(n) => {
-                                for _ in 0..n {
-                                    if stack.pop().is_none() { break; }
-                                }
-                                if stack.is_empty() {
-                                    break;
-                                }
-                            }
+                    // GLOBAL HOTKEY: F12 blanks the screen instantly (shared rooms, KVMs, screenshots)
+                    if k.code == KeyCode::F(12) {
+                        blanked = true;
+                        if ctx.settings.purge_scrollback {
+                            scrub_terminal(&mut terminal)?;
+                        } else {
+                            terminal.clear()?;
                         }
+                        continue;
+                    }
+
+                    let mut scrub = false;
+                    if dispatch_key(&mut stack, &mut ctx, k, &mut scrub).await? {
+                        finish_busy(&mut stack, &ctx, &mut exit_notes);
+                        break;
+                    }
+                    if scrub {
+                        secrets_shown = true;
+                        scrub_terminal(&mut terminal)?;
+                    }
+                    if stack.iter().any(|s| s.shows_secrets()) {
+                        secrets_shown = true;
                     }
                 }
                 _ => {}
@@ -138,10 +367,195 @@ pub async fn run_menu() -> Result<()> {
         }
     }
 
+    // Staged outputs nobody kept are shredded with the run directory
+    exit_notes.extend(ctx.staging.finish());
+    // Zeroize what the session held before handing the terminal back
+    exit_notes.extend(memory_hygiene::wipe_for_exit(&mut ctx, &mut stack));
+
     // restore
+    guard.armed = false;
     disable_raw_mode()?;
     let out = terminal.backend_mut();
     execute!(out, LeaveAlternateScreen)?;
+    if ctx.settings.purge_scrollback || secrets_shown {
+        // Leave nothing from this session in the main screen's scrollback either.
+        execute!(out, TermClear(ClearType::All), TermClear(ClearType::Purge))?;
+    }
     terminal.show_cursor()?;
+    for note in exit_notes {
+        println!("{note}");
+    }
     Ok(())
 }
+
+/// Context and first screen at startup: the workspace chooser when named workspaces exist and
+/// none was given with `--workspace`, otherwise the current workspace's first screen.
+pub fn initial_state() -> (AppCtx, Vec<Box<dyn ScreenWidget>>) {
+    let mut ctx = AppCtx::default();
+    ctx.set_fs(FsRef::default());
+    ctx.sandbox.reset_for_workspace(workspace::excluded_dirs());
+    let first: Box<dyn ScreenWidget> = if !workspace::chosen_at_launch() && workspace::list().len() > 1 {
+        Box::new(crate::screens::workspaces::WorkspacesScreen::at_startup())
+    } else {
+        start_session(&mut ctx, "Startup")
+    };
+    (ctx, vec![first])
+}
+
+/// Load the working directory's settings and key usage and return its first screen. With a
+/// master password set, settings stay encrypted until the unlock screen succeeds.
+fn start_session(ctx: &mut AppCtx, source: &str) -> Box<dyn ScreenWidget> {
+    if profile_enabled() {
+        ctx.set_settings(Settings::default());
+        Box::new(crate::screens::UnlockProfileScreen::new())
+    } else {
+        let migrated = migrate_stores(None);
+        ctx.set_settings(load_settings(None));
+        ctx.dispatch(source, AppEvent::KeyUsageChanged(load_key_usage(None)));
+        session_screen(ctx, migrated)
+    }
+}
+
+/// The home screen, behind a notice of the stores `migrate_stores` upgraded. When the migration
+/// failed the session closes after the error: saving the stores as read could lose data.
+pub fn session_screen(ctx: &AppCtx, migrated: Result<Vec<String>>) -> Box<dyn ScreenWidget> {
+    match migrated {
+        Ok(lines) if lines.is_empty() => home_screen(ctx),
+        Ok(mut lines) => {
+            lines.insert(0, "App data from an earlier release was upgraded:".to_string());
+            Box::new(ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Home))
+        }
+        Err(e) => Box::new(
+            ConfirmOkScreen::with_lines(vec![
+                format!("Error: {e:#}"),
+                "The app data was left as it is. The tool will now close.".to_string(),
+            ])
+            .with_after_ok(AfterOk::Quit),
+        ),
+    }
+}
+
+/// Change into workspace `name` and start its session over; returns the screen to reset the
+/// stack to. Handoffs, the unlocked profile and sandbox grants of the previous workspace are dropped.
+pub fn switch_workspace(ctx: &mut AppCtx, source: &str, name: &str) -> Result<Box<dyn ScreenWidget>> {
+    workspace::enter(name)?;
+    ctx.sandbox.reset_for_workspace(workspace::excluded_dirs());
+    ctx.dispatch(source, AppEvent::WorkspaceOpened(name.to_string()));
+    Ok(start_session(ctx, source))
+}
+
+/// The main menu, or the recovery prompt in front of it when an interrupted run left temp
+/// files or unfinished batch runs in the output directories the sandbox allows.
+pub fn home_screen(ctx: &AppCtx) -> Box<dyn ScreenWidget> {
+    let dirs: Vec<_> = output_dirs().into_iter().filter(|d| ctx.sandbox.allows(d)).collect();
+    let leftovers = scan(&*ctx.fs, &dirs);
+    if leftovers.is_empty() {
+        Box::new(crate::screens::MainMenuScreen::default())
+    } else {
+        Box::new(crate::screens::RecoveryScreen::new(leftovers))
+    }
+}
+
+/// Breadcrumb header plus the top screen.
+pub fn draw_stack(f: &mut Frame<'_>, stack: &[Box<dyn ScreenWidget>], ctx: &AppCtx) {
+    let size = f.size();
+    match stack.last() {
+        Some(top) => {
+            let [header, body] = split_breadcrumb(size);
+            f.render_widget(breadcrumb(stack, &workspace::current()), header);
+            top.draw(f, body, ctx);
+        }
+        // just in case—clear remaining area
+        None => f.render_widget(Clear, size),
+    }
+}
+
+/// Route one key press (after F12 handling) to the stack. Returns `true` to quit.
+pub async fn dispatch_key(
+    stack: &mut Vec<Box<dyn ScreenWidget>>,
+    ctx: &mut AppCtx,
+    k: KeyEvent,
+    scrub: &mut bool,
+) -> Result<bool> {
+    // GLOBAL HOTKEY: Ctrl+Q shows confirm quit from anywhere
+    if k.modifiers.contains(KeyModifiers::CONTROL) && matches!(k.code, KeyCode::Char('q' | 'Q')) {
+        let confirm = match stack.iter().find_map(|s| s.busy()) {
+            Some(what) => ConfirmQuitScreen::busy(what),
+            None => ConfirmQuitScreen::new(),
+        };
+        stack.push(Box::new(confirm));
+        return Ok(false);
+    }
+    let Some(top) = stack.last_mut() else { return Ok(true) };
+    let transition = top.on_key(k, ctx).await?;
+    let quit = apply_transition(stack, transition, scrub);
+    if !quit {
+        gate_signing(stack, ctx);
+        offer_sandbox_override(stack, ctx);
+    }
+    Ok(quit)
+}
+
+/// Step the busy top screen once. Returns `true` to quit.
+pub async fn tick_top(stack: &mut Vec<Box<dyn ScreenWidget>>, ctx: &mut AppCtx, scrub: &mut bool) -> Result<bool> {
+    let Some(top) = stack.last_mut() else { return Ok(true) };
+    let transition = top.tick(ctx).await?;
+    let quit = apply_transition(stack, transition, scrub);
+    if !quit {
+        gate_signing(stack, ctx);
+        offer_sandbox_override(stack, ctx);
+    }
+    Ok(quit)
+}
+
+/// If file access was just refused by the sandbox, put the override prompt on top of
+/// whatever the screen showed for the failure.
+fn offer_sandbox_override(stack: &mut Vec<Box<dyn ScreenWidget>>, ctx: &AppCtx) {
+    if let Some(denied) = ctx.sandbox.take_denied() {
+        stack.push(Box::new(SandboxOverrideScreen::new(denied, ctx.sandbox.roots())));
+    }
+}
+
+/// If a signing screen just came on top while the ceremony checklist is incomplete, put the
+/// checklist over it; Back from there leaves the signing screen too.
+fn gate_signing(stack: &mut Vec<Box<dyn ScreenWidget>>, ctx: &AppCtx) {
+    let locked = ctx.ceremony.as_ref().is_some_and(|c| !c.recorded());
+    if locked && stack.last().is_some_and(|s| s.signs()) {
+        stack.push(Box::new(CeremonyChecklistScreen::gate()));
+    }
+}
+
+/// "Main Menu ▸ Advanced Tools ▸ Create Revocation", from the screens on the stack, after
+/// "[acme]" in a named workspace.
+fn breadcrumb(stack: &[Box<dyn ScreenWidget>], workspace: &str) -> Paragraph<'static> {
+    let mut spans = Vec::new();
+    for crumb in stack.iter().map(|s| s.crumb()).filter(|c| !c.is_empty()) {
+        if !spans.is_empty() {
+            spans.push(Span::styled(" ▸ ", Style::default().fg(Color::DarkGray)));
+        }
+        spans.push(Span::styled(crumb.to_string(), Style::default().fg(Color::DarkGray)));
+    }
+    if let Some(last) = spans.last_mut() {
+        last.style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    }
+    if workspace != workspace::DEFAULT_WORKSPACE {
+        spans.insert(0, Span::styled(format!("[{workspace}] "), Style::default().fg(Color::Yellow)));
+    }
+    Paragraph::new(Line::from(spans))
+}
+
+/// One row for the breadcrumb, the rest for the screen.
+fn split_breadcrumb(area: Rect) -> [Rect; 2] {
+    let h = area.height.min(1);
+    [
+        Rect { height: h, ..area },
+        Rect { y: area.y + h, height: area.height - h, ..area },
+    ]
+}
+
+/// Let every busy screen flush its completed work before the app exits.
+pub fn finish_busy(stack: &mut [Box<dyn ScreenWidget>], ctx: &AppCtx, notes: &mut Vec<String>) {
+    for s in stack.iter_mut().filter(|s| s.busy().is_some()) {
+        notes.extend(s.finish_for_quit(ctx));
+    }
+}
@@ -0,0 +1,202 @@
+//! Backup and restore of the application data directory, for moving to replacement air-gapped
+//! hardware in one step.
+//!
+//! The backup holds every app data file present in the working directory: the stores in
+//! `PROTECTED_FILES` (settings, key usage, the audit log and usage statistics; plain, or their `.enc` copies while a master
+//! password is set) and the profile descriptor. Files are archived as they are on disk, so stores protected by a master password
+//! stay protected by it. The archive is then sealed with XChaCha20-Poly1305 under an Argon2id key
+//! from a backup passphrase, as the master-password profile does. Key files are not included:
+//! they are already encrypted files of their own and are copied like any other output.
+//!
+//! The file is JSON, `{ "formatVersion", "createdAt", "toolVersion", "t_cost", "m_cost_kib",
+//! "p_cost", "salt", "archive" }`, with `archive` the hex of the sealed
+//! `{ "files": [{ "name", "sha256", "data" }] }`.
+//!
+//! Restoring checks the whole archive before touching anything, then moves the app data files
+//! already present aside to `<name>.before-restore` and writes the backed-up ones.
+
+use anyhow::{anyhow, bail, Context, Result};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroize;
+
+use crate::crypto::modern::KdfParams;
+use crate::defaults::Defaults;
+use crate::profile::{derive_key, encrypted_path, open, seal, write_atomic, PROFILE_KDF, PROTECTED_FILES};
+use crate::util::{hex_to_bytes, sha256_hex};
+
+pub const APP_BACKUP_VERSION: u32 = 1;
+/// Name bound into the AAD of the sealed archive.
+const ARCHIVE_NAME: &str = "app-backup";
+/// Suffix of an app data file moved aside by a restore.
+pub const BEFORE_RESTORE_SUFFIX: &str = ".before-restore";
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize)]
+struct BackupFile {
+    formatVersion: u32,
+    createdAt: String,
+    toolVersion: String,
+    t_cost: u32,
+    m_cost_kib: u32,
+    p_cost: u8,
+    /// Argon2id salt, hex.
+    salt: String,
+    /// Sealed [`Archive`], hex.
+    archive: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Archive {
+    files: Vec<ArchivedFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchivedFile {
+    /// File name in the working directory.
+    name: String,
+    sha256: String,
+    /// Contents, hex.
+    data: String,
+}
+
+/// What a restore wrote and moved aside, by file name.
+pub struct RestoreOutcome {
+    pub restored: Vec<String>,
+    pub moved_aside: Vec<String>,
+    /// The restored data is protected by a master password, which must be unlocked next.
+    pub has_profile: bool,
+}
+
+/// Every app data file a backup covers, present or not.
+pub fn app_data_files() -> Vec<PathBuf> {
+    let mut out = vec![PathBuf::from(Defaults::PROFILE_FILE)];
+    for name in PROTECTED_FILES {
+        out.push(PathBuf::from(name));
+        out.push(encrypted_path(Path::new(name)));
+    }
+    out
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Archive the app data files present into a new backup at `path`, sealed with `passphrase`.
+/// Returns the names archived. Zeroizes `passphrase`.
+pub fn backup_app_data(path: &Path, passphrase: &mut Vec<u8>) -> Result<Vec<String>> {
+    let res = write_backup(path, passphrase);
+    passphrase.zeroize();
+    res
+}
+
+fn write_backup(path: &Path, passphrase: &[u8]) -> Result<Vec<String>> {
+    if path.exists() {
+        bail!("{} already exists; choose another Backup File.", path.display());
+    }
+    let mut files = Vec::new();
+    for p in app_data_files().into_iter().filter(|p| p.exists()) {
+        let bytes = fs::read(&p).with_context(|| format!("reading {}", p.display()))?;
+        files.push(ArchivedFile { name: file_name(&p), sha256: sha256_hex(&bytes), data: hex::encode(&bytes) });
+    }
+    if files.is_empty() {
+        bail!("There is no app data to back up in this directory.");
+    }
+    let names = files.iter().map(|f| f.name.clone()).collect();
+
+    let mut salt = [0u8; 16];
+    ChaCha20Rng::from_entropy().fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt, PROFILE_KDF)?;
+    let mut plaintext = serde_json::to_vec(&Archive { files })?;
+    let sealed = seal(&key, ARCHIVE_NAME, &plaintext);
+    plaintext.zeroize();
+
+    let backup = BackupFile {
+        formatVersion: APP_BACKUP_VERSION,
+        createdAt: time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        toolVersion: env!("CARGO_PKG_VERSION").to_string(),
+        t_cost: PROFILE_KDF.t_cost,
+        m_cost_kib: PROFILE_KDF.m_cost_kib,
+        p_cost: PROFILE_KDF.p_cost,
+        salt: hex::encode(salt),
+        archive: hex::encode(sealed?),
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    write_atomic(path, serde_json::to_string_pretty(&backup)?.as_bytes())?;
+    Ok(names)
+}
+
+/// Decrypt the backup at `path` with `passphrase` and replace this directory's app data with it.
+/// Zeroizes `passphrase`.
+pub fn restore_app_data(path: &Path, passphrase: &mut Vec<u8>) -> Result<RestoreOutcome> {
+    let files = read_backup(path, passphrase);
+    passphrase.zeroize();
+    let files = files?;
+
+    // Check every name and hash before touching the directory
+    let known = app_data_files();
+    let mut restore: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+    for f in files {
+        let Some(target) = known.iter().find(|p| file_name(p) == f.name) else {
+            bail!("The backup holds {:?}, which is not an app data file of this tool.", f.name);
+        };
+        let data = hex::decode(&f.data).with_context(|| format!("{} in the backup is not hex", f.name))?;
+        if sha256_hex(&data) != f.sha256 {
+            bail!("{} in the backup does not match its SHA-256.", f.name);
+        }
+        restore.push((target.clone(), data));
+    }
+    let present: Vec<PathBuf> = known.into_iter().filter(|p| p.exists()).collect();
+    for p in &present {
+        let aside = aside_path(p);
+        if aside.exists() {
+            bail!("{} is left from an earlier restore; move it away first.", aside.display());
+        }
+    }
+
+    let mut moved_aside = Vec::new();
+    for p in present {
+        fs::rename(&p, aside_path(&p)).with_context(|| format!("moving {} aside", p.display()))?;
+        moved_aside.push(file_name(&p));
+    }
+    let mut restored = Vec::new();
+    for (target, mut data) in restore {
+        let res = write_atomic(&target, &data);
+        data.zeroize();
+        res?;
+        restored.push(file_name(&target));
+    }
+    let has_profile = restored.iter().any(|n| *n == file_name(Path::new(Defaults::PROFILE_FILE)));
+    Ok(RestoreOutcome { restored, moved_aside, has_profile })
+}
+
+fn read_backup(path: &Path, passphrase: &[u8]) -> Result<Vec<ArchivedFile>> {
+    let raw = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let backup: BackupFile = serde_json::from_slice(&raw)
+        .with_context(|| format!("parsing {} as an app data backup", path.display()))?;
+    if backup.formatVersion != APP_BACKUP_VERSION {
+        bail!("Unsupported app data backup version {} in {}.", backup.formatVersion, path.display());
+    }
+    let salt = hex_to_bytes(&backup.salt).context("backup salt is not hex")?;
+    let sealed = hex_to_bytes(&backup.archive).context("backup archive is not hex")?;
+    let kdf = KdfParams { t_cost: backup.t_cost, m_cost_kib: backup.m_cost_kib, p_cost: backup.p_cost };
+    let key = derive_key(passphrase, &salt, kdf)?;
+    let mut plaintext = open(&key, ARCHIVE_NAME, &sealed)
+        .map_err(|_| anyhow!("Wrong backup passphrase, or the backup was modified."))?;
+    let archive = serde_json::from_slice::<Archive>(&plaintext).context("reading the decrypted archive");
+    plaintext.zeroize();
+    Ok(archive?.files)
+}
+
+fn aside_path(p: &Path) -> PathBuf {
+    let mut s = p.as_os_str().to_owned();
+    s.push(BEFORE_RESTORE_SUFFIX);
+    PathBuf::from(s)
+}
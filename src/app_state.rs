@@ -0,0 +1,207 @@
+//! The state screens hand each other: info-file prefills and keys loaded from encrypted key
+//! files on their way to a Create form, drafts of Create forms that were left and "Load From File" picker positions.
+//! Screens never write it directly: they send an [`AppEvent`] through `AppCtx::dispatch`, which
+//! applies it (`HandoffState::reduce`, or the session keys on `AppCtx`) and logs which screen sent
+//! it. The log holds event names and counts only, never values.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+
+use zeroize::Zeroize;
+
+use crate::app::{DelegationPrefill, LoadedSigningKey, PickerMemory};
+use crate::key_usage::KeyUsage;
+use crate::profile::ProfileKey;
+
+/// Most events kept in the log.
+const LOG_LEN: usize = 200;
+
+/// Shown on a Create form that reopened with the values it was left with.
+pub const DRAFT_RESTORED_NOTE: &str =
+    "The values from your last visit were restored. Private keys are never kept: enter them again.";
+
+/// The Create forms that take prefills and keep drafts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Form {
+    Delegation,
+    Revocation,
+    Redelegation,
+    PermanentInvalidation,
+}
+
+impl Form {
+    /// The form that builds `func_name` transactions (permanent invalidation for anything else).
+    pub fn for_function(func_name: &str) -> Self {
+        match func_name {
+            "createDelegationEvent" => Form::Delegation,
+            "createRevocationEvent" => Form::Revocation,
+            "createRevocationEventFollowedByDelegationEvent" => Form::Redelegation,
+            _ => Form::PermanentInvalidation,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Form::Delegation => "Create Delegation",
+            Form::Revocation => "Create Revocation",
+            Form::Redelegation => "Create Re-Delegation",
+            Form::PermanentInvalidation => "Create Permanent Invalidation",
+        }
+    }
+}
+
+pub enum AppEvent {
+    /// An info file (or a cloned signed file) was read for `form`.
+    PrefillLoaded { form: Form, prefill: DelegationPrefill },
+    /// `form` applied its pending prefill.
+    PrefillTaken(Form),
+    /// A private key was decrypted from a key file for one field of `form`.
+    SigningKeyLoaded { form: Form, key: LoadedSigningKey },
+    /// `form` took its loaded key.
+    SigningKeyTaken(Form),
+    /// `form` was left with these non-secret values, offered again on the next visit.
+    FormLeft { form: Form, draft: HashMap<String, String> },
+    /// A reopened `form` restored its draft.
+    DraftTaken(Form),
+    PickerDirChosen { picker: &'static str, dir: String },
+    PickerFileHighlighted { picker: &'static str, path: PathBuf },
+    /// The master-password profile was unlocked; `usage` was decrypted with it.
+    ProfileUnlocked { key: ProfileKey, usage: KeyUsage },
+    /// The master password was set (Some) or removed (None).
+    ProfileKeyChanged(Option<ProfileKey>),
+    /// Key usage records were loaded or edited (already saved).
+    KeyUsageChanged(KeyUsage),
+    /// The working directory changed to this workspace; nothing from the previous one is kept.
+    WorkspaceOpened(String),
+}
+
+impl AppEvent {
+    /// What happened, for the log: names and counts, no values.
+    fn summary(&self) -> String {
+        match self {
+            AppEvent::PrefillLoaded { form, prefill } => {
+                format!("prefill for {} loaded ({} value(s))", form.label(), prefill.map.len())
+            }
+            AppEvent::PrefillTaken(form) => format!("prefill for {} applied", form.label()),
+            AppEvent::SigningKeyLoaded { form, .. } => format!("key file loaded for {}", form.label()),
+            AppEvent::SigningKeyTaken(form) => format!("key file applied to {}", form.label()),
+            AppEvent::FormLeft { form, draft } => {
+                format!("{} left, draft of {} value(s) kept", form.label(), draft.len())
+            }
+            AppEvent::DraftTaken(form) => format!("draft for {} restored", form.label()),
+            AppEvent::PickerDirChosen { picker, .. } => format!("{picker} directory chosen"),
+            AppEvent::PickerFileHighlighted { picker, .. } => format!("{picker} file highlighted"),
+            AppEvent::ProfileUnlocked { .. } => "profile unlocked, key usage loaded".to_string(),
+            AppEvent::ProfileKeyChanged(Some(_)) => "master password set".to_string(),
+            AppEvent::ProfileKeyChanged(None) => "master password removed".to_string(),
+            AppEvent::KeyUsageChanged(_) => "key usage updated".to_string(),
+            AppEvent::WorkspaceOpened(name) => format!("workspace {name} opened"),
+        }
+    }
+}
+
+/// One applied event and the screen (breadcrumb name) that sent it. Read back only by the
+/// key-script driver (`driver.rs`).
+#[cfg_attr(not(any(test, feature = "script")), allow(dead_code))]
+pub struct LoggedEvent {
+    pub source: String,
+    pub summary: String,
+}
+
+#[derive(Default)]
+pub struct HandoffState {
+    prefills: HashMap<Form, DelegationPrefill>,
+    signing_keys: HashMap<Form, LoadedSigningKey>,
+    drafts: HashMap<Form, HashMap<String, String>>,
+    pickers: HashMap<&'static str, PickerMemory>,
+    log: VecDeque<LoggedEvent>,
+}
+
+impl HandoffState {
+    pub fn prefill(&self, form: Form) -> Option<&DelegationPrefill> {
+        self.prefills.get(&form)
+    }
+
+    pub fn signing_key(&self, form: Form) -> Option<&LoadedSigningKey> {
+        self.signing_keys.get(&form)
+    }
+
+    pub fn draft(&self, form: Form) -> Option<&HashMap<String, String>> {
+        self.drafts.get(&form)
+    }
+
+    /// Where the picker `name` (each Choose*InfoDir screen's `PICKER`) was last left.
+    pub fn picker(&self, name: &str) -> Option<&PickerMemory> {
+        self.pickers.get(name)
+    }
+
+    /// Applied events, oldest first.
+    #[cfg(any(test, feature = "script"))]
+    pub fn log(&self) -> impl Iterator<Item = &LoggedEvent> {
+        self.log.iter()
+    }
+
+    pub fn record(&mut self, source: &str, event: &AppEvent) {
+        if self.log.len() == LOG_LEN {
+            self.log.pop_front();
+        }
+        self.log.push_back(LoggedEvent { source: source.to_string(), summary: event.summary() });
+    }
+
+    /// Zeroize and drop every prefill, loaded key and draft value, before the app exits.
+    /// Returns how many values were wiped.
+    pub fn wipe(&mut self) -> usize {
+        let mut wiped = 0;
+        let prefills = self.prefills.values_mut().map(|p| &mut p.map);
+        for map in prefills.chain(self.drafts.values_mut()) {
+            for v in map.values_mut() {
+                v.zeroize();
+                wiped += 1;
+            }
+        }
+        wiped += self.signing_keys.len();
+        self.prefills.clear();
+        self.signing_keys.clear();
+        self.drafts.clear();
+        wiped
+    }
+
+    /// Apply `event`. The session-key events are applied by `AppCtx::dispatch` and never get here.
+    pub fn reduce(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::PrefillLoaded { form, prefill } => {
+                // A fresh file replaces both an older prefill and a kept draft
+                self.drafts.remove(&form);
+                self.prefills.insert(form, prefill);
+            }
+            AppEvent::PrefillTaken(form) => {
+                self.prefills.remove(&form);
+            }
+            AppEvent::SigningKeyLoaded { form, key } => {
+                self.signing_keys.insert(form, key);
+            }
+            AppEvent::SigningKeyTaken(form) => {
+                self.signing_keys.remove(&form);
+            }
+            AppEvent::FormLeft { form, draft } => {
+                self.drafts.insert(form, draft);
+            }
+            AppEvent::DraftTaken(form) => {
+                self.drafts.remove(&form);
+            }
+            AppEvent::PickerDirChosen { picker, dir } => {
+                self.pickers.entry(picker).or_default().dir = Some(dir);
+            }
+            AppEvent::PickerFileHighlighted { picker, path } => {
+                self.pickers.entry(picker).or_default().selected = Some(path);
+            }
+            AppEvent::WorkspaceOpened(_) => {
+                self.prefills.clear();
+                self.signing_keys.clear();
+                self.drafts.clear();
+                self.pickers.clear();
+            }
+            AppEvent::ProfileUnlocked { .. } | AppEvent::ProfileKeyChanged(_) | AppEvent::KeyUsageChanged(_) => {}
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Hash-chained audit log of signing events, and signed proofs of its state for compliance.
+//!
+//! Each entry is `{ "seq", "time", "event", "detail", "prevHash", "hash" }`, where `hash` is the
+//! SHA-256 (hex) of the entry's other fields as canonical JSON and `prevHash` is the previous
+//! entry's `hash` (64 zeros for the first). Changing or removing an entry breaks every hash after
+//! it. The log lives at `Defaults::AUDIT_LOG_FILE` and is encrypted at rest with the settings
+//! while a master password is set.
+//!
+//! A proof file is a snapshot of the whole log with its head hash signed by an operator key:
+//! `{ "formatVersion", "createdAt", "toolVersion", "entryCount", "headHash", "entries", "signer",
+//! "signature" }`. The signature is an EIP-191 personal-message signature over
+//! `keccak256(<{ createdAt, entryCount, headHash } as canonical JSON>)`. A later proof shows that
+//! no entry was removed since an earlier one when its entry at the earlier `entryCount - 1` has
+//! the earlier `headHash`.
+
+use anyhow::{bail, Context, Result};
+use ethers_core::types::Signature;
+use ethers_core::utils::{keccak256, to_checksum};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::canonical_json::canonical_string;
+use crate::defaults::Defaults;
+use crate::migrate::to_versioned_json;
+use crate::profile::{read_protected, write_protected, ProfileKey};
+use crate::signing::{open_signer, sign_message_eip191};
+use crate::util::{bytes_to_0x, hex_to_bytes, parse_addr, sha256_hex};
+
+pub const AUDIT_PROOF_VERSION: u32 = 1;
+/// `prevHash` of the first entry.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    /// RFC 3339 UTC.
+    pub time: String,
+    pub event: String,
+    pub detail: Value,
+    pub prevHash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let fields = json!({
+            "seq": self.seq,
+            "time": self.time,
+            "event": self.event,
+            "detail": self.detail,
+            "prevHash": self.prevHash,
+        });
+        sha256_hex(canonical_string(&fields).as_bytes())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLog {
+    #[serde(default)]
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Hash of the last entry (the genesis value while empty).
+    pub fn head(&self) -> &str {
+        self.entries.last().map_or(GENESIS_HASH, |e| e.hash.as_str())
+    }
+
+    /// Check sequence numbers, links and hashes from the first entry on.
+    pub fn verify_chain(&self) -> Result<()> {
+        let mut prev = GENESIS_HASH;
+        for (i, e) in self.entries.iter().enumerate() {
+            if e.seq != i as u64 {
+                bail!("Audit log entry {i} has sequence number {}; entries were removed or reordered.", e.seq);
+            }
+            if e.prevHash != prev {
+                bail!("Audit log entry {i} does not link to the entry before it.");
+            }
+            if e.compute_hash() != e.hash {
+                bail!("Audit log entry {i} does not match its hash; it was modified.");
+            }
+            prev = &e.hash;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, event: &str, detail: Value) {
+        let mut entry = AuditEntry {
+            seq: self.entries.len() as u64,
+            time: now_rfc3339(),
+            event: event.to_string(),
+            detail,
+            prevHash: self.head().to_string(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        self.entries.push(entry);
+    }
+}
+
+fn now_rfc3339() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    now.replace_nanosecond(0)
+        .unwrap_or(now)
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+/// Load the log, empty when missing. Unlike the other stores an unreadable or broken log is an
+/// error: starting it over would hide what it held.
+pub fn load_audit_log(key: Option<&ProfileKey>) -> Result<AuditLog> {
+    let Some(bytes) = read_protected(key, Path::new(Defaults::AUDIT_LOG_FILE))? else {
+        return Ok(AuditLog::default());
+    };
+    let log: AuditLog = serde_json::from_slice(&bytes)
+        .with_context(|| format!("parsing {}", Defaults::AUDIT_LOG_FILE))?;
+    log.verify_chain()?;
+    Ok(log)
+}
+
+/// Append one event and save the log (encrypted when a profile key is given).
+pub fn append_audit_event(key: Option<&ProfileKey>, event: &str, detail: Value) -> Result<()> {
+    let mut log = load_audit_log(key)?;
+    log.push(event, detail);
+    let bytes = to_versioned_json(Defaults::AUDIT_LOG_FILE, &log)?;
+    write_protected(key, Path::new(Defaults::AUDIT_LOG_FILE), &bytes)
+        .with_context(|| format!("saving the audit log to {}", Defaults::AUDIT_LOG_FILE))
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditProof {
+    formatVersion: u32,
+    createdAt: String,
+    toolVersion: String,
+    entryCount: u64,
+    headHash: String,
+    entries: Vec<AuditEntry>,
+    /// Checksummed address of the signing key.
+    signer: String,
+    /// `0x || r || s || v` (65 bytes).
+    signature: String,
+}
+
+/// The signed digest input of a proof.
+fn proof_hash(created_at: &str, entry_count: u64, head_hash: &str) -> [u8; 32] {
+    let statement = json!({ "createdAt": created_at, "entryCount": entry_count, "headHash": head_hash });
+    keccak256(canonical_string(&statement))
+}
+
+/// What an exported or verified proof covers.
+pub struct ProofSummary {
+    pub created_at: String,
+    pub entry_count: u64,
+    pub head_hash: String,
+    pub signer: String,
+}
+
+/// Snapshot the log, sign its head with the key `privkey_input` refers to and write the proof to
+/// `path` (a new file). The export is then recorded in the log itself.
+pub async fn export_audit_proof(key: Option<&ProfileKey>, privkey_input: &str, path: &Path) -> Result<ProofSummary> {
+    if path.exists() {
+        bail!("{} already exists; choose another Proof File.", path.display());
+    }
+    let log = load_audit_log(key)?;
+    if log.entries.is_empty() {
+        bail!("The audit log is empty; there is nothing to prove yet.");
+    }
+    let signing_key = open_signer(privkey_input)?;
+    let signer = to_checksum(&signing_key.address(), None);
+
+    let created_at = now_rfc3339();
+    let entry_count = log.entries.len() as u64;
+    let head_hash = log.head().to_string();
+    let sig = sign_message_eip191(&*signing_key, proof_hash(&created_at, entry_count, &head_hash)).await?;
+    let proof = AuditProof {
+        formatVersion: AUDIT_PROOF_VERSION,
+        createdAt: created_at.clone(),
+        toolVersion: env!("CARGO_PKG_VERSION").to_string(),
+        entryCount: entry_count,
+        headHash: head_hash.clone(),
+        entries: log.entries,
+        signer: signer.clone(),
+        signature: bytes_to_0x(&sig.to_vec()),
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&proof)?)
+        .with_context(|| format!("writing {}", path.display()))?;
+
+    append_audit_event(key, "audit_proof_exported", json!({
+        "file": path.display().to_string(),
+        "entryCount": entry_count,
+        "headHash": head_hash,
+        "signer": signer,
+    }))?;
+    Ok(ProofSummary { created_at, entry_count, head_hash, signer })
+}
+
+fn read_proof(path: &Path) -> Result<AuditProof> {
+    let raw = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let proof: AuditProof = serde_json::from_slice(&raw)
+        .with_context(|| format!("parsing {} as an audit log proof", path.display()))?;
+    if proof.formatVersion != AUDIT_PROOF_VERSION {
+        bail!("Unsupported audit log proof version {} in {}.", proof.formatVersion, path.display());
+    }
+    Ok(proof)
+}
+
+/// Check a proof's chain, head and signature; with `expected_signer` set, the signer must also
+/// equal it.
+fn check_proof(proof: &AuditProof, path: &Path, expected_signer: Option<&str>) -> Result<ProofSummary> {
+    let log = AuditLog { entries: proof.entries.clone() };
+    log.verify_chain().with_context(|| format!("checking {}", path.display()))?;
+    if proof.entryCount != log.entries.len() as u64 || proof.headHash != log.head() {
+        bail!("{} states {} entries with head {}, but its entries do not end there.", path.display(), proof.entryCount, proof.headHash);
+    }
+
+    let sig_bytes = hex_to_bytes(&proof.signature).context("proof signature is not hex")?;
+    let sig = Signature::try_from(sig_bytes.as_slice()).context("proof signature must be 65 bytes")?;
+    let recovered = sig
+        .recover(proof_hash(&proof.createdAt, proof.entryCount, &proof.headHash).to_vec())
+        .context("recovering proof signer")?;
+    let stated = parse_addr(&proof.signer).context("proof signer is not an address")?;
+    if recovered != stated {
+        bail!(
+            "{}: signature does not match: signed by {}, proof claims {}.",
+            path.display(),
+            to_checksum(&recovered, None),
+            proof.signer
+        );
+    }
+    if let Some(want) = expected_signer.map(str::trim).filter(|s| !s.is_empty()) {
+        let want = parse_addr(want).context("expected signer is not an address")?;
+        if recovered != want {
+            bail!(
+                "{} was signed by {}, not the expected {}.",
+                path.display(),
+                to_checksum(&recovered, None),
+                to_checksum(&want, None)
+            );
+        }
+    }
+    Ok(ProofSummary {
+        created_at: proof.createdAt.clone(),
+        entry_count: proof.entryCount,
+        head_hash: proof.headHash.clone(),
+        signer: to_checksum(&recovered, None),
+    })
+}
+
+/// Verify the proof at `path`, and with `earlier` set, that it still holds every entry of that
+/// earlier proof unchanged. Returns the summaries of the proof and of the earlier one.
+pub fn verify_audit_proof(
+    path: &Path,
+    earlier: Option<&Path>,
+    expected_signer: Option<&str>,
+) -> Result<(ProofSummary, Option<ProofSummary>)> {
+    let proof = read_proof(path)?;
+    let summary = check_proof(&proof, path, expected_signer)?;
+    let Some(earlier_path) = earlier else { return Ok((summary, None)) };
+
+    let before = read_proof(earlier_path)?;
+    let before_summary = check_proof(&before, earlier_path, expected_signer)?;
+    if before.entryCount > proof.entryCount {
+        bail!(
+            "{} holds {} entries, fewer than the {} of the earlier {}: entries were removed.",
+            path.display(), proof.entryCount, before.entryCount, earlier_path.display()
+        );
+    }
+    let matches = before.entryCount == 0
+        || proof.entries[before.entryCount as usize - 1].hash == before.headHash;
+    if !matches {
+        bail!(
+            "{} does not continue the log of {}: entries up to {} were changed or removed.",
+            path.display(), earlier_path.display(), before.entryCount - 1
+        );
+    }
+    Ok((summary, Some(before_summary)))
+}
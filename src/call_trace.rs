@@ -0,0 +1,128 @@
+//! Pseudo-Solidity rendering of encoded calldata, for reviewing what a signed tx will execute:
+//!
+//! ```text
+//! createDelegationEvent(DelegationInputData({
+//!     delegatorPubkey: 0x04…,
+//!     …
+//!     vDelegateePubkeySig: 27
+//! }))
+//! ```
+//!
+//! The arguments are decoded back out of `encodedData`, so the preview shows the calldata
+//! itself rather than the form inputs it was built from. Non-zero `…Time` members get the
+//! date they encode as a trailing comment, per the display settings.
+
+use anyhow::{anyhow, Context, Result};
+use ethers_core::abi::{Abi, Token};
+use ethers_core::types::U256;
+use ethers_core::utils::to_checksum;
+use serde_json::Value;
+
+use crate::abi::INKAN_ABI_JSON;
+use crate::display::DisplaySettings;
+use crate::util::{bytes_to_0x, hex_to_bytes};
+
+const INDENT: &str = "    ";
+
+/// Render `encoded_data` (0x selector || args) as a multi-line call, one struct member per line.
+pub fn call_trace(abi: &Abi, encoded_data: &str, display: &DisplaySettings) -> Result<String> {
+    let data = hex_to_bytes(encoded_data).context("encodedData is not hex")?;
+    if data.len() < 4 {
+        return Err(anyhow!("encodedData is shorter than a function selector"));
+    }
+    let func = abi
+        .functions()
+        .find(|f| f.short_signature() == data[..4])
+        .ok_or_else(|| anyhow!("no ABI function with selector {}", bytes_to_0x(&data[..4])))?;
+    let tokens = func.decode_input(&data[4..]).with_context(|| format!("decoding {} arguments", func.name))?;
+    let names = input_names(&func.name)?;
+
+    // Struct type named after the event: createDelegationEvent + inputData -> DelegationInputData
+    let event = func.name.strip_prefix("create").unwrap_or(&func.name);
+    let event = event.strip_suffix("Event").unwrap_or(event);
+
+    let mut out = format!("{}(", func.name);
+    match (tokens.as_slice(), names.as_slice()) {
+        // Single struct argument
+        ([tok @ Token::Tuple(_)], [(param, Some(fields))]) => {
+            out.push_str(&struct_text(&format!("{event}{}", upper_first(param)), tok, fields, "", display));
+        }
+        // Several arguments, shown with named-argument syntax
+        _ => {
+            out.push_str("{\n");
+            for (i, tok) in tokens.iter().enumerate() {
+                let (name, fields) = names.get(i).map(|(n, f)| (n.as_str(), f.as_deref())).unwrap_or(("_", None));
+                let value = match (tok, fields) {
+                    (Token::Tuple(_), Some(fields)) => struct_text(&upper_first(name), tok, fields, INDENT, display),
+                    _ => token_text(tok),
+                };
+                let sep = if i + 1 < tokens.len() { "," } else { "" };
+                out.push_str(&format!("{INDENT}{name}: {value}{sep}\n"));
+            }
+            out.push('}');
+        }
+    }
+    out.push(')');
+    Ok(out)
+}
+
+/// `Name({\n  member: value,\n  …\n})`, indented by `indent`.
+fn struct_text(type_name: &str, tok: &Token, fields: &[String], indent: &str, display: &DisplaySettings) -> String {
+    let Token::Tuple(members) = tok else { return token_text(tok) };
+    let mut out = format!("{type_name}({{\n");
+    for (i, m) in members.iter().enumerate() {
+        let name = fields.get(i).map(String::as_str).unwrap_or("_");
+        let sep = if i + 1 < members.len() { "," } else { "" };
+        let note = match m {
+            Token::Uint(t) if name.ends_with("Time") && !t.is_zero() && *t <= U256::from(i64::MAX) => {
+                format!("  // {}", display.unix_time(t.as_u64() as i64))
+            }
+            _ => String::new(),
+        };
+        out.push_str(&format!("{indent}{INDENT}{name}: {}{sep}{note}\n", token_text(m)));
+    }
+    out.push_str(&format!("{indent}}})"));
+    out
+}
+
+fn token_text(tok: &Token) -> String {
+    match tok {
+        Token::Bytes(b) | Token::FixedBytes(b) => bytes_to_0x(b),
+        Token::Uint(u) | Token::Int(u) => u.to_string(),
+        Token::Bool(b) => b.to_string(),
+        Token::Address(a) => to_checksum(a, None),
+        Token::String(s) => format!("{s:?}"),
+        Token::Array(items) | Token::FixedArray(items) | Token::Tuple(items) => {
+            let inner: Vec<String> = items.iter().map(token_text).collect();
+            format!("[{}]", inner.join(", "))
+        }
+    }
+}
+
+/// Input names of `func_name` from the embedded ABI JSON, with member names for tuple inputs
+/// (ethers' parsed ABI keeps tuple member types but drops their names).
+fn input_names(func_name: &str) -> Result<Vec<(String, Option<Vec<String>>)>> {
+    let abi: Value = serde_json::from_str(INKAN_ABI_JSON)?;
+    let func = abi
+        .as_array()
+        .and_then(|fs| fs.iter().find(|f| f["name"] == func_name))
+        .ok_or_else(|| anyhow!("{func_name} not in the embedded ABI"))?;
+    let name_of = |v: &Value| v["name"].as_str().unwrap_or("_").to_string();
+    Ok(func["inputs"]
+        .as_array()
+        .map(|inputs| {
+            inputs
+                .iter()
+                .map(|p| (name_of(p), p["components"].as_array().map(|c| c.iter().map(name_of).collect())))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn upper_first(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        Some(f) => f.to_uppercase().chain(c).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,91 @@
+//! Canonical JSON: the one encoding used wherever JSON content is hashed or signed, so a hash
+//! taken by one build (or one platform, or an online-side verifier) matches another's.
+//!
+//! Follows the JSON Canonicalization Scheme (RFC 8785) for structure and strings: no whitespace,
+//! object keys sorted by their UTF-16 code units, strings escaped only where JSON requires it
+//! (`\"`, `\\`, `\b \f \n \r \t`, other control characters as lowercase `\u00xx`). Numbers are
+//! fixed as: integers in plain decimal; any other number in the shortest decimal form that reads
+//! back to the same `f64`, never with an exponent; `-0` as `0`.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Number, Value};
+use std::fmt::Write;
+
+/// Canonical encoding of any serializable value.
+pub fn to_canonical_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(value).context("serializing for canonical JSON")?;
+    Ok(canonical_string(&value).into_bytes())
+}
+
+/// Canonical encoding of a JSON value.
+pub fn canonical_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, k);
+                out.push(':');
+                write_value(out, v);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, n: &Number) {
+    if let Some(u) = n.as_u64() {
+        let _ = write!(out, "{u}");
+    } else if let Some(i) = n.as_i64() {
+        let _ = write!(out, "{i}");
+    } else {
+        // serde_json numbers are always finite; Display is shortest round-trip, without exponent
+        let f = n.as_f64().unwrap_or(0.0);
+        let _ = write!(out, "{}", if f == 0.0 { 0.0 } else { f });
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
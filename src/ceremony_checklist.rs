@@ -0,0 +1,141 @@
+//! Key ceremony checklist: steps read from a YAML file (`Settings::ceremony_checklist`) that the
+//! operators tick off before any signing screen opens. The file looks like:
+//!
+//! ```yaml
+//! title: Q3 delegation ceremony
+//! steps:
+//!   - verify binary hash
+//!   - text: confirm contract address
+//!     detail: 0x… on chain 1, read from the deployment record
+//!   - two operators present
+//! ```
+//!
+//! Ticks last for the session. When the last step is ticked the checklist is written to the
+//! audit log (`ceremony_checklist`: file, its SHA-256, and each step with the time it was
+//! ticked), so signed output can be traced back to the checks made before it. A configured file
+//! that cannot be read keeps signing locked until it is fixed or the setting cleared.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::audit_log::append_audit_event;
+use crate::profile::ProfileKey;
+use crate::util::sha256_hex;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChecklistFile {
+    #[serde(default)]
+    title: String,
+    steps: Vec<StepSpec>,
+}
+
+/// A step is a plain string, or `text` with a longer `detail` shown under it.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StepSpec {
+    Text(String),
+    Full {
+        text: String,
+        #[serde(default)]
+        detail: String,
+    },
+}
+
+pub struct Step {
+    pub text: String,
+    pub detail: String,
+    /// RFC 3339 time the step was ticked.
+    pub ticked_at: Option<String>,
+}
+
+pub struct Checklist {
+    pub title: String,
+    /// SHA-256 (hex) of the file as read.
+    pub sha256: String,
+    pub steps: Vec<Step>,
+}
+
+/// The checklist configured in settings, with this session's ticks.
+pub struct Ceremony {
+    pub path: String,
+    /// The parsed file, or why it cannot be used.
+    pub checklist: std::result::Result<Checklist, String>,
+    /// The completed checklist is in the audit log; cleared again by unticking a step.
+    recorded: bool,
+}
+
+impl Ceremony {
+    /// Read the checklist at `path`; a bad file is kept as the error to show.
+    pub fn load(path: &str) -> Self {
+        Self { path: path.to_string(), checklist: read_checklist(path).map_err(|e| format!("{e:#}")), recorded: false }
+    }
+
+    /// Every step is ticked, so signing screens may open.
+    pub fn complete(&self) -> bool {
+        self.checklist.as_ref().is_ok_and(|c| c.steps.iter().all(|s| s.ticked_at.is_some()))
+    }
+
+    /// Complete and written to the audit log: signing screens open only then.
+    pub fn recorded(&self) -> bool {
+        self.recorded
+    }
+
+    /// Tick or untick step `i`.
+    pub fn toggle(&mut self, i: usize) {
+        let Ok(c) = &mut self.checklist else { return };
+        let Some(step) = c.steps.get_mut(i) else { return };
+        step.ticked_at = match step.ticked_at {
+            Some(_) => None,
+            None => Some(now_rfc3339()),
+        };
+        if step.ticked_at.is_none() {
+            self.recorded = false;
+        }
+    }
+
+    /// Write the completed checklist to the audit log, once per completion.
+    pub fn record(&mut self, key: Option<&ProfileKey>, operator: &str) -> Result<()> {
+        if self.recorded || !self.complete() {
+            return Ok(());
+        }
+        let Ok(c) = &self.checklist else { return Ok(()) };
+        let steps: Vec<_> = c.steps.iter().map(|s| json!({ "step": s.text, "tickedAt": s.ticked_at })).collect();
+        let mut detail = json!({ "file": self.path, "sha256": c.sha256, "title": c.title, "steps": steps });
+        if !operator.trim().is_empty() {
+            detail["operator"] = json!(operator.trim());
+        }
+        append_audit_event(key, "ceremony_checklist", detail)?;
+        self.recorded = true;
+        Ok(())
+    }
+}
+
+fn read_checklist(path: &str) -> Result<Checklist> {
+    let bytes = std::fs::read(path).with_context(|| format!("Cannot read ceremony checklist {path}"))?;
+    let file: ChecklistFile = serde_yaml::from_slice(&bytes).with_context(|| format!("{path} is not a valid ceremony checklist"))?;
+    let mut steps = Vec::new();
+    for (i, spec) in file.steps.into_iter().enumerate() {
+        let (text, detail) = match spec {
+            StepSpec::Text(text) => (text, String::new()),
+            StepSpec::Full { text, detail } => (text, detail),
+        };
+        if text.trim().is_empty() {
+            bail!("Step {} of {path} has no text", i + 1);
+        }
+        steps.push(Step { text: text.trim().to_string(), detail: detail.trim().to_string(), ticked_at: None });
+    }
+    if steps.is_empty() {
+        bail!("{path} lists no steps");
+    }
+    Ok(Checklist { title: file.title.trim().to_string(), sha256: sha256_hex(&bytes), steps })
+}
+
+fn now_rfc3339() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    now.replace_nanosecond(0)
+        .unwrap_or(now)
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
@@ -0,0 +1,221 @@
+//! Printable ceremony record for a signed transaction file: a self-contained HTML page with
+//! the decoded transactions, their signatures, the fingerprints of every public key involved,
+//! the automated verification results and checkboxes plus sign-off lines for the people
+//! checking it by hand. Print it from a browser (or "Print to PDF") for a paper record.
+//!
+//! Like Verify Signed File, everything is taken from the signed transactions themselves
+//! (decoded and sender-recovered), not from the informational `decodedTx` block.
+
+use anyhow::{Context, Result};
+use ethers_core::utils::{keccak256, to_checksum};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::abi::load_abi;
+use crate::crypto::modern::key_fingerprint_from_compressed;
+use crate::decoder::decode_calldata_to_json;
+use crate::signing::decode_signed_tx_and_recover;
+use crate::types::{DecodedOne, DelegationDecodedOrdered, InvalidationDecodedOrdered, RevocationDecodedOrdered};
+use crate::util::hex_to_bytes;
+use crate::verify_signed::{EntryReport, Finding, VerifyReport};
+use crate::vfs::Fs;
+
+/// Checks the people at the ceremony tick off for every transaction.
+const MANUAL_CHECKS: &[&str] = &[
+    "The sender is the account of the key presented at the ceremony",
+    "Every public key fingerprint matches the ceremony's key records",
+    "Chain ID and contract address are those of the intended network",
+    "Start and end times are as agreed",
+    "The nonce is the sender's next unused nonce",
+];
+
+/// `<file stem>.ceremony.html` beside the signed file.
+pub fn default_record_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!("{stem}.ceremony.html"))
+}
+
+/// Render the ceremony record for the file `report` was made from.
+pub fn ceremony_record_html(fs: &dyn Fs, report: &VerifyReport) -> Result<String> {
+    let path = &report.path;
+    let bytes = fs.read(path).with_context(|| format!("reading {}", path.display()))?;
+    let json: serde_json::Value = serde_json::from_slice(&bytes)
+        .with_context(|| format!("{} is not a signed transaction file (JSON)", path.display()))?;
+    let entries = json.get("entries").unwrap_or(&json).as_array().cloned().unwrap_or_default();
+    let abi = load_abi()?;
+
+    let mut html = String::new();
+    let title = format!("Key ceremony record: {}", path.file_name().unwrap_or_default().to_string_lossy());
+    let _ = write!(html, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n", esc(&title));
+    let _ = writeln!(html, "<h1>{}</h1>", esc(&title));
+
+    // File summary
+    html.push_str("<table>\n");
+    row(&mut html, "File", &path.display().to_string());
+    row(&mut html, "File SHA-256", &report.sha256);
+    row(&mut html, "Verified at", &report.checked_at);
+    row(&mut html, "Verdict", &report.verdict());
+    row(&mut html, "Transactions", &entries.len().to_string());
+    let meta = &json["metadata"];
+    for (label, field) in [("Created at", "createdAt"), ("Created by tool version", "toolVersion"), ("Operator", "operatorLabel"), ("Input SHA-256", "inputSha256")] {
+        if let Some(v) = meta[field].as_str() {
+            row(&mut html, label, v);
+        }
+    }
+    html.push_str("</table>\n");
+    findings(&mut html, &report.file_findings);
+
+    for (i, entry) in entries.iter().enumerate() {
+        let _ = writeln!(html, "<section>\n<h2>Transaction {} of {}</h2>", i + 1, entries.len());
+        if let Err(e) = transaction(&mut html, entry, &abi) {
+            let _ = writeln!(html, "<p class=\"fail\">Cannot decode this transaction: {}</p>", esc(&format!("{e:#}")));
+        }
+        if let Some(er) = report.entries.iter().find(|er| er.index == i + 1) {
+            entry_findings(&mut html, er);
+        }
+        html.push_str("<h3>Manual verification</h3>\n<ul class=\"checks\">\n");
+        for check in MANUAL_CHECKS {
+            let _ = writeln!(html, "<li><span class=\"box\"></span>{}</li>", esc(check));
+        }
+        html.push_str("</ul>\n</section>\n");
+    }
+
+    html.push_str("<section>\n<h2>Sign-off</h2>\n<table class=\"signoff\">\n<tr><th>Role</th><th>Name</th><th>Signature</th><th>Date</th></tr>\n");
+    for role in ["Operator", "Witness", "Witness"] {
+        let _ = writeln!(html, "<tr><td>{role}</td><td></td><td></td><td></td></tr>");
+    }
+    html.push_str("</table>\n</section>\n</body>\n</html>\n");
+    Ok(html)
+}
+
+fn transaction(html: &mut String, entry: &serde_json::Value, abi: &ethers_core::abi::Abi) -> Result<()> {
+    let signed_tx = entry["signedTx"].as_str().context("entry has no signedTx")?;
+    let (chain_id, nonce, prio, fee, gas, to, value, data, from) = decode_signed_tx_and_recover(signed_tx)?;
+    let tx_hash = keccak256(hex_to_bytes(signed_tx)?);
+    let (func_name, one, two) = decode_calldata_to_json(abi, &data)?;
+
+    html.push_str("<table>\n");
+    row(html, "Function", &func_name);
+    row(html, "Sender", &to_checksum(&from, None));
+    row(html, "Contract", &to_checksum(&to, None));
+    row(html, "Chain ID", &chain_id.to_string());
+    row(html, "Nonce", &nonce.to_string());
+    row(html, "Value (wei)", &value.to_string());
+    row(html, "Gas limit", &gas.to_string());
+    row(html, "Max fee per gas (wei)", &fee.to_string());
+    row(html, "Max priority fee per gas (wei)", &prio.to_string());
+    row(html, "Transaction hash", &format!("0x{}", hex::encode(tx_hash)));
+    html.push_str("</table>\n");
+
+    // A redelegation carries the revocation first, then the delegation
+    for decoded in [two, one].into_iter().flatten() {
+        payload(html, &decoded);
+    }
+    Ok(())
+}
+
+fn payload(html: &mut String, decoded: &DecodedOne) {
+    match decoded {
+        DecodedOne::Delegation(DelegationDecodedOrdered {
+            delegatorPubkey, delegateePubkey, delegationStartTime, delegationEndTime,
+            doesRevocationRequireDelegateeSignature, nonce, expectedAddressOfDeployedContract,
+            rDelegatorPubkeySig, sDelegatorPubkeySig, vDelegatorPubkeySig,
+            rDelegateePubkeySig, sDelegateePubkeySig, vDelegateePubkeySig,
+        }) => {
+            html.push_str("<h3>Delegation</h3>\n<table>\n");
+            pubkey_row(html, "Delegator public key", delegatorPubkey);
+            pubkey_row(html, "Delegatee public key", delegateePubkey);
+            row(html, "Start time", delegationStartTime);
+            row(html, "End time", delegationEndTime);
+            row(html, "Revocation requires delegatee signature", &doesRevocationRequireDelegateeSignature.to_string());
+            row(html, "Payload nonce", nonce);
+            row(html, "Expected contract", expectedAddressOfDeployedContract);
+            sig_row(html, "Delegator signature", rDelegatorPubkeySig, sDelegatorPubkeySig, vDelegatorPubkeySig);
+            sig_row(html, "Delegatee signature", rDelegateePubkeySig, sDelegateePubkeySig, vDelegateePubkeySig);
+        }
+        DecodedOne::Revocation(RevocationDecodedOrdered {
+            revokerPubkey, revokeePubkey, revocationStartTime, revocationEndTime, nonce,
+            expectedAddressOfDeployedContract,
+            rRevokerPubkeySig, sRevokerPubkeySig, vRevokerPubkeySig,
+            rRevokeePubkeySig, sRevokeePubkeySig, vRevokeePubkeySig,
+        }) => {
+            html.push_str("<h3>Revocation</h3>\n<table>\n");
+            pubkey_row(html, "Revoker public key", revokerPubkey);
+            pubkey_row(html, "Revokee public key", revokeePubkey);
+            row(html, "Start time", revocationStartTime);
+            row(html, "End time", revocationEndTime);
+            row(html, "Payload nonce", nonce);
+            row(html, "Expected contract", expectedAddressOfDeployedContract);
+            sig_row(html, "Revoker signature", rRevokerPubkeySig, sRevokerPubkeySig, vRevokerPubkeySig);
+            sig_row(html, "Revokee signature", rRevokeePubkeySig, sRevokeePubkeySig, vRevokeePubkeySig);
+        }
+        DecodedOne::Invalidation(InvalidationDecodedOrdered {
+            invalidatedPubkey, nonce, expectedAddressOfDeployedContract,
+            rInvalidatedPubkeySig, sInvalidatedPubkeySig, vInvalidatedPubkeySig,
+        }) => {
+            html.push_str("<h3>Permanent invalidation</h3>\n<table>\n");
+            pubkey_row(html, "Invalidated public key", invalidatedPubkey);
+            row(html, "Payload nonce", nonce);
+            row(html, "Expected contract", expectedAddressOfDeployedContract);
+            sig_row(html, "Signature", rInvalidatedPubkeySig, sInvalidatedPubkeySig, vInvalidatedPubkeySig);
+        }
+    }
+    html.push_str("</table>\n");
+}
+
+/// Key plus the fingerprint key files record in their v2 metadata, so the two can be matched.
+fn pubkey_row(html: &mut String, label: &str, pubkey: &str) {
+    let fingerprint = hex_to_bytes(pubkey)
+        .ok()
+        .and_then(|b| secp256k1::PublicKey::from_slice(&b).ok())
+        .map(|pk| key_fingerprint_from_compressed(&pk.serialize()))
+        .unwrap_or_else(|| "(not a valid secp256k1 key)".to_string());
+    row(html, label, pubkey);
+    row(html, &format!("{label} fingerprint"), &fingerprint);
+}
+
+fn sig_row(html: &mut String, label: &str, r: &str, s: &str, v: &str) {
+    let _ = writeln!(html, "<tr><th>{}</th><td class=\"mono\">r = {}<br>s = {}<br>v = {}</td></tr>", esc(label), esc(r), esc(s), esc(v));
+}
+
+fn row(html: &mut String, label: &str, value: &str) {
+    let _ = writeln!(html, "<tr><th>{}</th><td class=\"mono\">{}</td></tr>", esc(label), esc(value));
+}
+
+fn entry_findings(html: &mut String, report: &EntryReport) {
+    let tag = report.status().tag();
+    let _ = writeln!(html, "<h3>Automated checks: <span class=\"{}\">{tag}</span></h3>", tag.to_ascii_lowercase());
+    findings(html, &report.findings);
+}
+
+fn findings(html: &mut String, findings: &[Finding]) {
+    html.push_str("<ul class=\"findings\">\n");
+    for f in findings {
+        let tag = f.status.tag();
+        let _ = writeln!(html, "<li class=\"{}\">[{tag}] {}</li>", tag.to_ascii_lowercase(), esc(&f.text));
+    }
+    html.push_str("</ul>\n");
+}
+
+fn esc(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+const STYLE: &str = "
+body { font-family: sans-serif; font-size: 11pt; margin: 2em; }
+h1 { font-size: 16pt; }
+h2 { font-size: 13pt; border-bottom: 1px solid #000; }
+h3 { font-size: 11pt; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }
+th, td { border: 1px solid #888; padding: 4px 6px; text-align: left; vertical-align: top; }
+th { width: 28%; font-weight: normal; background: #eee; }
+.mono { font-family: monospace; word-break: break-all; }
+.signoff td { height: 3em; }
+.checks { list-style: none; padding-left: 0; }
+.checks li { margin: 6px 0; }
+.box { display: inline-block; width: 0.9em; height: 0.9em; border: 1px solid #000; margin-right: 0.6em; vertical-align: middle; }
+.fail { color: #b00; }
+.warn { color: #a60; }
+section { page-break-inside: avoid; }
+@media print { body { margin: 0; } section { page-break-before: always; } }
+";
@@ -1,37 +1,482 @@
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+//! Command line: subcommands that run one job without the terminal UI, for scripting on an
+//! air-gapped machine. Each calls the same `commands`/`process` code as the matching screen, so
+//! its output files are the ones the screen would write. With no subcommand the menu starts.
+//!
+//! Secrets (passwords, private keys) are never taken as arguments, where they would land in the
+//! shell history and the process list: they are prompted for without echo on a terminal, or read
+//! one per line from stdin when it is not one (`printf '%s\n' "$PW" | inkan decrypt …`).
+//! A master-password profile is unlocked the same way before anything else is read.
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroize;
+
+use crate::abi::load_abi;
+use crate::commands::batch::{parse_batch_items, BatchRun, BATCH_ERRORS_FILE};
+use crate::commands::decrypt_auto::{decrypt_auto_in_memory, write_decrypted_output};
+use crate::commands::decrypt_modern::modern_key_factors;
+use crate::commands::key_save::{emit_encrypted_one_modern, EncryptedSaveOptions};
+use crate::commands::keygen;
+use crate::crypto::factors::{mix_factors, KeyFactors};
+use crate::crypto::modern::KdfPreset;
+use crate::defaults::Defaults;
+use crate::event_stream::{Event, EventStream};
+use crate::key_usage::{load_key_usage, record_signed, KeyUsage};
+use crate::usage_stats::Written;
+use crate::lint;
+use crate::memory_hygiene::lock_warning;
+use crate::process::{process_item, BatchOpts};
+use crate::migrate::migrate_stores;
+use crate::profile::{profile_enabled, unlock_profile, ProfileKey};
+use crate::screens::create_key_pair::sanitize_filename;
+use crate::settings::{load_settings, Settings};
+use crate::signing::SignerCache;
+use crate::simulate;
+use crate::types::Item;
+use crate::units::{GasLimit, Nonce, PayloadNonce, Wei};
+use crate::validation::validate_gas_fields;
+use crate::vfs::{FsRef, Sandbox, SandboxFs};
+use crate::write_signed_transactions_to_file::{
+    build_filename_for_any_tx, signed_file_metadata, write_single_signed_transaction,
+};
 
 #[derive(Parser, Debug)]
-#[command(version, about="Inkan offline utility")]
+#[command(version, about = "Inkan offline utility")]
 pub struct Cli {
+    /// Run in this workspace (see Workspaces in the README)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub workspace: Option<String>,
+
+    /// Drive the screens from a key script instead of the keyboard (testing)
+    #[cfg(feature = "script")]
+    #[arg(long, value_name = "FILE")]
+    pub script: Option<PathBuf>,
+
     #[command(subcommand)]
-    pub cmd: Command,
+    pub cmd: Option<Command>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
-    /// Sign a batch JSON of contract calls
-    Batch {
-        #[arg(long)]
-        batch: PathBuf,
-        #[arg(long, default_value = "batch_output.json")]
-        out: PathBuf,
-        #[arg(long, default_value = "30000000")]
-        gas_limit: String,
-        #[arg(long, default_value = "30000000000")]
-        max_fee_per_gas: String,
-        #[arg(long, default_value = "2000000000")]
-        max_priority_fee_per_gas: String,
-    },
+    /// Launch the interactive terminal menu (the default)
+    Menu,
 
-    /// Generate Ethereum/Nostr keys
+    /// Generate key pairs and save each as an encrypted (modern format) key file
     Keygen {
+        /// Key pair name; numbered when more than one key is made
+        #[arg(long)]
+        nickname: String,
         #[arg(long, default_value = "1")]
         count: u32,
+        #[arg(long, default_value = Defaults::CREATE_KEYPAIR_OUT_DIR)]
+        out_dir: PathBuf,
+        /// Name the files as hot signing keys instead of cold-storage keys
         #[arg(long)]
-        out: Option<PathBuf>,
+        hot: bool,
+        #[arg(long, value_enum, default_value = "balanced")]
+        kdf: KdfArg,
     },
 
-    /// Launch an interactive terminal menu
-    Menu,
+    /// Sign a batch file (JSON array of items) into a new run directory, as Sign Batch File does
+    BatchSign {
+        #[arg(long, default_value = Defaults::BATCH_INPUT_FILE)]
+        batch: PathBuf,
+        #[arg(long, default_value = Defaults::BATCH_OUT_DIR)]
+        out_dir: PathBuf,
+        #[command(flatten)]
+        gas: GasArgs,
+        /// Sign without asking after the pre-flight summary (required when stdin is not a terminal)
+        #[arg(long)]
+        yes: bool,
+        /// Sign items that use one key for two roles
+        #[arg(long)]
+        allow_key_reuse: bool,
+        /// Sign items whose sender key is retired
+        #[arg(long)]
+        allow_retired_keys: bool,
+    },
+
+    /// Sign one delegation, as Create Delegation does; the keys are prompted for
+    CreateDelegation {
+        #[arg(long)]
+        nonce: Nonce,
+        /// Delegatee pubkey (0x04…); without it the delegatee privkey is prompted for
+        #[arg(long)]
+        delegatee_pubkey: Option<String>,
+        /// Revoking the delegation will need the delegatee's signature too
+        #[arg(long)]
+        require_delegatee_sig_for_revocation: bool,
+        #[arg(long)]
+        label: Option<String>,
+        /// Fixed payload nonce (0x + 32 hex digits or a UUID) for a reproducible payload; random without it
+        #[arg(long)]
+        payload_nonce: Option<PayloadNonce>,
+        #[arg(long, default_value = Defaults::CREATE_DELEGATION_OUT_DIR)]
+        out_dir: PathBuf,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+
+    /// Decrypt a key file or export (modern or OpenPGP); the password is prompted for
+    Decrypt {
+        input: PathBuf,
+        #[arg(long, default_value = Defaults::DECRYPT_OUTPUT_DIR)]
+        out_dir: PathBuf,
+        /// Keyfile, for a file encrypted with one
+        #[arg(long)]
+        keyfile: Option<PathBuf>,
+    },
+
+    /// Answer JSON-RPC requests on a local UNIX socket (see `serve.rs`)
+    #[cfg(unix)]
+    Serve {
+        #[arg(long)]
+        unix_socket: PathBuf,
+        /// Also accept `sign_batch`
+        #[arg(long)]
+        allow_signing: bool,
+    },
+
+    /// Print the release attestation of this binary
+    VerifyBinary,
+}
+
+/// Gas settings; unset ones take the same defaults as the screen.
+#[derive(clap::Args, Debug)]
+pub struct GasArgs {
+    #[arg(long)]
+    gas_limit: Option<String>,
+    #[arg(long)]
+    max_fee_per_gas: Option<String>,
+    #[arg(long)]
+    max_priority_fee_per_gas: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum KdfArg {
+    Fast,
+    Balanced,
+    Paranoid,
+}
+
+impl From<KdfArg> for KdfPreset {
+    fn from(k: KdfArg) -> Self {
+        match k {
+            KdfArg::Fast => KdfPreset::Fast,
+            KdfArg::Balanced => KdfPreset::Balanced,
+            KdfArg::Paranoid => KdfPreset::Paranoid,
+        }
+    }
+}
+
+/// Read a secret: without echo from the terminal, or the next line of stdin.
+fn read_secret(prompt: &str) -> Result<String> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            bail!("stdin ended before the {prompt} was read");
+        }
+        let secret = line.trim_end_matches(['\r', '\n']).to_string();
+        line.zeroize();
+        return Ok(secret);
+    }
+    eprint!("{prompt}: ");
+    std::io::stderr().flush()?;
+    enable_raw_mode()?;
+    let mut secret = String::new();
+    let res = loop {
+        match event::read() {
+            Ok(TermEvent::Key(k)) if k.kind != KeyEventKind::Release => match k.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Char('c') if k.modifiers.contains(KeyModifiers::CONTROL) => break Err(anyhow!("cancelled")),
+                KeyCode::Backspace => { secret.pop(); }
+                KeyCode::Char(c) => secret.push(c),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+    disable_raw_mode()?;
+    eprintln!();
+    if let Err(e) = res {
+        secret.zeroize();
+        return Err(e);
+    }
+    Ok(secret)
+}
+
+/// Settings, key usage and file access, as the menu would set them up.
+struct Session {
+    settings: Settings,
+    key_usage: KeyUsage,
+    profile_key: Option<ProfileKey>,
+    fs: FsRef,
+    events: EventStream,
+}
+
+impl Session {
+    fn open() -> Result<Self> {
+        let profile_key = if profile_enabled() {
+            let mut password = read_secret("Master password")?.into_bytes();
+            Some(unlock_profile(&mut password)?)
+        } else {
+            None
+        };
+        for line in migrate_stores(profile_key.as_ref())? {
+            eprintln!("{line}");
+        }
+        let settings = load_settings(profile_key.as_ref());
+        let sandbox = Sandbox::default();
+        sandbox.set_roots(&settings.sandbox_roots);
+        let events = EventStream::default();
+        events.set_target(&settings.event_stream);
+        Ok(Self {
+            key_usage: load_key_usage(profile_key.as_ref()),
+            fs: FsRef(std::sync::Arc::new(SandboxFs::new(FsRef::default(), sandbox))),
+            settings,
+            profile_key,
+            events,
+        })
+    }
+
+    fn gas(&self, gas: &GasArgs, default_limit: &str) -> Result<(GasLimit, Wei, Wei)> {
+        validate_gas_fields(
+            &self.settings.gas_caps,
+            gas.gas_limit.as_deref().unwrap_or(default_limit),
+            gas.max_fee_per_gas.as_deref().unwrap_or(Defaults::MAX_FEE_PER_GAS),
+            gas.max_priority_fee_per_gas.as_deref().unwrap_or(Defaults::MAX_PRIORITY_FEE_PER_GAS),
+        )
+    }
+
+    fn batch_opts(&self, gas: (GasLimit, Wei, Wei)) -> BatchOpts {
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: self.settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: self.key_usage.retired(),
+            allow_retired_key: false,
+            encoding: self.settings.signed_output_encoding,
+        }
+    }
+
+    /// Record a signing in the key usage, audit log and usage statistics; failures are printed,
+    /// not fatal.
+    fn record(&mut self, written: Written<'_>) {
+        for w in record_signed(&mut self.key_usage, self.profile_key.as_ref(), self.settings.usage_stats, written) {
+            eprintln!("{w}");
+        }
+    }
+}
+
+/// Run a subcommand other than `menu`.
+pub async fn run(cmd: Command) -> Result<()> {
+    // The menu prints its own exit notes
+    let menu = matches!(cmd, Command::Menu);
+    let result = match cmd {
+        Command::Menu => crate::app::run_menu().await,
+        Command::Keygen { nickname, count, out_dir, hot, kdf } => keygen_cmd(&nickname, count, &out_dir, hot, kdf.into()),
+        Command::BatchSign { batch, out_dir, gas, yes, allow_key_reuse, allow_retired_keys } => {
+            batch_sign(&batch, &out_dir, &gas, yes, allow_key_reuse, allow_retired_keys).await
+        }
+        Command::CreateDelegation { nonce, delegatee_pubkey, require_delegatee_sig_for_revocation, label, payload_nonce, out_dir, gas } => {
+            let payload_nonce = payload_nonce.unwrap_or_default();
+            create_delegation(nonce, delegatee_pubkey, require_delegatee_sig_for_revocation, label, payload_nonce, &out_dir, &gas).await
+        }
+        Command::Decrypt { input, out_dir, keyfile } => decrypt(&input, &out_dir, keyfile.as_deref()),
+        #[cfg(unix)]
+        Command::Serve { unix_socket, allow_signing } => crate::serve::run(&unix_socket, allow_signing).await,
+        Command::VerifyBinary => {
+            for line in crate::release::attestation_lines()? { println!("{line}"); }
+            Ok(())
+        }
+    };
+    if let (false, Some(warning)) = (menu, lock_warning()) {
+        eprintln!("Warning: {warning}");
+    }
+    result
+}
+
+fn keygen_cmd(nickname: &str, count: u32, out_dir: &Path, hot: bool, kdf: KdfPreset) -> Result<()> {
+    if count == 0 {
+        bail!("--count must be at least 1");
+    }
+    let mut password = read_secret("Password")?;
+    let mut confirm = read_secret("Confirm password")?;
+    let matched = password == confirm;
+    confirm.zeroize();
+    if !matched || password.is_empty() {
+        password.zeroize();
+        bail!(if matched { "Password cannot be empty." } else { "Password and Confirm Password do not match." });
+    }
+    std::fs::create_dir_all(out_dir).with_context(|| format!("creating directory {}", out_dir.display()))?;
+
+    let prefix = if hot { "HOT_PRIVKEY_FOR_SIGNING_KEEP_PROTECTED" } else { "COLD_STORAGE_PRIVKEY_KEEP_AIRGAPPED" };
+    let params = kdf.params();
+    let res = (1..=count).try_for_each(|i| {
+        let name = if count == 1 { nickname.to_string() } else { format!("{nickname}_{i}") };
+        let path = out_dir.join(format!("{prefix}_{}.enc", sanitize_filename(&name)));
+        let path = path.to_str().ok_or_else(|| anyhow!("invalid output path"))?.to_string();
+        let rec = keygen::generate(1)?.into_iter().next().ok_or_else(|| anyhow!("internal: expected one key"))?;
+        let mut password_utf8 = password.clone().into_bytes();
+        let written = emit_encrypted_one_modern(&rec, EncryptedSaveOptions {
+            out_path: &path,
+            nickname: &name,
+            password_utf8: &mut password_utf8,
+            argon_t_cost: params.t_cost,
+            argon_m_cost_kib: params.m_cost_kib,
+            argon_p_cost: params.p_cost,
+            add_noise_prefix: true,
+            factors: KeyFactors::default(),
+        })
+        .with_context(|| format!("writing {path}"))?;
+        println!("{}\t{}", rec.address, written.display());
+        Ok(())
+    });
+    password.zeroize();
+    res
+}
+
+async fn batch_sign(
+    batch: &Path,
+    out_dir: &Path,
+    gas: &GasArgs,
+    yes: bool,
+    allow_key_reuse: bool,
+    allow_retired_keys: bool,
+) -> Result<()> {
+    let mut session = Session::open()?;
+    let gas = session.gas(gas, Defaults::GAS_LIMIT)?;
+    session.fs.create_dir_all(out_dir).with_context(|| format!("creating output directory {}", out_dir.display()))?;
+    let raw = session.fs.read(batch).with_context(|| format!("reading {}", batch.display()))?;
+    let items = parse_batch_items(&raw, &batch.display().to_string())?;
+    let mut run = BatchRun::new(load_abi()?, session.batch_opts(gas), items, raw, out_dir.to_path_buf())
+        .with_events(session.events.clone());
+
+    let summary = run.summary(&session.settings.networks)?;
+    for line in summary.lines(&session.settings.display) { eprintln!("{line}"); }
+    eprintln!();
+    for w in summary.network_warnings.iter().cloned().chain(summary.nonce_warning()) { eprintln!("{w}"); }
+    if summary.key_reuse.is_some() {
+        if !allow_key_reuse { bail!("Items reuse a key for two roles (listed above); pass --allow-key-reuse to sign them."); }
+        run.allow_key_reuse();
+    }
+    if summary.retired.is_some() {
+        if !allow_retired_keys { bail!("Items are signed with a retired key (listed above); pass --allow-retired-keys to sign them."); }
+        run.allow_retired_keys();
+    }
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("Not signing without --yes: stdin is not a terminal to confirm on.");
+        }
+        eprint!("Sign {} item(s)? [y/N] ", run.total());
+        std::io::stderr().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            bail!("Cancelled; nothing was signed.");
+        }
+    }
+
+    while !run.is_done() {
+        if let Err(e) = run.step().await {
+            let saved = match run.write_failed(&*session.fs, &session.settings, &e) {
+                Ok(dir) => format!("\nSigned items and {BATCH_ERRORS_FILE} saved to {}", dir.display()),
+                Err(w) => format!("\nSigned items NOT saved: {w:#}"),
+            };
+            for line in run.stats_lines() { eprintln!("{line}"); }
+            bail!("{e:#}{saved}");
+        }
+        eprint!("\rSigned {}/{}", run.signed(), run.total());
+    }
+    eprintln!();
+    for line in run.stats_lines() { eprintln!("{line}"); }
+    let dir = run.write_complete(&*session.fs, &session.settings)?;
+    println!("{}", dir.display());
+    session.record(Written::Run(&run));
+    Ok(())
+}
+
+async fn create_delegation(
+    nonce: Nonce,
+    delegatee_pubkey: Option<String>,
+    require_delegatee_sig: bool,
+    label: Option<String>,
+    payload_nonce: PayloadNonce,
+    out_dir: &Path,
+    gas: &GasArgs,
+) -> Result<()> {
+    let mut session = Session::open()?;
+    let default_limit = session.settings.gas_limits.default_for("createDelegationEvent").to_string();
+    let opts = session.batch_opts(session.gas(gas, &default_limit)?);
+
+    let mut delegator_priv = read_secret("Delegator PrivKey")?;
+    let mut delegatee_priv = match &delegatee_pubkey {
+        Some(_) => String::new(),
+        None => read_secret("Delegatee PrivKey")?,
+    };
+    let item = Item {
+        function_to_call: "createDelegationEvent".to_string(),
+        nonce: Some(nonce),
+        chain_id: Some(Defaults::CHAIN_ID),
+        contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+        label,
+        type_a_privkey_x: Some(delegator_priv.trim().to_string()),
+        type_a_privkey_y: Some(delegatee_priv.trim().to_string()),
+        type_a_pubkey_y: Some(delegatee_pubkey.unwrap_or_default().trim().to_string()),
+        type_a_uint_x: Some(0),
+        type_a_uint_y: Some(0),
+        type_a_boolean: Some(require_delegatee_sig.to_string()),
+        type_a_nonce16: payload_nonce.hex(),
+        type_b_privkey_x: None,
+        type_b_privkey_y: None,
+        type_b_pubkey_y: None,
+        type_b_uint_x: None,
+        type_b_uint_y: None,
+        type_b_nonce16: None,
+        type_c_privkey_x: None,
+        type_c_nonce16: None,
+    };
+    delegator_priv.zeroize();
+    delegatee_priv.zeroize();
+
+    let abi = load_abi()?;
+    let entry = process_item(&abi, &opts, &item)
+        .await
+        .context("failed to construct and sign delegation transaction")?;
+    lint::check_entries(std::slice::from_ref(&entry))?;
+    simulate::check_entries(&session.settings, std::slice::from_ref(&entry))?;
+
+    session.fs.create_dir_all(out_dir).with_context(|| format!("creating output directory {}", out_dir.display()))?;
+    let out_path = out_dir.join(build_filename_for_any_tx(&entry, &session.settings.filename_templates));
+    let metadata = signed_file_metadata(&session.settings, None, Some("createDelegationEvent"));
+    let path = write_single_signed_transaction(&*session.fs, &out_path, &entry, metadata.as_ref(), opts.encoding.encoder())
+        .context("failed to write signed transaction file")?;
+    session.events.emit(Event::file_written(None, &path));
+    println!("{}", path.display());
+    session.record(Written::One { func: &entry.decoded_tx.funcName, from: &entry.decoded_tx.from });
+    Ok(())
+}
+
+fn decrypt(input: &Path, out_dir: &Path, keyfile: Option<&Path>) -> Result<()> {
+    let factors = std::fs::read(input).map(|buf| modern_key_factors(&buf)).unwrap_or_default();
+    let mut password_utf8 = read_secret("Password")?.into_bytes();
+    if let Err(e) = mix_factors(&mut password_utf8, &factors, keyfile) {
+        password_utf8.zeroize();
+        return Err(e);
+    }
+    let decrypted = decrypt_auto_in_memory(input, &mut password_utf8)?;
+    let path = write_decrypted_output(input, out_dir, None, &decrypted.plaintext)?;
+    eprintln!("Decrypted ({})", decrypted.method_label);
+    println!("{}", path.display());
+    Ok(())
 }
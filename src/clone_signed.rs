@@ -0,0 +1,109 @@
+//! "Clone Signed File": turn a previously signed transaction file back into Create-form
+//! values, so a similar event can be signed again (typically a delegation repeated after it
+//! expired). Values come from the signed transaction itself, decoded and sender-recovered,
+//! not from the file's informational `decodedTx` block, which could have been edited.
+//!
+//! The result uses the info-file keys (see the Create screens' `PREFILL_FIELDS`), so it goes
+//! through the same prefill path and summary as Load From File. Private keys are never in a
+//! signed file and the transaction nonce must be new, so those are left for manual entry.
+//! Values the form cannot take (public keys of signing parties, a re-delegation's delegation
+//! window, a different chain or contract) are added under their own keys and show up as ignored.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ethers_core::types::Address;
+use ethers_core::utils::to_checksum;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::abi::load_abi;
+use crate::app::DelegationPrefill;
+use crate::decoder::decode_calldata_to_json;
+use crate::defaults::Defaults;
+use crate::signing::decode_signed_tx_and_recover;
+use crate::types::{DecodedOne, DelegationDecodedOrdered, RevocationDecodedOrdered};
+use crate::util::sha256_hex;
+use crate::vfs::Fs;
+
+/// Read the single transaction in the signed file at `path` and map it to form values for
+/// `func_name`. Fails when the file holds another kind of transaction.
+pub fn clone_signed_file(fs: &dyn Fs, path: &Path, func_name: &str) -> Result<DelegationPrefill> {
+    let bytes = fs.read(path).with_context(|| format!("reading {}", path.display()))?;
+    let json: serde_json::Value = serde_json::from_slice(&bytes)
+        .with_context(|| format!("{} is not a signed transaction file (JSON)", path.display()))?;
+    // Versioned envelope or legacy bare array
+    let entries = json.get("entries").unwrap_or(&json).as_array()
+        .ok_or_else(|| anyhow!("{} has no entries array", path.display()))?;
+    let [entry] = entries.as_slice() else {
+        bail!("{} holds {} transactions; pick a single-transaction file", path.display(), entries.len());
+    };
+    let signed_tx = entry["signedTx"].as_str().ok_or_else(|| anyhow!("{} has no signedTx", path.display()))?;
+
+    let (chain_id, _nonce, max_prio, max_fee, gas, to, _value, data, _from) =
+        decode_signed_tx_and_recover(signed_tx).context("decoding signedTx")?;
+    let abi = load_abi()?;
+    let (name, one, two) = decode_calldata_to_json(&abi, &data)?;
+    if name != func_name {
+        bail!("{} holds a {name} transaction, not {func_name}", path.display());
+    }
+
+    let mut map: HashMap<String, String> = HashMap::new();
+    map.insert("GAS_LIMIT".into(), gas.to_string());
+    map.insert("MAX_FEE_PER_GAS".into(), max_fee.to_string());
+    map.insert("MAX_PRIORITY_FEE_PER_GAS".into(), max_prio.to_string());
+    // The forms always sign for the built-in chain and contract.
+    if chain_id != Defaults::CHAIN_ID {
+        map.insert("CHAIN_ID".into(), chain_id.to_string());
+    }
+    let builtin: Address = Defaults::CONTRACT_ADDRESS.parse()?;
+    if to != builtin {
+        map.insert("CONTRACT_ADDRESS".into(), to_checksum(&to, None));
+    }
+
+    match (one, two) {
+        (Some(DecodedOne::Delegation(d)), None) => {
+            map.insert("DELEGATOR_PUBKEY".into(), d.delegatorPubkey.clone());
+            insert_delegation(&mut map, &d);
+        }
+        (Some(DecodedOne::Revocation(r)), None) => {
+            map.insert("REVOKER_PUBKEY".into(), r.revokerPubkey.clone());
+            insert_revocation(&mut map, &r);
+        }
+        (Some(DecodedOne::Invalidation(i)), None) => {
+            map.insert("INVALIDATED_PUBKEY".into(), i.invalidatedPubkey);
+        }
+        // Re-delegation: (A, B) = (delegation, revocation), both signed by the revoker/redelegator.
+        (Some(DecodedOne::Delegation(d)), Some(DecodedOne::Revocation(r))) => {
+            map.insert("REVOKER_REDELEGATOR_PUBKEY".into(), r.revokerPubkey.clone());
+            insert_revocation(&mut map, &r);
+            insert_delegation(&mut map, &d);
+        }
+        _ => bail!("unexpected decoded shape for {name}"),
+    }
+
+    Ok(DelegationPrefill { map, source_sha256: Some(sha256_hex(&bytes)), includes: Vec::new() })
+}
+
+fn insert_delegation(map: &mut HashMap<String, String>, d: &DelegationDecodedOrdered) {
+    map.insert("DELEGATEE_PUBKEY".into(), d.delegateePubkey.clone());
+    map.insert(
+        "REQUIRE_DELEGATEE_SIG_FOR_REVOCATION".into(),
+        d.doesRevocationRequireDelegateeSignature.to_string(),
+    );
+    insert_window(map, "DELEGATION", &d.delegationStartTime, &d.delegationEndTime);
+}
+
+fn insert_revocation(map: &mut HashMap<String, String>, r: &RevocationDecodedOrdered) {
+    map.insert("REVOKEE_PUBKEY".into(), r.revokeePubkey.clone());
+    insert_window(map, "REVOCATION", &r.revocationStartTime, &r.revocationEndTime);
+}
+
+/// A set window as `<PREFIX>_START` / `<PREFIX>_END` Unix times, as the forms read it; an open
+/// side (0) is left out.
+fn insert_window(map: &mut HashMap<String, String>, prefix: &str, start: &str, end: &str) {
+    if start != "0" {
+        map.insert(format!("{prefix}_START"), start.to_string());
+    }
+    if end != "0" {
+        map.insert(format!("{prefix}_END"), end.to_string());
+    }
+}
@@ -0,0 +1,100 @@
+//! Address table: the Ethereum address, npub and key-file fingerprint of each public key in a
+//! list, for handing to online systems (allowlists, monitoring). Only public keys are read, so
+//! the table can be made and carried out without any key file being unlocked.
+
+use anyhow::{bail, Context, Result};
+use ethers_core::types::Address;
+use ethers_core::utils::{keccak256, to_checksum};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::PublicKey;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::commands::bulk_redelegation::load_pubkey_list;
+use crate::crypto::modern::key_fingerprint_from_compressed;
+use crate::crypto::nostr_utils::npub_from_xonly32;
+use crate::util::hex_to_bytes;
+use crate::vfs::Fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Json,
+}
+
+impl TableFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            TableFormat::Csv => "CSV",
+            TableFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::Json => "json",
+        }
+    }
+}
+
+/// One key's derived identifiers.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct AddressRow {
+    /// Uncompressed `0x04…`.
+    pub pubkey: String,
+    pub pubkeyCompressed: String,
+    /// Checksummed.
+    pub address: String,
+    pub npub: String,
+    /// As in a v2 key file's metadata, to match the table to key files.
+    pub keyFingerprint: String,
+}
+
+/// Derive the row of an uncompressed `0x04…` pubkey.
+pub fn address_row(pubkey_0x04: &str) -> Result<AddressRow> {
+    let bytes = hex_to_bytes(pubkey_0x04)?;
+    let pk = PublicKey::from_sec1_bytes(&bytes).context("not a secp256k1 public key")?;
+    let point = pk.to_encoded_point(true);
+    let compressed = point.as_bytes();
+    let x_only: [u8; 32] = compressed[1..].try_into().context("compressed pubkey length")?;
+    let address = Address::from_slice(&keccak256(&bytes[1..])[12..]);
+    Ok(AddressRow {
+        pubkey: pubkey_0x04.to_string(),
+        pubkeyCompressed: format!("0x{}", hex::encode(compressed)),
+        address: to_checksum(&address, None),
+        npub: npub_from_xonly32(&x_only),
+        keyFingerprint: key_fingerprint_from_compressed(compressed),
+    })
+}
+
+/// The table as CSV (with a header row) or as a JSON array.
+pub fn render_table(rows: &[AddressRow], format: TableFormat) -> Result<String> {
+    Ok(match format {
+        TableFormat::Csv => {
+            let mut out = String::from("address,npub,pubkey,pubkeyCompressed,keyFingerprint\n");
+            for r in rows {
+                out.push_str(&format!("{},{},{},{},{}\n", r.address, r.npub, r.pubkey, r.pubkeyCompressed, r.keyFingerprint));
+            }
+            out
+        }
+        TableFormat::Json => serde_json::to_string_pretty(rows)? + "\n",
+    })
+}
+
+/// Read the pubkey list at `list_path` and write its table to `out_path`, a new file.
+/// Returns the number of rows.
+pub fn write_address_table(fs: &dyn Fs, list_path: &Path, out_path: &Path, format: TableFormat) -> Result<usize> {
+    let (_, pubkeys) = load_pubkey_list(list_path)?;
+    let rows = pubkeys.iter().map(|p| address_row(p)).collect::<Result<Vec<_>>>()?;
+    if fs.exists(out_path) {
+        bail!("{} already exists; choose another Output File.", out_path.display());
+    }
+    if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs.create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs.create_new(out_path, render_table(&rows, format)?.as_bytes())
+        .with_context(|| format!("writing {}", out_path.display()))?;
+    Ok(rows.len())
+}
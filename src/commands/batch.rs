@@ -0,0 +1,636 @@
+use anyhow::{Context, Result};
+use ethers_core::abi::Abi;
+use ethers_core::types::{Address, U256};
+use ethers_core::utils::to_checksum;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::display::DisplaySettings;
+use crate::event_stream::{Event, EventStream};
+use crate::key_usage::{record_signed, KeyUsage};
+use crate::lint;
+use crate::profile::ProfileKey;
+use crate::process::{key_reuse, process_item, retired_keys, tx_sender_key, BatchOpts, KeyReuse, RetiredKey};
+use crate::settings::{Networks, Settings};
+use crate::simulate;
+use crate::types::{BatchEntryOut, BatchStatsOut, Item, SignedFileMetadata};
+use crate::units::{GasLimit, Nonce, Wei};
+use crate::usage_stats::Written;
+use crate::util::sha256_hex;
+use crate::vfs::Fs;
+use crate::write_signed_transactions_to_file::{
+    signed_file_metadata, write_batch_run_directory, Partial, RunWrite,
+};
+
+/// Read a batch input file (JSON array of `Item`).
+/// Returns the raw bytes as well, so callers can fingerprint the exact input.
+pub fn load_batch_items(path: &Path) -> Result<(Vec<u8>, Vec<Item>)> {
+    let raw = fs::read(path)
+        .with_context(|| format!("reading {}", path.display()))?;
+    let items = parse_batch_items(&raw, &path.display().to_string())?;
+    Ok((raw, items))
+}
+
+/// Parse batch input bytes read elsewhere; `source` names them in errors.
+pub fn parse_batch_items(raw: &[u8], source: &str) -> Result<Vec<Item>> {
+    let items: Vec<Item> = serde_json::from_slice(raw)
+        .with_context(|| format!("parsing {source} as a JSON array of batch items"))?;
+    if items.is_empty() {
+        anyhow::bail!("Batch file {source} contains no items.");
+    }
+    Ok(items)
+}
+
+/// Error report written next to the manifest of a batch that aborted.
+pub const BATCH_ERRORS_FILE: &str = "errors.json";
+
+/// Lint and simulation findings written next to the manifest of a partial run whose entries
+/// fail the checks a complete run must pass.
+pub const BATCH_CHECKS_FILE: &str = "checks.json";
+
+/// Per-sender totals of a batch, for the pre-flight summary.
+pub struct SenderSummary {
+    pub address: Address,
+    pub items: usize,
+    pub first_nonce: u64,
+    pub last_nonce: u64,
+}
+
+/// A sender whose nonces are not one consecutive run (gaps or repeats), with a proposed
+/// reassignment: consecutive from its lowest nonce, in item order.
+pub struct NonceFix {
+    pub address: Address,
+    /// (item index, nonce in the batch, proposed nonce), in item order.
+    pub items: Vec<(usize, u64, u64)>,
+}
+
+impl NonceFix {
+    /// None when `nonces` (item index, nonce; in item order) are already consecutive.
+    fn propose(address: Address, nonces: &[(usize, u64)]) -> Option<NonceFix> {
+        let mut sorted: Vec<u64> = nonces.iter().map(|(_, n)| *n).collect();
+        sorted.sort_unstable();
+        let first = *sorted.first()?;
+        if sorted.iter().zip(first..).all(|(n, want)| *n == want) {
+            return None;
+        }
+        let items = nonces.iter().zip(first..).map(|((i, n), proposed)| (*i, *n, proposed)).collect();
+        Some(NonceFix { address, items })
+    }
+}
+
+/// What a batch is about to sign, shown for confirmation before any signature is made.
+pub struct BatchSummary {
+    pub items: usize,
+    pub by_function: BTreeMap<String, usize>,
+    pub senders: Vec<SenderSummary>,
+    pub chain_ids: Vec<u64>,
+    /// One warning per (chain ID, contract) pair missing from the network registry.
+    pub network_warnings: Vec<String>,
+    /// Items that use one key for two roles (see `BatchRun::key_reuse`).
+    pub key_reuse: Option<KeyReuse>,
+    /// Items signed with a key retired on the Key Usage screen (see `BatchRun::retired_keys`).
+    pub retired: Option<RetiredKey>,
+    /// Gas limit, max fee and max priority fee per gas every item is signed with.
+    pub gas: (GasLimit, Wei, Wei),
+    /// Upper bound on fees: items × gas limit × max fee per gas (no tx carries value).
+    pub max_cost_wei: U256,
+    /// Senders whose nonces have gaps or repeats, offered for fixing before the summary.
+    pub nonce_fixes: Vec<NonceFix>,
+}
+
+impl BatchSummary {
+    /// The summary as text lines, one table row per function and per sender.
+    pub fn lines(&self, display: &DisplaySettings) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} item(s)", self.items),
+            "".to_string(),
+            format!("{:<48} {:>5}", "Function", "Items"),
+        ];
+        for (name, n) in &self.by_function {
+            lines.push(format!("{name:<48} {n:>5}"));
+        }
+        lines.push("".to_string());
+        lines.push(format!("{:<42} {:>5}  Nonces", "Sender", "Items"));
+        for s in &self.senders {
+            let nonces = if s.first_nonce == s.last_nonce {
+                s.first_nonce.to_string()
+            } else {
+                format!("{}–{}", s.first_nonce, s.last_nonce)
+            };
+            let flagged = self.nonce_fixes.iter().any(|fix| fix.address == s.address);
+            let note = if flagged { "  (gaps or repeats)" } else { "" };
+            lines.push(format!("{} {:>5}  {nonces}{note}", to_checksum(&s.address, None), s.items));
+        }
+        lines.push("".to_string());
+        let chains: Vec<String> = self.chain_ids.iter().map(u64::to_string).collect();
+        lines.push(format!("Chain ID(s): {}", chains.join(", ")));
+        let (gas_limit, max_fee, max_priority_fee) = self.gas;
+        lines.push(format!("Gas limit per item: {}", display.integer(gas_limit)));
+        lines.push(format!("Max fee per gas: {}", display.fee(max_fee.0)));
+        lines.push(format!("Max priority fee per gas: {}", display.fee(max_priority_fee.0)));
+        lines.push(format!("Total max cost: {}", display.amount(self.max_cost_wei)));
+        if let Some(reuse) = &self.key_reuse {
+            lines.push("".to_string());
+            lines.push("Key reuse:".to_string());
+            lines.extend(reuse.0.iter().cloned());
+        }
+        if let Some(retired) = &self.retired {
+            lines.push("".to_string());
+            lines.push("Retired keys:".to_string());
+            lines.extend(retired.0.iter().cloned());
+        }
+        lines
+    }
+
+    /// Banner text when items reuse a key; the findings themselves are in `lines`.
+    pub fn key_reuse_warning(&self) -> Option<String> {
+        let reuse = self.key_reuse.as_ref()?;
+        Some(format!(
+            "KEY REUSE: {} finding(s) of one key used for two roles, listed at the end of the summary. Confirm & Sign signs them anyway.",
+            reuse.0.len()
+        ))
+    }
+
+    /// Banner text when senders' nonces are not consecutive and were left that way.
+    pub fn nonce_warning(&self) -> Option<String> {
+        if self.nonce_fixes.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "NONCE GAPS OR REPEATS: {} sender(s), flagged below. A gap stalls later transactions and a repeat replaces one; Confirm & Sign signs them as they are.",
+            self.nonce_fixes.len()
+        ))
+    }
+
+    /// Banner text when items are signed with a retired key; the findings are in `lines`.
+    pub fn retired_warning(&self) -> Option<String> {
+        let retired = self.retired.as_ref()?;
+        Some(format!(
+            "RETIRED KEY: {} finding(s) of a retired key selected for signing, listed at the end of the summary. Confirm & Sign signs them anyway.",
+            retired.0.len()
+        ))
+    }
+}
+
+/// Incremental batch signer: one item per `step`, so the UI stays responsive and a quit
+/// request lands between items with every finished entry intact.
+pub struct BatchRun {
+    abi: Abi,
+    opts: BatchOpts,
+    items: Vec<Item>,
+    entries: Vec<BatchEntryOut>,
+    /// Raw input bytes, fingerprinted into the run directory name and manifest.
+    input: Vec<u8>,
+    out_dir: PathBuf,
+    /// Progress events (off unless set with `with_events`), tagged with `run_id`.
+    events: EventStream,
+    /// First 8 hex digits of the input's SHA-256, as in the run directory name.
+    run_id: String,
+    timing: RunTiming,
+}
+
+/// Where a run's time went, for `BatchRun::stats`.
+#[derive(Default)]
+struct RunTiming {
+    /// Start of the first item and end of the latest one.
+    span: Option<(Instant, Instant)>,
+    /// Over signed items only: total time, time inside the signers, signatures made.
+    items: Duration,
+    signing: Duration,
+    signatures: u64,
+    failures: usize,
+}
+
+impl BatchRun {
+    pub fn new(abi: Abi, opts: BatchOpts, items: Vec<Item>, input: Vec<u8>, out_dir: PathBuf) -> Self {
+        let run_id = sha256_hex(&input)[..8].to_string();
+        Self {
+            abi, opts, entries: Vec::with_capacity(items.len()), items, input, out_dir,
+            events: EventStream::default(), run_id, timing: RunTiming::default(),
+        }
+    }
+
+    /// Report item and file progress to `events` (see `event_stream.rs`).
+    pub fn with_events(mut self, events: EventStream) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn signed(&self) -> usize { self.entries.len() }
+    pub fn total(&self) -> usize { self.items.len() }
+    pub fn is_done(&self) -> bool { self.entries.len() >= self.items.len() }
+
+    /// Count items per function and per sender (deriving each sender address once, through
+    /// the run's signer cache) without signing anything, and check each (chain ID, contract)
+    /// pair against `networks`. Errors name the failing item's index.
+    pub fn summary(&self, networks: &Networks) -> Result<BatchSummary> {
+        let mut by_function: BTreeMap<String, usize> = BTreeMap::new();
+        let mut senders: BTreeMap<Address, SenderSummary> = BTreeMap::new();
+        let mut nonces: BTreeMap<Address, Vec<(usize, u64)>> = BTreeMap::new();
+        let mut chain_ids: Vec<u64> = Vec::new();
+        let mut network_warnings: Vec<String> = Vec::new();
+        for (i, it) in self.items.iter().enumerate() {
+            let address = tx_sender_key(it)
+                .and_then(|key| self.opts.signers.open(key))
+                .map(|signer| signer.address())
+                .with_context(|| format!("item {} ({})", i, it.function_to_call))?;
+            let nonce = it.nonce.unwrap_or_default().0;
+            *by_function.entry(it.function_to_call.clone()).or_default() += 1;
+            let s = senders.entry(address).or_insert(SenderSummary { address, items: 0, first_nonce: nonce, last_nonce: nonce });
+            s.items += 1;
+            s.first_nonce = s.first_nonce.min(nonce);
+            s.last_nonce = s.last_nonce.max(nonce);
+            nonces.entry(address).or_default().push((i, nonce));
+            let chain_id = it.chain_id.unwrap_or(31337);
+            if !chain_ids.contains(&chain_id) {
+                chain_ids.push(chain_id);
+            }
+            if let Some(w) = networks.unknown_warning(chain_id, &it.contract_address) {
+                if !network_warnings.contains(&w) {
+                    network_warnings.push(w);
+                }
+            }
+        }
+        let per_item = self.opts.gas_limit.max_cost(self.opts.max_fee_per_gas).0;
+        Ok(BatchSummary {
+            items: self.items.len(),
+            by_function,
+            senders: senders.into_values().collect(),
+            chain_ids,
+            network_warnings,
+            key_reuse: self.key_reuse(),
+            retired: self.retired_keys(),
+            gas: (self.opts.gas_limit, self.opts.max_fee_per_gas, self.opts.max_priority_fee_per_gas),
+            max_cost_wei: per_item.saturating_mul(U256::from(self.items.len())),
+            nonce_fixes: nonces.iter().filter_map(|(address, n)| NonceFix::propose(*address, n)).collect(),
+        })
+    }
+
+    /// Replace item nonces before signing, as accepted on the nonce fixup view.
+    pub fn set_nonces(&mut self, nonces: &[(usize, Nonce)]) {
+        for (i, nonce) in nonces {
+            if let Some(it) = self.items.get_mut(*i) {
+                it.nonce = Some(*nonce);
+            }
+        }
+    }
+
+    /// Every item that uses one key for two roles, each finding prefixed with its item index.
+    pub fn key_reuse(&self) -> Option<KeyReuse> {
+        let found: Vec<String> = self.items.iter().enumerate()
+            .filter_map(|(i, it)| key_reuse(it, &self.opts.signers).map(|r| (i, r)))
+            .flat_map(|(i, r)| r.0.into_iter().map(move |m| format!("Item {i}: {m}")))
+            .collect();
+        if found.is_empty() { None } else { Some(KeyReuse(found)) }
+    }
+
+    /// The operator confirmed the findings of `key_reuse`: sign those items anyway.
+    pub fn allow_key_reuse(&mut self) {
+        self.opts.allow_key_reuse = true;
+    }
+
+    /// Every item that signs with a retired key, each finding prefixed with its item index.
+    pub fn retired_keys(&self) -> Option<RetiredKey> {
+        let found: Vec<String> = self.items.iter().enumerate()
+            .filter_map(|(i, it)| retired_keys(it, &self.opts.signers, &self.opts.retired_keys).map(|r| (i, r)))
+            .flat_map(|(i, r)| r.0.into_iter().map(move |m| format!("Item {i}: {m}")))
+            .collect();
+        if found.is_empty() { None } else { Some(RetiredKey(found)) }
+    }
+
+    /// The operator confirmed the findings of `retired_keys`: sign those items anyway.
+    pub fn allow_retired_keys(&mut self) {
+        self.opts.allow_retired_key = true;
+    }
+
+    /// Sender address of every transaction signed so far, for the key usage store.
+    pub fn signed_senders(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.decoded_tx.from.as_str())
+    }
+
+    /// Contract function of every transaction signed so far, for the usage statistics.
+    pub fn signed_functions(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.decoded_tx.funcName.as_str())
+    }
+
+    /// Sign the next item. Errors name the failing item's index.
+    pub async fn step(&mut self) -> Result<()> {
+        let i = self.entries.len();
+        let Some(it) = self.items.get(i) else { return Ok(()) };
+        let (run, total, func) = (self.run_id.clone(), self.items.len(), it.function_to_call.clone());
+        self.events.emit(Event::ItemStarted { run: run.clone(), index: i, total, funcName: func.clone() });
+        let start = Instant::now();
+        let (sigs_before, signing_before) = self.opts.signers.signing_time();
+        let res = process_item(&self.abi, &self.opts, it).await;
+        let end = Instant::now();
+        let (sigs_after, signing_after) = self.opts.signers.signing_time();
+        let t = &mut self.timing;
+        t.span = Some((t.span.map_or(start, |(first, _)| first), end));
+        let entry = match res {
+            Ok(entry) => {
+                t.items += end - start;
+                t.signing += signing_after.saturating_sub(signing_before);
+                t.signatures += sigs_after - sigs_before;
+                entry
+            }
+            Err(e) => {
+                t.failures += 1;
+                let e = e.context(format!("item {} ({})", i, func));
+                self.events.emit(Event::Error { run: Some(run), index: Some(i), message: format!("{e:#}") });
+                return Err(e);
+            }
+        };
+        let tx = &entry.decoded_tx;
+        self.events.emit(Event::ItemSigned {
+            run, index: i, total, funcName: func,
+            from: tx.from.clone(), nonce: tx.nonce, chainId: tx.chainId.clone(),
+        });
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Throughput so far: items signed, failures, wall time and where it went.
+    pub fn stats(&self) -> BatchStatsOut {
+        let t = &self.timing;
+        let n = self.entries.len();
+        let total = t.span.map_or(Duration::ZERO, |(first, last)| last - first).as_secs_f64();
+        let per_item_ms = |d: Duration| if n == 0 { 0.0 } else { round3(d.as_secs_f64() * 1000.0 / n as f64) };
+        BatchStatsOut {
+            itemsSigned: n,
+            failures: t.failures,
+            totalSeconds: round3(total),
+            signatures: t.signatures,
+            signaturesPerSecond: if total > 0.0 { round3(t.signatures as f64 / total) } else { 0.0 },
+            avgItemMs: per_item_ms(t.items),
+            avgEncodeMs: per_item_ms(t.items.saturating_sub(t.signing)),
+            avgSignMs: per_item_ms(t.signing),
+        }
+    }
+
+    /// `stats` as lines for the completion message.
+    pub fn stats_lines(&self) -> Vec<String> {
+        let s = self.stats();
+        vec![
+            format!("Items signed: {}   Failures: {}", s.itemsSigned, s.failures),
+            format!("Total time: {:.2} s   {} signatures, {:.1}/s", s.totalSeconds, s.signatures, s.signaturesPerSecond),
+            format!("Per item: {:.2} ms (encode {:.2} ms, sign {:.2} ms)", s.avgItemMs, s.avgEncodeMs, s.avgSignMs),
+        ]
+    }
+
+    /// Check and write every entry as a complete run directory.
+    pub fn write_complete(&self, fs: &dyn Fs, settings: &Settings) -> Result<PathBuf> {
+        let written = self.check(settings).context("nothing written").and_then(|()| {
+            let metadata = signed_file_metadata(settings, Some(sha256_hex(&self.input)), None);
+            write_batch_run_directory(fs, &self.run_write(settings, metadata.as_ref(), None))
+                .context("failed to write batch run directory")
+        });
+        self.report(fs, &written);
+        written
+    }
+
+    /// Write the entries signed so far as a `_partial` run directory whose manifest records
+    /// the total item count and `reason`. The entries are saved even when they fail the lint or
+    /// simulation checks; the findings then go to `checks.json` in the same directory.
+    pub fn write_partial(&self, fs: &dyn Fs, settings: &Settings, reason: &str) -> Result<PathBuf> {
+        let dir = self.write_partial_run(fs, settings, reason)?;
+        self.write_checks(fs, settings, &dir)?;
+        Ok(dir)
+    }
+
+    fn write_partial_run(&self, fs: &dyn Fs, settings: &Settings, reason: &str) -> Result<PathBuf> {
+        let metadata = signed_file_metadata(settings, Some(sha256_hex(&self.input)), None);
+        let partial = Partial { total: self.items.len(), reason };
+        write_batch_run_directory(fs, &self.run_write(settings, metadata.as_ref(), Some(partial)))
+            .context("failed to write partial batch run directory")
+    }
+
+    /// Write `checks.json` into `dir` when the entries fail the lint or simulation checks.
+    fn write_checks(&self, fs: &dyn Fs, settings: &Settings, dir: &Path) -> Result<()> {
+        let Err(e) = self.check(settings) else { return Ok(()) };
+        let findings = serde_json::json!({ "findings": format!("{e:#}") });
+        let path = dir.join(BATCH_CHECKS_FILE);
+        fs.write(&path, serde_json::to_string_pretty(&findings)?.as_bytes())
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Stop here because the operator quit: save what is signed as a partial run.
+    /// Returns a line to print once the terminal is restored.
+    pub fn flush_for_quit(&self, fs: &dyn Fs, settings: &Settings) -> String {
+        if self.entries.is_empty() {
+            return "Batch stopped before any item was signed; nothing written.".to_string();
+        }
+        let (done, total) = (self.entries.len(), self.items.len());
+        let written = self.write_partial(fs, settings, "quit by operator");
+        self.report(fs, &written);
+        match written {
+            Ok(dir) => format!(
+                "Batch stopped after {done} of {total} item(s); partial run saved to {}{}",
+                dir.display(),
+                checks_note(fs, &dir),
+            ),
+            Err(e) => format!("Batch stopped after {done} of {total} item(s); partial run NOT saved: {e:#}"),
+        }
+    }
+
+    /// Signing stopped at the next item with `err`: keep the completed work as a partial run
+    /// plus `errors.json` (failed index, function and error chain) instead of discarding it.
+    /// Written even when the first item failed, so the error is on disk next to the (empty) run.
+    /// Returns the partial run directory.
+    pub fn write_failed(&self, fs: &dyn Fs, settings: &Settings, err: &anyhow::Error) -> Result<PathBuf> {
+        let failed = self.entries.len();
+        let written = self.write_partial_run(fs, settings, &format!("item {failed} failed")).and_then(|dir| {
+            let report = serde_json::json!({
+                "failedIndex": failed,
+                "functionToCall": self.items.get(failed).map(|it| it.function_to_call.as_str()),
+                "signedBeforeFailure": failed,
+                "totalItems": self.items.len(),
+                "error": format!("{err:#}"),
+            });
+            let path = dir.join(BATCH_ERRORS_FILE);
+            fs.write(&path, serde_json::to_string_pretty(&report)?.as_bytes())
+                .with_context(|| format!("writing {}", path.display()))?;
+            self.write_checks(fs, settings, &dir)?;
+            Ok(dir)
+        });
+        self.report(fs, &written);
+        written
+    }
+
+    /// Write the complete run (`write_complete`) and record the senders of its items
+    /// (`record_signed`). Returns the run directory and a warning line per store that failed.
+    pub fn finish(
+        &self,
+        fs: &dyn Fs,
+        settings: &Settings,
+        usage: &mut KeyUsage,
+        key: Option<&ProfileKey>,
+    ) -> Result<(PathBuf, Vec<String>)> {
+        let dir = self.write_complete(fs, settings)?;
+        Ok((dir, record_signed(usage, key, settings.usage_stats, Written::Run(self))))
+    }
+
+    /// After `step` failed with `err`: save the partial run (`write_failed`), record the senders
+    /// of the items signed before it and return the lines of the result modal, starting with
+    /// the error.
+    pub fn fail(
+        &self,
+        fs: &dyn Fs,
+        settings: &Settings,
+        usage: &mut KeyUsage,
+        key: Option<&ProfileKey>,
+        err: &anyhow::Error,
+    ) -> Vec<String> {
+        let mut lines = vec![format!("Error: {err:#}"), String::new()];
+        match self.write_failed(fs, settings, err) {
+            Ok(dir) => {
+                lines.extend([
+                    format!("The {} item(s) signed before the failure were saved to:", self.signed()),
+                    dir.display().to_string(),
+                    format!("See {BATCH_ERRORS_FILE} there for details{}.", checks_note(fs, &dir)),
+                ]);
+                lines.extend(record_signed(usage, key, settings.usage_stats, Written::Run(self)));
+            }
+            Err(save) => lines.push(format!("Partial output NOT saved: {save:#}")),
+        }
+        lines
+    }
+
+    /// Emit `file_written` for every file in a freshly written run directory, or `error`.
+    fn report(&self, fs: &dyn Fs, written: &Result<PathBuf>) {
+        let run = Some(self.run_id.clone());
+        match written {
+            Ok(dir) => {
+                let mut files: Vec<PathBuf> = fs.read_dir(dir).unwrap_or_default()
+                    .into_iter().filter(|e| !e.is_dir).map(|e| e.path).collect();
+                files.sort();
+                for path in files {
+                    self.events.emit(Event::file_written(run.clone(), &path));
+                }
+            }
+            Err(e) => self.events.emit(Event::Error { run, index: None, message: format!("{e:#}") }),
+        }
+    }
+
+    fn run_write<'a>(
+        &'a self,
+        settings: &'a Settings,
+        metadata: Option<&'a SignedFileMetadata>,
+        partial: Option<Partial<'a>>,
+    ) -> RunWrite<'a> {
+        RunWrite {
+            out_dir: &self.out_dir,
+            input: &self.input,
+            entries: &self.entries,
+            templates: &settings.filename_templates,
+            metadata,
+            encoder: self.opts.encoding.encoder(),
+            stats: Some(self.stats()),
+            partial,
+        }
+    }
+
+    fn check(&self, settings: &Settings) -> Result<()> {
+        lint::check_entries(&self.entries)?;
+        simulate::check_entries(settings, &self.entries)
+    }
+}
+
+/// " (…; see checks.json)" when the partial run in `dir` failed the batch checks, else "".
+fn checks_note(fs: &dyn Fs, dir: &Path) -> String {
+    if fs.exists(&dir.join(BATCH_CHECKS_FILE)) {
+        format!(" (the entries fail the batch checks; see {BATCH_CHECKS_FILE})")
+    } else {
+        String::new()
+    }
+}
+
+/// Rounded to 3 decimals, so the manifest does not carry float noise.
+fn round3(x: f64) -> f64 {
+    (x * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::abi::load_abi;
+    use crate::signing::SignerCache;
+    use crate::vfs::MemFs;
+    use crate::write_signed_transactions_to_file::BATCH_MANIFEST_FILE;
+
+    const KEYS: &str = r#""TYPE_B_PRIVKEY_X": "bff6ef95fa29fd9dfcf88dd08de1d7c4f1f9f08fd5fd7529f346446ea3661d03",
+        "TYPE_B_PRIVKEY_Y": "9942d10313bb6029acb7a35a91153ed3caae46906eb872f3fd85603d9b5f49f1",
+        "TYPE_B_UINT_X": 0, "TYPE_B_UINT_Y": 0"#;
+
+    /// A run of two revocations with the first one signed.
+    fn run_with_one_signed() -> BatchRun {
+        let item = |nonce: u64| format!(
+            r#"{{"FUNCTION_TO_CALL": "createRevocationEvent", "NONCE": {nonce}, "CHAIN_ID": 31337,
+            "CONTRACT_ADDRESS": "0x5FbDB2315678afecb367f032d93F642f64180aa3", {KEYS}}}"#
+        );
+        let raw = format!("[{}, {}]", item(0), item(1));
+        let items = parse_batch_items(raw.as_bytes(), "test").unwrap();
+        let opts = BatchOpts {
+            gas_limit: "300000".parse().unwrap(),
+            max_fee_per_gas: "30000000000".parse().unwrap(),
+            max_priority_fee_per_gas: "1000000000".parse().unwrap(),
+            payload_signing: Default::default(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: BTreeMap::new(),
+            allow_retired_key: false,
+            encoding: Default::default(),
+        };
+        let mut run = BatchRun::new(load_abi().unwrap(), opts, items, raw.into_bytes(), PathBuf::from("/out"));
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        rt.block_on(run.step()).unwrap();
+        assert_eq!((run.signed(), run.total()), (1, 2));
+        run
+    }
+
+    /// Settings whose simulation check always fails: the state file does not exist (or this
+    /// build has no simulation support).
+    fn failing_checks() -> Settings {
+        Settings { simulation_state_file: "/missing/state.json".into(), ..Default::default() }
+    }
+
+    fn files(fs: &MemFs, dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs.read_dir(dir).unwrap().into_iter()
+            .filter_map(|e| e.path.file_name().and_then(|n| n.to_str()).map(str::to_string))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn partial_run_is_saved_when_the_checks_fail() {
+        let (fs, run) = (MemFs::new(), run_with_one_signed());
+        let dir = run.write_partial(&fs, &failing_checks(), "quit by operator").unwrap();
+        assert!(dir.to_string_lossy().ends_with("_partial"), "{}", dir.display());
+        let names = files(&fs, &dir);
+        assert_eq!(names.len(), 3, "{names:?}");
+        assert!(names.contains(&BATCH_MANIFEST_FILE.to_string()) && names.contains(&BATCH_CHECKS_FILE.to_string()));
+        let checks = fs.read_to_string(&dir.join(BATCH_CHECKS_FILE)).unwrap();
+        assert!(checks.contains("findings"), "{checks}");
+    }
+
+    #[test]
+    fn failed_run_writes_errors_json_when_the_checks_fail() {
+        let (fs, run) = (MemFs::new(), run_with_one_signed());
+        let err = anyhow::anyhow!("signer unavailable");
+        let dir = run.write_failed(&fs, &failing_checks(), &err).unwrap();
+        let names = files(&fs, &dir);
+        assert!(names.contains(&BATCH_ERRORS_FILE.to_string()) && names.contains(&BATCH_CHECKS_FILE.to_string()), "{names:?}");
+        let errors = fs.read_to_string(&dir.join(BATCH_ERRORS_FILE)).unwrap();
+        assert!(errors.contains("\"failedIndex\": 1") && errors.contains("signer unavailable"), "{errors}");
+    }
+
+    #[test]
+    fn partial_run_without_findings_has_no_checks_file() {
+        let (fs, run) = (MemFs::new(), run_with_one_signed());
+        let dir = run.write_partial(&fs, &Settings::default(), "quit by operator").unwrap();
+        assert!(!fs.exists(&dir.join(BATCH_CHECKS_FILE)));
+    }
+}
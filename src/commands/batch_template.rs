@@ -0,0 +1,199 @@
+//! Batch templates: a batch file whose string values may hold `{{NAME}}` placeholders, and a
+//! values file that fills them in. Instantiating the pair gives an ordinary batch file for Sign
+//! Batch File, so a recurring batch (a quarterly key rotation) is kept as one template and only
+//! the values change between runs.
+//!
+//! Placeholders:
+//! - `{{NAME}}`: the value of `NAME`;
+//! - `{{NAME+n}}`: an integer `NAME` plus the index of the item in the output (0 for the first);
+//! - `{{NAME+k}}`: an integer `NAME` plus the constant `k`.
+//!
+//! A string that is exactly one placeholder takes the value's JSON type (`"NONCE": "{{NONCE_START+n}}"`
+//! becomes a number); a placeholder inside longer text is replaced by the value as text.
+//!
+//! The values file is a JSON object of variables. An optional `"rows"` array of objects repeats the
+//! whole template once per row, the row's variables added to (and overriding) the shared ones.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::commands::batch::parse_batch_items;
+use crate::vfs::Fs;
+
+/// Key of the values file holding per-row variables.
+pub const ROWS_KEY: &str = "rows";
+
+/// What an instantiation produced.
+pub struct Instantiated {
+    pub items: usize,
+    /// Rows in the values file (1 when it has none).
+    pub rows: usize,
+    /// Variables defined but never referenced by the template, usually a typo on one side.
+    pub unused: Vec<String>,
+}
+
+/// Variables visible to one row, and which of them the template used.
+struct Scope<'a> {
+    shared: &'a Map<String, Value>,
+    row: Option<&'a Map<String, Value>>,
+    used: &'a mut Vec<String>,
+}
+
+impl Scope<'_> {
+    fn get(&mut self, name: &str) -> Option<&Value> {
+        let v = self.row.and_then(|r| r.get(name)).or_else(|| self.shared.get(name))?;
+        if !self.used.iter().any(|u| u == name) {
+            self.used.push(name.to_string());
+        }
+        Some(v)
+    }
+}
+
+/// Value of the placeholder `expr` (the text between the braces) for output item `index`.
+fn resolve(expr: &str, index: usize, scope: &mut Scope) -> Result<Value> {
+    let (name, offset) = match expr.split_once('+') {
+        Some((name, off)) => (name.trim(), Some(off.trim())),
+        None => (expr.trim(), None),
+    };
+    if name.is_empty() {
+        bail!("empty placeholder {{{{{expr}}}}}");
+    }
+    let value = scope.get(name).ok_or_else(|| anyhow!("{{{{{expr}}}}}: {name} is not defined in the values file"))?;
+    let Some(offset) = offset else {
+        return match value {
+            Value::String(_) | Value::Number(_) | Value::Bool(_) => Ok(value.clone()),
+            _ => bail!("{{{{{expr}}}}}: {name} must be a string, number or boolean"),
+        };
+    };
+    let base = match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse::<u64>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("{{{{{expr}}}}}: {name} must be a non-negative integer to add to"))?;
+    let add = if offset == "n" {
+        index as u64
+    } else {
+        offset.parse::<u64>().map_err(|_| anyhow!("{{{{{expr}}}}}: the offset must be n or a non-negative integer"))?
+    };
+    let sum = base.checked_add(add).ok_or_else(|| anyhow!("{{{{{expr}}}}}: overflows"))?;
+    Ok(Value::from(sum))
+}
+
+fn as_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Substitute every placeholder in `s`.
+fn fill_string(s: &str, index: usize, scope: &mut Scope) -> Result<Value> {
+    let trimmed = s.trim();
+    if let Some(inner) = trimmed.strip_prefix("{{").and_then(|t| t.strip_suffix("}}")) {
+        if !inner.contains("{{") && !inner.contains("}}") {
+            return resolve(inner, index, scope);
+        }
+    }
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| anyhow!("unterminated {{{{ in \"{s}\""))?;
+        out.push_str(&as_text(&resolve(&after[..end], index, scope)?));
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(Value::String(out))
+}
+
+fn fill(v: &Value, index: usize, scope: &mut Scope) -> Result<Value> {
+    Ok(match v {
+        Value::String(s) => fill_string(s, index, scope)?,
+        Value::Array(a) => Value::Array(a.iter().map(|x| fill(x, index, scope)).collect::<Result<_>>()?),
+        Value::Object(o) => {
+            let mut m = Map::new();
+            for (k, x) in o {
+                m.insert(k.clone(), fill(x, index, scope).with_context(|| k.clone())?);
+            }
+            Value::Object(m)
+        }
+        other => other.clone(),
+    })
+}
+
+/// Expand `template` (a JSON array of items) with `values` into a concrete item array.
+pub fn instantiate(template: &Value, values: &Value) -> Result<(Vec<Value>, Instantiated)> {
+    let items = template.as_array().context("the template must be a JSON array of batch items")?;
+    if items.is_empty() {
+        bail!("the template contains no items");
+    }
+    let values = values.as_object().context("the values file must be a JSON object of variables")?;
+    let mut shared = values.clone();
+    shared.remove(ROWS_KEY);
+    let rows: Vec<Option<&Map<String, Value>>> = match values.get(ROWS_KEY) {
+        None => vec![None],
+        Some(list) => {
+            let list = list.as_array().context("\"rows\" must be an array of objects")?;
+            if list.is_empty() {
+                bail!("\"rows\" is empty, so the template would expand to nothing");
+            }
+            list.iter()
+                .enumerate()
+                .map(|(i, r)| r.as_object().map(Some).ok_or_else(|| anyhow!("row {} is not an object", i + 1)))
+                .collect::<Result<_>>()?
+        }
+    };
+
+    let mut used = Vec::new();
+    let mut out = Vec::with_capacity(items.len() * rows.len());
+    for (r, row) in rows.iter().enumerate() {
+        for (t, item) in items.iter().enumerate() {
+            let index = out.len();
+            let mut scope = Scope { shared: &shared, row: *row, used: &mut used };
+            let filled = fill(item, index, &mut scope).with_context(|| match row {
+                Some(_) => format!("template item {} (row {})", t + 1, r + 1),
+                None => format!("template item {}", t + 1),
+            })?;
+            out.push(filled);
+        }
+    }
+
+    let mut unused: Vec<String> = shared.keys().cloned().collect();
+    for row in rows.iter().flatten() {
+        unused.extend(row.keys().filter(|k| !shared.contains_key(*k)).cloned());
+    }
+    unused.sort();
+    unused.dedup();
+    unused.retain(|k| !used.contains(k));
+
+    let summary = Instantiated { items: out.len(), rows: rows.len(), unused };
+    Ok((out, summary))
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let raw = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_slice(&raw).with_context(|| format!("parsing {} as JSON", path.display()))
+}
+
+/// Instantiate the template at `template_path` with `values_path` and write the batch to
+/// `out_path`, a new file. The result is checked as a batch file before it is written.
+pub fn write_instantiated_batch(fs: &dyn Fs, template_path: &Path, values_path: &Path, out_path: &Path) -> Result<Instantiated> {
+    let template = read_json(template_path)?;
+    let values = read_json(values_path)?;
+    let (items, summary) = instantiate(&template, &values)?;
+    let body = serde_json::to_string_pretty(&items)? + "\n";
+    parse_batch_items(body.as_bytes(), "the instantiated batch")?;
+    if fs.exists(out_path) {
+        bail!("{} already exists; choose another Output File.", out_path.display());
+    }
+    if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs.create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs.create_new(out_path, body.as_bytes())
+        .with_context(|| format!("writing {}", out_path.display()))?;
+    Ok(summary)
+}
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::defaults::Defaults;
+use crate::process::normalize_pubkey_to_uncompressed_0x04;
+use crate::types::Item;
+use crate::units::Nonce;
+
+/// Read a list of pubkeys (new delegatees, or keys for the address table): one per line, blank
+/// lines and `#` comments ignored.
+/// Each key is normalized to uncompressed `0x04…`; duplicates are rejected.
+/// Returns the raw bytes as well, so callers can fingerprint the exact input.
+pub fn load_pubkey_list(path: &Path) -> Result<(Vec<u8>, Vec<String>)> {
+    let raw = fs::read(path)
+        .with_context(|| format!("reading {}", path.display()))?;
+    let text = std::str::from_utf8(&raw)
+        .with_context(|| format!("{} is not UTF-8 text", path.display()))?;
+
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for (lineno, line) in text.lines().enumerate() {
+        let t = line.trim();
+        if t.is_empty() || t.starts_with('#') {
+            continue;
+        }
+        let pubkey = normalize_pubkey_to_uncompressed_0x04(t)
+            .with_context(|| format!("line {}: invalid pubkey", lineno + 1))?;
+        if !seen.insert(pubkey.clone()) {
+            anyhow::bail!("line {}: duplicate pubkey {}", lineno + 1, pubkey);
+        }
+        out.push(pubkey);
+    }
+    if out.is_empty() {
+        anyhow::bail!("Pubkey list {} contains no pubkeys.", path.display());
+    }
+    Ok((raw, out))
+}
+
+/// Inputs shared by every re-delegation in a bulk run.
+pub struct BulkRedelegation<'a> {
+    pub redelegator_priv: &'a str,
+    /// Revoked key: privkey wins if non-empty, else pubkey (same precedence as process.rs).
+    pub revokee_priv: &'a str,
+    pub revokee_pubkey: &'a str,
+    pub require_delegatee_sig_revocation: bool,
+    /// Nonce of the first transaction; item `i` uses `start_nonce + i`.
+    pub start_nonce: u64,
+}
+
+/// One `createRevocationEventFollowedByDelegationEvent` item per delegatee pubkey,
+/// with sequential nonces. Delegatees are pubkey-only, so their signatures are zeroed.
+pub fn build_redelegation_items(params: &BulkRedelegation<'_>, delegatee_pubkeys: &[String]) -> Result<Vec<Item>> {
+    let mut items = Vec::with_capacity(delegatee_pubkeys.len());
+    for (i, pubkey) in delegatee_pubkeys.iter().enumerate() {
+        let nonce = params.start_nonce
+            .checked_add(i as u64)
+            .context("nonce overflow")?;
+        items.push(Item {
+            function_to_call: "createRevocationEventFollowedByDelegationEvent".to_string(),
+            nonce: Some(Nonce(nonce)),
+            chain_id: Some(Defaults::CHAIN_ID),
+            contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+            label: None,
+
+            // Type A (delegation side): owner = redelegator; delegatee = pubkey only
+            type_a_privkey_x: Some(params.redelegator_priv.to_string()),
+            type_a_privkey_y: Some("".to_string()),
+            type_a_pubkey_y: Some(pubkey.clone()),
+            type_a_uint_x: Some(0),
+            type_a_uint_y: Some(0),
+            type_a_boolean: Some(if params.require_delegatee_sig_revocation { "true".into() } else { "false".into() }),
+            type_a_nonce16: None,
+
+            // Type B (revocation side): owner = redelegator; revokee = (priv or pub)
+            type_b_privkey_x: Some(params.redelegator_priv.to_string()),
+            type_b_privkey_y: Some(params.revokee_priv.to_string()),
+            type_b_pubkey_y: Some(params.revokee_pubkey.to_string()),
+            type_b_uint_x: Some(0),
+            type_b_uint_y: Some(0),
+            type_b_nonce16: None,
+
+            // Type C (unused)
+            type_c_privkey_x: None,
+            type_c_nonce16: None,
+        });
+    }
+    Ok(items)
+}
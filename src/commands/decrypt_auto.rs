@@ -4,61 +4,161 @@ use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use zeroize::Zeroize;
 
-use crate::commands::decrypt_modern::try_decrypt_modern;
-use crate::commands::decrypt_pgp::try_decrypt_pgp;
+use crate::commands::decrypt_modern::{decrypt_modern_bytes, ModernDecryptError};
+use crate::commands::decrypt_pgp::decrypt_pgp_bytes;
+use crate::crypto::detect::{detect_format, Detection, EncryptionFormat};
+use crate::memory_hygiene::LockedBytes;
+
+/// A successfully decrypted file, held in memory (locked in RAM and zeroized on drop).
+pub struct DecryptedFile {
+    pub method_label: String,
+    pub plaintext: LockedBytes,
+}
 
-/// Try Modern first, then OpenPGP. Write output as:
-/// NOT_ENCRYPTED_DO_NOT_SHARE_[InputFileNameOrStem].json
-/// (if the final extension is .enc or .pgp, it is stripped before appending .json).
-///
-/// Returns (method_label, exact_output_path) on success.
-/// Returns Err if both methods fail.
-pub fn decrypt_auto(
+/// Whether `buf` looks like a file this tool can decrypt: a modern header, or an OpenPGP
+/// message (armored, or binary starting with a symmetric-key session key packet).
+pub fn looks_encrypted(buf: &[u8]) -> bool {
+    detect_format(buf) != Detection::Neither
+}
+
+/// Decrypt with the method the file's first bytes name, or try Modern, then OpenPGP when they
+/// are ambiguous. Returns the plaintext with a method label. Zeroizes `password_utf8`.
+pub fn decrypt_auto_in_memory(input_path: &Path, password_utf8: &mut Vec<u8>) -> Result<DecryptedFile> {
+    let data = read_input(input_path, password_utf8)?;
+    decrypt_auto_bytes(&data, password_utf8)
+}
+
+/// `decrypt_auto_in_memory` for a file image already read into memory, so a caller that read
+/// it through its own filesystem decrypts exactly those bytes. Zeroizes `password_utf8`.
+pub fn decrypt_auto_bytes(data: &[u8], password_utf8: &mut Vec<u8>) -> Result<DecryptedFile> {
+    decrypt_bytes_in_memory(data, password_utf8, detect_format(data).format())
+}
+
+/// Decrypt with `method`, or with both in turn when `None`. Zeroizes `password_utf8`.
+pub fn decrypt_in_memory(
     input_path: &Path,
     password_utf8: &mut Vec<u8>,
-    output_dir: &Path
-) -> Result<(String, PathBuf)> {
-    // Ensure output directory exists
-    fs::create_dir_all(output_dir)
-        .with_context(|| format!("creating directory {}", output_dir.display()))?;
+    method: Option<EncryptionFormat>,
+) -> Result<DecryptedFile> {
+    let data = read_input(input_path, password_utf8)?;
+    decrypt_bytes_in_memory(&data, password_utf8, method)
+}
 
+/// Read the file to decrypt, zeroizing `password_utf8` when that fails.
+fn read_input(input_path: &Path, password_utf8: &mut Vec<u8>) -> Result<Vec<u8>> {
+    fs::read(input_path)
+        .with_context(|| format!("reading {}", input_path.display()))
+        .inspect_err(|_| password_utf8.zeroize())
+}
+
+fn decrypt_bytes_in_memory(
+    data: &[u8],
+    password_utf8: &mut Vec<u8>,
+    method: Option<EncryptionFormat>,
+) -> Result<DecryptedFile> {
+    let result = match method {
+        Some(EncryptionFormat::Modern) => decrypt_modern(data, password_utf8).map_err(explain_modern),
+        Some(EncryptionFormat::OpenPgp) => decrypt_pgp(data, password_utf8).map_err(|e| anyhow!(
+            "OpenPGP decryption failed: {e:#}\nThe password may be wrong or the file damaged."
+        )),
+        None => decrypt_either(data, password_utf8),
+    };
+    // Zeroize the original provided password as well
+    password_utf8.zeroize();
+    result
+}
+
+/// Try Modern first, then OpenPGP.
+fn decrypt_either(data: &[u8], password_utf8: &[u8]) -> Result<DecryptedFile> {
     // Attempt 1: Modern
-    let mut pwd_modern = password_utf8.clone();
-    let modern_res = try_decrypt_modern(input_path, &mut pwd_modern);
-    pwd_modern.zeroize(); // zeroize the clone
+    let modern_err = match decrypt_modern(data, password_utf8) {
+        Ok(decrypted) => return Ok(decrypted),
+        Err(e) => e,
+    };
 
-    // On success -> write & return
-    if let Ok(plaintext) = modern_res {
-        let out_path = create_unique_path(output_dir, &derive_output_name(input_path));
-        write_file(&out_path, &plaintext)?;
-        drop(plaintext);
-        // Zeroize the original provided password as well
-        password_utf8.zeroize();
-        return Ok(("Argon2id + XChaCha20-Poly1305".to_string(), out_path));
+    // Attempt 2: OpenPGP
+    if let Ok(decrypted) = decrypt_pgp(data, password_utf8) {
+        return Ok(decrypted);
     }
 
-    // Attempt 2: OpenPGP
-    let mut pwd_pgp = password_utf8.clone();
-    let pgp_res = try_decrypt_pgp(input_path, &mut pwd_pgp);
+    // The file carried a modern header: report the specific modern failure and what to do.
+    if let Some(me) = modern_err.downcast_ref::<ModernDecryptError>() {
+        if *me != ModernDecryptError::NotModern {
+            return Err(anyhow!("{me}\n{}", me.guidance()));
+        }
+    }
+
+    Err(anyhow!(
+        "Tried both Argon2id + XChaCha20-Poly1305 and OpenPGP and couldn't decrypt with either.\n\
+         {}\n\
+         If this is an OpenPGP file, the password may be wrong or the file damaged.",
+        ModernDecryptError::NotModern.guidance()
+    ))
+}
+
+fn decrypt_modern(data: &[u8], password_utf8: &[u8]) -> Result<DecryptedFile> {
+    let mut pwd_modern = password_utf8.to_vec();
+    let modern_res = decrypt_modern_bytes(data, &mut pwd_modern);
+    pwd_modern.zeroize(); // zeroize the clone
+    let (plaintext, info) = modern_res?;
+    let method_label = match info.metadata {
+        Some(meta) if meta.key_count.is_some() => format!(
+            "Argon2id + XChaCha20-Poly1305, v{}, container of {} keys ({}), created {}",
+            info.version, meta.key_count.unwrap_or_default(), meta.key_fingerprint, meta.created_at
+        ),
+        Some(meta) => format!(
+            "Argon2id + XChaCha20-Poly1305, v{}, key {}, created {}",
+            info.version, meta.key_fingerprint, meta.created_at
+        ),
+        None => format!("Argon2id + XChaCha20-Poly1305, v{}", info.version),
+    };
+    Ok(DecryptedFile { method_label, plaintext: LockedBytes::from_vec(plaintext) })
+}
+
+fn decrypt_pgp(data: &[u8], password_utf8: &[u8]) -> Result<DecryptedFile> {
+    let mut pwd_pgp = password_utf8.to_vec();
+    let pgp_res = decrypt_pgp_bytes(data, &mut pwd_pgp);
     pwd_pgp.zeroize(); // zeroize the clone
+    Ok(DecryptedFile { method_label: "OpenPGP".to_string(), plaintext: LockedBytes::from_vec(pgp_res?) })
+}
 
-    if let Ok(plaintext) = pgp_res {
-        let out_path = create_unique_path(output_dir, &derive_output_name(input_path));
-        write_file(&out_path, &plaintext)?;
-        drop(plaintext);
-        password_utf8.zeroize();
-        return Ok(("OpenPGP".to_string(), out_path));
+/// A modern failure with what to do about it, when it is one of ours.
+fn explain_modern(e: anyhow::Error) -> anyhow::Error {
+    match e.downcast_ref::<ModernDecryptError>() {
+        Some(me) => anyhow!("{me}\n{}", me.guidance()),
+        None => e,
     }
+}
 
-    // Zeroize the original anyway before failing
-    password_utf8.zeroize();
+/// Write decrypted bytes as:
+/// CAREFUL_NOT_ENCRYPTED_[InputFileNameOrStem].json
+/// (if the final extension is .enc or .pgp, it is stripped before appending .json).
+/// `label` (e.g. a container key label) is appended to the stem when given.
+/// Never overwrites; returns the exact output path.
+pub fn write_decrypted_output(
+    input_path: &Path,
+    output_dir: &Path,
+    label: Option<&str>,
+    data: &[u8],
+) -> Result<PathBuf> {
+    // Ensure output directory exists
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("creating directory {}", output_dir.display()))?;
 
-    Err(anyhow!(
-        "Tried both Argon2id + XChaCha20-Poly1305 and OpenPGP and couldn't decrypt with either."
-    ))
+    let mut name = derive_output_name(input_path);
+    if let Some(label) = label {
+        let safe: String = label
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        name = format!("{}_{}.json", name.trim_end_matches(".json"), safe);
+    }
+    let out_path = create_unique_path(output_dir, &name);
+    write_file(&out_path, data)?;
+    Ok(out_path)
 }
 
-/// Build: NOT_ENCRYPTED_DO_NOT_SHARE_[InputFileName].json
+/// Build: CAREFUL_NOT_ENCRYPTED_[InputFileName].json
 /// If the *final* extension is ".enc" or ".pgp" (case-insensitive), strip it before adding ".json".
 fn derive_output_name(input_path: &Path) -> String {
     let fname = input_path
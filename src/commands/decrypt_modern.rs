@@ -1,13 +1,20 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Result};
 use argon2::{Algorithm, Argon2, Params, Version};
 use chacha20poly1305::aead::{Aead, Payload};
 use chacha20poly1305::{KeyInit, XChaCha20Poly1305};
-use std::fs;
-use std::path::Path;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 use zeroize::Zeroize;
 
-const VERSION_EXPECTED: u8 = 1;
+use crate::crypto::factors::KeyFactors;
+use crate::crypto::modern::{KeyFileMetadata, KdfParams, V2_MAC_LEN};
+use crate::memory_hygiene::LockedBytes;
+
+const VERSION_V1: u8 = 1;
+const VERSION_V2: u8 = 2;
 const KDF_ID_ARGON2ID: u8 = 1;
+/// Upper bound for a sane Argon2 memory cost (4 GiB); larger values mean a damaged header.
+const MAX_M_COST_KIB: u32 = 4 * 1024 * 1024;
 
 #[derive(Debug)]
 struct Header {
@@ -19,55 +26,152 @@ struct Header {
     p_cost: u8,
     salt: Vec<u8>,
     nonce: Vec<u8>,        // must be 24 bytes for XChaCha20-Poly1305
+    metadata: Option<Vec<u8>>, // v2 only: raw metadata JSON (part of the AAD)
     offset_after_header: usize,
 }
 
+impl Header {
+    /// Bytes after the header reserved for the outer MAC (v2 only).
+    fn trailer_len(&self) -> usize {
+        if self.version == VERSION_V2 { V2_MAC_LEN } else { 0 }
+    }
+
+    /// Length of the optional noise prefix in front of the version byte (0 or 8).
+    fn prefix_len(&self) -> usize {
+        let meta = self.metadata.as_ref().map(|m| 2 + m.len()).unwrap_or(0);
+        self.offset_after_header - (13 + self.salt.len() + self.nonce.len() + meta)
+    }
+}
+
+/// What a successful modern decrypt learned about the file, besides the plaintext.
+#[derive(Debug, Clone)]
+pub struct ModernFileInfo {
+    pub version: u8,
+    pub kdf: KdfParams,
+    pub has_noise_prefix: bool,
+    pub metadata: Option<KeyFileMetadata>,
+}
+
+/// Why a modern-format decrypt failed, each with user-facing guidance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModernDecryptError {
+    /// No modern header at offset 0 or 8; probably another format.
+    NotModern,
+    /// Header marker found, but written by a newer/unknown format version.
+    UnsupportedVersion(u8),
+    /// Header marker found, but with an unknown KDF identifier.
+    UnsupportedKdf(u8),
+    /// Header starts correctly but a field is missing or out of range.
+    CorruptedHeader(String),
+    /// Header is intact but the ciphertext is shorter than the 16-byte tag.
+    TruncatedCiphertext(usize),
+    /// AEAD tag check failed: wrong password, or the file was modified.
+    WrongPasswordOrTampered,
+}
+
+impl ModernDecryptError {
+    /// What the user should try next.
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            ModernDecryptError::NotModern =>
+                "The file does not start with a modern (Argon2id + XChaCha20-Poly1305) header.",
+            ModernDecryptError::UnsupportedVersion(_) | ModernDecryptError::UnsupportedKdf(_) =>
+                "The file was likely written by a newer release; decrypt it with that version.",
+            ModernDecryptError::CorruptedHeader(_) =>
+                "The header is damaged. Restore the file from a backup copy; use V in the file list to inspect it.",
+            ModernDecryptError::TruncatedCiphertext(_) =>
+                "The file ends early (incomplete copy?). Re-copy it from the source medium or a backup.",
+            ModernDecryptError::WrongPasswordOrTampered =>
+                "Check the password (Caps Lock, keyboard layout). If it is certainly right, the file was modified; use a backup.",
+        }
+    }
+}
+
+impl std::fmt::Display for ModernDecryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModernDecryptError::NotModern => write!(f, "Not a recognized modern-format file."),
+            ModernDecryptError::UnsupportedVersion(v) =>
+                write!(f, "Unsupported modern format version {v} (expected {VERSION_V1} or {VERSION_V2})."),
+            ModernDecryptError::UnsupportedKdf(k) =>
+                write!(f, "Unsupported KDF id {k} (expected {KDF_ID_ARGON2ID} = Argon2id)."),
+            ModernDecryptError::CorruptedHeader(why) => write!(f, "Corrupted modern header: {why}."),
+            ModernDecryptError::TruncatedCiphertext(n) =>
+                write!(f, "Ciphertext truncated: only {n} byte(s) follow the header."),
+            ModernDecryptError::WrongPasswordOrTampered =>
+                write!(f, "Wrong password, or the file was tampered with (authentication tag mismatch)."),
+        }
+    }
+}
+
+impl std::error::Error for ModernDecryptError {}
+
+/// Take `n` bytes at `*i` or report which header field is cut off.
+fn take<'a>(buf: &'a [u8], i: &mut usize, n: usize, field: &str) -> Result<&'a [u8], ModernDecryptError> {
+    if buf.len() < *i + n {
+        return Err(ModernDecryptError::CorruptedHeader(format!(
+            "file ends inside the {field} field (offset {})", *i
+        )));
+    }
+    let out = &buf[*i..*i + n];
+    *i += n;
+    Ok(out)
+}
+
 /// Attempt to parse a header at the given offset (0 or 8).
-fn try_parse_header_at(buf: &[u8], off: usize) -> Option<Header> {
-    if buf.len() < off + 2 { return None; }
+/// Only a matching version + KDF id marker counts as "this is a modern file";
+/// after that every failure is reported as a specific header problem.
+fn try_parse_header_at(buf: &[u8], off: usize) -> Result<Header, ModernDecryptError> {
+    if buf.len() < off + 2 { return Err(ModernDecryptError::NotModern); }
 
     let version = buf[off];
     let kdf_id  = buf[off + 1];
-    if version != VERSION_EXPECTED || kdf_id != KDF_ID_ARGON2ID {
-        return None;
+    if !(version == VERSION_V1 || version == VERSION_V2) || kdf_id != KDF_ID_ARGON2ID {
+        return Err(ModernDecryptError::NotModern);
     }
 
     let mut i = off + 2;
 
     // u32 t_cost (LE)
-    if buf.len() < i + 4 { return None; }
-    let t_cost = u32::from_le_bytes(buf[i..i + 4].try_into().ok()?);
-    i += 4;
-
+    let t_cost = u32::from_le_bytes(take(buf, &mut i, 4, "t_cost")?.try_into().unwrap());
     // u32 m_cost_kib (LE)
-    if buf.len() < i + 4 { return None; }
-    let m_cost_kib = u32::from_le_bytes(buf[i..i + 4].try_into().ok()?);
-    i += 4;
-
+    let m_cost_kib = u32::from_le_bytes(take(buf, &mut i, 4, "m_cost_kib")?.try_into().unwrap());
     // u8 p_cost
-    if buf.len() < i + 1 { return None; }
-    let p_cost = buf[i];
-    i += 1;
+    let p_cost = take(buf, &mut i, 1, "p_cost")?[0];
+
+    if t_cost == 0 || p_cost == 0 || m_cost_kib == 0 || m_cost_kib > MAX_M_COST_KIB {
+        return Err(ModernDecryptError::CorruptedHeader(format!(
+            "Argon2 parameters out of range (t_cost={t_cost}, m_cost_kib={m_cost_kib}, p_cost={p_cost})"
+        )));
+    }
 
     // u8 salt_len, then salt
-    if buf.len() < i + 1 { return None; }
-    let salt_len = buf[i] as usize;
-    i += 1;
-    if buf.len() < i + salt_len { return None; }
-    let salt = buf[i..i + salt_len].to_vec();
-    i += salt_len;
+    let salt_len = take(buf, &mut i, 1, "salt_len")?[0] as usize;
+    if salt_len < 8 {
+        return Err(ModernDecryptError::CorruptedHeader(format!("salt length {salt_len} is too short")));
+    }
+    let salt = take(buf, &mut i, salt_len, "salt")?.to_vec();
 
     // u8 nonce_len, then nonce
-    if buf.len() < i + 1 { return None; }
-    let nonce_len = buf[i] as usize;
-    i += 1;
-    if buf.len() < i + nonce_len { return None; }
-    let nonce = buf[i..i + nonce_len].to_vec();
-    i += nonce_len;
+    let nonce_len = take(buf, &mut i, 1, "nonce_len")?[0] as usize;
+    if nonce_len != 24 {
+        return Err(ModernDecryptError::CorruptedHeader(format!(
+            "nonce length {nonce_len} (expected 24)"
+        )));
+    }
+    let nonce = take(buf, &mut i, nonce_len, "nonce")?.to_vec();
+
+    // v2: u16 meta_len (LE), then metadata JSON
+    let metadata = if version == VERSION_V2 {
+        let meta_len = u16::from_le_bytes(take(buf, &mut i, 2, "meta_len")?.try_into().unwrap()) as usize;
+        Some(take(buf, &mut i, meta_len, "metadata")?.to_vec())
+    } else {
+        None
+    };
 
     let header_full = buf[..i].to_vec(); // AAD is *entire* prefix up to end of parsed header
 
-    Some(Header {
+    Ok(Header {
         header_full,
         version,
         kdf_id,
@@ -76,40 +180,142 @@ fn try_parse_header_at(buf: &[u8], off: usize) -> Option<Header> {
         p_cost,
         salt,
         nonce,
+        metadata,
         offset_after_header: i,
     })
 }
 
 /// Parse the header, trying without noise first, then with 8-byte noise.
-fn parse_header(buf: &[u8]) -> Option<Header> {
-    if let Some(h) = try_parse_header_at(buf, 0) {
-        return Some(h);
+/// If neither offset carries the marker, look for a near-miss (right KDF id with
+/// another version, or right version with another KDF) to report it specifically.
+fn parse_header(buf: &[u8]) -> Result<Header, ModernDecryptError> {
+    let mut specific: Option<ModernDecryptError> = None;
+    for off in [0usize, 8] {
+        match try_parse_header_at(buf, off) {
+            Ok(h) => return Ok(h),
+            Err(ModernDecryptError::NotModern) => {}
+            Err(e) => { specific.get_or_insert(e); }
+        }
+    }
+    if let Some(e) = specific {
+        return Err(e);
     }
-    if buf.len() >= 10 {
-        if let Some(h) = try_parse_header_at(buf, 8) {
-            return Some(h);
+
+    for off in [0usize, 8] {
+        if buf.len() < off + 2 { continue; }
+        let (version, kdf_id) = (buf[off], buf[off + 1]);
+        if kdf_id == KDF_ID_ARGON2ID && (3..=15).contains(&version) {
+            return Err(ModernDecryptError::UnsupportedVersion(version));
+        }
+        if (version == VERSION_V1 || version == VERSION_V2) && (2..=15).contains(&kdf_id) {
+            return Err(ModernDecryptError::UnsupportedKdf(kdf_id));
         }
     }
-    None
+    Err(ModernDecryptError::NotModern)
+}
+
+/// A labelled byte range inside a modern-format file (for the hex viewer).
+#[derive(Debug, Clone)]
+pub struct HeaderField {
+    pub label: &'static str,
+    pub range: std::ops::Range<usize>,
+}
+
+/// Header layout of a modern-format file: a one-line summary plus field ranges.
+#[derive(Debug, Clone)]
+pub struct HeaderAnnotation {
+    pub summary: String,
+    pub fields: Vec<HeaderField>,
+}
+
+/// Locate the modern-format header fields in `buf`, or None if no header parses.
+/// Ranges cover the optional noise prefix, every header field, the ciphertext
+/// and the trailing 16-byte Poly1305 tag (when the file is long enough).
+pub fn annotate_modern_header(buf: &[u8]) -> Option<HeaderAnnotation> {
+    let h = parse_header(buf).ok()?;
+    let start = h.prefix_len();
+
+    let mut fields = Vec::new();
+    let mut push = |label: &'static str, len: usize, at: &mut usize| {
+        fields.push(HeaderField { label, range: *at..*at + len });
+        *at += len;
+    };
+
+    let mut at = 0usize;
+    if start > 0 { push("noise prefix", start, &mut at); }
+    push("version", 1, &mut at);
+    push("kdf id", 1, &mut at);
+    push("t_cost", 4, &mut at);
+    push("m_cost_kib", 4, &mut at);
+    push("p_cost", 1, &mut at);
+    push("salt_len", 1, &mut at);
+    push("salt", h.salt.len(), &mut at);
+    push("nonce_len", 1, &mut at);
+    push("nonce", h.nonce.len(), &mut at);
+    if let Some(meta) = h.metadata.as_ref() {
+        push("meta_len", 2, &mut at);
+        push("metadata", meta.len(), &mut at);
+    }
+
+    let body = buf.len() - h.offset_after_header;
+    let trailer = h.trailer_len();
+    if body >= 16 + trailer {
+        push("ciphertext", body - 16 - trailer, &mut at);
+        push("tag", 16, &mut at);
+        if trailer > 0 { push("mac", trailer, &mut at); }
+    } else if body > 0 {
+        push("ciphertext (truncated)", body, &mut at);
+    }
+
+    let summary = format!(
+        "Modern format v{} (kdf {}): Argon2id t={} m={} KiB p={}, salt {} B, nonce {} B{}",
+        h.version, h.kdf_id, h.t_cost, h.m_cost_kib, h.p_cost,
+        h.salt.len(), h.nonce.len(),
+        if start > 0 { ", with 8-byte noise prefix" } else { "" },
+    );
+
+    Some(HeaderAnnotation { summary, fields })
+}
+
+/// Whether `buf` starts with a modern header, including damaged or newer ones that decryption
+/// reports specifically.
+pub fn has_modern_header(buf: &[u8]) -> bool {
+    !matches!(parse_header(buf), Err(ModernDecryptError::NotModern))
+}
+
+/// Argon2id `(m_cost_kib, t_cost)` from a modern-format header, for a progress estimate.
+pub fn modern_kdf_costs(buf: &[u8]) -> Option<(u32, u32)> {
+    parse_header(buf).ok().map(|h| (h.m_cost_kib, h.t_cost))
 }
 
-pub fn try_decrypt_modern(input_path: &Path, password_utf8: &mut Vec<u8>) -> Result<Vec<u8>> {
-    let data = fs::read(input_path)
-        .with_context(|| format!("reading {}", input_path.display()))?;
+/// Factors a modern-format file needs besides the password, read from its v2 metadata before
+/// decryption (editing them only makes the file fail to decrypt). Empty for v1 and other formats.
+pub fn modern_key_factors(buf: &[u8]) -> KeyFactors {
+    parse_header(buf).ok()
+        .and_then(|h| h.metadata)
+        .and_then(|raw| serde_json::from_slice::<KeyFileMetadata>(&raw).ok())
+        .map(|meta| meta.factors)
+        .unwrap_or_default()
+}
 
-    let header = parse_header(&data)
-        .ok_or_else(|| anyhow!("Not a recognized modern header (version/kdf/structure mismatch)."))?;
+/// Decrypt a complete modern-format file image (v1 or v2) held in memory.
+/// For v2 the outer HMAC is verified before the AEAD is attempted.
+/// Zeroizes `password_utf8` on success.
+pub fn decrypt_modern_bytes(data: &[u8], password_utf8: &mut Vec<u8>) -> Result<(Vec<u8>, ModernFileInfo)> {
+    let header = parse_header(data)?;
 
-    if header.nonce.len() != 24 {
-        return Err(anyhow!(
-            "Unexpected nonce length {} (expected 24).",
-            header.nonce.len()
-        ));
+    let body = &data[header.offset_after_header..];
+    let trailer = header.trailer_len();
+    if body.len() <= 16 + trailer {
+        return Err(ModernDecryptError::TruncatedCiphertext(body.len()).into());
     }
+    let (ciphertext, mac_tag) = body.split_at(body.len() - trailer);
 
-    // Derive key via Argon2id (p_cost is u8 in this format)
-    let params = Params::new(header.m_cost_kib, header.t_cost, header.p_cost as u32, None)
-        .map_err(|e| anyhow!("invalid Argon2 params: {e}"))?;
+    // Derive key via Argon2id (p_cost is u8 in this format).
+    // v1: 32-byte AEAD key. v2: 64 bytes = AEAD key || MAC key.
+    let okm_len = if header.version == VERSION_V2 { 64 } else { 32 };
+    let params = Params::new(header.m_cost_kib, header.t_cost, header.p_cost as u32, Some(okm_len))
+        .map_err(|e| ModernDecryptError::CorruptedHeader(format!("invalid Argon2 params: {e}")))?;
 
     let argon = Argon2::new_with_secret(
         &[], // no secret
@@ -118,31 +324,50 @@ pub fn try_decrypt_modern(input_path: &Path, password_utf8: &mut Vec<u8>) -> Res
         params,
     ).map_err(|e| anyhow!("Argon2 ctor failed: {e}"))?;
 
-    let mut key = [0u8; 32];
-    argon.hash_password_into(password_utf8, &header.salt, &mut key)
+    let mut okm = LockedBytes::zeroed(okm_len);
+    argon.hash_password_into(password_utf8, &header.salt, &mut okm)
         .map_err(|e| anyhow!("Argon2 hash_password_into failed: {e}"))?;
 
+    // v2: outer integrity MAC over header || ciphertext
+    if trailer > 0 {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&okm[32..])
+            .map_err(|e| anyhow!("HMAC key: {e}"))?;
+        mac.update(&data[..data.len() - trailer]);
+        if mac.verify_slice(mac_tag).is_err() {
+            return Err(ModernDecryptError::WrongPasswordOrTampered.into());
+        }
+    }
+
     // Decrypt with AAD = exact header bytes (including optional noise prefix)
-    let cipher = XChaCha20Poly1305::new((&key).into());
+    let cipher = XChaCha20Poly1305::new(okm[..32].into());
     let nonce = chacha20poly1305::XNonce::from_slice(&header.nonce);
 
-    let ciphertext = &data[header.offset_after_header..];
-    if ciphertext.len() < 16 {
-        key.zeroize();
-        return Err(anyhow!("ciphertext too short (missing tag)"));
-    }
-
-    let plaintext = cipher.decrypt(
+    let decrypted = cipher.decrypt(
         nonce,
         Payload {
             aad: &header.header_full,
             msg: ciphertext,
         },
-    ).map_err(|_| anyhow!("Modern decrypt failed (wrong password? tampered? params mismatch?)."))?;
+    );
+    drop(okm);
+    let plaintext = decrypted.map_err(|_| ModernDecryptError::WrongPasswordOrTampered)?;
 
     // Zeroize sensitive material
-    key.zeroize();
     password_utf8.zeroize();
 
-    Ok(plaintext)
+    // v2 metadata is covered by the AAD/MAC, so only trust it after decryption
+    let metadata = match header.metadata.as_ref() {
+        Some(raw) => Some(serde_json::from_slice::<KeyFileMetadata>(raw).map_err(|e| {
+            ModernDecryptError::CorruptedHeader(format!("metadata is not valid JSON ({e})"))
+        })?),
+        None => None,
+    };
+
+    let info = ModernFileInfo {
+        version: header.version,
+        kdf: KdfParams { t_cost: header.t_cost, m_cost_kib: header.m_cost_kib, p_cost: header.p_cost },
+        has_noise_prefix: header.prefix_len() > 0,
+        metadata,
+    };
+    Ok((plaintext, info))
 }
@@ -3,14 +3,13 @@ use sequoia_openpgp as openpgp;
 
 use openpgp::crypto::{Password, SessionKey};
 use openpgp::packet::{PKESK, SKESK};
-use openpgp::parse::Parse; // brings DecryptorBuilder::from_reader into scope
+use openpgp::parse::Parse; // brings DecryptorBuilder::from_bytes into scope
 use openpgp::parse::stream::{DecryptorBuilder, DecryptionHelper, MessageStructure, VerificationHelper};
 use openpgp::policy::StandardPolicy;
 use openpgp::{Fingerprint, KeyHandle}; // <-- FIX: from crate root
 use openpgp::types::SymmetricAlgorithm;
 
-use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::Read;
 
 /// Helper that supplies the passphrase for SKESK (symmetric) packets.
 struct SymmetricHelper {
@@ -52,20 +51,16 @@ impl VerificationHelper for SymmetricHelper {
     }
 }
 
-/// Attempt to decrypt an OpenPGP symmetrically-encrypted file (SKESK) using Sequoia (pure Rust).
-/// Returns plaintext bytes on success, or Err if the file is not PGP or the password is wrong.
-pub fn try_decrypt_pgp(input_path: &std::path::Path, password_utf8: &mut Vec<u8>) -> Result<Vec<u8>> {
-    let f = File::open(input_path).with_context(|| format!("opening {}", input_path.display()))?;
-    let mut reader = BufReader::new(f);
-
+/// Attempt to decrypt an OpenPGP symmetrically-encrypted message (SKESK) held in memory, using
+/// Sequoia (pure Rust). Returns plaintext bytes on success, or Err if it is not PGP or the
+/// password is wrong.
+pub fn decrypt_pgp_bytes(data: &[u8], password_utf8: &mut Vec<u8>) -> Result<Vec<u8>> {
     let policy = &StandardPolicy::new();
 
-    // Build the streaming decryptor with our helper. If your version prefers it,
-    // replace `from_reader` with `from_buffered_reader`.
     let helper = SymmetricHelper {
         password: Password::from(password_utf8.clone()),
     };
-    let mut decryptor = DecryptorBuilder::from_reader(&mut reader)?
+    let mut decryptor = DecryptorBuilder::from_bytes(data)?
         .with_policy(policy, None, helper)?;
 
     let mut out = Vec::new();
@@ -0,0 +1,236 @@
+//! Emergency revoke: after a delegate key is compromised, find every delegation to it in the
+//! signed files kept here, and build the revocations (or re-delegations to a replacement key)
+//! that undo them.
+//!
+//! Delegations are read from the signed transactions themselves, decoded like Clone Signed File
+//! does, so an edited `decodedTx` block cannot hide or invent one. The delegation half of a
+//! re-delegation counts as a delegation. Only this machine's files are searched: a delegation
+//! signed elsewhere is not found.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ethers_core::utils::to_checksum;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::abi::load_abi;
+use crate::commands::recovery::output_dirs;
+use crate::decoder::decode_calldata_to_json;
+use crate::process::normalize_pubkey_to_uncompressed_0x04;
+use crate::signing::{decode_signed_tx_and_recover, open_signer};
+use crate::types::{DecodedOne, Item};
+use crate::units::Nonce;
+use crate::vfs::Fs;
+
+/// The compromised key as entered: a public key, or a private key (then its signature can be
+/// added where a revocation needs the delegatee's).
+pub struct CompromisedKey {
+    pub pubkey: String,
+    pub privkey: Option<String>,
+}
+
+impl CompromisedKey {
+    /// A pubkey in any form `normalize_pubkey_to_uncompressed_0x04` takes, else a private key.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            bail!("Compromised Key cannot be empty.");
+        }
+        if let Ok(pubkey) = normalize_pubkey_to_uncompressed_0x04(input) {
+            return Ok(Self { pubkey, privkey: None });
+        }
+        let signer = open_signer(input).context("Compromised Key is neither a public nor a private key")?;
+        Ok(Self { pubkey: signer.pubkey_0x04().to_lowercase(), privkey: Some(input.to_string()) })
+    }
+}
+
+/// A delegator's delegation(s) to one delegatee on one chain and contract.
+#[derive(Clone, Debug)]
+pub struct FoundDelegation {
+    pub delegator: String,
+    pub delegatee: String,
+    pub chain_id: u64,
+    /// Checksummed.
+    pub contract: String,
+    /// Any of the delegations requires the delegatee's signature to be revoked.
+    pub require_delegatee_sig: bool,
+    /// A revocation of the same pair is also on file. Files do not show whether it was broadcast.
+    pub revoked_on_file: bool,
+    pub sources: Vec<PathBuf>,
+}
+
+/// Where to look: the output directories and the run directories one level below them.
+pub fn search_dirs(fs: &dyn Fs) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for dir in output_dirs() {
+        let Ok(entries) = fs.read_dir(&dir) else { continue };
+        dirs.push(dir);
+        dirs.extend(entries.into_iter().filter(|e| e.is_dir).map(|e| e.path));
+    }
+    dirs
+}
+
+/// Every delegation to `compromised` in the signed files directly inside `dirs`, one per
+/// delegator, chain and contract, sorted. Files that are not signed transaction files are skipped.
+pub fn find_delegations(fs: &dyn Fs, dirs: &[PathBuf], compromised: &str) -> Result<Vec<FoundDelegation>> {
+    scan(fs, dirs, |_, delegatee| same_key(delegatee, compromised))
+}
+
+/// Every delegation issued by `delegator` in the signed files directly inside `dirs`, one per
+/// delegatee, chain and contract, sorted.
+pub fn find_delegations_by(fs: &dyn Fs, dirs: &[PathBuf], delegator: &str) -> Result<Vec<FoundDelegation>> {
+    scan(fs, dirs, |from, _| same_key(from, delegator))
+}
+
+/// The delegations for which `wanted(delegator, delegatee)` holds, each marked when a
+/// revocation of the same pair, chain and contract is also on file.
+fn scan(fs: &dyn Fs, dirs: &[PathBuf], wanted: impl Fn(&str, &str) -> bool) -> Result<Vec<FoundDelegation>> {
+    let abi = load_abi()?;
+    let mut found: Vec<FoundDelegation> = Vec::new();
+    // (revoker, revokee, chain, contract) of every revocation of a wanted pair
+    let mut revoked: BTreeSet<(String, String, u64, String)> = BTreeSet::new();
+
+    let mut seen = BTreeSet::new();
+    for dir in dirs {
+        let Ok(entries) = fs.read_dir(dir) else { continue };
+        for entry in entries.into_iter().filter(|e| !e.is_dir) {
+            if !seen.insert(entry.path.clone()) {
+                continue;
+            }
+            for (chain_id, contract, decoded) in read_signed(fs, &abi, &entry.path) {
+                match decoded {
+                    DecodedOne::Delegation(d) if wanted(&d.delegatorPubkey, &d.delegateePubkey) => {
+                        let delegator = d.delegatorPubkey.to_lowercase();
+                        let delegatee = d.delegateePubkey.to_lowercase();
+                        match found.iter_mut().find(|f| {
+                            f.delegator == delegator && f.delegatee == delegatee && f.chain_id == chain_id && f.contract == contract
+                        }) {
+                            Some(f) => {
+                                f.require_delegatee_sig |= d.doesRevocationRequireDelegateeSignature;
+                                if !f.sources.contains(&entry.path) {
+                                    f.sources.push(entry.path.clone());
+                                }
+                            }
+                            None => found.push(FoundDelegation {
+                                delegator,
+                                delegatee,
+                                chain_id,
+                                contract: contract.clone(),
+                                require_delegatee_sig: d.doesRevocationRequireDelegateeSignature,
+                                revoked_on_file: false,
+                                sources: vec![entry.path.clone()],
+                            }),
+                        }
+                    }
+                    DecodedOne::Revocation(r) if wanted(&r.revokerPubkey, &r.revokeePubkey) => {
+                        revoked.insert((r.revokerPubkey.to_lowercase(), r.revokeePubkey.to_lowercase(), chain_id, contract.clone()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    for f in &mut found {
+        f.revoked_on_file = revoked.contains(&(f.delegator.clone(), f.delegatee.clone(), f.chain_id, f.contract.clone()));
+    }
+    found.sort_by(|a, b| {
+        (&a.delegator, &a.delegatee, a.chain_id, &a.contract).cmp(&(&b.delegator, &b.delegatee, b.chain_id, &b.contract))
+    });
+    Ok(found)
+}
+
+fn same_key(decoded: &str, normalized: &str) -> bool {
+    decoded.eq_ignore_ascii_case(normalized)
+}
+
+/// Chain, checksummed contract and decoded payloads of each transaction in a signed file;
+/// empty when it is not one.
+fn read_signed(fs: &dyn Fs, abi: &ethers_core::abi::Abi, path: &Path) -> Vec<(u64, String, DecodedOne)> {
+    let Ok(bytes) = fs.read(path) else { return Vec::new() };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&bytes) else { return Vec::new() };
+    // Versioned envelope or legacy bare array
+    let Some(entries) = json.get("entries").unwrap_or(&json).as_array() else { return Vec::new() };
+    let mut out = Vec::new();
+    for entry in entries {
+        let Some(signed_tx) = entry["signedTx"].as_str() else { continue };
+        let Ok((chain_id, _, _, _, _, to, _, data, _)) = decode_signed_tx_and_recover(signed_tx) else { continue };
+        let Ok((_, one, two)) = decode_calldata_to_json(abi, &data) else { continue };
+        let contract = to_checksum(&to, None);
+        out.extend([one, two].into_iter().flatten().map(|d| (chain_id, contract.clone(), d)));
+    }
+    out
+}
+
+/// Inputs shared by every transaction of an emergency revoke.
+pub struct EmergencyRevoke<'a> {
+    pub compromised: &'a CompromisedKey,
+    /// Private key of the delegator whose delegations are undone.
+    pub delegator_priv: &'a str,
+    /// Re-delegate to this pubkey (pubkey only, so its signature is zeroed) instead of only revoking.
+    pub replacement_pubkey: Option<&'a str>,
+    /// Nonce of the first transaction; item `i` uses `start_nonce + i`.
+    pub start_nonce: u64,
+}
+
+/// One revocation (or re-delegation) per found delegation, in order, with sequential nonces.
+/// All of them are sent from the delegator's account, so they must be on one chain.
+pub fn build_emergency_items(params: &EmergencyRevoke<'_>, found: &[FoundDelegation]) -> Result<Vec<Item>> {
+    let chains: BTreeSet<u64> = found.iter().map(|f| f.chain_id).collect();
+    if chains.len() > 1 {
+        let list: Vec<String> = chains.iter().map(u64::to_string).collect();
+        bail!(
+            "The delegations are on chains {}; nonces are per chain, so enter a Chain ID and run once per chain.",
+            list.join(", ")
+        );
+    }
+    if params.compromised.privkey.is_none() {
+        if let Some(f) = found.iter().find(|f| f.require_delegatee_sig) {
+            bail!(
+                "The delegation on chain {} at {} can only be revoked with the compromised key's signature; enter its private key as Compromised Key.",
+                f.chain_id, f.contract
+            );
+        }
+    }
+    let revokee_priv = params.compromised.privkey.clone().unwrap_or_default();
+
+    let mut items = Vec::with_capacity(found.len());
+    for (i, f) in found.iter().enumerate() {
+        let nonce = params.start_nonce
+            .checked_add(i as u64)
+            .ok_or_else(|| anyhow!("nonce overflow"))?;
+        let (function, type_a_pubkey_y) = match params.replacement_pubkey {
+            Some(pubkey) => ("createRevocationEventFollowedByDelegationEvent", Some(pubkey.to_string())),
+            None => ("createRevocationEvent", None),
+        };
+        let delegation = type_a_pubkey_y.is_some();
+        items.push(Item {
+            function_to_call: function.to_string(),
+            nonce: Some(Nonce(nonce)),
+            chain_id: Some(f.chain_id),
+            contract_address: f.contract.clone(),
+            label: None,
+
+            // Type A (re-delegation only): owner = delegator; new delegatee = pubkey only,
+            // with the same revocation rule as the delegation it replaces
+            type_a_privkey_x: delegation.then(|| params.delegator_priv.to_string()),
+            type_a_privkey_y: delegation.then(String::new),
+            type_a_pubkey_y,
+            type_a_uint_x: delegation.then_some(0),
+            type_a_uint_y: delegation.then_some(0),
+            type_a_boolean: delegation.then(|| f.require_delegatee_sig.to_string()),
+            type_a_nonce16: None,
+
+            // Type B (revocation): owner = delegator; revokee = the compromised key
+            type_b_privkey_x: Some(params.delegator_priv.to_string()),
+            type_b_privkey_y: Some(revokee_priv.clone()),
+            type_b_pubkey_y: Some(params.compromised.pubkey.clone()),
+            type_b_uint_x: Some(0),
+            type_b_uint_y: Some(0),
+            type_b_nonce16: None,
+
+            // Type C (unused)
+            type_c_privkey_x: None,
+            type_c_nonce16: None,
+        });
+    }
+    Ok(items)
+}
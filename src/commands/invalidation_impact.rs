@@ -0,0 +1,118 @@
+//! Impact preview for a permanent invalidation: the delegations on file that the key issued or
+//! received and that no revocation on file undoes. An invalidated key signs nothing more, so
+//! revocations of its own delegations can be queued ahead of the invalidation, in the same run.
+//!
+//! The search is Emergency Revoke's (`emergency_revoke.rs`): signed files in the output
+//! directories and their run directories, decoded from `signedTx`. Files only show what was
+//! signed here, not what was broadcast.
+
+use anyhow::{anyhow, Result};
+
+use crate::commands::emergency_revoke::{find_delegations, find_delegations_by, search_dirs, FoundDelegation};
+use crate::process::short_key;
+use crate::types::Item;
+use crate::units::Nonce;
+use crate::vfs::Fs;
+
+pub struct InvalidationImpact {
+    /// Issued by the key, with no revocation on file.
+    pub issued: Vec<FoundDelegation>,
+    /// Issued to the key, with no revocation on file; only their delegators can revoke these.
+    pub received: Vec<FoundDelegation>,
+}
+
+impl InvalidationImpact {
+    /// The active delegations on file from and to `pubkey` (uncompressed `0x04…`).
+    pub fn find(fs: &dyn Fs, pubkey: &str) -> Result<Self> {
+        let dirs = search_dirs(fs);
+        let active = |found: Vec<FoundDelegation>| found.into_iter().filter(|f| !f.revoked_on_file).collect();
+        Ok(Self {
+            issued: active(find_delegations_by(fs, &dirs, pubkey)?),
+            received: active(find_delegations(fs, &dirs, pubkey)?),
+        })
+    }
+
+    pub fn orphaned(&self) -> usize {
+        self.issued.len() + self.received.len()
+    }
+
+    /// Issued delegations the key can revoke in this run: on `chain_id`, where its nonces count,
+    /// and not needing the delegatee's signature.
+    pub fn revocable(&self, chain_id: u64) -> Vec<FoundDelegation> {
+        self.issued.iter().filter(|f| f.chain_id == chain_id && !f.require_delegatee_sig).cloned().collect()
+    }
+
+    /// What the invalidation leaves behind, for the confirmation. `queued` are the revocations
+    /// signed first, from `first_nonce`.
+    pub fn lines(&self, queued: &[FoundDelegation], first_nonce: u64) -> Vec<String> {
+        if self.orphaned() == 0 {
+            return vec!["No active delegation from or to this key is on file.".to_string()];
+        }
+        let mut lines = vec![format!("This will orphan {} active delegation(s) on file:", self.orphaned())];
+        for f in &self.issued {
+            let note = if queued.iter().any(|q| same(q, f)) {
+                "revoked first"
+            } else if f.require_delegatee_sig {
+                "needs the delegatee's signature to revoke"
+            } else {
+                "not revoked"
+            };
+            lines.push(format!("  to {} on chain {} ({note})", short_key(&f.delegatee), f.chain_id));
+        }
+        for f in &self.received {
+            lines.push(format!("  from {} on chain {} (its delegator revokes it)", short_key(&f.delegator), f.chain_id));
+        }
+        if !queued.is_empty() {
+            lines.push(format!(
+                "Revocations take nonces {first_nonce}..={}; the invalidation comes last, at {}.",
+                first_nonce + queued.len() as u64 - 1,
+                first_nonce + queued.len() as u64,
+            ));
+        }
+        lines
+    }
+}
+
+fn same(a: &FoundDelegation, b: &FoundDelegation) -> bool {
+    a.delegatee == b.delegatee && a.chain_id == b.chain_id && a.contract == b.contract
+}
+
+/// One `createRevocationEvent` per delegation in `found`, signed by its delegator `revoker_priv`,
+/// with nonces counting up from `start_nonce`. Delegatees are given by public key only.
+pub fn revocation_items(revoker_priv: &str, found: &[FoundDelegation], start_nonce: u64) -> Result<Vec<Item>> {
+    let mut items = Vec::with_capacity(found.len());
+    for (i, f) in found.iter().enumerate() {
+        let nonce = start_nonce
+            .checked_add(i as u64)
+            .ok_or_else(|| anyhow!("nonce overflow"))?;
+        items.push(Item {
+            function_to_call: "createRevocationEvent".to_string(),
+            nonce: Some(Nonce(nonce)),
+            chain_id: Some(f.chain_id),
+            contract_address: f.contract.clone(),
+            label: None,
+
+            // Type A (unused)
+            type_a_privkey_x: None,
+            type_a_privkey_y: None,
+            type_a_pubkey_y: None,
+            type_a_uint_x: None,
+            type_a_uint_y: None,
+            type_a_boolean: None,
+            type_a_nonce16: None,
+
+            // Type B: owner = the key being invalidated; revokee = pubkey only
+            type_b_privkey_x: Some(revoker_priv.to_string()),
+            type_b_privkey_y: Some(String::new()),
+            type_b_pubkey_y: Some(f.delegatee.clone()),
+            type_b_uint_x: Some(0),
+            type_b_uint_y: Some(0),
+            type_b_nonce16: None,
+
+            // Type C (unused)
+            type_c_privkey_x: None,
+            type_c_nonce16: None,
+        });
+    }
+    Ok(items)
+}
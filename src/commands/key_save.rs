@@ -1,6 +1,7 @@
 use anyhow::Result;
 use std::path::PathBuf;
 
+use crate::crypto::factors::KeyFactors;
 use crate::crypto::modern::{save_modern_encrypted_from_privkey_hex, ModernOptions};
 use crate::crypto::pgp::save_pgp_encrypted_from_privkey_hex;
 
@@ -17,6 +18,8 @@ pub struct EncryptedSaveOptions<'a> {
     pub argon_p_cost: u8,
     /// Add the 8-byte random noise prefix to the header (Modern)
     pub add_noise_prefix: bool,
+    /// Factors already mixed into `password_utf8` (Modern)
+    pub factors: KeyFactors,
 }
 
 /// Modern neutral-header writer (Argon2id + XChaCha20-Poly1305, ordered pretty JSON).
@@ -30,6 +33,8 @@ pub fn emit_encrypted_one_modern(record: &KeyRecord, opts: EncryptedSaveOptions<
         m_cost_kib: opts.argon_m_cost_kib,
         p_cost: opts.argon_p_cost,
         add_noise_prefix: opts.add_noise_prefix,
+        derivation_path: record.derivationPath.as_deref(),
+        factors: opts.factors,
     };
     let final_path = save_modern_encrypted_from_privkey_hex(&record.privateKeyHexNostrFormat, modern)?;
     Ok(final_path)
@@ -0,0 +1,113 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroize;
+
+use crate::commands::decrypt_modern::decrypt_modern_bytes;
+use crate::crypto::modern::{
+    encrypt_modern_v2, key_fingerprint_from_compressed, KdfParams, KeyFileMetadata, VERSION_V2,
+};
+use crate::util::hex_to_bytes;
+
+/// Upgrade a modern-format v1 key file to v2 **in place**.
+///
+/// The payload is decrypted with `password_utf8`, re-encrypted with the same
+/// Argon2 costs and noise-prefix choice (fresh salt and nonce), verified by a
+/// round-trip decrypt, written to a sibling temp file and renamed over the
+/// original. `created_at` is taken from the original file's modification time.
+/// Zeroizes `password_utf8`.
+pub fn upgrade_key_file_in_place(path: &Path, password_utf8: &mut Vec<u8>) -> Result<KeyFileMetadata> {
+    let data = fs::read(path)
+        .with_context(|| format!("reading {}", path.display()))?;
+
+    let mut pwd_decrypt = password_utf8.clone();
+    let decrypted = decrypt_modern_bytes(&data, &mut pwd_decrypt);
+    pwd_decrypt.zeroize();
+    let (mut plaintext, info) = match decrypted {
+        Ok(v) => v,
+        Err(e) => {
+            password_utf8.zeroize();
+            return Err(e);
+        }
+    };
+
+    if info.version >= VERSION_V2 {
+        plaintext.zeroize();
+        password_utf8.zeroize();
+        return Err(anyhow!("{} is already a v{} key file.", path.display(), info.version));
+    }
+
+    let result = reencrypt_and_replace(path, &plaintext, password_utf8, &info.kdf, info.has_noise_prefix);
+    plaintext.zeroize();
+    password_utf8.zeroize();
+    result
+}
+
+fn reencrypt_and_replace(
+    path: &Path,
+    plaintext: &[u8],
+    password_utf8: &[u8],
+    kdf: &KdfParams,
+    has_noise_prefix: bool,
+) -> Result<KeyFileMetadata> {
+    let payload: serde_json::Value = serde_json::from_slice(plaintext)
+        .context("decrypted payload is not JSON")?;
+    let compressed_hex = payload
+        .get("public_key_hex_compressed")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("payload has no public_key_hex_compressed field"))?;
+    let compressed = hex_to_bytes(compressed_hex).context("public_key_hex_compressed is not hex")?;
+
+    let metadata = KeyFileMetadata {
+        created_at: original_created_at(path),
+        key_fingerprint: key_fingerprint_from_compressed(&compressed),
+        derivation_path: None,
+        key_count: None,
+        factors: Default::default(),
+    };
+
+    let upgraded = encrypt_modern_v2(plaintext, password_utf8, *kdf, has_noise_prefix, &metadata)?;
+
+    // Round-trip before touching the original
+    let mut pwd_check = password_utf8.to_vec();
+    let check = decrypt_modern_bytes(&upgraded, &mut pwd_check);
+    pwd_check.zeroize();
+    let (mut roundtrip, _) = check.context("verification of the upgraded file failed")?;
+    let same = roundtrip == plaintext;
+    roundtrip.zeroize();
+    if !same {
+        return Err(anyhow!("verification of the upgraded file failed (payload mismatch)"));
+    }
+
+    let tmp = sibling_upgrade_tmp(path);
+    {
+        let mut f = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp)
+            .with_context(|| format!("creating {}", tmp.display()))?;
+        f.write_all(&upgraded)?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp, path)
+        .with_context(|| format!("replacing {}", path.display()))?;
+
+    Ok(metadata)
+}
+
+/// RFC 3339 modification time of the original file, or now if unavailable.
+fn original_created_at(path: &Path) -> String {
+    let ts = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(time::OffsetDateTime::from)
+        .unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+    ts.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+/// ".<name>.upgrade.tmp" next to the original (same filesystem, so rename is atomic).
+fn sibling_upgrade_tmp(path: &Path) -> PathBuf {
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("key");
+    path.with_file_name(format!(".{name}.upgrade.tmp"))
+}
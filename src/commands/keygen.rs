@@ -1,11 +1,20 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use bech32::{self, ToBase32, Variant};
 use ethers_core::types::Address;
 use ethers_core::utils::keccak256;
+use ethers_signers::coins_bip39::{English, Mnemonic};
 use k256::ecdsa::SigningKey;
 use rand_core::OsRng;
 use serde::Serialize;
 use std::{fs, path::PathBuf};
+use zeroize::Zeroize;
+
+/// BIP-44 path of Ethereum account keys; `{i}` is the key's index.
+pub const ETHEREUM_PATH: &str = "m/44'/60'/0'/0/{i}";
+/// NIP-06 path of Nostr keys, one account per index.
+pub const NOSTR_PATH: &str = "m/44'/1237'/{i}'/0/0";
+/// Words in a newly generated mnemonic.
+pub const MNEMONIC_WORDS: usize = 24;
 
 #[allow(non_snake_case)]
 #[derive(Serialize)]
@@ -23,59 +32,101 @@ pub struct KeyRecord {
     // Nostr bech32 encodings (NIP-19)
     pub nsec: String,                          // bech32 of 32-byte privkey
     pub npub: String,                          // bech32 of 32-byte x-only pubkey
+
+    // BIP-32 path the key was derived at from a BIP-39 mnemonic (random keys: none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivationPath: Option<String>,
 }
 
 pub fn generate(count: u32) -> Result<Vec<KeyRecord>> {
-    let mut out: Vec<KeyRecord> = Vec::with_capacity(count as usize);
-
-    for _ in 0..count {
-        // Generate a fresh secp256k1 keypair
-        let sk = SigningKey::random(&mut OsRng);
-
-        // Private key bytes/hex (32 bytes)
-        let sk_bytes = sk.to_bytes();
-        let private_hex_no0x = hex::encode(sk_bytes);
-        let private_hex_0x = format!("0x{}", private_hex_no0x);
-
-        // Public keys
-        let vk = sk.verifying_key();
-
-        // Uncompressed (0x04 || X || Y) — 65 bytes
-        let uncompressed = vk.to_encoded_point(false);
-        let pub_uncompressed_hex = format!("0x{}", hex::encode(uncompressed.as_bytes()));
-
-        // Compressed (0x02/0x03 || X) — 33 bytes
-        let compressed = vk.to_encoded_point(true);
-        let compressed_bytes = compressed.as_bytes();
-        let pub_compressed_hex = format!("0x{}", hex::encode(compressed_bytes));
-
-        // Nostr-style x-only pubkey: drop the first prefix byte (02/03), keep 32-byte X
-        let nostr_pub_x_only = &compressed_bytes[1..]; // [1..33], 32 bytes
-        let nostr_pub_x_only_hex = hex::encode(nostr_pub_x_only);
-
-        // NIP-19 bech32 encodings
-        let nsec = bech32::encode("nsec", sk_bytes.to_base32(), Variant::Bech32)?;
-        let npub = bech32::encode("npub", nostr_pub_x_only.to_base32(), Variant::Bech32)?;
-
-        // Ethereum address from uncompressed pubkey: keccak256(X||Y) last 20 bytes
-        let xy = &uncompressed.as_bytes()[1..]; // drop 0x04
-        let hash = keccak256(xy);
-        let addr = Address::from_slice(&hash[12..]);
-        let address_lower = format!("{:#x}", addr); // lowercase 0x…
-
-        out.push(KeyRecord {
-            privateKeyHex: private_hex_0x,
-            publicKeyUncompressed0x04: pub_uncompressed_hex,
-            publicKeyCompressed: pub_compressed_hex,
-            address: address_lower,
-            privateKeyHexNostrFormat: private_hex_no0x,
-            publicKeyHexNostrFormat: nostr_pub_x_only_hex,
-            nsec,
-            npub,
-        });
+    // Fresh secp256k1 keypairs
+    (0..count).map(|_| record_from_signing_key(&SigningKey::random(&mut OsRng), None)).collect()
+}
+
+/// A new random mnemonic of `MNEMONIC_WORDS` English words.
+pub fn new_mnemonic_phrase() -> String {
+    Mnemonic::<English>::new_with_count(&mut OsRng, MNEMONIC_WORDS)
+        .expect("24 is a valid BIP-39 word count")
+        .to_phrase()
+}
+
+/// `template` with `{i}` replaced by `index`. A template without `{i}` gives the same path for
+/// every index, so it is refused when more than one key is derived.
+pub fn derivation_path(template: &str, index: u32, count: u32) -> Result<String> {
+    let template = template.trim();
+    if !template.starts_with("m/") {
+        bail!("Derivation Path must start with m/ (e.g. {ETHEREUM_PATH})");
+    }
+    if count > 1 && !template.contains("{i}") {
+        bail!("Derivation Path needs an {{i}} for the key index when more than one key is derived");
     }
+    Ok(template.replace("{i}", &index.to_string()))
+}
+
+/// Keys `0..count` derived from a BIP-39 `phrase` (no passphrase) at `path_template`.
+/// The phrase is checked, checksum included; spacing and case do not matter.
+pub fn generate_from_mnemonic(phrase: &str, path_template: &str, count: u32) -> Result<Vec<KeyRecord>> {
+    let mut normalized = phrase.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let mnemonic = Mnemonic::<English>::new_from_phrase(&normalized)
+        .map_err(|e| anyhow!("not a valid BIP-39 English mnemonic ({e})"));
+    normalized.zeroize();
+    let mnemonic = mnemonic?;
+    (0..count)
+        .map(|i| {
+            let path = derivation_path(path_template, i, count)?;
+            let xpriv = mnemonic
+                .derive_key(path.as_str(), None)
+                .map_err(|e| anyhow!("deriving {path}: {e}"))?;
+            let sk: &SigningKey = xpriv.as_ref();
+            record_from_signing_key(sk, Some(path))
+        })
+        .collect()
+}
 
-    Ok(out)
+/// All the forms of `sk`'s private and public key.
+fn record_from_signing_key(sk: &SigningKey, derivation_path: Option<String>) -> Result<KeyRecord> {
+    // Private key bytes/hex (32 bytes)
+    let sk_bytes = sk.to_bytes();
+    let private_hex_no0x = hex::encode(sk_bytes);
+    let private_hex_0x = format!("0x{}", private_hex_no0x);
+
+    // Public keys
+    let vk = sk.verifying_key();
+
+    // Uncompressed (0x04 || X || Y) — 65 bytes
+    let uncompressed = vk.to_encoded_point(false);
+    let pub_uncompressed_hex = format!("0x{}", hex::encode(uncompressed.as_bytes()));
+
+    // Compressed (0x02/0x03 || X) — 33 bytes
+    let compressed = vk.to_encoded_point(true);
+    let compressed_bytes = compressed.as_bytes();
+    let pub_compressed_hex = format!("0x{}", hex::encode(compressed_bytes));
+
+    // Nostr-style x-only pubkey: drop the first prefix byte (02/03), keep 32-byte X
+    let nostr_pub_x_only = &compressed_bytes[1..]; // [1..33], 32 bytes
+    let nostr_pub_x_only_hex = hex::encode(nostr_pub_x_only);
+
+    // NIP-19 bech32 encodings
+    let nsec = bech32::encode("nsec", sk_bytes.to_base32(), Variant::Bech32)?;
+    let npub = bech32::encode("npub", nostr_pub_x_only.to_base32(), Variant::Bech32)?;
+
+    // Ethereum address from uncompressed pubkey: keccak256(X||Y) last 20 bytes
+    let xy = &uncompressed.as_bytes()[1..]; // drop 0x04
+    let hash = keccak256(xy);
+    let addr = Address::from_slice(&hash[12..]);
+    let address_lower = format!("{:#x}", addr); // lowercase 0x…
+
+    Ok(KeyRecord {
+        privateKeyHex: private_hex_0x,
+        publicKeyUncompressed0x04: pub_uncompressed_hex,
+        publicKeyCompressed: pub_compressed_hex,
+        address: address_lower,
+        privateKeyHexNostrFormat: private_hex_no0x,
+        publicKeyHexNostrFormat: nostr_pub_x_only_hex,
+        nsec,
+        npub,
+        derivationPath: derivation_path,
+    })
 }
 
 pub fn emit(records: Vec<KeyRecord>, out: Option<PathBuf>) -> Result<()> {
@@ -0,0 +1,71 @@
+//! Archive, restore and permanently delete key files in a key directory.
+//!
+//! Archiving moves a file into the directory's `.archived` subdirectory: it stays on disk
+//! but no longer shows in the file pickers, which list files only. Restoring moves it back.
+//! Deleting overwrites the file with random bytes before removing it, and needs the key's
+//! nickname typed to confirm. On SSDs and journaling or copy-on-write filesystems earlier
+//! copies of the blocks may survive the overwrite; destroy the medium when that matters.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::vfs::{list_files, Fs};
+
+/// Subdirectory of a key directory that holds archived key files.
+pub const ARCHIVE_DIR: &str = ".archived";
+
+/// Suffix Create Key Pair appends to the nickname when naming a key file.
+const KEY_FILE_SUFFIX: &str = "_Private_Key";
+
+pub fn archive_dir(dir: &Path) -> PathBuf {
+    dir.join(ARCHIVE_DIR)
+}
+
+/// Key files of `dir` (`archived = false`) or of its archive.
+pub fn list_key_files(fs: &dyn Fs, dir: &Path, archived: bool) -> Vec<PathBuf> {
+    let dir = if archived { archive_dir(dir) } else { dir.to_path_buf() };
+    list_files(fs, &dir).unwrap_or_default()
+}
+
+/// Nickname a key file is confirmed by: its file name without extension, uniqueness
+/// counter and `_Private_Key` suffix (`ops_Private_Key (2).inkan` → `ops`).
+pub fn nickname(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    // " (2)" from `create_unique_file`
+    let stem = match stem.rsplit_once(" (") {
+        Some((base, n)) if n.strip_suffix(')').is_some_and(|n| n.parse::<u32>().is_ok()) => base,
+        _ => stem.as_str(),
+    };
+    stem.strip_suffix(KEY_FILE_SUFFIX).unwrap_or(stem).to_string()
+}
+
+/// Move `path` from its key directory into the archive. Returns the new path.
+pub fn archive(fs: &dyn Fs, path: &Path) -> Result<PathBuf> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let name = path.file_name().context("not a file path")?;
+    let archive = archive_dir(dir);
+    fs.create_dir_all(&archive).with_context(|| format!("creating {}", archive.display()))?;
+    let to = archive.join(name);
+    fs.rename(path, &to).with_context(|| format!("archiving {}", path.display()))?;
+    Ok(to)
+}
+
+/// Move an archived `path` back into its key directory. Returns the new path.
+pub fn restore(fs: &dyn Fs, path: &Path) -> Result<PathBuf> {
+    let archive = path.parent().filter(|p| p.file_name().is_some_and(|n| n == ARCHIVE_DIR));
+    let Some(dir) = archive.and_then(Path::parent) else {
+        bail!("{} is not in an {ARCHIVE_DIR} directory", path.display());
+    };
+    let to = dir.join(path.file_name().context("not a file path")?);
+    fs.rename(path, &to).with_context(|| format!("restoring {} (a file of that name may already exist)", path.display()))?;
+    Ok(to)
+}
+
+/// Shred `path` once `typed` matches its nickname.
+pub fn shred(fs: &dyn Fs, path: &Path, typed: &str) -> Result<()> {
+    let expected = nickname(path);
+    if typed.trim() != expected {
+        bail!("the typed name does not match \"{expected}\"; nothing was deleted");
+    }
+    fs.shred(path).with_context(|| format!("deleting {}", path.display()))
+}
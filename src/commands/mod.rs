@@ -2,4 +2,15 @@ pub mod keygen;
 pub mod key_save;
 pub mod decrypt_auto;
 pub mod decrypt_modern;
-pub mod decrypt_pgp;
\ No newline at end of file
+pub mod decrypt_pgp;
+pub mod batch;
+pub mod key_upgrade;
+pub mod bulk_redelegation;
+pub mod emergency_revoke;
+pub mod invalidation_impact;
+pub mod address_table;
+pub mod batch_template;
+
+pub mod keystore;
+pub mod recovery;
+pub mod transfer;
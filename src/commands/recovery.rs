@@ -0,0 +1,237 @@
+//! Startup scan for output an interrupted run left behind, so half-written artifacts do not
+//! pile up unnoticed in the output directories.
+//!
+//! Two kinds of leftovers are found:
+//! - `*.tmp` files. Stores and upgraded key files are written to a sibling temp file and then
+//!   renamed over the original; a temp file that is still there means the run stopped before
+//!   the rename, and the original was never touched. These can only be cleaned up.
+//! - Batch run directories without `manifest.json`, which is written after every signed file.
+//!   Resuming re-validates each file in the directory, removes the ones that do not hold a
+//!   decodable signed transaction (the one being written when the run stopped), and writes
+//!   the manifest marked incomplete, as for a batch that stopped early. Safe bundles carry no
+//!   signed transaction, so a run written in that encoding cannot be resumed.
+
+use anyhow::{bail, Context, Result};
+use ethers_core::abi::Abi;
+use std::path::{Path, PathBuf};
+
+use crate::abi::load_abi;
+use crate::defaults::Defaults;
+use crate::signing::{decode_signed_tx_and_recover, signed_tx_has_high_s, HIGH_S_TX_WARNING};
+use crate::types::{BatchIncompleteOut, BatchManifestEntryOut, BatchManifestOut};
+use crate::vfs::{list_files, Fs};
+use crate::write_signed_transactions_to_file::BATCH_MANIFEST_FILE;
+
+/// Prefix of the per-run directories `write_batch_run_directory` creates.
+const RUN_DIR_PREFIX: &str = "batch_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    /// A sibling temp file that was never renamed into place.
+    TempFile,
+    /// A batch run directory whose manifest was never written.
+    UnfinishedRun,
+}
+
+#[derive(Debug, Clone)]
+pub struct Leftover {
+    pub path: PathBuf,
+    pub kind: LeftoverKind,
+}
+
+impl Leftover {
+    /// Only unfinished runs can be finalized; a temp file's original is still in place.
+    pub fn resumable(&self) -> bool {
+        self.kind == LeftoverKind::UnfinishedRun
+    }
+
+    pub fn describe(&self) -> String {
+        match self.kind {
+            LeftoverKind::TempFile => format!("Temp file: {}", self.path.display()),
+            LeftoverKind::UnfinishedRun => format!("Batch run without manifest: {}", self.path.display()),
+        }
+    }
+}
+
+/// Directories the tool writes into: the working directory (stores) and the default output
+/// directories, each once.
+pub fn output_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in [
+        ".",
+        Defaults::CREATE_KEYPAIR_OUT_DIR,
+        Defaults::CREATE_DELEGATION_OUT_DIR,
+        Defaults::CREATE_REVOCATION_OUT_DIR,
+        Defaults::CREATE_REDELEGATION_OUT_DIR,
+        Defaults::CREATE_PERMANENT_INVALIDATION_OUT_DIR,
+        Defaults::BATCH_OUT_DIR,
+        Defaults::DECRYPT_OUTPUT_DIR,
+    ] {
+        let dir = PathBuf::from(dir);
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Leftovers directly inside `dirs`, sorted by path. Directories that do not exist or cannot
+/// be listed are skipped.
+pub fn scan(fs: &dyn Fs, dirs: &[PathBuf]) -> Vec<Leftover> {
+    let mut found = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs.read_dir(dir) else { continue };
+        for entry in entries {
+            let name = entry.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if !entry.is_dir && name.ends_with(".tmp") {
+                found.push(Leftover { path: entry.path, kind: LeftoverKind::TempFile });
+            } else if entry.is_dir && name.starts_with(RUN_DIR_PREFIX) && !fs.exists(&entry.path.join(BATCH_MANIFEST_FILE)) {
+                found.push(Leftover { path: entry.path, kind: LeftoverKind::UnfinishedRun });
+            }
+        }
+    }
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+/// Remove a leftover: a temp file is overwritten and deleted (it may hold key material), a run
+/// directory is deleted with everything in it.
+pub fn clean_up(fs: &dyn Fs, leftover: &Leftover) -> Result<()> {
+    let path = &leftover.path;
+    match leftover.kind {
+        LeftoverKind::TempFile => fs.shred(path),
+        LeftoverKind::UnfinishedRun => fs.remove_dir_all(path),
+    }
+    .with_context(|| format!("removing {}", path.display()))
+}
+
+/// Finalize an unfinished run directory (see the module docs). Returns summary lines for the
+/// result modal.
+pub fn resume(fs: &dyn Fs, leftover: &Leftover) -> Result<Vec<String>> {
+    if !leftover.resumable() {
+        bail!("{} cannot be resumed; the file it was meant to replace is unchanged", leftover.path.display());
+    }
+    let dir = &leftover.path;
+    let files = list_files(fs, dir).with_context(|| format!("listing {}", dir.display()))?;
+    let abi = load_abi()?;
+
+    let mut kept: Vec<(BatchManifestEntryOut, Option<String>)> = Vec::new();
+    let mut dropped: Vec<PathBuf> = Vec::new();
+    for path in files {
+        match validate_signed_file(fs, &abi, &path) {
+            Ok(v) => kept.push(v),
+            Err(_) => dropped.push(path),
+        }
+    }
+    if kept.is_empty() {
+        bail!("{} holds no complete signed file; clean it up instead", dir.display());
+    }
+    for path in &dropped {
+        fs.shred(path).with_context(|| format!("removing {}", path.display()))?;
+    }
+
+    // Item order is not recorded anywhere else; nonces keep each sender's order.
+    kept.sort_by(|(a, _), (b, _)| (a.nonce, &a.file).cmp(&(b.nonce, &b.file)));
+    let input_sha256 = kept.iter().find_map(|(_, sha)| sha.clone()).unwrap_or_default();
+    let entries: Vec<BatchManifestEntryOut> = kept
+        .into_iter()
+        .enumerate()
+        .map(|(index, (entry, _))| BatchManifestEntryOut { index, ..entry })
+        .collect();
+    let count = entries.len();
+    let manifest = BatchManifestOut {
+        createdAt: time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        inputSha256: input_sha256,
+        itemCount: count,
+        incomplete: Some(BatchIncompleteOut {
+            totalItems: count,
+            reason: "recovered after an interrupted run; the number of items requested is unknown".to_string(),
+        }),
+        stats: None,
+        entries,
+    };
+    let manifest_path = dir.join(BATCH_MANIFEST_FILE);
+    fs.create_new(&manifest_path, serde_json::to_string_pretty(&manifest)?.as_bytes())
+        .with_context(|| format!("writing {}", manifest_path.display()))?;
+
+    let mut lines = vec![
+        format!("Run finalized: {}", dir.display()),
+        format!("{count} signed file(s) kept; the manifest is marked incomplete."),
+    ];
+    if !dropped.is_empty() {
+        lines.push(format!("{} half-written file(s) removed.", dropped.len()));
+    }
+    Ok(lines)
+}
+
+/// One signed file of a run, in any encoding (see `output_encoder.rs`): it holds exactly one
+/// raw signed transaction that decodes, and a JSON file must parse and its `decodedTx` name the
+/// recovered sender. Returns its manifest entry and the file's input hash.
+fn validate_signed_file(fs: &dyn Fs, abi: &Abi, path: &Path) -> Result<(BatchManifestEntryOut, Option<String>)> {
+    let text = String::from_utf8(fs.read(path)?)?;
+    let txs = signed_txs_in(&text);
+    let [signed_tx] = txs.as_slice() else { bail!("expected one signed transaction, found {}", txs.len()) };
+    let (chain_id, nonce, _prio, _fee, _gas, _to, _value, data, from) = decode_signed_tx_and_recover(signed_tx)?;
+    // This tool only writes low-s signatures
+    if signed_tx_has_high_s(signed_tx) {
+        bail!(HIGH_S_TX_WARNING);
+    }
+    let from = format!("{from:?}");
+
+    let json: Option<serde_json::Value> = if text.trim_start().starts_with(['{', '[']) {
+        Some(serde_json::from_str(&text)?)
+    } else {
+        None
+    };
+    let mut label = None;
+    if let Some(json) = &json {
+        // Envelope, legacy array, or one NDJSON line
+        let entry = match json.get("entries").unwrap_or(json).as_array() {
+            Some(entries) => match entries.as_slice() {
+                [entry] => entry,
+                _ => bail!("expected one entry, found {}", entries.len()),
+            },
+            None => json,
+        };
+        if !entry["decodedTx"]["from"].as_str().is_some_and(|f| f.eq_ignore_ascii_case(&from)) {
+            bail!("decodedTx does not match the signed transaction");
+        }
+        label = entry["label"].as_str().map(str::to_string);
+    }
+
+    let func_name = data
+        .get(..4)
+        .and_then(|selector| abi.functions().find(|f| f.short_signature() == selector))
+        .map(|f| f.name.clone())
+        .unwrap_or_else(|| "?".to_string());
+    let manifest_entry = BatchManifestEntryOut {
+        index: 0,
+        file: path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+        funcName: func_name,
+        from,
+        nonce,
+        chainId: chain_id.to_string(),
+        label,
+    };
+    let input_sha256 = json.as_ref().and_then(|j| j["metadata"]["inputSha256"].as_str()).map(str::to_string);
+    Ok((manifest_entry, input_sha256))
+}
+
+/// Raw signed transactions in `text`, whatever the encoder wrote: `0x02…` strings in JSON and
+/// CSV, `hex"02…"` literals in Foundry scripts.
+fn signed_txs_in(text: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for token in text.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let hex = token.strip_prefix("0x").unwrap_or(token);
+        if hex.len() < 64 || !hex.starts_with("02") || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            continue;
+        }
+        let raw = format!("0x{hex}");
+        if decode_signed_tx_and_recover(&raw).is_ok() && !out.contains(&raw) {
+            out.push(raw);
+        }
+    }
+    out
+}
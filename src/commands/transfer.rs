@@ -0,0 +1,142 @@
+//! Copy output files to removable media for the air-gap transfer, verifying every copy.
+//!
+//! Volumes are detected from the mount table: on Linux a mount of a `/dev/…` device counts as
+//! removable when the kernel flags the disk removable or it is mounted under `/media` or
+//! `/run/media` (where desktops and Tails mount USB sticks); on macOS every volume under
+//! `/Volumes` is offered. Each copied file is read back and its SHA-256 compared with the
+//! original; originals are shredded only once every copy has been verified. The read-back can
+//! be served from the page cache, so eject the volume before unplugging it.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::util::sha256_hex;
+use crate::vfs::{DirEntry, Fs};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Volume {
+    pub mount_point: PathBuf,
+    /// Block device (`/dev/sdb1`), empty for `/Volumes` entries.
+    pub device: String,
+}
+
+/// Mounted removable volumes, sorted by mount point.
+pub fn removable_volumes() -> Vec<Volume> {
+    let mut out = Vec::new();
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    for line in mounts.lines() {
+        let mut cols = line.split_whitespace();
+        let (Some(device), Some(mount_point)) = (cols.next(), cols.next()) else { continue };
+        if !device.starts_with("/dev/") {
+            continue;
+        }
+        let mount_point = unescape_mount(mount_point);
+        let under_media = mount_point.starts_with("/media/") || mount_point.starts_with("/run/media/");
+        if under_media || is_removable_disk(device) {
+            out.push(Volume { mount_point: PathBuf::from(mount_point), device: device.to_string() });
+        }
+    }
+    if let Ok(entries) = std::fs::read_dir("/Volumes") {
+        for e in entries.flatten() {
+            let path = e.path();
+            // The startup disk is linked into /Volumes too
+            if path.is_dir() && !path.is_symlink() {
+                out.push(Volume { mount_point: path, device: String::new() });
+            }
+        }
+    }
+    out.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    out.dedup_by(|a, b| a.mount_point == b.mount_point);
+    out
+}
+
+/// `/proc/mounts` writes space, tab, newline and backslash as octal escapes.
+fn unescape_mount(s: &str) -> String {
+    s.replace("\\040", " ").replace("\\011", "\t").replace("\\012", "\n").replace("\\134", "\\")
+}
+
+/// Whether the disk holding `device` (a partition or a whole disk) is flagged removable.
+fn is_removable_disk(device: &str) -> bool {
+    let Some(name) = Path::new(device).file_name() else { return false };
+    // /sys/class/block/<partition> links into its disk's directory
+    let Ok(dev_dir) = std::fs::canonicalize(Path::new("/sys/class/block").join(name)) else { return false };
+    [dev_dir.join("removable"), dev_dir.join("../removable")]
+        .iter()
+        .any(|flag| std::fs::read_to_string(flag).is_ok_and(|v| v.trim() == "1"))
+}
+
+/// Files and directories directly inside `dir`, for picking what to copy.
+pub fn list_sources(fs: &dyn Fs, dir: &Path) -> Vec<DirEntry> {
+    let mut entries = fs.read_dir(dir).unwrap_or_default();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+/// Files below `path` (itself when it is a file), with their path relative to `root`.
+fn files_below(fs: &dyn Fs, root: &Path, path: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut out = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(p) = pending.pop() {
+        if fs.is_dir(&p) {
+            pending.extend(fs.read_dir(&p).with_context(|| format!("listing {}", p.display()))?.into_iter().map(|e| e.path));
+        } else {
+            let rel = p.strip_prefix(root).unwrap_or(&p).to_path_buf();
+            out.push((p, rel));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// One verified copy.
+pub struct Copied {
+    pub source: PathBuf,
+    pub copy: PathBuf,
+    pub sha256: String,
+}
+
+/// Copy `sources` (files or whole directories inside `root`) into `dest`, keeping their paths
+/// relative to `root`, and read every copy back to compare hashes. Never overwrites: an
+/// existing file at the destination stops the copy. Stops at the first failure; what was
+/// copied and verified before it is in the error's context.
+pub fn copy_verified(fs: &dyn Fs, root: &Path, sources: &[PathBuf], dest: &Path) -> Result<Vec<Copied>> {
+    let mut done: Vec<Copied> = Vec::new();
+    for source in sources {
+        for (file, rel) in files_below(fs, root, source)? {
+            let copy = dest.join(&rel);
+            let sha256 = copy_one(fs, &file, &copy)
+                .with_context(|| format!("{} file(s) were copied and verified before this", done.len()))?;
+            done.push(Copied { source: file, copy, sha256 });
+        }
+    }
+    if done.is_empty() {
+        bail!("nothing to copy: the selection holds no files");
+    }
+    Ok(done)
+}
+
+/// Copy one file and read it back; returns its SHA-256.
+fn copy_one(fs: &dyn Fs, file: &Path, copy: &Path) -> Result<String> {
+    let data = fs.read(file).with_context(|| format!("reading {}", file.display()))?;
+    if let Some(parent) = copy.parent() {
+        fs.create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs.create_new(copy, &data).with_context(|| format!("copying to {} (a file of that name may already exist)", copy.display()))?;
+    let back = fs.read(copy).with_context(|| format!("reading back {}", copy.display()))?;
+    let sha256 = sha256_hex(&data);
+    if sha256_hex(&back) != sha256 {
+        bail!("verification failed: {} differs from {}", copy.display(), file.display());
+    }
+    Ok(sha256)
+}
+
+/// Shred the originals of verified copies, then remove the selected directories they emptied.
+pub fn shred_originals(fs: &dyn Fs, copied: &[Copied], sources: &[PathBuf]) -> Result<()> {
+    for c in copied {
+        fs.shred(&c.source).with_context(|| format!("shredding {}", c.source.display()))?;
+    }
+    for dir in sources.iter().filter(|s| fs.is_dir(s)) {
+        fs.remove_dir_all(dir).with_context(|| format!("removing {}", dir.display()))?;
+    }
+    Ok(())
+}
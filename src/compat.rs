@@ -0,0 +1,80 @@
+//! Compatibility handshake file (`compat.json`) describing what this signer build accepts
+//! and produces, so online-side tooling can check a batch input against the exact build
+//! that will sign it before preparing one.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::abi::{load_abi, INKAN_ABI_JSON};
+use crate::canonical_json::canonical_string;
+use crate::crypto::container::CONTAINER_VERSION;
+use crate::scheme::AVAILABLE_SCHEMES;
+use crate::settings_bundle::{NETWORKS_BUNDLE_VERSION, SETTINGS_BUNDLE_VERSION};
+use crate::types::SIGNED_FILE_FORMAT_VERSION;
+use crate::util::sha256_hex;
+
+/// Version of the `compat.json` layout itself.
+pub const COMPAT_VERSION: u32 = 1;
+
+/// SHA-256 of the ABI as canonical JSON, so whitespace and key order in the embedded
+/// source do not change the hash.
+pub fn abi_sha256() -> Result<String> {
+    let v: Value = serde_json::from_str(INKAN_ABI_JSON)?;
+    Ok(sha256_hex(canonical_string(&v).as_bytes()))
+}
+
+/// Keys a batch input item may carry (see `types::Item`).
+const BATCH_INPUT_FIELDS: &[&str] = &[
+    "function_to_call", "nonce", "chain_id", "contract_address",
+    "type_a_privkey_x", "type_a_privkey_y", "type_a_pubkey_y", "type_a_uint_x", "type_a_uint_y", "type_a_boolean",
+    "type_a_nonce16",
+    "type_b_privkey_x", "type_b_privkey_y", "type_b_pubkey_y", "type_b_uint_x", "type_b_uint_y", "type_b_nonce16",
+    "type_c_privkey_x", "type_c_nonce16",
+];
+
+pub fn compat_json() -> Result<Value> {
+    let abi = load_abi()?;
+    let mut functions: Vec<Value> = abi
+        .functions()
+        .map(|f| json!({
+            "name": f.name,
+            "signature": f.signature(),
+            "selector": format!("0x{}", hex::encode(f.short_signature())),
+        }))
+        .collect();
+    functions.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    Ok(json!({
+        "compatVersion": COMPAT_VERSION,
+        "tool": env!("CARGO_PKG_NAME"),
+        "toolVersion": env!("CARGO_PKG_VERSION"),
+        "abiSha256": abi_sha256()?,
+        "functions": functions,
+        "batchInputFields": BATCH_INPUT_FIELDS,
+        "schemas": {
+            "signedFile": SIGNED_FILE_FORMAT_VERSION,
+            "settingsBundle": SETTINGS_BUNDLE_VERSION,
+            "networksBundle": NETWORKS_BUNDLE_VERSION,
+            "keyContainer": CONTAINER_VERSION,
+        },
+        "outputFormats": ["with_metadata", "legacy_array"],
+        "transactionType": "eip1559",
+        "payloadDigests": ["eip191", "raw_keccak"],
+        "signatureSchemes": AVAILABLE_SCHEMES,
+        "features": {
+            "simulation": cfg!(feature = "simulation"),
+            "remoteSigner": cfg!(feature = "remote-signer"),
+        },
+    }))
+}
+
+/// Write `compat.json` to `path`.
+pub fn write_compat_file(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&compat_json()?)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
@@ -0,0 +1,160 @@
+use crate::crypto::modern::{
+    create_unique_file, encrypt_modern_v2, key_fingerprint_from_compressed, KdfParams, KeyFileMetadata,
+};
+use crate::crypto::payload::{build_payload_from_sk, OrderedPayload};
+
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Version of the container plaintext layout (inside a modern v2 file).
+pub const CONTAINER_VERSION: u32 = 1;
+
+/// One labeled key inside a container: `label` followed by the usual ordered payload fields.
+#[derive(Serialize)]
+struct ContainerKeyOut<'a> {
+    label: &'a str,
+    #[serde(flatten)]
+    key: OrderedPayload<'a>,
+}
+
+/// Plaintext of a multi-key container:
+/// `{ "container_version": 1, "keys": [ { "label": ..., "key_pair_nickname": ..., ... }, ... ] }`
+#[derive(Serialize)]
+struct ContainerOut<'a> {
+    container_version: u32,
+    keys: Vec<ContainerKeyOut<'a>>,
+}
+
+/// Owned mirror of [`ContainerKeyOut`] for reading; keeps the field order on re-serialization.
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+struct ContainerKeyIn {
+    #[serde(default)]
+    label: String,
+    key_pair_nickname: String,
+    private_key_hex: String,
+    private_key_nsec: String,
+    public_key_hex_uncompressed: String,
+    public_key_hex_compressed: String,
+    public_key_npub: String,
+    eth_address: String,
+}
+
+#[derive(Deserialize)]
+struct ContainerIn {
+    container_version: u32,
+    keys: Vec<ContainerKeyIn>,
+}
+
+/// Options for the container saver.
+pub struct ContainerOptions<'a> {
+    /// Full output path including a filename (made unique if it already exists).
+    pub file_path: &'a Path,
+    /// Password bytes (UTF-8). This will be zeroized here.
+    pub password_utf8: &'a mut Vec<u8>,
+    pub kdf: KdfParams,
+    pub add_noise_prefix: bool,
+}
+
+/// A single key record recovered from a decrypted container.
+pub struct ContainerRecord {
+    pub label: String,
+    /// Pretty JSON of this key alone (same shape as a single-key payload plus `label`).
+    pub json_pretty: String,
+}
+
+impl Drop for ContainerRecord {
+    fn drop(&mut self) {
+        self.json_pretty.zeroize();
+    }
+}
+
+/// Encrypt several labeled private keys into one modern v2 file.
+///
+/// `keys` is `(label, secret key)`; each label doubles as the record's
+/// `key_pair_nickname`. The v2 metadata carries the key count and a fingerprint
+/// over the concatenated compressed public keys (in order); labels stay encrypted.
+/// RETURNS: PathBuf of the actual file written.
+pub fn save_modern_container(keys: &[(String, [u8; 32])], opts: ContainerOptions<'_>) -> io::Result<PathBuf> {
+    if keys.is_empty() {
+        return Err(io_err("container needs at least one key"));
+    }
+
+    let secp = Secp256k1::new();
+    let mut compressed_all: Vec<u8> = Vec::with_capacity(keys.len() * 33);
+    let mut records: Vec<ContainerKeyOut<'_>> = Vec::with_capacity(keys.len());
+    for (label, sk) in keys {
+        let sec = SecretKey::from_slice(sk)
+            .map_err(|e| io_err(format!("invalid secret key for {label}: {e}")))?;
+        compressed_all.extend_from_slice(&PublicKey::from_secret_key(&secp, &sec).serialize());
+        let key = build_payload_from_sk(label, sk)
+            .map_err(|e| io_err(format!("payload build error: {e}")))?;
+        records.push(ContainerKeyOut { label, key });
+    }
+
+    let mut plaintext = serde_json::to_string_pretty(&ContainerOut {
+        container_version: CONTAINER_VERSION,
+        keys: records,
+    })
+    .map_err(|e| io_err(format!("container encode error: {e}")))?;
+
+    let metadata = KeyFileMetadata {
+        created_at: time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        key_fingerprint: key_fingerprint_from_compressed(&compressed_all),
+        derivation_path: None,
+        key_count: Some(keys.len() as u32),
+        factors: Default::default(),
+    };
+
+    let file_bytes = encrypt_modern_v2(
+        plaintext.as_bytes(),
+        opts.password_utf8,
+        opts.kdf,
+        opts.add_noise_prefix,
+        &metadata,
+    );
+    plaintext.zeroize();
+    opts.password_utf8.zeroize();
+    let file_bytes = file_bytes?;
+
+    let base_dir = opts.file_path.parent().unwrap_or_else(|| Path::new("."));
+    let filename = opts
+        .file_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| io_err("container output path has no filename"))?;
+    fs::create_dir_all(base_dir)
+        .map_err(|e| io_err(format!("create dir {}: {e}", base_dir.display())))?;
+
+    let (f, final_path) = create_unique_file(base_dir, filename)?;
+    let mut w = BufWriter::new(f);
+    w.write_all(&file_bytes)?;
+    w.flush()?;
+    Ok(final_path)
+}
+
+/// Split a decrypted payload into its key records.
+/// Returns `None` when the plaintext is not a container (e.g. a single-key file).
+pub fn parse_container(plaintext: &[u8]) -> Option<Vec<ContainerRecord>> {
+    let c: ContainerIn = serde_json::from_slice(plaintext).ok()?;
+    if c.container_version == 0 || c.container_version > CONTAINER_VERSION {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(c.keys.len());
+    for (i, k) in c.keys.iter().enumerate() {
+        let label = if k.label.is_empty() { format!("key_{}", i + 1) } else { k.label.clone() };
+        let json_pretty = serde_json::to_string_pretty(k).ok()?;
+        out.push(ContainerRecord { label, json_pretty });
+    }
+    Some(out)
+}
+
+fn io_err<M: Into<String>>(msg: M) -> io::Error {
+    io::Error::other(msg.into())
+}
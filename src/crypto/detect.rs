@@ -0,0 +1,94 @@
+//! Telling the two encrypted formats apart from their first bytes, so Decrypt File can pick the
+//! method instead of asking. Modern files have no magic number (an optional noise prefix may come
+//! first), so a random prefix can occasionally look like an OpenPGP packet too; such files, and
+//! files matching neither format, are reported as ambiguous.
+
+use crate::commands::decrypt_modern::has_modern_header;
+
+/// An encrypted file format this tool can decrypt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionFormat {
+    Modern,
+    OpenPgp,
+}
+
+impl EncryptionFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            EncryptionFormat::Modern => "Argon2id + XChaCha20-Poly1305",
+            EncryptionFormat::OpenPgp => "OpenPGP",
+        }
+    }
+}
+
+/// What the first bytes of a file say about its format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detection {
+    Detected(EncryptionFormat),
+    /// Both headers parse (a noise prefix that happens to look like an OpenPGP packet).
+    Both,
+    /// Neither header parses.
+    Neither,
+}
+
+impl Detection {
+    /// The format, when only one matches.
+    pub fn format(self) -> Option<EncryptionFormat> {
+        match self {
+            Detection::Detected(f) => Some(f),
+            Detection::Both | Detection::Neither => None,
+        }
+    }
+}
+
+/// Sniff `buf` (the whole file, or at least its first few hundred bytes).
+pub fn detect_format(buf: &[u8]) -> Detection {
+    match (has_modern_header(buf), looks_like_pgp_message(buf)) {
+        (true, false) => Detection::Detected(EncryptionFormat::Modern),
+        (false, true) => Detection::Detected(EncryptionFormat::OpenPgp),
+        (true, true) => Detection::Both,
+        (false, false) => Detection::Neither,
+    }
+}
+
+/// An ASCII-armored OpenPGP message, or a binary one whose first packet is a well-formed
+/// symmetric-key session key packet (what this tool's OpenPGP files start with).
+fn looks_like_pgp_message(buf: &[u8]) -> bool {
+    let text = buf.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(buf);
+    let start = text.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(text.len());
+    if text[start..].starts_with(b"-----BEGIN PGP MESSAGE-----") {
+        return true;
+    }
+    skesk_body(buf).is_some_and(plausible_skesk)
+}
+
+/// Body of the first packet when it is tagged SKESK (tag 3) and its length fits in `buf`.
+fn skesk_body(buf: &[u8]) -> Option<&[u8]> {
+    let first = *buf.first()?;
+    let (len, at): (usize, usize) = match first {
+        // New format, tag 3
+        0xC3 => match *buf.get(1)? {
+            n @ 0..=191 => (n as usize, 2),
+            n @ 192..=223 => (((n as usize - 192) << 8) + *buf.get(2)? as usize + 192, 3),
+            255 => (u32::from_be_bytes(buf.get(2..6)?.try_into().ok()?) as usize, 6),
+            // Partial lengths are not allowed for this packet
+            _ => return None,
+        },
+        // Old format, tag 3, length type in the low two bits
+        0x8C => (*buf.get(1)? as usize, 2),
+        0x8D => (u16::from_be_bytes(buf.get(1..3)?.try_into().ok()?) as usize, 3),
+        0x8E => (u32::from_be_bytes(buf.get(1..5)?.try_into().ok()?) as usize, 5),
+        _ => return None,
+    };
+    buf.get(at..at.checked_add(len)?)
+}
+
+/// Version 4 (RFC 4880) with a known cipher and S2K type, or the newer versions 5 and 6.
+fn plausible_skesk(body: &[u8]) -> bool {
+    match body {
+        // version, cipher (IDEA..Camellia-256), S2K type (simple, salted, iterated+salted), hash
+        [4, cipher, s2k, _hash, ..] => (1..=13).contains(cipher) && matches!(s2k, 0 | 1 | 3),
+        [5 | 6, ..] => body.len() >= 4,
+        _ => false,
+    }
+}
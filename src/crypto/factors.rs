@@ -0,0 +1,200 @@
+//! Extra factors for modern-format key files: a keyfile (e.g. on removable media) and a FIDO2
+//! security key's `hmac-secret`, mixed into the password Argon2id stretches.
+//!
+//! Which factors a file needs is recorded in its v2 metadata (`factors`), so decryption knows
+//! what to ask for before deriving. The metadata is part of the AAD and the outer MAC: editing
+//! it only makes the file undecryptable. Without factors the password is used unchanged, so
+//! files written before this feature (and without factors) keep their layout.
+//!
+//! FIDO2 goes through libfido2's command-line tools (`fido2-token`, `fido2-cred`,
+//! `fido2-assert`, Debian/Tails package `fido2-tools`) rather than a USB HID stack in this
+//! binary. The credential is not resident and is created without user verification, so a
+//! security key with a PIN set is refused by the tools rather than prompted for it.
+
+use anyhow::{anyhow, bail, Context, Result};
+// Standard base64 with padding, the encoding libfido2's tools read and print
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use zeroize::Zeroize;
+
+/// Keyfiles shorter than this are refused; they would add little to the password.
+pub const KEYFILE_MIN_LEN: usize = 32;
+
+/// Relying party id of the credentials this tool creates.
+const FIDO2_RP_ID: &str = "inkan-management-utility";
+
+/// Factors a key file needs besides the typed password (which may then be empty).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyFactors {
+    /// SHA-256 of a keyfile is mixed in; the file itself is not named.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keyfile: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fido2: Option<Fido2Credential>,
+}
+
+/// A non-resident FIDO2 credential with the `hmac-secret` extension.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Fido2Credential {
+    pub rp_id: String,
+    /// Credential id as printed by `fido2-cred` (base64).
+    pub credential_id: String,
+    /// 32-byte `hmac-secret` salt, hex.
+    pub salt: String,
+}
+
+impl KeyFactors {
+    pub fn is_empty(&self) -> bool {
+        !self.keyfile && self.fido2.is_none()
+    }
+
+    /// "a keyfile and a FIDO2 security key", for prompts and errors.
+    pub fn describe(&self) -> String {
+        match (self.keyfile, self.fido2.is_some()) {
+            (true, true) => "a keyfile and a FIDO2 security key".to_string(),
+            (true, false) => "a keyfile".to_string(),
+            (false, true) => "a FIDO2 security key".to_string(),
+            (false, false) => "the password only".to_string(),
+        }
+    }
+}
+
+/// SHA-256 of a keyfile's contents.
+pub fn read_keyfile(path: &Path) -> Result<[u8; 32]> {
+    let mut data = fs::read(path).with_context(|| format!("reading keyfile {}", path.display()))?;
+    let len = data.len();
+    let digest: [u8; 32] = Sha256::digest(&data).into();
+    data.zeroize();
+    if len < KEYFILE_MIN_LEN {
+        bail!(
+            "keyfile {} has {len} byte(s); use a file of at least {KEYFILE_MIN_LEN} random bytes",
+            path.display()
+        );
+    }
+    Ok(digest)
+}
+
+/// Append the factors to `password_utf8` as
+/// `"\0inkan-factors-v1" ["\0keyfile" || SHA-256(keyfile)] ["\0fido2" || hmac-secret]`.
+/// Leaves the password unchanged when `factors` is empty. Runs the security key's assertion
+/// (waits for a touch) when `factors.fido2` is set.
+pub fn mix_factors(password_utf8: &mut Vec<u8>, factors: &KeyFactors, keyfile: Option<&Path>) -> Result<()> {
+    if factors.is_empty() {
+        return Ok(());
+    }
+    password_utf8.extend_from_slice(b"\0inkan-factors-v1");
+    if factors.keyfile {
+        let path = keyfile.ok_or_else(|| anyhow!("this key file also needs its keyfile; enter the keyfile path"))?;
+        let mut digest = read_keyfile(path)?;
+        password_utf8.extend_from_slice(b"\0keyfile");
+        password_utf8.extend_from_slice(&digest);
+        digest.zeroize();
+    }
+    if let Some(cred) = factors.fido2.as_ref() {
+        let mut secret = fido2_hmac_secret(cred)?;
+        password_utf8.extend_from_slice(b"\0fido2");
+        password_utf8.extend_from_slice(&secret);
+        secret.zeroize();
+    }
+    Ok(())
+}
+
+/// Create a new `hmac-secret` credential on the first connected security key (waits for a touch).
+pub fn fido2_enroll() -> Result<Fido2Credential> {
+    let device = fido2_device()?;
+    let input = format!(
+        "{}\n{FIDO2_RP_ID}\ninkan\n{}\n",
+        B64.encode(random_32()),
+        B64.encode(random_32()),
+    );
+    let out = run_fido2_tool("fido2-cred", &["-M", "-h", &device], &input)?;
+    // client data hash, rp id, format, authenticator data, credential id, ...
+    let credential_id = out
+        .get(4)
+        .filter(|id| B64.decode(id).is_ok())
+        .ok_or_else(|| anyhow!("fido2-cred printed no credential id"))?
+        .clone();
+    Ok(Fido2Credential {
+        rp_id: FIDO2_RP_ID.to_string(),
+        credential_id,
+        salt: hex::encode(random_32()),
+    })
+}
+
+/// The security key's `hmac-secret` for `cred` (waits for a touch).
+pub fn fido2_hmac_secret(cred: &Fido2Credential) -> Result<[u8; 32]> {
+    let salt = hex::decode(&cred.salt).context("FIDO2 salt in the key file metadata is not hex")?;
+    if salt.len() != 32 {
+        bail!("FIDO2 salt in the key file metadata is {} bytes (expected 32)", salt.len());
+    }
+    let device = fido2_device()?;
+    let input = format!(
+        "{}\n{}\n{}\n{}\n",
+        B64.encode(random_32()),
+        cred.rp_id,
+        cred.credential_id,
+        B64.encode(&salt),
+    );
+    let out = run_fido2_tool("fido2-assert", &["-G", "-h", &device], &input)?;
+    // client data hash, rp id, authenticator data, signature, then the hmac-secret
+    let mut secret = out
+        .last()
+        .and_then(|l| B64.decode(l).ok())
+        .filter(|s| s.len() == 32)
+        .ok_or_else(|| anyhow!("fido2-assert returned no 32-byte hmac-secret; is this the security key the file was created with?"))?;
+    let mut arr = [0u8; 32];
+    arr.copy_from_slice(&secret);
+    secret.zeroize();
+    Ok(arr)
+}
+
+/// Path of the first security key listed by `fido2-token -L`.
+fn fido2_device() -> Result<String> {
+    let out = run_fido2_tool("fido2-token", &["-L"], "")?;
+    // "/dev/hidraw3: vendor=0x1050, product=0x0407 (Yubico YubiKey OTP+FIDO+CCID)"
+    out.first()
+        .and_then(|l| l.split(": ").next())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("no FIDO2 security key found; plug one in and try again"))
+}
+
+/// Run one of libfido2's tools with `input` on stdin; returns the non-empty output lines.
+fn run_fido2_tool(tool: &str, args: &[&str], input: &str) -> Result<Vec<String>> {
+    let mut child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => anyhow!("{tool} not found; install libfido2's command-line tools (Debian/Tails: fido2-tools)"),
+            _ => anyhow!("starting {tool}: {e}"),
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).with_context(|| format!("writing to {tool}"))?;
+    }
+    let out = child.wait_with_output().with_context(|| format!("waiting for {tool}"))?;
+    if !out.status.success() {
+        let err = String::from_utf8_lossy(&out.stderr);
+        let hint = if err.contains("PIN") { " (security keys with a PIN set are not supported)" } else { "" };
+        bail!("{tool} failed: {}{hint}", err.trim());
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn random_32() -> [u8; 32] {
+    let mut b = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut b);
+    b
+}
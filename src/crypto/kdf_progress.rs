@@ -0,0 +1,95 @@
+//! Progress feedback for long Argon2id derivations (key file creation and decryption), so an
+//! operator on slow hardware can tell "working" from "hung".
+//!
+//! The argon2 crate runs all `t_cost` passes inside one call and reports nothing, and a pass
+//! cannot be run separately without changing the derived key. So the work runs on a worker
+//! thread ([`KdfJob`]) and its progress is estimated: elapsed time against this machine's
+//! Argon2id throughput, measured once per session on a small one-pass run. The bar holds at
+//! 99% until the work returns while the elapsed time keeps counting, so a slower run than
+//! estimated never shows as finished.
+
+use anyhow::{anyhow, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Memory cost of the calibration run (8 MiB, one pass; a few milliseconds).
+const CALIBRATION_KIB: u32 = 8 * 1024;
+
+/// How long `poll` waits for the result, so a screen polling every loop turn does not spin.
+const POLL_WAIT: Duration = Duration::from_millis(50);
+
+/// Argon2id throughput of this machine in KiB-passes per second, measured on first use.
+fn throughput() -> f64 {
+    static RATE: OnceLock<f64> = OnceLock::new();
+    *RATE.get_or_init(|| {
+        let Ok(params) = Params::new(CALIBRATION_KIB, 1, 1, Some(32)) else { return f64::INFINITY };
+        let argon = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+        let mut out = [0u8; 32];
+        let start = Instant::now();
+        let _ = argon.hash_password_into(b"calibration", b"calibration salt", &mut out);
+        CALIBRATION_KIB as f64 / start.elapsed().as_secs_f64().max(1e-4)
+    })
+}
+
+/// Expected duration of an Argon2id derivation with these costs on this machine.
+pub fn estimate(m_cost_kib: u32, t_cost: u32) -> Duration {
+    Duration::from_secs_f64(m_cost_kib as f64 * t_cost as f64 / throughput())
+}
+
+/// Work running on a worker thread, polled by a screen's `tick`.
+pub struct KdfJob<T> {
+    /// What is running, e.g. "Encrypting the key file (Argon2id, 256 MiB, 3 passes)".
+    pub label: String,
+    started: Instant,
+    /// From `estimate`; `None` shows the elapsed time only.
+    expected: Option<Duration>,
+    rx: Receiver<Result<T>>,
+}
+
+impl<T: Send + 'static> KdfJob<T> {
+    pub fn spawn(
+        label: String,
+        expected: Option<Duration>,
+        work: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(work());
+        });
+        Self { label, started: Instant::now(), expected, rx }
+    }
+
+    /// The result once the work has returned (waits up to `POLL_WAIT` for it).
+    pub fn poll(&self) -> Option<Result<T>> {
+        match self.rx.recv_timeout(POLL_WAIT) {
+            Ok(result) => Some(result),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => Some(Err(anyhow!("the worker thread stopped without a result"))),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Estimated completion in 0..=0.99, or `None` without an estimate.
+    pub fn fraction(&self) -> Option<f64> {
+        let expected = self.expected?.as_secs_f64().max(1e-3);
+        Some((self.elapsed().as_secs_f64() / expected).min(0.99))
+    }
+
+    /// "12.3 s elapsed, about 4 s left" (or "… taking longer than estimated").
+    pub fn status(&self) -> String {
+        let elapsed = self.elapsed();
+        let mut s = format!("{:.1} s elapsed", elapsed.as_secs_f64());
+        if let Some(expected) = self.expected {
+            match expected.checked_sub(elapsed) {
+                Some(left) => s.push_str(&format!(", about {} s left", left.as_secs() + 1)),
+                None => s.push_str(", taking longer than estimated; still working"),
+            }
+        }
+        s
+    }
+}
@@ -2,6 +2,10 @@ pub mod modern;
 pub mod nostr_utils;
 pub mod pgp;
 pub mod payload; // ⬅️ add this line
+pub mod container;
+pub mod kdf_progress;
+pub mod factors;
+pub mod detect;
 
 use zeroize::Zeroize;
 
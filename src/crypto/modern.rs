@@ -1,19 +1,51 @@
+use crate::crypto::factors::KeyFactors;
 use crate::crypto::payload::build_payload_pretty_from_sk;
+use crate::memory_hygiene::LockedBytes;
 
 use argon2::Argon2;
 use chacha20poly1305::aead::{Aead, KeyInit, Payload};
 use chacha20poly1305::XChaCha20Poly1305;
+use hmac::{Hmac, Mac};
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
-use secp256k1::SecretKey;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufWriter, Write};
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use zeroize::Zeroize;
 
-const VERSION: u8 = 1;
+/// Format version written by this build (v1 files remain readable).
+pub const VERSION_V2: u8 = 2;
 const KDF_ID_ARGON2ID: u8 = 1;
+/// Length of the trailing HMAC-SHA256 over header || ciphertext (v2 only).
+pub const V2_MAC_LEN: usize = 32;
+
+/// Cleartext (but authenticated) metadata carried in the v2 header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyFileMetadata {
+    /// RFC 3339 UTC timestamp of when the key file was written (or of the
+    /// original v1 file, for migrated files).
+    pub created_at: String,
+    /// First 8 bytes of SHA-256(compressed public key), hex.
+    pub key_fingerprint: String,
+    /// Optional HD derivation path the key came from, e.g. `m/44'/60'/0'/0/0`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    /// Number of key records, present only for multi-key containers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_count: Option<u32>,
+    /// Keyfile / FIDO2 security key mixed into the password (absent when none).
+    #[serde(default, skip_serializing_if = "KeyFactors::is_empty")]
+    pub factors: KeyFactors,
+}
+
+/// Fingerprint used in v2 metadata: hex of SHA-256(compressed pubkey)[..8].
+pub fn key_fingerprint_from_compressed(compressed33: &[u8]) -> String {
+    hex::encode(&Sha256::digest(compressed33)[..8])
+}
 
 /// Options for the modern saver.
 pub struct ModernOptions<'a> {
@@ -30,10 +62,148 @@ pub struct ModernOptions<'a> {
     pub p_cost: u8,      // parallelism
     /// If true, include 8 bytes of random preface noise to look like ciphertext.
     pub add_noise_prefix: bool,
+    /// Optional derivation path recorded in the v2 metadata.
+    pub derivation_path: Option<&'a str>,
+    /// Factors already mixed into `password_utf8`, recorded in the v2 metadata.
+    pub factors: KeyFactors,
+}
+
+/// Argon2id cost parameters as stored in the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub t_cost: u32,
+    pub m_cost_kib: u32,
+    pub p_cost: u8,
+}
+
+/// Named Argon2id costs offered when creating a key file. The chosen costs are written into
+/// the header like any others, so decryption does not need to know which preset was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KdfPreset {
+    Fast,
+    #[default]
+    Balanced,
+    Paranoid,
+}
+
+impl KdfPreset {
+    pub const ALL: [KdfPreset; 3] = [KdfPreset::Fast, KdfPreset::Balanced, KdfPreset::Paranoid];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KdfPreset::Fast => "Fast",
+            KdfPreset::Balanced => "Balanced",
+            KdfPreset::Paranoid => "Paranoid",
+        }
+    }
+
+    pub fn params(self) -> KdfParams {
+        match self {
+            KdfPreset::Fast => KdfParams { t_cost: 2, m_cost_kib: 65_536, p_cost: 1 },        // 64 MiB
+            KdfPreset::Balanced => KdfParams { t_cost: 3, m_cost_kib: 262_144, p_cost: 1 },   // 256 MiB
+            KdfPreset::Paranoid => KdfParams { t_cost: 4, m_cost_kib: 1_048_576, p_cost: 1 }, // 1 GiB
+        }
+    }
+
+    /// When to pick it, for the Create Key Pair form.
+    pub fn description(self) -> &'static str {
+        match self {
+            KdfPreset::Fast => "For keys unlocked often or on low-memory machines; weakest against password guessing.",
+            KdfPreset::Balanced => "The default (the costs used by earlier releases); right for most keys.",
+            KdfPreset::Paranoid => "For rarely unlocked cold keys; every unlocking machine needs 1 GiB of free memory.",
+        }
+    }
+
+    /// The next preset in `ALL` (wrapping), `forward` or backward.
+    pub fn cycle(self, forward: bool) -> KdfPreset {
+        let i = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        let n = Self::ALL.len();
+        Self::ALL[if forward { (i + 1) % n } else { (i + n - 1) % n }]
+    }
+}
+
+/// Encrypt `plaintext` into a complete v2 file image:
+/// `[noise?][header incl. metadata][ciphertext || tag][HMAC-SHA256]`.
+///
+/// Argon2id produces 64 bytes: the first 32 are the XChaCha20-Poly1305 key,
+/// the last 32 the HMAC key for the outer integrity MAC.
+pub fn encrypt_modern_v2(
+    plaintext: &[u8],
+    password_utf8: &[u8],
+    kdf: KdfParams,
+    add_noise_prefix: bool,
+    metadata: &KeyFileMetadata,
+) -> io::Result<Vec<u8>> {
+    let meta_json = serde_json::to_vec(metadata)
+        .map_err(|e| io_err(format!("metadata encode error: {e}")))?;
+    if meta_json.len() > u16::MAX as usize {
+        return Err(io_err("metadata too large"));
+    }
+
+    // KDF: Argon2id -> 64 bytes (enc key || mac key)
+    let mut rng = ChaCha20Rng::from_entropy();
+    let mut salt = vec![0u8; 16];
+    rng.fill_bytes(&mut salt);
+
+    let params = argon2::Params::new(kdf.m_cost_kib, kdf.t_cost, kdf.p_cost as u32, Some(64))
+        .map_err(|e| io_err(format!("Argon2 params: {e}")))?;
+    let argon = Argon2::new_with_secret(&[], argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .map_err(|e| io_err(format!("Argon2 ctor: {e}")))?;
+
+    let mut okm = LockedBytes::zeroed(64);
+    argon
+        .hash_password_into(password_utf8, &salt, &mut okm)
+        .map_err(|e| io_err(format!("Argon2 error: {e}")))?;
+
+    let mut nonce = [0u8; 24];
+    rng.fill_bytes(&mut nonce);
+
+    // Header layout (neutral, no branding):
+    // [8B noise?][u8 version=2][u8 kdf_id][u32 t_cost][u32 m_cost_kib][u8 p_cost]
+    // [u8 salt_len][salt][u8 nonce_len=24][nonce][u16 meta_len][meta JSON]
+    let mut out = Vec::with_capacity(
+        (if add_noise_prefix { 8 } else { 0 }) + 13 + salt.len() + nonce.len()
+            + 2 + meta_json.len() + plaintext.len() + 16 + V2_MAC_LEN,
+    );
+
+    if add_noise_prefix {
+        let mut noise = [0u8; 8];
+        rng.fill_bytes(&mut noise);
+        out.extend_from_slice(&noise);
+    }
+
+    out.push(VERSION_V2);                                      // u8
+    out.push(KDF_ID_ARGON2ID);                                 // u8
+    out.extend_from_slice(&kdf.t_cost.to_le_bytes());          // u32
+    out.extend_from_slice(&kdf.m_cost_kib.to_le_bytes());      // u32
+    out.push(kdf.p_cost);                                      // u8
+    out.push(salt.len() as u8);                                // u8
+    out.extend_from_slice(&salt);                              // salt
+    out.push(nonce.len() as u8);                               // u8
+    out.extend_from_slice(&nonce);                             // nonce
+    out.extend_from_slice(&(meta_json.len() as u16).to_le_bytes()); // u16
+    out.extend_from_slice(&meta_json);                         // metadata
+
+    // Encrypt (AAD = entire header, metadata included)
+    let cipher = XChaCha20Poly1305::new((&okm[..32]).into());
+    let ciphertext = cipher
+        .encrypt((&nonce).into(), Payload { aad: &out, msg: plaintext })
+        .map_err(|e| io_err(format!("encrypt error: {e}")))?;
+    out.extend_from_slice(&ciphertext);
+
+    // Outer integrity MAC over everything written so far
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&okm[32..])
+        .map_err(|e| io_err(format!("HMAC key: {e}")))?;
+    mac.update(&out);
+    out.extend_from_slice(&mac.finalize().into_bytes());
+
+    drop(okm);
+    salt.zeroize();
+    Ok(out)
 }
 
 /// Create a file with a unique name, avoiding overwrite by appending " (1)", " (2)", ...
-fn create_unique_file(base_dir: &Path, filename: &str) -> io::Result<(File, PathBuf)> {
+pub(crate) fn create_unique_file(base_dir: &Path, filename: &str) -> io::Result<(File, PathBuf)> {
     let stem = Path::new(filename)
         .file_stem()
         .and_then(|s| s.to_str())
@@ -75,7 +245,7 @@ fn create_unique_file(base_dir: &Path, filename: &str) -> io::Result<(File, Path
 /// If `opts.file_path` is a directory, we derive a filename from the nickname.
 pub fn save_modern_encrypted_from_privkey_hex(
     privkey_hex_no0x: &str,
-    mut opts: ModernOptions<'_>,
+    opts: ModernOptions<'_>,
 ) -> io::Result<PathBuf> {
     // 1) Decode privkey (32 bytes)
     let sk_bytes_vec = hex::decode(privkey_hex_no0x)
@@ -87,7 +257,7 @@ pub fn save_modern_encrypted_from_privkey_hex(
     sk_bytes.copy_from_slice(&sk_bytes_vec);
 
     // Validate the secret key early (will also be used inside payload builder)
-    let _ = SecretKey::from_slice(&sk_bytes)
+    let sec = SecretKey::from_slice(&sk_bytes)
         .map_err(|e| io_err(format!("invalid secret key: {e}")))?;
 
     // 2) Build ordered, pretty JSON payload (centralized in payload.rs)
@@ -95,68 +265,29 @@ pub fn save_modern_encrypted_from_privkey_hex(
     let payload_pretty = build_payload_pretty_from_sk(opts.key_pair_nickname, &sk_bytes)
         .map_err(|e| io_err(format!("payload build error: {e}")))?;
 
-    // 3) KDF: Argon2id -> 32-byte key
-    let mut rng = ChaCha20Rng::from_entropy();
-    let mut salt = vec![0u8; 16];
-    rng.fill_bytes(&mut salt);
-
-    let argon = Argon2::new_with_secret(
-        &[],
-        argon2::Algorithm::Argon2id,
-        argon2::Version::V0x13,
-        argon2::Params::new(opts.m_cost_kib, opts.t_cost, opts.p_cost as u32, None)
-            .expect("argon2 params"),
-    )
-    .expect("argon2 ctor");
-
-    let mut key = [0u8; 32];
-    argon
-        .hash_password_into(opts.password_utf8, &salt, &mut key)
-        .map_err(|e| io_err(format!("Argon2 error: {e}")))?;
-
-    // 4) Nonce and header
-    let mut nonce = [0u8; 24];
-    rng.fill_bytes(&mut nonce);
-
-    // Header layout (neutral, no branding):
-    // [8B noise?][u8 version][u8 kdf_id][u32 t_cost][u32 m_cost_kib][u8 p_cost]
-    // [u8 salt_len][salt][u8 nonce_len=24][nonce]
-    let mut header = Vec::with_capacity(
-        (if opts.add_noise_prefix { 8 } else { 0 })
-            + 1
-            + 1
-            + 4
-            + 4
-            + 1
-            + 1
-            + salt.len()
-            + 1
-            + nonce.len(),
-    );
-
-    if opts.add_noise_prefix {
-        let mut noise = [0u8; 8];
-        rng.fill_bytes(&mut noise);
-        header.extend_from_slice(&noise);
-    }
+    // 3) Metadata: creation time + fingerprint of the compressed pubkey
+    let pk = PublicKey::from_secret_key(&Secp256k1::new(), &sec);
+    let metadata = KeyFileMetadata {
+        created_at: time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        key_fingerprint: key_fingerprint_from_compressed(&pk.serialize()),
+        derivation_path: opts.derivation_path.map(str::to_string),
+        key_count: None,
+        factors: opts.factors,
+    };
 
-    header.push(VERSION);                                     // u8
-    header.push(KDF_ID_ARGON2ID);                             // u8
-    header.extend_from_slice(&opts.t_cost.to_le_bytes());     // u32
-    header.extend_from_slice(&opts.m_cost_kib.to_le_bytes()); // u32
-    header.push(opts.p_cost);                                 // u8
-    header.push(salt.len() as u8);                            // u8
-    header.extend_from_slice(&salt);                          // salt
-    header.push(nonce.len() as u8);                           // u8
-    header.extend_from_slice(&nonce);                         // nonce
-
-    // 5) Encrypt (AAD = header)
-    let cipher = XChaCha20Poly1305::new((&key).into());
-    let ciphertext = cipher
-        .encrypt((&nonce).into(), Payload { aad: &header, msg: payload_pretty.as_bytes() })
-        .map_err(|e| io_err(format!("encrypt error: {e}")))?;
+    // 4) + 5) KDF, header and AEAD, with the outer MAC (v2 layout)
+    let kdf = KdfParams { t_cost: opts.t_cost, m_cost_kib: opts.m_cost_kib, p_cost: opts.p_cost };
+    let file_bytes = encrypt_modern_v2(
+        payload_pretty.as_bytes(),
+        opts.password_utf8,
+        kdf,
+        opts.add_noise_prefix,
+        &metadata,
+    )?;
 
-    // 6) Resolve output path & write file: [header || ciphertext] with unique filename
+    // 6) Resolve output path & write file: [header || ciphertext || mac] with unique filename
     let provided = Path::new(opts.file_path);
 
     // Determine base_dir and filename_to_use
@@ -188,13 +319,10 @@ pub fn save_modern_encrypted_from_privkey_hex(
     // open a uniquely named file (no overwrite) and remember the final path
     let (f, final_path) = create_unique_file(&base_dir, &filename_to_use)?;
     let mut w = BufWriter::new(f);
-    w.write_all(&header)?;
-    w.write_all(&ciphertext)?;
+    w.write_all(&file_bytes)?;
     w.flush()?;
 
     // 7) Zeroize sensitive buffers
-    key.zeroize();
-    salt.zeroize();
     opts.password_utf8.zeroize();
     sk_bytes.zeroize();
 
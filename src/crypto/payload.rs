@@ -21,10 +21,20 @@ pub struct OrderedPayload<'a> {
 /// - `key_pair_nickname` is included as a borrowed `&str`
 /// - All hex values are 0x-prefixed
 /// - `eth_address` is derived as Keccak256(uncompressed[1..]) last 20 bytes
-pub fn build_payload_pretty_from_sk<'a>(
-    key_pair_nickname: &'a str,
+pub fn build_payload_pretty_from_sk(
+    key_pair_nickname: &str,
     sk_bytes: &[u8; 32],
 ) -> anyhow::Result<String> {
+    let payload = build_payload_from_sk(key_pair_nickname, sk_bytes)?;
+    let s = serde_json::to_string_pretty(&payload)?;
+    Ok(s)
+}
+
+/// Same fields as [`build_payload_pretty_from_sk`], unserialized (for embedding).
+pub fn build_payload_from_sk<'a>(
+    key_pair_nickname: &'a str,
+    sk_bytes: &[u8; 32],
+) -> anyhow::Result<OrderedPayload<'a>> {
     // Validate secret key
     let sec = SecretKey::from_slice(sk_bytes)?;
 
@@ -57,6 +67,5 @@ pub fn build_payload_pretty_from_sk<'a>(
         eth_address,
     };
 
-    let s = serde_json::to_string_pretty(&payload)?;
-    Ok(s)
+    Ok(payload)
 }
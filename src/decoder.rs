@@ -1,13 +1,94 @@
+//! Calldata and signed transactions decoded into the `decodedTx` shape of the signed files.
+
 use anyhow::{anyhow, Result};
 use ethers_core::abi::FunctionExt;
 use ethers_core::abi::{Abi, Function, Token};
+use ethers_core::types::{Signature, U256};
+use serde::Serialize;
+
+use crate::hexstr::{bytes_to_0x, hex_to_bytes};
+use crate::payload::{DelegationPayload, InvalidationPayload, Payload, PayloadDigest, RevocationPayload};
+use crate::signed_tx::decode_signed_tx_and_recover;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct DecodedTxOut {
+    pub from: String,
+    pub to: String,
+    pub value: String,
+    pub gasLimit: String,
+    pub nonce: u64,
+    pub chainId: String,
+    pub maxFeePerGas: String,
+    pub maxPriorityFeePerGas: String,
+    pub funcName: String,
+    pub encodedData: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decodedData: Option<DecodedOne>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decodedDataTypeA: Option<DelegationDecodedOrdered>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decodedDataTypeB: Option<RevocationDecodedOrdered>,
+    /// Digest the payload signatures cover; unknown when decoding a raw tx.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payloadDigest: Option<PayloadDigest>,
+}
+
+/// Ordered decoded output structs (to guarantee field order in JSON)
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct DelegationDecodedOrdered {
+    pub delegatorPubkey: String,
+    pub delegateePubkey: String,
+    pub delegationStartTime: String,
+    pub delegationEndTime: String,
+    pub doesRevocationRequireDelegateeSignature: bool,
+    pub nonce: String,
+    pub expectedAddressOfDeployedContract: String,
+    pub rDelegatorPubkeySig: String,
+    pub sDelegatorPubkeySig: String,
+    pub vDelegatorPubkeySig: String,
+    pub rDelegateePubkeySig: String,
+    pub sDelegateePubkeySig: String,
+    pub vDelegateePubkeySig: String,
+}
 
-use crate::types::{
-    DecodedOne, DecodedTxOut, DelegationDecodedOrdered, InvalidationDecodedOrdered,
-    RevocationDecodedOrdered,
-};
-use crate::util::bytes_to_0x;
-use crate::{signing::decode_signed_tx_and_recover};
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct RevocationDecodedOrdered {
+    pub revokerPubkey: String,
+    pub revokeePubkey: String,
+    pub revocationStartTime: String,
+    pub revocationEndTime: String,
+    pub nonce: String,
+    pub expectedAddressOfDeployedContract: String,
+    pub rRevokerPubkeySig: String,
+    pub sRevokerPubkeySig: String,
+    pub vRevokerPubkeySig: String,
+    pub rRevokeePubkeySig: String,
+    pub sRevokeePubkeySig: String,
+    pub vRevokeePubkeySig: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct InvalidationDecodedOrdered {
+    pub invalidatedPubkey: String,
+    pub nonce: String,
+    pub expectedAddressOfDeployedContract: String,
+    pub rInvalidatedPubkeySig: String,
+    pub sInvalidatedPubkeySig: String,
+    pub vInvalidatedPubkeySig: String,
+}
+
+/// Untagged enum so `decodedData` can be one of the three ordered shapes
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum DecodedOne {
+    Delegation(DelegationDecodedOrdered),
+    Revocation(RevocationDecodedOrdered),
+    Invalidation(InvalidationDecodedOrdered),
+}
 
 fn as_bytes_hex(tok: &Token) -> Result<String> {
     match tok {
@@ -172,10 +253,11 @@ pub fn build_decoded(
         maxFeePerGas: max_fee.to_string(),
         maxPriorityFeePerGas: max_prio.to_string(),
         funcName: func_name,
-        encodedData: crate::util::bytes_to_0x(&data),
+        encodedData: bytes_to_0x(&data),
         decodedData: one,
         decodedDataTypeA: None,
         decodedDataTypeB: None,
+        payloadDigest: None,
     })
 }
 
@@ -208,10 +290,161 @@ pub fn build_decoded_for_combo(
         maxFeePerGas: max_fee.to_string(),
         maxPriorityFeePerGas: max_prio.to_string(),
         funcName: func_name,
-        encodedData: crate::util::bytes_to_0x(&data),
+        encodedData: bytes_to_0x(&data),
         decodedData: None,
         decodedDataTypeA: a, // (A,B) with strict struct order
         decodedDataTypeB: b,
+        payloadDigest: None,
     })
 }
 
+/// Decode and sender-recover `raw_hex`, with the calldata decoded for whichever Inkan
+/// function it calls (the re-delegation combo fills `decodedDataTypeA`/`B`).
+pub fn decode_signed_tx(raw_hex: &str, abi: &Abi) -> Result<DecodedTxOut> {
+    let (.., to, _value, data, _from) = decode_signed_tx_and_recover(raw_hex)?;
+    let (name, _, _) = decode_calldata_to_json(abi, &data)?;
+    if name == "createRevocationEventFollowedByDelegationEvent" {
+        build_decoded_for_combo(raw_hex, &to, &data, abi)
+    } else {
+        build_decoded(raw_hex, &to, &data, abi)
+    }
+}
+
+impl DecodedTxOut {
+    /// Fields of an informational `decodedTx` block that disagree with this (decoded from the
+    /// signed transaction itself); reviewers tend to read the block, not the raw bytes.
+    pub fn differing_fields(&self, block: &serde_json::Value) -> Vec<&'static str> {
+        let text = |k: &str| block[k].as_str().map(str::to_ascii_lowercase);
+        [
+            ("from", text("from") != Some(self.from.to_ascii_lowercase())),
+            ("to", text("to") != Some(self.to.to_ascii_lowercase())),
+            ("nonce", block["nonce"].as_u64() != Some(self.nonce)),
+            ("chainId", text("chainId") != Some(self.chainId.clone())),
+            ("funcName", text("funcName") != Some(self.funcName.to_ascii_lowercase())),
+            ("encodedData", text("encodedData") != Some(self.encodedData.to_ascii_lowercase())),
+        ]
+        .into_iter()
+        .filter(|(_, differs)| *differs)
+        .map(|(k, _)| k)
+        .collect()
+    }
+
+    /// Every payload signature in the calldata, with the payload it covers.
+    pub fn payloads(&self) -> Vec<(Result<Payload>, Vec<PayloadSig<'_>>)> {
+        match (&self.decodedData, &self.decodedDataTypeA, &self.decodedDataTypeB) {
+            (Some(DecodedOne::Delegation(d)), _, _) => vec![(d.payload(), d.signatures())],
+            (Some(DecodedOne::Revocation(r)), _, _) => vec![(r.payload(), r.signatures())],
+            (Some(DecodedOne::Invalidation(p)), _, _) => vec![(p.payload(), p.signatures())],
+            (None, Some(a), Some(b)) => vec![(b.payload(), b.signatures()), (a.payload(), a.signatures())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// One payload signature of a decoded event, as hex strings from the calldata.
+pub struct PayloadSig<'a> {
+    /// Who signs: "delegator", "delegatee", "revoker", "revokee" or "invalidated key".
+    pub role: &'static str,
+    pub pubkey: &'a str,
+    pub r: &'a str,
+    pub s: &'a str,
+    pub v: &'a str,
+    /// False for a counterparty that may be given by public key only (all-zero signature).
+    pub required: bool,
+}
+
+impl PayloadSig<'_> {
+    /// A counterparty given by public key only: optional, and r, s and v all zero.
+    pub fn is_absent(&self) -> bool {
+        let is_zero = |h: &str| hex_to_bytes(h).map(|b| b.iter().all(|x| *x == 0)).unwrap_or(false);
+        !self.required && is_zero(self.r) && is_zero(self.s) && self.v == "0"
+    }
+
+    pub fn signature(&self) -> Result<Signature> {
+        Ok(Signature {
+            r: U256::from_big_endian(&hex_to_bytes(self.r)?),
+            s: U256::from_big_endian(&hex_to_bytes(self.s)?),
+            v: self.v.parse()?,
+        })
+    }
+}
+
+fn nonce16(hex: &str) -> Result<[u8; 16]> {
+    hex_to_bytes(hex)?.try_into().map_err(|_| anyhow!("nonce is not 16 bytes"))
+}
+
+impl DelegationDecodedOrdered {
+    /// The unsigned fields the payload signatures cover.
+    pub fn payload(&self) -> Result<Payload> {
+        Ok(Payload::Delegation(DelegationPayload {
+            delegator_pubkey: hex_to_bytes(&self.delegatorPubkey)?,
+            delegatee_pubkey: hex_to_bytes(&self.delegateePubkey)?,
+            start_time: self.delegationStartTime.parse()?,
+            end_time: self.delegationEndTime.parse()?,
+            revocation_requires_delegatee_signature: self.doesRevocationRequireDelegateeSignature,
+            nonce: nonce16(&self.nonce)?,
+            contract_address: hex_to_bytes(&self.expectedAddressOfDeployedContract)?,
+        }))
+    }
+
+    pub fn signatures(&self) -> Vec<PayloadSig<'_>> {
+        vec![
+            PayloadSig {
+                role: "delegator", pubkey: &self.delegatorPubkey,
+                r: &self.rDelegatorPubkeySig, s: &self.sDelegatorPubkeySig, v: &self.vDelegatorPubkeySig,
+                required: true,
+            },
+            PayloadSig {
+                role: "delegatee", pubkey: &self.delegateePubkey,
+                r: &self.rDelegateePubkeySig, s: &self.sDelegateePubkeySig, v: &self.vDelegateePubkeySig,
+                required: false,
+            },
+        ]
+    }
+}
+
+impl RevocationDecodedOrdered {
+    pub fn payload(&self) -> Result<Payload> {
+        Ok(Payload::Revocation(RevocationPayload {
+            revoker_pubkey: hex_to_bytes(&self.revokerPubkey)?,
+            revokee_pubkey: hex_to_bytes(&self.revokeePubkey)?,
+            start_time: self.revocationStartTime.parse()?,
+            end_time: self.revocationEndTime.parse()?,
+            nonce: nonce16(&self.nonce)?,
+            contract_address: hex_to_bytes(&self.expectedAddressOfDeployedContract)?,
+        }))
+    }
+
+    pub fn signatures(&self) -> Vec<PayloadSig<'_>> {
+        vec![
+            PayloadSig {
+                role: "revoker", pubkey: &self.revokerPubkey,
+                r: &self.rRevokerPubkeySig, s: &self.sRevokerPubkeySig, v: &self.vRevokerPubkeySig,
+                required: true,
+            },
+            PayloadSig {
+                role: "revokee", pubkey: &self.revokeePubkey,
+                r: &self.rRevokeePubkeySig, s: &self.sRevokeePubkeySig, v: &self.vRevokeePubkeySig,
+                required: false,
+            },
+        ]
+    }
+}
+
+impl InvalidationDecodedOrdered {
+    pub fn payload(&self) -> Result<Payload> {
+        Ok(Payload::Invalidation(InvalidationPayload {
+            invalidated_pubkey: hex_to_bytes(&self.invalidatedPubkey)?,
+            nonce: nonce16(&self.nonce)?,
+            contract_address: hex_to_bytes(&self.expectedAddressOfDeployedContract)?,
+        }))
+    }
+
+    pub fn signatures(&self) -> Vec<PayloadSig<'_>> {
+        vec![PayloadSig {
+            role: "invalidated key", pubkey: &self.invalidatedPubkey,
+            r: &self.rInvalidatedPubkeySig, s: &self.sInvalidatedPubkeySig, v: &self.vInvalidatedPubkeySig,
+            required: true,
+        }]
+    }
+}
@@ -19,6 +19,57 @@ impl Defaults {
     pub const REDELEGATION_INPUT_DIR: &'static str = "./input_files";
     pub const PERMANENT_INVALIDATION_INPUT_DIR: &'static str = "./input_files";
 
+    /* Batch signing */
+    pub const BATCH_INPUT_FILE: &'static str = "./input_files/batch.json";
+    pub const BATCH_OUT_DIR: &'static str = "./generated_transactions";
+    /// Batch template and its values (see `commands/batch_template.rs`).
+    pub const BATCH_TEMPLATE_FILE: &'static str = "./input_files/batch_template.json";
+    pub const BATCH_TEMPLATE_VALUES_FILE: &'static str = "./input_files/batch_values.json";
+    pub const BATCH_TEMPLATE_OUT_FILE: &'static str = "./input_files/batch_from_template.json";
+
+    /* Bulk re-delegation */
+    pub const BULK_REDELEGATION_PUBKEYS_FILE: &'static str = "./input_files/delegatee_pubkeys.txt";
+
+    /* Address table (see `commands/address_table.rs`) */
+    pub const ADDRESS_TABLE_PUBKEYS_FILE: &'static str = "./input_files/pubkeys.txt";
+    pub const ADDRESS_TABLE_OUT_FILE: &'static str = "./address_table.csv";
+
+    /* ABI selectors (see `screens/abi_selectors.rs`) */
+    pub const ABI_SELECTORS_OUT_FILE: &'static str = "./inkan_abi_selectors.txt";
+
+
+    /* Settings */
+    pub const SETTINGS_FILE: &'static str = "./inkan_settings.json";
+    /// Default path of an exported, signed settings bundle.
+    pub const SETTINGS_BUNDLE_FILE: &'static str = "./inkan_settings_bundle.json";
+    /// Default path of an exported, signed bundle of network profiles only.
+    pub const NETWORKS_BUNDLE_FILE: &'static str = "./inkan_networks_bundle.json";
+    /// Master-password profile descriptor (present only when a master password is set).
+    pub const PROFILE_FILE: &'static str = "./inkan_profile.json";
+    /// Per-key signing statistics and retired flags (see `key_usage.rs`).
+    pub const KEY_USAGE_FILE: &'static str = "./inkan_key_usage.json";
+    /// Hash-chained log of signing events (see `audit_log.rs`).
+    pub const AUDIT_LOG_FILE: &'static str = "./inkan_audit_log.json";
+    /// Opt-in local usage counters (see `usage_stats.rs`).
+    pub const USAGE_STATS_FILE: &'static str = "./inkan_usage_stats.json";
+    /// Default path of a passphrase-encrypted backup of all of the above (see `app_backup.rs`).
+    pub const APP_BACKUP_FILE: &'static str = "./inkan_app_backup.json";
+    /// Default path of an exported, signed snapshot of the audit log.
+    pub const AUDIT_PROOF_FILE: &'static str = "./inkan_audit_proof.json";
+
+    /* Workspaces (see `workspace.rs`): named workspaces are subdirectories of this one */
+    pub const WORKSPACES_DIR: &'static str = "workspaces";
+
+    /* Compatibility handshake (see `compat.rs`) */
+    pub const COMPAT_FILE: &'static str = "./compat.json";
+
+    /* Remote signer (see `remote_signer.rs`) */
+    /// USB gadget serial port as seen on the air-gapped machine.
+    pub const REMOTE_SIGNER_DEVICE: &'static str = "/dev/ttyGS0";
+    pub const REMOTE_SIGNER_BAUD: &'static str = "115200";
+
+    /* Verify Signed File: where files received from other organizations are kept */
+    pub const VERIFY_INPUT_DIR: &'static str = "./received_transactions";
 
     /* Decryption */
     pub const DECRYPT_OUTPUT_DIR: &'static str = "./decrypted_files";
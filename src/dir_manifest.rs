@@ -0,0 +1,199 @@
+//! Checksum manifest for a whole output directory, for checking a transfer from the air-gapped
+//! machine to the online one.
+//!
+//! `MANIFEST.sha256` lists every file below the directory in `sha256sum` format
+//! (`<hex>  <relative/path>`, sorted), so `sha256sum -c MANIFEST.sha256` checks it as well as
+//! this tool does. The optional detached signature `MANIFEST.sha256.sig` is
+//! `{ "signer", "signature" }`: an EIP-191 personal-message signature over
+//! `keccak256(<manifest bytes>)`, as for settings bundles.
+
+use anyhow::{bail, Context, Result};
+use ethers_core::types::Signature;
+use ethers_core::utils::{keccak256, to_checksum};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::signing::{open_signer, sign_message_eip191};
+use crate::util::{bytes_to_0x, hex_to_bytes, parse_addr, sha256_hex};
+use crate::vfs::Fs;
+
+pub const MANIFEST_FILE: &str = "MANIFEST.sha256";
+pub const MANIFEST_SIG_FILE: &str = "MANIFEST.sha256.sig";
+
+/// Longest list of problem files shown in the result; the rest are counted.
+const MAX_LISTED: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DetachedSignature {
+    /// Checksummed address of the signing key.
+    signer: String,
+    /// `0x || r || s || v` (65 bytes).
+    signature: String,
+}
+
+/// Files below `dir` (recursively) by `/`-separated relative path, without the manifest and
+/// its signature.
+fn walk(fs: &dyn Fs, dir: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut out = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(d) = pending.pop() {
+        for entry in fs.read_dir(&d).with_context(|| format!("listing {}", d.display()))? {
+            if entry.is_dir {
+                pending.push(entry.path);
+                continue;
+            }
+            let Ok(rel) = entry.path.strip_prefix(dir) else { continue };
+            let rel = rel.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+            if rel != MANIFEST_FILE && rel != MANIFEST_SIG_FILE {
+                out.insert(rel, entry.path);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Hash every file below `dir` into `MANIFEST.sha256` (replacing an older one) and, with a
+/// signing key, write the detached signature. Returns summary lines.
+pub async fn write_dir_manifest(fs: &dyn Fs, dir: &Path, privkey_input: Option<&str>) -> Result<Vec<String>> {
+    if !fs.is_dir(dir) {
+        bail!("{} is not a directory", dir.display());
+    }
+    let files = walk(fs, dir)?;
+    if files.is_empty() {
+        bail!("{} holds no files to list", dir.display());
+    }
+    let mut manifest = String::new();
+    for (rel, path) in &files {
+        let bytes = fs.read(path).with_context(|| format!("reading {}", path.display()))?;
+        manifest.push_str(&format!("{}  {rel}\n", sha256_hex(&bytes)));
+    }
+    let manifest_path = dir.join(MANIFEST_FILE);
+    fs.write(&manifest_path, manifest.as_bytes())
+        .with_context(|| format!("writing {}", manifest_path.display()))?;
+
+    let mut lines = vec![
+        format!("Manifest written: {}", manifest_path.display()),
+        format!("{} file(s) listed.", files.len()),
+    ];
+    let sig_path = dir.join(MANIFEST_SIG_FILE);
+    match privkey_input.map(str::trim).filter(|k| !k.is_empty()) {
+        Some(key) => {
+            let key = open_signer(key)?;
+            let signer = to_checksum(&key.address(), None);
+            let sig = sign_message_eip191(&*key, keccak256(manifest.as_bytes())).await?;
+            let detached = DetachedSignature { signer: signer.clone(), signature: bytes_to_0x(&sig.to_vec()) };
+            fs.write(&sig_path, serde_json::to_string_pretty(&detached)?.as_bytes())
+                .with_context(|| format!("writing {}", sig_path.display()))?;
+            lines.push(format!("Signed by {signer} ({MANIFEST_SIG_FILE})."));
+        }
+        None if fs.exists(&sig_path) => {
+            // A signature over an older manifest would no longer verify
+            fs.shred(&sig_path).with_context(|| format!("removing the outdated {}", sig_path.display()))?;
+            lines.push(format!("Not signed; the outdated {MANIFEST_SIG_FILE} was removed."));
+        }
+        None => lines.push("Not signed.".to_string()),
+    }
+    Ok(lines)
+}
+
+/// Check every file listed in `dir`'s manifest and the detached signature when there is one.
+/// With `expected_signer` set, the manifest must be signed by that address. Returns result
+/// lines, the first saying PASSED or FAILED; an invalid signature is an error.
+pub fn verify_dir_manifest(fs: &dyn Fs, dir: &Path, expected_signer: Option<&str>) -> Result<Vec<String>> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let manifest = fs.read(&manifest_path).with_context(|| format!("reading {}", manifest_path.display()))?;
+    let text = String::from_utf8(manifest.clone()).with_context(|| format!("{} is not text", manifest_path.display()))?;
+
+    let mut lines = Vec::new();
+
+    // Signature first: a bad one makes the listed hashes meaningless
+    let sig_path = dir.join(MANIFEST_SIG_FILE);
+    let expected = expected_signer.map(str::trim).filter(|s| !s.is_empty());
+    if fs.exists(&sig_path) {
+        let signer = check_signature(fs, &sig_path, &manifest, expected)?;
+        lines.push(format!("Signature valid, signed by {signer}."));
+    } else if expected.is_some() {
+        bail!("{} has no {MANIFEST_SIG_FILE}, but an expected signer was given", dir.display());
+    } else {
+        lines.push("Manifest is not signed.".to_string());
+    }
+
+    let mut listed: BTreeMap<String, String> = BTreeMap::new();
+    for (n, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        // `sha256sum` writes `<hex>  <path>`, or `<hex> *<path>` in binary mode
+        let Some((hash, rel)) = line.split_once(' ') else {
+            bail!("{} line {} is not `<sha256>  <path>`", manifest_path.display(), n + 1);
+        };
+        let rel = rel.strip_prefix(' ').or_else(|| rel.strip_prefix('*')).unwrap_or(rel);
+        listed.insert(rel.to_string(), hash.to_ascii_lowercase());
+    }
+
+    let present = walk(fs, dir)?;
+    let (mut changed, mut missing) = (Vec::new(), Vec::new());
+    for (rel, hash) in &listed {
+        match present.get(rel) {
+            Some(path) => {
+                let bytes = fs.read(path).with_context(|| format!("reading {}", path.display()))?;
+                if sha256_hex(&bytes) != *hash {
+                    changed.push(rel.clone());
+                }
+            }
+            None => missing.push(rel.clone()),
+        }
+    }
+    let unlisted: Vec<String> = present.keys().filter(|rel| !listed.contains_key(*rel)).cloned().collect();
+
+    let ok = changed.is_empty() && missing.is_empty();
+    lines.push(format!(
+        "{} of {} listed file(s) match.",
+        listed.len() - changed.len() - missing.len(),
+        listed.len()
+    ));
+    list(&mut lines, "Changed", &changed);
+    list(&mut lines, "Missing", &missing);
+    list(&mut lines, "Not in the manifest (not checked)", &unlisted);
+    lines.insert(0, if ok { "Manifest check PASSED".to_string() } else { "Manifest check FAILED".to_string() });
+    Ok(lines)
+}
+
+/// Verify the detached signature over `manifest`; returns the signer address.
+fn check_signature(fs: &dyn Fs, sig_path: &Path, manifest: &[u8], expected: Option<&str>) -> Result<String> {
+    let raw = fs.read(sig_path).with_context(|| format!("reading {}", sig_path.display()))?;
+    let detached: DetachedSignature = serde_json::from_slice(&raw)
+        .with_context(|| format!("parsing {}", sig_path.display()))?;
+    let sig_bytes = hex_to_bytes(&detached.signature).context("manifest signature is not hex")?;
+    let sig = Signature::try_from(sig_bytes.as_slice()).context("manifest signature must be 65 bytes")?;
+    let recovered = sig.recover(keccak256(manifest).to_vec()).context("recovering manifest signer")?;
+
+    let stated = parse_addr(&detached.signer).context("manifest signer is not an address")?;
+    if recovered != stated {
+        bail!(
+            "Manifest signature does not match: signed by {}, {MANIFEST_SIG_FILE} claims {}. The manifest was modified after signing.",
+            to_checksum(&recovered, None),
+            detached.signer
+        );
+    }
+    if let Some(want) = expected {
+        let want = parse_addr(want).context("expected signer is not an address")?;
+        if recovered != want {
+            bail!(
+                "Manifest was signed by {}, not the expected {}.",
+                to_checksum(&recovered, None),
+                to_checksum(&want, None)
+            );
+        }
+    }
+    Ok(to_checksum(&recovered, None))
+}
+
+fn list(lines: &mut Vec<String>, label: &str, files: &[String]) {
+    if files.is_empty() {
+        return;
+    }
+    lines.push(format!("{label}: {}", files.len()));
+    lines.extend(files.iter().take(MAX_LISTED).map(|f| format!("  {f}")));
+    if files.len() > MAX_LISTED {
+        lines.push(format!("  … and {} more", files.len() - MAX_LISTED));
+    }
+}
@@ -0,0 +1,189 @@
+//! How numbers and dates are shown to the operator (`Settings::display`): digit grouping and
+//! decimal mark per locale, gwei/ETH equivalents next to wei amounts, and timestamps in a fixed
+//! UTC offset. Only what is read on screen (and in the verification report) is localized;
+//! signed files, manifests and audit records keep plain integers and RFC 3339 UTC.
+
+use ethers_core::types::U256;
+use ethers_core::utils::format_units;
+use serde::{Deserialize, Serialize};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayLocale {
+    /// `1,234,567.5`, `2025-01-31 12:00:00`
+    #[default]
+    En,
+    /// `1.234.567,5`, `31.01.2025 12:00:00`
+    De,
+    /// `1 234 567,5`, `31/01/2025 12:00:00`
+    Fr,
+    /// `1234567.5`, `2025-01-31T12:00:00Z`: no grouping, as written to files
+    Plain,
+}
+
+impl DisplayLocale {
+    pub const ALL: [DisplayLocale; 4] = [DisplayLocale::En, DisplayLocale::De, DisplayLocale::Fr, DisplayLocale::Plain];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DisplayLocale::En => "English (1,234.5)",
+            DisplayLocale::De => "German (1.234,5)",
+            DisplayLocale::Fr => "French (1 234,5)",
+            DisplayLocale::Plain => "Plain (1234.5, RFC 3339)",
+        }
+    }
+
+    /// The next choice, for cycling in the settings.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|l| *l == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    fn group_separator(self) -> Option<char> {
+        match self {
+            DisplayLocale::En => Some(','),
+            DisplayLocale::De => Some('.'),
+            DisplayLocale::Fr => Some(' '),
+            DisplayLocale::Plain => None,
+        }
+    }
+
+    fn decimal_mark(self) -> char {
+        match self {
+            DisplayLocale::De | DisplayLocale::Fr => ',',
+            DisplayLocale::En | DisplayLocale::Plain => '.',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub locale: DisplayLocale,
+    /// Show gwei (fees per gas) or ETH (costs) next to wei amounts.
+    pub unit_equivalents: bool,
+    /// Offset dates are shown in, `+02:00` or `-05:30`; empty (or unparsable) = UTC.
+    pub utc_offset: String,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self { locale: DisplayLocale::default(), unit_equivalents: true, utc_offset: String::new() }
+    }
+}
+
+impl DisplaySettings {
+    /// A non-negative integer with the locale's digit grouping.
+    pub fn integer(&self, value: impl ToString) -> String {
+        let digits = value.to_string();
+        let Some(sep) = self.locale.group_separator() else { return digits };
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push(sep);
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// A fee per gas: `30,000,000,000 wei (30 gwei)`.
+    pub fn fee(&self, wei: U256) -> String {
+        self.wei_with(wei, "gwei")
+    }
+
+    /// A cost or value: `18,000,000,000,000,000 wei (0.018 ETH)`.
+    pub fn amount(&self, wei: U256) -> String {
+        self.wei_with(wei, "ether")
+    }
+
+    fn wei_with(&self, wei: U256, unit: &str) -> String {
+        let shown = format!("{} wei", self.integer(wei));
+        if !self.unit_equivalents {
+            return shown;
+        }
+        let Ok(decimal) = format_units(wei, unit) else { return shown };
+        let (int, frac) = decimal.split_once('.').unwrap_or((&decimal, ""));
+        let frac = frac.trim_end_matches('0');
+        let mut value = self.integer(int);
+        if !frac.is_empty() {
+            value.push(self.locale.decimal_mark());
+            value.push_str(frac);
+        }
+        let symbol = if unit == "ether" { "ETH" } else { unit };
+        format!("{shown} ({value} {symbol})")
+    }
+
+    fn offset(&self) -> UtcOffset {
+        parse_offset(self.utc_offset.trim()).unwrap_or(UtcOffset::UTC)
+    }
+
+    /// A moment in the configured offset and the locale's date order.
+    pub fn datetime(&self, t: OffsetDateTime) -> String {
+        let t = t.to_offset(self.offset());
+        let (y, mo, d) = (t.year(), u8::from(t.month()), t.day());
+        let clock = format!("{:02}:{:02}:{:02}", t.hour(), t.minute(), t.second());
+        let (h, m, _) = t.offset().as_hms();
+        let zone = if t.offset().is_utc() {
+            "UTC".to_string()
+        } else {
+            format!("UTC{}{:02}:{:02}", if t.offset().is_negative() { '-' } else { '+' }, h.abs(), m.abs())
+        };
+        match self.locale {
+            DisplayLocale::En => format!("{y:04}-{mo:02}-{d:02} {clock} {zone}"),
+            DisplayLocale::De => format!("{d:02}.{mo:02}.{y:04} {clock} {zone}"),
+            DisplayLocale::Fr => format!("{d:02}/{mo:02}/{y:04} {clock} {zone}"),
+            DisplayLocale::Plain => t
+                .format(&time::format_description::well_known::Rfc3339)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// A Unix timestamp (e.g. a decoded start or end time), or the number as is when out of range.
+    pub fn unix_time(&self, secs: i64) -> String {
+        OffsetDateTime::from_unix_timestamp(secs).map(|t| self.datetime(t)).unwrap_or_else(|_| secs.to_string())
+    }
+
+    /// A delegation or revocation window from its start and end (0 = open on that side).
+    pub fn window(&self, start: u64, end: u64) -> String {
+        let from = if start == 0 { "at once".to_string() } else { format!("from {}", self.unix_time(i64::try_from(start).unwrap_or(i64::MAX))) };
+        let until = if end == 0 { "no end".to_string() } else { format!("until {}", self.unix_time(i64::try_from(end).unwrap_or(i64::MAX))) };
+        format!("{from}, {until}")
+    }
+
+    /// An RFC 3339 UTC timestamp as this tool writes it (`2025-01-31T12:00:00Z`, fractions
+    /// allowed); anything else is shown unchanged.
+    pub fn rfc3339(&self, ts: &str) -> String {
+        parse_utc_rfc3339(ts).map(|t| self.datetime(t)).unwrap_or_else(|| ts.to_string())
+    }
+}
+
+/// `+HH:MM` / `-HH:MM` (or `Z`).
+fn parse_offset(s: &str) -> Option<UtcOffset> {
+    if s.eq_ignore_ascii_case("z") || s.eq_ignore_ascii_case("utc") {
+        return Some(UtcOffset::UTC);
+    }
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (h, m) = rest.split_once(':').unwrap_or((rest, "0"));
+    let (h, m): (i8, i8) = (h.parse().ok()?, m.parse().ok()?);
+    UtcOffset::from_hms(sign * h, sign * m, 0).ok()
+}
+
+/// `YYYY-MM-DDTHH:MM:SS[.fff]Z`, without the time crate's parsing feature.
+fn parse_utc_rfc3339(ts: &str) -> Option<OffsetDateTime> {
+    let ts = ts.strip_suffix('Z')?;
+    let (date, clock) = ts.split_once('T')?;
+    let mut d = date.splitn(3, '-').map(str::parse::<i32>);
+    let (y, mo, day) = (d.next()?.ok()?, d.next()?.ok()?, d.next()?.ok()?);
+    let clock = clock.split('.').next()?;
+    let mut c = clock.splitn(3, ':').map(str::parse::<u8>);
+    let (h, mi, s) = (c.next()?.ok()?, c.next()?.ok()?, c.next()?.ok()?);
+    let date = Date::from_calendar_date(y, Month::try_from(u8::try_from(mo).ok()?).ok()?, u8::try_from(day).ok()?).ok()?;
+    let time = Time::from_hms(h, mi, s).ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_utc())
+}
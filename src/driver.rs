@@ -0,0 +1,349 @@
+//! In-memory driver for the TUI: the real screen stack, rendered into ratatui's `TestBackend`
+//! and fed synthesized key events, so whole flows ("fill the delegation form → submit → file
+//! written") can be exercised without a terminal.
+//!
+//! Compiled into tests and into builds with the `script` feature, where `--script <file>` runs a
+//! key script through the driver, one command per line:
+//!
+//! ```text
+//! # comment
+//! key Down          # one key press; a comment may follow a key
+//! key Ctrl+S
+//! type ./input.json
+//! expect Sign Batch
+//! screen
+//! events
+//! memfs
+//! put ./input_files/a.env DELEGATOR_PRIVKEY=0x…
+//! expect-file ./generated_transactions/createDelegationEvent_nonce_0.txt
+//! ```
+//!
+//! Key names are `Enter`, `Esc`, `Tab`, `BackTab`, `Up`, `Down`, `Left`, `Right`, `Home`, `End`,
+//! `Backspace`, `Delete`, `Space`, `F1`–`F12` or a single character, optionally prefixed with
+//! `Ctrl+`, `Alt+` and `Shift+`. `type` presses each character of the rest of the line, `expect`
+//! fails the script unless the rendered screen contains the rest of the line, and `screen`
+//! prints the rendered screen to stdout. `events` prints the state events applied so far (which
+//! screen loaded, took or kept which prefill or draft; see `app_state`). `memfs` switches the screens to an empty in-memory
+//! filesystem, so nothing the script does touches the disk; `put <path> <text>` creates a file
+//! (and its directories) there, and `expect-file <path>` fails unless the path exists.
+//!
+//! After every key, a busy screen (e.g. a signing batch) is stepped until it is idle, as the
+//! event loop would between key presses.
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use std::path::Path;
+
+use crate::app::{dispatch_key, draw_stack, finish_busy, initial_state, tick_top, AppCtx, ScreenWidget};
+use crate::vfs::FsRef;
+#[cfg(feature = "script")]
+use crate::vfs::MemFs;
+
+/// Upper bound on busy steps after one key, so a screen that stays busy (a remote-signer
+/// session) cannot hang a script.
+const MAX_TICKS: usize = 100_000;
+
+pub struct Driver {
+    terminal: Terminal<TestBackend>,
+    stack: Vec<Box<dyn ScreenWidget>>,
+    ctx: AppCtx,
+    quit: bool,
+    /// Notes busy screens left when the app quit (e.g. where a partial batch went).
+    pub exit_notes: Vec<String>,
+}
+
+impl Driver {
+    /// Start where the app starts (unlock screen or main menu), on a `width`×`height` screen.
+    pub fn new(width: u16, height: u16) -> Result<Self> {
+        let (ctx, stack) = initial_state();
+        Self::with_stack(width, height, ctx, stack)
+    }
+
+    /// Start from a given context and screen stack.
+    pub fn with_stack(width: u16, height: u16, ctx: AppCtx, stack: Vec<Box<dyn ScreenWidget>>) -> Result<Self> {
+        let terminal = Terminal::new(TestBackend::new(width, height))?;
+        Ok(Self { terminal, stack, ctx, quit: false, exit_notes: Vec::new() })
+    }
+
+    /// Replace the filesystem the screens see (see `vfs`).
+    pub fn use_fs(&mut self, fs: FsRef) {
+        self.ctx.fs = fs;
+    }
+
+    /// Whether `path` exists as a file or directory on the screens' filesystem.
+    pub fn exists(&self, path: &Path) -> bool {
+        self.ctx.fs.exists(path)
+    }
+
+    /// Press one key, then let a busy screen run until idle.
+    pub async fn press(&mut self, k: KeyEvent) -> Result<()> {
+        if self.quit {
+            bail!("the app has already quit");
+        }
+        // The event loop applies pending prefills before each draw, i.e. before the next key.
+        if let Some(top) = self.stack.last_mut() {
+            top.apply_prefill(&mut self.ctx);
+        }
+        let mut scrub = false;
+        self.quit = dispatch_key(&mut self.stack, &mut self.ctx, k, &mut scrub).await?;
+        let mut ticks = 0;
+        while !self.quit && ticks < MAX_TICKS && self.stack.last().is_some_and(|s| s.busy().is_some()) {
+            self.quit = tick_top(&mut self.stack, &mut self.ctx, &mut scrub).await?;
+            ticks += 1;
+        }
+        if self.quit {
+            finish_busy(&mut self.stack, &self.ctx, &mut self.exit_notes);
+        }
+        Ok(())
+    }
+
+    pub async fn key(&mut self, code: KeyCode) -> Result<()> {
+        self.press(KeyEvent::new(code, KeyModifiers::NONE)).await
+    }
+
+    /// Type `text` one character at a time.
+    pub async fn type_text(&mut self, text: &str) -> Result<()> {
+        for c in text.chars() {
+            self.key(KeyCode::Char(c)).await?;
+        }
+        Ok(())
+    }
+
+    /// The state events applied so far (see `app_state`), oldest first: `<screen>: <what>`.
+    pub fn events(&self) -> Vec<String> {
+        self.ctx.state.log().map(|e| format!("{}: {}", e.source, e.summary)).collect()
+    }
+
+    /// Render the stack and return the screen as text, one line per row.
+    pub fn screen(&mut self) -> Result<String> {
+        if let Some(top) = self.stack.last_mut() {
+            top.apply_prefill(&mut self.ctx);
+        }
+        let (stack, ctx) = (&self.stack, &self.ctx);
+        self.terminal.draw(|f| draw_stack(f, stack, ctx))?;
+        let buf = self.terminal.backend().buffer();
+        let width = buf.area.width as usize;
+        let cells: Vec<&str> = buf.content.iter().map(|c| c.symbol()).collect();
+        Ok(cells.chunks(width).map(|row| row.concat().trim_end().to_string()).collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// Parse a key name such as `Enter`, `Ctrl+S`, `Shift+Tab` or `x`.
+pub fn parse_key(name: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name.trim();
+    loop {
+        let (m, tail) = match rest.split_once('+') {
+            Some(("Ctrl", tail)) => (KeyModifiers::CONTROL, tail),
+            Some(("Alt", tail)) => (KeyModifiers::ALT, tail),
+            Some(("Shift", tail)) => (KeyModifiers::SHIFT, tail),
+            _ => break,
+        };
+        modifiers |= m;
+        rest = tail;
+    }
+    let code = match rest {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Space" => KeyCode::Char(' '),
+        f if f.len() > 1 && f.starts_with('F') => KeyCode::F(f[1..].parse().with_context(|| format!("unknown key '{name}'"))?),
+        c if c.chars().count() == 1 => KeyCode::Char(c.chars().next().unwrap_or(' ')),
+        _ => return Err(anyhow!("unknown key '{name}'")),
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Run a key script (see the module docs) from the app's start screen.
+#[cfg(feature = "script")]
+pub async fn run_script(path: &Path) -> Result<()> {
+    let script = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut driver = Driver::new(120, 48)?;
+    for (i, raw) in script.lines().enumerate() {
+        let line = raw.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (cmd, arg) = line.split_once(' ').unwrap_or((line, ""));
+        let at = || format!("{}:{}", path.display(), i + 1);
+        match cmd {
+            "key" => {
+                let name = arg.split(" #").next().unwrap_or("").trim();
+                driver.press(parse_key(name).with_context(at)?).await.with_context(at)?;
+            }
+            "type" => driver.type_text(arg).await.with_context(at)?,
+            "expect" => {
+                let screen = driver.screen()?;
+                if !screen.contains(arg) {
+                    bail!("{}: expected \"{arg}\" on screen:\n{screen}", at());
+                }
+            }
+            "screen" => println!("{}\n", driver.screen()?),
+            "events" => println!("{}\n", driver.events().join("\n")),
+            "memfs" => driver.use_fs(FsRef(std::sync::Arc::new(MemFs::new()))),
+            "put" => {
+                let (file, text) = arg.split_once(' ').unwrap_or((arg, ""));
+                let file = Path::new(file);
+                if let Some(parent) = file.parent() {
+                    driver.ctx.fs.create_dir_all(parent).with_context(at)?;
+                }
+                driver.ctx.fs.write(file, text.as_bytes()).with_context(at)?;
+            }
+            "expect-file" => {
+                if !driver.exists(Path::new(arg)) {
+                    bail!("{}: expected file {arg} to exist", at());
+                }
+            }
+            other => bail!("{}: unknown command '{other}'", at()),
+        }
+    }
+    for note in &driver.exit_notes {
+        println!("{note}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex, MutexGuard};
+
+    use crate::vfs::MemFs;
+
+    const K1: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const K2: &str = "0x8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f";
+
+    /// Where the delegation of K1 to K2 with transaction nonce 0 is written.
+    const DELEGATION_FILE: &str =
+        "./generated_transactions/4e3b81af..8a25de6e_delegates_to_d11e9491..d8de3cc9_nonce_0.txt";
+
+    /// The stores (settings, key usage, audit log) live in the working directory, which the
+    /// whole process shares: flows take turns, each in a fresh empty directory.
+    static CWD: Mutex<()> = Mutex::new(());
+
+    struct TempCwd {
+        dir: PathBuf,
+        prev: PathBuf,
+        _turn: MutexGuard<'static, ()>,
+    }
+
+    impl TempCwd {
+        fn enter(name: &str) -> Self {
+            let turn = CWD.lock().unwrap_or_else(|e| e.into_inner());
+            let dir = std::env::temp_dir().join(format!("inkan_driver_{name}_{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let prev = std::env::current_dir().unwrap();
+            std::env::set_current_dir(&dir).unwrap();
+            Self { dir, prev, _turn: turn }
+        }
+    }
+
+    impl Drop for TempCwd {
+        fn drop(&mut self) {
+            let _ = std::env::set_current_dir(&self.prev);
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    /// Run one flow from the start screen with the screens on an empty `MemFs`.
+    fn flow(name: &str, f: impl AsyncFnOnce(&mut Driver) -> Result<()>) {
+        let _cwd = TempCwd::enter(name);
+        let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        rt.block_on(async {
+            let mut d = Driver::new(120, 48)?;
+            d.use_fs(FsRef(Arc::new(MemFs::new())));
+            f(&mut d).await
+        })
+        .unwrap();
+    }
+
+    /// Open a tool through the main menu's search.
+    async fn open_tool(d: &mut Driver, search: &str) -> Result<()> {
+        d.key(KeyCode::Down).await?;
+        d.key(KeyCode::Down).await?;
+        d.key(KeyCode::Enter).await?;
+        d.type_text(search).await?;
+        d.key(KeyCode::Enter).await
+    }
+
+    async fn tab(d: &mut Driver, times: usize) -> Result<()> {
+        for _ in 0..times {
+            d.key(KeyCode::Tab).await?;
+        }
+        Ok(())
+    }
+
+    fn assert_on_screen(d: &mut Driver, text: &str) {
+        let screen = d.screen().unwrap();
+        assert!(screen.contains(text), "expected \"{text}\" on screen:\n{screen}");
+    }
+
+    #[test]
+    fn create_delegation_writes_the_signed_file() {
+        flow("delegation", async |d| {
+            open_tool(d, "create delegation").await?;
+            assert_on_screen(d, "Delegator PrivKey");
+            d.type_text(K1).await?;
+            tab(d, 1).await?;
+            d.type_text(K2).await?;
+            tab(d, 5).await?; // Transaction Nonce
+            d.type_text("0").await?;
+            tab(d, 7).await?; // Create Delegation
+            d.key(KeyCode::Enter).await?;
+            assert_on_screen(d, "Contract Call Preview");
+            assert!(!d.exists(Path::new(DELEGATION_FILE)), "written before the preview was confirmed");
+
+            d.key(KeyCode::Enter).await?; // Write Signed File
+            assert_on_screen(d, "Saved signed transaction:");
+            assert!(d.exists(Path::new(DELEGATION_FILE)));
+            let written = String::from_utf8(d.ctx.fs.read(Path::new(DELEGATION_FILE))?)?;
+            assert!(written.contains("\"funcName\": \"createDelegationEvent\""), "{written}");
+            assert!(written.contains("0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"), "{written}");
+
+            // Only the stores reach the disk
+            assert!(!Path::new("generated_transactions").exists());
+            assert!(d.exit_notes.is_empty());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn create_delegation_without_a_key_writes_nothing() {
+        flow("delegation_no_key", async |d| {
+            open_tool(d, "create delegation").await?;
+            tab(d, 13).await?; // Create Delegation
+            d.key(KeyCode::Enter).await?;
+            assert_on_screen(d, "Error:");
+            d.key(KeyCode::Enter).await?; // OK
+            assert_on_screen(d, "Delegator PrivKey");
+            assert!(d.ctx.fs.read_dir(Path::new("./generated_transactions")).is_err());
+            assert!(d.events().iter().any(|e| e.starts_with("Startup:")), "{:?}", d.events());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn parse_key_names() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(parse_key("Enter").unwrap(), key(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(parse_key("Ctrl+S").unwrap(), key(KeyCode::Char('S'), KeyModifiers::CONTROL));
+        assert_eq!(parse_key("Ctrl+Shift+Tab").unwrap(), key(KeyCode::Tab, KeyModifiers::CONTROL | KeyModifiers::SHIFT));
+        assert_eq!(parse_key("F5").unwrap(), key(KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(parse_key("Space").unwrap(), key(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(parse_key("+").unwrap(), key(KeyCode::Char('+'), KeyModifiers::NONE));
+        assert!(parse_key("Fx").is_err());
+        assert!(parse_key("Return").is_err());
+    }
+}
@@ -1,19 +1,15 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use ethers_core::abi::Token;
 use uuid::Uuid;
 
+use crate::units::PayloadNonce;
 use crate::util::{hex_to_bytes, parse_u256_any};
 
+/// The given payload nonce (see `units::PayloadNonce`), or a random UUID when `None` or empty.
 pub fn bytes16_or_random(opt_hex: Option<&str>) -> Result<Token> {
-    let bytes = if let Some(h) = opt_hex {
-        hex_to_bytes(h)?
-    } else {
-        Uuid::new_v4().as_bytes().to_vec()
-    };
-    if bytes.len() != 16 {
-        return Err(anyhow!("bytes16 must be 16 bytes, got {}", bytes.len()));
-    }
-    Ok(Token::FixedBytes(bytes))
+    let given: PayloadNonce = opt_hex.unwrap_or("").parse()?;
+    let bytes = given.0.unwrap_or_else(|| *Uuid::new_v4().as_bytes());
+    Ok(Token::FixedBytes(bytes.to_vec()))
 }
 
 pub fn t_bytes(hex0x: &str) -> Result<Token> {
@@ -0,0 +1,145 @@
+//! Machine-readable progress events for monitoring on the signing host (e.g. a ceremony
+//! recorder): one JSON object per line, appended to a file or sent to a UNIX stream socket
+//! named by `Settings::event_stream`.
+//!
+//! Every line carries `"v": 1` and an RFC 3339 `ts`; the `event` field says which of the
+//! shapes in [`Event`] follows. New fields may be added within a version, existing ones are
+//! not renamed or removed. Batch events carry `run`, the first 8 hex digits of the input's
+//! SHA-256 (the same suffix as the batch run directory).
+//!
+//! The stream is best effort: a missing socket or a failed write never stops signing. The
+//! connection is retried at the next event, and a reader that stalls is dropped after
+//! `WRITE_TIMEOUT`. Like the settings file, the target is not subject to the sandbox.
+
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Version of the line format, emitted as `v`.
+pub const EVENT_STREAM_VERSION: u32 = 1;
+
+/// How long a socket reader may block a write before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+#[allow(non_snake_case)]
+pub enum Event {
+    /// A batch item is about to be signed (`index` counts from 0).
+    ItemStarted { run: String, index: usize, total: usize, funcName: String },
+    /// A batch item was signed; `from` and `nonce` are those of the signed transaction.
+    ItemSigned { run: String, index: usize, total: usize, funcName: String, from: String, nonce: u64, chainId: String },
+    /// A file was written: a signed transaction file, a manifest or an error report (absolute path).
+    FileWritten {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        run: Option<String>,
+        path: String,
+    },
+    /// An operation stopped with an error (`index` is the failing batch item, if any).
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        run: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<usize>,
+        message: String,
+    },
+}
+
+impl Event {
+    /// `file_written` with `path` made absolute, so a monitor need not know the working directory.
+    pub fn file_written(run: Option<String>, path: &Path) -> Self {
+        Event::FileWritten { run, path: crate::vfs::resolve(path).display().to_string() }
+    }
+}
+
+#[derive(Serialize)]
+struct Line<'a> {
+    v: u32,
+    ts: String,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+enum Sink {
+    File(File),
+    #[cfg(unix)]
+    Socket(std::os::unix::net::UnixStream),
+}
+
+impl Sink {
+    /// A UNIX socket at `path` is connected to; anything else is opened for appending.
+    fn open(path: &Path) -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if std::fs::metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+                let sock = std::os::unix::net::UnixStream::connect(path)?;
+                sock.set_write_timeout(Some(WRITE_TIMEOUT))?;
+                return Ok(Sink::Socket(sock));
+            }
+        }
+        OpenOptions::new().create(true).append(true).open(path).map(Sink::File)
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        match self {
+            Sink::File(f) => f.write_all(line),
+            #[cfg(unix)]
+            Sink::Socket(s) => s.write_all(line),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// `Settings::event_stream`, trimmed; empty = off.
+    target: String,
+    sink: Option<Sink>,
+}
+
+/// Shared handle to the event stream; follows `Settings::event_stream` via `AppCtx::set_settings`.
+#[derive(Clone, Default)]
+pub struct EventStream(Arc<Mutex<State>>);
+
+impl EventStream {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.0.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Point the stream at `target` (a file or socket path; empty = off).
+    pub fn set_target(&self, target: &str) {
+        let mut state = self.state();
+        if state.target != target.trim() {
+            state.target = target.trim().to_string();
+            state.sink = None;
+        }
+    }
+
+    /// Send one event line, (re)connecting first if needed. Failures are swallowed.
+    pub fn emit(&self, event: Event) {
+        let mut state = self.state();
+        if state.target.is_empty() {
+            return;
+        }
+        let line = Line {
+            v: EVENT_STREAM_VERSION,
+            ts: time::OffsetDateTime::now_utc()
+                .format(&time::format_description::well_known::Rfc3339)
+                .unwrap_or_default(),
+            event: &event,
+        };
+        let Ok(mut bytes) = serde_json::to_vec(&line) else { return };
+        bytes.push(b'\n');
+        if state.sink.is_none() {
+            state.sink = Sink::open(Path::new(&state.target)).ok();
+        }
+        if let Some(sink) = state.sink.as_mut() {
+            if sink.write_line(&bytes).is_err() {
+                state.sink = None;
+            }
+        }
+    }
+}
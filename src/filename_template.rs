@@ -0,0 +1,214 @@
+//! Placeholder-based filename templates for signed transaction outputs.
+//!
+//! A template is a plain filename with `{placeholder}` tokens, e.g.
+//! `{delegator}_delegates_to_{delegatee}_nonce_{nonce}.txt`.
+//! Templates are configured per event type in the settings file; the defaults
+//! reproduce the historical hard-coded names exactly.
+
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::process::{normalize_pubkey_to_uncompressed_0x04, privkey_bytes_from_input};
+use crate::types::{DecodedOne, DecodedTxOut};
+
+/// Per-event-type filename templates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct FilenameTemplates {
+    pub delegation: String,
+    pub revocation: String,
+    pub redelegation: String,
+    pub permanent_invalidation: String,
+}
+
+impl Default for FilenameTemplates {
+    fn default() -> Self {
+        Self {
+            delegation: "{delegator}_delegates_to_{delegatee}_nonce_{nonce}.txt".to_string(),
+            revocation: "{revoker}_revokes_from_{revokee}_nonce_{nonce}.txt".to_string(),
+            redelegation: "{revoker}_revokes_from_{revokee}_delegates_to_{delegatee}_nonce_{nonce}.txt".to_string(),
+            permanent_invalidation: "invalidate_{invalidated}_nonce_{nonce}.txt".to_string(),
+        }
+    }
+}
+
+impl FilenameTemplates {
+    /// Template for a given ABI function name, if it is one of ours.
+    pub fn for_func(&self, func_name: &str) -> Option<&str> {
+        match func_name {
+            "createDelegationEvent" => Some(&self.delegation),
+            "createRevocationEvent" => Some(&self.revocation),
+            "createRevocationEventFollowedByDelegationEvent" => Some(&self.redelegation),
+            "createPermanentInvalidationEvent" => Some(&self.permanent_invalidation),
+            _ => None,
+        }
+    }
+}
+
+/// Key roles that contribute `{role}` and `{role8}` placeholders.
+#[derive(Copy, Clone, Debug)]
+pub enum KeyRole {
+    Delegator,
+    Delegatee,
+    Revoker,
+    Revokee,
+    Invalidated,
+}
+
+impl KeyRole {
+    fn names(self) -> (&'static str, &'static str) {
+        match self {
+            KeyRole::Delegator => ("delegator", "delegator8"),
+            KeyRole::Delegatee => ("delegatee", "delegatee8"),
+            KeyRole::Revoker => ("revoker", "revoker8"),
+            KeyRole::Revokee => ("revokee", "revokee8"),
+            KeyRole::Invalidated => ("invalidated", "invalidated8"),
+        }
+    }
+}
+
+pub type FilenameVars = HashMap<&'static str, String>;
+
+/// Variables every template can use: `{func}`, `{nonce}`, `{chain}`, `{date}`.
+pub fn base_vars(func_name: &str, nonce: &str, chain_id: &str) -> FilenameVars {
+    let mut vars = FilenameVars::new();
+    vars.insert("func", func_name.to_string());
+    if !nonce.is_empty() {
+        vars.insert("nonce", nonce.to_string());
+    }
+    if !chain_id.is_empty() {
+        vars.insert("chain", chain_id.to_string());
+    }
+    vars.insert("date", today_yyyymmdd());
+    vars
+}
+
+/// Insert `{label}`: the item's label, or empty without one (so a template using it still renders).
+pub fn insert_label(vars: &mut FilenameVars, label: Option<&str>) {
+    vars.insert("label", label.unwrap_or_default().trim().to_string());
+}
+
+/// Insert `{role}` (first8..last8) and `{role8}` from a 64-char X coordinate.
+pub fn insert_key_vars(vars: &mut FilenameVars, role: KeyRole, x64: &str) {
+    let (full, short) = role.names();
+    vars.insert(full, abbrev_64_hex(x64));
+    vars.insert(short, x64.chars().take(8).collect());
+}
+
+/// Collect all variables available for a decoded transaction.
+pub fn vars_from_decoded(decoded: &DecodedTxOut) -> FilenameVars {
+    let mut vars = base_vars(&decoded.funcName, &decoded.nonce.to_string(), &decoded.chainId);
+
+    match decoded.decodedData.as_ref() {
+        Some(DecodedOne::Delegation(a)) => {
+            insert_from_uncompressed(&mut vars, KeyRole::Delegator, &a.delegatorPubkey);
+            insert_from_uncompressed(&mut vars, KeyRole::Delegatee, &a.delegateePubkey);
+        }
+        Some(DecodedOne::Revocation(b)) => {
+            insert_from_uncompressed(&mut vars, KeyRole::Revoker, &b.revokerPubkey);
+            insert_from_uncompressed(&mut vars, KeyRole::Revokee, &b.revokeePubkey);
+        }
+        Some(DecodedOne::Invalidation(i)) => {
+            insert_from_uncompressed(&mut vars, KeyRole::Invalidated, &i.invalidatedPubkey);
+        }
+        None => {}
+    }
+
+    // Re-delegation combo: A = delegation side, B = revocation side
+    if let Some(a) = decoded.decodedDataTypeA.as_ref() {
+        insert_from_uncompressed(&mut vars, KeyRole::Delegator, &a.delegatorPubkey);
+        insert_from_uncompressed(&mut vars, KeyRole::Delegatee, &a.delegateePubkey);
+    }
+    if let Some(b) = decoded.decodedDataTypeB.as_ref() {
+        insert_from_uncompressed(&mut vars, KeyRole::Revoker, &b.revokerPubkey);
+        insert_from_uncompressed(&mut vars, KeyRole::Revokee, &b.revokeePubkey);
+    }
+
+    vars
+}
+
+fn insert_from_uncompressed(vars: &mut FilenameVars, role: KeyRole, uncompressed_hex: &str) {
+    if let Some(x) = x_coord_hex_from_uncompressed(uncompressed_hex) {
+        insert_key_vars(vars, role, &x);
+    }
+}
+
+/// Render a template strictly: returns None if any referenced placeholder is
+/// unknown or has no value (callers fall back to a generic name).
+pub fn render_template(template: &str, vars: &FilenameVars) -> Option<String> {
+    render_with(template, |name| vars.get(name).cloned())
+}
+
+/// Render a template for on-screen preview: missing values are shown as `?`.
+pub fn render_template_preview(template: &str, vars: &FilenameVars) -> String {
+    render_with(template, |name| Some(vars.get(name).cloned().unwrap_or_else(|| "?".to_string())))
+        .unwrap_or_default()
+}
+
+fn render_with<F: Fn(&str) -> Option<String>>(template: &str, lookup: F) -> Option<String> {
+    let mut out = String::with_capacity(template.len() + 32);
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let close = after.find('}')?;
+        let name = &after[..close];
+        out.push_str(&lookup(name)?);
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+
+    Some(sanitize_rendered(&out))
+}
+
+/// Keep rendered names inside the output directory: no path separators, no empty names.
+fn sanitize_rendered(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c == '/' || c == '\\' || c.is_control() { '_' } else { c })
+        .collect();
+    let trimmed = cleaned.trim().trim_start_matches('.');
+    if trimmed.is_empty() { "transaction.txt".to_string() } else { trimmed.to_string() }
+}
+
+/// X coordinate (64 hex chars) derived from a private key input (hex or nsec).
+/// Returns None while the input is incomplete or invalid.
+pub fn x_coord_from_privkey_input(input: &str) -> Option<String> {
+    let sk_bytes = privkey_bytes_from_input(input).ok()?;
+    let sk = SigningKey::from_slice(&sk_bytes).ok()?;
+    let pt = sk.verifying_key().to_encoded_point(false);
+    Some(hex::encode(&pt.as_bytes()[1..33]))
+}
+
+/// X coordinate (64 hex chars) from any accepted pubkey input (compressed/uncompressed).
+pub fn x_coord_from_pubkey_input(input: &str) -> Option<String> {
+    let normalized = normalize_pubkey_to_uncompressed_0x04(input).ok()?;
+    x_coord_hex_from_uncompressed(&normalized)
+}
+
+/// Extract the 32-byte X coordinate (64 hex chars) from an uncompressed pubkey hex.
+/// Accepts "0x04..." or "04..." (hex), must be 65 bytes = 130 hex chars.
+pub fn x_coord_hex_from_uncompressed(uncompressed_hex: &str) -> Option<String> {
+    let h = uncompressed_hex.strip_prefix("0x").unwrap_or(uncompressed_hex);
+    if !h.starts_with("04") || h.len() != 130 {
+        return None;
+    }
+    Some(h[2..66].to_ascii_lowercase())
+}
+
+/// Abbreviate a 64-char hex string as "first8..last8".
+pub fn abbrev_64_hex(x64: &str) -> String {
+    if x64.len() >= 16 {
+        format!("{}..{}", &x64[..8], &x64[x64.len() - 8..])
+    } else {
+        x64.to_string()
+    }
+}
+
+/// Current UTC date as YYYYMMDD.
+pub fn today_yyyymmdd() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    format!("{:04}{:02}{:02}", now.year(), u8::from(now.month()), now.day())
+}
@@ -0,0 +1,257 @@
+//! Startup checks of the machine the tool runs on: things outside the tool that can leak what it
+//! shows or holds (a recorded terminal, unencrypted swap, output directories other users can read,
+//! core dumps). Each check reports what it found and what to do about it; nothing is changed.
+//! The report opens at launch when a check warns, and from Advanced Tools → Configuration.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::commands::recovery::output_dirs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Warn,
+    /// The check cannot be made here (another platform, or nothing to read it from).
+    Unknown,
+}
+
+impl Status {
+    fn tag(self) -> &'static str {
+        match self {
+            Status::Ok => "[OK]",
+            Status::Warn => "[WARN]",
+            Status::Unknown => "[??]",
+        }
+    }
+}
+
+/// The result of one check.
+pub struct Finding {
+    pub status: Status,
+    pub what: String,
+    /// What to do about it; empty when there is nothing to do.
+    pub suggestion: String,
+}
+
+impl Finding {
+    fn ok(what: impl Into<String>) -> Self {
+        Self { status: Status::Ok, what: what.into(), suggestion: String::new() }
+    }
+
+    fn warn(what: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self { status: Status::Warn, what: what.into(), suggestion: suggestion.into() }
+    }
+
+    fn unknown(what: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self { status: Status::Unknown, what: what.into(), suggestion: suggestion.into() }
+    }
+}
+
+/// Run every check.
+pub fn check() -> Vec<Finding> {
+    let mut out = Vec::new();
+    out.extend(check_recording());
+    out.extend(check_swap());
+    out.extend(check_output_dirs());
+    out.push(check_core_dumps());
+    out
+}
+
+/// True when any finding is a warning, i.e. the report is worth showing at launch.
+pub fn any_warning(findings: &[Finding]) -> bool {
+    findings.iter().any(|f| f.status == Status::Warn)
+}
+
+/// The report as text lines, warnings first.
+pub fn report_lines(findings: &[Finding]) -> Vec<String> {
+    let mut sorted: Vec<&Finding> = findings.iter().collect();
+    sorted.sort_by_key(|f| match f.status {
+        Status::Warn => 0,
+        Status::Unknown => 1,
+        Status::Ok => 2,
+    });
+    let mut lines = Vec::new();
+    for f in sorted {
+        lines.push(format!("{} {}", f.status.tag(), f.what));
+        if !f.suggestion.is_empty() {
+            lines.push(format!("     → {}", f.suggestion));
+        }
+    }
+    lines
+}
+
+/// Session recorders among our ancestors, and tmux logging this pane.
+fn check_recording() -> Vec<Finding> {
+    let mut out = Vec::new();
+    match recorder_ancestor() {
+        Some(name) => out.push(Finding::warn(
+            format!("The terminal session is being recorded by `{name}`."),
+            "Start the tool outside the recorder, or destroy the recording after the ceremony.",
+        )),
+        None if cfg!(target_os = "linux") => out.push(Finding::ok("No session recorder (script, asciinema, ttyrec) runs this terminal.")),
+        None => {}
+    }
+    if std::env::var_os("TMUX").is_some() {
+        let piped = Command::new("tmux")
+            .args(["display-message", "-p", "#{pane_pipe}"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "1");
+        out.push(match piped {
+            Some(true) => Finding::warn(
+                "tmux pipe-pane is logging this pane to a command or file.",
+                "Stop it with `tmux pipe-pane` (no arguments) before any secret is shown, and delete the log.",
+            ),
+            Some(false) => Finding::ok("tmux is not piping this pane anywhere (its scrollback still survives scrubbing)."),
+            None => Finding::unknown("Running in tmux, but pipe-pane logging could not be queried.", "Check for `pipe-pane` in your tmux configuration."),
+        });
+    }
+    if std::env::var_os("STY").is_some() {
+        out.push(Finding::unknown(
+            "Running in GNU screen, whose logging (C-a H) cannot be checked from inside.",
+            "Make sure logging is off for this window (no `deflog on` or `log on` in .screenrc).",
+        ));
+    }
+    out
+}
+
+/// Name of a session-recording program this process runs under, if any.
+#[cfg(target_os = "linux")]
+fn recorder_ancestor() -> Option<String> {
+    const RECORDERS: [&str; 4] = ["script", "asciinema", "ttyrec", "termrec"];
+    let mut pid = std::process::id();
+    // Walk up to init, bounded in case /proc answers strangely
+    for _ in 0..64 {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // "pid (comm) state ppid ...": comm may contain spaces, so split at the last ')'
+        let (head, tail) = stat.rsplit_once(')')?;
+        let comm = head.split_once('(')?.1;
+        if RECORDERS.contains(&comm) {
+            return Some(comm.to_string());
+        }
+        pid = tail.split_whitespace().nth(1)?.parse().ok()?;
+        if pid <= 1 {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn recorder_ancestor() -> Option<String> {
+    None
+}
+
+/// Active swap areas that are neither compressed RAM (zram) nor dm-crypt devices.
+#[cfg(target_os = "linux")]
+fn check_swap() -> Vec<Finding> {
+    let Ok(swaps) = std::fs::read_to_string("/proc/swaps") else {
+        return vec![Finding::unknown("Could not read /proc/swaps to check swap encryption.", "Check `swapon --show` yourself.")];
+    };
+    // Header line, then "Filename Type Size Used Priority"
+    let areas: Vec<(String, String)> = swaps
+        .lines()
+        .skip(1)
+        .filter_map(|l| {
+            let mut cols = l.split_whitespace();
+            Some((cols.next()?.to_string(), cols.next()?.to_string()))
+        })
+        .collect();
+    if areas.is_empty() {
+        return vec![Finding::ok("No swap is active.")];
+    }
+    let off = "Turn swap off for the ceremony (`sudo swapoff -a`) or move it onto dm-crypt or zram.";
+    areas
+        .into_iter()
+        .map(|(name, kind)| {
+            if kind == "file" {
+                return Finding::warn(
+                    format!("Swap file {name} is active; whether its filesystem is encrypted cannot be told from here."),
+                    off,
+                );
+            }
+            let dev = std::fs::canonicalize(&name).unwrap_or_else(|_| Path::new(&name).to_path_buf());
+            let base = dev.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if base.starts_with("zram") {
+                return Finding::ok(format!("Swap on {name} is compressed RAM, never written to disk."));
+            }
+            let crypt = std::fs::read_to_string(format!("/sys/block/{base}/dm/uuid"))
+                .is_ok_and(|uuid| uuid.starts_with("CRYPT-"));
+            if crypt {
+                Finding::ok(format!("Swap on {name} is encrypted (dm-crypt)."))
+            } else {
+                Finding::warn(format!("Swap on {name} is not encrypted: memory paged out there stays on disk."), off)
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn check_swap() -> Vec<Finding> {
+    vec![Finding::unknown(
+        "Swap encryption is only checked on Linux.",
+        "Make sure the page file or swap is encrypted (e.g. FileVault, BitLocker), or disabled.",
+    )]
+}
+
+/// Output directories that other users can list or read.
+fn check_output_dirs() -> Vec<Finding> {
+    let mut open = Vec::new();
+    for dir in output_dirs() {
+        if let Some(mode) = other_access(&dir) {
+            // "." says little in a report; show where it is
+            let shown = std::fs::canonicalize(&dir).unwrap_or(dir);
+            open.push(Finding::warn(
+                format!("{} can be read by other users (mode {mode:o}).", shown.display()),
+                format!("chmod 700 {}", shown.display()),
+            ));
+        }
+    }
+    if open.is_empty() && cfg!(unix) {
+        open.push(Finding::ok("No existing output directory is readable by other users."));
+    }
+    open
+}
+
+/// The permission bits of `dir` when "other" has any access to it.
+#[cfg(unix)]
+fn other_access(dir: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = std::fs::metadata(dir).ok()?.permissions().mode() & 0o777;
+    (mode & 0o007 != 0).then_some(mode)
+}
+
+#[cfg(not(unix))]
+fn other_access(_dir: &Path) -> Option<u32> {
+    None
+}
+
+/// Whether a crash would write this process's memory to a core file.
+#[cfg(unix)]
+fn check_core_dumps() -> Finding {
+    let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    // SAFETY: plain syscall filling a stack value
+    if unsafe { libc::getrlimit(libc::RLIMIT_CORE, &mut limit) } != 0 {
+        return Finding::unknown("Could not read the core dump limit.", "Run `ulimit -c 0` before starting the tool.");
+    }
+    if limit.rlim_cur == 0 {
+        return Finding::ok("Core dumps are disabled (ulimit -c 0).");
+    }
+    let handler = std::fs::read_to_string("/proc/sys/kernel/core_pattern")
+        .ok()
+        .filter(|p| p.starts_with('|'))
+        .map(|p| format!(", handed to {}", p.trim().trim_start_matches('|').split_whitespace().next().unwrap_or("a helper")))
+        .unwrap_or_default();
+    let size = if limit.rlim_cur == libc::RLIM_INFINITY { "unlimited".to_string() } else { limit.rlim_cur.to_string() };
+    Finding::warn(
+        format!("Core dumps are enabled (ulimit -c {size}{handler}): a crash would write key material to disk."),
+        "Run `ulimit -c 0` in this shell before starting the tool.",
+    )
+}
+
+#[cfg(not(unix))]
+fn check_core_dumps() -> Finding {
+    Finding::unknown("Crash dump settings are only checked on Unix.", "Make sure Windows Error Reporting does not keep full dumps.")
+}
@@ -0,0 +1,16 @@
+//! `0x` hex strings, as used throughout the signed files.
+
+use anyhow::Result;
+
+/// Bytes of a hex string, with or without a `0x`/`0X` prefix.
+pub fn hex_to_bytes(s: &str) -> Result<Vec<u8>> {
+    // Accept both lowercase and uppercase 0x prefix
+    let t = s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    Ok(hex::decode(t)?)
+}
+
+pub fn bytes_to_0x(v: &[u8]) -> String {
+    format!("0x{}", hex::encode(v))
+}
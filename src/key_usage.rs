@@ -0,0 +1,115 @@
+//! Per-key signing statistics: when each signing key last signed a transaction that was
+//! written out, how many it has signed, and whether the operator has retired it.
+//!
+//! Keys are recorded by the sender address of the transactions they sign (payload
+//! co-signatures, e.g. a delegatee's, are not counted). The store lives at
+//! `Defaults::KEY_USAGE_FILE` and is encrypted at rest with the settings while a master
+//! password is set. Recording is best effort: the signed file is already on disk, so a
+//! failed save is reported but never undoes it.
+
+use anyhow::{Context, Result};
+use ethers_core::types::Address;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::audit_log::append_audit_event;
+use crate::defaults::Defaults;
+use crate::migrate::to_versioned_json;
+use crate::profile::{read_protected, write_protected, ProfileKey};
+use crate::usage_stats::{record_written, Written};
+
+/// Usage of one key, keyed in [`KeyUsage`] by its `0x…` address (lowercase).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyRecord {
+    /// Transactions signed by this key and written to a signed file.
+    #[serde(default)]
+    pub signed: u64,
+    /// RFC 3339 time of the first and of the latest such transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_used: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<String>,
+    /// RFC 3339 time the operator retired the key; signing with it again asks first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retired: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyUsage {
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyRecord>,
+}
+
+fn now_rfc3339() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    now.replace_nanosecond(0)
+        .unwrap_or(now)
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+impl KeyUsage {
+    /// Count one signed transaction for each sender in `from` (as in `DecodedTxOut::from`).
+    pub fn record<'a>(&mut self, from: impl IntoIterator<Item = &'a str>) {
+        let now = now_rfc3339();
+        for addr in from {
+            let rec = self.keys.entry(addr.trim().to_ascii_lowercase()).or_default();
+            rec.signed += 1;
+            rec.first_used.get_or_insert_with(|| now.clone());
+            rec.last_used = Some(now.clone());
+        }
+    }
+
+    /// Mark `addr` retired (now) or active again.
+    pub fn set_retired(&mut self, addr: &str, retired: bool) {
+        let rec = self.keys.entry(addr.trim().to_ascii_lowercase()).or_default();
+        rec.retired = retired.then(now_rfc3339);
+    }
+
+    /// Retired keys with the time they were retired, for `BatchOpts::retired_keys`.
+    pub fn retired(&self) -> BTreeMap<Address, String> {
+        self.keys
+            .iter()
+            .filter_map(|(addr, rec)| Some((addr.parse().ok()?, rec.retired.clone()?)))
+            .collect()
+    }
+}
+
+/// Load the usage store, empty when missing or unreadable.
+/// Pass the profile key when a master password is enabled.
+pub fn load_key_usage(key: Option<&ProfileKey>) -> KeyUsage {
+    let Ok(Some(bytes)) = read_protected(key, Path::new(Defaults::KEY_USAGE_FILE)) else {
+        return KeyUsage::default();
+    };
+    serde_json::from_slice(&bytes).unwrap_or_default()
+}
+
+/// Persist the usage store (encrypted when a profile key is given).
+pub fn save_key_usage(usage: &KeyUsage, key: Option<&ProfileKey>) -> Result<()> {
+    let bytes = to_versioned_json(Defaults::KEY_USAGE_FILE, usage)?;
+    write_protected(key, Path::new(Defaults::KEY_USAGE_FILE), &bytes)
+        .with_context(|| format!("saving key usage to {}", Defaults::KEY_USAGE_FILE))
+}
+
+/// Record the senders of freshly written transactions in `usage` and save it, append them to
+/// the audit log and, with `stats` (`settings.usage_stats`) on, count them in the usage
+/// statistics. Returns a warning line for the success modal per save that failed.
+pub fn record_signed(usage: &mut KeyUsage, key: Option<&ProfileKey>, stats: bool, written: Written<'_>) -> Vec<String> {
+    let from: Vec<String> = written.senders().into_iter().map(|a| a.trim().to_ascii_lowercase()).collect();
+    usage.record(from.iter().map(String::as_str));
+    let mut warnings = Vec::new();
+    if let Err(e) = save_key_usage(usage, key) {
+        warnings.push(format!("Warning: key usage was not recorded: {e:#}"));
+    }
+    if let Err(e) = append_audit_event(key, "signed", json!({ "count": from.len(), "senders": from })) {
+        warnings.push(format!("Warning: the signing was not added to the audit log: {e:#}"));
+    }
+    if stats {
+        if let Err(e) = record_written(key, &written) {
+            warnings.push(format!("Warning: usage statistics were not updated: {e:#}"));
+        }
+    }
+    warnings
+}
@@ -0,0 +1,14 @@
+//! Library surface of the Inkan management utility: the pieces auditors may want to
+//! call directly, independent of the TUI binary. Nothing here touches files, keys or an async
+//! runtime, so it also builds for `wasm32-unknown-unknown`, where `wasm` exports a decoder and
+//! verifier for signed files to JavaScript.
+
+pub mod abi;
+pub mod canonical_json;
+pub mod decoder;
+pub mod hexstr;
+pub mod payload;
+pub mod signed_tx;
+
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
@@ -0,0 +1,131 @@
+//! Static sanity checks on encoded calldata, run after signing and before anything is written.
+//! Cheaper than `simulate.rs` and always on: catches payloads the contract would certainly
+//! reject (malformed pubkeys, impossible signatures, wrong nonce width).
+
+use anyhow::Result;
+use ethers_core::types::U256;
+
+use crate::types::{
+    BatchEntryOut, DecodedOne, DecodedTxOut, DelegationDecodedOrdered,
+    InvalidationDecodedOrdered, RevocationDecodedOrdered,
+};
+use crate::signing::is_low_s;
+use crate::util::hex_to_bytes;
+
+/// Check the decoded arguments of one transaction against the Inkan ABI constraints.
+/// Returns one human-readable issue per violation (empty = clean).
+pub fn lint_calldata(tx: &DecodedTxOut) -> Vec<String> {
+    let mut issues = Vec::new();
+    match &tx.decodedData {
+        Some(DecodedOne::Delegation(d)) => lint_delegation(&mut issues, "", d, &tx.to),
+        Some(DecodedOne::Revocation(r)) => lint_revocation(&mut issues, "", r, &tx.to),
+        Some(DecodedOne::Invalidation(p)) => lint_invalidation(&mut issues, p, &tx.to),
+        None => {}
+    }
+    if let Some(r) = &tx.decodedDataTypeB {
+        lint_revocation(&mut issues, "revocation: ", r, &tx.to);
+    }
+    if let Some(d) = &tx.decodedDataTypeA {
+        lint_delegation(&mut issues, "delegation: ", d, &tx.to);
+    }
+    if tx.decodedData.is_none() && tx.decodedDataTypeA.is_none() && tx.decodedDataTypeB.is_none() {
+        issues.push(format!("{}: no decoded arguments to check", tx.funcName));
+    }
+    issues
+}
+
+/// Lint every entry; fails with a per-entry report if any issue is found.
+pub fn check_entries(entries: &[BatchEntryOut]) -> Result<()> {
+    let mut report = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        for issue in lint_calldata(&entry.decoded_tx) {
+            report.push(format!("entry {} (nonce {}): {issue}", i + 1, entry.decoded_tx.nonce));
+        }
+    }
+    if !report.is_empty() {
+        anyhow::bail!("Calldata checks failed:\n{}", report.join("\n"));
+    }
+    Ok(())
+}
+
+fn lint_delegation(out: &mut Vec<String>, pre: &str, d: &DelegationDecodedOrdered, to: &str) {
+    check_pubkey(out, pre, "delegatorPubkey", &d.delegatorPubkey);
+    check_pubkey(out, pre, "delegateePubkey", &d.delegateePubkey);
+    check_nonce16(out, pre, &d.nonce);
+    check_contract(out, pre, &d.expectedAddressOfDeployedContract, to);
+    check_sig(out, pre, "delegator", &d.rDelegatorPubkeySig, &d.sDelegatorPubkeySig, &d.vDelegatorPubkeySig, true);
+    // Zeroed when the delegatee was given by pubkey only.
+    check_sig(out, pre, "delegatee", &d.rDelegateePubkeySig, &d.sDelegateePubkeySig, &d.vDelegateePubkeySig, false);
+}
+
+fn lint_revocation(out: &mut Vec<String>, pre: &str, r: &RevocationDecodedOrdered, to: &str) {
+    check_pubkey(out, pre, "revokerPubkey", &r.revokerPubkey);
+    check_pubkey(out, pre, "revokeePubkey", &r.revokeePubkey);
+    check_nonce16(out, pre, &r.nonce);
+    check_contract(out, pre, &r.expectedAddressOfDeployedContract, to);
+    check_sig(out, pre, "revoker", &r.rRevokerPubkeySig, &r.sRevokerPubkeySig, &r.vRevokerPubkeySig, true);
+    // Zeroed when the revokee was given by pubkey only.
+    check_sig(out, pre, "revokee", &r.rRevokeePubkeySig, &r.sRevokeePubkeySig, &r.vRevokeePubkeySig, false);
+}
+
+fn lint_invalidation(out: &mut Vec<String>, p: &InvalidationDecodedOrdered, to: &str) {
+    check_pubkey(out, "", "invalidatedPubkey", &p.invalidatedPubkey);
+    check_nonce16(out, "", &p.nonce);
+    check_contract(out, "", &p.expectedAddressOfDeployedContract, to);
+    check_sig(out, "", "invalidated key", &p.rInvalidatedPubkeySig, &p.sInvalidatedPubkeySig, &p.vInvalidatedPubkeySig, true);
+}
+
+fn check_pubkey(out: &mut Vec<String>, pre: &str, name: &str, hex: &str) {
+    match hex_to_bytes(hex) {
+        Ok(b) if b.len() == 65 && b[0] == 0x04 => {}
+        Ok(b) => out.push(format!(
+            "{pre}{name} must be 65 bytes starting 0x04 (got {} bytes{})",
+            b.len(),
+            b.first().map(|x| format!(", first 0x{x:02x}")).unwrap_or_default()
+        )),
+        Err(_) => out.push(format!("{pre}{name} is not valid hex")),
+    }
+}
+
+fn check_nonce16(out: &mut Vec<String>, pre: &str, hex: &str) {
+    match hex_to_bytes(hex) {
+        Ok(b) if b.len() == 16 => {}
+        Ok(b) => out.push(format!("{pre}nonce must be exactly 16 bytes (got {})", b.len())),
+        Err(_) => out.push(format!("{pre}nonce is not valid hex")),
+    }
+}
+
+fn check_contract(out: &mut Vec<String>, pre: &str, expected: &str, to: &str) {
+    let Ok(b) = hex_to_bytes(expected) else {
+        out.push(format!("{pre}expectedAddressOfDeployedContract is not valid hex"));
+        return;
+    };
+    if b.len() != 20 {
+        out.push(format!("{pre}expectedAddressOfDeployedContract must be 20 bytes (got {})", b.len()));
+    } else if hex_to_bytes(to).ok().as_deref() != Some(&b[..]) {
+        out.push(format!("{pre}expectedAddressOfDeployedContract {expected} differs from tx recipient {to}"));
+    }
+}
+
+/// `required` signatures need r/s nonzero, low s (EIP-2) and v in {27, 28};
+/// optional ones may instead be all zero.
+fn check_sig(out: &mut Vec<String>, pre: &str, who: &str, r: &str, s: &str, v: &str, required: bool) {
+    let is_zero = |h: &str| hex_to_bytes(h).map(|b| b.iter().all(|x| *x == 0)).unwrap_or(false);
+    let (r_zero, s_zero) = (is_zero(r), is_zero(s));
+    if !required && r_zero && s_zero && v == "0" {
+        return;
+    }
+    if r_zero {
+        out.push(format!("{pre}{who} signature r is zero"));
+    }
+    if s_zero {
+        out.push(format!("{pre}{who} signature s is zero"));
+    } else if let Ok(b) = hex_to_bytes(s) {
+        if b.len() <= 32 && !is_low_s(U256::from_big_endian(&b)) {
+            out.push(format!("{pre}{who} signature s is high (EIP-2); many contracts reject it"));
+        }
+    }
+    if v != "27" && v != "28" {
+        out.push(format!("{pre}{who} signature v must be 27 or 28 (got {v})"));
+    }
+}
@@ -1,26 +1,81 @@
 mod app;
+mod app_state;
 mod ui;
 mod screens;
 
-mod abi;
+use inkan_management_utility::abi;
+use inkan_management_utility::canonical_json;
+mod call_trace;
+mod cli;
 mod commands;
+mod compat;
+mod release;
 mod process;
+mod remote_signer;
+#[cfg(unix)]
+mod serve;
 mod defaults;
+mod display;
+#[cfg(any(test, feature = "script"))]
+mod driver;
+mod event_stream;
 
 mod types;
 mod util;
+mod units;
+mod validation;
+mod vfs;
+mod staging;
+mod ceremony_checklist;
+mod workspace;
 mod signing;
 mod key;
 mod encoding;
-mod decoder;
+use inkan_management_utility::decoder;
+mod clone_signed;
+mod verify_signed;
+mod ceremony_record;
+mod scheme;
+mod settings;
+mod settings_bundle;
+mod app_backup;
+mod dir_manifest;
+mod profile;
+mod migrate;
+mod key_usage;
+mod usage_stats;
+mod memory_hygiene;
+mod hardening;
+mod audit_log;
+mod filename_template;
+mod lint;
+mod simulate;
 
 
 mod crypto;
 
 mod write_signed_transactions_to_file;
+mod output_encoder;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    app::run_menu().await
+    use clap::Parser;
+    let args = cli::Cli::parse();
+    // Everything below runs in the chosen workspace (see `workspace.rs`)
+    if let Some(name) = &args.workspace {
+        workspace::enter_at_launch(name)?;
+    }
+    // Headless: drive the screens from a key script (see `driver.rs`)
+    #[cfg(feature = "script")]
+    if let Some(script) = &args.script {
+        if args.cmd.is_some() {
+            anyhow::bail!("--script cannot be combined with a subcommand");
+        }
+        return driver::run_script(script).await;
+    }
+    match args.cmd {
+        // One job without the menu (see `cli.rs`)
+        Some(cmd) => cli::run(cmd).await,
+        None => app::run_menu().await,
+    }
 }
-
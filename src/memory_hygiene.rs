@@ -0,0 +1,197 @@
+//! Clearing secrets from process memory when the app quits. Best effort: the known holders of
+//! secret material (text fields, decrypted payloads, the signer cache, the profile session key,
+//! kept prefills and drafts) are zeroized as they are dropped, but copies the allocator or the
+//! terminal library made along the way are out of reach. With `exit_memory_hygiene` set, the
+//! OS is also asked to keep the process out of core dumps and to take back the freed heap.
+//!
+//! Derived AEAD keys and decrypted key files live in [`LockedBytes`], pinned in RAM with `mlock`
+//! so they never reach swap. Where locking fails (usually `RLIMIT_MEMLOCK`, see `ulimit -l`) the
+//! buffer is used unlocked and [`lock_warning`] says so on the main menu and at exit.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget};
+
+/// Why key material could not be locked in RAM, once that happened.
+static LOCK_WARNING: OnceLock<String> = OnceLock::new();
+
+/// Set once a [`LockedBytes`] could not be locked: its contents may be swapped to disk.
+pub fn lock_warning() -> Option<&'static str> {
+    LOCK_WARNING.get().map(String::as_str)
+}
+
+/// A fixed-length heap buffer for key material, locked into RAM while it lives and zeroized
+/// (then unlocked) on drop. Locking is per page: dropping one buffer unlocks pages it shares
+/// with another, so a long-lived key should not sit next to short-lived ones for long.
+pub struct LockedBytes {
+    buf: Box<[u8]>,
+    locked: bool,
+}
+
+impl LockedBytes {
+    pub fn zeroed(len: usize) -> Self {
+        let mut buf = vec![0u8; len].into_boxed_slice();
+        let locked = buf.is_empty() || lock_pages(&mut buf);
+        Self { buf, locked }
+    }
+
+    /// Move `data` into a locked buffer; the original is zeroized. It may have been paged out
+    /// before this call, so allocate with [`LockedBytes::zeroed`] where the producer allows.
+    pub fn from_vec(mut data: Vec<u8>) -> Self {
+        let mut out = Self::zeroed(data.len());
+        out.copy_from_slice(&data);
+        data.zeroize();
+        out
+    }
+}
+
+impl Deref for LockedBytes {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] { &self.buf }
+}
+
+impl DerefMut for LockedBytes {
+    fn deref_mut(&mut self) -> &mut [u8] { &mut self.buf }
+}
+
+impl Drop for LockedBytes {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+        if self.locked && !self.buf.is_empty() {
+            unlock_pages(&mut self.buf);
+        }
+    }
+}
+
+/// `mlock` `buf`, recording a warning the first time that fails.
+fn lock_pages(buf: &mut [u8]) -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: the range is one live allocation
+        if unsafe { libc::mlock(buf.as_mut_ptr().cast(), buf.len()) } == 0 {
+            #[cfg(target_os = "linux")]
+            // SAFETY: as above; only changes core dump coverage of the range
+            unsafe { libc::madvise(page_start(buf).cast(), page_span(buf), libc::MADV_DONTDUMP) };
+            return true;
+        }
+        let err = std::io::Error::last_os_error();
+        let _ = LOCK_WARNING.set(format!(
+            "Key material could not be locked in RAM ({err}); it may be written to swap. Raise the limit (ulimit -l) or disable swap."
+        ));
+        false
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = buf;
+        let _ = LOCK_WARNING.set(
+            "Key material cannot be locked in RAM on this platform; it may be written to the page file.".to_string(),
+        );
+        false
+    }
+}
+
+fn unlock_pages(buf: &mut [u8]) {
+    #[cfg(unix)]
+    // SAFETY: the range was locked by `lock_pages` and is still allocated
+    unsafe {
+        libc::munlock(buf.as_mut_ptr().cast(), buf.len());
+    }
+    #[cfg(not(unix))]
+    let _ = buf;
+}
+
+/// madvise wants a page-aligned start: the page holding `buf`'s first byte.
+#[cfg(target_os = "linux")]
+fn page_start(buf: &mut [u8]) -> *mut u8 {
+    let addr = buf.as_mut_ptr() as usize;
+    (addr - addr % page_size()) as *mut u8
+}
+
+/// Bytes from `page_start(buf)` to the end of `buf`.
+#[cfg(target_os = "linux")]
+fn page_span(buf: &mut [u8]) -> usize {
+    let addr = buf.as_mut_ptr() as usize;
+    addr % page_size() + buf.len()
+}
+
+#[cfg(target_os = "linux")]
+fn page_size() -> usize {
+    // SAFETY: sysconf has no preconditions
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 { size as usize } else { 4096 }
+}
+
+/// Wipe the session's secrets before exit. Returns the lines for the exit notes.
+pub fn wipe_for_exit(ctx: &mut AppCtx, stack: &mut Vec<Box<dyn ScreenWidget>>) -> Vec<String> {
+    let os = ctx.settings.exit_memory_hygiene;
+    let mut steps = Vec::new();
+    if os {
+        // Before anything is freed, so a crash while wiping cannot dump it either
+        steps.extend(disable_core_dumps());
+    }
+
+    // Screens own their TextFields, decrypted files and signer caches, all zeroized on drop
+    let screens = stack.len();
+    stack.clear();
+    let values = ctx.state.wipe();
+    let session_key = ctx.profile_key.take().is_some();
+
+    if os {
+        steps.extend(release_freed_heap());
+    }
+
+    let mut what = vec![format!("{screens} open screen(s) with their fields"), format!("{values} kept form value(s)")];
+    if session_key {
+        what.push("the profile session key".to_string());
+    }
+    let mut lines = vec![format!("Secrets cleared: {}.", what.join(", "))];
+    if let Some(warning) = lock_warning() {
+        lines.push(format!("Warning: {warning}"));
+    }
+    if os {
+        lines.push(if steps.is_empty() {
+            "Memory hygiene: no OS-level step is available on this platform.".to_string()
+        } else {
+            format!("Memory hygiene: {}.", steps.join("; "))
+        });
+    }
+    lines
+}
+
+/// Keep this process out of core dumps. Returns what was done.
+fn disable_core_dumps() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut done = Vec::new();
+    #[cfg(unix)]
+    {
+        let none = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+        // SAFETY: plain syscall on a stack value
+        if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &none) } == 0 {
+            done.push("core dumps disabled");
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: PR_SET_DUMPABLE takes one integer argument
+        if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0) } == 0 {
+            done.push("process marked non-dumpable");
+        }
+    }
+    done
+}
+
+/// Hand free heap pages back to the OS, so zeroized-and-freed memory is not kept mapped.
+fn release_freed_heap() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut done = Vec::new();
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    {
+        // SAFETY: glibc-only call with no pointers; returns 1 if memory was released
+        unsafe { libc::malloc_trim(0) };
+        done.push("freed heap returned to the OS");
+    }
+    done
+}
@@ -0,0 +1,147 @@
+//! Schema migrations for the app data stores in `PROTECTED_FILES` (settings, key usage, the audit
+//! log and usage statistics), so files written by earlier releases are upgraded instead of
+//! failing to parse.
+//!
+//! Each store records its layout in a top-level `schemaVersion`; a file without one has version 1,
+//! the layout of every release before versioning. At session start, once the stores can be read
+//! (after the unlock while a master password is set), `migrate_stores` runs each store's steps
+//! from its version up to the current one. The file as it was is first copied to
+//! `<file>.schema-v<N>.bak` (the `.enc` copy while a master password is set, still encrypted), and
+//! the result must read back as the current layout before it replaces the file. A store written
+//! by a newer release is refused rather than read: saving it again would drop what this build
+//! does not know.
+//!
+//! A layout change appends a step to its store in `STORES`. So far every change has been an
+//! added field with a default, which needs no step.
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::audit_log::{append_audit_event, AuditLog};
+use crate::defaults::Defaults;
+use crate::key_usage::KeyUsage;
+use crate::profile::{encrypted_path, read_protected, write_protected, ProfileKey};
+use crate::settings::Settings;
+use crate::usage_stats::UsageStats;
+
+/// Top-level member holding a store's layout version.
+const VERSION_KEY: &str = "schemaVersion";
+
+/// Takes a store's JSON from one version to the next.
+type Step = fn(&mut Value) -> Result<()>;
+
+struct Store {
+    file: &'static str,
+    /// `steps[i]` upgrades version `i + 1` to `i + 2`.
+    steps: &'static [Step],
+    /// Whether the JSON reads as this build's type.
+    parses: fn(&Value) -> Result<()>,
+}
+
+impl Store {
+    fn current(&self) -> u64 {
+        self.steps.len() as u64 + 1
+    }
+}
+
+const STORES: &[Store] = &[
+    Store { file: Defaults::SETTINGS_FILE, steps: &[], parses: parses::<Settings> },
+    Store { file: Defaults::KEY_USAGE_FILE, steps: &[], parses: parses::<KeyUsage> },
+    Store { file: Defaults::AUDIT_LOG_FILE, steps: &[], parses: parses::<AuditLog> },
+    Store { file: Defaults::USAGE_STATS_FILE, steps: &[], parses: parses::<UsageStats> },
+];
+
+fn parses<T: DeserializeOwned>(v: &Value) -> Result<()> {
+    serde_json::from_value::<T>(v.clone())?;
+    Ok(())
+}
+
+fn store(file: &str) -> &'static Store {
+    STORES.iter().find(|s| s.file == file).expect("store registered in STORES")
+}
+
+/// `value` as pretty JSON for the store at `file`, stamped with its current `schemaVersion`.
+/// Every save of a store goes through this, so the next start knows it needs no migration.
+pub fn to_versioned_json<T: Serialize>(file: &str, value: &T) -> Result<Vec<u8>> {
+    let mut v = serde_json::to_value(value)?;
+    if let Value::Object(map) = &mut v {
+        map.insert(VERSION_KEY.to_string(), json!(store(file).current()));
+    }
+    Ok(serde_json::to_vec_pretty(&v)?)
+}
+
+/// Upgrade every store older than this build, keeping a backup of each. Returns two lines per
+/// upgraded store (none when all are current) and records each upgrade in the audit log.
+pub fn migrate_stores(key: Option<&ProfileKey>) -> Result<Vec<String>> {
+    let mut done = Vec::new();
+    for store in STORES {
+        if let Some(m) = migrate_store(store, key)? {
+            done.push(m);
+        }
+    }
+    let mut lines = Vec::new();
+    for m in &done {
+        append_audit_event(
+            key,
+            "schema_migrated",
+            json!({ "file": m.file, "from": m.from, "to": m.to, "backup": m.backup.display().to_string() }),
+        )?;
+        lines.push(format!("{}: schema {} to {}", m.file, m.from, m.to));
+        lines.push(format!("  previous file kept as {}", m.backup.display()));
+    }
+    Ok(lines)
+}
+
+struct Migrated {
+    file: &'static str,
+    from: u64,
+    to: u64,
+    backup: PathBuf,
+}
+
+fn migrate_store(store: &Store, key: Option<&ProfileKey>) -> Result<Option<Migrated>> {
+    let plain = Path::new(store.file);
+    let Some(bytes) = read_protected(key, plain)? else {
+        return Ok(None);
+    };
+    let mut value: Value = serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", store.file))?;
+    let from = match value.get(VERSION_KEY) {
+        None => 1,
+        Some(v) => v
+            .as_u64()
+            .filter(|&n| n >= 1)
+            .with_context(|| format!("{} has an invalid {VERSION_KEY} {v}", store.file))?,
+    };
+    let to = store.current();
+    if from > to {
+        bail!("{} is from a newer release (schema {from}; this build reads up to {to}).", store.file);
+    }
+    if from == to {
+        return Ok(None);
+    }
+
+    for (i, step) in store.steps[(from - 1) as usize..].iter().enumerate() {
+        let at = from + i as u64;
+        step(&mut value).with_context(|| format!("upgrading {} from schema {at} to {}", store.file, at + 1))?;
+    }
+    if let Value::Object(map) = &mut value {
+        map.insert(VERSION_KEY.to_string(), json!(to));
+    }
+    (store.parses)(&value).with_context(|| format!("{} does not read back after upgrading to schema {to}", store.file))?;
+
+    // Back up the file as stored (encrypted or not) before replacing it
+    let on_disk = if key.is_some() { encrypted_path(plain) } else { plain.to_path_buf() };
+    let mut backup = on_disk.clone().into_os_string();
+    backup.push(format!(".schema-v{from}.bak"));
+    let backup = PathBuf::from(backup);
+    if !backup.exists() {
+        fs::copy(&on_disk, &backup).with_context(|| format!("backing up {} to {}", on_disk.display(), backup.display()))?;
+    }
+    write_protected(key, plain, &serde_json::to_vec_pretty(&value)?)
+        .with_context(|| format!("saving the upgraded {}", store.file))?;
+    Ok(Some(Migrated { file: store.file, from, to, backup }))
+}
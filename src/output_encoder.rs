@@ -0,0 +1,245 @@
+//! Encoders for signed transaction files. `write_signed_transactions_to_file` serializes every
+//! file through an [`OutputEncoder`]; which one is a setting (`Settings::signed_output_encoding`)
+//! carried into each run by `BatchOpts::encoding`. A new format is a new encoder plus an
+//! `OutputEncoding` variant.
+//!
+//! Only the two JSON encoders write the `{ formatVersion, metadata, entries }` envelope (or the
+//! legacy bare array) that this tool reads back (verify, clone). The other formats are for the
+//! online side's tooling:
+//! - NDJSON: one compact `{ signedTx, decodedTx }` object per line.
+//! - CSV: one row per transaction, the raw transaction in the last column.
+//! - Safe bundle: a Safe{Wallet} Transaction Builder batch of the same calls (to, value, data)
+//!   for a Safe to execute; the EOA signatures are not part of it.
+//! - Foundry script: a `forge script` broadcasting the raw signed transactions.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::types::{BatchEntryOut, SignedFileMetadata, SignedFileOut, SIGNED_FILE_FORMAT_VERSION};
+
+pub trait OutputEncoder {
+    /// Extension the written file gets in place of the template's, or None to keep it.
+    fn extension(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// File contents for `entries`. `metadata` is None for the legacy format.
+    fn encode(&self, entries: &[BatchEntryOut], metadata: Option<&SignedFileMetadata>) -> Result<Vec<u8>>;
+}
+
+/// The selectable encoders, as stored in the settings.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputEncoding {
+    #[default]
+    PrettyJson,
+    CompactJson,
+    Ndjson,
+    Csv,
+    SafeBundle,
+    FoundryScript,
+}
+
+impl OutputEncoding {
+    pub const ALL: [OutputEncoding; 6] = [
+        OutputEncoding::PrettyJson,
+        OutputEncoding::CompactJson,
+        OutputEncoding::Ndjson,
+        OutputEncoding::Csv,
+        OutputEncoding::SafeBundle,
+        OutputEncoding::FoundryScript,
+    ];
+
+    pub fn encoder(self) -> &'static dyn OutputEncoder {
+        match self {
+            OutputEncoding::PrettyJson => &Json { pretty: true },
+            OutputEncoding::CompactJson => &Json { pretty: false },
+            OutputEncoding::Ndjson => &Ndjson,
+            OutputEncoding::Csv => &Csv,
+            OutputEncoding::SafeBundle => &SafeBundle,
+            OutputEncoding::FoundryScript => &FoundryScript,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OutputEncoding::PrettyJson => "JSON (pretty)",
+            OutputEncoding::CompactJson => "JSON (compact)",
+            OutputEncoding::Ndjson => "NDJSON",
+            OutputEncoding::Csv => "CSV",
+            OutputEncoding::SafeBundle => "Safe Transaction Builder bundle",
+            OutputEncoding::FoundryScript => "Foundry script",
+        }
+    }
+
+    /// Whether this tool can read the files back (verify, clone).
+    pub fn readable(self) -> bool {
+        matches!(self, OutputEncoding::PrettyJson | OutputEncoding::CompactJson)
+    }
+
+    /// The next choice, for cycling in the settings.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|e| *e == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// The signed file format: metadata envelope, or a bare array without metadata.
+struct Json {
+    pretty: bool,
+}
+
+impl OutputEncoder for Json {
+    fn encode(&self, entries: &[BatchEntryOut], metadata: Option<&SignedFileMetadata>) -> Result<Vec<u8>> {
+        let value = match metadata {
+            Some(metadata) => serde_json::to_value(SignedFileOut {
+                formatVersion: SIGNED_FILE_FORMAT_VERSION,
+                metadata,
+                entries,
+            })?,
+            None => serde_json::to_value(entries)?,
+        };
+        let json = if self.pretty {
+            serde_json::to_string_pretty(&value)?
+        } else {
+            serde_json::to_string(&value)?
+        };
+        Ok(json.into_bytes())
+    }
+}
+
+struct Ndjson;
+
+impl OutputEncoder for Ndjson {
+    fn extension(&self) -> Option<&'static str> {
+        Some("ndjson")
+    }
+
+    fn encode(&self, entries: &[BatchEntryOut], _metadata: Option<&SignedFileMetadata>) -> Result<Vec<u8>> {
+        let mut out = String::new();
+        for entry in entries {
+            out.push_str(&serde_json::to_string(entry)?);
+            out.push('\n');
+        }
+        Ok(out.into_bytes())
+    }
+}
+
+struct Csv;
+
+const CSV_HEADER: &str = "funcName,chainId,from,to,nonce,value,gasLimit,maxFeePerGas,maxPriorityFeePerGas,label,signedTx";
+
+impl OutputEncoder for Csv {
+    fn extension(&self) -> Option<&'static str> {
+        Some("csv")
+    }
+
+    fn encode(&self, entries: &[BatchEntryOut], _metadata: Option<&SignedFileMetadata>) -> Result<Vec<u8>> {
+        let mut out = format!("{CSV_HEADER}\n");
+        for e in entries {
+            let tx = &e.decoded_tx;
+            let nonce = tx.nonce.to_string();
+            let label = e.label.clone().unwrap_or_default();
+            let row = [
+                &tx.funcName, &tx.chainId, &tx.from, &tx.to, &nonce, &tx.value,
+                &tx.gasLimit, &tx.maxFeePerGas, &tx.maxPriorityFeePerGas, &label, &e.signed_tx,
+            ];
+            out.push_str(&row.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(","));
+            out.push('\n');
+        }
+        Ok(out.into_bytes())
+    }
+}
+
+/// Quote a field holding a separator, quote or line break (RFC 4180).
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+struct SafeBundle;
+
+impl OutputEncoder for SafeBundle {
+    fn extension(&self) -> Option<&'static str> {
+        Some("json")
+    }
+
+    fn encode(&self, entries: &[BatchEntryOut], metadata: Option<&SignedFileMetadata>) -> Result<Vec<u8>> {
+        // A Transaction Builder batch runs on one chain
+        let Some(chain_id) = entries.first().map(|e| e.decoded_tx.chainId.clone()) else {
+            bail!("a Safe bundle needs at least one transaction");
+        };
+        if entries.iter().any(|e| e.decoded_tx.chainId != chain_id) {
+            bail!("a Safe bundle holds transactions for one chain; these span several chain IDs");
+        }
+        let mut description = format!("{} Inkan transaction(s)", entries.len());
+        if let Some(label) = metadata.and_then(|m| m.operatorLabel.as_deref()) {
+            description.push_str(&format!(", operator {label}"));
+        }
+        let transactions: Vec<Value> = entries
+            .iter()
+            .map(|e| json!({
+                "to": e.decoded_tx.to,
+                "value": e.decoded_tx.value,
+                "data": e.decoded_tx.encodedData,
+                "contractMethod": null,
+                "contractInputsValues": null,
+            }))
+            .collect();
+        let created_at = time::OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000;
+        let bundle = json!({
+            "version": "1.0",
+            "chainId": chain_id,
+            "createdAt": created_at as i64,
+            "meta": {
+                "name": "Inkan batch",
+                "description": description,
+                "txBuilderVersion": "1.16.5",
+                "createdFromSafeAddress": "",
+                "createdFromOwnerAddress": "",
+            },
+            "transactions": transactions,
+        });
+        Ok(serde_json::to_string_pretty(&bundle)?.into_bytes())
+    }
+}
+
+struct FoundryScript;
+
+impl OutputEncoder for FoundryScript {
+    fn extension(&self) -> Option<&'static str> {
+        Some("s.sol")
+    }
+
+    fn encode(&self, entries: &[BatchEntryOut], metadata: Option<&SignedFileMetadata>) -> Result<Vec<u8>> {
+        let mut out = String::from("// SPDX-License-Identifier: UNLICENSED\n");
+        out.push_str(&format!("// Written by inkan-management-utility {}", env!("CARGO_PKG_VERSION")));
+        if let Some(m) = metadata {
+            out.push_str(&format!(" at {}", m.createdAt));
+            if let Some(label) = &m.operatorLabel {
+                out.push_str(&format!(" for {label}"));
+            }
+        }
+        out.push_str(".\n// Run: forge script <this file> --rpc-url <url> --broadcast\n");
+        out.push_str("pragma solidity ^0.8.13;\n\nimport {Script} from \"forge-std/Script.sol\";\n\n");
+        out.push_str("contract BroadcastInkanTransactions is Script {\n    function run() external {\n");
+        for e in entries {
+            let tx = &e.decoded_tx;
+            out.push_str(&format!("        // {} from {} nonce {} (chain {})", tx.funcName, tx.from, tx.nonce, tx.chainId));
+            if let Some(label) = &e.label {
+                out.push_str(&format!(": {}", label.replace(['\n', '\r'], " ")));
+            }
+            out.push('\n');
+            out.push_str(&format!(
+                "        vm.broadcastRawTransaction(hex\"{}\");\n",
+                e.signed_tx.trim_start_matches("0x")
+            ));
+        }
+        out.push_str("    }\n}\n");
+        Ok(out.into_bytes())
+    }
+}
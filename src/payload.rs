@@ -0,0 +1,204 @@
+//! Off-chain payload hashes: exactly what the delegation / revocation / invalidation signatures cover.
+//!
+//! Exported from the library crate so auditors can recompute a hash from typed inputs and compare it
+//! with what the tool signed, without going through the TUI. The payload is the ABI encoding
+//! (`abi.encode`, not packed) of the event's unsigned fields, in struct order:
+//!
+//! - delegation:   `(bytes delegatorPubkey, bytes delegateePubkey, uint256 start, uint256 end,
+//!                   bool doesRevocationRequireDelegateeSignature, bytes16 nonce, bytes contract)`
+//! - revocation:   `(bytes revokerPubkey, bytes revokeePubkey, uint256 start, uint256 end,
+//!                   bytes16 nonce, bytes contract)`
+//! - invalidation: `(bytes invalidatedPubkey, bytes16 nonce, bytes contract)`
+//!
+//! `payload_hash` is `keccak256(payload)`. In the default EIP-191 mode the signature covers
+//! `eip191_digest(payload_hash)`; in raw mode it covers `payload_hash` itself.
+
+use ethers_core::abi::{encode, Token};
+use ethers_core::types::{Address, Signature, H256, U256};
+use ethers_core::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+/// What the off-chain payload signature is computed over.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadDigest {
+    /// `keccak256("\x19Ethereum Signed Message:\n32" || keccak256(payload))` (ethers.js `signMessage`).
+    #[default]
+    Eip191,
+    /// `keccak256(payload)` signed directly, no personal-message prefix.
+    RawKeccak,
+}
+
+/// Unsigned fields of `createDelegationEvent`. Pubkeys are uncompressed (`0x04 || X || Y`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegationPayload {
+    pub delegator_pubkey: Vec<u8>,
+    pub delegatee_pubkey: Vec<u8>,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub revocation_requires_delegatee_signature: bool,
+    pub nonce: [u8; 16],
+    /// `expectedAddressOfDeployedContract` (20 address bytes).
+    pub contract_address: Vec<u8>,
+}
+
+/// Unsigned fields of `createRevocationEvent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevocationPayload {
+    pub revoker_pubkey: Vec<u8>,
+    pub revokee_pubkey: Vec<u8>,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub nonce: [u8; 16],
+    pub contract_address: Vec<u8>,
+}
+
+/// Unsigned fields of `createPermanentInvalidationEvent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidationPayload {
+    pub invalidated_pubkey: Vec<u8>,
+    pub nonce: [u8; 16],
+    pub contract_address: Vec<u8>,
+}
+
+/// Any signed payload. `createRevocationEventFollowedByDelegationEvent` signs one
+/// `Revocation` and one `Delegation` payload separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    Delegation(DelegationPayload),
+    Revocation(RevocationPayload),
+    Invalidation(InvalidationPayload),
+}
+
+impl Payload {
+    /// ABI-encoded payload bytes (the keccak preimage).
+    pub fn encode(&self) -> Vec<u8> {
+        let uint = |v: u64| Token::Uint(U256::from(v));
+        let tokens = match self {
+            Payload::Delegation(d) => vec![
+                Token::Bytes(d.delegator_pubkey.clone()),
+                Token::Bytes(d.delegatee_pubkey.clone()),
+                uint(d.start_time),
+                uint(d.end_time),
+                Token::Bool(d.revocation_requires_delegatee_signature),
+                Token::FixedBytes(d.nonce.to_vec()),
+                Token::Bytes(d.contract_address.clone()),
+            ],
+            Payload::Revocation(r) => vec![
+                Token::Bytes(r.revoker_pubkey.clone()),
+                Token::Bytes(r.revokee_pubkey.clone()),
+                uint(r.start_time),
+                uint(r.end_time),
+                Token::FixedBytes(r.nonce.to_vec()),
+                Token::Bytes(r.contract_address.clone()),
+            ],
+            Payload::Invalidation(p) => vec![
+                Token::Bytes(p.invalidated_pubkey.clone()),
+                Token::FixedBytes(p.nonce.to_vec()),
+                Token::Bytes(p.contract_address.clone()),
+            ],
+        };
+        encode(&tokens)
+    }
+}
+
+/// `keccak256(abi.encode(payload))`.
+pub fn payload_hash(payload: &Payload) -> [u8; 32] {
+    keccak256(payload.encode())
+}
+
+/// What is actually signed for `hash` under `digest`.
+pub fn signed_digest(hash: [u8; 32], digest: PayloadDigest) -> [u8; 32] {
+    match digest {
+        PayloadDigest::Eip191 => eip191_digest(hash),
+        PayloadDigest::RawKeccak => hash,
+    }
+}
+
+/// EIP-191 personal-message digest of a payload hash:
+/// `keccak256("\x19Ethereum Signed Message:\n32" || hash)`.
+pub fn eip191_digest(hash: [u8; 32]) -> [u8; 32] {
+    let mut msg = b"\x19Ethereum Signed Message:\n32".to_vec();
+    msg.extend_from_slice(&hash);
+    keccak256(msg)
+}
+
+/// Ethereum address of an uncompressed secp256k1 public key (`0x04 || X || Y`).
+pub fn account_address(pubkey: &[u8]) -> Option<Address> {
+    if pubkey.len() != 65 || pubkey[0] != 0x04 {
+        return None;
+    }
+    Some(Address::from_slice(&keccak256(&pubkey[1..])[12..]))
+}
+
+/// Whether `sig` over `hash` under `digest` was made with the secp256k1 key `pubkey`.
+pub fn verify_secp256k1(hash: [u8; 32], digest: PayloadDigest, sig: &Signature, pubkey: &[u8]) -> bool {
+    let Some(expected) = account_address(pubkey) else { return false };
+    sig.r != U256::zero() && sig.recover(H256::from(signed_digest(hash, digest))).is_ok_and(|a| a == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pubkey(byte: u8) -> Vec<u8> {
+        let mut key = vec![0x04];
+        key.extend([byte; 64]);
+        key
+    }
+
+    /// The known vectors of the README ("Payload hashes for auditors"): `(payload, payload_hash,
+    /// eip191_digest)`.
+    fn readme_vectors() -> [(Payload, &'static str, &'static str); 3] {
+        let (nonce, contract_address) = ([0x22; 16], vec![0x11; 20]);
+        [
+            (
+                Payload::Delegation(DelegationPayload {
+                    delegator_pubkey: pubkey(0xaa),
+                    delegatee_pubkey: pubkey(0xbb),
+                    start_time: 0,
+                    end_time: 0,
+                    revocation_requires_delegatee_signature: true,
+                    nonce,
+                    contract_address: contract_address.clone(),
+                }),
+                "9f9bac48cdd601a3383ee392e4f3c2a6f1f1c108df913c6173e5507e65ca7a94",
+                "c77809cce887b177e8e6516b8b17acb2cb2746f432399c797787f82064953f8f",
+            ),
+            (
+                Payload::Revocation(RevocationPayload {
+                    revoker_pubkey: pubkey(0xaa),
+                    revokee_pubkey: pubkey(0xbb),
+                    start_time: 0,
+                    end_time: 0,
+                    nonce,
+                    contract_address: contract_address.clone(),
+                }),
+                "bff6ef95fa29fd9dfcf88dd08de1d7c4f1f9f08fd5fd7529f346446ea3661d03",
+                "9942d10313bb6029acb7a35a91153ed3caae46906eb872f3fd85603d9b5f49f1",
+            ),
+            (
+                Payload::Invalidation(InvalidationPayload { invalidated_pubkey: pubkey(0xaa), nonce, contract_address }),
+                "a21f29ca328313dbcc8cb25e9384ebda3670ff80be754e63a5a7c98f9f9953a7",
+                "0dcddf0a7e8222b601d92be13d030ffbcbcf9a279b3a1c44184445a26282e904",
+            ),
+        ]
+    }
+
+    #[test]
+    fn payload_hashes_match_readme_vectors() {
+        for (payload, hash, _) in readme_vectors() {
+            assert_eq!(hex::encode(payload_hash(&payload)), hash, "{payload:?}");
+        }
+    }
+
+    #[test]
+    fn eip191_digests_match_readme_vectors() {
+        for (payload, _, digest) in readme_vectors() {
+            let hash = payload_hash(&payload);
+            assert_eq!(hex::encode(eip191_digest(hash)), digest, "{payload:?}");
+            assert_eq!(signed_digest(hash, PayloadDigest::Eip191), eip191_digest(hash));
+            assert_eq!(signed_digest(hash, PayloadDigest::RawKeccak), hash);
+        }
+    }
+}
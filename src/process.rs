@@ -3,16 +3,25 @@ use bech32::{decode as bech32_decode, FromBase32, Variant};
 use ethers_core::abi::{Abi, Function};
 use ethers_core::types::Address;
 use ethers_core::types::U256;
-use ethers_signers::{LocalWallet, Signer};
+use ethers_core::utils::to_checksum;
 use k256::elliptic_curve::sec1::ToEncodedPoint;
 use k256::PublicKey as KPub;
+use zeroize::Zeroize;
 
+use crate::crypto::modern::key_fingerprint_from_compressed;
 use crate::decoder::{build_decoded, build_decoded_for_combo};
 use crate::encoding::{bytes16_or_random, encode_calldata, t_bool, t_bytes, t_uint};
-use crate::key::uncompressed_pubkey_0x04;
-use crate::signing::{sign_eip1559, sign_message_eip191};
+use crate::output_encoder::OutputEncoding;
+use crate::settings::PayloadSigning;
+use crate::signing::{sign_eip1559, Signer, SignerCache};
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use crate::types::{BatchEntryOut, Item};
-use crate::util::{parse_addr, u256_to_be32};
+use crate::units::{GasLimit, Wei};
+use crate::util::{hex_to_bytes, parse_addr, u256_to_be32};
+use inkan_management_utility::payload::{
+    DelegationPayload, InvalidationPayload, Payload, RevocationPayload,
+};
 
 
 
@@ -20,15 +29,163 @@ use crate::util::{parse_addr, u256_to_be32};
 /// Options for the batch signer subcommand
 #[derive(Clone, Debug)]
 pub struct BatchOpts {
-    pub gas_limit: String,
-    pub max_fee_per_gas: String,
-    pub max_priority_fee_per_gas: String,
+    pub gas_limit: GasLimit,
+    pub max_fee_per_gas: Wei,
+    pub max_priority_fee_per_gas: Wei,
+    /// Payload digest mode(s), usually copied from `Settings::payload_signing`.
+    pub payload_signing: PayloadSigning,
+    /// Signers opened so far in this run; shared by clones.
+    pub signers: SignerCache,
+    /// Sign items that `key_reuse` flags; set only after the operator confirmed the reuse.
+    pub allow_key_reuse: bool,
+    /// Keys retired on the Key Usage screen, with the time retired (`KeyUsage::retired`).
+    pub retired_keys: BTreeMap<Address, String>,
+    /// Sign items that `retired_keys` flags; set only after the operator confirmed.
+    pub allow_retired_key: bool,
+    /// Encoder for the signed files, usually `Settings::signed_output_encoding`.
+    pub encoding: OutputEncoding,
+}
+
+/// One key supplied for two roles of the same event (e.g. delegator and delegatee), almost
+/// always a copy-paste mistake. Returned as the error of `process_item` unless
+/// `BatchOpts::allow_key_reuse` is set, so screens can ask before signing anyway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyReuse(pub Vec<String>);
+
+impl std::fmt::Display for KeyReuse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(" "))
+    }
+}
+
+impl std::error::Error for KeyReuse {}
+
+impl KeyReuse {
+    /// Modal text for a form: the findings (the first few, for a long batch), then how to
+    /// confirm with `submit_label`.
+    pub fn confirm_lines(&self, submit_label: &str) -> Vec<String> {
+        const SHOWN: usize = 8;
+        let mut lines = vec!["Warning: the same key is used for two roles.".to_string(), "".to_string()];
+        lines.extend(self.0.iter().take(SHOWN).cloned());
+        if self.0.len() > SHOWN {
+            lines.push(format!("… and {} more.", self.0.len() - SHOWN));
+        }
+        lines.push("".to_string());
+        lines.push(format!("If this is intended, select {submit_label} again to sign anyway."));
+        lines
+    }
+}
+
+/// A private key input that belongs to a key the operator retired. Returned as the error of
+/// `process_item` unless `BatchOpts::allow_retired_key` is set, like [`KeyReuse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetiredKey(pub Vec<String>);
+
+impl std::fmt::Display for RetiredKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(" "))
+    }
+}
+
+impl std::error::Error for RetiredKey {}
+
+impl RetiredKey {
+    /// Modal text for a form, as `KeyReuse::confirm_lines`.
+    pub fn confirm_lines(&self, submit_label: &str) -> Vec<String> {
+        const SHOWN: usize = 8;
+        let mut lines = vec!["Warning: a retired key is selected for signing.".to_string(), "".to_string()];
+        lines.extend(self.0.iter().take(SHOWN).cloned());
+        if self.0.len() > SHOWN {
+            lines.push(format!("… and {} more.", self.0.len() - SHOWN));
+        }
+        lines.push("".to_string());
+        lines.push(format!("If this is intended, select {submit_label} again to sign anyway."));
+        lines
+    }
+}
+
+/// Private key inputs of `it` whose address is in `retired` (inputs that cannot be opened
+/// are left to `process_item` to report).
+pub fn retired_keys(it: &Item, signers: &SignerCache, retired: &BTreeMap<Address, String>) -> Option<RetiredKey> {
+    if retired.is_empty() {
+        return None;
+    }
+    let inputs = [
+        &it.type_a_privkey_x, &it.type_a_privkey_y, &it.type_b_privkey_x, &it.type_b_privkey_y, &it.type_c_privkey_x,
+    ];
+    let mut found: Vec<String> = Vec::new();
+    for input in inputs.into_iter().flatten().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let Ok(signer) = signers.open(input) else { continue };
+        let address = signer.address();
+        if let Some(when) = retired.get(&address) {
+            let msg = format!("{} was retired on the Key Usage screen ({when}).", to_checksum(&address, None));
+            if !found.contains(&msg) {
+                found.push(msg);
+            }
+        }
+    }
+    if found.is_empty() { None } else { Some(RetiredKey(found)) }
+}
+
+/// The key behind one role, as its uncompressed pubkey: a private key input wins over a
+/// pubkey input (same precedence as `process_item`). Inputs that cannot be opened compare
+/// as typed; `None` when the role is empty.
+fn role_key(signers: &SignerCache, privkey: Option<&str>, pubkey: Option<&str>) -> Option<String> {
+    let privkey = privkey.map(str::trim).filter(|s| !s.is_empty());
+    let pubkey = pubkey.map(str::trim).filter(|s| !s.is_empty());
+    match (privkey, pubkey) {
+        (Some(pk), _) => Some(
+            signers.open(pk).map(|s| normalize_0x_lower(&s.pubkey_0x04())).unwrap_or_else(|_| pk.to_string()),
+        ),
+        (None, Some(pubk)) => Some(normalize_pubkey_to_uncompressed_0x04(pubk).unwrap_or_else(|_| pubk.to_string())),
+        (None, None) => None,
+    }
+}
+
+/// Roles of `it` that share a key: delegator/delegatee, revoker/revokee, and for a
+/// re-delegation the revoker/redelegator against both the revokee and the new delegatee.
+pub fn key_reuse(it: &Item, signers: &SignerCache) -> Option<KeyReuse> {
+    let a_owner = || role_key(signers, it.type_a_privkey_x.as_deref(), None);
+    let a_other = || role_key(signers, it.type_a_privkey_y.as_deref(), it.type_a_pubkey_y.as_deref());
+    let b_owner = || role_key(signers, it.type_b_privkey_x.as_deref(), None);
+    let b_other = || role_key(signers, it.type_b_privkey_y.as_deref(), it.type_b_pubkey_y.as_deref());
+    let pairs = match it.function_to_call.as_str() {
+        "createDelegationEvent" => vec![("Delegator", "delegatee", a_owner(), a_other())],
+        "createRevocationEvent" => vec![("Revoker", "revokee", b_owner(), b_other())],
+        "createRevocationEventFollowedByDelegationEvent" => vec![
+            ("Revoker/redelegator", "revokee", b_owner(), b_other()),
+            ("Revoker/redelegator", "new delegatee", a_owner(), a_other()),
+        ],
+        _ => return None,
+    };
+    let found: Vec<String> = pairs
+        .into_iter()
+        .filter_map(|(owner, other, x, y)| match (x, y) {
+            (Some(x), Some(y)) if x == y => Some(format!("{owner} and {other} are the same key ({}).", short_key(&x))),
+            _ => None,
+        })
+        .collect();
+    if found.is_empty() { None } else { Some(KeyReuse(found)) }
+}
+
+/// `0x04abcdef…12345678` for messages; short inputs are shown whole.
+pub fn short_key(key: &str) -> String {
+    if key.len() <= 20 || !key.is_ascii() {
+        return key.to_string();
+    }
+    format!("{}…{}", &key[..10], &key[key.len() - 8..])
+}
+
+/// Drop byte separators from a hex key as other tools print it ("ab:cd:…", "ab cd …",
+/// wrapped over lines), leaving plain hex with any 0x/0X prefix.
+fn strip_hex_separators(input: &str) -> String {
+    input.chars().filter(|c| *c != ':' && !c.is_whitespace()).collect()
 }
 
 /// Parse a secret key input as either:
-/// - hex (64 hex chars, optional 0x/0X prefix), or
+/// - hex (64 hex chars, optional 0x/0X prefix; `:` and whitespace between bytes are ignored), or
 /// - bech32 "nsec1..." (payload must be exactly 32 bytes)
-fn privkey_bytes_from_input(input: &str) -> Result<[u8; 32]> {
+pub fn privkey_bytes_from_input(input: &str) -> Result<[u8; 32]> {
     let s = input.trim();
 
     // Try nsec first if it looks like one (case-insensitive match on prefix)
@@ -50,9 +207,10 @@ fn privkey_bytes_from_input(input: &str) -> Result<[u8; 32]> {
     }
 
     // Otherwise, treat as hex (optionally 0x/0X-prefixed).
+    let s = strip_hex_separators(s);
     let pk = s.strip_prefix("0x")
         .or_else(|| s.strip_prefix("0X"))
-        .unwrap_or(s);
+        .unwrap_or(&s);
     let bytes = hex::decode(pk)?; // preserves nice hex errors like "Odd number of digits"
     if bytes.len() != 32 {
         return Err(anyhow!("hex secret key must be exactly 32 bytes (got {})", bytes.len()));
@@ -64,15 +222,15 @@ fn privkey_bytes_from_input(input: &str) -> Result<[u8; 32]> {
 
 /// Normalize any pubkey input to canonical uncompressed 65-byte hex with 0x04 prefix (lowercase).
 /// Accepts:
-/// - 0x/0X-prefixed or bare hex
+/// - 0x/0X-prefixed or bare hex, with `:` or whitespace between bytes ignored
 /// - compressed (33 bytes) starting with 0x02/0x03 -> decompress
 /// - uncompressed (65 bytes) starting with 0x04 -> passthrough normalized
 /// - 64-byte "bare" uncompressed (missing 0x04) -> we add 0x04 prefix
-fn normalize_pubkey_to_uncompressed_0x04(input_hex: &str) -> Result<String> {
-    let t = input_hex.trim();
+pub fn normalize_pubkey_to_uncompressed_0x04(input_hex: &str) -> Result<String> {
+    let t = strip_hex_separators(input_hex);
     let no0x = t.strip_prefix("0x")
         .or_else(|| t.strip_prefix("0X"))
-        .unwrap_or(t);
+        .unwrap_or(&t);
     let bytes = hex::decode(no0x)?; // preserves nice hex errors
 
     match bytes.len() {
@@ -109,8 +267,19 @@ fn normalize_pubkey_to_uncompressed_0x04(input_hex: &str) -> Result<String> {
 }
 
 
+/// The 16 raw bytes of a `bytes16` nonce token.
+fn nonce16(tok: &ethers_core::abi::Token) -> Result<[u8; 16]> {
+    match tok {
+        ethers_core::abi::Token::FixedBytes(b) => b
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("nonce must be 16 bytes, got {}", b.len())),
+        _ => Err(anyhow!("nonce must be bytes16")),
+    }
+}
+
 /// Canonicalize any 0x/0X/no-prefix hex string into 0x + lowercase.
-fn normalize_0x_lower(s: &str) -> String {
+pub fn normalize_0x_lower(s: &str) -> String {
     let t = s.trim();
     let no0x = t.strip_prefix("0x")
         .or_else(|| t.strip_prefix("0X"))
@@ -118,25 +287,63 @@ fn normalize_0x_lower(s: &str) -> String {
     format!("0x{}", no0x.to_ascii_lowercase())
 }
 
+/// Address of a local private key input (hex or nsec), or `None` while it does not parse
+/// (still being typed, or a hardware/remote reference).
+pub fn address_from_privkey_input(input: &str) -> Option<Address> {
+    let mut sk = privkey_bytes_from_input(input).ok()?;
+    let key = k256::ecdsa::SigningKey::from_slice(&sk).ok();
+    sk.zeroize();
+    key.map(|k| ethers_core::utils::secret_key_to_address(&k))
+}
+
+/// Key-file fingerprint (see `key_fingerprint_from_compressed`) of a local private key input,
+/// or `None` while it does not parse.
+pub fn fingerprint_from_privkey_input(input: &str) -> Option<String> {
+    let mut sk = privkey_bytes_from_input(input).ok()?;
+    let key = k256::ecdsa::SigningKey::from_slice(&sk).ok();
+    sk.zeroize();
+    key.map(|k| key_fingerprint_from_compressed(k.verifying_key().to_encoded_point(true).as_bytes()))
+}
+
+/// Key input of the transaction sender (the event's owner) for `it.function_to_call`.
+pub fn tx_sender_key(it: &Item) -> Result<&str> {
+    let (field, key) = match it.function_to_call.as_str() {
+        "createDelegationEvent" | "createRevocationEventFollowedByDelegationEvent" => ("TYPE_A_PRIVKEY_X", &it.type_a_privkey_x),
+        "createRevocationEvent" => ("TYPE_B_PRIVKEY_X", &it.type_b_privkey_x),
+        "createPermanentInvalidationEvent" => ("TYPE_C_PRIVKEY_X", &it.type_c_privkey_x),
+        other => return Err(anyhow!("function '{}' not supported", other)),
+    };
+    key.as_deref().ok_or_else(|| anyhow!("{field} required"))
+}
+
 /// Build the struct payload, sign, and assemble calldata for each function
 pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<BatchEntryOut> {
     let func_name = it.function_to_call.as_str();
 
     // Common params
     let chain_id = it.chain_id.unwrap_or(31337);
-    let nonce_tx = it.nonce.unwrap_or(0);
+    let nonce_tx = it.nonce.unwrap_or_default();
     let to_addr: Address = parse_addr(&it.contract_address)?;
-    let gas_limit = &opts.gas_limit;
-    let max_fee = &opts.max_fee_per_gas;
-    let max_prio = &opts.max_priority_fee_per_gas;
-
-    // Helper to make a wallet from a hex or nsec input
-    let mk_wallet = |input: &str| -> Result<LocalWallet> {
-        let sk_bytes = privkey_bytes_from_input(input)?;
-        let sk = k256::ecdsa::SigningKey::from_slice(&sk_bytes)
-            .context("invalid secp256k1 secret key (out of range or zero)")?;
-        Ok(LocalWallet::from(sk).with_chain_id(chain_id))
-    };
+    let gas_limit = opts.gas_limit;
+    let max_fee = opts.max_fee_per_gas;
+    let max_prio = opts.max_priority_fee_per_gas;
+    let digest = opts.payload_signing.digest_for(&it.contract_address);
+    let scheme = opts.payload_signing.scheme_for(&it.contract_address)?;
+
+    // Each key input picks its signing backend (see `signing::open_signer`); repeated keys
+    // come from the run's cache
+    let mk_signer = |input: &str| -> Result<Arc<dyn Signer>> { opts.signers.open(input) };
+
+    if !opts.allow_key_reuse {
+        if let Some(reuse) = key_reuse(it, &opts.signers) {
+            return Err(reuse.into());
+        }
+    }
+    if !opts.allow_retired_key {
+        if let Some(retired) = retired_keys(it, &opts.signers, &opts.retired_keys) {
+            return Err(retired.into());
+        }
+    }
 
     // Use Abi::function() (unique names in this ABI)
     let func: &Function = abi
@@ -150,57 +357,57 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
                 .type_a_privkey_x
                 .as_ref()
                 .ok_or_else(|| anyhow!("TYPE_A_PRIVKEY_X required"))?;
-            let wallet = mk_wallet(owner_pk)?;
+            let wallet = mk_signer(owner_pk)?;
 
             // delegatee (allow both privkey+pubkey, check for consistency, normalize pubkey)
             let (delegatee_pubkey_0x04, must_zero_sigs, delegatee_wallet_opt) =
                 match (&it.type_a_privkey_y, &it.type_a_pubkey_y) {
                     // Both provided: verify they match
                     (Some(pk), Some(pubk)) if !pk.is_empty() && !pubk.is_empty() => {
-                        let computed = normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?));
-                        let provided = normalize_pubkey_to_uncompressed_0x04(pubk)?;
+                        let signer = mk_signer(pk)?;
+                        let computed = scheme.signer_pubkey(&*signer);
+                        let provided = scheme.normalize_pubkey(pubk)?;
                         if computed != provided {
                             return Err(anyhow!(
                                 "Inconsistent DELEGATEE_PRIVKEY and DELEGATEE_PUBKEY: the provided pubkey does not match the given privkey."
                             ));
                         }
-                        (provided, false, Some(mk_wallet(pk)?))
+                        (provided, false, Some(signer))
                     }
                     // Privkey only
                     (Some(pk), _) if !pk.is_empty() => {
-                        (normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?)), false, Some(mk_wallet(pk)?))
+                        let signer = mk_signer(pk)?;
+                        (scheme.signer_pubkey(&*signer), false, Some(signer))
                     }
                     // Pubkey only
-                    (_, Some(pubk)) if !pubk.is_empty() => (normalize_pubkey_to_uncompressed_0x04(pubk)?, true, None),
+                    (_, Some(pubk)) if !pubk.is_empty() => (scheme.normalize_pubkey(pubk)?, true, None),
                     _ => return Err(anyhow!("Provide TYPE_A_PRIVKEY_Y or TYPE_A_PUBKEY_Y")),
                 };
 
-            let delegator_pubkey = normalize_0x_lower(&uncompressed_pubkey_0x04(&wallet));
+            let delegator_pubkey = scheme.signer_pubkey(&*wallet);
             let delegation_start = it.type_a_uint_x.unwrap_or(0);
             let delegation_end = it.type_a_uint_y.unwrap_or(0);
             let requires_delegatee_sig = it.type_a_boolean.as_deref().unwrap_or("true") == "true";
-            let uuid16 = bytes16_or_random(None)?;
+            let uuid16 = bytes16_or_random(it.type_a_nonce16.as_deref()).context("TYPE_A_NONCE16")?;
 
             // off-chain payload
-            let payload = vec![
-                t_bytes(&delegator_pubkey)?,
-                t_bytes(&delegatee_pubkey_0x04)?,
-                t_uint(delegation_start),
-                t_uint(delegation_end),
-                t_bool(requires_delegatee_sig),
-                uuid16.clone(),
-                t_bytes(&it.contract_address.to_ascii_lowercase())?,
-            ];
-            let encoded = ethers_core::abi::encode(&payload);
-            let msg_hash = ethers_core::utils::keccak256(encoded);
-            let sig_delegator = sign_message_eip191(&wallet, msg_hash).await?;
+            let msg_hash = scheme.payload_hash(&Payload::Delegation(DelegationPayload {
+                delegator_pubkey: hex_to_bytes(&delegator_pubkey)?,
+                delegatee_pubkey: hex_to_bytes(&delegatee_pubkey_0x04)?,
+                start_time: delegation_start,
+                end_time: delegation_end,
+                revocation_requires_delegatee_signature: requires_delegatee_sig,
+                nonce: nonce16(&uuid16)?,
+                contract_address: hex_to_bytes(&it.contract_address)?,
+            }));
+            let sig_delegator = scheme.sign_payload(&*wallet, msg_hash, digest).await?;
 
             let (r_delegator, s_delegator, v_delegator) = (sig_delegator.r, sig_delegator.s, sig_delegator.v);
             let (r_delegatee, s_delegatee, v_delegatee) = if must_zero_sigs {
                 (U256::from(0u64), U256::from(0u64), 0u64)
             } else {
                 let w = delegatee_wallet_opt.as_ref().unwrap();
-                let sig = sign_message_eip191(w, msg_hash).await?;
+                let sig = scheme.sign_payload(&**w, msg_hash, digest).await?;
                 (sig.r, sig.s, sig.v)
             };
 
@@ -221,7 +428,7 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
             ]);
             let data = encode_calldata(func, vec![tuple_tokens])?;
             let (raw, _typed) =
-                sign_eip1559(&wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
+                sign_eip1559(&*wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
             let decoded = build_decoded(&raw, &to_addr, &data, abi)?;
             (data, raw, decoded)
         }
@@ -231,52 +438,52 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
                 .type_b_privkey_x
                 .as_ref()
                 .ok_or_else(|| anyhow!("TYPE_B_PRIVKEY_X required"))?;
-            let wallet = mk_wallet(owner_pk)?;
+            let wallet = mk_signer(owner_pk)?;
 
             // revokee (allow both privkey+pubkey, check for consistency, normalize pubkey)
             let (revokee_pubkey_0x04, must_zero_sigs, revokee_wallet_opt) =
                 match (&it.type_b_privkey_y, &it.type_b_pubkey_y) {
                     // Both provided: verify they match
                     (Some(pk), Some(pubk)) if !pk.is_empty() && !pubk.is_empty() => {
-                        let computed = normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?));
-                        let provided = normalize_pubkey_to_uncompressed_0x04(pubk)?;
+                        let signer = mk_signer(pk)?;
+                        let computed = scheme.signer_pubkey(&*signer);
+                        let provided = scheme.normalize_pubkey(pubk)?;
                         if computed != provided {
                             return Err(anyhow!(
                                 "Inconsistent REVOKEE_PRIVKEY and REVOKEE_PUBKEY: the provided pubkey does not match the given privkey."
                             ));
                         }
-                        (provided, false, Some(mk_wallet(pk)?))
+                        (provided, false, Some(signer))
                     }
                     // Privkey only
                     (Some(pk), _) if !pk.is_empty() => {
-                        (normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?)), false, Some(mk_wallet(pk)?))
+                        let signer = mk_signer(pk)?;
+                        (scheme.signer_pubkey(&*signer), false, Some(signer))
                     }
                     // Pubkey only
-                    (_, Some(pubk)) if !pubk.is_empty() => (normalize_pubkey_to_uncompressed_0x04(pubk)?, true, None),
+                    (_, Some(pubk)) if !pubk.is_empty() => (scheme.normalize_pubkey(pubk)?, true, None),
                     _ => return Err(anyhow!("Provide TYPE_B_PRIVKEY_Y or TYPE_B_PUBKEY_Y")),
                 };
 
-            let revoker_pubkey = normalize_0x_lower(&uncompressed_pubkey_0x04(&wallet));
+            let revoker_pubkey = scheme.signer_pubkey(&*wallet);
             let start = it.type_b_uint_x.unwrap_or(0);
             let end = it.type_b_uint_y.unwrap_or(0);
-            let uuid16 = bytes16_or_random(None)?;
-            let payload = vec![
-                t_bytes(&revoker_pubkey)?,
-                t_bytes(&revokee_pubkey_0x04)?,
-                t_uint(start),
-                t_uint(end),
-                uuid16.clone(),
-                t_bytes(&it.contract_address.to_ascii_lowercase())?,
-            ];
-            let encoded = ethers_core::abi::encode(&payload);
-            let msg_hash = ethers_core::utils::keccak256(encoded);
-            let sig_revoker = sign_message_eip191(&wallet, msg_hash).await?;
+            let uuid16 = bytes16_or_random(it.type_b_nonce16.as_deref()).context("TYPE_B_NONCE16")?;
+            let msg_hash = scheme.payload_hash(&Payload::Revocation(RevocationPayload {
+                revoker_pubkey: hex_to_bytes(&revoker_pubkey)?,
+                revokee_pubkey: hex_to_bytes(&revokee_pubkey_0x04)?,
+                start_time: start,
+                end_time: end,
+                nonce: nonce16(&uuid16)?,
+                contract_address: hex_to_bytes(&it.contract_address)?,
+            }));
+            let sig_revoker = scheme.sign_payload(&*wallet, msg_hash, digest).await?;
             let (r_revoker, s_revoker, v_revoker) = (sig_revoker.r, sig_revoker.s, sig_revoker.v);
             let (r_revokee, s_revokee, v_revokee) = if must_zero_sigs {
                 (U256::from(0u64), U256::from(0u64), 0u64)
             } else {
                 let w = revokee_wallet_opt.as_ref().unwrap();
-                let sig = sign_message_eip191(w, msg_hash).await?;
+                let sig = scheme.sign_payload(&**w, msg_hash, digest).await?;
                 (sig.r, sig.s, sig.v)
             };
 
@@ -296,7 +503,7 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
             ]);
             let data = encode_calldata(func, vec![tuple])?;
             let (raw, _typed) =
-                sign_eip1559(&wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
+                sign_eip1559(&*wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
             let decoded = build_decoded(&raw, &to_addr, &data, abi)?;
             (data, raw, decoded)
         }
@@ -306,17 +513,15 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
                 .type_c_privkey_x
                 .as_ref()
                 .ok_or_else(|| anyhow!("TYPE_C_PRIVKEY_X required"))?;
-            let wallet = mk_wallet(owner_pk)?;
-            let invalidated_pubkey = normalize_0x_lower(&uncompressed_pubkey_0x04(&wallet));
-            let uuid16 = bytes16_or_random(None)?;
-            let payload = vec![
-                t_bytes(&invalidated_pubkey)?,
-                uuid16.clone(),
-                t_bytes(&it.contract_address.to_ascii_lowercase())?,
-            ];
-            let encoded = ethers_core::abi::encode(&payload);
-            let msg_hash = ethers_core::utils::keccak256(encoded);
-            let sig = sign_message_eip191(&wallet, msg_hash).await?;
+            let wallet = mk_signer(owner_pk)?;
+            let invalidated_pubkey = scheme.signer_pubkey(&*wallet);
+            let uuid16 = bytes16_or_random(it.type_c_nonce16.as_deref()).context("TYPE_C_NONCE16")?;
+            let msg_hash = scheme.payload_hash(&Payload::Invalidation(InvalidationPayload {
+                invalidated_pubkey: hex_to_bytes(&invalidated_pubkey)?,
+                nonce: nonce16(&uuid16)?,
+                contract_address: hex_to_bytes(&it.contract_address)?,
+            }));
+            let sig = scheme.sign_payload(&*wallet, msg_hash, digest).await?;
             let (r, s, v) = (sig.r, sig.s, sig.v);
 
             let tuple = ethers_core::abi::Token::Tuple(vec![
@@ -329,7 +534,7 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
             ]);
             let data = encode_calldata(func, vec![tuple])?;
             let (raw, _typed) =
-                sign_eip1559(&wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
+                sign_eip1559(&*wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
             let decoded = build_decoded(&raw, &to_addr, &data, abi)?;
             (data, raw, decoded)
         }
@@ -340,25 +545,27 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
                 .type_a_privkey_x
                 .as_ref()
                 .ok_or_else(|| anyhow!("TYPE_A_PRIVKEY_X required"))?;
-            let wallet = mk_wallet(owner_pk)?;
+            let wallet = mk_signer(owner_pk)?;
 
             // A side (delegation) — allow both & check consistency; normalize pubkey
             let (delegatee_pubkey_0x04, must_zero_delegatee, delegatee_wallet_opt) =
                 match (&it.type_a_privkey_y, &it.type_a_pubkey_y) {
                     (Some(pk), Some(pubk)) if !pk.is_empty() && !pubk.is_empty() => {
-                        let computed = normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?));
-                        let provided = normalize_pubkey_to_uncompressed_0x04(pubk)?;
+                        let signer = mk_signer(pk)?;
+                        let computed = scheme.signer_pubkey(&*signer);
+                        let provided = scheme.normalize_pubkey(pubk)?;
                         if computed != provided {
                             return Err(anyhow!(
                                 "Inconsistent DELEGATEE_PRIVKEY and DELEGATEE_PUBKEY: the provided pubkey does not match the given privkey."
                             ));
                         }
-                        (provided, false, Some(mk_wallet(pk)?))
+                        (provided, false, Some(signer))
                     }
                     (Some(pk), _) if !pk.is_empty() => {
-                        (normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?)), false, Some(mk_wallet(pk)?))
+                        let signer = mk_signer(pk)?;
+                        (scheme.signer_pubkey(&*signer), false, Some(signer))
                     }
-                    (_, Some(pubk)) if !pubk.is_empty() => (normalize_pubkey_to_uncompressed_0x04(pubk)?, true, None),
+                    (_, Some(pubk)) if !pubk.is_empty() => (scheme.normalize_pubkey(pubk)?, true, None),
                     _ => return Err(anyhow!("Provide TYPE_A_PRIVKEY_Y or TYPE_A_PUBKEY_Y")),
                 };
 
@@ -366,73 +573,71 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
             let (revokee_pubkey_0x04, must_zero_revokee, revokee_wallet_opt) =
                 match (&it.type_b_privkey_y, &it.type_b_pubkey_y) {
                     (Some(pk), Some(pubk)) if !pk.is_empty() && !pubk.is_empty() => {
-                        let computed = normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?));
-                        let provided = normalize_pubkey_to_uncompressed_0x04(pubk)?;
+                        let signer = mk_signer(pk)?;
+                        let computed = scheme.signer_pubkey(&*signer);
+                        let provided = scheme.normalize_pubkey(pubk)?;
                         if computed != provided {
                             return Err(anyhow!(
                                 "Inconsistent REVOKEE_PRIVKEY and REVOKEE_PUBKEY: the provided pubkey does not match the given privkey."
                             ));
                         }
-                        (provided, false, Some(mk_wallet(pk)?))
+                        (provided, false, Some(signer))
                     }
                     (Some(pk), _) if !pk.is_empty() => {
-                        (normalize_0x_lower(&uncompressed_pubkey_0x04(&mk_wallet(pk)?)), false, Some(mk_wallet(pk)?))
+                        let signer = mk_signer(pk)?;
+                        (scheme.signer_pubkey(&*signer), false, Some(signer))
                     }
-                    (_, Some(pubk)) if !pubk.is_empty() => (normalize_pubkey_to_uncompressed_0x04(pubk)?, true, None),
+                    (_, Some(pubk)) if !pubk.is_empty() => (scheme.normalize_pubkey(pubk)?, true, None),
                     _ => return Err(anyhow!("Provide TYPE_B_PRIVKEY_Y or TYPE_B_PUBKEY_Y")),
                 };
 
-            let delegator_pubkey = normalize_0x_lower(&uncompressed_pubkey_0x04(&wallet));
+            let delegator_pubkey = scheme.signer_pubkey(&*wallet);
             // A params
             let a_start = it.type_a_uint_x.unwrap_or(0);
             let a_end = it.type_a_uint_y.unwrap_or(0);
             let a_req = it.type_a_boolean.as_deref().unwrap_or("true") == "true";
-            let a_nonce = bytes16_or_random(None)?;
+            let a_nonce = bytes16_or_random(it.type_a_nonce16.as_deref()).context("TYPE_A_NONCE16")?;
             // B params
             let b_start = it.type_b_uint_x.unwrap_or(0);
             let b_end = it.type_b_uint_y.unwrap_or(0);
-            let b_nonce = bytes16_or_random(None)?;
+            let b_nonce = bytes16_or_random(it.type_b_nonce16.as_deref()).context("TYPE_B_NONCE16")?;
 
             // Type A payload/signatures
-            let payload_a = vec![
-                t_bytes(&delegator_pubkey)?,
-                t_bytes(&delegatee_pubkey_0x04)?,
-                t_uint(a_start),
-                t_uint(a_end),
-                t_bool(a_req),
-                a_nonce.clone(),
-                t_bytes(&it.contract_address.to_ascii_lowercase())?,
-            ];
-            let enc_a = ethers_core::abi::encode(&payload_a);
-            let hash_a = ethers_core::utils::keccak256(enc_a);
-            let sig_a_delegator = sign_message_eip191(&wallet, hash_a).await?;
+            let hash_a = scheme.payload_hash(&Payload::Delegation(DelegationPayload {
+                delegator_pubkey: hex_to_bytes(&delegator_pubkey)?,
+                delegatee_pubkey: hex_to_bytes(&delegatee_pubkey_0x04)?,
+                start_time: a_start,
+                end_time: a_end,
+                revocation_requires_delegatee_signature: a_req,
+                nonce: nonce16(&a_nonce)?,
+                contract_address: hex_to_bytes(&it.contract_address)?,
+            }));
+            let sig_a_delegator = scheme.sign_payload(&*wallet, hash_a, digest).await?;
             let (r_a_del, s_a_del, v_a_del) = (sig_a_delegator.r, sig_a_delegator.s, sig_a_delegator.v);
             let (r_a_dee, s_a_dee, v_a_dee) = if must_zero_delegatee {
                 (U256::from(0u64), U256::from(0u64), 0u64)
             } else {
                 let w = delegatee_wallet_opt.as_ref().unwrap();
-                let sig = sign_message_eip191(w, hash_a).await?;
+                let sig = scheme.sign_payload(&**w, hash_a, digest).await?;
                 (sig.r, sig.s, sig.v)
             };
 
             // Type B payload/signatures
-            let payload_b = vec![
-                t_bytes(&delegator_pubkey)?,
-                t_bytes(&revokee_pubkey_0x04)?,
-                t_uint(b_start),
-                t_uint(b_end),
-                b_nonce.clone(),
-                t_bytes(&it.contract_address.to_ascii_lowercase())?,
-            ];
-            let enc_b = ethers_core::abi::encode(&payload_b);
-            let hash_b = ethers_core::utils::keccak256(enc_b);
-            let sig_b_revoker = sign_message_eip191(&wallet, hash_b).await?;
+            let hash_b = scheme.payload_hash(&Payload::Revocation(RevocationPayload {
+                revoker_pubkey: hex_to_bytes(&delegator_pubkey)?,
+                revokee_pubkey: hex_to_bytes(&revokee_pubkey_0x04)?,
+                start_time: b_start,
+                end_time: b_end,
+                nonce: nonce16(&b_nonce)?,
+                contract_address: hex_to_bytes(&it.contract_address)?,
+            }));
+            let sig_b_revoker = scheme.sign_payload(&*wallet, hash_b, digest).await?;
             let (r_b_rev, s_b_rev, v_b_rev) = (sig_b_revoker.r, sig_b_revoker.s, sig_b_revoker.v);
             let (r_b_ree, s_b_ree, v_b_ree) = if must_zero_revokee {
                 (U256::from(0u64), U256::from(0u64), 0u64)
             } else {
                 let w = revokee_wallet_opt.as_ref().unwrap();
-                let sig = sign_message_eip191(w, hash_b).await?;
+                let sig = scheme.sign_payload(&**w, hash_b, digest).await?;
                 (sig.r, sig.s, sig.v)
             };
 
@@ -469,7 +674,7 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
 
             let data = encode_calldata(func, vec![tuple_b, tuple_a])?;
             let (raw, _typed) =
-                sign_eip1559(&wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
+                sign_eip1559(&*wallet, chain_id, to_addr, nonce_tx, gas_limit, max_fee, max_prio, data.clone()).await?;
             let decoded = build_decoded_for_combo(&raw, &to_addr, &data, abi)?;
             (data, raw, decoded)
         }
@@ -477,8 +682,84 @@ pub async fn process_item(abi: &Abi, opts: &BatchOpts, it: &Item) -> Result<Batc
         _ => return Err(anyhow!("Unsupported FUNCTION_TO_CALL: {}", func_name)),
     };
 
+    let mut decoded = decoded;
+    decoded.payloadDigest = Some(digest);
+
     Ok(BatchEntryOut {
         signed_tx: signed_tx_hex,
         decoded_tx: decoded,
+        label: it.label.as_deref().map(str::trim).filter(|l| !l.is_empty()).map(String::from),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const K1: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    /// `hex` with `sep` between every byte.
+    fn separated(hex: &str, sep: &str) -> String {
+        hex.as_bytes().chunks(2).map(|b| std::str::from_utf8(b).unwrap()).collect::<Vec<_>>().join(sep)
+    }
+
+    /// K1's public key, uncompressed (`04…`) and compressed (`02…`/`03…`), bare hex.
+    fn k1_pubkeys() -> (String, String) {
+        let sk = k256::SecretKey::from_slice(&hex::decode(K1).unwrap()).unwrap();
+        let pk = sk.public_key();
+        (hex::encode(pk.to_encoded_point(false).as_bytes()), hex::encode(pk.to_encoded_point(true).as_bytes()))
+    }
+
+    #[test]
+    fn privkey_ignores_colon_space_and_newline_separators() {
+        let expected: [u8; 32] = hex::decode(K1).unwrap().try_into().unwrap();
+        for input in [
+            separated(K1, ":"),
+            format!("0x{}", separated(K1, ":")),
+            separated(K1, " "),
+            separated(K1, "\n"),
+            format!("  {}\r\n", separated(K1, ": ")),
+            format!("{}\n{}", &K1[..32], &K1[32..]),
+        ] {
+            assert_eq!(privkey_bytes_from_input(&input).unwrap(), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn privkey_rejects_odd_length_and_invalid_characters() {
+        let odd = format!("{}:{}", &K1[..32], &K1[33..]);
+        assert!(privkey_bytes_from_input(&odd).unwrap_err().to_string().contains("Odd number of digits"));
+
+        let invalid = separated(&K1.replacen('4', "g", 1), ":");
+        assert!(privkey_bytes_from_input(&invalid).unwrap_err().to_string().contains("Invalid character"));
+
+        // Separators are only ignored, never treated as digits: 31 bytes stays 31 bytes
+        let short = separated(&K1[..62], ":");
+        assert!(privkey_bytes_from_input(&short).unwrap_err().to_string().contains("got 31"));
+    }
+
+    #[test]
+    fn pubkey_ignores_colon_space_and_newline_separators() {
+        let (uncompressed, compressed) = k1_pubkeys();
+        let expected = format!("0x{uncompressed}");
+        for input in [
+            separated(&uncompressed, ":"),
+            format!("0x{}", separated(&uncompressed[2..], ":")),
+            separated(&compressed, " "),
+            separated(&compressed, "\n"),
+            format!("0X{}\n", separated(&compressed, ":")),
+        ] {
+            assert_eq!(normalize_pubkey_to_uncompressed_0x04(&input).unwrap(), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn pubkey_rejects_odd_length_and_invalid_characters() {
+        let (_, compressed) = k1_pubkeys();
+        let odd = separated(&compressed[1..], ":");
+        assert!(normalize_pubkey_to_uncompressed_0x04(&odd).unwrap_err().to_string().contains("Odd number of digits"));
+
+        let invalid = format!("{}:zz", separated(&compressed[..64], ":"));
+        assert!(normalize_pubkey_to_uncompressed_0x04(&invalid).unwrap_err().to_string().contains("Invalid character"));
+    }
+}
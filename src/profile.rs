@@ -0,0 +1,267 @@
+//! Optional master-password profile.
+//!
+//! When enabled, every file in [`PROTECTED_FILES`] is stored encrypted at rest as
+//! `<file>.enc` (XChaCha20-Poly1305, the same AEAD as modern key files) under a key
+//! derived once per session from the master password with Argon2id. The profile
+//! descriptor at `Defaults::PROFILE_FILE` holds only the KDF parameters, salt and a
+//! password check value; it never contains the password or the derived key.
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::XChaCha20Poly1305;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroize;
+
+use crate::crypto::modern::KdfParams;
+use crate::defaults::Defaults;
+use crate::memory_hygiene::LockedBytes;
+use crate::util::hex_to_bytes;
+
+/// Plaintext files that are encrypted while a profile is enabled.
+/// New at-rest stores (address book, nonce tracker, ...) register here and in `migrate::STORES`.
+pub const PROTECTED_FILES: &[&str] =
+    &[Defaults::SETTINGS_FILE, Defaults::KEY_USAGE_FILE, Defaults::AUDIT_LOG_FILE, Defaults::USAGE_STATS_FILE];
+
+const PROFILE_VERSION: u32 = 1;
+/// Magic + version prefix of a protected file: `[b"IKP1"][u8 nonce_len=24][nonce][ct || tag]`.
+const PROTECTED_MAGIC: &[u8; 4] = b"IKP1";
+const CHECK_PLAINTEXT: &[u8] = b"inkan-profile-check";
+const CHECK_NAME: &str = "profile-check";
+
+/// Same Argon2id costs as newly created key files.
+pub const PROFILE_KDF: KdfParams = KdfParams { t_cost: 3, m_cost_kib: 262_144, p_cost: 1 };
+
+/// On-disk profile descriptor (`Defaults::PROFILE_FILE`).
+#[derive(Serialize, Deserialize)]
+struct ProfileFile {
+    version: u32,
+    t_cost: u32,
+    m_cost_kib: u32,
+    p_cost: u8,
+    /// Argon2id salt, hex.
+    salt: String,
+    /// AEAD of a fixed string under the profile key; verifies the password.
+    check: String,
+}
+
+/// Session key derived from the master password. Locked in RAM and zeroized on drop.
+pub struct ProfileKey {
+    key: LockedBytes,
+}
+
+/// True when a master-password profile has been set up.
+pub fn profile_enabled() -> bool {
+    Path::new(Defaults::PROFILE_FILE).exists()
+}
+
+/// Verify `password_utf8` against the profile and return the session key.
+/// Zeroizes `password_utf8`.
+pub fn unlock_profile(password_utf8: &mut Vec<u8>) -> Result<ProfileKey> {
+    let path = Path::new(Defaults::PROFILE_FILE);
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("reading {}", path.display()))?;
+    let profile: ProfileFile = serde_json::from_str(&raw)
+        .with_context(|| format!("parsing {}", path.display()))?;
+    if profile.version != PROFILE_VERSION {
+        password_utf8.zeroize();
+        bail!("Unsupported profile version {} in {}.", profile.version, path.display());
+    }
+
+    let salt = hex_to_bytes(&profile.salt).context("profile salt is not hex")?;
+    let kdf = KdfParams { t_cost: profile.t_cost, m_cost_kib: profile.m_cost_kib, p_cost: profile.p_cost };
+    let key = derive_key(password_utf8, &salt, kdf);
+    password_utf8.zeroize();
+    let key = key?;
+
+    let check = hex_to_bytes(&profile.check).context("profile check value is not hex")?;
+    match open(&key, CHECK_NAME, &check) {
+        Ok(mut pt) if pt == CHECK_PLAINTEXT => {
+            pt.zeroize();
+            Ok(key)
+        }
+        _ => Err(anyhow!("Wrong master password.")),
+    }
+}
+
+/// Create a profile with a new master password and encrypt every existing protected file.
+/// Zeroizes `password_utf8`.
+pub fn enable_profile(password_utf8: &mut Vec<u8>) -> Result<ProfileKey> {
+    if profile_enabled() {
+        password_utf8.zeroize();
+        bail!("A master password is already set.");
+    }
+
+    let mut salt = [0u8; 16];
+    ChaCha20Rng::from_entropy().fill_bytes(&mut salt);
+    let key = derive_key(password_utf8, &salt, PROFILE_KDF);
+    password_utf8.zeroize();
+    let key = key?;
+
+    // Encrypt existing plaintext stores first; only then publish the profile file.
+    let mut migrated: Vec<PathBuf> = Vec::new();
+    for name in PROTECTED_FILES {
+        let plain = Path::new(name);
+        if !plain.exists() {
+            continue;
+        }
+        let mut data = fs::read(plain).with_context(|| format!("reading {}", plain.display()))?;
+        let res = write_encrypted(&key, plain, &data);
+        data.zeroize();
+        res?;
+        migrated.push(plain.to_path_buf());
+    }
+
+    let profile = ProfileFile {
+        version: PROFILE_VERSION,
+        t_cost: PROFILE_KDF.t_cost,
+        m_cost_kib: PROFILE_KDF.m_cost_kib,
+        p_cost: PROFILE_KDF.p_cost,
+        salt: hex::encode(salt),
+        check: hex::encode(seal(&key, CHECK_NAME, CHECK_PLAINTEXT)?),
+    };
+    let json = serde_json::to_string_pretty(&profile)?;
+    write_atomic(Path::new(Defaults::PROFILE_FILE), json.as_bytes())?;
+
+    for plain in migrated {
+        fs::remove_file(&plain)
+            .with_context(|| format!("removing plaintext {}", plain.display()))?;
+    }
+    Ok(key)
+}
+
+/// Decrypt every protected file back to plaintext and remove the profile.
+pub fn disable_profile(key: &ProfileKey) -> Result<()> {
+    for name in PROTECTED_FILES {
+        let plain = Path::new(name);
+        let Some(mut data) = read_encrypted(key, plain)? else { continue };
+        let res = write_atomic(plain, &data);
+        data.zeroize();
+        res?;
+        let enc = encrypted_path(plain);
+        fs::remove_file(&enc).with_context(|| format!("removing {}", enc.display()))?;
+    }
+    fs::remove_file(Defaults::PROFILE_FILE)
+        .with_context(|| format!("removing {}", Defaults::PROFILE_FILE))?;
+    Ok(())
+}
+
+/// Read a protected store: the `.enc` copy when a key is given, the plain file otherwise.
+/// Returns `Ok(None)` if the file does not exist.
+pub fn read_protected(key: Option<&ProfileKey>, plain: &Path) -> Result<Option<Vec<u8>>> {
+    match key {
+        Some(k) => read_encrypted(k, plain),
+        None if plain.exists() => Ok(Some(
+            fs::read(plain).with_context(|| format!("reading {}", plain.display()))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Write a protected store: sealed to `<file>.enc` when a key is given, the plain file otherwise.
+pub fn write_protected(key: Option<&ProfileKey>, plain: &Path, data: &[u8]) -> Result<()> {
+    match key {
+        Some(k) => write_encrypted(k, plain, data),
+        None => write_atomic(plain, data),
+    }
+}
+
+/// `<file>.enc` next to the plaintext path.
+pub fn encrypted_path(plain: &Path) -> PathBuf {
+    let mut s = plain.as_os_str().to_owned();
+    s.push(".enc");
+    PathBuf::from(s)
+}
+
+/// Logical name bound into the AAD so one store's ciphertext can't be swapped for another's.
+fn store_name(plain: &Path) -> String {
+    plain.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn read_encrypted(key: &ProfileKey, plain: &Path) -> Result<Option<Vec<u8>>> {
+    let enc = encrypted_path(plain);
+    if !enc.exists() {
+        return Ok(None);
+    }
+    let blob = fs::read(&enc).with_context(|| format!("reading {}", enc.display()))?;
+    let pt = open(key, &store_name(plain), &blob)
+        .with_context(|| format!("decrypting {}", enc.display()))?;
+    Ok(Some(pt))
+}
+
+fn write_encrypted(key: &ProfileKey, plain: &Path, data: &[u8]) -> Result<()> {
+    let blob = seal(key, &store_name(plain), data)?;
+    write_atomic(&encrypted_path(plain), &blob)
+}
+
+/// Argon2id key for `password_utf8`; also keys app data backups (see `app_backup.rs`).
+pub fn derive_key(password_utf8: &[u8], salt: &[u8], kdf: KdfParams) -> Result<ProfileKey> {
+    let params = argon2::Params::new(kdf.m_cost_kib, kdf.t_cost, kdf.p_cost as u32, Some(32))
+        .map_err(|e| anyhow!("Argon2 params: {e}"))?;
+    let argon = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    let mut key = ProfileKey { key: LockedBytes::zeroed(32) };
+    argon
+        .hash_password_into(password_utf8, salt, &mut key.key)
+        .map_err(|e| anyhow!("Argon2 error: {e}"))?;
+    Ok(key)
+}
+
+/// AEAD `plaintext` under `key`, binding `name` into the AAD.
+pub fn seal(key: &ProfileKey, name: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce = [0u8; 24];
+    ChaCha20Rng::from_entropy().fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(4 + 1 + nonce.len() + plaintext.len() + 16);
+    out.extend_from_slice(PROTECTED_MAGIC);
+    out.push(nonce.len() as u8);
+    out.extend_from_slice(&nonce);
+
+    let mut aad = out.clone();
+    aad.extend_from_slice(name.as_bytes());
+
+    let cipher = XChaCha20Poly1305::new(key.key[..].into());
+    let ct = cipher
+        .encrypt((&nonce).into(), Payload { aad: &aad, msg: plaintext })
+        .map_err(|e| anyhow!("encrypt error: {e}"))?;
+    out.extend_from_slice(&ct);
+    Ok(out)
+}
+
+pub fn open(key: &ProfileKey, name: &str, blob: &[u8]) -> Result<Vec<u8>> {
+    let header_len = 4 + 1 + 24;
+    if blob.len() < header_len + 16 || &blob[..4] != PROTECTED_MAGIC || blob[4] != 24 {
+        bail!("not a profile-encrypted file or truncated");
+    }
+    let nonce: [u8; 24] = blob[5..header_len].try_into().expect("slice to 24");
+
+    let mut aad = blob[..header_len].to_vec();
+    aad.extend_from_slice(name.as_bytes());
+
+    let cipher = XChaCha20Poly1305::new(key.key[..].into());
+    cipher
+        .decrypt((&nonce).into(), Payload { aad: &aad, msg: &blob[header_len..] })
+        .map_err(|_| anyhow!("authentication failed (wrong master password or tampered file)"))
+}
+
+/// Write via a sibling temp file and rename, so a crash never leaves a half-written store.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("store");
+    let tmp = path.with_file_name(format!(".{name}.tmp"));
+    {
+        let mut f = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp)
+            .with_context(|| format!("creating {}", tmp.display()))?;
+        f.write_all(data)?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp, path).with_context(|| format!("writing {}", path.display()))?;
+    Ok(())
+}
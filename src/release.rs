@@ -0,0 +1,62 @@
+//! Release verification: what this binary reports about itself, for comparing against the
+//! values published with a release before trusting it on the air-gapped host.
+//!
+//! The attestation holds the SHA-256 of the running executable, the embedded ABI hash (as in
+//! `compat.json`), the hash of `compat.json` as canonical JSON, the hash of the `Cargo.lock` it
+//! was built from and the build inputs a reproducible build pins (target, profile, features, `SOURCE_DATE_EPOCH` and the source
+//! commit the release builder passes in `INKAN_BUILD_COMMIT`). Printed by
+//! `verify-binary` and shown on the About screen.
+//!
+//! A tampered binary can report anything about itself: always also hash the file with an
+//! independent tool (`sha256sum`) and compare that with the published value.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::canonical_json::canonical_string;
+use crate::compat::{abi_sha256, compat_json};
+use crate::util::sha256_hex;
+
+/// Version of the attestation layout.
+pub const ATTESTATION_VERSION: u32 = 1;
+
+/// The lockfile the binary was compiled against.
+const CARGO_LOCK: &[u8] = include_bytes!("../Cargo.lock");
+
+/// Path and SHA-256 of the running executable's file.
+fn binary_sha256() -> Result<(String, String)> {
+    let exe = std::env::current_exe().context("locating the running executable")?;
+    let bytes = std::fs::read(&exe).with_context(|| format!("reading {}", exe.display()))?;
+    Ok((exe.display().to_string(), sha256_hex(&bytes)))
+}
+
+pub fn attestation() -> Result<Value> {
+    let (binary_path, binary_sha256) = binary_sha256()?;
+    Ok(json!({
+        "attestationVersion": ATTESTATION_VERSION,
+        "tool": env!("CARGO_PKG_NAME"),
+        "toolVersion": env!("CARGO_PKG_VERSION"),
+        "binaryPath": binary_path,
+        "binarySha256": binary_sha256,
+        "abiSha256": abi_sha256()?,
+        "compatSha256": sha256_hex(canonical_string(&compat_json()?).as_bytes()),
+        "build": {
+            "cargoLockSha256": sha256_hex(CARGO_LOCK),
+            "sourceCommit": option_env!("INKAN_BUILD_COMMIT"),
+            "sourceDateEpoch": option_env!("SOURCE_DATE_EPOCH"),
+            "arch": std::env::consts::ARCH,
+            "os": std::env::consts::OS,
+            "profile": if cfg!(debug_assertions) { "debug" } else { "release" },
+            "features": {
+                "simulation": cfg!(feature = "simulation"),
+                "remoteSigner": cfg!(feature = "remote-signer"),
+                "script": cfg!(feature = "script"),
+            },
+        },
+    }))
+}
+
+/// Lines for the About screen and the CLI: the attestation as pretty JSON.
+pub fn attestation_lines() -> Result<Vec<String>> {
+    Ok(serde_json::to_string_pretty(&attestation()?)?.lines().map(str::to_string).collect())
+}
@@ -0,0 +1,237 @@
+//! Remote-signer mode: the air-gapped machine answers signature requests from a connected
+//! online machine over a serial line (a USB gadget serial port such as `/dev/ttyGS0`, or a
+//! plain RS-232 link), so payload hashes and signatures do not have to travel on USB sticks.
+//! Only 32-byte hashes come in and only signatures and public keys go out; the key never
+//! leaves this machine, and every signature waits for on-screen approval.
+//!
+//! Each frame is a 4-byte big-endian length followed by one CBOR map of that length
+//! (at most [`MAX_FRAME_LEN`] bytes). Requests:
+//!
+//! ```text
+//! { "v": 1, "id": <uint>, "op": "address" }
+//! { "v": 1, "id": <uint>, "op": "sign", "hash": <bytes 32>, "digest": "eip191" | "raw", "note": <text, optional> }
+//! ```
+//!
+//! Replies echo `id`:
+//!
+//! ```text
+//! { "v": 1, "id": <uint>, "ok": true, "address": "0x…", "pubkey": <bytes 65> }
+//! { "v": 1, "id": <uint>, "ok": true, "sig": <bytes 65, r || s || v, v in {27, 28}> }
+//! { "v": 1, "id": <uint>, "ok": false, "error": <text> }
+//! ```
+//!
+//! `digest` selects how the hash is signed, as in `PayloadDigest`: `eip191` signs the
+//! personal-message digest of `hash`, `raw` signs `hash` itself. Malformed requests get an
+//! error reply with `id` 0 when no id could be read. Serial support is compiled in with
+//! `--features remote-signer`.
+
+// Without the feature, requests are never decoded and most of the protocol types go unused.
+#![cfg_attr(not(feature = "remote-signer"), allow(dead_code))]
+
+use anyhow::Result;
+
+use crate::settings::PayloadDigest;
+
+/// Version carried in every frame as `"v"`.
+pub const PROTOCOL_VERSION: u64 = 1;
+/// Largest accepted frame body; a sign request is well under 1 KiB.
+pub const MAX_FRAME_LEN: usize = 4096;
+
+/// What the online machine asked for.
+#[derive(Debug, Clone)]
+pub enum RequestOp {
+    /// Report the signing address and public key; answered without approval.
+    Address,
+    Sign { hash: [u8; 32], digest: PayloadDigest, note: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub id: u64,
+    pub op: RequestOp,
+}
+
+pub enum Reply {
+    Address { address: String, pubkey: Vec<u8> },
+    Signature(Vec<u8>),
+    Error(String),
+}
+
+/// An open serial link speaking the frame protocol above.
+pub struct Link(imp::Port);
+
+impl Link {
+    pub fn open(device: &str, baud: u32) -> Result<Self> {
+        Ok(Self(imp::Port::open(device, baud)?))
+    }
+
+    /// Next complete request, if one has arrived. Waits at most a short read timeout, so it
+    /// can be called from the UI loop. Malformed frames are answered with an error and skipped;
+    /// `Err` means the link itself failed.
+    pub fn poll(&mut self) -> Result<Option<Request>> {
+        self.0.poll()
+    }
+
+    pub fn reply(&mut self, id: u64, reply: Reply) -> Result<()> {
+        self.0.reply(id, reply)
+    }
+}
+
+#[cfg(not(feature = "remote-signer"))]
+mod imp {
+    use anyhow::Result;
+
+    use super::{Reply, Request};
+
+    /// No serial support in this build; a `Port` can never exist.
+    pub enum Port {}
+
+    impl Port {
+        pub fn open(_device: &str, _baud: u32) -> Result<Self> {
+            anyhow::bail!("This build has no remote-signer support. Rebuild with `--features remote-signer`.")
+        }
+
+        pub fn poll(&mut self) -> Result<Option<Request>> {
+            match *self {}
+        }
+
+        pub fn reply(&mut self, _id: u64, _reply: Reply) -> Result<()> {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(feature = "remote-signer")]
+mod imp {
+    use anyhow::{anyhow, bail, Context, Result};
+    use ciborium::value::Value;
+    use serialport::SerialPort;
+    use std::io::{ErrorKind, Read, Write};
+    use std::time::Duration;
+
+    use super::{Reply, Request, RequestOp, MAX_FRAME_LEN, PROTOCOL_VERSION};
+    use crate::settings::PayloadDigest;
+
+    /// Read timeout per poll; short enough that keys stay responsive.
+    const POLL_TIMEOUT: Duration = Duration::from_millis(50);
+
+    pub struct Port {
+        port: Box<dyn SerialPort>,
+        /// Bytes received but not yet consumed as a frame.
+        buf: Vec<u8>,
+    }
+
+    impl Port {
+        pub fn open(device: &str, baud: u32) -> Result<Self> {
+            let port = serialport::new(device, baud)
+                .timeout(POLL_TIMEOUT)
+                .open()
+                .with_context(|| format!("opening serial device {device}"))?;
+            Ok(Self { port, buf: Vec::new() })
+        }
+
+        pub fn poll(&mut self) -> Result<Option<Request>> {
+            let mut chunk = [0u8; 512];
+            match self.port.read(&mut chunk) {
+                Ok(n) => self.buf.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::TimedOut => {}
+                Err(e) => return Err(e).context("reading from serial device"),
+            }
+
+            if self.buf.len() < 4 {
+                return Ok(None);
+            }
+            let len = u32::from_be_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]) as usize;
+            if len > MAX_FRAME_LEN {
+                // Lost framing; drop everything buffered and let the sender retry.
+                self.buf.clear();
+                self.reply(0, Reply::Error(format!("frame of {len} bytes exceeds the {MAX_FRAME_LEN}-byte limit")))?;
+                return Ok(None);
+            }
+            if self.buf.len() < 4 + len {
+                return Ok(None);
+            }
+            let body: Vec<u8> = self.buf.drain(..4 + len).skip(4).collect();
+            match parse_request(&body) {
+                Ok(req) => Ok(Some(req)),
+                Err((id, e)) => {
+                    self.reply(id, Reply::Error(format!("{e:#}")))?;
+                    Ok(None)
+                }
+            }
+        }
+
+        pub fn reply(&mut self, id: u64, reply: Reply) -> Result<()> {
+            let mut fields = vec![
+                (text("v"), Value::Integer(PROTOCOL_VERSION.into())),
+                (text("id"), Value::Integer(id.into())),
+            ];
+            match reply {
+                Reply::Address { address, pubkey } => fields.extend([
+                    (text("ok"), Value::Bool(true)),
+                    (text("address"), Value::Text(address)),
+                    (text("pubkey"), Value::Bytes(pubkey)),
+                ]),
+                Reply::Signature(sig) => fields.extend([
+                    (text("ok"), Value::Bool(true)),
+                    (text("sig"), Value::Bytes(sig)),
+                ]),
+                Reply::Error(msg) => fields.extend([
+                    (text("ok"), Value::Bool(false)),
+                    (text("error"), Value::Text(msg)),
+                ]),
+            }
+            let mut body = Vec::new();
+            ciborium::into_writer(&Value::Map(fields), &mut body).context("encoding reply")?;
+            let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+            frame.extend_from_slice(&body);
+            self.port.write_all(&frame).context("writing to serial device")?;
+            self.port.flush().context("writing to serial device")
+        }
+    }
+
+    fn text(s: &str) -> Value {
+        Value::Text(s.to_string())
+    }
+
+    fn field<'a>(map: &'a [(Value, Value)], key: &str) -> Option<&'a Value> {
+        map.iter().find(|(k, _)| k.as_text() == Some(key)).map(|(_, v)| v)
+    }
+
+    /// Decode one request body. On failure, also returns the request id if it could be read.
+    fn parse_request(body: &[u8]) -> std::result::Result<Request, (u64, anyhow::Error)> {
+        let value: Value = ciborium::from_reader(body).map_err(|e| (0, anyhow!("frame is not CBOR: {e}")))?;
+        let Some(map) = value.as_map() else {
+            return Err((0, anyhow!("request must be a CBOR map")));
+        };
+        let id = field(map, "id")
+            .and_then(Value::as_integer)
+            .and_then(|i| u64::try_from(i).ok())
+            .ok_or_else(|| (0, anyhow!("request has no unsigned integer \"id\"")))?;
+        parse_op(map).map(|op| Request { id, op }).map_err(|e| (id, e))
+    }
+
+    fn parse_op(map: &[(Value, Value)]) -> Result<RequestOp> {
+        let version = field(map, "v").and_then(Value::as_integer).and_then(|i| u64::try_from(i).ok());
+        if version != Some(PROTOCOL_VERSION) {
+            bail!("unsupported protocol version {version:?}, expected {PROTOCOL_VERSION}");
+        }
+        match field(map, "op").and_then(Value::as_text) {
+            Some("address") => Ok(RequestOp::Address),
+            Some("sign") => {
+                let hash = field(map, "hash")
+                    .and_then(Value::as_bytes)
+                    .and_then(|b| <[u8; 32]>::try_from(b.as_slice()).ok())
+                    .ok_or_else(|| anyhow!("\"hash\" must be 32 bytes"))?;
+                let digest = match field(map, "digest").and_then(Value::as_text) {
+                    Some("eip191") => PayloadDigest::Eip191,
+                    Some("raw") => PayloadDigest::RawKeccak,
+                    other => bail!("\"digest\" must be \"eip191\" or \"raw\", got {other:?}"),
+                };
+                let note = field(map, "note").and_then(Value::as_text).unwrap_or("").to_string();
+                Ok(RequestOp::Sign { hash, digest, note })
+            }
+            other => bail!("unknown \"op\" {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Signature scheme of the off-chain payload signatures (delegation, revocation, invalidation).
+//!
+//! The Ethereum transaction is always signed with secp256k1 by the sending account; what a
+//! contract variant may change is how payloads are hashed and signed and how the public keys
+//! inside them are encoded. Those steps go through [`PayloadScheme`], so `process_item` (and
+//! with it every Create screen and the batch pipeline) and the verifier do not depend on the
+//! curve. The scheme is chosen per contract (`PayloadSigning::scheme_for`).
+//!
+//! Only `secp256k1_keccak` is built in. Another scheme (e.g. for a BLS or ed25519 contract
+//! variant) is a new implementation behind its own cargo feature, like `remote-signer`,
+//! registered in [`scheme_named`]. Reserved names are recognized already, so a settings file
+//! can name them and a build without them refuses with a clear message instead of signing.
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use ethers_core::types::{Address, Signature};
+
+use crate::process::{normalize_0x_lower, normalize_pubkey_to_uncompressed_0x04};
+use crate::settings::PayloadDigest;
+use crate::signing::Signer;
+use inkan_management_utility::payload::{account_address, payload_hash, verify_secp256k1, Payload};
+
+/// The built-in scheme: secp256k1 ECDSA over `keccak256(abi.encode(payload))`, uncompressed keys.
+pub const SECP256K1_KECCAK: &str = "secp256k1_keccak";
+
+/// Schemes this build can sign and verify with.
+pub const AVAILABLE_SCHEMES: &[&str] = &[SECP256K1_KECCAK];
+
+/// Names kept for contract variants that are not built in.
+const RESERVED_SCHEMES: &[&str] = &["ed25519", "bls12_381"];
+
+#[async_trait]
+pub trait PayloadScheme: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Canonical encoding (lowercase `0x` hex) of a public key typed by the operator.
+    fn normalize_pubkey(&self, input: &str) -> Result<String>;
+    /// Public key the payload names for a signing key, in the same encoding.
+    fn signer_pubkey(&self, signer: &dyn Signer) -> String;
+    /// Hash of the payload; the digest mode is applied on top of it when signing.
+    fn payload_hash(&self, payload: &Payload) -> [u8; 32];
+    async fn sign_payload(&self, signer: &dyn Signer, hash: [u8; 32], digest: PayloadDigest) -> Result<Signature>;
+    /// Whether `sig` over `hash` under `digest` was made with `pubkey`.
+    fn verify_payload(&self, hash: [u8; 32], digest: PayloadDigest, sig: &Signature, pubkey: &[u8]) -> bool;
+    /// Ethereum address of `pubkey`, for schemes whose keys are also account keys.
+    fn account_address(&self, pubkey: &[u8]) -> Option<Address>;
+}
+
+pub struct Secp256k1Keccak;
+
+#[async_trait]
+impl PayloadScheme for Secp256k1Keccak {
+    fn name(&self) -> &'static str { SECP256K1_KECCAK }
+
+    fn normalize_pubkey(&self, input: &str) -> Result<String> {
+        normalize_pubkey_to_uncompressed_0x04(input)
+    }
+
+    fn signer_pubkey(&self, signer: &dyn Signer) -> String {
+        normalize_0x_lower(&signer.pubkey_0x04())
+    }
+
+    fn payload_hash(&self, payload: &Payload) -> [u8; 32] {
+        payload_hash(payload)
+    }
+
+    async fn sign_payload(&self, signer: &dyn Signer, hash: [u8; 32], digest: PayloadDigest) -> Result<Signature> {
+        crate::signing::sign_payload(signer, hash, digest).await
+    }
+
+    fn verify_payload(&self, hash: [u8; 32], digest: PayloadDigest, sig: &Signature, pubkey: &[u8]) -> bool {
+        verify_secp256k1(hash, digest, sig, pubkey)
+    }
+
+    fn account_address(&self, pubkey: &[u8]) -> Option<Address> {
+        account_address(pubkey)
+    }
+}
+
+/// The scheme called `name` (empty = `secp256k1_keccak`).
+pub fn scheme_named(name: &str) -> Result<&'static dyn PayloadScheme> {
+    match name.trim() {
+        "" | SECP256K1_KECCAK => Ok(&Secp256k1Keccak),
+        n if RESERVED_SCHEMES.contains(&n) => {
+            bail!("signature scheme '{n}': no {n} backend is available in this build")
+        }
+        n => bail!("unknown signature scheme '{n}' (available: {})", AVAILABLE_SCHEMES.join(", ")),
+    }
+}
@@ -0,0 +1,281 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+
+use crate::abi::{load_abi, missing_signatures, parse_abi_json, selector_collisions, selector_rows};
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::defaults::Defaults;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::vfs::Fs;
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 37,
+        title: "ABI Selectors",
+        description: "List each contract function's 4-byte selector and signature, and check another ABI for selector collisions.",
+        open: |ctx| Transition::Push(Box::new(AbiSelectorsScreen::new(&*ctx.fs))),
+    }
+}
+
+/// The 4-byte selector and canonical signature of every function in the Inkan ABI this build
+/// encodes against, optionally beside a contract developer's ABI file with the selectors the two
+/// share across different signatures. The listing can be saved to a text file to send on.
+pub struct AbiSelectorsScreen {
+    // 0 compare file, 1 output file, 2 list, 3 save, 4 back
+    field_index: usize,
+    compare_file: TextField,
+    out_file: TextField,
+    /// The last listing: its lines, or why it failed.
+    listing: Result<Vec<String>, String>,
+    /// First listing line shown.
+    scroll: usize,
+}
+
+impl AbiSelectorsScreen {
+    pub fn new(fs: &dyn Fs) -> Self {
+        let mut s = Self {
+            field_index: 0,
+            compare_file: TextField::default(),
+            out_file: TextField::with(Defaults::ABI_SELECTORS_OUT_FILE),
+            listing: Ok(Vec::new()),
+            scroll: 0,
+        };
+        s.list(fs);
+        s
+    }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.compare_file,
+            1 => &mut self.out_file,
+            _ => unreachable!(),
+        }
+    }
+
+    fn is_text(&self) -> bool { self.field_index <= 1 }
+
+    fn list(&mut self, fs: &dyn Fs) {
+        self.listing = listing(fs, self.compare_file.text.trim()).map_err(|e| format!("{e:#}"));
+        self.scroll = 0;
+    }
+
+    /// Write the current listing to Output File, a new file.
+    fn save(&self, fs: &dyn Fs) -> Result<Vec<String>> {
+        let lines = match &self.listing {
+            Ok(lines) => lines,
+            Err(_) => bail!("Fix the listing before saving it."),
+        };
+        let out = self.out_file.text.trim();
+        if out.is_empty() {
+            bail!("Output File cannot be empty.");
+        }
+        let out_path = Path::new(out);
+        if fs.exists(out_path) {
+            bail!("{out} already exists; choose another Output File.");
+        }
+        if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs.create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        fs.create_new(out_path, (lines.join("\n") + "\n").as_bytes())
+            .with_context(|| format!("writing {out}"))?;
+        Ok(vec![format!("Selector listing written to {out}")])
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (i, label) in ["List", "Save Selectors", "Back"].into_iter().enumerate() {
+            if i > 0 { spans.push(Span::raw("   ")); }
+            spans.extend(button_spans(label, self.field_index == i + 2));
+        }
+        Line::from(spans)
+    }
+}
+
+/// Selectors of the Inkan ABI, then of the ABI at `compare` (when given) with the collisions
+/// between the two and the Inkan signatures it lacks, one line each.
+fn listing(fs: &dyn Fs, compare: &str) -> Result<Vec<String>> {
+    let inkan = load_abi()?;
+    let mut lines = vec![format!("Inkan ABI (built in): {} function(s)", inkan.functions().count())];
+    lines.extend(selector_rows(&inkan).iter().map(|r| format!("  {}  {}", r.selector, r.signature)));
+    let mut abis = vec![("Inkan", &inkan)];
+    let other;
+    if !compare.is_empty() {
+        let text = fs.read_to_string(Path::new(compare)).with_context(|| format!("reading {compare}"))?;
+        other = parse_abi_json(&text).with_context(|| format!("{compare} is not an ABI"))?;
+        lines.push(String::new());
+        lines.push(format!("{compare}: {} function(s)", other.functions().count()));
+        lines.extend(selector_rows(&other).iter().map(|r| format!("  {}  {}", r.selector, r.signature)));
+        abis.push((compare, &other));
+    }
+
+    lines.push(String::new());
+    let collisions = selector_collisions(&abis);
+    if collisions.is_empty() {
+        lines.push("No selector collisions.".to_string());
+    } else {
+        lines.push("Selector collisions:".to_string());
+        lines.extend(collisions);
+    }
+    if let [_, (_, other)] = abis[..] {
+        let missing = missing_signatures(&inkan, other);
+        if missing.is_empty() {
+            lines.push(format!("{compare} has every Inkan function."));
+        } else {
+            lines.push(format!("Inkan functions {compare} lacks (by signature):"));
+            lines.extend(missing.iter().map(|sig| format!("  {sig}")));
+        }
+    }
+    Ok(lines)
+}
+
+#[async_trait]
+impl ScreenWidget for AbiSelectorsScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "ABI Selectors" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "ABI Selectors";
+        let explanation_paras = [
+            "Every function of the Inkan ABI this utility encodes calls against, with its 4-byte selector and signature. To check a contract build, give its ABI (a JSON array, or a Foundry or Hardhat artifact) as Compare With ABI File and select List: selectors it shares with a different Inkan function, and Inkan functions it lacks, are shown below.",
+            "Save Selectors writes the listing to Output File, to send to the contract developers.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        let output_rows = match &self.listing {
+            Ok(lines) => lines.len(),
+            Err(_) => 1,
+        } as u16;
+        // spacer + 2 inputs + spacer + buttons + spacer + output
+        let middle_needed = 2 + 2 + 6 + output_rows.max(1);
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: inputs and buttons above the scrollable listing
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(regions.middle_inner);
+        f.render_widget(
+            Paragraph::new(field_line_text("Compare With ABI File (optional)", &self.compare_file, self.field_index == 0)),
+            mid_chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new(field_line_text("Output File", &self.out_file, self.field_index == 1)),
+            mid_chunks[2],
+        );
+        f.render_widget(Paragraph::new(self.buttons_line()), mid_chunks[4]);
+        let output: Vec<Line> = match &self.listing {
+            Ok(lines) => lines.iter().skip(self.scroll).map(|l| Line::from(l.clone())).collect(),
+            Err(e) => vec![Line::styled(format!("Error: {e}"), Style::default().fg(Color::Red))],
+        };
+        f.render_widget(Paragraph::new(output).wrap(Wrap { trim: false }), mid_chunks[6]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"),   span_text(" Navigate"), span_sep(),
+            span_key("PgUp/PgDn"), span_text(" Scroll"), span_sep(),
+            span_key("Enter"),     span_text(" Select"), span_sep(),
+            span_key("Esc"),       span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),    span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let output_len = self.listing.as_ref().map_or(0, |lines| lines.len());
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 5;
+            }
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(output_len.saturating_sub(1)),
+            KeyCode::Left if (3..=4).contains(&self.field_index) => self.field_index -= 1,
+            KeyCode::Right if (2..=3).contains(&self.field_index) => self.field_index += 1,
+
+            // Enter on Compare With ABI File or [List]
+            KeyCode::Enter if self.field_index == 0 || self.field_index == 2 => self.list(&*ctx.fs),
+            // Enter on [Save Selectors]
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(Transition::Push(Box::new(match self.save(&*ctx.fs) {
+                    Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+                })));
+            }
+            // Enter on [Back]
+            KeyCode::Enter if self.field_index == 4 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
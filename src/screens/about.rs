@@ -0,0 +1,129 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::release::attestation_lines;
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_line, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Main,
+        order: 50,
+        title: "About / Verify This Binary",
+        description: "Version and release attestation of the running binary.",
+        open: |_| Transition::Push(Box::new(AboutScreen::new())),
+    }
+}
+
+/// Version and release attestation of the running binary (see `release.rs`), the same
+/// output as `verify-binary`.
+pub struct AboutScreen {
+    lines: Vec<String>,
+    /// First attestation line shown.
+    scroll: usize,
+}
+
+impl AboutScreen {
+    pub fn new() -> Self {
+        let lines = attestation_lines().unwrap_or_else(|e| vec![format!("Error: {e:#}")]);
+        Self { lines, scroll: 0 }
+    }
+}
+
+impl Default for AboutScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for AboutScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "About" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let explanation_paras = [
+            "Compare these values with the ones published for this release. The same output is printed by running the binary with verify-binary.",
+            "A modified binary can report anything about itself: also hash the file with an independent tool (sha256sum) and compare binarySha256.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(&header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // attestation + spacer + button
+        let middle_needed = 2 + 2 + self.lines.len() as u16 + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: scrollable attestation above the button
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let lines: Vec<Line> = self.lines.iter().skip(self.scroll).map(|l| Line::from(l.clone())).collect();
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), mid_chunks[0]);
+        f.render_widget(Paragraph::new(button_line("Back", true)), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),    span_text(" Scroll"), span_sep(),
+            span_key("Enter"),  span_text(" Back"), span_sep(),
+            span_key("Esc"),    span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"), span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if self.scroll + 1 < self.lines.len() => self.scroll += 1,
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::address_table::{write_address_table, TableFormat};
+use crate::defaults::Defaults;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 60,
+        title: "Address Table (From Public Keys)",
+        description: "List the Ethereum address and npub of each public key, as CSV or JSON, for online systems.",
+        open: |_| Transition::Push(Box::new(AddressTableScreen::new())),
+    }
+}
+
+/// Derive addresses and npubs for a list of public keys and write them as a table.
+pub struct AddressTableScreen {
+    // indices: 0 pubkeys file, 1 format (toggle), 2 output file, 3 create, 4 back
+    field_index: usize,
+    pubkeys_file: TextField,
+    format: TableFormat,
+    out_file: TextField,
+}
+
+impl AddressTableScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            pubkeys_file: TextField::with(Defaults::ADDRESS_TABLE_PUBKEYS_FILE),
+            format: TableFormat::Csv,
+            out_file: TextField::with(Defaults::ADDRESS_TABLE_OUT_FILE),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 2) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.pubkeys_file,
+            2 => &mut self.out_file,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    /// Switch the format, and the output file's extension along with it.
+    fn toggle_format(&mut self) {
+        let old = self.format;
+        self.format = match old {
+            TableFormat::Csv => TableFormat::Json,
+            TableFormat::Json => TableFormat::Csv,
+        };
+        let old_ext = format!(".{}", old.extension());
+        if let Some(stem) = self.out_file.text.strip_suffix(&old_ext) {
+            self.out_file = TextField::with(&format!("{stem}.{}", self.format.extension()));
+        }
+    }
+
+    fn format_line(&self) -> Line<'static> {
+        let val_style = if self.field_index == 1 {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Line::from(vec![
+            Span::styled("Table Format:  ", Style::default().fg(Color::Yellow)),
+            Span::styled(self.format.label().to_string(), val_style),
+        ])
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Create Table", self.field_index == 3));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 4));
+        Line::from(spans)
+    }
+
+    fn create(&self, ctx: &AppCtx) -> Result<Vec<String>> {
+        let list = self.pubkeys_file.text.trim();
+        if list.is_empty() {
+            return Err(anyhow!("PubKeys File cannot be empty."));
+        }
+        let out = self.out_file.text.trim();
+        if out.is_empty() {
+            return Err(anyhow!("Output File cannot be empty."));
+        }
+        let count = write_address_table(&*ctx.fs, Path::new(list), Path::new(out), self.format)?;
+        Ok(vec![
+            format!("Address table of {count} key(s) written to {out}"),
+            "It holds public material only and can be copied to online systems.".to_string(),
+        ])
+    }
+}
+
+impl Default for AddressTableScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for AddressTableScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Address Table" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Address Table (From Public Keys)";
+        let explanation_paras = [
+            "Derive the Ethereum address, npub, compressed pubkey and key-file fingerprint of every public key in a list, for allowlists and monitoring on online systems. Only public keys are read; no key file is unlocked.",
+            "The PubKeys File lists one pubkey per line (compressed, uncompressed, or 64 bytes without the 04 prefix; # comments allowed). An npub cannot be used: it does not determine the address.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 3 fields + spacer + buttons
+        let middle_needed = 2 + 2 + 6;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("PubKeys File", &self.pubkeys_file, self.field_index == 0),
+            self.format_line(),
+            field_line_text("Output File", &self.out_file, self.field_index == 2),
+            Line::from(""),
+            self.buttons_line(),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 5;
+            }
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 1 => self.toggle_format(),
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(Transition::Push(Box::new(match self.create(ctx) {
+                    Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+                })));
+            }
+            KeyCode::Enter if self.field_index == 4 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -8,52 +8,96 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
+use std::path::Path;
 use textwrap::wrap;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::compat::write_compat_file;
+use crate::defaults::Defaults;
+use crate::screens::registry::{self, Menu, MenuEntry, ToolCategory};
+use crate::screens::ConfirmOkScreen;
+use crate::ui::components::{field_line_text, TextField};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text};
-use crate::ui::common_nav::esc_to_back;
 
+inventory::submit! {
+    MenuEntry { menu: Menu::Main, order: 30, title: "Advanced Tools", description: "Builders and utilities, grouped by task.", open: |_| Transition::Push(Box::new(AdvancedToolsScreen::new())) }
+}
+
+// compat.json is written straight from the menu; there is no screen of its own to register it
+inventory::submit! {
+    MenuEntry { menu: Menu::Advanced(ToolCategory::Files), order: 40, title: "Write Compatibility File (compat.json)", description: "Describe this signer's formats and limits for the online side.", open: |_| open_write_compat_file() }
+}
+
+fn open_write_compat_file() -> Transition {
+    let path = Defaults::COMPAT_FILE;
+    Transition::Push(Box::new(match write_compat_file(Path::new(path)) {
+        Ok(()) => ConfirmOkScreen::new(format!("Compatibility file written to {path}")),
+        Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")),
+    }))
+}
+
+#[derive(Copy, Clone)]
+enum Row {
+    Category(ToolCategory),
+    Tool(&'static MenuEntry),
+    Back,
+}
+
+impl Row {
+    fn description(self) -> &'static str {
+        match self {
+            Row::Category(c) => c.description(),
+            Row::Tool(t) => t.description,
+            Row::Back => "",
+        }
+    }
+}
+
+/// The categories (or, inside one, its tools). Typing searches every tool in scope.
 #[derive(Default)]
 pub struct AdvancedToolsScreen {
+    /// None for the top level.
+    category: Option<ToolCategory>,
+    search: TextField,
+    /// Index into the rows.
     menu_index: usize,
 }
+
 impl AdvancedToolsScreen {
     pub fn new() -> Self { Self::default() }
-}
 
-#[derive(Copy, Clone, Debug)]
-enum MenuItem {
-    CreateKeyPair,
-    CreateDelegation,
-    CreateRevocation,
-    CreateRedelegation,
-    CreatePermanentInvalidation,
-    DecryptFile,                 // NEW
-    BackToMain,
-}
-impl MenuItem {
-    fn all() -> Vec<MenuItem> {
-        vec![
-            MenuItem::CreateKeyPair,
-            MenuItem::CreateDelegation,
-            MenuItem::CreateRevocation,
-            MenuItem::CreateRedelegation,
-            MenuItem::CreatePermanentInvalidation,
-            MenuItem::DecryptFile,          // NEW: placed before BackToMain
-            MenuItem::BackToMain,
-        ]
+    fn in_category(category: ToolCategory) -> Self {
+        Self { category: Some(category), ..Self::default() }
     }
-    fn label(&self) -> &'static str {
-        match self {
-            MenuItem::CreateKeyPair => "Create Key Pair",
-            MenuItem::CreateDelegation => "Create Delegation",
-            MenuItem::CreateRevocation => "Create Revocation",
-            MenuItem::CreateRedelegation => "Create Re-Delegation",
-            MenuItem::CreatePermanentInvalidation => "Create Permanent Invalidation",
-            MenuItem::DecryptFile => "Decrypt File",                 // NEW
-            MenuItem::BackToMain => "Back To Main Menu",
+
+    fn rows(&self) -> Vec<Row> {
+        let query = self.search.text.trim();
+        let tools = match self.category {
+            None => registry::tools(),
+            Some(c) => registry::entries(Menu::Advanced(c)),
+        };
+        if !query.is_empty() {
+            return tools.into_iter().filter(|t| t.matches(query)).map(Row::Tool).collect();
+        }
+        let mut rows: Vec<Row> = match self.category {
+            None => ToolCategory::ALL.into_iter().map(Row::Category).collect(),
+            Some(_) => tools.into_iter().map(Row::Tool).collect(),
+        };
+        rows.push(Row::Back);
+        rows
+    }
+
+    fn row_label(&self, row: Row) -> String {
+        match row {
+            Row::Category(c) => format!("{} ▸", c.label()),
+            // Search results from the top level say where the tool lives
+            Row::Tool(t) => match (self.category, t.menu) {
+                (None, Menu::Advanced(c)) => format!("{}  ({})", t.title, c.label()),
+                _ => t.title.to_string(),
+            },
+            Row::Back if self.category.is_none() => "Back To Main Menu".to_string(),
+            Row::Back => "Back To Advanced Tools".to_string(),
         }
     }
 }
@@ -61,12 +105,18 @@ impl MenuItem {
 #[async_trait]
 impl ScreenWidget for AdvancedToolsScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str {
+        self.category.map_or("Advanced Tools", ToolCategory::label)
+    }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
-        let header_text = "Advanced Tools";
+        let header_text = self.category.map_or("Advanced Tools", ToolCategory::label);
         let explanation_paras = [
-            "This section hosts advanced, offline-safe builders and utilities.",
-            "Select a tool to proceed. Press Ctrl+Q to confirm quit.",
+            match self.category {
+                None => "This section hosts advanced, offline-safe builders and utilities, grouped by task.",
+                Some(c) => c.description(),
+            },
+            "Select an entry, or type to search the tools. Press Ctrl+Q to confirm quit.",
         ];
 
         // dynamic sizing like your other screens
@@ -79,8 +129,12 @@ impl ScreenWidget for AdvancedToolsScreen {
 
         let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
 
-        let menu_items = MenuItem::all();
-        let middle_needed = 2 + 2 + (menu_items.len() as u16);
+        let rows = self.rows();
+        let selected = rows.get(self.menu_index).copied();
+        let description = selected.map(Row::description).unwrap_or("");
+        let desc_lines = wrap(description, top_inner_width).len().max(1) as u16;
+        // search + blank + rows (at least the "no match" line) + blank + description
+        let middle_needed = 2 + 2 + 2 + (rows.len().max(1) as u16) + 1 + desc_lines;
         let footer_height = 3;
 
         let regions = three_box_layout(
@@ -121,19 +175,34 @@ impl ScreenWidget for AdvancedToolsScreen {
         f.render_widget(header_para, top_chunks[0]);
         f.render_widget(explanation_para, top_chunks[2]);
 
-        // MIDDLE
+        // MIDDLE: search, entries, description of the highlighted one
         f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(desc_lines),
+            ])
+            .split(regions.middle_inner);
 
-        // Build list items, but start with an empty spacer line
-        let mut list_items: Vec<ListItem> = Vec::new();
-        list_items.push(ListItem::new(Line::from(""))); // <-- adds one blank line at top
+        f.render_widget(Paragraph::new(field_line_text("Search", &self.search, true)), mid_chunks[0]);
 
-        for (i, it) in menu_items.iter().enumerate() {
+        let mut list_items: Vec<ListItem> = Vec::new();
+        if rows.is_empty() {
+            list_items.push(ListItem::new(Line::from(Span::styled(
+                "No tool matches the search.",
+                Style::default().fg(Color::DarkGray),
+            ))));
+        }
+        for (i, row) in rows.iter().enumerate() {
             let selected = i == self.menu_index;
             let prefix = if selected { "▶ " } else { "  " };
             let line = Line::from(vec![
                 Span::styled(prefix, Style::default().fg(Color::Cyan)),
-                Span::raw(it.label()),
+                Span::raw(self.row_label(*row)),
             ]);
             list_items.push(ListItem::new(line));
         }
@@ -141,24 +210,29 @@ impl ScreenWidget for AdvancedToolsScreen {
         let list = List::new(list_items)
             .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
 
-        f.render_widget(list, regions.middle_inner);
+        f.render_widget(list, mid_chunks[2]);
+
+        let desc: Vec<Line> = wrap(description, top_inner_width)
+            .into_iter()
+            .map(|seg| Line::from(Span::styled(seg.to_string(), Style::default().fg(Color::DarkGray))))
+            .collect();
+        f.render_widget(Paragraph::new(desc), mid_chunks[4]);
 
         // FOOTER
         f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let esc_text = if self.search.text.is_empty() { " Back" } else { " Clear search" };
         let footer_line = Line::from(vec![
             span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
             span_key("Enter"), span_text(" Select"), span_sep(),
-            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Type"), span_text(" Search"), span_sep(),
+            span_key("Esc"),     span_text(esc_text), span_sep(),
             span_key("Ctrl+Q"), span_text(" Quit"),
         ]);
         let footer_para = Paragraph::new(footer_line).wrap(Wrap { trim: true });
         f.render_widget(footer_para, regions.bottom_inner);
     }
 
-    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
-        if let Some(t) = esc_to_back(k) {
-            return Ok(t); // Esc -> Back
-        }
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         // Ctrl+Q here opens confirmation dialog (unlike main menu)
         if let KeyCode::Char('q') = k.code {
             if k.modifiers.contains(KeyModifiers::CONTROL) {
@@ -166,34 +240,38 @@ impl ScreenWidget for AdvancedToolsScreen {
             }
         }
 
+        let rows = self.rows();
         match k.code {
-            KeyCode::Up => {
-                if self.menu_index == 0 { self.menu_index = MenuItem::all().len() - 1; }
+            KeyCode::Esc if !self.search.text.is_empty() => {
+                self.search = TextField::default();
+                self.menu_index = 0;
+            }
+            KeyCode::Esc => return Ok(Transition::Pop),
+            KeyCode::Up if !rows.is_empty() => {
+                if self.menu_index == 0 { self.menu_index = rows.len() - 1; }
                 else { self.menu_index -= 1; }
             }
-            KeyCode::Down | KeyCode::Tab => {
-                self.menu_index = (self.menu_index + 1) % MenuItem::all().len();
+            KeyCode::Down | KeyCode::Tab if !rows.is_empty() => {
+                self.menu_index = (self.menu_index + 1) % rows.len();
             }
             KeyCode::Enter => {
-                return Ok(match MenuItem::all()[self.menu_index] {
-                    MenuItem::CreateKeyPair =>
-                        Transition::Push(Box::new(crate::screens::CreateKeyPairScreen::new())),
-                    MenuItem::CreateDelegation =>
-                        Transition::Push(Box::new(crate::screens::CreateDelegationScreen::new())),
-                    MenuItem::CreateRevocation =>
-                        Transition::Push(Box::new(crate::screens::CreateRevocationScreen::new())),
-                    MenuItem::CreateRedelegation =>
-                        Transition::Push(Box::new(crate::screens::CreateRedelegationScreen::new())),
-                    MenuItem::CreatePermanentInvalidation =>
-                        Transition::Push(Box::new(crate::screens::CreatePermanentInvalidationScreen::new())),
-                    MenuItem::DecryptFile =>                            // NEW
-                        Transition::Push(Box::new(crate::screens::DecryptFileScreen::new())),
-                    MenuItem::BackToMain => Transition::Pop,
-                })
+                return Ok(match rows.get(self.menu_index) {
+                    Some(Row::Category(c)) => Transition::Push(Box::new(Self::in_category(*c))),
+                    Some(Row::Tool(t)) => t.launch(ctx),
+                    Some(Row::Back) => Transition::Pop,
+                    None => Transition::Stay,
+                });
+            }
+            KeyCode::Backspace => {
+                self.search.backspace();
+                self.menu_index = 0;
+            }
+            KeyCode::Char(c) if !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.insert_char(c);
+                self.menu_index = 0;
             }
             _ => {}
         }
         Ok(Transition::Stay)
     }
 }
-
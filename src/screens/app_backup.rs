@@ -0,0 +1,269 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_backup::{backup_app_data, restore_app_data};
+use crate::app_state::AppEvent;
+use crate::defaults::Defaults;
+use crate::key_usage::load_key_usage;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::migrate::migrate_stores;
+use crate::settings::load_settings;
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, RevealBriefly, TextField, field_line_password};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Configuration),
+        order: 30,
+        title: "Back Up / Restore App Data",
+        description: "Archive settings, key usage and the master-password profile into one encrypted file, or restore them from one.",
+        open: |_| Transition::Push(Box::new(AppBackupScreen::new())),
+    }
+}
+
+/// Back up this directory's app data into a passphrase-encrypted file, or restore it from one.
+pub struct AppBackupScreen {
+    // indices: 0 backup file, 1 passphrase, 2 confirm passphrase, 3 back up, 4 restore, 5 back
+    field_index: usize,
+    backup_file: TextField,
+    passphrase: TextField,
+    confirm: TextField,
+    reveal: RevealBriefly,
+}
+
+impl AppBackupScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            backup_file: TextField::with(Defaults::APP_BACKUP_FILE),
+            passphrase: TextField::default(),
+            confirm: TextField::default(),
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0..=2) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.backup_file,
+            1 => &mut self.passphrase,
+            2 => &mut self.confirm,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Back Up", self.field_index == 3));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Restore", self.field_index == 4));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 5));
+        Line::from(spans)
+    }
+
+    /// The typed passphrase, taken out of both fields.
+    fn take_passphrase(&mut self) -> Vec<u8> {
+        self.confirm.text.zeroize();
+        self.confirm.cursor = 0;
+        self.passphrase.cursor = 0;
+        std::mem::take(&mut self.passphrase.text).into_bytes()
+    }
+
+    fn backup(&mut self) -> Result<Vec<String>> {
+        if self.passphrase.text.is_empty() {
+            return Err(anyhow!("Backup Passphrase cannot be empty."));
+        }
+        if self.passphrase.text != self.confirm.text {
+            return Err(anyhow!("Backup Passphrase and Confirm Passphrase do not match."));
+        }
+        let path = self.backup_file.text.trim().to_string();
+        let names = backup_app_data(Path::new(&path), &mut self.take_passphrase())?;
+        Ok(vec![
+            format!("App data backed up to {path}"),
+            format!("Files: {}", names.join(", ")),
+            "Keep the passphrase apart from the backup. Key files are not included.".to_string(),
+        ])
+    }
+
+    /// Restore, then bring the session in line with the restored data. Returns the message and
+    /// whether the tool has to close.
+    fn restore(&mut self, ctx: &mut AppCtx) -> Result<(Vec<String>, bool)> {
+        if self.passphrase.text.is_empty() {
+            return Err(anyhow!("Backup Passphrase cannot be empty."));
+        }
+        let path = self.backup_file.text.trim().to_string();
+        let outcome = restore_app_data(Path::new(&path), &mut self.take_passphrase())?;
+        let mut lines = vec![
+            format!("App data restored from {path}"),
+            format!("Restored: {}", outcome.restored.join(", ")),
+        ];
+        if !outcome.moved_aside.is_empty() {
+            lines.push(format!("Previous files kept as <name>.before-restore: {}", outcome.moved_aside.join(", ")));
+        }
+        // The old session key belongs to files that were just moved aside
+        if ctx.profile_key.is_some() {
+            ctx.dispatch(self.crumb(), AppEvent::ProfileKeyChanged(None));
+        }
+        if outcome.has_profile {
+            lines.push("The restored data is protected by a master password. The tool will now close;".to_string());
+            lines.push("start it again and unlock with that password.".to_string());
+            return Ok((lines, true));
+        }
+        match migrate_stores(None) {
+            Ok(upgraded) => lines.extend(upgraded),
+            Err(e) => {
+                lines.push(format!("Error: {e:#}"));
+                lines.push("The tool will now close.".to_string());
+                return Ok((lines, true));
+            }
+        }
+        ctx.set_settings(load_settings(None));
+        ctx.dispatch(self.crumb(), AppEvent::KeyUsageChanged(load_key_usage(None)));
+        Ok((lines, false))
+    }
+}
+
+impl Default for AppBackupScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for AppBackupScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "App Data Backup" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Back Up / Restore App Data";
+        let explanation_paras = [
+            "Move this tool's data to replacement hardware in one file: settings, key usage records and the master-password profile, encrypted with a backup passphrase. Key files are not included; copy them separately.",
+            "Back Up writes a new file and never overwrites one. Restore checks the whole backup first, keeps the app data already here as <name>.before-restore, then writes the backed-up files.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 3 fields + spacer + buttons
+        let middle_needed = 2 + 2 + 6;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Backup File", &self.backup_file, self.field_index == 0),
+            field_line_password("Backup Passphrase", &self.passphrase, self.field_index == 1, self.reveal.shows(1, self.field_index)),
+            field_line_password("Confirm Passphrase (back up)", &self.confirm, self.field_index == 2, self.reveal.shows(2, self.field_index)),
+            Line::from(""),
+            self.buttons_line(),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        if is_reveal_key(&k) {
+            if matches!(self.field_index, 1 | 2) { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
+        let error = |e: anyhow::Error| {
+            Transition::Push(Box::new(ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)))
+        };
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 5; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 6;
+            }
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(match self.backup() {
+                    Ok(lines) => Transition::Push(Box::new(ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop))),
+                    Err(e) => error(e),
+                });
+            }
+            KeyCode::Enter if self.field_index == 4 => {
+                return Ok(match self.restore(ctx) {
+                    Ok((lines, quit)) => {
+                        let after = if quit { AfterOk::Quit } else { AfterOk::Pop };
+                        Transition::Push(Box::new(ConfirmOkScreen::with_lines(lines).with_after_ok(after)))
+                    }
+                    Err(e) => error(e),
+                });
+            }
+            KeyCode::Enter if self.field_index == 5 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::audit_log::{export_audit_proof, verify_audit_proof, ProofSummary};
+use crate::defaults::Defaults;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, RevealBriefly, TextField, field_line_password};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 40,
+        title: "Audit Log Proof",
+        description: "Export a signed snapshot of the audit log, or check that a later one kept every entry.",
+        open: |_| Transition::Push(Box::new(AuditProofScreen::new())),
+    }
+}
+
+/// Export a signed proof of the audit log, or verify one against an earlier proof.
+pub struct AuditProofScreen {
+    // indices: 0 proof file, 1 signing key, 2 earlier proof, 3 expected signer,
+    // 4 export, 5 verify, 6 back
+    field_index: usize,
+    proof_file: TextField,
+    signing_key: TextField,
+    earlier_proof: TextField,
+    expected_signer: TextField,
+    reveal: RevealBriefly,
+}
+
+impl AuditProofScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            proof_file: TextField::with(Defaults::AUDIT_PROOF_FILE),
+            signing_key: TextField::default(),
+            earlier_proof: TextField::default(),
+            expected_signer: TextField::default(),
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0..=3) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.proof_file,
+            1 => &mut self.signing_key,
+            2 => &mut self.earlier_proof,
+            3 => &mut self.expected_signer,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Export Proof", self.field_index == 4));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Verify Proof", self.field_index == 5));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 6));
+        Line::from(spans)
+    }
+
+    fn summary_lines(s: &ProofSummary) -> Vec<String> {
+        vec![
+            format!("Entries: {}, taken {}", s.entry_count, s.created_at),
+            format!("Head hash: {}", s.head_hash),
+            format!("Signed by {}", s.signer),
+        ]
+    }
+
+    async fn export(&mut self, ctx: &AppCtx) -> Result<Vec<String>> {
+        if self.signing_key.text.trim().is_empty() {
+            return Err(anyhow!("Signing Key is required to export a proof."));
+        }
+        let path = self.proof_file.text.trim().to_string();
+        let res = export_audit_proof(ctx.profile_key.as_ref(), &self.signing_key.text, Path::new(&path)).await;
+        self.signing_key.text.zeroize();
+        self.signing_key.cursor = 0;
+        let summary = res?;
+        let mut lines = vec![format!("Audit log proof written to {path}")];
+        lines.extend(Self::summary_lines(&summary));
+        lines.push("Keep it with the ceremony records; the next proof is checked against it.".to_string());
+        Ok(lines)
+    }
+
+    fn verify(&self) -> Result<Vec<String>> {
+        let path = self.proof_file.text.trim().to_string();
+        let earlier = self.earlier_proof.text.trim();
+        let expected = self.expected_signer.text.trim();
+        let (summary, before) = verify_audit_proof(
+            Path::new(&path),
+            (!earlier.is_empty()).then(|| Path::new(earlier)),
+            Some(expected),
+        )?;
+        let mut lines = vec![format!("{path}: chain and signature valid")];
+        lines.extend(Self::summary_lines(&summary));
+        if let Some(before) = before {
+            lines.push("".to_string());
+            lines.push(format!("It holds all {} entries of {earlier} unchanged, up to head", before.entry_count));
+            lines.push(before.head_hash);
+        }
+        if expected.is_empty() {
+            lines.push("No Expected Signer was given: compare the signer above with the operator's address.".to_string());
+        }
+        Ok(lines)
+    }
+}
+
+impl Default for AuditProofScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for AuditProofScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Audit Log Proof" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Audit Log Proof";
+        let explanation_paras = [
+            "Every signing that writes a signed file is appended to the hash-chained audit log. A proof is a time-stamped snapshot of the whole log with its head hash signed by an operator key (hex or nsec; the key is not stored).",
+            "Export writes a new proof and records the export in the log. Verify checks a proof's chain and signature and, given the proof from an earlier ceremony, that every entry it held is still there unchanged.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 4 fields + spacer + buttons
+        let middle_needed = 2 + 2 + 7;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Proof File", &self.proof_file, self.field_index == 0),
+            field_line_password("Signing Key (export)", &self.signing_key, self.field_index == 1, self.reveal.shows(1, self.field_index)),
+            field_line_text("Earlier Proof File (verify, optional)", &self.earlier_proof, self.field_index == 2),
+            field_line_text("Expected Signer (verify, optional)", &self.expected_signer, self.field_index == 3),
+            Line::from(""),
+            self.buttons_line(),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        if is_reveal_key(&k) {
+            if self.field_index == 1 { self.reveal.start(1); }
+            return Ok(Transition::Stay);
+        }
+
+        let outcome = |res: Result<Vec<String>>| {
+            Transition::Push(Box::new(match res {
+                Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+            }))
+        };
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 6; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 7;
+            }
+            KeyCode::Enter if self.field_index == 4 => return Ok(outcome(self.export(ctx).await)),
+            KeyCode::Enter if self.field_index == 5 => return Ok(outcome(self.verify())),
+            KeyCode::Enter if self.field_index == 6 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,494 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::decrypt_auto::{decrypt_auto_in_memory, looks_encrypted, write_decrypted_output};
+use crate::commands::decrypt_modern::{modern_kdf_costs, modern_key_factors};
+use crate::crypto::container::parse_container;
+use crate::crypto::factors::mix_factors;
+use crate::crypto::kdf_progress::{estimate, KdfJob};
+use crate::defaults::Defaults;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, kdf_progress_lines, RevealBriefly, TextField, field_line_password};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+
+enum Phase {
+    /// 0 password, 1 show pwd, 2 keyfile, 3 password mode, 4 out dir, 5 start, 6 cancel
+    Setup,
+    /// Ask-each mode, before file `next`: 0 password, 1 decrypt, 2 skip, 3 stop
+    Prompt,
+    Running(KdfJob<String>),
+    Done,
+}
+
+/// Outcome of one file, for the summary.
+enum Outcome {
+    Written(String),
+    Failed(String),
+    Skipped(&'static str),
+}
+
+/// Decrypt every recognized encrypted key file of a directory into one output directory, with
+/// one password for all of them or a prompt per file, then list what happened to each file.
+/// A failed file does not stop the run; a multi-key container is written whole.
+pub struct BatchDecryptScreen {
+    dir: PathBuf,
+    files: Vec<PathBuf>,
+    phase: Phase,
+    field_index: usize,
+    password: TextField,
+    show_password: bool,
+    reveal: RevealBriefly,
+    keyfile: TextField,
+    ask_each: bool,
+    out_dir: TextField,
+    out_dir_path: PathBuf,
+    /// Password typed in the per-file prompt.
+    file_password: TextField,
+    /// Index into `files` of the running or next file.
+    next: usize,
+    results: Vec<(PathBuf, Outcome)>,
+    /// First summary line shown.
+    scroll: usize,
+}
+
+impl BatchDecryptScreen {
+    /// `entries` are the files listed in `dir`; those that are not encrypted files are
+    /// reported as skipped.
+    pub fn new(dir: PathBuf, entries: &[PathBuf]) -> Self {
+        let mut files = Vec::new();
+        let mut results = Vec::new();
+        for p in entries {
+            match fs::read(p) {
+                Ok(buf) if looks_encrypted(&buf) => files.push(p.clone()),
+                Ok(_) => results.push((p.clone(), Outcome::Skipped("not a recognized encrypted file"))),
+                Err(_) => results.push((p.clone(), Outcome::Skipped("not readable"))),
+            }
+        }
+        Self {
+            dir,
+            files,
+            phase: Phase::Setup,
+            field_index: 0,
+            password: TextField::with(""),
+            show_password: false,
+            reveal: RevealBriefly::default(),
+            keyfile: TextField::with(""),
+            ask_each: false,
+            out_dir: TextField::with(Defaults::DECRYPT_OUTPUT_DIR),
+            out_dir_path: PathBuf::new(),
+            file_password: TextField::with(""),
+            next: 0,
+            results,
+            scroll: 0,
+        }
+    }
+
+    fn file_name(p: &Path) -> String {
+        p.file_name().unwrap_or_default().to_string_lossy().to_string()
+    }
+
+    fn keyfile_path(&self) -> Option<PathBuf> {
+        let k = self.keyfile.text.trim();
+        (!k.is_empty()).then(|| PathBuf::from(k))
+    }
+
+    /// Decrypt file `next` with `password_utf8` on a worker thread.
+    fn start_file(&mut self, mut password_utf8: Vec<u8>) {
+        let input = self.files[self.next].clone();
+        let head = fs::read(&input).unwrap_or_default();
+        let factors = modern_key_factors(&head);
+        let expected = modern_kdf_costs(&head)
+            .filter(|_| factors.fido2.is_none())
+            .map(|(m_cost_kib, t_cost)| estimate(m_cost_kib, t_cost));
+        let label = format!(
+            "File {} of {}: {}{}",
+            self.next + 1, self.files.len(), Self::file_name(&input),
+            if factors.fido2.is_some() { " (touch the security key)" } else { "" },
+        );
+        let keyfile = self.keyfile_path();
+        let out_dir = self.out_dir_path.clone();
+        self.phase = Phase::Running(KdfJob::spawn(label, expected, move || {
+            if let Err(e) = mix_factors(&mut password_utf8, &factors, keyfile.as_deref()) {
+                password_utf8.zeroize();
+                return Err(e);
+            }
+            let decrypted = decrypt_auto_in_memory(&input, &mut password_utf8)?;
+            let out = write_decrypted_output(&input, &out_dir, None, &decrypted.plaintext)?;
+            Ok(match parse_container(&decrypted.plaintext) {
+                Some(records) => format!("{} (container of {} keys, written whole; {})", out.display(), records.len(), decrypted.method_label),
+                None => format!("{} ({})", out.display(), decrypted.method_label),
+            })
+        }));
+    }
+
+    /// Move on to file `next`: prompt for it, start it, or finish.
+    fn advance(&mut self) {
+        if self.next >= self.files.len() {
+            self.password.text.zeroize();
+            self.password = TextField::with("");
+            self.phase = Phase::Done;
+            return;
+        }
+        if self.ask_each {
+            self.file_password = TextField::with("");
+            self.field_index = 0;
+            self.phase = Phase::Prompt;
+        } else {
+            let pwd = self.password.text.clone().into_bytes();
+            self.start_file(pwd);
+        }
+    }
+
+    fn summary_lines(&self) -> Vec<String> {
+        let written = self.results.iter().filter(|(_, o)| matches!(o, Outcome::Written(_))).count();
+        let failed = self.results.iter().filter(|(_, o)| matches!(o, Outcome::Failed(_))).count();
+        let skipped = self.results.len() - written - failed;
+        let mut out = vec![
+            format!("Decrypted {written} file(s), {failed} failed, {skipped} skipped."),
+            String::new(),
+        ];
+        for (p, o) in &self.results {
+            out.push(match o {
+                Outcome::Written(to) => format!("[OK]   {} -> {to}", Self::file_name(p)),
+                Outcome::Failed(e) => format!("[FAIL] {}: {e}", Self::file_name(p)),
+                Outcome::Skipped(why) => format!("[SKIP] {}: {why}", Self::file_name(p)),
+            });
+        }
+        out
+    }
+
+    fn toggle_line(label: &str, val: &str, selected: bool) -> Line<'static> {
+        let val_style = if selected {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::Yellow)),
+            Span::styled(val.to_string(), val_style),
+        ])
+    }
+
+    fn buttons_line(labels: &[&'static str], selected: Option<usize>) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (i, l) in labels.iter().enumerate() {
+            if i > 0 { spans.push(Span::raw("   ")); }
+            spans.extend(button_spans(*l, selected == Some(i)));
+        }
+        Line::from(spans)
+    }
+
+    fn middle_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = vec![Line::from("")];
+        match &self.phase {
+            Phase::Setup => {
+                let fi = self.field_index;
+                if self.ask_each {
+                    lines.push(Self::toggle_line("Password", "asked for each file", false));
+                } else {
+                    lines.push(field_line_password("Password", &self.password, fi == 0, self.show_password || self.reveal.shows(0, fi)));
+                }
+                lines.push(Self::toggle_line("Show Password", if self.show_password { "On" } else { "Off" }, fi == 1));
+                lines.push(field_line_text("Keyfile (If Required)", &self.keyfile, fi == 2));
+                lines.push(Self::toggle_line(
+                    "Password Mode",
+                    if self.ask_each { "Ask For Each File" } else { "Same For All Files" },
+                    fi == 3,
+                ));
+                lines.push(field_line_text("Output Directory", &self.out_dir, fi == 4));
+                lines.push(Line::from(""));
+                lines.push(Self::buttons_line(&["Decrypt All", "Cancel"], fi.checked_sub(5)));
+            }
+            Phase::Prompt => {
+                let input = &self.files[self.next];
+                let factors = fs::read(input).map(|b| modern_key_factors(&b)).unwrap_or_default();
+                lines.push(Line::from(format!("File {} of {}: {}", self.next + 1, self.files.len(), Self::file_name(input))));
+                if !factors.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "Also needs {}.{}",
+                            factors.describe(),
+                            if factors.keyfile { " The keyfile entered before the run is used." } else { "" },
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+                let fi = self.field_index;
+                lines.push(field_line_password("Password", &self.file_password, fi == 0, self.show_password || self.reveal.shows(0, fi)));
+                lines.push(Line::from(""));
+                lines.push(Self::buttons_line(&["Decrypt This File", "Skip", "Stop"], fi.checked_sub(1)));
+            }
+            Phase::Running(job) => lines.extend(kdf_progress_lines(job)),
+            Phase::Done => {
+                for l in self.summary_lines().into_iter().skip(self.scroll) {
+                    let color = if l.starts_with("[FAIL]") {
+                        Some(Color::Red)
+                    } else if l.starts_with("[SKIP]") {
+                        Some(Color::Yellow)
+                    } else if l.starts_with("[OK]") {
+                        Some(Color::Green)
+                    } else {
+                        None
+                    };
+                    lines.push(match color {
+                        Some(c) => Line::from(Span::styled(l, Style::default().fg(c))),
+                        None => Line::from(l),
+                    });
+                }
+            }
+        }
+        lines
+    }
+
+    fn on_key_setup(&mut self, k: KeyEvent) -> Result<Transition> {
+        let skip = |i: usize| i == 0 && self.ask_each;
+        match k.code {
+            KeyCode::Up => {
+                self.field_index = if self.field_index == 0 { 6 } else { self.field_index - 1 };
+                if skip(self.field_index) { self.field_index = 6; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 7;
+                if skip(self.field_index) { self.field_index = 1; }
+            }
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 1 => {
+                self.show_password = !self.show_password;
+            }
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 3 => {
+                self.ask_each = !self.ask_each;
+            }
+            KeyCode::Enter if self.field_index == 5 => {
+                if self.files.is_empty() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: No encrypted files found in this directory.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                if !self.ask_each && self.password.text.is_empty() && self.keyfile_path().is_none() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: Password cannot be empty.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                let out_dir = self.out_dir.text.trim();
+                if out_dir.is_empty() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: Output Directory cannot be empty.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                self.out_dir_path = PathBuf::from(out_dir);
+                fs::create_dir_all(&self.out_dir_path)
+                    .with_context(|| format!("creating directory {}", self.out_dir_path.display()))?;
+                self.advance();
+            }
+            KeyCode::Enter if self.field_index == 6 => return Ok(Transition::Pop),
+            _ => {
+                let tf = match self.field_index {
+                    0 => &mut self.password,
+                    2 => &mut self.keyfile,
+                    4 => &mut self.out_dir,
+                    _ => return Ok(Transition::Stay),
+                };
+                edit_text(tf, k);
+            }
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn on_key_prompt(&mut self, k: KeyEvent) -> Transition {
+        match k.code {
+            KeyCode::Up => self.field_index = if self.field_index == 0 { 3 } else { self.field_index - 1 },
+            KeyCode::Down | KeyCode::Tab => self.field_index = (self.field_index + 1) % 4,
+            KeyCode::Enter if matches!(self.field_index, 0 | 1) => {
+                let pwd = std::mem::take(&mut self.file_password.text).into_bytes();
+                self.file_password = TextField::with("");
+                self.start_file(pwd);
+            }
+            KeyCode::Enter if self.field_index == 2 => {
+                self.results.push((self.files[self.next].clone(), Outcome::Skipped("skipped by the operator")));
+                self.next += 1;
+                self.advance();
+            }
+            KeyCode::Enter if self.field_index == 3 => {
+                for p in &self.files[self.next..] {
+                    self.results.push((p.clone(), Outcome::Skipped("run stopped by the operator")));
+                }
+                self.next = self.files.len();
+                self.advance();
+            }
+            _ if self.field_index == 0 => edit_text(&mut self.file_password, k),
+            _ => {}
+        }
+        Transition::Stay
+    }
+}
+
+/// Cursor movement and editing for the focused text field.
+fn edit_text(tf: &mut TextField, k: KeyEvent) {
+    match k.code {
+        KeyCode::Left => tf.move_left(),
+        KeyCode::Right => tf.move_right(),
+        KeyCode::Home => tf.home(),
+        KeyCode::End => tf.end(),
+        KeyCode::Backspace => tf.backspace(),
+        KeyCode::Delete => tf.delete(),
+        KeyCode::Char(c) if !k.modifiers.contains(KeyModifiers::CONTROL) => tf.insert_char(c),
+        _ => {}
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for BatchDecryptScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Decrypt All" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Decrypt All Files in a Directory";
+        let explanation_paras = [
+            format!("Directory: {} ({} encrypted file(s) found)", self.dir.display(), self.files.len()),
+            "Each file is decrypted into the Output Directory under the same name as a single decrypt. With one password for all files, a file it does not open is reported and the run continues; Ask For Each File prompts before every file.".to_string(),
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        let lines = self.middle_lines();
+        let done = matches!(self.phase, Phase::Done);
+        // Done: summary + spacer + button
+        let middle_needed = 2 + 2 + lines.len() as u16 + if done { 2 } else { 0 };
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE (the summary scrolls above its button)
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        if done {
+            let mid_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+                .split(regions.middle_inner);
+            f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), mid_chunks[0]);
+            f.render_widget(Paragraph::new(Self::buttons_line(&["Done"], Some(0))), mid_chunks[2]);
+        } else {
+            f.render_widget(Paragraph::new(lines), regions.middle_inner);
+        }
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = if done {
+            Line::from(vec![
+                span_key("↑/↓"),    span_text(" Scroll"), span_sep(),
+                span_key("Enter"),  span_text(" Done"), span_sep(),
+                span_key("Ctrl+Q"), span_text(" Quit"),
+            ])
+        } else {
+            Line::from(vec![
+                span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+                span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+                span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+                span_key("Enter"),   span_text(" Select"), span_sep(),
+                span_key("Esc"),     span_text(" Back"), span_sep(),
+                span_key("Ctrl+Q"),  span_text(" Quit"),
+            ])
+        };
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    fn busy(&self) -> Option<String> {
+        match &self.phase {
+            Phase::Running(job) => Some(format!("Batch decryption ({}) is still running.", job.label)),
+            _ => None,
+        }
+    }
+
+    async fn tick(&mut self, _ctx: &mut AppCtx) -> Result<Transition> {
+        let Phase::Running(job) = &self.phase else { return Ok(Transition::Stay) };
+        let Some(result) = job.poll() else { return Ok(Transition::Stay) };
+        let outcome = match result {
+            Ok(written) => Outcome::Written(written),
+            Err(e) => Outcome::Failed(format!("{e:#}").lines().collect::<Vec<_>>().join(" ")),
+        };
+        self.results.push((self.files[self.next].clone(), outcome));
+        self.next += 1;
+        self.advance();
+        Ok(Transition::Stay)
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match self.phase {
+            Phase::Setup | Phase::Prompt => {
+                // Esc leaves only before the run starts; in a prompt it means Stop
+                if let Some(t) = esc_to_back(k) {
+                    if matches!(self.phase, Phase::Setup) { return Ok(t); }
+                    self.field_index = 3;
+                    return Ok(self.on_key_prompt(KeyEvent::from(KeyCode::Enter)));
+                }
+                // Reveal only the focused password, and only briefly
+                if is_reveal_key(&k) {
+                    if self.field_index == 0 { self.reveal.start(0); }
+                    return Ok(Transition::Stay);
+                }
+                if matches!(self.phase, Phase::Setup) {
+                    self.on_key_setup(k)
+                } else {
+                    Ok(self.on_key_prompt(k))
+                }
+            }
+            Phase::Done => {
+                let total = self.summary_lines().len();
+                match k.code {
+                    KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                    KeyCode::Down if self.scroll + 1 < total => self.scroll += 1,
+                    KeyCode::Enter | KeyCode::Esc => return Ok(Transition::Pop),
+                    _ => {}
+                }
+                Ok(Transition::Stay)
+            }
+            // The worker thread owns the password until the file is done.
+            Phase::Running(_) => Ok(Transition::Stay),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::batch_template::write_instantiated_batch;
+use crate::defaults::Defaults;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 5,
+        title: "Instantiate Batch Template",
+        description: "Fill a batch template's {{placeholders}} from a values file, giving a batch file to sign.",
+        open: |_| Transition::Push(Box::new(BatchTemplateScreen::new())),
+    }
+}
+
+/// Expand a batch template with a values file into a batch file for Sign Batch File.
+pub struct BatchTemplateScreen {
+    // indices: 0 template file, 1 values file, 2 output file, 3 instantiate, 4 back
+    field_index: usize,
+    template_file: TextField,
+    values_file: TextField,
+    out_file: TextField,
+}
+
+impl BatchTemplateScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            template_file: TextField::with(Defaults::BATCH_TEMPLATE_FILE),
+            values_file: TextField::with(Defaults::BATCH_TEMPLATE_VALUES_FILE),
+            out_file: TextField::with(Defaults::BATCH_TEMPLATE_OUT_FILE),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0..=2) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.template_file,
+            1 => &mut self.values_file,
+            2 => &mut self.out_file,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Instantiate", self.field_index == 3));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 4));
+        Line::from(spans)
+    }
+
+    fn instantiate(&self, ctx: &AppCtx) -> Result<Vec<String>> {
+        let template = self.template_file.text.trim();
+        let values = self.values_file.text.trim();
+        let out = self.out_file.text.trim();
+        for (label, v) in [("Template File", template), ("Values File", values), ("Output File", out)] {
+            if v.is_empty() {
+                return Err(anyhow!("{label} cannot be empty."));
+            }
+        }
+        let done = write_instantiated_batch(&*ctx.fs, Path::new(template), Path::new(values), Path::new(out))?;
+        let mut lines = vec![
+            format!("Batch of {} item(s) ({} row(s)) written to {out}", done.items, done.rows),
+            "Review it, then sign it with Sign Batch File.".to_string(),
+        ];
+        if !done.unused.is_empty() {
+            lines.push("".to_string());
+            lines.push(format!("Not used by the template: {}", done.unused.join(", ")));
+        }
+        Ok(lines)
+    }
+}
+
+impl Default for BatchTemplateScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for BatchTemplateScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Batch Template" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Instantiate Batch Template";
+        let explanation_paras = [
+            "A template is a batch file whose string values may hold placeholders: {{NAME}} for a value from the Values File, {{NAME+n}} for an integer NAME plus the item's position in the output (0 for the first), {{NAME+3}} for a fixed offset. A value that is only a placeholder keeps the value's type, so \"NONCE\": \"{{NONCE_START+n}}\" becomes a number.",
+            "The Values File is a JSON object of variables. An optional \"rows\" array of objects repeats the template once per row with that row's variables. The result is checked as a batch and written to a new Output File; nothing is signed here.",
+        ];
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 3 fields + spacer + buttons
+        let middle_needed = 2 + 2 + 6;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Template File", &self.template_file, self.field_index == 0),
+            field_line_text("Values File", &self.values_file, self.field_index == 1),
+            field_line_text("Output File", &self.out_file, self.field_index == 2),
+            Line::from(""),
+            self.buttons_line(),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 5;
+            }
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(Transition::Push(Box::new(match self.instantiate(ctx) {
+                    Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+                })));
+            }
+            KeyCode::Enter if self.field_index == 4 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,480 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{TextField, field_line_text, warning_banner_lines};
+use crate::units::{GasLimit, Wei};
+use crate::validation::validate_gas_fields;
+use crate::defaults::Defaults;
+
+// Generic OK-only modal
+use crate::screens::{ConfirmOkScreen, AfterOk};
+
+// ABI / batch signer / writer
+use crate::abi::load_abi;
+use crate::commands::batch::BatchRun;
+use crate::commands::bulk_redelegation::{build_redelegation_items, load_pubkey_list, BulkRedelegation};
+use crate::process::{BatchOpts, KeyReuse, RetiredKey};
+use crate::key_usage::KeyUsage;
+use crate::signing::SignerCache;
+use crate::settings::{GasLimits, Settings};
+use crate::vfs::Fs;
+use crate::write_signed_transactions_to_file::BATCH_MANIFEST_FILE;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Transactions),
+        order: 50,
+        title: "Bulk Re-Delegation",
+        description: "Re-delegate many identities to new keys in one run.",
+        open: |ctx| Transition::Push(Box::new(BulkRedelegationScreen::new(&ctx.settings.gas_limits))),
+    }
+}
+
+/// Wizard: revoke one key and re-delegate to N new pubkey-only delegatees in one pass.
+pub struct BulkRedelegationScreen {
+    // 0 redelegator_priv, 1 revokee_priv, 2 revokee_pubkey, 3 pubkeys_file,
+    // 4 require_delegatee_sig_revocation (toggle),
+    // 5 start_nonce, 6 gas_limit, 7 max_fee_per_gas, 8 max_priority_fee_per_gas,
+    // 9 out_dir, 10 submit, 11 back
+    field_index: usize,
+    redelegator_priv: TextField,
+    revokee_priv: TextField,
+    revokee_pubkey: TextField,
+    pubkeys_file: TextField,
+    require_delegatee_sig_revocation: bool,
+    start_nonce: TextField,
+    gas_limit: TextField,
+    max_fee_per_gas: TextField,
+    max_priority_fee_per_gas: TextField,
+    out_dir: TextField,
+    /// Set while signing (with the first nonce, for the summary), one item per tick.
+    run: Option<(BatchRun, u64)>,
+    /// Key reuse the operator was warned about; the next submit with the same keys signs anyway.
+    key_reuse_ack: Option<KeyReuse>,
+    /// Retired keys the operator was warned about; the next submit with the same keys signs anyway.
+    retired_ack: Option<RetiredKey>,
+}
+
+impl BulkRedelegationScreen {
+    pub fn new(gas_limits: &GasLimits) -> Self {
+        Self {
+            field_index: 0,
+            redelegator_priv: TextField::with(""),
+            revokee_priv: TextField::with(""),
+            revokee_pubkey: TextField::with(""),
+            pubkeys_file: TextField::with(Defaults::BULK_REDELEGATION_PUBKEYS_FILE),
+            require_delegatee_sig_revocation: false, // default off
+            start_nonce: TextField::with(""),
+            gas_limit: TextField::with(gas_limits.default_for("createRevocationEventFollowedByDelegationEvent")),
+            max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
+            max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
+            out_dir: TextField::with(Defaults::BATCH_OUT_DIR),
+            run: None,
+            key_reuse_ack: None,
+            retired_ack: None,
+        }
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self.field_index, 0..=3 | 5..=9)
+    }
+
+    fn tf_ref(&self, idx: usize) -> &TextField {
+        match idx {
+            0 => &self.redelegator_priv,
+            1 => &self.revokee_priv,
+            2 => &self.revokee_pubkey,
+            3 => &self.pubkeys_file,
+            5 => &self.start_nonce,
+            6 => &self.gas_limit,
+            7 => &self.max_fee_per_gas,
+            8 => &self.max_priority_fee_per_gas,
+            9 => &self.out_dir,
+            _ => unreachable!("tf_ref called on non-text field"),
+        }
+    }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.redelegator_priv,
+            1 => &mut self.revokee_priv,
+            2 => &mut self.revokee_pubkey,
+            3 => &mut self.pubkeys_file,
+            5 => &mut self.start_nonce,
+            6 => &mut self.gas_limit,
+            7 => &mut self.max_fee_per_gas,
+            8 => &mut self.max_priority_fee_per_gas,
+            9 => &mut self.out_dir,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    // < Sign All >   < Back >
+    fn buttons_line(submit_selected: bool, back_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Sign All Re-Delegations", submit_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+
+    /// Build one re-delegation per listed pubkey and prepare a run; `tick` signs them.
+    /// Returns the run and the first nonce.
+    fn start(&self, fs: &dyn Fs, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<(BatchRun, u64)> {
+        let pk_owner = self.redelegator_priv.text.trim();
+        let pk_revokee = self.revokee_priv.text.trim();
+        let pub_revokee = self.revokee_pubkey.text.trim();
+        if pk_owner.is_empty() {
+            anyhow::bail!("Revoker/Redelegator PrivKey cannot be empty.");
+        }
+        if pk_revokee.is_empty() && pub_revokee.is_empty() {
+            anyhow::bail!("Provide either Revokee PrivKey or Revokee PubKey.");
+        }
+        let list_path = self.pubkeys_file.text.trim();
+        if list_path.is_empty() {
+            anyhow::bail!("Delegatee PubKeys File cannot be empty.");
+        }
+        let out_dir = self.out_dir.text.trim();
+        if out_dir.is_empty() {
+            anyhow::bail!("Output Directory cannot be empty.");
+        }
+        // Create it now, so an unusable (or sandbox-refused) directory shows up before signing.
+        fs.create_dir_all(Path::new(out_dir)).with_context(|| format!("creating output directory {out_dir}"))?;
+        let start_nonce: u64 = self.start_nonce.text.trim()
+            .parse()
+            .context("Starting Nonce must be an integer")?;
+
+        let (raw, pubkeys) = load_pubkey_list(Path::new(list_path))?;
+        let items = build_redelegation_items(
+            &BulkRedelegation {
+                redelegator_priv: pk_owner,
+                revokee_priv: pk_revokee,
+                revokee_pubkey: pub_revokee,
+                require_delegatee_sig_revocation: self.require_delegatee_sig_revocation,
+                start_nonce,
+            },
+            &pubkeys,
+        )?;
+
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
+        };
+        let abi = load_abi()?;
+
+        Ok((BatchRun::new(abi, opts, items, raw, PathBuf::from(out_dir)), start_nonce))
+    }
+}
+
+impl Default for BulkRedelegationScreen {
+    fn default() -> Self { Self::new(&GasLimits::default()) }
+}
+
+#[async_trait]
+impl ScreenWidget for BulkRedelegationScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Bulk Re-Delegation" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
+        let header_text = "Bulk Re-Delegation";
+        let explanation_paras = [
+            "Revoke one key and re-delegate to many new delegatees in one pass. The pubkeys file lists one",
+            "delegatee pubkey per line (# comments allowed). One createRevocationEventFollowedByDelegationEvent",
+            "transaction is signed per pubkey, with nonces counting up from the starting nonce.",
+            "Delegatees are pubkey-only (their signatures are zeroed). Results go into a new run directory",
+            "with one file per transaction plus a manifest.json.",
+        ];
+
+        // === TOP BOX ===
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+
+        // Unregistered chain/contract pair: a bold banner under the explanation.
+        let banner = ctx.settings.networks
+            .unknown_warning(Defaults::CHAIN_ID, Defaults::CONTRACT_ADDRESS)
+            .map(|w| warning_banner_lines(&w, top_inner_width))
+            .unwrap_or_default();
+        let banner_lines = if banner.is_empty() { 0 } else { banner.len() as u16 + 1 };
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + banner_lines;
+
+        // Middle: 12 focusable positions (0..=11) plus top spacer and button spacer
+        let middle_rows: u16 = 12 + 2;
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header_lines.max(1)),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        if !banner.is_empty() {
+            expl_lines.push(Line::from(""));
+            expl_lines.extend(banner);
+        }
+        let explanation_para = Paragraph::new(expl_lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // === MIDDLE BOX ===
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut lines: Vec<Line> = vec![
+            Line::from(""), // spacer above first field
+            field_line_text("Revoker/Redelegator PrivKey", self.tf_ref(0), self.field_index == 0),
+            field_line_text("Revokee PrivKey (optional if PubKey is provided)", self.tf_ref(1), self.field_index == 1),
+            field_line_text("Revokee PubKey (0x04… uncompressed, optional)", self.tf_ref(2), self.field_index == 2),
+            field_line_text("Delegatee PubKeys File", self.tf_ref(3), self.field_index == 3),
+        ];
+
+        // toggle line at index 4
+        let toggle_val = if self.require_delegatee_sig_revocation { "yes" } else { "no" };
+        let label_span = Span::styled(
+            "Require Delegatee Signature For Revocation?  ",
+            Style::default().fg(Color::Yellow)
+        );
+        let val_style = if self.field_index == 4 {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![label_span, Span::styled(toggle_val.to_string(), val_style)]));
+
+        lines.push(field_line_text("Starting Transaction Nonce", self.tf_ref(5), self.field_index == 5));
+
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(6), self.field_index == 6));
+
+        let mfg_label = format!(
+            "Maximum Fee Per Gas (maximum {} wei)",
+            ctx.settings.gas_caps.max_fee_per_gas()
+        );
+        lines.push(field_line_text(&mfg_label, self.tf_ref(7), self.field_index == 7));
+
+        let mpfg_label = format!(
+            "Maximum Priority Fee Per Gas (maximum {} wei)",
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
+        );
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(8), self.field_index == 8));
+
+        lines.push(field_line_text("Output Directory", self.tf_ref(9), self.field_index == 9));
+
+        lines.push(Line::from("")); // spacer
+        match &self.run {
+            Some((run, _)) => lines.push(Line::from(format!(
+                "Signing re-delegation {} of {}…  (Ctrl+Q stops after the current item)",
+                run.signed() + 1, run.total()
+            ))),
+            None => lines.push(Self::buttons_line(self.field_index == 10, self.field_index == 11)),
+        }
+
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // === BOTTOM BOX (legend) ===
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Enter"),   span_text(" Select"),  span_sep(),
+            span_key("Esc"),     span_text(" Back"),    span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.run.as_ref().map(|(run, _)| format!(
+            "A bulk re-delegation is in progress ({} of {} signed).", run.signed(), run.total()
+        ))
+    }
+
+    async fn tick(&mut self, ctx: &mut AppCtx) -> Result<Transition> {
+        let Some((run, first_nonce)) = self.run.as_mut() else { return Ok(Transition::Stay) };
+        let first_nonce = *first_nonce;
+        if let Err(e) = run.step().await {
+            let e = e.context("failed to sign re-delegations");
+            let lines = run.fail(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref(), &e);
+            self.run = None;
+            return Ok(Transition::Push(Box::new(
+                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+            )));
+        }
+        if !run.is_done() {
+            return Ok(Transition::Stay);
+        }
+        let count = run.total();
+        let written = run.finish(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref());
+        self.run = None;
+        match written {
+            Ok((run_dir, recorded)) => {
+                let mut lines = vec![
+                    format!(
+                        "Signed {count} re-delegation(s), nonces {first_nonce}..={}, into run directory:",
+                        first_nonce + count as u64 - 1
+                    ),
+                    "".to_string(),
+                    run_dir.display().to_string(),
+                    "".to_string(),
+                    format!("See {BATCH_MANIFEST_FILE} in that directory for the file list."),
+                ];
+                lines.extend(recorded);
+                Ok(Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                )))
+            }
+            Err(e) => Ok(Transition::Push(Box::new(
+                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+            ))),
+        }
+    }
+
+    fn finish_for_quit(&mut self, ctx: &AppCtx) -> Option<String> {
+        let (run, _) = self.run.take()?;
+        Some(run.flush_for_quit(&*ctx.fs, &ctx.settings))
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        // While signing, only the global hotkeys (Ctrl+Q, F12) apply.
+        if self.run.is_some() { return Ok(Transition::Stay); }
+
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 11; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 12;
+            }
+
+            // Toggle boolean (index 4)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 4 => {
+                self.require_delegatee_sig_revocation = !self.require_delegatee_sig_revocation;
+            }
+
+            // Enter on [Sign All Re-Delegations]
+            KeyCode::Enter if self.field_index == 10 => {
+                let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+                    Ok(gas) => gas,
+                    Err(e) => return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e}")).with_after_ok(AfterOk::Pop)
+                    ))),
+                };
+
+                match self.start(&*ctx.fs, &ctx.settings, &ctx.key_usage, gas).map(|(run, n)| (run.with_events(ctx.events.clone()), n)) {
+                    Ok((mut run, first_nonce)) => {
+                        if let Some(reuse) = run.key_reuse() {
+                            if self.key_reuse_ack.as_ref() != Some(&reuse) {
+                                let lines = reuse.confirm_lines("Sign All Re-Delegations");
+                                self.key_reuse_ack = Some(reuse);
+                                return Ok(Transition::Push(Box::new(
+                                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                                )));
+                            }
+                            run.allow_key_reuse();
+                        }
+                        if let Some(retired) = run.retired_keys() {
+                            if self.retired_ack.as_ref() != Some(&retired) {
+                                let lines = retired.confirm_lines("Sign All Re-Delegations");
+                                self.retired_ack = Some(retired);
+                                return Ok(Transition::Push(Box::new(
+                                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                                )));
+                            }
+                            run.allow_retired_keys();
+                        }
+                        self.run = Some((run, first_nonce));
+                    }
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::new(format!("Error: {e:#}"))
+                                .with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                }
+            }
+
+            // Enter on [Back]
+            KeyCode::Enter if self.field_index == 11 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Cursor movement in text fields
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+
+            // Editing
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::PathBuf;
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::output_encoder::OutputEncoding;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::types::{BatchEntryOut, SignedFileMetadata};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::event_stream::Event;
+use crate::key_usage::record_signed;
+use crate::usage_stats::Written;
+use crate::write_signed_transactions_to_file::write_single_signed_transaction;
+
+/// Contract-call preview of a signed transaction that has not been written yet.
+/// Writing happens only from here; Back discards the signed transaction.
+pub struct CallPreviewScreen {
+    trace: String,
+    entry: BatchEntryOut,
+    out_path: PathBuf,
+    metadata: Option<SignedFileMetadata>,
+    encoding: OutputEncoding,
+    /// First trace line shown.
+    scroll: usize,
+    // 0 write, 1 back
+    button_index: usize,
+}
+
+impl CallPreviewScreen {
+    pub fn new(
+        trace: String,
+        entry: BatchEntryOut,
+        out_path: PathBuf,
+        metadata: Option<SignedFileMetadata>,
+        encoding: OutputEncoding,
+    ) -> Self {
+        Self { trace, entry, out_path, metadata, encoding, scroll: 0, button_index: 0 }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Write Signed File", self.button_index == 0));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.button_index == 1));
+        Line::from(spans)
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for CallPreviewScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Call Preview" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Contract Call Preview";
+        let explanation_paras = [
+            "This is the call the signed transaction will make, decoded from its calldata. Check every member before writing the file.",
+            "Back discards the signed transaction; nothing has been written yet.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        let trace_lines = self.trace.lines().count() as u16;
+        // trace + spacer + buttons
+        let middle_needed = 2 + 2 + trace_lines + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: scrollable trace above the buttons
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let lines: Vec<Line> = self.trace.lines().skip(self.scroll).map(|l| Line::from(l.to_string())).collect();
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), mid_chunks[0]);
+        f.render_widget(Paragraph::new(self.buttons_line()), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),     span_text(" Scroll"), span_sep(),
+            span_key("←/→/Tab"), span_text(" Choose"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if self.scroll + 1 < self.trace.lines().count() => self.scroll += 1,
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab => self.button_index = 1 - self.button_index,
+            KeyCode::Enter if self.button_index == 0 => {
+                let modal = match write_single_signed_transaction(&*ctx.fs, &self.out_path, &self.entry, self.metadata.as_ref(), self.encoding.encoder()) {
+                    Ok(path) => {
+                        ctx.events.emit(Event::file_written(None, &path));
+                        let mut lines = vec![
+                            "Saved signed transaction:".to_string(),
+                            "".to_string(),
+                            path.display().to_string(),
+                        ];
+                        let tx = &self.entry.decoded_tx;
+                        let written = Written::One { func: &tx.funcName, from: &tx.from };
+                        lines.extend(record_signed(&mut ctx.key_usage, ctx.profile_key.as_ref(), ctx.settings.usage_stats, written));
+                        ConfirmOkScreen::with_lines(lines)
+                    }
+                    Err(e) => ConfirmOkScreen::new(format!("Error: failed to write signed transaction file: {e:#}")),
+                };
+                return Ok(Transition::Replace(Box::new(modal.with_after_ok(AfterOk::Pop))));
+            }
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
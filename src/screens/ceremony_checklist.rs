@@ -0,0 +1,256 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, button_spans_disabled, span_key, span_sep, span_text};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 1,
+        title: "Ceremony Checklist",
+        description: "Tick off the configured ceremony steps; signing screens stay locked until all are done.",
+        open: |_| Transition::Push(Box::new(CeremonyChecklistScreen::new())),
+    }
+}
+
+/// The ceremony checklist of `ceremony_checklist.rs`. Opened from the menu, or by the app over a
+/// signing screen while steps are unticked; there Back leaves the signing screen too.
+pub struct CeremonyChecklistScreen {
+    /// 0 = steps, 1.. = buttons
+    field_index: usize,
+    list_index: usize,
+    /// Put over a signing screen that it unlocks.
+    gate: bool,
+    /// Why the completed checklist could not be written to the audit log.
+    record_error: Option<String>,
+}
+
+impl CeremonyChecklistScreen {
+    pub fn new() -> Self {
+        Self { field_index: 0, list_index: 0, gate: false, record_error: None }
+    }
+
+    /// Over a signing screen that just opened.
+    pub fn gate() -> Self {
+        Self { gate: true, ..Self::new() }
+    }
+
+    fn labels(&self) -> &'static [&'static str] {
+        if self.gate { &["Continue", "Back"] } else { &["Back"] }
+    }
+
+    /// Leave the screen: in gate mode the signing screen underneath goes as well.
+    fn back(&self) -> Transition {
+        if self.gate { Transition::PopN(2) } else { Transition::Pop }
+    }
+
+    /// Write the checklist to the audit log once its last step is ticked.
+    fn record(&mut self, ctx: &mut AppCtx) {
+        let Some(ceremony) = ctx.ceremony.as_mut() else { return };
+        self.record_error = ceremony
+            .record(ctx.profile_key.as_ref(), &ctx.settings.operator_label)
+            .err()
+            .map(|e| format!("{e:#}"));
+    }
+}
+
+impl Default for CeremonyChecklistScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for CeremonyChecklistScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Ceremony Checklist" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
+        let header_text = match ctx.ceremony.as_ref().and_then(|c| c.checklist.as_ref().ok()) {
+            Some(c) if !c.title.is_empty() => c.title.clone(),
+            _ => "Ceremony Checklist".to_string(),
+        };
+        let mut explanation_paras = Vec::new();
+        let mut status: Option<(String, Color)> = None;
+        match &ctx.ceremony {
+            None => explanation_paras.push("No checklist is configured: set \"Ceremony checklist (YAML)\" in Settings to require one before signing.".to_string()),
+            Some(c) => {
+                explanation_paras.push(format!("Checklist file: {}", c.path));
+                explanation_paras.push(if self.gate {
+                    "Signing is locked until every step is ticked. The completed checklist, with the time of each tick, is written to the audit log.".to_string()
+                } else {
+                    "Signing screens open only once every step is ticked. The completed checklist, with the time of each tick, is written to the audit log.".to_string()
+                });
+                if let Err(e) = &c.checklist {
+                    status = Some((format!("{e}. Signing stays locked until the file is fixed or the setting cleared."), Color::Red));
+                } else if let Some(e) = &self.record_error {
+                    status = Some((format!("Could not write the audit log: {e}. Signing stays locked."), Color::Red));
+                } else if c.recorded() {
+                    status = Some(("All steps ticked and recorded: signing is unlocked.".to_string(), Color::Green));
+                }
+            }
+        }
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(&header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        let steps = ctx.ceremony.as_ref().and_then(|c| c.checklist.as_ref().ok()).map(|c| c.steps.as_slice()).unwrap_or_default();
+        let step_rows: usize = steps.iter().map(|s| 1 + usize::from(!s.detail.is_empty())).sum();
+        let status_rows = status.as_ref().map_or(0, |(s, _)| wrap(s, top_inner_width).len() + 1) as u16;
+        // spacer + steps (or a note) + status + spacer + buttons
+        let middle_needed = 2 + 2 + 1 + (step_rows.max(1) as u16) + status_rows + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text.clone()).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: steps, then the status line, then the buttons
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1), Constraint::Length(status_rows), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let rows: Vec<Line> = if steps.is_empty() {
+            vec![Line::from("No steps to tick.")]
+        } else {
+            let mut rows = Vec::new();
+            for (i, step) in steps.iter().enumerate() {
+                let focused = self.field_index == 0 && self.list_index == i;
+                let mut spans = vec![
+                    Span::styled(if focused { "▶ " } else { "  " }, Style::default().fg(Color::Cyan)),
+                    Span::raw(if step.ticked_at.is_some() { "[x] " } else { "[ ] " }),
+                    Span::raw(step.text.clone()),
+                ];
+                if let Some(at) = &step.ticked_at {
+                    spans.push(Span::styled(format!("  ({at})"), Style::default().fg(Color::DarkGray)));
+                }
+                rows.push(Line::from(spans));
+                if !step.detail.is_empty() {
+                    rows.push(Line::styled(format!("      {}", step.detail), Style::default().fg(Color::DarkGray)));
+                }
+            }
+            // Keep the highlighted step visible
+            let visible = mid_chunks[1].height.max(1) as usize;
+            let focus_row: usize = steps.iter().take(self.list_index).map(|s| 1 + usize::from(!s.detail.is_empty())).sum();
+            rows.into_iter().skip(focus_row.saturating_sub(visible - 1)).collect()
+        };
+        f.render_widget(Paragraph::new(rows), mid_chunks[1]);
+        if let Some((text, color)) = status {
+            let p = Paragraph::new(Line::styled(text, Style::default().fg(color))).wrap(Wrap { trim: true });
+            f.render_widget(p, Rect { height: mid_chunks[2].height.saturating_sub(1), y: mid_chunks[2].y + 1, ..mid_chunks[2] });
+        }
+        let unlocked = ctx.ceremony.as_ref().is_none_or(|c| c.recorded());
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (i, &label) in self.labels().iter().enumerate() {
+            if i > 0 { spans.push(Span::raw("   ")); }
+            if self.gate && i == 0 && !unlocked {
+                spans.extend(button_spans_disabled(label));
+            } else {
+                spans.extend(button_spans(label, self.field_index == i + 1));
+            }
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), mid_chunks[4]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Space"),   span_text(" Tick"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if k.code == KeyCode::Esc {
+            return Ok(self.back());
+        }
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let steps = ctx.ceremony.as_ref().and_then(|c| c.checklist.as_ref().ok()).map_or(0, |c| c.steps.len());
+        let last = self.labels().len();
+        if steps == 0 && self.field_index == 0 {
+            self.field_index = 1;
+        }
+        match k.code {
+            KeyCode::Down | KeyCode::Tab => {
+                if self.field_index == 0 && self.list_index + 1 < steps {
+                    self.list_index += 1;
+                } else if self.field_index == last {
+                    self.field_index = if steps > 0 { 0 } else { 1 };
+                    self.list_index = 0;
+                } else {
+                    self.field_index += 1;
+                }
+            }
+            KeyCode::Up => {
+                if self.field_index == 0 && self.list_index > 0 {
+                    self.list_index -= 1;
+                } else if self.field_index == 1 && steps > 0 {
+                    self.field_index = 0;
+                    self.list_index = steps - 1;
+                } else if self.field_index <= 1 {
+                    self.field_index = last;
+                } else {
+                    self.field_index -= 1;
+                }
+            }
+            KeyCode::Left if self.field_index > 1 => self.field_index -= 1,
+            KeyCode::Right if (1..last).contains(&self.field_index) => self.field_index += 1,
+            KeyCode::Char(' ') | KeyCode::Enter if self.field_index == 0 => {
+                if let Some(ceremony) = ctx.ceremony.as_mut() {
+                    ceremony.toggle(self.list_index);
+                }
+                self.record_error = None;
+                self.record(ctx);
+            }
+            // Continue, once everything is ticked and recorded
+            KeyCode::Enter if self.gate && self.field_index == 1 => {
+                self.record(ctx);
+                if ctx.ceremony.as_ref().is_none_or(|c| c.recorded()) {
+                    return Ok(Transition::Pop);
+                }
+            }
+            KeyCode::Enter if self.field_index == last => return Ok(self.back()),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -1,5 +1,5 @@
 // src/screens/choose_delegation_info_dir.rs
-use anyhow::{Context, Result};
+use anyhow::Result;
 use async_trait::async_trait;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -10,10 +10,10 @@ use ratatui::{
 };
 use textwrap::wrap;
 
-use std::fs;
 use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::AppEvent;
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
 use crate::ui::common_nav::esc_to_back;
@@ -30,6 +30,9 @@ pub struct ChooseDelegationInfoDirScreen {
     input_dir: TextField,
 }
 
+/// Key of this load flow in `HandoffState::picker`.
+pub const PICKER: &str = "delegation";
+
 impl ChooseDelegationInfoDirScreen {
     pub fn new() -> Self {
         let mut s = Self::default();
@@ -38,6 +41,16 @@ impl ChooseDelegationInfoDirScreen {
         s
     }
 
+
+    /// Re-open with the directory entered last time (if any) instead of the default.
+    pub fn resume(ctx: &AppCtx) -> Self {
+        let mut s = Self::new();
+        if let Some(dir) = ctx.state.picker(PICKER).and_then(|m| m.dir.as_deref()) {
+            s.input_dir = TextField::with(dir);
+        }
+        s
+    }
+
     fn is_text(&self) -> bool { self.field_index == 0 }
 
     fn buttons_line(open_selected: bool, cancel_selected: bool) -> Line<'static> {
@@ -52,6 +65,7 @@ impl ChooseDelegationInfoDirScreen {
 #[async_trait]
 impl ScreenWidget for ChooseDelegationInfoDirScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Load From File" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Load Delegation Info – Choose Directory";
@@ -124,7 +138,12 @@ impl ScreenWidget for ChooseDelegationInfoDirScreen {
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
     }
 
-    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if ctx.state.picker(PICKER).and_then(|m| m.dir.as_deref()) != Some(self.input_dir.text.as_str()) {
+            let event = AppEvent::PickerDirChosen { picker: PICKER, dir: self.input_dir.text.clone() };
+            ctx.dispatch(self.crumb(), event);
+        }
+
         if let Some(t) = esc_to_back(k) { return Ok(t); }
 
         if let KeyCode::Char('q') = k.code {
@@ -151,18 +170,21 @@ impl ScreenWidget for ChooseDelegationInfoDirScreen {
                     )));
                 }
                 let dir_path = PathBuf::from(dir);
-                fs::create_dir_all(&dir_path)
-                    .with_context(|| format!("creating directory {}", dir_path.display()))?;
-                let md = fs::metadata(&dir_path)
-                    .with_context(|| format!("accessing {}", dir_path.display()))?;
-                if !md.is_dir() {
+                if let Err(e) = ctx.fs.create_dir_all(&dir_path) {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: creating directory {}: {e}", dir_path.display()))
+                            .with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                if !ctx.fs.is_dir(&dir_path) {
                     return Ok(Transition::Push(Box::new(
                         ConfirmOkScreen::new("Error: Input Directory is not a directory.")
                             .with_after_ok(AfterOk::Pop)
                     )));
                 }
                 return Ok(Transition::Push(Box::new(
-                    crate::screens::SelectDelegationInfoFileScreen::new(dir_path)
+                    crate::screens::SelectDelegationInfoFileScreen::new(dir_path, ctx.fs.clone())
+                        .with_selection(ctx.state.picker(PICKER).and_then(|m| m.selected.as_deref()))
                 )));
             }
 
@@ -17,6 +17,8 @@ use crate::ui::style;
 pub enum AfterOk {
     Pop,                // just close the modal
     PopToMainMenu,      // replace current screen with Main Menu
+    Home,               // replace current screen with the home screen (see `app::home_screen`)
+    Quit,               // close the tool (the session state is no longer valid)
 }
 
 pub struct ConfirmOkScreen {
@@ -87,7 +89,7 @@ impl ScreenWidget for ConfirmOkScreen {
         f.render_widget(buttons_line, vchunks[vchunks.len() - 1]);
     }
 
-    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         match k.code {
             KeyCode::Esc | KeyCode::Enter => {
                 Ok(match self.after_ok {
@@ -95,6 +97,8 @@ impl ScreenWidget for ConfirmOkScreen {
                 AfterOk::PopToMainMenu => Transition::Replace(Box::new(
                     crate::screens::MainMenuScreen::default()
                 )),     
+                AfterOk::Home          => Transition::Replace(crate::app::home_screen(ctx)),
+                AfterOk::Quit          => Transition::Quit,
                 })
             }
             _ => Ok(Transition::Stay),
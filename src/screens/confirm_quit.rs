@@ -14,11 +14,18 @@ use crate::ui::style; // centralized style
 
 pub struct ConfirmQuitScreen {
     selected: usize, // 0 = Don't Quit, 1 = Quit
+    /// Operation still running underneath (see `ScreenWidget::busy`).
+    busy: Option<String>,
 }
 
 impl ConfirmQuitScreen {
     pub fn new() -> Self {
-        Self { selected: 0 }
+        Self { selected: 0, busy: None }
+    }
+
+    /// Quit confirmation while `what` is still running; quitting saves its completed part.
+    pub fn busy(what: String) -> Self {
+        Self { selected: 0, busy: Some(what) }
     }
 }
 
@@ -30,16 +37,23 @@ impl ScreenWidget for ConfirmQuitScreen {
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let msg = "Do you really want to quit the Inkan Management Utility?";
+        let note = "Quitting finishes the current item and saves the signed ones as a partial run.";
         let left_label = "Don't Quit";
-        let right_label = "Quit";
+        let right_label = if self.busy.is_some() { "Save & Quit" } else { "Quit" };
+        let busy_lines: Vec<&str> = match &self.busy {
+            Some(what) => vec![what.as_str(), note],
+            None => Vec::new(),
+        };
 
         // Compute width
         let btn_len = |label: &str| 4 + label.len(); // "< " + label + " >"
         let buttons_len = btn_len(left_label) + 3 + btn_len(right_label);
 
-        let inner_w_needed = msg.len().max(buttons_len) as u16;
+        let busy_w = busy_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let inner_w_needed = msg.len().max(buttons_len).max(busy_w) as u16;
         let inner_width = inner_w_needed.max(36);
-        let inner_height = 4;
+        let extra = if busy_lines.is_empty() { 0 } else { busy_lines.len() as u16 + 1 };
+        let inner_height = 4 + extra;
 
         let total_w = inner_width + 4;
         let total_h = inner_height + 3;
@@ -51,6 +65,7 @@ impl ScreenWidget for ConfirmQuitScreen {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(1),
+                Constraint::Length(extra),
                 Constraint::Length(1),
                 Constraint::Length(1),
                 Constraint::Length(1),
@@ -73,16 +88,24 @@ impl ScreenWidget for ConfirmQuitScreen {
 
         f.render_widget(Clear, area);
         f.render_widget(Block::default().borders(Borders::ALL).title(self.title()), area);
-        f.render_widget(msg_line, vchunks[1]);
-        f.render_widget(buttons_line, vchunks[3]);
+        if !busy_lines.is_empty() {
+            let lines: Vec<Line> = busy_lines.iter().map(|l| Line::from(*l)).collect();
+            f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), vchunks[1]);
+        }
+        f.render_widget(msg_line, vchunks[2]);
+        f.render_widget(buttons_line, vchunks[4]);
     }
 
-    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         match k.code {
             KeyCode::Esc => return Ok(Transition::Pop),
             KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => {
                 self.selected = 1 - self.selected;
             }
+            // Staged outputs are shredded at exit: ask which to keep first
+            KeyCode::Enter if self.selected == 1 && self.busy.is_none() && !ctx.staging.pending().is_empty() => {
+                return Ok(Transition::Replace(Box::new(crate::screens::staged_outputs::StagedOutputsScreen::at_quit(ctx))));
+            }
             KeyCode::Enter => {
                 return Ok(if self.selected == 1 {
                     Transition::Quit
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, DelegationPrefill, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::layout::centered_rect_abs;
+use crate::ui::style;
+use crate::util::{env_secrets, EnvSecret};
+
+/// Stash `prefill`, read from the info file `path`, for `form` and go back to the form
+/// (Select File -> Choose Dir -> form). A file holding private keys is confirmed first, or
+/// refused when `settings.refuse_secret_prefill` is on.
+pub fn stash_prefill(ctx: &mut AppCtx, source: &str, form: Form, path: &Path, mut prefill: DelegationPrefill) -> Transition {
+    let secrets = env_secrets(&prefill.map);
+    if secrets.is_empty() {
+        ctx.dispatch(source, AppEvent::PrefillLoaded { form, prefill });
+        return Transition::PopN(2);
+    }
+    if ctx.settings.refuse_secret_prefill {
+        wipe(&mut prefill);
+        let keys: Vec<&str> = secrets.iter().map(|s| s.key.as_str()).collect();
+        return Transition::Push(Box::new(ConfirmOkScreen::with_lines(vec![
+            format!("Not loaded: {} holds private keys ({}).", path.display(), keys.join(", ")),
+            "\"Refuse info files with private keys\" is on in Settings.".to_string(),
+            "Remove the keys from the file and enter them on the form.".to_string(),
+        ]).with_after_ok(AfterOk::Pop)));
+    }
+    Transition::Push(Box::new(ConfirmSecretPrefillScreen {
+        source: source.to_string(),
+        form,
+        path: path.to_path_buf(),
+        prefill: Some(prefill),
+        secrets,
+        selected: 0,
+    }))
+}
+
+fn wipe(prefill: &mut DelegationPrefill) {
+    for v in prefill.map.values_mut() {
+        v.zeroize();
+    }
+}
+
+/// Shown over the file list before an info file's private keys reach a Create form: names each
+/// key and its fingerprint, never its value. Cancel drops the loaded values.
+pub struct ConfirmSecretPrefillScreen {
+    /// Breadcrumb of the file list, for the dispatched event.
+    source: String,
+    form: Form,
+    path: PathBuf,
+    /// Taken on Import; wiped on drop otherwise.
+    prefill: Option<DelegationPrefill>,
+    secrets: Vec<EnvSecret>,
+    selected: usize, // 0 = Cancel, 1 = Import Keys
+}
+
+impl Drop for ConfirmSecretPrefillScreen {
+    fn drop(&mut self) {
+        if let Some(p) = self.prefill.as_mut() {
+            wipe(p);
+        }
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for ConfirmSecretPrefillScreen {
+    fn title(&self) -> &str { "" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let left_label = "Cancel";
+        let right_label = "Import Keys";
+        let mut lines: Vec<Line> = vec![
+            Line::from(format!("{} holds private keys:", self.path.display())),
+            Line::from(""),
+        ];
+        for s in &self.secrets {
+            let fp = match &s.fingerprint {
+                Some(fp) => Span::raw(format!("fingerprint {fp}")),
+                None => Span::styled("not a valid key", Style::default().fg(Color::Red)),
+            };
+            lines.push(Line::from(vec![Span::styled(format!("  {}: ", s.key), Style::default().fg(Color::Yellow)), fp]));
+        }
+        lines.extend([
+            Line::from(""),
+            Line::from(format!("Import them into the {} form?", self.form.label())),
+            Line::from("Check each fingerprint against your key records first."),
+        ]);
+
+        let btn_len = |label: &str| 4 + label.len(); // "< " + label + " >"
+        let buttons_len = btn_len(left_label) + 3 + btn_len(right_label);
+        let content_w = lines.iter().map(|l| l.width()).max().unwrap_or(0).max(buttons_len) as u16;
+        let inner_width = content_w.max(36);
+        let inner_height = lines.len() as u16 + 2; // lines + spacer + buttons
+
+        let area = centered_rect_abs(inner_width + 4, inner_height + 3, size);
+        let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(lines.len() as u16),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let mut btn_spans = Vec::new();
+        btn_spans.extend(style::button_spans(left_label, self.selected == 0));
+        btn_spans.push(Span::raw("   "));
+        btn_spans.extend(style::button_spans(right_label, self.selected == 1));
+
+        f.render_widget(Clear, area);
+        f.render_widget(Block::default().borders(Borders::ALL).title(" Private Keys In File "), area);
+        f.render_widget(Paragraph::new(lines), vchunks[0]);
+        f.render_widget(Paragraph::new(Line::from(btn_spans)).alignment(Alignment::Center), vchunks[2]);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        match k.code {
+            KeyCode::Esc => return Ok(Transition::Pop),
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::Char(' ') => {
+                self.selected = 1 - self.selected;
+            }
+            KeyCode::Enter if self.selected == 1 => {
+                if let Some(prefill) = self.prefill.take() {
+                    ctx.dispatch(&self.source, AppEvent::PrefillLoaded { form: self.form, prefill });
+                }
+                // This dialog, Select File and Choose Dir
+                return Ok(Transition::PopN(3));
+            }
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
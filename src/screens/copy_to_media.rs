@@ -0,0 +1,383 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::transfer::{copy_verified, list_sources, removable_volumes, shred_originals, Volume};
+use crate::defaults::Defaults;
+use crate::vfs::DirEntry;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{TextField, bool_field_line, field_line_text};
+use crate::ui::typed_confirm::{TypedConfirm, TypedConfirmChoice};
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Files),
+        order: 30,
+        title: "Copy to Removable Media",
+        description: "Copy an output directory to a mounted drive and verify the copy.",
+        open: |ctx| Transition::Push(Box::new(CopyToMediaScreen::new(ctx))),
+    }
+}
+
+/// Folder created on the chosen volume for the copies.
+const TRANSFER_FOLDER: &str = "inkan_transfer";
+
+/// Copies shown by hash in the result; the rest are counted.
+const MAX_LISTED: usize = 10;
+
+/// Copy selected output files to a removable volume, read every copy back to verify it and
+/// optionally shred the originals (see `commands/transfer.rs`).
+pub struct CopyToMediaScreen {
+    source: TextField,
+    entries: Vec<DirEntry>,
+    selected: BTreeSet<PathBuf>,
+    volumes: Vec<Volume>,
+    volume_index: usize,
+    destination: TextField,
+    shred: bool,
+    field_index: usize, // 0 = source, 1 = list, 2 = volume, 3 = destination, 4 = shred, 5 = copy, 6 = Back
+    list_index: usize,
+    /// Open while the operator types SHRED before a copy that shreds the originals.
+    shred_confirm: Option<TypedConfirm>,
+}
+
+impl CopyToMediaScreen {
+    pub fn new(ctx: &AppCtx) -> Self {
+        let mut s = Self {
+            source: TextField::with(Defaults::BATCH_OUT_DIR),
+            entries: Vec::new(),
+            selected: BTreeSet::new(),
+            volumes: Vec::new(),
+            volume_index: 0,
+            destination: TextField::default(),
+            shred: false,
+            field_index: 1,
+            list_index: 0,
+            shred_confirm: None,
+        };
+        s.reload(ctx);
+        s.rescan();
+        s
+    }
+
+    fn reload(&mut self, ctx: &AppCtx) {
+        self.entries = list_sources(&*ctx.fs, Path::new(self.source.text.trim()));
+        self.selected.retain(|p| self.entries.iter().any(|e| &e.path == p));
+        self.list_index = self.list_index.min(self.entries.len().saturating_sub(1));
+        if self.entries.is_empty() && self.field_index == 1 {
+            self.field_index = 2;
+        }
+    }
+
+    /// Detect volumes again and point the destination at the first one.
+    fn rescan(&mut self) {
+        self.volumes = removable_volumes();
+        self.volume_index = 0;
+        self.use_volume();
+    }
+
+    fn use_volume(&mut self) {
+        if let Some(v) = self.volumes.get(self.volume_index) {
+            self.destination = TextField::with(&v.mount_point.join(TRANSFER_FOLDER).display().to_string());
+        }
+    }
+
+    fn volume_line(&self) -> Line<'static> {
+        let val = match self.volumes.get(self.volume_index) {
+            Some(v) if v.device.is_empty() => format!("‹ {} ›", v.mount_point.display()),
+            Some(v) => format!("‹ {} ({}) ›", v.mount_point.display(), v.device),
+            None => "none detected (Enter to scan again)".to_string(),
+        };
+        let val_style = if self.field_index == 2 {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Line::from(vec![
+            Span::styled("Removable Volume: ", Style::default().fg(Color::Yellow)),
+            Span::styled(val, val_style),
+        ])
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let copy = if self.shred { "Copy, Verify & Shred" } else { "Copy & Verify" };
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans(copy, self.field_index == 5));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 6));
+        Line::from(spans)
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 3) }
+
+    fn tf_mut(&mut self) -> &mut TextField {
+        if self.field_index == 0 { &mut self.source } else { &mut self.destination }
+    }
+
+    fn copy(&mut self, ctx: &AppCtx) -> Result<Vec<String>> {
+        let root = PathBuf::from(self.source.text.trim());
+        let dest = PathBuf::from(self.destination.text.trim());
+        if self.selected.is_empty() {
+            bail!("Select at least one file or directory (Space).");
+        }
+        if dest.as_os_str().is_empty() {
+            bail!("Destination is required.");
+        }
+        let sources: Vec<PathBuf> = self.selected.iter().cloned().collect();
+        let copied = copy_verified(&*ctx.fs, &root, &sources, &dest)?;
+
+        let mut lines = vec![
+            format!("{} file(s) copied to {} and verified by reading them back.", copied.len(), dest.display()),
+            String::new(),
+        ];
+        for c in copied.iter().take(MAX_LISTED) {
+            let name = c.copy.strip_prefix(&dest).unwrap_or(&c.copy);
+            lines.push(format!("{}…  {}", &c.sha256[..16], name.display()));
+        }
+        if copied.len() > MAX_LISTED {
+            lines.push(format!("… and {} more", copied.len() - MAX_LISTED));
+        }
+        lines.push(String::new());
+        if self.shred {
+            shred_originals(&*ctx.fs, &copied, &sources)?;
+            lines.push("Originals shredded.".to_string());
+        }
+        lines.push("Eject the volume before unplugging it.".to_string());
+        self.selected.clear();
+        Ok(lines)
+    }
+
+    /// Ask for SHRED before a copy that shreds the originals.
+    fn confirm_shred(&mut self) {
+        self.shred_confirm = Some(TypedConfirm::new(
+            "Shred Originals",
+            vec![
+                format!("After the copies are verified, the {} selected original(s) are", self.selected.len()),
+                "overwritten and removed. Shredded files cannot be recovered.".to_string(),
+            ],
+            "SHRED",
+            "Copy, Verify & Shred",
+        ));
+    }
+
+    /// Run the copy and show its result.
+    fn run_copy(&mut self, ctx: &AppCtx) -> Transition {
+        let res = self.copy(ctx);
+        self.reload(ctx);
+        Transition::Push(Box::new(match res {
+            Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+            Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+        }))
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for CopyToMediaScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Copy to Removable Media" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Copy to Removable Media";
+        let explanation_paras = [
+            "Select files or whole directories with Space (A selects all). Each file is copied to the destination, keeping its path, then read back and compared by SHA-256. Existing files are never overwritten.",
+            "With Shred Originals, the originals are overwritten and deleted only after every copy was verified.",
+        ];
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: spacer + source + spacer + entries + spacer + volume + destination + shred + spacer + buttons
+        let middle_rows: u16 = (self.entries.len().max(1) as u16).saturating_add(9);
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut items: Vec<ListItem> = vec![
+            ListItem::new(Line::from("")),
+            ListItem::new(field_line_text("Source Directory", &self.source, self.field_index == 0)),
+            ListItem::new(Line::from("")),
+        ];
+        if self.entries.is_empty() {
+            items.push(ListItem::new(Line::from("  Nothing in this directory.")));
+        }
+        for (i, entry) in self.entries.iter().enumerate() {
+            let focused = self.field_index == 1 && self.list_index == i;
+            let prefix = if focused { "▶ " } else { "  " };
+            let mark = if self.selected.contains(&entry.path) { "[x] " } else { "[ ] " };
+            let mut name = entry.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if entry.is_dir { name.push('/'); }
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::raw(mark),
+                Span::raw(name),
+            ])));
+        }
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(self.volume_line()));
+        items.push(ListItem::new(field_line_text("Destination", &self.destination, self.field_index == 3)));
+        items.push(ListItem::new(bool_field_line("Shred Originals", self.shred, self.field_index == 4)));
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(self.buttons_line()));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        f.render_widget(list, regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Space"), span_text(" Select"), span_sep(),
+            span_key("←/→"), span_text(" Volume"), span_sep(),
+            span_key("Esc"),   span_text(" Back"),     span_sep(),
+            span_key("Ctrl+Q"),span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some(prompt) = &self.shred_confirm {
+            prompt.draw(f, size);
+        }
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(prompt) = self.shred_confirm.as_mut() {
+            let Some(choice) = prompt.on_key(k) else { return Ok(Transition::Stay) };
+            self.shred_confirm = None;
+            return Ok(match choice {
+                TypedConfirmChoice::Confirmed => self.run_copy(ctx),
+                TypedConfirmChoice::Cancelled => Transition::Stay,
+            });
+        }
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let len = self.entries.len();
+        match k.code {
+            // DOWN: Source -> List (top to bottom) -> Volume -> Destination -> Shred -> Copy -> Back -> Source
+            KeyCode::Down | KeyCode::Tab => match self.field_index {
+                0 if len > 0 => { self.field_index = 1; self.list_index = 0; }
+                0 => self.field_index = 2,
+                1 if self.list_index + 1 < len => self.list_index += 1,
+                1 => self.field_index = 2,
+                6 => self.field_index = 0,
+                _ => self.field_index += 1,
+            },
+
+            // UP reverses
+            KeyCode::Up => match self.field_index {
+                0 => self.field_index = 6,
+                1 if self.list_index > 0 => self.list_index -= 1,
+                1 => self.field_index = 0,
+                2 if len > 0 => { self.field_index = 1; self.list_index = len - 1; }
+                2 => self.field_index = 0,
+                _ => self.field_index -= 1,
+            },
+
+            KeyCode::Enter if self.field_index == 0 => {
+                self.field_index = 1;
+                self.list_index = 0;
+                self.reload(ctx);
+            }
+
+            KeyCode::Char(' ') | KeyCode::Enter if self.field_index == 1 => {
+                if let Some(entry) = self.entries.get(self.list_index) {
+                    if !self.selected.remove(&entry.path) {
+                        self.selected.insert(entry.path.clone());
+                    }
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') if self.field_index == 1 => {
+                if self.selected.len() == len {
+                    self.selected.clear();
+                } else {
+                    self.selected = self.entries.iter().map(|e| e.path.clone()).collect();
+                }
+            }
+
+            KeyCode::Left | KeyCode::Right if self.field_index == 2 && !self.volumes.is_empty() => {
+                let n = self.volumes.len();
+                self.volume_index = if k.code == KeyCode::Right { (self.volume_index + 1) % n } else { (self.volume_index + n - 1) % n };
+                self.use_volume();
+            }
+            KeyCode::Enter if self.field_index == 2 => self.rescan(),
+
+            KeyCode::Char(' ') | KeyCode::Enter if self.field_index == 4 => self.shred = !self.shred,
+
+            // Shredding needs the typed phrase; a copy with nothing selected reports its error first
+            KeyCode::Enter if self.field_index == 5 && self.shred && !self.selected.is_empty() => self.confirm_shred(),
+            KeyCode::Enter if self.field_index == 5 => return Ok(self.run_copy(ctx)),
+
+            KeyCode::Enter if self.field_index == 6 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Text editing on the source and destination fields
+            KeyCode::Left if self.is_text() => self.tf_mut().move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut().move_right(),
+            KeyCode::Home if self.is_text() => self.tf_mut().home(),
+            KeyCode::End if self.is_text() => self.tf_mut().end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut().backspace(),
+            KeyCode::Delete if self.is_text() => self.tf_mut().delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut().insert_char(c)
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -10,73 +10,163 @@ use ratatui::{
 };
 use textwrap::wrap;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form, DRAFT_RESTORED_NOTE};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
-use crate::ui::common_nav::esc_to_back;
-use crate::ui::components::{TextField, field_line_text};
+use crate::ui::common_nav::{esc_to_back, is_load_key_file_key};
+use crate::ui::key_file_fields::KeyFileFields;
+use crate::ui::components::{TextField, field_line_text, funding_lines, info_line_text, warning_banner_lines};
+use crate::ui::out_dir_prompt::{OutDirChoice, OutDirPrompt};
+use crate::ui::prefill_summary::{PrefillField, PrefillSummary};
+use crate::units::{check_window, GasLimit, Nonce, PayloadNonce, UnixTime, Wei};
+use crate::validation::validate_gas_fields;
 use crate::defaults::Defaults;
+use crate::display::DisplaySettings;
+use crate::settings::{GasLimits, Settings};
 
 // Generic OK-only modal
-use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::{ConfirmOkScreen, AfterOk, LoadSigningKeyScreen};
 
 // NEW: bring in ABI loader, processor, types, writer helpers
 use crate::abi::load_abi;
-use crate::process::{process_item, BatchOpts};
+use crate::process::{key_reuse, process_item, retired_keys, BatchOpts, KeyReuse, RetiredKey};
+use crate::key_usage::KeyUsage;
+use crate::signing::SignerCache;
+use crate::lint;
+use crate::simulate;
 use crate::types::Item;
+use crate::call_trace::call_trace;
+use crate::screens::{CallPreviewScreen, SelectSignedFileScreen};
 use crate::write_signed_transactions_to_file::{
-    write_single_signed_transaction,
     build_filename_for_any_tx,
+    signed_file_metadata,
+};
+use crate::filename_template::{
+    base_vars, insert_key_vars, insert_label, render_template_preview,
+    x_coord_from_privkey_input, x_coord_from_pubkey_input, FilenameTemplates, KeyRole,
 };
 
 // NEW: load-from-file flow (directory picker)
 use crate::screens::ChooseDelegationInfoDirScreen;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Transactions),
+        order: 10,
+        title: "Create Delegation",
+        description: "Sign one delegation from manual input or an info file.",
+        open: |ctx| Transition::Push(Box::new(CreateDelegationScreen::resume(ctx))),
+    }
+}
+
+/// Info-file keys this form reads, for the summary shown after Load From File.
+const PREFILL_FIELDS: &[PrefillField] = &[
+    PrefillField { key: "DELEGATOR_PRIVKEY", label: "Delegator PrivKey", secret: true },
+    PrefillField { key: "DELEGATEE_PRIVKEY", label: "Delegatee PrivKey", secret: true },
+    PrefillField { key: "DELEGATEE_PUBKEY", label: "Delegatee PubKey", secret: false },
+    PrefillField { key: "REQUIRE_DELEGATEE_SIG_FOR_REVOCATION", label: "Require Delegatee Signature For Revocation", secret: false },
+    PrefillField { key: "DELEGATION_START", label: "Delegation Start", secret: false },
+    PrefillField { key: "DELEGATION_END", label: "Delegation End", secret: false },
+    PrefillField { key: "NONCE", label: "Transaction Nonce", secret: false },
+    PrefillField { key: "DELEGATION_PAYLOAD_NONCE", label: "Payload Nonce", secret: false },
+    PrefillField { key: "GAS_LIMIT", label: "Gas limit", secret: false },
+    PrefillField { key: "MAX_FEE_PER_GAS", label: "Maximum Fee Per Gas", secret: false },
+    PrefillField { key: "MAX_PRIORITY_FEE_PER_GAS", label: "Maximum Priority Fee Per Gas", secret: false },
+    PrefillField { key: "LABEL", label: "Notes", secret: false },
+    PrefillField { key: "OUTPUT_DIRECTORY", label: "Output Directory", secret: false },
+];
+
+/// Private-key fields Ctrl+K can load from an encrypted key file, with their labels.
+const KEY_FILE_FIELDS: &[(usize, &str)] = &[(0, "Delegator PrivKey"), (1, "Delegatee PrivKey")];
 
 pub struct CreateDelegationScreen {
-    // 0 delegator, 1 delegatee, 2 toggle, 3 nonce,
-    // 4 gas_limit, 5 max_fee_per_gas, 6 max_priority_fee_per_gas,
-    // 7 out_dir, 8 submit, 9 load_from_file, 10 back
+    // 0 delegator, 1 delegatee_priv, 2 delegatee_pubkey, 3 toggle, 4 start, 5 end, 6 nonce,
+    // 7 payload_nonce, 8 gas_limit, 9 max_fee_per_gas, 10 max_priority_fee_per_gas,
+    // 11 out_dir, 12 notes, 13 submit, 14 load_from_file, 15 clone_signed, 16 back
     field_index: usize,
     delegator_priv: TextField,
     delegatee_priv: TextField,
+    delegatee_pubkey: TextField,
     require_delegatee_sig_revocation: bool,
+    /// Validity window (see `units::UnixTime`); empty = open.
+    start_time: TextField,
+    end_time: TextField,
     nonce: TextField,
+    /// Fixed `bytes16` payload nonce (see `units::PayloadNonce`); empty = random. Not kept in
+    /// drafts, so a fixed nonce is never reused by accident.
+    payload_nonce: TextField,
     gas_limit: TextField,
     max_fee_per_gas: TextField,
     max_priority_fee_per_gas: TextField,
     out_dir: TextField,
+    /// Becomes the item's label (see `types::Item`).
+    notes: TextField,
+    /// SHA-256 of the info file last loaded via Load From File (recorded in output metadata).
+    input_sha256: Option<String>,
+    /// Open while an Output Directory from a loaded file awaits confirmation.
+    out_dir_prompt: Option<OutDirPrompt>,
+    /// What the last loaded file set; shown once, before any Output Directory prompt.
+    prefill_summary: Option<PrefillSummary>,
+    /// Key reuse the operator was warned about; the next submit with the same keys signs anyway.
+    key_reuse_ack: Option<KeyReuse>,
+    /// Retired keys the operator was warned about; the next submit with the same keys signs anyway.
+    retired_ack: Option<RetiredKey>,
+    /// Reopened with the values of the last visit (see `resume`).
+    restored: bool,
+    /// Private-key fields loaded from key files, shown by source instead of value.
+    key_files: KeyFileFields,
 }
 
 impl CreateDelegationScreen {
-    pub fn new() -> Self {
+    pub fn new(gas_limits: &GasLimits) -> Self {
         Self {
             field_index: 0,
             delegator_priv: TextField::with(""),
             delegatee_priv: TextField::with(""),
+            delegatee_pubkey: TextField::with(""),
             require_delegatee_sig_revocation: false, // default: no
+            start_time: TextField::with(""),
+            end_time: TextField::with(""),
             nonce: TextField::with(""),
-            gas_limit: TextField::with(Defaults::GAS_LIMIT),
+            payload_nonce: TextField::with(""),
+            gas_limit: TextField::with(gas_limits.default_for("createDelegationEvent")),
             max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
             max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
             out_dir: TextField::with(Defaults::CREATE_DELEGATION_OUT_DIR),
+            notes: TextField::with(""),
+            input_sha256: None,
+            out_dir_prompt: None,
+            prefill_summary: None,
+            key_reuse_ack: None,
+            retired_ack: None,
+            restored: false,
+            key_files: KeyFileFields::default(),
         }
     }
 
     fn is_text(&self) -> bool {
-        matches!(self.field_index, 0 | 1 | 3 | 4 | 5 | 6 | 7)
+        matches!(self.field_index, 0 | 1 | 2 | 4..=12)
     }
 
     fn tf_ref(&self, idx: usize) -> &TextField {
         match idx {
             0 => &self.delegator_priv,
             1 => &self.delegatee_priv,
-            3 => &self.nonce,
-            4 => &self.gas_limit,
-            5 => &self.max_fee_per_gas,
-            6 => &self.max_priority_fee_per_gas,
-            7 => &self.out_dir,
+            2 => &self.delegatee_pubkey,
+            4 => &self.start_time,
+            5 => &self.end_time,
+            6 => &self.nonce,
+            7 => &self.payload_nonce,
+            8 => &self.gas_limit,
+            9 => &self.max_fee_per_gas,
+            10 => &self.max_priority_fee_per_gas,
+            11 => &self.out_dir,
+            12 => &self.notes,
             _ => unreachable!("tf_ref called on non-text field"),
         }
     }
@@ -85,11 +175,16 @@ impl CreateDelegationScreen {
         match idx {
             0 => &mut self.delegator_priv,
             1 => &mut self.delegatee_priv,
-            3 => &mut self.nonce,
-            4 => &mut self.gas_limit,
-            5 => &mut self.max_fee_per_gas,
-            6 => &mut self.max_priority_fee_per_gas,
-            7 => &mut self.out_dir,
+            2 => &mut self.delegatee_pubkey,
+            4 => &mut self.start_time,
+            5 => &mut self.end_time,
+            6 => &mut self.nonce,
+            7 => &mut self.payload_nonce,
+            8 => &mut self.gas_limit,
+            9 => &mut self.max_fee_per_gas,
+            10 => &mut self.max_priority_fee_per_gas,
+            11 => &mut self.out_dir,
+            12 => &mut self.notes,
             _ => unreachable!("tf_mut called on non-text field"),
         }
     }
@@ -102,52 +197,129 @@ impl CreateDelegationScreen {
 
     // Apply pending prefill from ctx (we call this at the top of on_key).
     fn apply_prefill_if_any(&mut self, ctx: &mut AppCtx) {
-        if let Some(prefill) = ctx.pending_delegation_prefill.take() {
-            // Strings
-            if let Some(v) = prefill.map.get("DELEGATOR_PRIVKEY") {
-                Self::set_textfield(&mut self.delegator_priv, v);
-            }
-            if let Some(v) = prefill.map.get("DELEGATEE_PRIVKEY") {
-                Self::set_textfield(&mut self.delegatee_priv, v);
-            }
-            if let Some(v) = prefill.map.get("NONCE") {
-                Self::set_textfield(&mut self.nonce, v);
-            }
-            if let Some(v) = prefill.map.get("GAS_LIMIT") {
-                Self::set_textfield(&mut self.gas_limit, v);
-            }
-            if let Some(v) = prefill.map.get("MAX_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_fee_per_gas, v);
-            }
-            if let Some(v) = prefill.map.get("MAX_PRIORITY_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
-            }
+        if let Some(key) = ctx.take_signing_key(self.crumb(), Form::Delegation) {
+            *self.tf_mut(key.field) = self.key_files.fill(&key);
+        }
+        if let Some(prefill) = ctx.take_prefill(self.crumb(), Form::Delegation) {
+            self.input_sha256 = prefill.source_sha256.clone();
+            self.prefill_summary = Some(PrefillSummary::new(PREFILL_FIELDS, &prefill));
+            self.apply_values(&prefill.map);
             if let Some(v) = prefill.map.get("OUTPUT_DIRECTORY") {
-                Self::set_textfield(&mut self.out_dir, v);
+                // Never taken silently: the operator confirms the resolved path first.
+                self.out_dir_prompt = OutDirPrompt::for_prefill(v, &self.out_dir.text, ctx);
             }
+        }
+    }
+
+    /// Set the fields named in `map` (info-file keys), except the Output Directory.
+    fn apply_values(&mut self, map: &HashMap<String, String>) {
+        // Strings
+        if let Some(v) = map.get("DELEGATOR_PRIVKEY") {
+            Self::set_textfield(&mut self.delegator_priv, v);
+        }
+        if let Some(v) = map.get("DELEGATEE_PRIVKEY") {
+            Self::set_textfield(&mut self.delegatee_priv, v);
+        }
+        if let Some(v) = map.get("DELEGATEE_PUBKEY") {
+            Self::set_textfield(&mut self.delegatee_pubkey, v);
+        }
+        if let Some(v) = map.get("DELEGATION_START") {
+            Self::set_textfield(&mut self.start_time, v);
+        }
+        if let Some(v) = map.get("DELEGATION_END") {
+            Self::set_textfield(&mut self.end_time, v);
+        }
+        if let Some(v) = map.get("NONCE") {
+            Self::set_textfield(&mut self.nonce, v);
+        }
+        if let Some(v) = map.get("DELEGATION_PAYLOAD_NONCE") {
+            Self::set_textfield(&mut self.payload_nonce, v);
+        }
+        if let Some(v) = map.get("GAS_LIMIT") {
+            Self::set_textfield(&mut self.gas_limit, v);
+        }
+        if let Some(v) = map.get("MAX_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("MAX_PRIORITY_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("LABEL") {
+            Self::set_textfield(&mut self.notes, v);
+        }
+
+        // Boolean (treat anything else as false)
+        if let Some(v) = map.get("REQUIRE_DELEGATEE_SIG_FOR_REVOCATION") {
+            let vv = v.to_ascii_lowercase();
+            self.require_delegatee_sig_revocation = matches!(
+                vv.as_str(),
+                "true" | "1" | "yes" | "on" | "y" | "t"
+            );
+        }
+    }
 
-            // Boolean (treat anything else as false)
-            if let Some(v) = prefill.map.get("REQUIRE_DELEGATEE_SIG_FOR_REVOCATION") {
-                let vv = v.to_ascii_lowercase();
-                self.require_delegatee_sig_revocation = matches!(
-                    vv.as_str(),
-                    "true" | "1" | "yes" | "on" | "y" | "t"
-                );
+    /// A new form holding the values it was last left with, if any.
+    pub fn resume(ctx: &mut AppCtx) -> Self {
+        let mut screen = Self::new(&ctx.settings.gas_limits);
+        if let Some(draft) = ctx.take_draft(screen.crumb(), Form::Delegation) {
+            screen.apply_values(&draft);
+            if let Some(dir) = draft.get("OUTPUT_DIRECTORY") {
+                Self::set_textfield(&mut screen.out_dir, dir);
             }
+            screen.restored = true;
+        }
+        screen
+    }
+
+    /// Keep the non-secret values for the next visit (private keys are left out).
+    fn leave(&self, ctx: &mut AppCtx) {
+        let mut map = HashMap::new();
+        for (key, tf) in [
+            ("DELEGATEE_PUBKEY", &self.delegatee_pubkey),
+            ("DELEGATION_START", &self.start_time),
+            ("DELEGATION_END", &self.end_time),
+            ("NONCE", &self.nonce),
+            ("GAS_LIMIT", &self.gas_limit),
+            ("MAX_FEE_PER_GAS", &self.max_fee_per_gas),
+            ("MAX_PRIORITY_FEE_PER_GAS", &self.max_priority_fee_per_gas),
+            ("LABEL", &self.notes),
+            ("OUTPUT_DIRECTORY", &self.out_dir),
+        ] {
+            map.insert(key.to_string(), tf.text.clone());
         }
+        map.insert("REQUIRE_DELEGATEE_SIG_FOR_REVOCATION".to_string(), self.require_delegatee_sig_revocation.to_string());
+        ctx.dispatch(self.crumb(), AppEvent::FormLeft { form: Form::Delegation, draft: map });
     }
 
-    // One horizontal line: < Create Delegation >   < Load From File >   < Back >
-    fn buttons_line(submit_selected: bool, load_selected: bool, back_selected: bool) -> Line<'static> {
+    // One horizontal line: < Create Delegation >   < Load From File >   < Clone Signed File >   < Back >
+    fn buttons_line(submit_selected: bool, load_selected: bool, clone_selected: bool, back_selected: bool) -> Line<'static> {
         let mut spans: Vec<Span<'static>> = Vec::new();
         spans.extend(button_spans("Create Delegation", submit_selected));
         spans.push(Span::raw("   "));
         spans.extend(button_spans("Load From File", load_selected));
         spans.push(Span::raw("   "));
+        spans.extend(button_spans("Clone Signed File", clone_selected));
+        spans.push(Span::raw("   "));
         spans.extend(button_spans("Back", back_selected));
         Line::from(spans)
     }
 
+    /// Start and end as entered, checked as a window.
+    fn window(&self) -> Result<(UnixTime, UnixTime)> {
+        let start: UnixTime = self.start_time.text.parse().context("Delegation Start")?;
+        let end: UnixTime = self.end_time.text.parse().context("Delegation End")?;
+        check_window("Delegation", start, end)?;
+        Ok((start, end))
+    }
+
+    /// The window as it will be signed, in the configured display format.
+    fn window_preview(&self, display: &DisplaySettings) -> String {
+        match self.window() {
+            Ok((start, end)) => display.window(start.0, end.0),
+            Err(e) => format!("{e:#}"),
+        }
+    }
+
     fn ensure_out_dir_nonempty(&self) -> Result<PathBuf> {
         let out_dir = self.out_dir.text.trim();
         if out_dir.is_empty() {
@@ -156,47 +328,86 @@ impl CreateDelegationScreen {
         Ok(PathBuf::from(out_dir))
     }
 
-    /// Create, sign, and write a single delegation tx using process_item() + writer.
-    async fn create_and_write_delegation(&self) -> Result<PathBuf> {
+    /// The chain ID and contract the delegation is signed for. The form has no fields for them,
+    /// so the item, the filename preview and the network banner all take them from here.
+    fn network(&self) -> (u64, &'static str) {
+        (Defaults::CHAIN_ID, Defaults::CONTRACT_ADDRESS)
+    }
+
+    /// Live preview of the output filename from the current inputs.
+    fn filename_preview(&self, templates: &FilenameTemplates) -> String {
+        let mut vars = base_vars("createDelegationEvent", self.nonce.text.trim(), &self.network().0.to_string());
+        if let Some(x) = x_coord_from_privkey_input(&self.delegator_priv.text) {
+            insert_key_vars(&mut vars, KeyRole::Delegator, &x);
+        }
+        // Delegatee: privkey wins if present, else pubkey (same precedence as process.rs)
+        let delegatee_x = if self.delegatee_priv.text.trim().is_empty() {
+            x_coord_from_pubkey_input(&self.delegatee_pubkey.text)
+        } else {
+            x_coord_from_privkey_input(&self.delegatee_priv.text)
+        };
+        if let Some(x) = delegatee_x {
+            insert_key_vars(&mut vars, KeyRole::Delegatee, &x);
+        }
+        insert_label(&mut vars, Some(&self.notes.text));
+        render_template_preview(&templates.delegation, &vars)
+    }
+
+    /// Create and sign a single delegation tx using process_item(), and return its
+    /// contract-call preview; the file is written from there once reviewed.
+    async fn sign_delegation(&self, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<CallPreviewScreen> {
         // Validate required secrets
         let pk_x = self.delegator_priv.text.trim();
         let pk_y = self.delegatee_priv.text.trim();
+        let pub_y = self.delegatee_pubkey.text.trim();
         if pk_x.is_empty() {
             anyhow::bail!("Delegator PrivKey cannot be empty.");
         }
-        if pk_y.is_empty() {
-            anyhow::bail!("Delegatee PrivKey cannot be empty.");
+        if pk_y.is_empty() && pub_y.is_empty() {
+            anyhow::bail!("Provide either Delegatee PrivKey or Delegatee PubKey.");
         }
 
+        let (start, end) = self.window()?;
+
         // Parse nonce
         let nonce_str = self.nonce.text.trim();
-        let nonce: u64 = nonce_str.parse().context("Nonce must be an integer")?;
-
-        // Parse / collect gas opts
-        let opts = BatchOpts {
-            gas_limit: self.gas_limit.text.trim().to_string(),
-            max_fee_per_gas: self.max_fee_per_gas.text.trim().to_string(),
-            max_priority_fee_per_gas: self.max_priority_fee_per_gas.text.trim().to_string(),
+        let nonce: Nonce = nonce_str.parse().context("Nonce must be an integer")?;
+        let payload_nonce: PayloadNonce = self.payload_nonce.text.parse().context("Payload Nonce")?;
+
+        // Gas opts, checked by the caller
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let mut opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
         };
 
         // Build ABI
         let abi = load_abi()?;
 
         // Assemble Item for createDelegationEvent
+        let (chain_id, contract_address) = self.network();
         let item = Item {
             function_to_call: "createDelegationEvent".to_string(),
             nonce: Some(nonce),
-            // Defaults::CHAIN_ID is u64
-            chain_id: Some(Defaults::CHAIN_ID),
-            contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+            chain_id: Some(chain_id),
+            contract_address: contract_address.to_string(),
+            label: Some(self.notes.text.clone()),
 
             // Type A
             type_a_privkey_x: Some(pk_x.to_string()),
-            type_a_privkey_y: Some(pk_y.to_string()),
-            type_a_pubkey_y: Some("".to_string()), // not used if we provide privkey_y
-            type_a_uint_x: Some(0),
-            type_a_uint_y: Some(0),
+            type_a_privkey_y: Some(pk_y.to_string()),   // may be empty string; process.rs prefers privkey if non-empty
+            type_a_pubkey_y: Some(pub_y.to_string()),   // otherwise falls back to pubkey (delegatee sig zeroed)
+            type_a_uint_x: Some(start.0),
+            type_a_uint_y: Some(end.0),
             type_a_boolean: Some(if self.require_delegatee_sig_revocation { "true".into() } else { "false".into() }),
+            type_a_nonce16: payload_nonce.hex(),
 
             // Type B (unused)
             type_b_privkey_x: None,
@@ -204,103 +415,45 @@ impl CreateDelegationScreen {
             type_b_pubkey_y: None,
             type_b_uint_x: None,
             type_b_uint_y: None,
+            type_b_nonce16: None,
 
             // Type C (unused)
             type_c_privkey_x: None,
+            type_c_nonce16: None,
         };
 
+        // A reuse already confirmed for these exact keys is signed as is
+        opts.allow_key_reuse = self.key_reuse_ack.is_some() && key_reuse(&item, &opts.signers) == self.key_reuse_ack;
+        opts.allow_retired_key =
+            self.retired_ack.is_some() && retired_keys(&item, &opts.signers, &opts.retired_keys) == self.retired_ack;
+
         // Build & sign the transaction
         let entry = process_item(&abi, &opts, &item)
             .await
             .context("failed to construct and sign delegation transaction")?;
+        lint::check_entries(std::slice::from_ref(&entry))?;
+        simulate::check_entries(settings, std::slice::from_ref(&entry))?;
 
-        // Build filename per spec: "[DelegatorX]_delegates_to_[DelegateeX]_nonce_[nonce].txt"
-        let filename = build_filename_for_any_tx(&entry.decoded_tx);
+        // Build filename from the configured template (default: "[DelegatorX]_delegates_to_[DelegateeX]_nonce_[nonce].txt")
+        let filename = build_filename_for_any_tx(&entry, &settings.filename_templates);
         let mut out_path = self.ensure_out_dir_nonempty()?;
         out_path.push(filename);
-        let written = write_single_signed_transaction(&out_path, &entry, true)
-            .context("failed to write signed transaction file")?;
-
-        Ok(written)
-    }
-
-    fn validate_gas_limit(&self) -> Result<()> {
-        let max_str = Defaults::GAS_LIMIT.trim();
-        let max: u64 = max_str.parse().context("Defaults::GAS_LIMIT must be an integer")?;
-
-        let user_str = self.gas_limit.text.trim();
-        let user: u64 = user_str.parse().context("Gas limit must be an integer")?;
+        let metadata = signed_file_metadata(settings, self.input_sha256.clone(), Some("createDelegationEvent"));
+        let trace = call_trace(&abi, &entry.decoded_tx.encodedData, &settings.display)?;
 
-        if user == 0 {
-            anyhow::bail!("Gas limit must be greater than zero.");
-        }
-        if user > max {
-            anyhow::bail!(format!(
-                "Gas limit {} exceeds the maximum allowed {}.",
-                user, max
-            ));
-        }
-        Ok(())
-    }
-
-    fn validate_fee_caps(&self) -> Result<()> {
-        // maxFeePerGas cap
-        let max_fee_cap_str = Defaults::MAX_FEE_PER_GAS.trim();
-        let max_fee_cap: u64 = max_fee_cap_str
-            .parse()
-            .context("Defaults::MAX_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_max_fee_str = self.max_fee_per_gas.text.trim();
-        let user_max_fee: u64 = user_max_fee_str
-            .parse()
-            .context("Maximum Fee Per Gas must be an integer (wei)")?;
-        if user_max_fee == 0 {
-            anyhow::bail!("Maximum Fee Per Gas must be greater than zero.");
-        }
-        if user_max_fee > max_fee_cap {
-            anyhow::bail!(format!(
-                "Maximum Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_max_fee, max_fee_cap
-            ));
-        }
-
-        // maxPriorityFeePerGas cap
-        let max_prio_cap_str = Defaults::MAX_PRIORITY_FEE_PER_GAS.trim();
-        let max_prio_cap: u64 = max_prio_cap_str
-            .parse()
-            .context("Defaults::MAX_PRIORITY_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_prio_str = self.max_priority_fee_per_gas.text.trim();
-        let user_prio: u64 = user_prio_str
-            .parse()
-            .context("Maximum Priority Fee Per Gas must be an integer (wei)")?;
-
-        // priority fee can be zero, but not above cap
-        if user_prio > max_prio_cap {
-            anyhow::bail!(format!(
-                "Maximum Priority Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_prio, max_prio_cap
-            ));
-        }
-
-        // Sanity: priority <= max fee
-        if user_prio > user_max_fee {
-            anyhow::bail!("Maximum Priority Fee Per Gas cannot exceed Maximum Fee Per Gas.");
-        }
-
-        Ok(())
+        Ok(CallPreviewScreen::new(trace, entry, out_path, metadata, opts.encoding))
     }
 }
 
 impl Default for CreateDelegationScreen {
-    fn default() -> Self { Self::new() }
+    fn default() -> Self { Self::new(&GasLimits::default()) }
 }
 
 #[async_trait]
 impl ScreenWidget for CreateDelegationScreen {
 
   fn apply_prefill(&mut self, ctx: &mut AppCtx) {
-        self.apply_prefill_if_any(ctx); // consumes ctx.pending_delegation_prefill exactly once
+        self.apply_prefill_if_any(ctx); // consumes the pending prefill exactly once (AppEvent::PrefillTaken)
     }
 
 
@@ -308,27 +461,47 @@ impl ScreenWidget for CreateDelegationScreen {
 
     fn title(&self) -> &str { "" }
 
-    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+
+
+
+    fn crumb(&self) -> &str { "Create Delegation" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
         let header_text = "Create Delegation";
-        let explanation_paras = [
+        let mut explanation_paras = vec![
             "Enter the fields below. The app will create and sign an EIP-1559 transaction",
-            "for createDelegationEvent and save a one-element JSON array (pretty-printed)",
-            "to your chosen output directory. The filename will be:",
-            "[delegatorX]_delegates_to_[delegateeX]_nonce_[nonce].txt",
+            "for createDelegationEvent and save it as pretty-printed JSON (entries plus a metadata block)",
+            "to your chosen output directory. The filename follows the delegation template",
+            "in the settings file; a live preview is shown below the output directory.",
+            "If only the Delegatee PubKey is given, the delegatee signature is left zeroed, so the delegatee's secret never has to be on this machine.",
+            "Delegation Start and End bound when the delegation is in force; leave them empty for one that starts at once and never ends.",
         ];
+        if self.restored {
+            explanation_paras.push(DRAFT_RESTORED_NOTE);
+        }
 
         // === TOP BOX ===
         let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
         let header_lines = wrap(header_text, top_inner_width).len() as u16;
 
         let mut exp_lines = 0usize;
-        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
         let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
 
-        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+        // Unregistered chain/contract pair: a bold banner under the explanation.
+        let (chain_id, contract_address) = self.network();
+        let banner = ctx.settings.networks
+            .unknown_warning(chain_id, contract_address)
+            .map(|w| warning_banner_lines(&w, top_inner_width))
+            .unwrap_or_default();
+        let banner_lines = if banner.is_empty() { 0 } else { banner.len() as u16 + 1 };
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + banner_lines;
 
-        // Middle: 11 focusable positions (0..=10) plus spacer
-        let middle_rows: u16 = 11 + 1;
+        // Middle: 14 field rows plus validity, filename preview, funding lines and spacer
+        let middle_rows: u16 = 14 + 5;
         let middle_needed = 2 + 2 + middle_rows;
 
         let footer_height = 3;
@@ -358,6 +531,10 @@ impl ScreenWidget for CreateDelegationScreen {
             for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
             if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
         }
+        if !banner.is_empty() {
+            expl_lines.push(Line::from(""));
+            expl_lines.extend(banner);
+        }
         let explanation_para = Paragraph::new(expl_lines)
             .alignment(Alignment::Left)
             .wrap(Wrap { trim: true });
@@ -372,49 +549,58 @@ impl ScreenWidget for CreateDelegationScreen {
 
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from("")); // spacer above first field
-        lines.push(field_line_text("Delegator PrivKey", self.tf_ref(0), self.field_index == 0));
-        lines.push(field_line_text("Delegatee PrivKey", self.tf_ref(1), self.field_index == 1));
+        lines.push(self.key_files.line("Delegator PrivKey", 0, self.tf_ref(0), self.field_index == 0));
+        lines.push(self.key_files.line("Delegatee PrivKey (optional if PubKey is provided)", 1, self.tf_ref(1), self.field_index == 1));
+        lines.push(field_line_text("Delegatee PubKey (0x04… uncompressed, optional)", self.tf_ref(2), self.field_index == 2));
 
-        // toggle line at index 2
+        // toggle line at index 3
         let label_span = Span::styled(
             "Require Delegatee Signature For Revocation?  ",
             Style::default().fg(Color::Yellow)
         );
-        let val_style = if self.field_index == 2 {
+        let val_style = if self.field_index == 3 {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::White)
         };
         lines.push(Line::from(vec![label_span, Span::styled(toggle_val.to_string(), val_style)]));
 
-        lines.push(field_line_text("Transaction Nonce", self.tf_ref(3), self.field_index == 3));
+        lines.push(field_line_text("Delegation Start (Unix time or YYYY-MM-DD [HH:MM], UTC; empty = now)", self.tf_ref(4), self.field_index == 4));
+        lines.push(field_line_text("Delegation End (empty = no end)", self.tf_ref(5), self.field_index == 5));
+        lines.push(info_line_text("Validity", &self.window_preview(&ctx.settings.display)));
+        lines.push(field_line_text("Transaction Nonce", self.tf_ref(6), self.field_index == 6));
+        lines.push(field_line_text("Payload Nonce (0x + 32 hex digits or UUID; empty = random)", self.tf_ref(7), self.field_index == 7));
 
         // Gas limit (cap label)
-        let gas_label = format!("Gas limit (maximum {} gas)", Defaults::GAS_LIMIT);
-        lines.push(field_line_text(&gas_label, self.tf_ref(4), self.field_index == 4));
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(8), self.field_index == 8));
 
         // Max fee per gas (cap label)
         let mfg_label = format!(
             "Maximum Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_FEE_PER_GAS
+            ctx.settings.gas_caps.max_fee_per_gas()
         );
-        lines.push(field_line_text(&mfg_label, self.tf_ref(5), self.field_index == 5));
+        lines.push(field_line_text(&mfg_label, self.tf_ref(9), self.field_index == 9));
 
         // Max priority fee per gas (cap label)
         let mpfg_label = format!(
             "Maximum Priority Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_PRIORITY_FEE_PER_GAS
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
         );
-        lines.push(field_line_text(&mpfg_label, self.tf_ref(6), self.field_index == 6));
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(10), self.field_index == 10));
 
         // Output directory
-        lines.push(field_line_text("Output Directory", self.tf_ref(7), self.field_index == 7));
+        lines.push(field_line_text("Output Directory", self.tf_ref(11), self.field_index == 11));
+        lines.push(field_line_text("Notes (label, optional)", self.tf_ref(12), self.field_index == 12));
+        lines.push(info_line_text("Filename Preview", &self.filename_preview(&ctx.settings.filename_templates)));
+        lines.extend(funding_lines(&self.delegator_priv.text, &self.gas_limit.text, &self.max_fee_per_gas.text, &ctx.settings.display));
 
         lines.push(Line::from("")); // spacer
         lines.push(Self::buttons_line(
-            self.field_index == 8,
-            self.field_index == 9,
-            self.field_index == 10
+            self.field_index == 13,
+            self.field_index == 14,
+            self.field_index == 15,
+            self.field_index == 16
         ));
 
         let middle_para = Paragraph::new(lines);
@@ -427,16 +613,38 @@ impl ScreenWidget for CreateDelegationScreen {
             span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
             span_key("Enter"),   span_text(" Select"), span_sep(),
             span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+K"),  span_text(" Key File"), span_sep(),
             span_key("Ctrl+Q"),  span_text(" Quit"),
         ]);
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some(summary) = &self.prefill_summary {
+            summary.draw(f, size);
+        } else if let Some(prompt) = &self.out_dir_prompt {
+            prompt.draw(f, size);
+        }
     }
 
     async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         // Apply pending prefill if any
         self.apply_prefill_if_any(ctx);
 
+        // Any key dismisses the summary.
+        if self.prefill_summary.take().is_some() {
+            return Ok(Transition::Stay);
+        }
+        if let Some(prompt) = self.out_dir_prompt.as_mut() {
+            match prompt.on_key(k) {
+                Some(OutDirChoice::Use(dir)) => Self::set_textfield(&mut self.out_dir, &dir),
+                Some(OutDirChoice::Keep) => {}
+                None => return Ok(Transition::Stay),
+            }
+            self.out_dir_prompt = None;
+            return Ok(Transition::Stay);
+        }
+
         if let Some(t) = esc_to_back(k) {
+            self.leave(ctx);
             return Ok(t); // Esc -> Back
         }
 
@@ -446,47 +654,66 @@ impl ScreenWidget for CreateDelegationScreen {
             }
         }
 
+        // Ctrl+K on a private-key field: load it from an encrypted key file
+        if is_load_key_file_key(&k) {
+            if let Some(&(idx, label)) = KEY_FILE_FIELDS.iter().find(|(i, _)| *i == self.field_index) {
+                return Ok(Transition::Push(Box::new(LoadSigningKeyScreen::new(Form::Delegation, idx, label, ctx.fs.clone()))));
+            }
+            return Ok(Transition::Stay);
+        }
+        // A key loaded from a file is replaced, never edited unseen
+        if matches!(k.code, KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_))
+            && self.is_text()
+            && self.key_files.source(self.field_index, self.tf_ref(self.field_index)).is_some()
+        {
+            self.key_files.forget(self.field_index);
+            *self.tf_mut(self.field_index) = TextField::default();
+        }
+
         match k.code {
             // Navigation
             KeyCode::Up => {
-                if self.field_index == 0 { self.field_index = 10; } else { self.field_index -= 1; }
+                if self.field_index == 0 { self.field_index = 16; } else { self.field_index -= 1; }
             }
             KeyCode::Down | KeyCode::Tab => {
-                self.field_index = (self.field_index + 1) % 11;
+                self.field_index = (self.field_index + 1) % 17;
             }
 
-            // Toggle boolean (index 2)
-            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 2 => {
+            // Toggle boolean (index 3)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 3 => {
                 self.require_delegatee_sig_revocation = !self.require_delegatee_sig_revocation;
             }
 
             // Enter on [Create Delegation]
-            KeyCode::Enter if self.field_index == 8 => {
+            KeyCode::Enter if self.field_index == 13 => {
                 // Enforce caps first
-                if let Err(e) = self.validate_gas_limit() {
-                    return Ok(Transition::Push(Box::new(
-                        ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
-                if let Err(e) = self.validate_fee_caps() {
-                    return Ok(Transition::Push(Box::new(
+                let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+                    Ok(gas) => gas,
+                    Err(e) => return Ok(Transition::Push(Box::new(
                         ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
+                    ))),
+                };
 
-                // Create, sign, and write the single-entry JSON
-                match self.create_and_write_delegation().await {
-                    Ok(path) => {
-                        let lines = vec![
-                            "Saved signed delegation transaction:".to_string(),
-                            "".to_string(),
-                            path.display().to_string(),
-                        ];
-                        return Ok(Transition::Push(Box::new(
-                            ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
-                        )));
+                // Create and sign, then review the call before the single-entry JSON is written
+                match self.sign_delegation(&ctx.settings, &ctx.key_usage, gas).await {
+                    Ok(preview) => {
+                        return Ok(Transition::Push(Box::new(preview)));
                     }
                     Err(e) => {
+                        if let Some(retired) = e.downcast_ref::<RetiredKey>() {
+                            let lines = retired.confirm_lines("Create Delegation");
+                            self.retired_ack = Some(retired.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                        if let Some(reuse) = e.downcast_ref::<KeyReuse>() {
+                            let lines = reuse.confirm_lines("Create Delegation");
+                            self.key_reuse_ack = Some(reuse.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
                         return Ok(Transition::Push(Box::new(
                             ConfirmOkScreen::new(&format!("Error: {e:#}"))
                                 .with_after_ok(AfterOk::Pop)
@@ -496,14 +723,22 @@ impl ScreenWidget for CreateDelegationScreen {
             }
 
             // Enter on [Load From File]
-            KeyCode::Enter if self.field_index == 9 => {
+            KeyCode::Enter if self.field_index == 14 => {
+                return Ok(Transition::Push(Box::new(
+                    ChooseDelegationInfoDirScreen::resume(ctx)
+                )));
+            }
+
+            // Enter on [Clone Signed File]: start where this form writes signed files
+            KeyCode::Enter if self.field_index == 15 => {
                 return Ok(Transition::Push(Box::new(
-                    ChooseDelegationInfoDirScreen::new()
+                    SelectSignedFileScreen::new("createDelegationEvent", PathBuf::from(self.out_dir.text.trim()), ctx.fs.clone())
                 )));
             }
 
             // Enter on [Back]
-            KeyCode::Enter if self.field_index == 10 => {
+            KeyCode::Enter if self.field_index == 16 => {
+                self.leave(ctx);
                 return Ok(Transition::Pop); // Back
             }
 
@@ -14,6 +14,17 @@ use crate::app::{AppCtx, ScreenWidget, Transition};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text};
 use crate::ui::common_nav::esc_to_back;
+use crate::screens::registry::{Menu, MenuEntry};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Main,
+        order: 10,
+        title: "Create Inkan Identity",
+        description: "Set up a new Inkan identity.",
+        open: |_| Transition::Push(Box::new(CreateInkanIdentityScreen::new())),
+    }
+}
 
 
 #[derive(Default)]
@@ -48,6 +59,7 @@ impl MenuItem {
 #[async_trait]
 impl ScreenWidget for CreateInkanIdentityScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Create Inkan Identity" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Create Inkan Identity";
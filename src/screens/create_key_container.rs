@@ -0,0 +1,313 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{TextField, field_line_text, RevealBriefly, field_line_password, show_password_line};
+use crate::defaults::Defaults;
+use crate::commands::keygen;
+use crate::crypto::container::{save_modern_container, ContainerOptions};
+use crate::crypto::modern::KdfParams;
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 20,
+        title: "Create Key Container",
+        description: "Generate several key pairs and store them in one encrypted file.",
+        open: |_| Transition::Push(Box::new(CreateKeyContainerScreen::new())),
+    }
+}
+
+/// Upper bound on keys per container (keeps the v2 header and the picker list reasonable).
+const MAX_CONTAINER_KEYS: usize = 64;
+
+pub struct CreateKeyContainerScreen {
+    // indices: 0 container name, 1 key labels, 2 password, 3 confirm,
+    // 4 show pwd toggle, 5 out dir, 6 submit, 7 back
+    field_index: usize,
+    name: TextField,
+    labels: TextField,
+    password: TextField,
+    confirm: TextField,
+    out_dir: TextField,
+    show_password: bool,
+    reveal: RevealBriefly,
+}
+
+impl CreateKeyContainerScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            name: TextField::with(""),
+            labels: TextField::with(""),
+            password: TextField::with(""),
+            confirm: TextField::with(""),
+            out_dir: TextField::with(Defaults::CREATE_KEYPAIR_OUT_DIR),
+            show_password: false,
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0..=3 | 5) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.name,
+            1 => &mut self.labels,
+            2 => &mut self.password,
+            3 => &mut self.confirm,
+            5 => &mut self.out_dir,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(submit_selected: bool, back_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Create Key Container", submit_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+
+    /// Comma-separated labels -> trimmed, non-empty, unique list.
+    fn parse_labels(&self) -> Result<Vec<String>> {
+        let labels: Vec<String> = self
+            .labels
+            .text
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if labels.is_empty() {
+            return Err(anyhow!("Key Labels must list at least one label (comma-separated)."));
+        }
+        if labels.len() > MAX_CONTAINER_KEYS {
+            return Err(anyhow!("A container holds at most {MAX_CONTAINER_KEYS} keys."));
+        }
+        for (i, l) in labels.iter().enumerate() {
+            if labels[..i].contains(l) {
+                return Err(anyhow!("Duplicate key label: {l}"));
+            }
+        }
+        Ok(labels)
+    }
+
+    fn create_container(&self) -> Result<(PathBuf, usize)> {
+        let name = self.name.text.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Container Name cannot be empty."));
+        }
+        let labels = self.parse_labels()?;
+        if self.password.text.is_empty() {
+            return Err(anyhow!("Password cannot be empty."));
+        }
+        if self.password.text != self.confirm.text {
+            return Err(anyhow!("Password and Confirm Password do not match."));
+        }
+        let out_dir = self.out_dir.text.trim();
+        if out_dir.is_empty() {
+            return Err(anyhow!("Output Directory cannot be empty."));
+        }
+
+        // One fresh key per label
+        let records = keygen::generate(labels.len() as u32).context("generating keypairs")?;
+        let mut keys: Vec<(String, [u8; 32])> = Vec::with_capacity(labels.len());
+        for (label, rec) in labels.into_iter().zip(records.iter()) {
+            let mut raw = hex::decode(&rec.privateKeyHexNostrFormat).context("decoding generated key")?;
+            let mut sk = [0u8; 32];
+            sk.copy_from_slice(&raw);
+            raw.zeroize();
+            keys.push((label, sk));
+        }
+
+        let safe_name: String = name
+            .chars()
+            .filter_map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' { Some(c) }
+                else if c.is_whitespace() { Some('_') }
+                else { None }
+            })
+            .collect();
+        let safe_name = if safe_name.is_empty() { "container".to_string() } else { safe_name };
+        let file_path = PathBuf::from(out_dir)
+            .join(format!("COLD_STORAGE_KEY_CONTAINER_KEEP_AIRGAPPED_{safe_name}.enc"));
+
+        let mut password_utf8 = self.password.text.clone().into_bytes();
+        let result = save_modern_container(&keys, ContainerOptions {
+            file_path: &file_path,
+            password_utf8: &mut password_utf8,
+            kdf: KdfParams { t_cost: 3, m_cost_kib: 262_144, p_cost: 1 }, // 256 MiB, as for single keys
+            add_noise_prefix: true,
+        });
+        let count = keys.len();
+        for (_, sk) in keys.iter_mut() { sk.zeroize(); }
+
+        let final_path = result.with_context(|| format!("writing {}", file_path.display()))?;
+        Ok((final_path, count))
+    }
+}
+
+impl Default for CreateKeyContainerScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for CreateKeyContainerScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Create Key Container" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Create Key Container";
+        let explanation_paras = [
+            "Generate several new key pairs and save them together in one encrypted file (Argon2id + XChaCha20-Poly1305), e.g. a complete delegate set shipped as a single artifact.",
+            "Enter one label per key, separated by commas (e.g. delegate-1, delegate-2, delegate-3). Labels are stored inside the encrypted payload; when the container is decrypted you can pick a single key by its label.",
+        ];
+
+        // TOP sizing
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 6 fields + spacer + buttons
+        let middle_rows: u16 = 8 + 1;
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Container Name", &self.name, self.field_index == 0),
+            field_line_text("Key Labels", &self.labels, self.field_index == 1),
+            field_line_password("Password For Output File", &self.password, self.field_index == 2, self.show_password || self.reveal.shows(2, self.field_index)),
+            field_line_password("Confirm Password", &self.confirm, self.field_index == 3, self.show_password || self.reveal.shows(3, self.field_index)),
+            show_password_line(self.show_password, self.field_index == 4),
+            field_line_text("Output Directory", &self.out_dir, self.field_index == 5),
+            Line::from(""),
+            Self::buttons_line(self.field_index == 6, self.field_index == 7),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        // Reveal only the focused secret, and only briefly
+        if is_reveal_key(&k) {
+            if matches!(self.field_index, 2 | 3) { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 7; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 8;
+            }
+
+            // Enter on Create
+            KeyCode::Enter if self.field_index == 6 => {
+                return Ok(Transition::Push(Box::new(match self.create_container() {
+                    Ok((path, count)) => ConfirmOkScreen::with_lines(vec![
+                        format!("Created {count} key pairs and saved them in this encrypted container:"),
+                        "".to_string(),
+                        path.display().to_string(),
+                    ])
+                    .with_after_ok(AfterOk::PopToMainMenu),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+                })));
+            }
+
+            // Enter on Back
+            KeyCode::Enter if self.field_index == 7 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Toggle Show Password
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 4 => {
+                self.show_password = !self.show_password;
+            }
+
+            // Text cursor/editing
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -16,9 +16,13 @@ use std::path::PathBuf;
 use crate::app::{AppCtx, ScreenWidget, Transition};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
-use crate::ui::common_nav::esc_to_back;
-use crate::ui::components::{TextField, field_line_text};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{TextField, field_line_text, kdf_progress_lines, RevealBriefly, field_line_password, show_password_line};
+use crate::crypto::kdf_progress::{estimate, KdfJob};
+use crate::crypto::factors::{fido2_enroll, mix_factors, read_keyfile, KeyFactors};
+use crate::crypto::modern::KdfPreset;
 use crate::defaults::Defaults;
+use zeroize::Zeroize;
 
 // Commands
 use crate::commands::keygen;
@@ -26,43 +30,140 @@ use crate::commands::key_save::{emit_encrypted_one_modern, emit_encrypted_one_pg
 
 // Generic OK-only modal (success & errors)
 use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 10,
+        title: "Create Key Pair",
+        description: "Generate a secp256k1 key pair and export it encrypted.",
+        open: |_| Transition::Push(Box::new(CreateKeyPairScreen::new())),
+    }
+}
 
 const CURSOR_BLOCK: &str = "█";
 
+/// Where the private keys come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum KeySource {
+    #[default]
+    Random,
+    /// BIP-39 mnemonic, derived at the BIP-44 Ethereum path.
+    Bip39Ethereum,
+    /// BIP-39 mnemonic, derived at the NIP-06 Nostr path.
+    Bip39Nostr,
+}
+
+impl KeySource {
+    fn label(self) -> &'static str {
+        match self {
+            KeySource::Random => "Random Key Pair",
+            KeySource::Bip39Ethereum => "BIP-39 Mnemonic, Ethereum Path",
+            KeySource::Bip39Nostr => "BIP-39 Mnemonic, Nostr Path (NIP-06)",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            KeySource::Random => KeySource::Bip39Ethereum,
+            KeySource::Bip39Ethereum => KeySource::Bip39Nostr,
+            KeySource::Bip39Nostr => KeySource::Random,
+        }
+    }
+
+    fn default_path(self) -> &'static str {
+        match self {
+            KeySource::Bip39Nostr => keygen::NOSTR_PATH,
+            _ => keygen::ETHEREUM_PATH,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct CreateKeyPairScreen {
     // Focusable indices:
-    // 0 nickname (text), 1 password (text), 2 confirm (text),
-    // 3 show password (toggle), 4 out dir (text), 5 enc method (toggle),
-    // 6 hot_for_signing (toggle), 7 spacer, 8 submit (button), 9 cancel (button)
+    // 0 nickname (text), 1 key source (toggle), 2 mnemonic (text), 3 derivation path (text),
+    // 4 key count (text) -- 2..=4 BIP-39 only,
+    // 5 password (text), 6 confirm (text),
+    // 7 show password (toggle), 8 out dir (text), 9 enc method (toggle),
+    // 10 KDF preset (toggle), 11 keyfile (text), 12 FIDO2 security key (toggle) -- 10..=12 modern only,
+    // 13 hot_for_signing (toggle), 14 spacer, 15 submit (button), 16 cancel (button)
     field_index: usize,
     nickname: TextField,
+    key_source: KeySource,
+    mnemonic: TextField,    // existing phrase to derive from (empty = generate a new one)
+    derivation_path: TextField,
+    key_count: TextField,
     password: TextField,
     confirm: TextField,
     out_dir: TextField,
     format_modern: bool,    // true = Argon2id + XChaCha20-Poly1305, false = OpenPGP
+    kdf_preset: KdfPreset,  // Argon2id costs written into the modern header
+    keyfile: TextField,     // optional keyfile mixed into the password (modern)
+    use_fido2: bool,        // mix in a new FIDO2 hmac-secret credential (modern)
     show_password: bool,    // show/hide password fields
+    reveal: RevealBriefly,  // Ctrl+R: unmask the focused secret for 5s
     hot_for_signing: bool,  // NEW: “Mark As Hot Key For Signing”, default false (No)
+    /// Modern-format save running on a worker thread; the form is locked until it returns.
+    saving: Option<KdfJob<Vec<PathBuf>>>,
+    /// Mnemonic generated for the save in progress, shown once it is done.
+    new_phrase: Option<String>,
 }
 
 impl CreateKeyPairScreen {
     pub fn new() -> Self {
         let mut s = Self::default();
         s.out_dir = TextField::with(Defaults::CREATE_KEYPAIR_OUT_DIR);
+        s.derivation_path = TextField::with(keygen::ETHEREUM_PATH);
+        s.key_count = TextField::with("1");
         s.format_modern = true;
         s.show_password = false;
         s.hot_for_signing = false; // default “No”
         s
     }
 
-    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 1 | 2 | 4) }
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 2 | 3 | 4 | 5 | 6 | 8 | 11) }
+
+    fn bip39(&self) -> bool { self.key_source != KeySource::Random }
+
+    fn saved(final_paths: Vec<PathBuf>, new_phrase: Option<String>) -> Transition {
+        // SUCCESS: show a single OK modal with header, blank line, and the REAL final paths.
+        let header = if final_paths.len() == 1 {
+            "Created and saved key pair in this symmetrically encrypted file:".to_string()
+        } else {
+            format!("Created and saved {} key pairs in these symmetrically encrypted files:", final_paths.len())
+        };
+        let mut lines = vec![header, "".to_string()];
+        lines.extend(final_paths.iter().map(|p| p.display().to_string()));
+        if let Some(mut phrase) = new_phrase {
+            lines.push("".to_string());
+            lines.push("Write down this recovery phrase now. It is not saved anywhere:".to_string());
+            let words: Vec<&str> = phrase.split(' ').collect();
+            for (row, chunk) in words.chunks(6).enumerate() {
+                let numbered: Vec<String> = chunk.iter().enumerate()
+                    .map(|(j, w)| format!("{:>2}. {w:<8}", row * 6 + j + 1))
+                    .collect();
+                // Same width on every row, so the centered rows line up
+                lines.push(numbered.join("  "));
+            }
+            phrase.zeroize();
+        }
+        Transition::Push(Box::new(
+            ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::PopToMainMenu)
+        ))
+    }
 
     fn tf_mut(&mut self, idx: usize) -> &mut TextField {
         match idx {
             0 => &mut self.nickname,
-            1 => &mut self.password,
-            2 => &mut self.confirm,
-            4 => &mut self.out_dir,
+            2 => &mut self.mnemonic,
+            3 => &mut self.derivation_path,
+            4 => &mut self.key_count,
+            5 => &mut self.password,
+            6 => &mut self.confirm,
+            8 => &mut self.out_dir,
+            11 => &mut self.keyfile,
             _ => unreachable!("tf_mut called on non-text field"),
         }
     }
@@ -70,44 +171,98 @@ impl CreateKeyPairScreen {
     fn tf_ref(&self, idx: usize) -> &TextField {
         match idx {
             0 => &self.nickname,
-            1 => &self.password,
-            2 => &self.confirm,
-            4 => &self.out_dir,
+            2 => &self.mnemonic,
+            3 => &self.derivation_path,
+            4 => &self.key_count,
+            5 => &self.password,
+            6 => &self.confirm,
+            8 => &self.out_dir,
+            11 => &self.keyfile,
             _ => unreachable!("tf_ref called on non-text field"),
         }
     }
 
-    // Password field that visually matches field_line_text (yellow label and SAME cursor behavior/color).
-    // Convert the cursor from char index -> byte index for the temporary TextField to avoid UTF-8 boundary panics.
-    fn field_line_password(label: &str, tf: &TextField, selected: bool, show: bool) -> Line<'static> {
-        let render = if show { tf.text.clone() } else { "•".repeat(tf.text.chars().count()) };
+    fn encryption_method_line(&self, selected: bool) -> Line<'static> {
+        let label_span = Span::styled("Encryption Method: ", Style::default().fg(Color::Yellow));
+        let val = if self.format_modern { "Argon2id + XChaCha20-Poly1305" } else { "OpenPGP" };
+        let val_style = if selected {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Line::from(vec![label_span, Span::styled(val.to_string(), val_style)])
+    }
 
-        let mut tmp = TextField::with(&render);
-        let cursor_chars = tf.cursor.min(render.chars().count());
-        let cursor_bytes = if cursor_chars == 0 {
-            0
+    fn key_source_line(&self, selected: bool) -> Line<'static> {
+        let label_span = Span::styled("Key Source: ", Style::default().fg(Color::Yellow));
+        let val_style = if selected {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
-            render.char_indices().nth(cursor_chars).map(|(i, _)| i).unwrap_or_else(|| render.len())
+            Style::default().fg(Color::White)
         };
-        tmp.cursor = cursor_bytes;
+        Line::from(vec![label_span, Span::styled(self.key_source.label().to_string(), val_style)])
+    }
 
-        field_line_text(label, &tmp, selected)
+    // Mnemonic, path and count; a random key pair has none of them
+    fn bip39_lines(&self) -> [Line<'static>; 3] {
+        if !self.bip39() {
+            let na = |label: &str| Line::from(vec![
+                Span::styled(format!("{label}: "), Style::default().fg(Color::Yellow)),
+                Span::styled("n/a (random key pair)".to_string(), Style::default().fg(Color::DarkGray)),
+            ]);
+            return [na("Existing Mnemonic (Optional)"), na("Derivation Path"), na("Number Of Keys")];
+        }
+        [
+            field_line_password("Existing Mnemonic (Optional)", self.tf_ref(2), self.field_index == 2, self.show_password || self.reveal.shows(2, self.field_index)),
+            field_line_text("Derivation Path", self.tf_ref(3), self.field_index == 3),
+            field_line_text("Number Of Keys", self.tf_ref(4), self.field_index == 4),
+        ]
     }
 
-    fn encryption_method_line(&self, selected: bool) -> Line<'static> {
-        let label_span = Span::styled("Encryption Method: ", Style::default().fg(Color::Yellow));
-        let val = if self.format_modern { "Argon2id + XChaCha20-Poly1305" } else { "OpenPGP" };
+    // KDF preset toggle (with how long an unlock takes here) plus a dim line saying what it is for
+    fn kdf_preset_lines(&self, selected: bool) -> [Line<'static>; 2] {
+        let label_span = Span::styled("KDF Preset: ", Style::default().fg(Color::Yellow));
+        if !self.format_modern {
+            return [
+                Line::from(vec![label_span, Span::styled("n/a (OpenPGP)".to_string(), Style::default().fg(Color::DarkGray))]),
+                Line::from(Span::styled("OpenPGP files use the OpenPGP library's own password hashing.", Style::default().fg(Color::DarkGray))),
+            ];
+        }
+        let kdf = self.kdf_preset.params();
+        let secs = estimate(kdf.m_cost_kib, kdf.t_cost).as_secs_f64();
+        let unlock = if secs < 1.0 { "under 1 s".to_string() } else { format!("about {secs:.1} s") };
+        let val = format!(
+            "{} ({} MiB, {} passes; unlock {unlock} on this machine)",
+            self.kdf_preset.name(), kdf.m_cost_kib / 1024, kdf.t_cost,
+        );
         let val_style = if selected {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::White)
         };
-        Line::from(vec![label_span, Span::styled(val.to_string(), val_style)])
+        [
+            Line::from(vec![label_span, Span::styled(val, val_style)]),
+            Line::from(Span::styled(self.kdf_preset.description(), Style::default().fg(Color::DarkGray))),
+        ]
     }
 
-    fn show_password_line(&self, selected: bool) -> Line<'static> {
-        let label_span = Span::styled("Show Password: ", Style::default().fg(Color::Yellow));
-        let val = if self.show_password { "On" } else { "Off" };
+    // Keyfile path; OpenPGP files cannot take one
+    fn keyfile_line(&self, selected: bool) -> Line<'static> {
+        if !self.format_modern {
+            return Line::from(vec![
+                Span::styled("Keyfile (Optional): ", Style::default().fg(Color::Yellow)),
+                Span::styled("n/a (OpenPGP)".to_string(), Style::default().fg(Color::DarkGray)),
+            ]);
+        }
+        field_line_text("Keyfile (Optional)", self.tf_ref(11), selected)
+    }
+
+    fn fido2_line(&self, selected: bool) -> Line<'static> {
+        let label_span = Span::styled("FIDO2 Security Key: ", Style::default().fg(Color::Yellow));
+        if !self.format_modern {
+            return Line::from(vec![label_span, Span::styled("n/a (OpenPGP)".to_string(), Style::default().fg(Color::DarkGray))]);
+        }
+        let val = if self.use_fido2 { "Yes (touch it twice when it blinks)" } else { "No" };
         let val_style = if selected {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
@@ -141,6 +296,8 @@ impl CreateKeyPairScreen {
 #[async_trait]
 impl ScreenWidget for CreateKeyPairScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Create Key Pair" }
+    fn shows_secrets(&self) -> bool { true }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Create Key Pair";
@@ -148,6 +305,9 @@ impl ScreenWidget for CreateKeyPairScreen {
             "Generate a new offline Inkan key pair and save it as an encrypted file.",
             "Fill in the fields below. Password must be entered twice. Choose the output directory.",
             "You can choose between Argon2id + XChaCha20-Poly1305 and OpenPGP encryption.",
+            "For Argon2id, the KDF preset sets how much memory and time each password guess costs, and so how long every unlock of the file takes. The estimate is measured on this machine.",
+            "A keyfile (at least 32 random bytes, e.g. on a USB stick) and/or a FIDO2 security key can be required in addition to the password, or instead of it. Without them the file cannot be decrypted.",
+            "With a BIP-39 key source the keys are derived from a mnemonic: a new 24-word one (shown once, after saving) or the one you enter. {i} in the Derivation Path is the key's index, counting from 0; each key gets its own file and the path is recorded in it.",
         ];
 
         // === TOP BOX ===
@@ -160,8 +320,8 @@ impl ScreenWidget for CreateKeyPairScreen {
 
         let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
 
-        // Middle: now we have 10 focusable positions (0..=9) plus spacer line
-        let middle_rows: u16 = 10 + 1;
+        // Middle: 17 positions (0..=16, spacer included), the preset description and the top spacer
+        let middle_rows: u16 = 17 + 2;
         let middle_needed = 2 + 2 + middle_rows;
 
         let footer_height = 3;
@@ -205,14 +365,25 @@ impl ScreenWidget for CreateKeyPairScreen {
 
         lines.push(Line::from("")); // empty line above first field
         lines.push(field_line_text("Key Pair Name", self.tf_ref(0), self.field_index == 0));
-        lines.push(Self::field_line_password("Password For Output File", self.tf_ref(1), self.field_index == 1, self.show_password));
-        lines.push(Self::field_line_password("Confirm Password", self.tf_ref(2), self.field_index == 2, self.show_password));
-        lines.push(self.show_password_line(self.field_index == 3)); // directly under Confirm Password
-        lines.push(field_line_text("Output Directory", self.tf_ref(4), self.field_index == 4)); // Output Dir at index 4
-        lines.push(self.encryption_method_line(self.field_index == 5));
-        lines.push(self.hot_for_signing_line(self.field_index == 6)); // NEW toggle line
-        lines.push(Line::from("")); // spacer at index 7
-        lines.push(Self::buttons_line(self.field_index == 8, self.field_index == 9)); // Submit / Cancel
+        lines.push(self.key_source_line(self.field_index == 1));
+        lines.extend(self.bip39_lines());
+        lines.push(field_line_password("Password For Output File", self.tf_ref(5), self.field_index == 5, self.show_password || self.reveal.shows(5, self.field_index)));
+        lines.push(field_line_password("Confirm Password", self.tf_ref(6), self.field_index == 6, self.show_password || self.reveal.shows(6, self.field_index)));
+        lines.push(show_password_line(self.show_password, self.field_index == 7)); // directly under Confirm Password
+        lines.push(field_line_text("Output Directory", self.tf_ref(8), self.field_index == 8)); // Output Dir at index 8
+        lines.push(self.encryption_method_line(self.field_index == 9));
+        lines.extend(self.kdf_preset_lines(self.field_index == 10));
+        lines.push(self.keyfile_line(self.field_index == 11));
+        lines.push(self.fido2_line(self.field_index == 12));
+        lines.push(self.hot_for_signing_line(self.field_index == 13)); // NEW toggle line
+        match &self.saving {
+            // Progress replaces spacer + buttons while the key file is being encrypted
+            Some(job) => lines.extend(kdf_progress_lines(job)),
+            None => {
+                lines.push(Line::from("")); // spacer at index 14
+                lines.push(Self::buttons_line(self.field_index == 15, self.field_index == 16)); // Submit / Cancel
+            }
+        }
 
         let middle_para = Paragraph::new(lines);
         f.render_widget(middle_para, regions.middle_inner);
@@ -222,6 +393,7 @@ impl ScreenWidget for CreateKeyPairScreen {
         let footer_line = Line::from(vec![
             span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
             span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
             span_key("Enter"),   span_text(" Select"), span_sep(),
             span_key("Esc"),     span_text(" Back"), span_sep(),
             span_key("Ctrl+Q"),  span_text(" Quit"),
@@ -229,7 +401,28 @@ impl ScreenWidget for CreateKeyPairScreen {
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
     }
 
+    fn busy(&self) -> Option<String> {
+        self.saving.as_ref().map(|job| format!("{} is still running.", job.label))
+    }
+
+    async fn tick(&mut self, _ctx: &mut AppCtx) -> Result<Transition> {
+        let Some(result) = self.saving.as_ref().and_then(|job| job.poll()) else { return Ok(Transition::Stay) };
+        self.saving = None;
+        let new_phrase = self.new_phrase.take();
+        Ok(match result {
+            Ok(final_paths) => Self::saved(final_paths, new_phrase),
+            Err(e) => Transition::Push(Box::new(
+                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+            )),
+        })
+    }
+
     async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        // The worker thread owns the password and output path until it returns.
+        if self.saving.is_some() {
+            return Ok(Transition::Stay);
+        }
+
         if let Some(t) = esc_to_back(k) {
             return Ok(t); // Esc -> Back
         }
@@ -240,16 +433,26 @@ impl ScreenWidget for CreateKeyPairScreen {
             }
         }
 
-            // helper to skip the spacer index
-            fn next_focus(i: usize) -> usize {
-                let mut n = (i + 1) % 10;
-                if n == 7 { n = (n + 1) % 10; }
+            // helper to skip the spacer index, the BIP-39-only fields for a random key pair,
+            // and the modern-only fields for OpenPGP
+            let skip = |i: usize| {
+                i == 14 || (matches!(i, 2..=4) && !self.bip39()) || (matches!(i, 10..=12) && !self.format_modern)
+            };
+            let next_focus = |i: usize| {
+                let mut n = (i + 1) % 17;
+                while skip(n) { n = (n + 1) % 17; }
                 n
-            }
-            fn prev_focus(i: usize) -> usize {
-                let mut p = if i == 0 { 9 } else { i - 1 };
-                if p == 7 { p = if p == 0 { 9 } else { p - 1 }; }
+            };
+            let prev_focus = |i: usize| {
+                let mut p = if i == 0 { 16 } else { i - 1 };
+                while skip(p) { p = if p == 0 { 16 } else { p - 1 }; }
                 p
+            };
+
+            // Reveal only the focused secret, and only briefly
+            if is_reveal_key(&k) {
+                if matches!(self.field_index, 2 | 5 | 6) { self.reveal.start(self.field_index); }
+                return Ok(Transition::Stay);
             }
 
             match k.code {
@@ -262,7 +465,7 @@ impl ScreenWidget for CreateKeyPairScreen {
                 }
 
             // Enter on buttons
-            KeyCode::Enter if self.field_index == 8 => {
+            KeyCode::Enter if self.field_index == 15 => {
                 // === SUBMIT: create + encrypt + save ===
                 let nickname = self.nickname.text.trim();
                 if nickname.is_empty() {
@@ -280,12 +483,23 @@ impl ScreenWidget for CreateKeyPairScreen {
                             .with_after_ok(AfterOk::Pop)
                     )));
                 }
-                if pwd.is_empty() {
+                // A keyfile or security key can stand in for the password (modern only)
+                let keyfile = self.keyfile.text.trim();
+                let has_factor = self.format_modern && (!keyfile.is_empty() || self.use_fido2);
+                if pwd.is_empty() && !has_factor {
                     return Ok(Transition::Push(Box::new(
-                        ConfirmOkScreen::new("Error: Password cannot be empty.")
+                        ConfirmOkScreen::new("Error: Password cannot be empty (unless a keyfile or FIDO2 security key is used).")
                             .with_after_ok(AfterOk::Pop)
                     )));
                 }
+                let keyfile = (self.format_modern && !keyfile.is_empty()).then(|| PathBuf::from(keyfile));
+                if let Some(path) = keyfile.as_deref() {
+                    if let Err(e) = read_keyfile(path) {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                }
 
                 let out_dir = self.out_dir.text.trim();
                 if out_dir.is_empty() {
@@ -300,15 +514,35 @@ impl ScreenWidget for CreateKeyPairScreen {
                 fs::create_dir_all(&out_dir_path)
                     .with_context(|| format!("creating directory {}", out_dir_path.display()))?;
 
-                // Generate exactly one KeyRecord
-                let rec = {
-                    let v = keygen::generate(1).with_context(|| "generating keypair")?;
-                    v.into_iter().next().ok_or_else(|| anyhow!("internal: expected one key"))?
+                // Generate the KeyRecords: one random pair, or `count` derived from a mnemonic
+                let mut new_phrase = None;
+                let records = if self.bip39() {
+                    let count = match self.key_count.text.trim().parse::<u32>() {
+                        Ok(n) if n >= 1 => n,
+                        _ => {
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::new("Error: Number Of Keys must be a whole number of at least 1.")
+                                    .with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                    };
+                    let entered = !self.mnemonic.text.trim().is_empty();
+                    let mut phrase = if entered { self.mnemonic.text.clone() } else { keygen::new_mnemonic_phrase() };
+                    let derived = keygen::generate_from_mnemonic(&phrase, &self.derivation_path.text, count);
+                    if entered { phrase.zeroize(); } else { new_phrase = Some(phrase); }
+                    match derived {
+                        Ok(records) => records,
+                        Err(e) => {
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                    }
+                } else {
+                    keygen::generate(1).with_context(|| "generating keypair")?
                 };
 
-                    // === Build file path and name template ===
-                    let nick_sanitized = sanitize_filename(nickname);
-
+                    // === Build file paths and names ===
                     // Extension is fixed per encryption type
                     let ext = if self.format_modern { "enc" } else { "pgp" };
 
@@ -319,62 +553,133 @@ impl ScreenWidget for CreateKeyPairScreen {
                         "COLD_STORAGE_PRIVKEY_KEEP_AIRGAPPED"
                     };
 
-                    // Final filename pattern
-                    let base = format!("{}_{}", prefix, nick_sanitized);
-                    let filename = format!("{}.{}", base, ext);
-                    let file_path = out_dir_path.join(filename);
+                    // Several derived keys are told apart by their index in the path
+                    let several = records.len() > 1;
+                    let mut jobs: Vec<(keygen::KeyRecord, String, String)> = Vec::with_capacity(records.len());
+                    for (i, rec) in records.into_iter().enumerate() {
+                        let name = if several { format!("{nickname}_{i}") } else { nickname.to_string() };
+                        let filename = format!("{}_{}.{}", prefix, sanitize_filename(&name), ext);
+                        let file_path = out_dir_path.join(filename);
+                        let out_path = file_path.to_str().ok_or_else(|| anyhow!("invalid output path"))?.to_string();
+                        jobs.push((rec, out_path, name));
+                    }
 
                 // Password bytes (will be zeroized by savers)
                 let mut password_utf8 = pwd.into_bytes();
 
-                // Encrypt & save -> get the ACTUAL final path from the emitters
-                let final_path = if self.format_modern {
-                    let opts = EncryptedSaveOptions {
-                        out_path: file_path.to_str().ok_or_else(|| anyhow!("invalid output path"))?,
-                        nickname,
-                        password_utf8: &mut password_utf8,
-                        argon_t_cost: 3,
-                        argon_m_cost_kib: 262_144, // 256 MiB
-                        argon_p_cost: 1,
-                        add_noise_prefix: true,
+                // Modern: Argon2id takes seconds, so encrypt & save on a worker thread and
+                // report progress from `tick`
+                if self.format_modern {
+                    let kdf = self.kdf_preset.params();
+                    let (t_cost, m_cost_kib) = (kdf.t_cost, kdf.m_cost_kib);
+                    let files = jobs.len() as u32;
+                    let use_fido2 = self.use_fido2;
+                    let what = if files == 1 { "the key file".to_string() } else { format!("{files} key files") };
+                    // Touches take as long as the operator does, so no estimate with a security key
+                    let (label, expected) = if use_fido2 {
+                        (format!("Touch the security key twice, then encrypting {what} (Argon2id, {} MiB, {t_cost} passes)", m_cost_kib / 1024), None)
+                    } else {
+                        (format!("Encrypting {what} (Argon2id, {} MiB, {t_cost} passes)", m_cost_kib / 1024), Some(estimate(m_cost_kib, t_cost) * files))
                     };
-                    emit_encrypted_one_modern(&rec, opts)
-                        .with_context(|| format!("writing {}", file_path.display()))?
-                } else {
-                    emit_encrypted_one_pgp(
-                        &rec,
-                        file_path.to_str().ok_or_else(|| anyhow!("invalid output path"))?,
-                        nickname,
-                        &mut password_utf8
-                    ).with_context(|| format!("writing {}", file_path.display()))?
-                };
+                    self.new_phrase = new_phrase;
+                    self.saving = Some(KdfJob::spawn(
+                        label,
+                        expected,
+                        move || {
+                            let factors = KeyFactors {
+                                keyfile: keyfile.is_some(),
+                                fido2: if use_fido2 { Some(fido2_enroll().inspect_err(|_| password_utf8.zeroize())?) } else { None },
+                            };
+                            if let Err(e) = mix_factors(&mut password_utf8, &factors, keyfile.as_deref()) {
+                                password_utf8.zeroize();
+                                return Err(e);
+                            }
+                            let mut written = Vec::with_capacity(jobs.len());
+                            for (rec, out_path, name) in &jobs {
+                                let mut file_password = password_utf8.clone();
+                                let opts = EncryptedSaveOptions {
+                                    out_path,
+                                    nickname: name,
+                                    password_utf8: &mut file_password,
+                                    argon_t_cost: t_cost,
+                                    argon_m_cost_kib: m_cost_kib,
+                                    argon_p_cost: kdf.p_cost,
+                                    add_noise_prefix: true,
+                                    factors: factors.clone(),
+                                };
+                                let res = emit_encrypted_one_modern(rec, opts).with_context(|| format!("writing {out_path}"));
+                                file_password.zeroize();
+                                match res {
+                                    Ok(path) => written.push(path),
+                                    Err(e) => {
+                                        password_utf8.zeroize();
+                                        return Err(e);
+                                    }
+                                }
+                            }
+                            password_utf8.zeroize();
+                            Ok(written)
+                        },
+                    ));
+                    return Ok(Transition::Stay);
+                }
 
-                // SUCCESS: show a single OK modal with header, blank line, and the REAL final path.
-                let lines = vec![
-                    "Created and saved key pair in this symmetrically encrypted file:".to_string(),
-                    "".to_string(),
-                    final_path.display().to_string(),
-                ];
-                return Ok(Transition::Push(Box::new(
-                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::PopToMainMenu)
-                )));
+                // Encrypt & save -> get the ACTUAL final paths from the emitter
+                let mut final_paths = Vec::with_capacity(jobs.len());
+                for (rec, out_path, name) in &jobs {
+                    let mut file_password = password_utf8.clone();
+                    let res = emit_encrypted_one_pgp(rec, out_path, name, &mut file_password)
+                        .with_context(|| format!("writing {out_path}"));
+                    file_password.zeroize();
+                    match res {
+                        Ok(path) => final_paths.push(path),
+                        Err(e) => {
+                            password_utf8.zeroize();
+                            return Err(e);
+                        }
+                    }
+                }
+                password_utf8.zeroize();
+                return Ok(Self::saved(final_paths, new_phrase));
             }
-            KeyCode::Enter if self.field_index == 9 => {
+            KeyCode::Enter if self.field_index == 16 => {
                 return Ok(Transition::Pop);
             }
 
-            // Toggle Encryption Method (index 5)
-            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 5 => {
+            // Cycle Key Source (index 1); the path follows unless it was edited
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 1 => {
+                let old = self.key_source;
+                self.key_source = old.next();
+                if self.derivation_path.text == old.default_path() {
+                    self.derivation_path = TextField::with(self.key_source.default_path());
+                }
+            }
+
+            // Toggle Encryption Method (index 9)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 9 => {
                 self.format_modern = !self.format_modern;
             }
 
-            // Toggle Show Password (index 3)
-            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 3 => {
+            // Toggle Show Password (index 7)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 7 => {
                 self.show_password = !self.show_password;
             }
 
-            // Toggle Hot For Signing (index 6) — Yes/No
-            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 6 => {
+            // Cycle KDF Preset (index 10)
+            KeyCode::Char(' ') | KeyCode::Right if self.field_index == 10 => {
+                self.kdf_preset = self.kdf_preset.cycle(true);
+            }
+            KeyCode::Left if self.field_index == 10 => {
+                self.kdf_preset = self.kdf_preset.cycle(false);
+            }
+
+            // Toggle FIDO2 Security Key (index 12)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 12 => {
+                self.use_fido2 = !self.use_fido2;
+            }
+
+            // Toggle Hot For Signing (index 13) — Yes/No
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 13 => {
                 self.hot_for_signing = !self.hot_for_signing;
             }
 
@@ -410,7 +715,7 @@ fn split_at_char(s: &str, idx: usize) -> (&str, &str) {
 }
 
 // Simple filesystem-safe name (keeps ASCII letters, numbers, '-', '_', '.')
-fn sanitize_filename(name: &str) -> String {
+pub(crate) fn sanitize_filename(name: &str) -> String {
     let mut out = String::with_capacity(name.len());
     for ch in name.chars() {
         if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || ch == '.' {
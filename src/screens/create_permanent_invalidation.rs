@@ -9,69 +9,159 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 use textwrap::wrap;
+use ethers_core::utils::to_checksum;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form, DRAFT_RESTORED_NOTE};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
-use crate::ui::common_nav::esc_to_back;
-use crate::ui::components::{TextField, field_line_text};
+use crate::ui::common_nav::{esc_to_back, is_load_key_file_key};
+use crate::ui::key_file_fields::KeyFileFields;
+use crate::ui::components::{TextField, field_line_text, funding_lines, info_line_text, warning_banner_lines};
+use crate::ui::out_dir_prompt::{OutDirChoice, OutDirPrompt};
+use crate::ui::typed_confirm::{TypedConfirm, TypedConfirmChoice};
+use crate::ui::prefill_summary::{PrefillField, PrefillSummary};
+use crate::units::{PayloadNonce, GasLimit, Nonce, Wei};
+use crate::validation::validate_gas_fields;
 use crate::defaults::Defaults;
+use crate::settings::{GasLimits, Settings};
 
 // Generic OK-only modal
-use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::{ConfirmOkScreen, AfterOk, LoadSigningKeyScreen};
 
 // ABI / processor / types / writer
 use crate::abi::load_abi;
-use crate::process::{process_item, BatchOpts};
+use crate::process::{address_from_privkey_input, process_item, retired_keys, BatchOpts, RetiredKey};
+use crate::key_usage::{record_signed, KeyUsage};
+use crate::usage_stats::Written;
+use crate::signing::SignerCache;
+use crate::vfs::Fs;
+use crate::event_stream::Event;
+use crate::lint;
+use crate::simulate;
 use crate::types::Item;
 use crate::write_signed_transactions_to_file::{
     write_single_signed_transaction,
     build_filename_for_any_tx,
+    signed_file_metadata,
+};
+use crate::filename_template::{
+    base_vars, insert_key_vars, insert_label, render_template_preview,
+    x_coord_from_privkey_input, FilenameTemplates, KeyRole,
 };
 
 // Load-from-file flow (directory picker) — invalidation version
-use crate::screens::ChoosePermanentInvalidationInfoDirScreen;
+use crate::screens::{ChoosePermanentInvalidationInfoDirScreen, SelectSignedFileScreen};
+use crate::commands::batch::{BatchRun, BATCH_ERRORS_FILE};
+use crate::commands::emergency_revoke::FoundDelegation;
+use crate::commands::invalidation_impact::{revocation_items, InvalidationImpact};
+use crate::signing::open_signer;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Transactions),
+        order: 40,
+        title: "Create Permanent Invalidation",
+        description: "Sign one permanent invalidation from manual input or an info file.",
+        open: |ctx| Transition::Push(Box::new(CreatePermanentInvalidationScreen::resume(ctx))),
+    }
+}
+
+/// Info-file keys this form reads, for the summary shown after Load From File.
+const PREFILL_FIELDS: &[PrefillField] = &[
+    PrefillField { key: "PRIVKEY_TO_BE_INVALIDATED", label: "PrivKey To Be Invalidated", secret: true },
+    PrefillField { key: "NONCE", label: "Transaction Nonce", secret: false },
+    PrefillField { key: "INVALIDATION_PAYLOAD_NONCE", label: "Payload Nonce", secret: false },
+    PrefillField { key: "GAS_LIMIT", label: "Gas limit", secret: false },
+    PrefillField { key: "MAX_FEE_PER_GAS", label: "Maximum Fee Per Gas", secret: false },
+    PrefillField { key: "MAX_PRIORITY_FEE_PER_GAS", label: "Maximum Priority Fee Per Gas", secret: false },
+    PrefillField { key: "LABEL", label: "Notes", secret: false },
+    PrefillField { key: "REVOKE_ISSUED_DELEGATIONS", label: "Revoke Its Delegations First", secret: false },
+    PrefillField { key: "OUTPUT_DIRECTORY", label: "Output Directory", secret: false },
+];
+
+/// Private-key fields Ctrl+K can load from an encrypted key file, with their labels.
+const KEY_FILE_FIELDS: &[(usize, &str)] = &[(0, "PrivKey To Be Invalidated")];
 
 pub struct CreatePermanentInvalidationScreen {
-    // 0 privkey_to_be_invalidated, 1 nonce,
-    // 2 gas_limit, 3 max_fee_per_gas, 4 max_priority_fee_per_gas,
-    // 5 out_dir, 6 submit, 7 load_from_file, 8 back
+    // 0 privkey_to_be_invalidated, 1 nonce, 2 payload_nonce,
+    // 3 gas_limit, 4 max_fee_per_gas, 5 max_priority_fee_per_gas,
+    // 6 out_dir, 7 notes, 8 revoke_issued toggle,
+    // 9 submit, 10 load_from_file, 11 clone_signed, 12 back
     field_index: usize,
     privkey_to_be_invalidated: TextField,
     nonce: TextField,
+    /// Fixed `bytes16` payload nonce (see `units::PayloadNonce`); empty = random. Not kept in
+    /// drafts, so a fixed nonce is never reused by accident.
+    payload_nonce: TextField,
     gas_limit: TextField,
     max_fee_per_gas: TextField,
     max_priority_fee_per_gas: TextField,
     out_dir: TextField,
+    /// Becomes the item's label (see `types::Item`).
+    notes: TextField,
+    /// Sign revocations of the key's active delegations before the invalidation, in one run.
+    revoke_issued: bool,
+    /// SHA-256 of the info file last loaded via Load From File (recorded in output metadata).
+    input_sha256: Option<String>,
+    /// Open while an Output Directory from a loaded file awaits confirmation.
+    out_dir_prompt: Option<OutDirPrompt>,
+    /// What the last loaded file set; shown once, before any Output Directory prompt.
+    prefill_summary: Option<PrefillSummary>,
+    /// Retired key the operator was warned about; the next submit with the same key signs anyway.
+    retired_ack: Option<RetiredKey>,
+    /// Open while INVALIDATE has to be typed before signing.
+    invalidate_confirm: Option<TypedConfirm>,
+    /// Delegations the open confirmation said would be revoked first.
+    queued: Vec<FoundDelegation>,
+    /// Reopened with the values of the last visit (see `resume`).
+    restored: bool,
+    /// Private-key fields loaded from key files, shown by source instead of value.
+    key_files: KeyFileFields,
 }
 
 impl CreatePermanentInvalidationScreen {
-    pub fn new() -> Self {
+    pub fn new(gas_limits: &GasLimits) -> Self {
         Self {
             field_index: 0,
             privkey_to_be_invalidated: TextField::with(""),
             nonce: TextField::with(""),
-            gas_limit: TextField::with(Defaults::GAS_LIMIT),
+            payload_nonce: TextField::with(""),
+            gas_limit: TextField::with(gas_limits.default_for("createPermanentInvalidationEvent")),
             max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
             max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
             out_dir: TextField::with(Defaults::CREATE_REVOCATION_OUT_DIR),
+            notes: TextField::with(""),
+            revoke_issued: false,
+            input_sha256: None,
+            out_dir_prompt: None,
+            prefill_summary: None,
+            retired_ack: None,
+            invalidate_confirm: None,
+            queued: Vec::new(),
+            restored: false,
+            key_files: KeyFileFields::default(),
         }
     }
 
     fn is_text(&self) -> bool {
-        matches!(self.field_index, 0 | 1 | 2 | 3 | 4 | 5)
+        matches!(self.field_index, 0..=7)
     }
 
     fn tf_ref(&self, idx: usize) -> &TextField {
         match idx {
             0 => &self.privkey_to_be_invalidated,
             1 => &self.nonce,
-            2 => &self.gas_limit,
-            3 => &self.max_fee_per_gas,
-            4 => &self.max_priority_fee_per_gas,
-            5 => &self.out_dir,
+            2 => &self.payload_nonce,
+            3 => &self.gas_limit,
+            4 => &self.max_fee_per_gas,
+            5 => &self.max_priority_fee_per_gas,
+            6 => &self.out_dir,
+            7 => &self.notes,
             _ => unreachable!("tf_ref called on non-text field"),
         }
     }
@@ -80,10 +170,12 @@ impl CreatePermanentInvalidationScreen {
         match idx {
             0 => &mut self.privkey_to_be_invalidated,
             1 => &mut self.nonce,
-            2 => &mut self.gas_limit,
-            3 => &mut self.max_fee_per_gas,
-            4 => &mut self.max_priority_fee_per_gas,
-            5 => &mut self.out_dir,
+            2 => &mut self.payload_nonce,
+            3 => &mut self.gas_limit,
+            4 => &mut self.max_fee_per_gas,
+            5 => &mut self.max_priority_fee_per_gas,
+            6 => &mut self.out_dir,
+            7 => &mut self.notes,
             _ => unreachable!("tf_mut called on non-text field"),
         }
     }
@@ -94,37 +186,162 @@ impl CreatePermanentInvalidationScreen {
         tf.end();
     }
 
-    // Apply pending prefill (consumes ctx.pending_invalidation_prefill if present)
+    // Apply pending prefill (consumed through AppEvent::PrefillTaken if present)
     fn apply_prefill_if_any(&mut self, ctx: &mut AppCtx) {
-        if let Some(prefill) = ctx.pending_permanent_invalidation_prefill.take() {
-            if let Some(v) = prefill.map.get("PRIVKEY_TO_BE_INVALIDATED") {
-                Self::set_textfield(&mut self.privkey_to_be_invalidated, v);
-            }
-            if let Some(v) = prefill.map.get("NONCE") {
-                Self::set_textfield(&mut self.nonce, v);
-            }
-            if let Some(v) = prefill.map.get("GAS_LIMIT") {
-                Self::set_textfield(&mut self.gas_limit, v);
+        if let Some(key) = ctx.take_signing_key(self.crumb(), Form::PermanentInvalidation) {
+            *self.tf_mut(key.field) = self.key_files.fill(&key);
+        }
+        if let Some(prefill) = ctx.take_prefill(self.crumb(), Form::PermanentInvalidation) {
+            self.input_sha256 = prefill.source_sha256.clone();
+            self.prefill_summary = Some(PrefillSummary::new(PREFILL_FIELDS, &prefill));
+            self.apply_values(&prefill.map);
+            if let Some(v) = prefill.map.get("OUTPUT_DIRECTORY") {
+                // Never taken silently: the operator confirms the resolved path first.
+                self.out_dir_prompt = OutDirPrompt::for_prefill(v, &self.out_dir.text, ctx);
             }
-            if let Some(v) = prefill.map.get("MAX_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_fee_per_gas, v);
+        }
+    }
+
+    /// Set the fields named in `map` (info-file keys), except the Output Directory.
+    fn apply_values(&mut self, map: &HashMap<String, String>) {
+        if let Some(v) = map.get("PRIVKEY_TO_BE_INVALIDATED") {
+            Self::set_textfield(&mut self.privkey_to_be_invalidated, v);
+        }
+        if let Some(v) = map.get("NONCE") {
+            Self::set_textfield(&mut self.nonce, v);
+        }
+        if let Some(v) = map.get("INVALIDATION_PAYLOAD_NONCE") {
+            Self::set_textfield(&mut self.payload_nonce, v);
+        }
+        if let Some(v) = map.get("GAS_LIMIT") {
+            Self::set_textfield(&mut self.gas_limit, v);
+        }
+        if let Some(v) = map.get("MAX_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("MAX_PRIORITY_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("LABEL") {
+            Self::set_textfield(&mut self.notes, v);
+        }
+        // Boolean (treat anything else as false)
+        if let Some(v) = map.get("REVOKE_ISSUED_DELEGATIONS") {
+            let vv = v.to_ascii_lowercase();
+            self.revoke_issued = matches!(
+                vv.as_str(),
+                "true" | "1" | "yes" | "on" | "y" | "t"
+            );
+        }
+    }
+
+    /// A new form holding the values it was last left with, if any.
+    pub fn resume(ctx: &mut AppCtx) -> Self {
+        let mut screen = Self::new(&ctx.settings.gas_limits);
+        if let Some(draft) = ctx.take_draft(screen.crumb(), Form::PermanentInvalidation) {
+            screen.apply_values(&draft);
+            if let Some(dir) = draft.get("OUTPUT_DIRECTORY") {
+                Self::set_textfield(&mut screen.out_dir, dir);
             }
-            if let Some(v) = prefill.map.get("MAX_PRIORITY_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
+            screen.restored = true;
+        }
+        screen
+    }
+
+    /// Keep the non-secret values for the next visit (private keys are left out).
+    fn leave(&self, ctx: &mut AppCtx) {
+        let mut map = HashMap::new();
+        for (key, tf) in [
+            ("NONCE", &self.nonce),
+            ("GAS_LIMIT", &self.gas_limit),
+            ("MAX_FEE_PER_GAS", &self.max_fee_per_gas),
+            ("MAX_PRIORITY_FEE_PER_GAS", &self.max_priority_fee_per_gas),
+            ("LABEL", &self.notes),
+            ("OUTPUT_DIRECTORY", &self.out_dir),
+        ] {
+            map.insert(key.to_string(), tf.text.clone());
+        }
+        map.insert("REVOKE_ISSUED_DELEGATIONS".to_string(), self.revoke_issued.to_string());
+        ctx.dispatch(self.crumb(), AppEvent::FormLeft { form: Form::PermanentInvalidation, draft: map });
+    }
+
+    /// Sign and write the invalidation, once INVALIDATE was typed.
+    async fn submit(&mut self, ctx: &mut AppCtx) -> Transition {
+        // Enforce caps first
+        let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+            Ok(gas) => gas,
+            Err(e) => return Transition::Push(Box::new(
+                ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
+            )),
+        };
+
+        // Revocations first: a run directory holding them and the invalidation
+        let signed = if self.queued.is_empty() {
+            Ok(None)
+        } else {
+            self.sign_with_revocations(ctx, gas).await.map(Some)
+        };
+        match signed {
+            Ok(Some((dir, recorded))) => {
+                let mut lines = vec![
+                    format!("Signed {} revocation(s) and the permanent invalidation into run directory:", self.queued.len()),
+                    "".to_string(),
+                    dir.display().to_string(),
+                    "".to_string(),
+                    "Broadcast them in nonce order: the key cannot revoke anything once invalidated.".to_string(),
+                ];
+                lines.extend(recorded);
+                return Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                ));
             }
-            if let Some(v) = prefill.map.get("OUTPUT_DIRECTORY") {
-                Self::set_textfield(&mut self.out_dir, v);
+            Ok(None) => {}
+            Err(e) => return self.signing_failed(e),
+        }
+
+        // Create, sign, and write the single-entry JSON
+        match self.create_and_write_invalidation(&*ctx.fs, &ctx.settings, &ctx.key_usage, gas).await {
+            Ok((path, from)) => {
+                ctx.events.emit(Event::file_written(None, &path));
+                let mut lines = vec![
+                    "Saved signed permanent invalidation transaction:".to_string(),
+                    "".to_string(),
+                    path.display().to_string(),
+                ];
+                let written = Written::One { func: "createPermanentInvalidationEvent", from: &from };
+                lines.extend(record_signed(&mut ctx.key_usage, ctx.profile_key.as_ref(), ctx.settings.usage_stats, written));
+                Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                ))
             }
+            Err(e) => self.signing_failed(e),
+        }
+    }
+
+    /// A retired key is confirmed first (the next submit signs anyway); other errors are shown.
+    fn signing_failed(&mut self, e: anyhow::Error) -> Transition {
+        if let Some(retired) = e.downcast_ref::<RetiredKey>() {
+            let lines = retired.confirm_lines("Create Permanent Invalidation");
+            self.retired_ack = Some(retired.clone());
+            return Transition::Push(Box::new(
+                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+            ));
         }
+        Transition::Push(Box::new(
+            ConfirmOkScreen::new(&format!("Error: {e:#}"))
+                .with_after_ok(AfterOk::Pop)
+        ))
     }
 
-    // < Create Permanent Invalidation >   < Load From File >   < Back >
-    fn buttons_line(submit_selected: bool, load_selected: bool, back_selected: bool) -> Line<'static> {
+    // < Create Permanent Invalidation >   < Load From File >   < Clone Signed File >   < Back >
+    fn buttons_line(submit_selected: bool, load_selected: bool, clone_selected: bool, back_selected: bool) -> Line<'static> {
         let mut spans: Vec<Span<'static>> = Vec::new();
         spans.extend(button_spans("Create Permanent Invalidation", submit_selected));
         spans.push(Span::raw("   "));
         spans.extend(button_spans("Load From File", load_selected));
         spans.push(Span::raw("   "));
+        spans.extend(button_spans("Clone Signed File", clone_selected));
+        spans.push(Span::raw("   "));
         spans.extend(button_spans("Back", back_selected));
         Line::from(spans)
     }
@@ -137,34 +354,44 @@ impl CreatePermanentInvalidationScreen {
         Ok(PathBuf::from(out_dir))
     }
 
-    /// Create, sign, and write a single invalidation tx using process_item() + writer.
-    async fn create_and_write_invalidation(&self) -> Result<PathBuf> {
-        // Validate required secret
-        let pk = self.privkey_to_be_invalidated.text.trim();
-        if pk.is_empty() {
-            anyhow::bail!("PrivKey To Be Invalidated cannot be empty.");
+    /// Live preview of the output filename from the current inputs.
+    fn filename_preview(&self, templates: &FilenameTemplates) -> String {
+        let mut vars = base_vars(
+            "createPermanentInvalidationEvent",
+            self.nonce.text.trim(),
+            &Defaults::CHAIN_ID.to_string(),
+        );
+        if let Some(x) = x_coord_from_privkey_input(&self.privkey_to_be_invalidated.text) {
+            insert_key_vars(&mut vars, KeyRole::Invalidated, &x);
         }
+        insert_label(&mut vars, Some(&self.notes.text));
+        render_template_preview(&templates.permanent_invalidation, &vars)
+    }
 
-        // Parse nonce
-        let nonce_str = self.nonce.text.trim();
-        let nonce: u64 = nonce_str.parse().context("Nonce must be an integer")?;
-
-        // Gas options
-        let opts = BatchOpts {
-            gas_limit: self.gas_limit.text.trim().to_string(),
-            max_fee_per_gas: self.max_fee_per_gas.text.trim().to_string(),
-            max_priority_fee_per_gas: self.max_priority_fee_per_gas.text.trim().to_string(),
-        };
-
-        // ABI
-        let abi = load_abi()?;
+    /// Gas and signing options shared by the single transaction and the run.
+    fn batch_opts(settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> BatchOpts {
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
+        }
+    }
 
-        // Assemble Item for createPermanentInvalidationEvent
-        let item = Item {
+    /// The createPermanentInvalidationEvent item for `pk` at `nonce`.
+    fn invalidation_item(&self, pk: &str, nonce: Nonce, payload_nonce: PayloadNonce) -> Item {
+        Item {
             function_to_call: "createPermanentInvalidationEvent".to_string(),
             nonce: Some(nonce),
             chain_id: Some(Defaults::CHAIN_ID),
             contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+            label: Some(self.notes.text.clone()),
 
             // Type A (unused)
             type_a_privkey_x: None,
@@ -173,6 +400,7 @@ impl CreatePermanentInvalidationScreen {
             type_a_uint_x: None,
             type_a_uint_y: None,
             type_a_boolean: None,
+            type_a_nonce16: None,
 
             // Type B (unused)
             type_b_privkey_x: None,
@@ -180,127 +408,162 @@ impl CreatePermanentInvalidationScreen {
             type_b_pubkey_y: None,
             type_b_uint_x: None,
             type_b_uint_y: None,
+            type_b_nonce16: None,
 
             // Type C
             type_c_privkey_x: Some(pk.to_string()),
-        };
+            type_c_nonce16: payload_nonce.hex(),
+        }
+    }
+
+    /// The required key, the first nonce and the payload nonce.
+    fn key_and_nonce(&self) -> Result<(&str, Nonce, PayloadNonce)> {
+        let pk = self.privkey_to_be_invalidated.text.trim();
+        if pk.is_empty() {
+            anyhow::bail!("PrivKey To Be Invalidated cannot be empty.");
+        }
+        let nonce: Nonce = self.nonce.text.trim().parse().context("Nonce must be an integer")?;
+        let payload_nonce: PayloadNonce = self.payload_nonce.text.parse().context("Payload Nonce")?;
+        Ok((pk, nonce, payload_nonce))
+    }
+
+    /// The active delegations on file from and to the key, and the confirmation lines for them.
+    fn impact_lines(&mut self, fs: &dyn Fs) -> Result<Vec<String>> {
+        let (pk, nonce, _) = self.key_and_nonce()?;
+        let pubkey = open_signer(pk).context("PrivKey To Be Invalidated")?.pubkey_0x04();
+        let impact = InvalidationImpact::find(fs, &pubkey)?;
+        let revocable = impact.revocable(Defaults::CHAIN_ID);
+        self.queued = if self.revoke_issued { revocable.clone() } else { Vec::new() };
+        let mut lines = impact.lines(&self.queued, nonce.0);
+        if !self.revoke_issued && !revocable.is_empty() {
+            lines.push("Set \"Revoke Its Delegations First\" to yes to revoke them in this run.".to_string());
+        }
+        Ok(lines)
+    }
+
+    /// Create, sign, and write a single invalidation tx using process_item() + writer.
+    /// Returns the written file and the transaction sender, for the key usage store.
+    async fn create_and_write_invalidation(&self, fs: &dyn Fs, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<(PathBuf, String)> {
+        let (pk, nonce, payload_nonce) = self.key_and_nonce()?;
+        let mut opts = Self::batch_opts(settings, usage, gas);
+
+        // ABI
+        let abi = load_abi()?;
+
+        let item = self.invalidation_item(pk, nonce, payload_nonce);
+
+        // A retired key already confirmed is signed as is
+        opts.allow_retired_key =
+            self.retired_ack.is_some() && retired_keys(&item, &opts.signers, &opts.retired_keys) == self.retired_ack;
 
         // Build & sign the transaction
         let entry = process_item(&abi, &opts, &item)
             .await
             .context("failed to construct and sign permanent invalidation transaction")?;
+        lint::check_entries(std::slice::from_ref(&entry))?;
+        simulate::check_entries(settings, std::slice::from_ref(&entry))?;
 
-        // Filename per helper: "[X]_invalidation_nonce_[nonce].txt"
-        let filename = build_filename_for_any_tx(&entry.decoded_tx);
+        // Filename from the configured template (default: "invalidate_[X]_nonce_[nonce].txt")
+        let filename = build_filename_for_any_tx(&entry, &settings.filename_templates);
         let mut out_path = self.ensure_out_dir_nonempty()?;
         out_path.push(filename);
-        let written = write_single_signed_transaction(&out_path, &entry, true)
+        let metadata = signed_file_metadata(settings, self.input_sha256.clone(), Some("createPermanentInvalidationEvent"));
+        let written = write_single_signed_transaction(fs, &out_path, &entry, metadata.as_ref(), opts.encoding.encoder())
             .context("failed to write signed transaction file")?;
 
-        Ok(written)
-    }
-
-    fn validate_gas_limit(&self) -> Result<()> {
-        let max_str = Defaults::GAS_LIMIT.trim();
-        let max: u64 = max_str.parse().context("Defaults::GAS_LIMIT must be an integer")?;
-
-        let user_str = self.gas_limit.text.trim();
-        let user: u64 = user_str.parse().context("Gas limit must be an integer")?;
-
-        if user == 0 {
-            anyhow::bail!("Gas limit must be greater than zero.");
-        }
-        if user > max {
-            anyhow::bail!(format!(
-                "Gas limit {} exceeds the maximum allowed {}.",
-                user, max
-            ));
-        }
-        Ok(())
+        Ok((written, entry.decoded_tx.from))
     }
 
-    fn validate_fee_caps(&self) -> Result<()> {
-        // maxFeePerGas cap
-        let max_fee_cap_str = Defaults::MAX_FEE_PER_GAS.trim();
-        let max_fee_cap: u64 = max_fee_cap_str
-            .parse()
-            .context("Defaults::MAX_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_max_fee_str = self.max_fee_per_gas.text.trim();
-        let user_max_fee: u64 = user_max_fee_str
-            .parse()
-            .context("Maximum Fee Per Gas must be an integer (wei)")?;
-        if user_max_fee == 0 {
-            anyhow::bail!("Maximum Fee Per Gas must be greater than zero.");
-        }
-        if user_max_fee > max_fee_cap {
-            anyhow::bail!(format!(
-                "Maximum Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_max_fee, max_fee_cap
-            ));
-        }
-
-        // maxPriorityFeePerGas cap
-        let max_prio_cap_str = Defaults::MAX_PRIORITY_FEE_PER_GAS.trim();
-        let max_prio_cap: u64 = max_prio_cap_str
-            .parse()
-            .context("Defaults::MAX_PRIORITY_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_prio_str = self.max_priority_fee_per_gas.text.trim();
-        let user_prio: u64 = user_prio_str
-            .parse()
-            .context("Maximum Priority Fee Per Gas must be an integer (wei)")?;
-
-        // priority fee can be zero, but not above cap
-        if user_prio > max_prio_cap {
-            anyhow::bail!(format!(
-                "Maximum Priority Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_prio, max_prio_cap
-            ));
+    /// Sign the queued revocations, then the invalidation, into one run directory as a batch
+    /// run does. Returns the run directory and any key usage warnings; on failure the signed
+    /// part is saved as a partial run.
+    async fn sign_with_revocations(&self, ctx: &mut AppCtx, gas: (GasLimit, Wei, Wei)) -> Result<(PathBuf, Vec<String>)> {
+        let (pk, nonce, payload_nonce) = self.key_and_nonce()?;
+        let out_dir = self.ensure_out_dir_nonempty()?;
+        let mut items = revocation_items(pk, &self.queued, nonce.0)?;
+        items.push(self.invalidation_item(pk, Nonce(nonce.0 + items.len() as u64), payload_nonce));
+
+        // The run is named after its plan, as Emergency Revoke's is.
+        let input = items.iter()
+            .map(|it| format!("{} nonce {} {}", it.function_to_call, it.nonce.unwrap_or_default().0, it.type_b_pubkey_y.as_deref().unwrap_or("")))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into_bytes();
+        let opts = Self::batch_opts(&ctx.settings, &ctx.key_usage, gas);
+        let mut run = BatchRun::new(load_abi()?, opts, items, input, out_dir).with_events(ctx.events.clone());
+        if let Some(retired) = run.retired_keys() {
+            if self.retired_ack.as_ref() != Some(&retired) {
+                return Err(retired.into());
+            }
+            run.allow_retired_keys();
         }
 
-        // Sanity: priority <= max fee
-        if user_prio > user_max_fee {
-            anyhow::bail!("Maximum Priority Fee Per Gas cannot exceed Maximum Fee Per Gas.");
+        while !run.is_done() {
+            if let Err(e) = run.step().await {
+                let e = e.context("failed to sign the revocations and invalidation");
+                return Err(match run.write_failed(&*ctx.fs, &ctx.settings, &e) {
+                    Ok(dir) => {
+                        record_signed(&mut ctx.key_usage, ctx.profile_key.as_ref(), ctx.settings.usage_stats, Written::Run(&run));
+                        e.context(format!(
+                            "{} item(s) signed before the failure were saved to {} (see {BATCH_ERRORS_FILE})",
+                            run.signed(), dir.display()
+                        ))
+                    }
+                    Err(save) => e.context(format!("partial output NOT saved: {save:#}")),
+                });
+            }
         }
-
-        Ok(())
+        run.finish(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref())
     }
 }
 
 impl Default for CreatePermanentInvalidationScreen {
-    fn default() -> Self { Self::new() }
+    fn default() -> Self { Self::new(&GasLimits::default()) }
 }
 
 #[async_trait]
 impl ScreenWidget for CreatePermanentInvalidationScreen {
     fn apply_prefill(&mut self, ctx: &mut AppCtx) {
-        self.apply_prefill_if_any(ctx); // consumes ctx.pending_invalidation_prefill exactly once
+        self.apply_prefill_if_any(ctx); // consumes the pending prefill exactly once (AppEvent::PrefillTaken)
     }
 
     fn title(&self) -> &str { "" }
 
-    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+    fn crumb(&self) -> &str { "Create Permanent Invalidation" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
         let header_text = "Create Permanent Invalidation";
-        let explanation_paras = [
+        let mut explanation_paras = vec![
             "Enter the fields below. The app will create and sign an EIP-1559 transaction",
-            "for createPermanentInvalidationEvent and save a one-element JSON array (pretty-printed)",
-            "to your chosen output directory. The filename will be:",
-            "[invalidatedX]_invalidation_nonce_[nonce].txt",
+            "for createPermanentInvalidationEvent and save it as pretty-printed JSON (entries plus a metadata block)",
+            "to your chosen output directory. The filename follows the permanent invalidation",
+            "template in the settings file; a live preview is shown below the output directory.",
         ];
+        if self.restored {
+            explanation_paras.push(DRAFT_RESTORED_NOTE);
+        }
 
         // === TOP BOX ===
         let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
         let header_lines = wrap(header_text, top_inner_width).len() as u16;
 
         let mut exp_lines = 0usize;
-        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
         let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
 
-        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+        // Unregistered chain/contract pair: a bold banner under the explanation.
+        let banner = ctx.settings.networks
+            .unknown_warning(Defaults::CHAIN_ID, Defaults::CONTRACT_ADDRESS)
+            .map(|w| warning_banner_lines(&w, top_inner_width))
+            .unwrap_or_default();
+        let banner_lines = if banner.is_empty() { 0 } else { banner.len() as u16 + 1 };
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + banner_lines;
 
-        // Middle: 9 focusable positions (0..=8) plus spacer
-        let middle_rows: u16 = 9 + 1;
+        // Middle: 12 rows (fields, toggle, buttons) plus filename preview, funding lines and spacer
+        let middle_rows: u16 = 12 + 4;
         let middle_needed = 2 + 2 + middle_rows;
 
         let footer_height = 3;
@@ -330,6 +593,10 @@ impl ScreenWidget for CreatePermanentInvalidationScreen {
             for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
             if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
         }
+        if !banner.is_empty() {
+            expl_lines.push(Line::from(""));
+            expl_lines.extend(banner);
+        }
         let explanation_para = Paragraph::new(expl_lines)
             .alignment(Alignment::Left)
             .wrap(Wrap { trim: true });
@@ -342,35 +609,53 @@ impl ScreenWidget for CreatePermanentInvalidationScreen {
 
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from("")); // spacer above first field
-        lines.push(field_line_text("PrivKey To Be Invalidated", self.tf_ref(0), self.field_index == 0));
+        lines.push(self.key_files.line("PrivKey To Be Invalidated", 0, self.tf_ref(0), self.field_index == 0));
         lines.push(field_line_text("Transaction Nonce", self.tf_ref(1), self.field_index == 1));
+        lines.push(field_line_text("Payload Nonce (0x + 32 hex digits or UUID; empty = random)", self.tf_ref(2), self.field_index == 2));
 
         // Gas limit (cap label)
-        let gas_label = format!("Gas limit (maximum {} gas)", Defaults::GAS_LIMIT);
-        lines.push(field_line_text(&gas_label, self.tf_ref(2), self.field_index == 2));
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(3), self.field_index == 3));
 
         // Max fee per gas (cap label)
         let mfg_label = format!(
             "Maximum Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_FEE_PER_GAS
+            ctx.settings.gas_caps.max_fee_per_gas()
         );
-        lines.push(field_line_text(&mfg_label, self.tf_ref(3), self.field_index == 3));
+        lines.push(field_line_text(&mfg_label, self.tf_ref(4), self.field_index == 4));
 
         // Max priority fee per gas (cap label)
         let mpfg_label = format!(
             "Maximum Priority Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_PRIORITY_FEE_PER_GAS
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
         );
-        lines.push(field_line_text(&mpfg_label, self.tf_ref(4), self.field_index == 4));
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(5), self.field_index == 5));
 
         // Output directory
-        lines.push(field_line_text("Output Directory", self.tf_ref(5), self.field_index == 5));
+        lines.push(field_line_text("Output Directory", self.tf_ref(6), self.field_index == 6));
+        lines.push(field_line_text("Notes (label, optional)", self.tf_ref(7), self.field_index == 7));
+
+        // toggle line at index 7
+        let label_span = Span::styled(
+            "Revoke Its Delegations First?  ",
+            Style::default().fg(Color::Yellow)
+        );
+        let val_style = if self.field_index == 8 {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let toggle_val = if self.revoke_issued { "yes" } else { "no" };
+        lines.push(Line::from(vec![label_span, Span::styled(toggle_val.to_string(), val_style)]));
+        lines.push(info_line_text("Filename Preview", &self.filename_preview(&ctx.settings.filename_templates)));
+        lines.extend(funding_lines(&self.privkey_to_be_invalidated.text, &self.gas_limit.text, &self.max_fee_per_gas.text, &ctx.settings.display));
 
         lines.push(Line::from("")); // spacer
         lines.push(Self::buttons_line(
-            self.field_index == 6,
-            self.field_index == 7,
-            self.field_index == 8
+            self.field_index == 9,
+            self.field_index == 10,
+            self.field_index == 11,
+            self.field_index == 12
         ));
 
         let middle_para = Paragraph::new(lines);
@@ -380,18 +665,53 @@ impl ScreenWidget for CreatePermanentInvalidationScreen {
         f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
         let footer_line = Line::from(vec![
             span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
             span_key("Enter"),   span_text(" Select"),  span_sep(),
             span_key("Esc"),     span_text(" Back"),    span_sep(),
+            span_key("Ctrl+K"),  span_text(" Key File"), span_sep(),
             span_key("Ctrl+Q"),  span_text(" Quit"),
         ]);
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some(summary) = &self.prefill_summary {
+            summary.draw(f, size);
+        } else if let Some(prompt) = &self.out_dir_prompt {
+            prompt.draw(f, size);
+        } else if let Some(prompt) = &self.invalidate_confirm {
+            prompt.draw(f, size);
+        }
     }
 
     async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         // Apply pending prefill if any
         self.apply_prefill_if_any(ctx);
 
-        if let Some(t) = esc_to_back(k) { return Ok(t); }
+        // Any key dismisses the summary.
+        if self.prefill_summary.take().is_some() {
+            return Ok(Transition::Stay);
+        }
+        if let Some(prompt) = self.out_dir_prompt.as_mut() {
+            match prompt.on_key(k) {
+                Some(OutDirChoice::Use(dir)) => Self::set_textfield(&mut self.out_dir, &dir),
+                Some(OutDirChoice::Keep) => {}
+                None => return Ok(Transition::Stay),
+            }
+            self.out_dir_prompt = None;
+            return Ok(Transition::Stay);
+        }
+        if let Some(prompt) = self.invalidate_confirm.as_mut() {
+            let Some(choice) = prompt.on_key(k) else { return Ok(Transition::Stay) };
+            self.invalidate_confirm = None;
+            return Ok(match choice {
+                TypedConfirmChoice::Confirmed => self.submit(ctx).await,
+                TypedConfirmChoice::Cancelled => Transition::Stay,
+            });
+        }
+
+        if let Some(t) = esc_to_back(k) {
+            self.leave(ctx);
+            return Ok(t); // Esc -> Back
+        }
 
         if let KeyCode::Char('q') = k.code {
             if k.modifiers.contains(KeyModifiers::CONTROL) {
@@ -399,59 +719,87 @@ impl ScreenWidget for CreatePermanentInvalidationScreen {
             }
         }
 
+        // Ctrl+K on a private-key field: load it from an encrypted key file
+        if is_load_key_file_key(&k) {
+            if let Some(&(idx, label)) = KEY_FILE_FIELDS.iter().find(|(i, _)| *i == self.field_index) {
+                return Ok(Transition::Push(Box::new(LoadSigningKeyScreen::new(Form::PermanentInvalidation, idx, label, ctx.fs.clone()))));
+            }
+            return Ok(Transition::Stay);
+        }
+        // A key loaded from a file is replaced, never edited unseen
+        if matches!(k.code, KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_))
+            && self.is_text()
+            && self.key_files.source(self.field_index, self.tf_ref(self.field_index)).is_some()
+        {
+            self.key_files.forget(self.field_index);
+            *self.tf_mut(self.field_index) = TextField::default();
+        }
+
         match k.code {
             // Navigation
             KeyCode::Up => {
-                if self.field_index == 0 { self.field_index = 8; } else { self.field_index -= 1; }
+                if self.field_index == 0 { self.field_index = 12; } else { self.field_index -= 1; }
             }
             KeyCode::Down | KeyCode::Tab => {
-                self.field_index = (self.field_index + 1) % 9;
+                self.field_index = (self.field_index + 1) % 13;
             }
 
-            // Enter on [Create Permanent Invalidation]
-            KeyCode::Enter if self.field_index == 6 => {
-                // Enforce caps first
-                if let Err(e) = self.validate_gas_limit() {
-                    return Ok(Transition::Push(Box::new(
-                        ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
-                if let Err(e) = self.validate_fee_caps() {
+            // Toggle boolean (index 7)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 8 => {
+                self.revoke_issued = !self.revoke_issued;
+            }
+
+            // Enter on [Create Permanent Invalidation]: cannot be undone, so the operator types
+            // INVALIDATE first (gas caps are checked before asking), with the delegations it
+            // orphans listed
+            KeyCode::Enter if self.field_index == 9 => {
+                if let Err(e) = validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
                     return Ok(Transition::Push(Box::new(
-                        ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
+                        ConfirmOkScreen::new(format!("Error: {e}")).with_after_ok(AfterOk::Pop)
                     )));
                 }
-
-                // Create, sign, and write the single-entry JSON
-                match self.create_and_write_invalidation().await {
-                    Ok(path) => {
-                        let lines = vec![
-                            "Saved signed permanent invalidation transaction:".to_string(),
-                            "".to_string(),
-                            path.display().to_string(),
-                        ];
-                        return Ok(Transition::Push(Box::new(
-                            ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
-                        )));
-                    }
-                    Err(e) => {
-                        return Ok(Transition::Push(Box::new(
-                            ConfirmOkScreen::new(&format!("Error: {e:#}"))
-                                .with_after_ok(AfterOk::Pop)
-                        )));
-                    }
-                }
+                let impact = match self.impact_lines(&*ctx.fs) {
+                    Ok(lines) => lines,
+                    Err(e) => return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                    ))),
+                };
+                let key = match address_from_privkey_input(&self.privkey_to_be_invalidated.text) {
+                    Some(addr) => format!("Key: {}", to_checksum(&addr, None)),
+                    None => "Key: (the private key entered above)".to_string(),
+                };
+                let mut lines = vec![
+                    key,
+                    "Once this transaction is broadcast the key is invalidated for good;".to_string(),
+                    "no later transaction can undo it.".to_string(),
+                    "".to_string(),
+                ];
+                lines.extend(impact);
+                self.invalidate_confirm = Some(TypedConfirm::new(
+                    "Permanently Invalidate Key",
+                    lines,
+                    "INVALIDATE",
+                    if self.queued.is_empty() { "Sign Invalidation" } else { "Sign Revocations & Invalidation" },
+                ));
             }
 
             // Enter on [Load From File]
-            KeyCode::Enter if self.field_index == 7 => {
+            KeyCode::Enter if self.field_index == 10 => {
+                return Ok(Transition::Push(Box::new(
+                    ChoosePermanentInvalidationInfoDirScreen::resume(ctx)
+                )));
+            }
+
+            // Enter on [Clone Signed File]: start where this form writes signed files
+            KeyCode::Enter if self.field_index == 11 => {
                 return Ok(Transition::Push(Box::new(
-                    ChoosePermanentInvalidationInfoDirScreen::new()
+                    SelectSignedFileScreen::new("createPermanentInvalidationEvent", PathBuf::from(self.out_dir.text.trim()), ctx.fs.clone())
                 )));
             }
 
             // Enter on [Back]
-            KeyCode::Enter if self.field_index == 8 => {
+            KeyCode::Enter if self.field_index == 12 => {
+                self.leave(ctx);
                 return Ok(Transition::Pop); // Back
             }
 
@@ -10,67 +10,160 @@ use ratatui::{
 };
 use textwrap::wrap;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form, DRAFT_RESTORED_NOTE};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
-use crate::ui::common_nav::esc_to_back;
-use crate::ui::components::{TextField, field_line_text};
+use crate::ui::common_nav::{esc_to_back, is_load_key_file_key};
+use crate::ui::key_file_fields::KeyFileFields;
+use crate::ui::components::{TextField, field_line_text, funding_lines, info_line_text, warning_banner_lines};
+use crate::ui::out_dir_prompt::{OutDirChoice, OutDirPrompt};
+use crate::ui::prefill_summary::{PrefillField, PrefillSummary};
+use crate::units::{check_window, GasLimit, Nonce, PayloadNonce, UnixTime, Wei};
+use crate::validation::validate_gas_fields;
 use crate::defaults::Defaults;
+use crate::display::DisplaySettings;
+use crate::settings::{GasLimits, Settings};
 
 // Generic OK-only modal
-use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::{ConfirmOkScreen, AfterOk, LoadSigningKeyScreen};
 
 // ABI / processor / types / writer
 use crate::abi::load_abi;
-use crate::process::{process_item, BatchOpts};
+use crate::process::{key_reuse, process_item, retired_keys, BatchOpts, KeyReuse, RetiredKey};
+use crate::key_usage::{record_signed, KeyUsage};
+use crate::usage_stats::Written;
+use crate::signing::SignerCache;
+use crate::vfs::Fs;
+use crate::event_stream::Event;
+use crate::lint;
+use crate::simulate;
 use crate::types::Item;
 use crate::write_signed_transactions_to_file::{
     write_single_signed_transaction,
     build_filename_for_any_tx,
+    signed_file_metadata,
+};
+use crate::filename_template::{
+    base_vars, insert_key_vars, insert_label, render_template_preview,
+    x_coord_from_privkey_input, x_coord_from_pubkey_input, FilenameTemplates, KeyRole,
 };
 
 // Load-from-file flow (directory picker) — redelegation version
-use crate::screens::ChooseRedelegationInfoDirScreen;
+use crate::screens::{ChooseRedelegationInfoDirScreen, SelectSignedFileScreen};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Transactions),
+        order: 30,
+        title: "Create Re-Delegation",
+        description: "Sign one re-delegation from manual input or an info file.",
+        open: |ctx| Transition::Push(Box::new(CreateRedelegationScreen::resume(ctx))),
+    }
+}
+
+/// Info-file keys this form reads, for the summary shown after Load From File.
+const PREFILL_FIELDS: &[PrefillField] = &[
+    PrefillField { key: "REVOKER_REDELEGATOR_PRIVKEY", label: "Revoker/Redelegator PrivKey", secret: true },
+    PrefillField { key: "REVOKEE_PRIVKEY", label: "Revokee PrivKey", secret: true },
+    PrefillField { key: "REVOKEE_PUBKEY", label: "Revokee PubKey", secret: false },
+    PrefillField { key: "DELEGATEE_PRIVKEY", label: "New Delegatee PrivKey", secret: true },
+    PrefillField { key: "DELEGATEE_PUBKEY", label: "New Delegatee PubKey", secret: false },
+    PrefillField { key: "REQUIRE_DELEGATEE_SIG_FOR_REVOCATION", label: "Require Delegatee Signature For Revocation", secret: false },
+    PrefillField { key: "REVOCATION_START", label: "Revocation Start", secret: false },
+    PrefillField { key: "REVOCATION_END", label: "Revocation End", secret: false },
+    PrefillField { key: "NONCE", label: "Transaction Nonce", secret: false },
+    PrefillField { key: "DELEGATION_PAYLOAD_NONCE", label: "Delegation Payload Nonce", secret: false },
+    PrefillField { key: "REVOCATION_PAYLOAD_NONCE", label: "Revocation Payload Nonce", secret: false },
+    PrefillField { key: "GAS_LIMIT", label: "Gas limit", secret: false },
+    PrefillField { key: "MAX_FEE_PER_GAS", label: "Maximum Fee Per Gas", secret: false },
+    PrefillField { key: "MAX_PRIORITY_FEE_PER_GAS", label: "Maximum Priority Fee Per Gas", secret: false },
+    PrefillField { key: "LABEL", label: "Notes", secret: false },
+    PrefillField { key: "OUTPUT_DIRECTORY", label: "Output Directory", secret: false },
+];
+
+/// Private-key fields Ctrl+K can load from an encrypted key file, with their labels.
+const KEY_FILE_FIELDS: &[(usize, &str)] = &[(0, "Revoker/Redelegator PrivKey"), (1, "Revokee PrivKey"), (3, "New Delegatee PrivKey")];
 
 pub struct CreateRedelegationScreen {
-    // 0 redelegator_priv, 1 revokee_priv, 2 revokee_pubkey, 3 delegatee_priv,
-    // 4 require_delegatee_sig_revocation (toggle),
-    // 5 nonce, 6 gas_limit, 7 max_fee_per_gas, 8 max_priority_fee_per_gas,
-    // 9 out_dir, 10 submit, 11 load_from_file, 12 back
+    // 0 redelegator_priv, 1 revokee_priv, 2 revokee_pubkey, 3 delegatee_priv, 4 delegatee_pubkey,
+    // 5 require_delegatee_sig_revocation (toggle), 6 revocation start, 7 revocation end,
+    // 8 nonce, 9 delegation payload nonce, 10 revocation payload nonce,
+    // 11 gas_limit, 12 max_fee_per_gas, 13 max_priority_fee_per_gas,
+    // 14 out_dir, 15 notes, 16 submit, 17 load_from_file, 18 clone_signed, 19 back
     field_index: usize,
     redelegator_priv: TextField,
     revokee_priv: TextField,
     revokee_pubkey: TextField,
     delegatee_priv: TextField,
+    delegatee_pubkey: TextField,
     require_delegatee_sig_revocation: bool,
+    /// Validity window of the revocation side (see `units::UnixTime`); empty = open.
+    start_time: TextField,
+    end_time: TextField,
     nonce: TextField,
+    /// Fixed `bytes16` payload nonces of each side (see `units::PayloadNonce`); empty = random.
+    /// Not kept in drafts, so a fixed nonce is never reused by accident.
+    delegation_payload_nonce: TextField,
+    revocation_payload_nonce: TextField,
     gas_limit: TextField,
     max_fee_per_gas: TextField,
     max_priority_fee_per_gas: TextField,
     out_dir: TextField,
+    /// Becomes the item's label (see `types::Item`).
+    notes: TextField,
+    /// SHA-256 of the info file last loaded via Load From File (recorded in output metadata).
+    input_sha256: Option<String>,
+    /// Open while an Output Directory from a loaded file awaits confirmation.
+    out_dir_prompt: Option<OutDirPrompt>,
+    /// What the last loaded file set; shown once, before any Output Directory prompt.
+    prefill_summary: Option<PrefillSummary>,
+    /// Key reuse the operator was warned about; the next submit with the same keys signs anyway.
+    key_reuse_ack: Option<KeyReuse>,
+    /// Retired keys the operator was warned about; the next submit with the same keys signs anyway.
+    retired_ack: Option<RetiredKey>,
+    /// Reopened with the values of the last visit (see `resume`).
+    restored: bool,
+    /// Private-key fields loaded from key files, shown by source instead of value.
+    key_files: KeyFileFields,
 }
 
 impl CreateRedelegationScreen {
-    pub fn new() -> Self {
+    pub fn new(gas_limits: &GasLimits) -> Self {
         Self {
             field_index: 0,
             redelegator_priv: TextField::with(""),
             revokee_priv: TextField::with(""),
             revokee_pubkey: TextField::with(""),
             delegatee_priv: TextField::with(""),
+            delegatee_pubkey: TextField::with(""),
             require_delegatee_sig_revocation: false, // default off
+            start_time: TextField::with(""),
+            end_time: TextField::with(""),
             nonce: TextField::with(""),
-            gas_limit: TextField::with(Defaults::GAS_LIMIT),
+            delegation_payload_nonce: TextField::with(""),
+            revocation_payload_nonce: TextField::with(""),
+            gas_limit: TextField::with(gas_limits.default_for("createRevocationEventFollowedByDelegationEvent")),
             max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
             max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
             out_dir: TextField::with(Defaults::CREATE_REDELEGATION_OUT_DIR),
+            notes: TextField::with(""),
+            input_sha256: None,
+            out_dir_prompt: None,
+            prefill_summary: None,
+            key_reuse_ack: None,
+            retired_ack: None,
+            restored: false,
+            key_files: KeyFileFields::default(),
         }
     }
 
     fn is_text(&self) -> bool {
-        matches!(self.field_index, 0 | 1 | 2 | 3 | 5 | 6 | 7 | 8 | 9)
+        matches!(self.field_index, 0..=4 | 6..=15)
     }
 
     fn tf_ref(&self, idx: usize) -> &TextField {
@@ -79,11 +172,17 @@ impl CreateRedelegationScreen {
             1 => &self.revokee_priv,
             2 => &self.revokee_pubkey,
             3 => &self.delegatee_priv,
-            5 => &self.nonce,
-            6 => &self.gas_limit,
-            7 => &self.max_fee_per_gas,
-            8 => &self.max_priority_fee_per_gas,
-            9 => &self.out_dir,
+            4 => &self.delegatee_pubkey,
+            6 => &self.start_time,
+            7 => &self.end_time,
+            8 => &self.nonce,
+            9 => &self.delegation_payload_nonce,
+            10 => &self.revocation_payload_nonce,
+            11 => &self.gas_limit,
+            12 => &self.max_fee_per_gas,
+            13 => &self.max_priority_fee_per_gas,
+            14 => &self.out_dir,
+            15 => &self.notes,
             _ => unreachable!("tf_ref called on non-text field"),
         }
     }
@@ -94,11 +193,17 @@ impl CreateRedelegationScreen {
             1 => &mut self.revokee_priv,
             2 => &mut self.revokee_pubkey,
             3 => &mut self.delegatee_priv,
-            5 => &mut self.nonce,
-            6 => &mut self.gas_limit,
-            7 => &mut self.max_fee_per_gas,
-            8 => &mut self.max_priority_fee_per_gas,
-            9 => &mut self.out_dir,
+            4 => &mut self.delegatee_pubkey,
+            6 => &mut self.start_time,
+            7 => &mut self.end_time,
+            8 => &mut self.nonce,
+            9 => &mut self.delegation_payload_nonce,
+            10 => &mut self.revocation_payload_nonce,
+            11 => &mut self.gas_limit,
+            12 => &mut self.max_fee_per_gas,
+            13 => &mut self.max_priority_fee_per_gas,
+            14 => &mut self.out_dir,
+            15 => &mut self.notes,
             _ => unreachable!("tf_mut called on non-text field"),
         }
     }
@@ -111,57 +216,138 @@ impl CreateRedelegationScreen {
 
     // Apply pending prefill from ctx (Re-Delegation keys)
     fn apply_prefill_if_any(&mut self, ctx: &mut AppCtx) {
-        if let Some(prefill) = ctx.pending_redelegation_prefill.take() {
-            if let Some(v) = prefill.map.get("REVOKER_REDELEGATOR_PRIVKEY") {
-                Self::set_textfield(&mut self.redelegator_priv, v);
-            }
-            if let Some(v) = prefill.map.get("REVOKEE_PRIVKEY") {
-                Self::set_textfield(&mut self.revokee_priv, v);
-            }
-            if let Some(v) = prefill.map.get("REVOKEE_PUBKEY") {
-                Self::set_textfield(&mut self.revokee_pubkey, v);
-            }
-            if let Some(v) = prefill.map.get("DELEGATEE_PRIVKEY") {
-                Self::set_textfield(&mut self.delegatee_priv, v);
-            }
-            if let Some(v) = prefill.map.get("NONCE") {
-                Self::set_textfield(&mut self.nonce, v);
-            }
-            if let Some(v) = prefill.map.get("GAS_LIMIT") {
-                Self::set_textfield(&mut self.gas_limit, v);
-            }
-            if let Some(v) = prefill.map.get("MAX_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_fee_per_gas, v);
-            }
-            if let Some(v) = prefill.map.get("MAX_PRIORITY_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
-            }
+        if let Some(key) = ctx.take_signing_key(self.crumb(), Form::Redelegation) {
+            *self.tf_mut(key.field) = self.key_files.fill(&key);
+        }
+        if let Some(prefill) = ctx.take_prefill(self.crumb(), Form::Redelegation) {
+            self.input_sha256 = prefill.source_sha256.clone();
+            self.prefill_summary = Some(PrefillSummary::new(PREFILL_FIELDS, &prefill));
+            self.apply_values(&prefill.map);
             if let Some(v) = prefill.map.get("OUTPUT_DIRECTORY") {
-                Self::set_textfield(&mut self.out_dir, v);
+                // Never taken silently: the operator confirms the resolved path first.
+                self.out_dir_prompt = OutDirPrompt::for_prefill(v, &self.out_dir.text, ctx);
             }
+        }
+    }
 
-            // Boolean (treat anything else as false)
-            if let Some(v) = prefill.map.get("REQUIRE_DELEGATEE_SIG_FOR_REVOCATION") {
-                let vv = v.to_ascii_lowercase();
-                self.require_delegatee_sig_revocation = matches!(
-                    vv.as_str(),
-                    "true" | "1" | "yes" | "on" | "y" | "t"
-                );
+    /// Set the fields named in `map` (info-file keys), except the Output Directory.
+    fn apply_values(&mut self, map: &HashMap<String, String>) {
+        if let Some(v) = map.get("REVOKER_REDELEGATOR_PRIVKEY") {
+            Self::set_textfield(&mut self.redelegator_priv, v);
+        }
+        if let Some(v) = map.get("REVOKEE_PRIVKEY") {
+            Self::set_textfield(&mut self.revokee_priv, v);
+        }
+        if let Some(v) = map.get("REVOKEE_PUBKEY") {
+            Self::set_textfield(&mut self.revokee_pubkey, v);
+        }
+        if let Some(v) = map.get("DELEGATEE_PRIVKEY") {
+            Self::set_textfield(&mut self.delegatee_priv, v);
+        }
+        if let Some(v) = map.get("DELEGATEE_PUBKEY") {
+            Self::set_textfield(&mut self.delegatee_pubkey, v);
+        }
+        if let Some(v) = map.get("REVOCATION_START") {
+            Self::set_textfield(&mut self.start_time, v);
+        }
+        if let Some(v) = map.get("REVOCATION_END") {
+            Self::set_textfield(&mut self.end_time, v);
+        }
+        if let Some(v) = map.get("NONCE") {
+            Self::set_textfield(&mut self.nonce, v);
+        }
+        if let Some(v) = map.get("DELEGATION_PAYLOAD_NONCE") {
+            Self::set_textfield(&mut self.delegation_payload_nonce, v);
+        }
+        if let Some(v) = map.get("REVOCATION_PAYLOAD_NONCE") {
+            Self::set_textfield(&mut self.revocation_payload_nonce, v);
+        }
+        if let Some(v) = map.get("GAS_LIMIT") {
+            Self::set_textfield(&mut self.gas_limit, v);
+        }
+        if let Some(v) = map.get("MAX_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("MAX_PRIORITY_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("LABEL") {
+            Self::set_textfield(&mut self.notes, v);
+        }
+
+        // Boolean (treat anything else as false)
+        if let Some(v) = map.get("REQUIRE_DELEGATEE_SIG_FOR_REVOCATION") {
+            let vv = v.to_ascii_lowercase();
+            self.require_delegatee_sig_revocation = matches!(
+                vv.as_str(),
+                "true" | "1" | "yes" | "on" | "y" | "t"
+            );
+        }
+    }
+
+    /// A new form holding the values it was last left with, if any.
+    pub fn resume(ctx: &mut AppCtx) -> Self {
+        let mut screen = Self::new(&ctx.settings.gas_limits);
+        if let Some(draft) = ctx.take_draft(screen.crumb(), Form::Redelegation) {
+            screen.apply_values(&draft);
+            if let Some(dir) = draft.get("OUTPUT_DIRECTORY") {
+                Self::set_textfield(&mut screen.out_dir, dir);
             }
+            screen.restored = true;
+        }
+        screen
+    }
+
+    /// Keep the non-secret values for the next visit (private keys are left out).
+    fn leave(&self, ctx: &mut AppCtx) {
+        let mut map = HashMap::new();
+        for (key, tf) in [
+            ("REVOKEE_PUBKEY", &self.revokee_pubkey),
+            ("DELEGATEE_PUBKEY", &self.delegatee_pubkey),
+            ("REVOCATION_START", &self.start_time),
+            ("REVOCATION_END", &self.end_time),
+            ("NONCE", &self.nonce),
+            ("GAS_LIMIT", &self.gas_limit),
+            ("MAX_FEE_PER_GAS", &self.max_fee_per_gas),
+            ("MAX_PRIORITY_FEE_PER_GAS", &self.max_priority_fee_per_gas),
+            ("LABEL", &self.notes),
+            ("OUTPUT_DIRECTORY", &self.out_dir),
+        ] {
+            map.insert(key.to_string(), tf.text.clone());
         }
+        map.insert("REQUIRE_DELEGATEE_SIG_FOR_REVOCATION".to_string(), self.require_delegatee_sig_revocation.to_string());
+        ctx.dispatch(self.crumb(), AppEvent::FormLeft { form: Form::Redelegation, draft: map });
     }
 
-    // Buttons: < Create Re-Delegation >   < Load From File >   < Back >
-    fn buttons_line(submit_selected: bool, load_selected: bool, back_selected: bool) -> Line<'static> {
+    // Buttons: < Create Re-Delegation >   < Load From File >   < Clone Signed File >   < Back >
+    fn buttons_line(submit_selected: bool, load_selected: bool, clone_selected: bool, back_selected: bool) -> Line<'static> {
         let mut spans: Vec<Span<'static>> = Vec::new();
         spans.extend(button_spans("Create Re-Delegation", submit_selected));
         spans.push(Span::raw("   "));
         spans.extend(button_spans("Load From File", load_selected));
         spans.push(Span::raw("   "));
+        spans.extend(button_spans("Clone Signed File", clone_selected));
+        spans.push(Span::raw("   "));
         spans.extend(button_spans("Back", back_selected));
         Line::from(spans)
     }
 
+    /// Revocation start and end as entered, checked as a window.
+    fn window(&self) -> Result<(UnixTime, UnixTime)> {
+        let start: UnixTime = self.start_time.text.parse().context("Revocation Start")?;
+        let end: UnixTime = self.end_time.text.parse().context("Revocation End")?;
+        check_window("Revocation", start, end)?;
+        Ok((start, end))
+    }
+
+    /// The window as it will be signed, in the configured display format.
+    fn window_preview(&self, display: &DisplaySettings) -> String {
+        match self.window() {
+            Ok((start, end)) => display.window(start.0, end.0),
+            Err(e) => format!("{e:#}"),
+        }
+    }
+
     fn ensure_out_dir_nonempty(&self) -> Result<PathBuf> {
         let out_dir = self.out_dir.text.trim();
         if out_dir.is_empty() {
@@ -170,33 +356,80 @@ impl CreateRedelegationScreen {
         Ok(PathBuf::from(out_dir))
     }
 
+    /// Live preview of the output filename from the current inputs.
+    fn filename_preview(&self, templates: &FilenameTemplates) -> String {
+        let mut vars = base_vars(
+            "createRevocationEventFollowedByDelegationEvent",
+            self.nonce.text.trim(),
+            &Defaults::CHAIN_ID.to_string(),
+        );
+        // The redelegator is both the revoker (B side) and the delegator (A side)
+        if let Some(x) = x_coord_from_privkey_input(&self.redelegator_priv.text) {
+            insert_key_vars(&mut vars, KeyRole::Revoker, &x);
+            insert_key_vars(&mut vars, KeyRole::Delegator, &x);
+        }
+        let revokee_x = if self.revokee_priv.text.trim().is_empty() {
+            x_coord_from_pubkey_input(&self.revokee_pubkey.text)
+        } else {
+            x_coord_from_privkey_input(&self.revokee_priv.text)
+        };
+        if let Some(x) = revokee_x {
+            insert_key_vars(&mut vars, KeyRole::Revokee, &x);
+        }
+        let delegatee_x = if self.delegatee_priv.text.trim().is_empty() {
+            x_coord_from_pubkey_input(&self.delegatee_pubkey.text)
+        } else {
+            x_coord_from_privkey_input(&self.delegatee_priv.text)
+        };
+        if let Some(x) = delegatee_x {
+            insert_key_vars(&mut vars, KeyRole::Delegatee, &x);
+        }
+        insert_label(&mut vars, Some(&self.notes.text));
+        render_template_preview(&templates.redelegation, &vars)
+    }
+
     /// Create, sign, and write a single "re-delegation" tx (revocation + delegation combo).
-    async fn create_and_write_redelegation(&self) -> Result<PathBuf> {
+    /// Returns the written file and the transaction sender, for the key usage store.
+    async fn create_and_write_redelegation(&self, fs: &dyn Fs, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<(PathBuf, String)> {
         // Validate inputs
         let pk_owner = self.redelegator_priv.text.trim();
         let pk_revokee = self.revokee_priv.text.trim();
         let pub_revokee = self.revokee_pubkey.text.trim();
         let pk_delegatee = self.delegatee_priv.text.trim();
+        let pub_delegatee = self.delegatee_pubkey.text.trim();
 
         if pk_owner.is_empty() {
             anyhow::bail!("Revoker/Redelegator PrivKey cannot be empty.");
         }
-        if pk_delegatee.is_empty() {
-            anyhow::bail!("Delegatee PrivKey cannot be empty.");
+        if pk_delegatee.is_empty() && pub_delegatee.is_empty() {
+            anyhow::bail!("Provide either New Delegatee PrivKey or New Delegatee PubKey.");
         }
         if pk_revokee.is_empty() && pub_revokee.is_empty() {
             anyhow::bail!("Provide either Revokee PrivKey or Revokee PubKey.");
         }
 
+        let (start, end) = self.window()?;
+
         // Parse nonce
         let nonce_str = self.nonce.text.trim();
-        let nonce: u64 = nonce_str.parse().context("Nonce must be an integer")?;
+        let nonce: Nonce = nonce_str.parse().context("Nonce must be an integer")?;
+        let delegation_payload_nonce: PayloadNonce =
+            self.delegation_payload_nonce.text.parse().context("Delegation Payload Nonce")?;
+        let revocation_payload_nonce: PayloadNonce =
+            self.revocation_payload_nonce.text.parse().context("Revocation Payload Nonce")?;
 
         // Gas opts
-        let opts = BatchOpts {
-            gas_limit: self.gas_limit.text.trim().to_string(),
-            max_fee_per_gas: self.max_fee_per_gas.text.trim().to_string(),
-            max_priority_fee_per_gas: self.max_priority_fee_per_gas.text.trim().to_string(),
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let mut opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
         };
 
         // ABI
@@ -208,142 +441,104 @@ impl CreateRedelegationScreen {
             nonce: Some(nonce),
             chain_id: Some(Defaults::CHAIN_ID),
             contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+            label: Some(self.notes.text.clone()),
 
-            // Type A (delegation side): owner = pk_owner; delegatee = pk_delegatee
+            // Type A (delegation side): owner = pk_owner; delegatee = (priv or pub)
             type_a_privkey_x: Some(pk_owner.to_string()),
-            type_a_privkey_y: Some(pk_delegatee.to_string()),
-            type_a_pubkey_y: Some("".to_string()),
+            type_a_privkey_y: Some(pk_delegatee.to_string()), // may be empty; process.rs falls back to pub if priv empty
+            type_a_pubkey_y: Some(pub_delegatee.to_string()),
             type_a_uint_x: Some(0),
             type_a_uint_y: Some(0),
             type_a_boolean: Some(if self.require_delegatee_sig_revocation { "true".into() } else { "false".into() }),
+            type_a_nonce16: delegation_payload_nonce.hex(),
 
             // Type B (revocation side): owner = pk_owner; revokee = (priv or pub)
             type_b_privkey_x: Some(pk_owner.to_string()),
             type_b_privkey_y: Some(pk_revokee.to_string()), // may be empty; process.rs falls back to pub if priv empty
             type_b_pubkey_y: Some(pub_revokee.to_string()),
-            type_b_uint_x: Some(0),
-            type_b_uint_y: Some(0),
+            type_b_uint_x: Some(start.0),
+            type_b_uint_y: Some(end.0),
+            type_b_nonce16: revocation_payload_nonce.hex(),
 
             // Type C (unused)
             type_c_privkey_x: None,
+            type_c_nonce16: None,
         };
 
+        // A reuse already confirmed for these exact keys is signed as is
+        opts.allow_key_reuse = self.key_reuse_ack.is_some() && key_reuse(&item, &opts.signers) == self.key_reuse_ack;
+        opts.allow_retired_key =
+            self.retired_ack.is_some() && retired_keys(&item, &opts.signers, &opts.retired_keys) == self.retired_ack;
+
         // Build & sign
         let entry = process_item(&abi, &opts, &item)
             .await
             .context("failed to construct and sign re-delegation transaction")?;
+        lint::check_entries(std::slice::from_ref(&entry))?;
+        simulate::check_entries(settings, std::slice::from_ref(&entry))?;
 
         // Filename determined from decoded contents
-        let filename = build_filename_for_any_tx(&entry.decoded_tx);
+        let filename = build_filename_for_any_tx(&entry, &settings.filename_templates);
         let mut out_path = self.ensure_out_dir_nonempty()?;
         out_path.push(filename);
 
-        let written = write_single_signed_transaction(&out_path, &entry, true)
+        let metadata = signed_file_metadata(settings, self.input_sha256.clone(), Some("createRevocationEventFollowedByDelegationEvent"));
+        let written = write_single_signed_transaction(fs, &out_path, &entry, metadata.as_ref(), opts.encoding.encoder())
             .context("failed to write signed transaction file")?;
 
-        Ok(written)
-    }
-
-    fn validate_gas_limit(&self) -> Result<()> {
-        let max_str = Defaults::GAS_LIMIT.trim();
-        let max: u64 = max_str.parse().context("Defaults::GAS_LIMIT must be an integer")?;
-
-        let user_str = self.gas_limit.text.trim();
-        let user: u64 = user_str.parse().context("Gas limit must be an integer")?;
-
-        if user == 0 {
-            anyhow::bail!("Gas limit must be greater than zero.");
-        }
-        if user > max {
-            anyhow::bail!(format!(
-                "Gas limit {} exceeds the maximum allowed {}.",
-                user, max
-            ));
-        }
-        Ok(())
-    }
-
-    fn validate_fee_caps(&self) -> Result<()> {
-        // maxFeePerGas cap
-        let max_fee_cap_str = Defaults::MAX_FEE_PER_GAS.trim();
-        let max_fee_cap: u64 = max_fee_cap_str
-            .parse()
-            .context("Defaults::MAX_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_max_fee_str = self.max_fee_per_gas.text.trim();
-        let user_max_fee: u64 = user_max_fee_str
-            .parse()
-            .context("Maximum Fee Per Gas must be an integer (wei)")?;
-        if user_max_fee == 0 {
-            anyhow::bail!("Maximum Fee Per Gas must be greater than zero.");
-        }
-        if user_max_fee > max_fee_cap {
-            anyhow::bail!(format!(
-                "Maximum Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_max_fee, max_fee_cap
-            ));
-        }
-
-        // maxPriorityFeePerGas cap
-        let max_prio_cap_str = Defaults::MAX_PRIORITY_FEE_PER_GAS.trim();
-        let max_prio_cap: u64 = max_prio_cap_str
-            .parse()
-            .context("Defaults::MAX_PRIORITY_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_prio_str = self.max_priority_fee_per_gas.text.trim();
-        let user_prio: u64 = user_prio_str
-            .parse()
-            .context("Maximum Priority Fee Per Gas must be an integer (wei)")?;
-
-        // priority fee can be zero, but not above cap
-        if user_prio > max_prio_cap {
-            anyhow::bail!(format!(
-                "Maximum Priority Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_prio, max_prio_cap
-            ));
-        }
-
-        // Sanity: priority <= max fee
-        if user_prio > user_max_fee {
-            anyhow::bail!("Maximum Priority Fee Per Gas cannot exceed Maximum Fee Per Gas.");
-        }
-
-        Ok(())
+        Ok((written, entry.decoded_tx.from))
     }
 }
 
 impl Default for CreateRedelegationScreen {
-    fn default() -> Self { Self::new() }
+    fn default() -> Self { Self::new(&GasLimits::default()) }
 }
 
 #[async_trait]
 impl ScreenWidget for CreateRedelegationScreen {
     fn apply_prefill(&mut self, ctx: &mut AppCtx) {
-        self.apply_prefill_if_any(ctx); // consumes ctx.pending_redelegation_prefill exactly once
+        self.apply_prefill_if_any(ctx); // consumes the pending prefill exactly once (AppEvent::PrefillTaken)
     }
 
     fn title(&self) -> &str { "" }
 
-    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+    fn crumb(&self) -> &str { "Create Re-Delegation" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
         let header_text = "Create Re-Delegation";
-        let explanation_paras = [
+        let mut explanation_paras = vec![
             "Enter the fields below. The app will create and sign an EIP-1559 transaction",
-            "for createRevocationEventFollowedByDelegationEvent and save a one-element JSON array (pretty-printed)",
-            "to your chosen output directory. The filename will be derived from the decoded tx.",
+            "for createRevocationEventFollowedByDelegationEvent and save it as pretty-printed JSON (entries plus a metadata block)",
+            "to your chosen output directory. The filename follows the re-delegation template",
+            "in the settings file; a live preview is shown below the output directory.",
+            "If only the New Delegatee PubKey is given, the delegatee signature is left zeroed, so the new delegatee's secret never has to be on this machine.",
+            "Revocation Start and End bound when the revocation is in force; leave them empty for one that starts at once and never ends.",
         ];
+        if self.restored {
+            explanation_paras.push(DRAFT_RESTORED_NOTE);
+        }
 
         // === TOP BOX ===
         let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
         let header_lines = wrap(header_text, top_inner_width).len() as u16;
 
         let mut exp_lines = 0usize;
-        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
         let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
 
-        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+        // Unregistered chain/contract pair: a bold banner under the explanation.
+        let banner = ctx.settings.networks
+            .unknown_warning(Defaults::CHAIN_ID, Defaults::CONTRACT_ADDRESS)
+            .map(|w| warning_banner_lines(&w, top_inner_width))
+            .unwrap_or_default();
+        let banner_lines = if banner.is_empty() { 0 } else { banner.len() as u16 + 1 };
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + banner_lines;
 
-        // Middle: 13 focusable positions (0..=12) plus spacer
-        let middle_rows: u16 = 13 + 1;
+        // Middle: 19 field rows plus validity, filename preview, funding lines and spacer
+        let middle_rows: u16 = 19 + 5;
         let middle_needed = 2 + 2 + middle_rows;
 
         let footer_height = 3;
@@ -373,6 +568,10 @@ impl ScreenWidget for CreateRedelegationScreen {
             for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
             if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
         }
+        if !banner.is_empty() {
+            expl_lines.push(Line::from(""));
+            expl_lines.extend(banner);
+        }
         let explanation_para = Paragraph::new(expl_lines)
             .alignment(Alignment::Left)
             .wrap(Wrap { trim: true });
@@ -388,51 +587,61 @@ impl ScreenWidget for CreateRedelegationScreen {
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from("")); // spacer above first field
 
-        lines.push(field_line_text("Revoker/Redelegator PrivKey", self.tf_ref(0), self.field_index == 0));
-        lines.push(field_line_text("Revokee PrivKey (optional if PubKey is provided)", self.tf_ref(1), self.field_index == 1));
+        lines.push(self.key_files.line("Revoker/Redelegator PrivKey", 0, self.tf_ref(0), self.field_index == 0));
+        lines.push(self.key_files.line("Revokee PrivKey (optional if PubKey is provided)", 1, self.tf_ref(1), self.field_index == 1));
         lines.push(field_line_text("Revokee PubKey (0x04… uncompressed, optional)", self.tf_ref(2), self.field_index == 2));
-        lines.push(field_line_text("New Delegatee PrivKey", self.tf_ref(3), self.field_index == 3));
+        lines.push(self.key_files.line("New Delegatee PrivKey (optional if PubKey is provided)", 3, self.tf_ref(3), self.field_index == 3));
+        lines.push(field_line_text("New Delegatee PubKey (0x04… uncompressed, optional)", self.tf_ref(4), self.field_index == 4));
 
         // toggle line at index 4
         let label_span = Span::styled(
             "Require Delegatee Signature For Revocation?  ",
             Style::default().fg(Color::Yellow)
         );
-        let val_style = if self.field_index == 4 {
+        let val_style = if self.field_index == 5 {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::White)
         };
         lines.push(Line::from(vec![label_span, Span::styled(toggle_val.to_string(), val_style)]));
 
-        lines.push(field_line_text("Transaction Nonce", self.tf_ref(5), self.field_index == 5));
+        lines.push(field_line_text("Revocation Start (Unix time or YYYY-MM-DD [HH:MM], UTC; empty = now)", self.tf_ref(6), self.field_index == 6));
+        lines.push(field_line_text("Revocation End (empty = no end)", self.tf_ref(7), self.field_index == 7));
+        lines.push(info_line_text("Validity", &self.window_preview(&ctx.settings.display)));
+        lines.push(field_line_text("Transaction Nonce", self.tf_ref(8), self.field_index == 8));
+        lines.push(field_line_text("Delegation Payload Nonce (0x + 32 hex digits or UUID; empty = random)", self.tf_ref(9), self.field_index == 9));
+        lines.push(field_line_text("Revocation Payload Nonce (0x + 32 hex digits or UUID; empty = random)", self.tf_ref(10), self.field_index == 10));
 
         // Gas limit (cap label)
-        let gas_label = format!("Gas limit (maximum {} gas)", Defaults::GAS_LIMIT);
-        lines.push(field_line_text(&gas_label, self.tf_ref(6), self.field_index == 6));
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(11), self.field_index == 11));
 
         // Max fee per gas (cap label)
         let mfg_label = format!(
             "Maximum Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_FEE_PER_GAS
+            ctx.settings.gas_caps.max_fee_per_gas()
         );
-        lines.push(field_line_text(&mfg_label, self.tf_ref(7), self.field_index == 7));
+        lines.push(field_line_text(&mfg_label, self.tf_ref(12), self.field_index == 12));
 
         // Max priority fee per gas (cap label)
         let mpfg_label = format!(
             "Maximum Priority Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_PRIORITY_FEE_PER_GAS
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
         );
-        lines.push(field_line_text(&mpfg_label, self.tf_ref(8), self.field_index == 8));
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(13), self.field_index == 13));
 
         // Output directory
-        lines.push(field_line_text("Output Directory", self.tf_ref(9), self.field_index == 9));
+        lines.push(field_line_text("Output Directory", self.tf_ref(14), self.field_index == 14));
+        lines.push(field_line_text("Notes (label, optional)", self.tf_ref(15), self.field_index == 15));
+        lines.push(info_line_text("Filename Preview", &self.filename_preview(&ctx.settings.filename_templates)));
+        lines.extend(funding_lines(&self.redelegator_priv.text, &self.gas_limit.text, &self.max_fee_per_gas.text, &ctx.settings.display));
 
         lines.push(Line::from("")); // spacer
         lines.push(Self::buttons_line(
-            self.field_index == 10,
-            self.field_index == 11,
-            self.field_index == 12
+            self.field_index == 16,
+            self.field_index == 17,
+            self.field_index == 18,
+            self.field_index == 19
         ));
 
         let middle_para = Paragraph::new(lines);
@@ -445,16 +654,38 @@ impl ScreenWidget for CreateRedelegationScreen {
             span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
             span_key("Enter"),   span_text(" Select"), span_sep(),
             span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+K"),  span_text(" Key File"), span_sep(),
             span_key("Ctrl+Q"),  span_text(" Quit"),
         ]);
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some(summary) = &self.prefill_summary {
+            summary.draw(f, size);
+        } else if let Some(prompt) = &self.out_dir_prompt {
+            prompt.draw(f, size);
+        }
     }
 
     async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         // Apply pending prefill if any
         self.apply_prefill_if_any(ctx);
 
+        // Any key dismisses the summary.
+        if self.prefill_summary.take().is_some() {
+            return Ok(Transition::Stay);
+        }
+        if let Some(prompt) = self.out_dir_prompt.as_mut() {
+            match prompt.on_key(k) {
+                Some(OutDirChoice::Use(dir)) => Self::set_textfield(&mut self.out_dir, &dir),
+                Some(OutDirChoice::Keep) => {}
+                None => return Ok(Transition::Stay),
+            }
+            self.out_dir_prompt = None;
+            return Ok(Transition::Stay);
+        }
+
         if let Some(t) = esc_to_back(k) {
+            self.leave(ctx);
             return Ok(t); // Esc -> Back
         }
 
@@ -464,47 +695,76 @@ impl ScreenWidget for CreateRedelegationScreen {
             }
         }
 
+        // Ctrl+K on a private-key field: load it from an encrypted key file
+        if is_load_key_file_key(&k) {
+            if let Some(&(idx, label)) = KEY_FILE_FIELDS.iter().find(|(i, _)| *i == self.field_index) {
+                return Ok(Transition::Push(Box::new(LoadSigningKeyScreen::new(Form::Redelegation, idx, label, ctx.fs.clone()))));
+            }
+            return Ok(Transition::Stay);
+        }
+        // A key loaded from a file is replaced, never edited unseen
+        if matches!(k.code, KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_))
+            && self.is_text()
+            && self.key_files.source(self.field_index, self.tf_ref(self.field_index)).is_some()
+        {
+            self.key_files.forget(self.field_index);
+            *self.tf_mut(self.field_index) = TextField::default();
+        }
+
         match k.code {
             // Navigation
             KeyCode::Up => {
-                if self.field_index == 0 { self.field_index = 12; } else { self.field_index -= 1; }
+                if self.field_index == 0 { self.field_index = 19; } else { self.field_index -= 1; }
             }
             KeyCode::Down | KeyCode::Tab => {
-                self.field_index = (self.field_index + 1) % 13;
+                self.field_index = (self.field_index + 1) % 20;
             }
 
             // Toggle boolean (index 4)
-            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 4 => {
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 5 => {
                 self.require_delegatee_sig_revocation = !self.require_delegatee_sig_revocation;
             }
 
             // Enter on [Create Re-Delegation]
-            KeyCode::Enter if self.field_index == 10 => {
+            KeyCode::Enter if self.field_index == 16 => {
                 // Enforce caps first
-                if let Err(e) = self.validate_gas_limit() {
-                    return Ok(Transition::Push(Box::new(
-                        ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
-                if let Err(e) = self.validate_fee_caps() {
-                    return Ok(Transition::Push(Box::new(
+                let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+                    Ok(gas) => gas,
+                    Err(e) => return Ok(Transition::Push(Box::new(
                         ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
+                    ))),
+                };
 
                 // Create, sign, and write the single-entry JSON
-                match self.create_and_write_redelegation().await {
-                    Ok(path) => {
-                        let lines = vec![
+                match self.create_and_write_redelegation(&*ctx.fs, &ctx.settings, &ctx.key_usage, gas).await {
+                    Ok((path, from)) => {
+                        ctx.events.emit(Event::file_written(None, &path));
+                        let mut lines = vec![
                             "Saved signed re-delegation transaction:".to_string(),
                             "".to_string(),
                             path.display().to_string(),
                         ];
+                        let written = Written::One { func: "createRevocationEventFollowedByDelegationEvent", from: &from };
+                        lines.extend(record_signed(&mut ctx.key_usage, ctx.profile_key.as_ref(), ctx.settings.usage_stats, written));
                         return Ok(Transition::Push(Box::new(
                             ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
                         )));
                     }
                     Err(e) => {
+                        if let Some(retired) = e.downcast_ref::<RetiredKey>() {
+                            let lines = retired.confirm_lines("Create Re-Delegation");
+                            self.retired_ack = Some(retired.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                        if let Some(reuse) = e.downcast_ref::<KeyReuse>() {
+                            let lines = reuse.confirm_lines("Create Re-Delegation");
+                            self.key_reuse_ack = Some(reuse.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
                         return Ok(Transition::Push(Box::new(
                             ConfirmOkScreen::new(&format!("Error: {e:#}"))
                                 .with_after_ok(AfterOk::Pop)
@@ -514,14 +774,22 @@ impl ScreenWidget for CreateRedelegationScreen {
             }
 
             // Enter on [Load From File]
-            KeyCode::Enter if self.field_index == 11 => {
+            KeyCode::Enter if self.field_index == 17 => {
+                return Ok(Transition::Push(Box::new(
+                    ChooseRedelegationInfoDirScreen::resume(ctx)
+                )));
+            }
+
+            // Enter on [Clone Signed File]: start where this form writes signed files
+            KeyCode::Enter if self.field_index == 18 => {
                 return Ok(Transition::Push(Box::new(
-                    ChooseRedelegationInfoDirScreen::new()
+                    SelectSignedFileScreen::new("createRevocationEventFollowedByDelegationEvent", PathBuf::from(self.out_dir.text.trim()), ctx.fs.clone())
                 )));
             }
 
             // Enter on [Back]
-            KeyCode::Enter if self.field_index == 12 => {
+            KeyCode::Enter if self.field_index == 19 => {
+                self.leave(ctx);
                 return Ok(Transition::Pop); // Back
             }
 
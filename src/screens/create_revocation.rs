@@ -10,62 +10,146 @@ use ratatui::{
 };
 use textwrap::wrap;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form, DRAFT_RESTORED_NOTE};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
-use crate::ui::common_nav::esc_to_back;
-use crate::ui::components::{TextField, field_line_text};
+use crate::ui::common_nav::{esc_to_back, is_load_key_file_key};
+use crate::ui::key_file_fields::KeyFileFields;
+use crate::ui::components::{TextField, field_line_text, funding_lines, info_line_text, warning_banner_lines};
+use crate::ui::out_dir_prompt::{OutDirChoice, OutDirPrompt};
+use crate::ui::prefill_summary::{PrefillField, PrefillSummary};
+use crate::units::{check_window, GasLimit, Nonce, PayloadNonce, UnixTime, Wei};
+use crate::validation::validate_gas_fields;
 use crate::defaults::Defaults;
+use crate::display::DisplaySettings;
+use crate::settings::{GasLimits, Settings};
 
 // Generic OK-only modal
-use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::{ConfirmOkScreen, AfterOk, LoadSigningKeyScreen};
 
 // bring in ABI loader, processor, types, writer helpers
 use crate::abi::load_abi;
-use crate::process::{process_item, BatchOpts};
+use crate::process::{key_reuse, process_item, retired_keys, BatchOpts, KeyReuse, RetiredKey};
+use crate::key_usage::{record_signed, KeyUsage};
+use crate::usage_stats::Written;
+use crate::signing::SignerCache;
+use crate::vfs::Fs;
+use crate::event_stream::Event;
+use crate::lint;
+use crate::simulate;
 use crate::types::Item;
 use crate::write_signed_transactions_to_file::{
     write_single_signed_transaction,
     build_filename_for_any_tx,
+    signed_file_metadata,
+};
+use crate::filename_template::{
+    base_vars, insert_key_vars, insert_label, render_template_preview,
+    x_coord_from_privkey_input, x_coord_from_pubkey_input, FilenameTemplates, KeyRole,
 };
 
 // load-from-file flow (directory picker) — revocation version
-use crate::screens::ChooseRevocationInfoDirScreen;
+use crate::screens::{ChooseRevocationInfoDirScreen, SelectSignedFileScreen};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Transactions),
+        order: 20,
+        title: "Create Revocation",
+        description: "Sign one revocation from manual input or an info file.",
+        open: |ctx| Transition::Push(Box::new(CreateRevocationScreen::resume(ctx))),
+    }
+}
+
+/// Info-file keys this form reads, for the summary shown after Load From File.
+const PREFILL_FIELDS: &[PrefillField] = &[
+    PrefillField { key: "REVOKER_PRIVKEY", label: "Revoker PrivKey", secret: true },
+    PrefillField { key: "REVOKEE_PRIVKEY", label: "Revokee PrivKey", secret: true },
+    PrefillField { key: "REVOKEE_PUBKEY", label: "Revokee PubKey", secret: false },
+    PrefillField { key: "REVOCATION_START", label: "Revocation Start", secret: false },
+    PrefillField { key: "REVOCATION_END", label: "Revocation End", secret: false },
+    PrefillField { key: "NONCE", label: "Transaction Nonce", secret: false },
+    PrefillField { key: "REVOCATION_PAYLOAD_NONCE", label: "Payload Nonce", secret: false },
+    PrefillField { key: "GAS_LIMIT", label: "Gas limit", secret: false },
+    PrefillField { key: "MAX_FEE_PER_GAS", label: "Maximum Fee Per Gas", secret: false },
+    PrefillField { key: "MAX_PRIORITY_FEE_PER_GAS", label: "Maximum Priority Fee Per Gas", secret: false },
+    PrefillField { key: "LABEL", label: "Notes", secret: false },
+    PrefillField { key: "OUTPUT_DIRECTORY", label: "Output Directory", secret: false },
+];
+
+/// Private-key fields Ctrl+K can load from an encrypted key file, with their labels.
+const KEY_FILE_FIELDS: &[(usize, &str)] = &[(0, "Revoker PrivKey"), (1, "Revokee PrivKey")];
 
 pub struct CreateRevocationScreen {
-    // 0 revoker_priv, 1 revokee_priv, 2 revokee_pubkey,
-    // 3 nonce, 4 gas_limit, 5 max_fee_per_gas, 6 max_priority_fee_per_gas,
-    // 7 out_dir, 8 submit, 9 load_from_file, 10 back
+    // 0 revoker_priv, 1 revokee_priv, 2 revokee_pubkey, 3 start, 4 end,
+    // 5 nonce, 6 payload_nonce, 7 gas_limit, 8 max_fee_per_gas, 9 max_priority_fee_per_gas,
+    // 10 out_dir, 11 notes, 12 submit, 13 load_from_file, 14 clone_signed, 15 back
     field_index: usize,
     revoker_priv: TextField,
     revokee_priv: TextField,
     revokee_pubkey: TextField,
+    /// Validity window (see `units::UnixTime`); empty = open.
+    start_time: TextField,
+    end_time: TextField,
     nonce: TextField,
+    /// Fixed `bytes16` payload nonce (see `units::PayloadNonce`); empty = random. Not kept in
+    /// drafts, so a fixed nonce is never reused by accident.
+    payload_nonce: TextField,
     gas_limit: TextField,
     max_fee_per_gas: TextField,
     max_priority_fee_per_gas: TextField,
     out_dir: TextField,
+    /// Becomes the item's label (see `types::Item`).
+    notes: TextField,
+    /// SHA-256 of the info file last loaded via Load From File (recorded in output metadata).
+    input_sha256: Option<String>,
+    /// Open while an Output Directory from a loaded file awaits confirmation.
+    out_dir_prompt: Option<OutDirPrompt>,
+    /// What the last loaded file set; shown once, before any Output Directory prompt.
+    prefill_summary: Option<PrefillSummary>,
+    /// Key reuse the operator was warned about; the next submit with the same keys signs anyway.
+    key_reuse_ack: Option<KeyReuse>,
+    /// Retired keys the operator was warned about; the next submit with the same keys signs anyway.
+    retired_ack: Option<RetiredKey>,
+    /// Reopened with the values of the last visit (see `resume`).
+    restored: bool,
+    /// Private-key fields loaded from key files, shown by source instead of value.
+    key_files: KeyFileFields,
 }
 
 impl CreateRevocationScreen {
-    pub fn new() -> Self {
+    pub fn new(gas_limits: &GasLimits) -> Self {
         Self {
             field_index: 0,
             revoker_priv: TextField::with(""),
             revokee_priv: TextField::with(""),
             revokee_pubkey: TextField::with(""),
+            start_time: TextField::with(""),
+            end_time: TextField::with(""),
             nonce: TextField::with(""),
-            gas_limit: TextField::with(Defaults::GAS_LIMIT),
+            payload_nonce: TextField::with(""),
+            gas_limit: TextField::with(gas_limits.default_for("createRevocationEvent")),
             max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
             max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
             out_dir: TextField::with(Defaults::CREATE_REVOCATION_OUT_DIR),
+            notes: TextField::with(""),
+            input_sha256: None,
+            out_dir_prompt: None,
+            prefill_summary: None,
+            key_reuse_ack: None,
+            retired_ack: None,
+            restored: false,
+            key_files: KeyFileFields::default(),
         }
     }
 
     fn is_text(&self) -> bool {
-        matches!(self.field_index, 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7)
+        matches!(self.field_index, 0..=11)
     }
 
     fn tf_ref(&self, idx: usize) -> &TextField {
@@ -73,11 +157,15 @@ impl CreateRevocationScreen {
             0 => &self.revoker_priv,
             1 => &self.revokee_priv,
             2 => &self.revokee_pubkey,
-            3 => &self.nonce,
-            4 => &self.gas_limit,
-            5 => &self.max_fee_per_gas,
-            6 => &self.max_priority_fee_per_gas,
-            7 => &self.out_dir,
+            3 => &self.start_time,
+            4 => &self.end_time,
+            5 => &self.nonce,
+            6 => &self.payload_nonce,
+            7 => &self.gas_limit,
+            8 => &self.max_fee_per_gas,
+            9 => &self.max_priority_fee_per_gas,
+            10 => &self.out_dir,
+            11 => &self.notes,
             _ => unreachable!("tf_ref called on non-text field"),
         }
     }
@@ -87,11 +175,15 @@ impl CreateRevocationScreen {
             0 => &mut self.revoker_priv,
             1 => &mut self.revokee_priv,
             2 => &mut self.revokee_pubkey,
-            3 => &mut self.nonce,
-            4 => &mut self.gas_limit,
-            5 => &mut self.max_fee_per_gas,
-            6 => &mut self.max_priority_fee_per_gas,
-            7 => &mut self.out_dir,
+            3 => &mut self.start_time,
+            4 => &mut self.end_time,
+            5 => &mut self.nonce,
+            6 => &mut self.payload_nonce,
+            7 => &mut self.gas_limit,
+            8 => &mut self.max_fee_per_gas,
+            9 => &mut self.max_priority_fee_per_gas,
+            10 => &mut self.out_dir,
+            11 => &mut self.notes,
             _ => unreachable!("tf_mut called on non-text field"),
         }
     }
@@ -104,45 +196,118 @@ impl CreateRevocationScreen {
 
     // Apply pending prefill from ctx (identical pattern to delegation, but with revocation keys)
     fn apply_prefill_if_any(&mut self, ctx: &mut AppCtx) {
-        if let Some(prefill) = ctx.pending_revocation_prefill.take() {
-            if let Some(v) = prefill.map.get("REVOKER_PRIVKEY") {
-                Self::set_textfield(&mut self.revoker_priv, v);
-            }
-            if let Some(v) = prefill.map.get("REVOKEE_PRIVKEY") {
-                Self::set_textfield(&mut self.revokee_priv, v);
-            }
-            if let Some(v) = prefill.map.get("REVOKEE_PUBKEY") {
-                Self::set_textfield(&mut self.revokee_pubkey, v);
-            }
-            if let Some(v) = prefill.map.get("NONCE") {
-                Self::set_textfield(&mut self.nonce, v);
-            }
-            if let Some(v) = prefill.map.get("GAS_LIMIT") {
-                Self::set_textfield(&mut self.gas_limit, v);
-            }
-            if let Some(v) = prefill.map.get("MAX_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_fee_per_gas, v);
-            }
-            if let Some(v) = prefill.map.get("MAX_PRIORITY_FEE_PER_GAS") {
-                Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
-            }
+        if let Some(key) = ctx.take_signing_key(self.crumb(), Form::Revocation) {
+            *self.tf_mut(key.field) = self.key_files.fill(&key);
+        }
+        if let Some(prefill) = ctx.take_prefill(self.crumb(), Form::Revocation) {
+            self.input_sha256 = prefill.source_sha256.clone();
+            self.prefill_summary = Some(PrefillSummary::new(PREFILL_FIELDS, &prefill));
+            self.apply_values(&prefill.map);
             if let Some(v) = prefill.map.get("OUTPUT_DIRECTORY") {
-                Self::set_textfield(&mut self.out_dir, v);
+                // Never taken silently: the operator confirms the resolved path first.
+                self.out_dir_prompt = OutDirPrompt::for_prefill(v, &self.out_dir.text, ctx);
             }
         }
     }
 
-    // One horizontal line: < Create Revocation >   < Load From File >   < Back >
-    fn buttons_line(submit_selected: bool, load_selected: bool, back_selected: bool) -> Line<'static> {
+    /// Set the fields named in `map` (info-file keys), except the Output Directory.
+    fn apply_values(&mut self, map: &HashMap<String, String>) {
+        if let Some(v) = map.get("REVOKER_PRIVKEY") {
+            Self::set_textfield(&mut self.revoker_priv, v);
+        }
+        if let Some(v) = map.get("REVOKEE_PRIVKEY") {
+            Self::set_textfield(&mut self.revokee_priv, v);
+        }
+        if let Some(v) = map.get("REVOKEE_PUBKEY") {
+            Self::set_textfield(&mut self.revokee_pubkey, v);
+        }
+        if let Some(v) = map.get("REVOCATION_START") {
+            Self::set_textfield(&mut self.start_time, v);
+        }
+        if let Some(v) = map.get("REVOCATION_END") {
+            Self::set_textfield(&mut self.end_time, v);
+        }
+        if let Some(v) = map.get("NONCE") {
+            Self::set_textfield(&mut self.nonce, v);
+        }
+        if let Some(v) = map.get("REVOCATION_PAYLOAD_NONCE") {
+            Self::set_textfield(&mut self.payload_nonce, v);
+        }
+        if let Some(v) = map.get("GAS_LIMIT") {
+            Self::set_textfield(&mut self.gas_limit, v);
+        }
+        if let Some(v) = map.get("MAX_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("MAX_PRIORITY_FEE_PER_GAS") {
+            Self::set_textfield(&mut self.max_priority_fee_per_gas, v);
+        }
+        if let Some(v) = map.get("LABEL") {
+            Self::set_textfield(&mut self.notes, v);
+        }
+    }
+
+    /// A new form holding the values it was last left with, if any.
+    pub fn resume(ctx: &mut AppCtx) -> Self {
+        let mut screen = Self::new(&ctx.settings.gas_limits);
+        if let Some(draft) = ctx.take_draft(screen.crumb(), Form::Revocation) {
+            screen.apply_values(&draft);
+            if let Some(dir) = draft.get("OUTPUT_DIRECTORY") {
+                Self::set_textfield(&mut screen.out_dir, dir);
+            }
+            screen.restored = true;
+        }
+        screen
+    }
+
+    /// Keep the non-secret values for the next visit (private keys are left out).
+    fn leave(&self, ctx: &mut AppCtx) {
+        let mut map = HashMap::new();
+        for (key, tf) in [
+            ("REVOKEE_PUBKEY", &self.revokee_pubkey),
+            ("REVOCATION_START", &self.start_time),
+            ("REVOCATION_END", &self.end_time),
+            ("NONCE", &self.nonce),
+            ("GAS_LIMIT", &self.gas_limit),
+            ("MAX_FEE_PER_GAS", &self.max_fee_per_gas),
+            ("MAX_PRIORITY_FEE_PER_GAS", &self.max_priority_fee_per_gas),
+            ("LABEL", &self.notes),
+            ("OUTPUT_DIRECTORY", &self.out_dir),
+        ] {
+            map.insert(key.to_string(), tf.text.clone());
+        }
+        ctx.dispatch(self.crumb(), AppEvent::FormLeft { form: Form::Revocation, draft: map });
+    }
+
+    // One horizontal line: < Create Revocation >   < Load From File >   < Clone Signed File >   < Back >
+    fn buttons_line(submit_selected: bool, load_selected: bool, clone_selected: bool, back_selected: bool) -> Line<'static> {
         let mut spans: Vec<Span<'static>> = Vec::new();
         spans.extend(button_spans("Create Revocation", submit_selected));
         spans.push(Span::raw("   "));
         spans.extend(button_spans("Load From File", load_selected));
         spans.push(Span::raw("   "));
+        spans.extend(button_spans("Clone Signed File", clone_selected));
+        spans.push(Span::raw("   "));
         spans.extend(button_spans("Back", back_selected));
         Line::from(spans)
     }
 
+    /// Start and end as entered, checked as a window.
+    fn window(&self) -> Result<(UnixTime, UnixTime)> {
+        let start: UnixTime = self.start_time.text.parse().context("Revocation Start")?;
+        let end: UnixTime = self.end_time.text.parse().context("Revocation End")?;
+        check_window("Revocation", start, end)?;
+        Ok((start, end))
+    }
+
+    /// The window as it will be signed, in the configured display format.
+    fn window_preview(&self, display: &DisplaySettings) -> String {
+        match self.window() {
+            Ok((start, end)) => display.window(start.0, end.0),
+            Err(e) => format!("{e:#}"),
+        }
+    }
+
     fn ensure_out_dir_nonempty(&self) -> Result<PathBuf> {
         let out_dir = self.out_dir.text.trim();
         if out_dir.is_empty() {
@@ -151,8 +316,28 @@ impl CreateRevocationScreen {
         Ok(PathBuf::from(out_dir))
     }
 
+    /// Live preview of the output filename from the current inputs.
+    fn filename_preview(&self, templates: &FilenameTemplates) -> String {
+        let mut vars = base_vars("createRevocationEvent", self.nonce.text.trim(), &Defaults::CHAIN_ID.to_string());
+        if let Some(x) = x_coord_from_privkey_input(&self.revoker_priv.text) {
+            insert_key_vars(&mut vars, KeyRole::Revoker, &x);
+        }
+        // Revokee: privkey wins if present, else pubkey (same precedence as process.rs)
+        let revokee_x = if self.revokee_priv.text.trim().is_empty() {
+            x_coord_from_pubkey_input(&self.revokee_pubkey.text)
+        } else {
+            x_coord_from_privkey_input(&self.revokee_priv.text)
+        };
+        if let Some(x) = revokee_x {
+            insert_key_vars(&mut vars, KeyRole::Revokee, &x);
+        }
+        insert_label(&mut vars, Some(&self.notes.text));
+        render_template_preview(&templates.revocation, &vars)
+    }
+
     /// Create, sign, and write a single revocation tx using process_item() + writer.
-    async fn create_and_write_revocation(&self) -> Result<PathBuf> {
+    /// Returns the written file and the transaction sender, for the key usage store.
+    async fn create_and_write_revocation(&self, fs: &dyn Fs, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<(PathBuf, String)> {
         // Validate required secrets
         let pk_x = self.revoker_priv.text.trim();
         let pk_y = self.revokee_priv.text.trim();
@@ -165,15 +350,25 @@ impl CreateRevocationScreen {
             anyhow::bail!("Provide either Revokee PrivKey or Revokee PubKey.");
         }
 
+        let (start, end) = self.window()?;
+
         // Parse nonce
         let nonce_str = self.nonce.text.trim();
-        let nonce: u64 = nonce_str.parse().context("Nonce must be an integer")?;
-
-        // Parse / collect gas opts
-        let opts = BatchOpts {
-            gas_limit: self.gas_limit.text.trim().to_string(),
-            max_fee_per_gas: self.max_fee_per_gas.text.trim().to_string(),
-            max_priority_fee_per_gas: self.max_priority_fee_per_gas.text.trim().to_string(),
+        let nonce: Nonce = nonce_str.parse().context("Nonce must be an integer")?;
+        let payload_nonce: PayloadNonce = self.payload_nonce.text.parse().context("Payload Nonce")?;
+
+        // Gas opts, checked by the caller
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let mut opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
         };
 
         // Build ABI
@@ -185,6 +380,7 @@ impl CreateRevocationScreen {
             nonce: Some(nonce),
             chain_id: Some(Defaults::CHAIN_ID),
             contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+            label: Some(self.notes.text.clone()),
 
             // Type A (unused)
             type_a_privkey_x: None,
@@ -193,134 +389,93 @@ impl CreateRevocationScreen {
             type_a_uint_x: None,
             type_a_uint_y: None,
             type_a_boolean: None,
+            type_a_nonce16: None,
 
             // Type B
             type_b_privkey_x: Some(pk_x.to_string()),
             type_b_privkey_y: Some(pk_y.to_string()),   // may be empty string; process.rs prefers privkey if non-empty
             type_b_pubkey_y: Some(pub_y.to_string()),   // otherwise falls back to pubkey if non-empty
-            type_b_uint_x: Some(0),
-            type_b_uint_y: Some(0),
+            type_b_uint_x: Some(start.0),
+            type_b_uint_y: Some(end.0),
+            type_b_nonce16: payload_nonce.hex(),
 
             // Type C (unused)
             type_c_privkey_x: None,
+            type_c_nonce16: None,
         };
 
+        // A reuse already confirmed for these exact keys is signed as is
+        opts.allow_key_reuse = self.key_reuse_ack.is_some() && key_reuse(&item, &opts.signers) == self.key_reuse_ack;
+        opts.allow_retired_key =
+            self.retired_ack.is_some() && retired_keys(&item, &opts.signers, &opts.retired_keys) == self.retired_ack;
+
         // Build & sign the transaction
         let entry = process_item(&abi, &opts, &item)
             .await
             .context("failed to construct and sign revocation transaction")?;
+        lint::check_entries(std::slice::from_ref(&entry))?;
+        simulate::check_entries(settings, std::slice::from_ref(&entry))?;
 
         // Filename per builder (will reflect revocation details)
-        let filename = build_filename_for_any_tx(&entry.decoded_tx);
+        let filename = build_filename_for_any_tx(&entry, &settings.filename_templates);
         let mut out_path = self.ensure_out_dir_nonempty()?;
         out_path.push(filename);
-        let written = write_single_signed_transaction(&out_path, &entry, true)
+        let metadata = signed_file_metadata(settings, self.input_sha256.clone(), Some("createRevocationEvent"));
+        let written = write_single_signed_transaction(fs, &out_path, &entry, metadata.as_ref(), opts.encoding.encoder())
             .context("failed to write signed transaction file")?;
 
-        Ok(written)
-    }
-
-    fn validate_gas_limit(&self) -> Result<()> {
-        let max_str = Defaults::GAS_LIMIT.trim();
-        let max: u64 = max_str.parse().context("Defaults::GAS_LIMIT must be an integer")?;
-
-        let user_str = self.gas_limit.text.trim();
-        let user: u64 = user_str.parse().context("Gas limit must be an integer")?;
-
-        if user == 0 {
-            anyhow::bail!("Gas limit must be greater than zero.");
-        }
-        if user > max {
-            anyhow::bail!(format!(
-                "Gas limit {} exceeds the maximum allowed {}.",
-                user, max
-            ));
-        }
-        Ok(())
-    }
-
-    fn validate_fee_caps(&self) -> Result<()> {
-        // maxFeePerGas cap
-        let max_fee_cap_str = Defaults::MAX_FEE_PER_GAS.trim();
-        let max_fee_cap: u64 = max_fee_cap_str
-            .parse()
-            .context("Defaults::MAX_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_max_fee_str = self.max_fee_per_gas.text.trim();
-        let user_max_fee: u64 = user_max_fee_str
-            .parse()
-            .context("Maximum Fee Per Gas must be an integer (wei)")?;
-        if user_max_fee == 0 {
-            anyhow::bail!("Maximum Fee Per Gas must be greater than zero.");
-        }
-        if user_max_fee > max_fee_cap {
-            anyhow::bail!(format!(
-                "Maximum Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_max_fee, max_fee_cap
-            ));
-        }
-
-        // maxPriorityFeePerGas cap
-        let max_prio_cap_str = Defaults::MAX_PRIORITY_FEE_PER_GAS.trim();
-        let max_prio_cap: u64 = max_prio_cap_str
-            .parse()
-            .context("Defaults::MAX_PRIORITY_FEE_PER_GAS must be an integer (wei)")?;
-
-        let user_prio_str = self.max_priority_fee_per_gas.text.trim();
-        let user_prio: u64 = user_prio_str
-            .parse()
-            .context("Maximum Priority Fee Per Gas must be an integer (wei)")?;
-
-        // priority fee can be zero, but not above cap
-        if user_prio > max_prio_cap {
-            anyhow::bail!(format!(
-                "Maximum Priority Fee Per Gas {} exceeds the allowed maximum {} wei.",
-                user_prio, max_prio_cap
-            ));
-        }
-
-        // Sanity: priority <= max fee
-        if user_prio > user_max_fee {
-            anyhow::bail!("Maximum Priority Fee Per Gas cannot exceed Maximum Fee Per Gas.");
-        }
-
-        Ok(())
+        Ok((written, entry.decoded_tx.from))
     }
 }
 
 impl Default for CreateRevocationScreen {
-    fn default() -> Self { Self::new() }
+    fn default() -> Self { Self::new(&GasLimits::default()) }
 }
 
 #[async_trait]
 impl ScreenWidget for CreateRevocationScreen {
     fn apply_prefill(&mut self, ctx: &mut AppCtx) {
-        self.apply_prefill_if_any(ctx); // consumes ctx.pending_revocation_prefill exactly once
+        self.apply_prefill_if_any(ctx); // consumes the pending prefill exactly once (AppEvent::PrefillTaken)
     }
 
     fn title(&self) -> &str { "" }
 
-    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+    fn crumb(&self) -> &str { "Create Revocation" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
         let header_text = "Create Revocation";
-        let explanation_paras = [
+        let mut explanation_paras = vec![
             "Enter the fields below. The app will create and sign an EIP-1559 transaction",
-            "for createRevocationEvent and save a one-element JSON array (pretty-printed)",
-            "to your chosen output directory. The filename will be:",
-            "[revokerX]_revokes_[revokeeX]_nonce_[nonce].txt",
+            "for createRevocationEvent and save it as pretty-printed JSON (entries plus a metadata block)",
+            "to your chosen output directory. The filename follows the revocation template",
+            "in the settings file; a live preview is shown below the output directory.",
+            "Revocation Start and End bound when the revocation is in force; leave them empty for one that starts at once and never ends.",
         ];
+        if self.restored {
+            explanation_paras.push(DRAFT_RESTORED_NOTE);
+        }
 
         // === TOP BOX ===
         let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
         let header_lines = wrap(header_text, top_inner_width).len() as u16;
 
         let mut exp_lines = 0usize;
-        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
         let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
 
-        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+        // Unregistered chain/contract pair: a bold banner under the explanation.
+        let banner = ctx.settings.networks
+            .unknown_warning(Defaults::CHAIN_ID, Defaults::CONTRACT_ADDRESS)
+            .map(|w| warning_banner_lines(&w, top_inner_width))
+            .unwrap_or_default();
+        let banner_lines = if banner.is_empty() { 0 } else { banner.len() as u16 + 1 };
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + banner_lines;
 
-        // Middle: 11 focusable positions (0..=10) plus spacer
-        let middle_rows: u16 = 11 + 1;
+        // Middle: 15 field rows plus validity, filename preview, funding lines and spacer
+        let middle_rows: u16 = 15 + 5;
         let middle_needed = 2 + 2 + middle_rows;
 
         let footer_height = 3;
@@ -350,6 +505,10 @@ impl ScreenWidget for CreateRevocationScreen {
             for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
             if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
         }
+        if !banner.is_empty() {
+            expl_lines.push(Line::from(""));
+            expl_lines.extend(banner);
+        }
         let explanation_para = Paragraph::new(expl_lines)
             .alignment(Alignment::Left)
             .wrap(Wrap { trim: true });
@@ -362,37 +521,45 @@ impl ScreenWidget for CreateRevocationScreen {
 
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from("")); // spacer above first field
-        lines.push(field_line_text("Revoker PrivKey", self.tf_ref(0), self.field_index == 0));
-        lines.push(field_line_text("Revokee PrivKey (optional if PubKey is provided)", self.tf_ref(1), self.field_index == 1));
+        lines.push(self.key_files.line("Revoker PrivKey", 0, self.tf_ref(0), self.field_index == 0));
+        lines.push(self.key_files.line("Revokee PrivKey (optional if PubKey is provided)", 1, self.tf_ref(1), self.field_index == 1));
         lines.push(field_line_text("Revokee PubKey (0x04… uncompressed, optional)", self.tf_ref(2), self.field_index == 2));
-        lines.push(field_line_text("Transaction Nonce", self.tf_ref(3), self.field_index == 3));
+        lines.push(field_line_text("Revocation Start (Unix time or YYYY-MM-DD [HH:MM], UTC; empty = now)", self.tf_ref(3), self.field_index == 3));
+        lines.push(field_line_text("Revocation End (empty = no end)", self.tf_ref(4), self.field_index == 4));
+        lines.push(info_line_text("Validity", &self.window_preview(&ctx.settings.display)));
+        lines.push(field_line_text("Transaction Nonce", self.tf_ref(5), self.field_index == 5));
+        lines.push(field_line_text("Payload Nonce (0x + 32 hex digits or UUID; empty = random)", self.tf_ref(6), self.field_index == 6));
 
         // Gas limit (cap label)
-        let gas_label = format!("Gas limit (maximum {} gas)", Defaults::GAS_LIMIT);
-        lines.push(field_line_text(&gas_label, self.tf_ref(4), self.field_index == 4));
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(7), self.field_index == 7));
 
         // Max fee per gas (cap label)
         let mfg_label = format!(
             "Maximum Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_FEE_PER_GAS
+            ctx.settings.gas_caps.max_fee_per_gas()
         );
-        lines.push(field_line_text(&mfg_label, self.tf_ref(5), self.field_index == 5));
+        lines.push(field_line_text(&mfg_label, self.tf_ref(8), self.field_index == 8));
 
         // Max priority fee per gas (cap label)
         let mpfg_label = format!(
             "Maximum Priority Fee Per Gas (maximum {} wei)",
-            Defaults::MAX_PRIORITY_FEE_PER_GAS
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
         );
-        lines.push(field_line_text(&mpfg_label, self.tf_ref(6), self.field_index == 6));
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(9), self.field_index == 9));
 
         // Output directory
-        lines.push(field_line_text("Output Directory", self.tf_ref(7), self.field_index == 7));
+        lines.push(field_line_text("Output Directory", self.tf_ref(10), self.field_index == 10));
+        lines.push(field_line_text("Notes (label, optional)", self.tf_ref(11), self.field_index == 11));
+        lines.push(info_line_text("Filename Preview", &self.filename_preview(&ctx.settings.filename_templates)));
+        lines.extend(funding_lines(&self.revoker_priv.text, &self.gas_limit.text, &self.max_fee_per_gas.text, &ctx.settings.display));
 
         lines.push(Line::from("")); // spacer
         lines.push(Self::buttons_line(
-            self.field_index == 8,
-            self.field_index == 9,
-            self.field_index == 10
+            self.field_index == 12,
+            self.field_index == 13,
+            self.field_index == 14,
+            self.field_index == 15
         ));
 
         let middle_para = Paragraph::new(lines);
@@ -405,16 +572,38 @@ impl ScreenWidget for CreateRevocationScreen {
             span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
             span_key("Enter"),   span_text(" Select"), span_sep(),
             span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+K"),  span_text(" Key File"), span_sep(),
             span_key("Ctrl+Q"),  span_text(" Quit"),
         ]);
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some(summary) = &self.prefill_summary {
+            summary.draw(f, size);
+        } else if let Some(prompt) = &self.out_dir_prompt {
+            prompt.draw(f, size);
+        }
     }
 
     async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         // Apply pending prefill if any
         self.apply_prefill_if_any(ctx);
 
+        // Any key dismisses the summary.
+        if self.prefill_summary.take().is_some() {
+            return Ok(Transition::Stay);
+        }
+        if let Some(prompt) = self.out_dir_prompt.as_mut() {
+            match prompt.on_key(k) {
+                Some(OutDirChoice::Use(dir)) => Self::set_textfield(&mut self.out_dir, &dir),
+                Some(OutDirChoice::Keep) => {}
+                None => return Ok(Transition::Stay),
+            }
+            self.out_dir_prompt = None;
+            return Ok(Transition::Stay);
+        }
+
         if let Some(t) = esc_to_back(k) {
+            self.leave(ctx);
             return Ok(t); // Esc -> Back
         }
 
@@ -424,42 +613,71 @@ impl ScreenWidget for CreateRevocationScreen {
             }
         }
 
+        // Ctrl+K on a private-key field: load it from an encrypted key file
+        if is_load_key_file_key(&k) {
+            if let Some(&(idx, label)) = KEY_FILE_FIELDS.iter().find(|(i, _)| *i == self.field_index) {
+                return Ok(Transition::Push(Box::new(LoadSigningKeyScreen::new(Form::Revocation, idx, label, ctx.fs.clone()))));
+            }
+            return Ok(Transition::Stay);
+        }
+        // A key loaded from a file is replaced, never edited unseen
+        if matches!(k.code, KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_))
+            && self.is_text()
+            && self.key_files.source(self.field_index, self.tf_ref(self.field_index)).is_some()
+        {
+            self.key_files.forget(self.field_index);
+            *self.tf_mut(self.field_index) = TextField::default();
+        }
+
         match k.code {
             // Navigation
             KeyCode::Up => {
-                if self.field_index == 0 { self.field_index = 10; } else { self.field_index -= 1; }
+                if self.field_index == 0 { self.field_index = 15; } else { self.field_index -= 1; }
             }
             KeyCode::Down | KeyCode::Tab => {
-                self.field_index = (self.field_index + 1) % 11;
+                self.field_index = (self.field_index + 1) % 16;
             }
 
             // Enter on [Create Revocation]
-            KeyCode::Enter if self.field_index == 8 => {
+            KeyCode::Enter if self.field_index == 12 => {
                 // Enforce caps first
-                if let Err(e) = self.validate_gas_limit() {
-                    return Ok(Transition::Push(Box::new(
-                        ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
-                if let Err(e) = self.validate_fee_caps() {
-                    return Ok(Transition::Push(Box::new(
+                let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+                    Ok(gas) => gas,
+                    Err(e) => return Ok(Transition::Push(Box::new(
                         ConfirmOkScreen::new(&format!("Error: {e}")).with_after_ok(AfterOk::Pop)
-                    )));
-                }
+                    ))),
+                };
 
                 // Create, sign, and write the single-entry JSON
-                match self.create_and_write_revocation().await {
-                    Ok(path) => {
-                        let lines = vec![
+                match self.create_and_write_revocation(&*ctx.fs, &ctx.settings, &ctx.key_usage, gas).await {
+                    Ok((path, from)) => {
+                        ctx.events.emit(Event::file_written(None, &path));
+                        let mut lines = vec![
                             "Saved signed revocation transaction:".to_string(),
                             "".to_string(),
                             path.display().to_string(),
                         ];
+                        let written = Written::One { func: "createRevocationEvent", from: &from };
+                        lines.extend(record_signed(&mut ctx.key_usage, ctx.profile_key.as_ref(), ctx.settings.usage_stats, written));
                         return Ok(Transition::Push(Box::new(
                             ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
                         )));
                     }
                     Err(e) => {
+                        if let Some(retired) = e.downcast_ref::<RetiredKey>() {
+                            let lines = retired.confirm_lines("Create Revocation");
+                            self.retired_ack = Some(retired.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                        if let Some(reuse) = e.downcast_ref::<KeyReuse>() {
+                            let lines = reuse.confirm_lines("Create Revocation");
+                            self.key_reuse_ack = Some(reuse.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
                         return Ok(Transition::Push(Box::new(
                             ConfirmOkScreen::new(&format!("Error: {e:#}"))
                                 .with_after_ok(AfterOk::Pop)
@@ -469,14 +687,22 @@ impl ScreenWidget for CreateRevocationScreen {
             }
 
             // Enter on [Load From File]
-            KeyCode::Enter if self.field_index == 9 => {
+            KeyCode::Enter if self.field_index == 13 => {
+                return Ok(Transition::Push(Box::new(
+                    ChooseRevocationInfoDirScreen::resume(ctx)
+                )));
+            }
+
+            // Enter on [Clone Signed File]: start where this form writes signed files
+            KeyCode::Enter if self.field_index == 14 => {
                 return Ok(Transition::Push(Box::new(
-                    ChooseRevocationInfoDirScreen::new()
+                    SelectSignedFileScreen::new("createRevocationEvent", PathBuf::from(self.out_dir.text.trim()), ctx.fs.clone())
                 )));
             }
 
             // Enter on [Back]
-            KeyCode::Enter if self.field_index == 10 => {
+            KeyCode::Enter if self.field_index == 15 => {
+                self.leave(ctx);
                 return Ok(Transition::Pop); // Back
             }
 
@@ -0,0 +1,281 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ethers_core::utils::{keccak256, to_checksum};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::abi::load_abi;
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::decoder::{decode_calldata_to_json, DecodedOne};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+use crate::settings::Settings;
+use crate::signing::{decode_signed_tx_and_recover, signed_tx_has_high_s, HIGH_S_TX_WARNING};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::util::{bytes_to_0x, hex_to_bytes};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 35,
+        title: "Decode Raw Transaction",
+        description: "Paste a raw 0x02… transaction to see its fields, recovered sender and decoded Inkan call.",
+        open: |_| Transition::Push(Box::new(DecodeRawTxScreen::new())),
+    }
+}
+
+/// Offline decoder for a pasted raw transaction, e.g. one a colleague sent for review. Nothing
+/// is signed or written; the decoded fields are shown in a scrollable pane.
+pub struct DecodeRawTxScreen {
+    // 0 raw hex, 1 decode, 2 clear, 3 back
+    field_index: usize,
+    raw: TextField,
+    /// The last decode: its lines, or why it failed.
+    decoded: Option<Result<Vec<String>, String>>,
+    /// First decoded line shown.
+    scroll: usize,
+}
+
+impl DecodeRawTxScreen {
+    pub fn new() -> Self {
+        Self { field_index: 0, raw: TextField::default(), decoded: None, scroll: 0 }
+    }
+
+    fn decode(&mut self, settings: &Settings) {
+        self.decoded = Some(describe(&self.raw.text, settings).map_err(|e| format!("{e:#}")));
+        self.scroll = 0;
+    }
+
+    /// The input line; a long paste shows its ends and length instead of overflowing the box.
+    fn raw_line(&self, width: usize) -> Line<'static> {
+        let label = "Raw Transaction (hex)";
+        let text = &self.raw.text;
+        let focused = self.field_index == 0;
+        if label.len() + 2 + text.len() < width || text.len() < 40 || !text.is_ascii() {
+            return field_line_text(label, &self.raw, focused);
+        }
+        let mut spans = vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{}…{} ({} characters)", &text[..18], &text[text.len() - 8..], text.len())),
+        ];
+        if focused {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(" ", Style::default().bg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        }
+        Line::from(spans)
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (i, label) in ["Decode", "Clear", "Back"].into_iter().enumerate() {
+            if i > 0 { spans.push(Span::raw("   ")); }
+            spans.extend(button_spans(label, self.field_index == i + 1));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Default for DecodeRawTxScreen {
+    fn default() -> Self { Self::new() }
+}
+
+/// The transaction fields, recovered sender and decoded call of a raw type-2 transaction,
+/// one line each. Whitespace in `raw` (from wrapped pastes) is ignored.
+fn describe(raw: &str, settings: &Settings) -> Result<Vec<String>> {
+    let raw: String = raw.chars().filter(|c| !c.is_whitespace()).collect();
+    if raw.is_empty() {
+        bail!("Paste a raw transaction first");
+    }
+    let bytes = hex_to_bytes(&raw).context("Raw Transaction is not hex")?;
+    let (chain_id, nonce, prio, fee, gas, to, value, data, from) =
+        decode_signed_tx_and_recover(&raw).context("not a signed EIP-1559 transaction")?;
+
+    let display = &settings.display;
+    let network = match settings.networks.find(chain_id, &format!("{to:?}")) {
+        Some(n) => format!("registered network \"{}\"", n.name),
+        None => "not a registered network".to_string(),
+    };
+    let mut lines = vec![
+        "Transaction (EIP-1559)".to_string(),
+        format!("  Hash:                  {}", bytes_to_0x(&keccak256(&bytes))),
+        format!("  From (recovered):      {}", to_checksum(&from, None)),
+        format!("  To:                    {}", to_checksum(&to, None)),
+        format!("  Chain ID:              {chain_id} ({network})"),
+        format!("  Nonce:                 {nonce}"),
+        format!("  Gas limit:             {}", display.integer(gas)),
+        format!("  Max fee per gas:       {}", display.fee(fee)),
+        format!("  Max priority fee:      {}", display.fee(prio)),
+        format!("  Value:                 {}", display.amount(value)),
+        String::new(),
+    ];
+    if signed_tx_has_high_s(&raw) {
+        lines.push(format!("Warning: {HIGH_S_TX_WARNING}."));
+        lines.push(String::new());
+    }
+
+    match decode_calldata_to_json(&load_abi()?, &data) {
+        Ok((func_name, one, two)) => {
+            lines.push(format!("Call: {func_name}"));
+            for decoded in [one, two].into_iter().flatten() {
+                let kind = match decoded {
+                    DecodedOne::Delegation(_) => "Delegation",
+                    DecodedOne::Revocation(_) => "Revocation",
+                    DecodedOne::Invalidation(_) => "Permanent invalidation",
+                };
+                lines.push(String::new());
+                lines.push(format!("{kind}:"));
+                let json = serde_json::to_string_pretty(&decoded)?;
+                lines.extend(json.lines().map(|l| format!("  {l}")));
+            }
+        }
+        Err(e) => {
+            lines.push(format!("Calldata is not an Inkan call ({e:#}); {} byte(s):", data.len()));
+            lines.push(bytes_to_0x(&data));
+        }
+    }
+    Ok(lines)
+}
+
+#[async_trait]
+impl ScreenWidget for DecodeRawTxScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Decode Raw Transaction" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Decode Raw Transaction";
+        let explanation_paras = [
+            "Paste a signed 0x02… transaction and select Decode. Its fields, the sender recovered from its signature and the Inkan call it makes are shown below. Nothing is signed or written.",
+            "The decoded structs come from the calldata itself. To check their signatures as well, use Verify Signed File on the file the transaction came in.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        let output_rows = match &self.decoded {
+            Some(Ok(lines)) => lines.len(),
+            Some(Err(_)) => 1,
+            None => 0,
+        } as u16;
+        // spacer + input + spacer + buttons + spacer + output
+        let middle_needed = 2 + 2 + 5 + output_rows.max(1);
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: input and buttons above the scrollable output
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(regions.middle_inner);
+        f.render_widget(Paragraph::new(self.raw_line(regions.middle_inner.width as usize)), mid_chunks[1]);
+        f.render_widget(Paragraph::new(self.buttons_line()), mid_chunks[3]);
+        let output: Vec<Line> = match &self.decoded {
+            Some(Ok(lines)) => lines.iter().skip(self.scroll).map(|l| Line::from(l.clone())).collect(),
+            Some(Err(e)) => vec![Line::styled(format!("Error: {e}"), Style::default().fg(Color::Red))],
+            None => Vec::new(),
+        };
+        f.render_widget(Paragraph::new(output).wrap(Wrap { trim: false }), mid_chunks[5]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"),   span_text(" Navigate"), span_sep(),
+            span_key("PgUp/PgDn"), span_text(" Scroll"), span_sep(),
+            span_key("Enter"),     span_text(" Select"), span_sep(),
+            span_key("Esc"),       span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),    span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let output_len = match &self.decoded {
+            Some(Ok(lines)) => lines.len(),
+            _ => 0,
+        };
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 3; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 4;
+            }
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(output_len.saturating_sub(1)),
+            KeyCode::Left if (2..=3).contains(&self.field_index) => self.field_index -= 1,
+            KeyCode::Right if (1..=2).contains(&self.field_index) => self.field_index += 1,
+
+            // Enter on the input or [Decode]
+            KeyCode::Enter if self.field_index <= 1 => self.decode(&ctx.settings),
+            // Enter on [Clear]
+            KeyCode::Enter if self.field_index == 2 => {
+                self.raw = TextField::default();
+                self.decoded = None;
+                self.scroll = 0;
+                self.field_index = 0;
+            }
+            // Enter on [Back]
+            KeyCode::Enter if self.field_index == 3 => return Ok(Transition::Pop),
+
+            // Text editing on the input
+            KeyCode::Left if self.field_index == 0 => self.raw.move_left(),
+            KeyCode::Right if self.field_index == 0 => self.raw.move_right(),
+            KeyCode::Home if self.field_index == 0 => self.raw.home(),
+            KeyCode::End if self.field_index == 0 => self.raw.end(),
+            KeyCode::Backspace if self.field_index == 0 => self.raw.backspace(),
+            KeyCode::Delete if self.field_index == 0 => self.raw.delete(),
+            KeyCode::Char(c) if self.field_index == 0 && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.raw.insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use async_trait::async_trait;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -9,7 +9,6 @@ use ratatui::{
 };
 use textwrap::wrap;
 
-use std::fs;
 use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
@@ -18,6 +17,17 @@ use crate::ui::style::{span_key, span_sep, span_text, button_spans};
 use crate::ui::common_nav::esc_to_back;
 use crate::ui::components::{TextField, field_line_text};
 use crate::defaults::Defaults;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Files),
+        order: 10,
+        title: "Decrypt File",
+        description: "Decrypt exported key files and containers, one or all at once.",
+        open: |_| Transition::Push(Box::new(DecryptFileScreen::new())),
+    }
+}
 
 #[derive(Default)]
 pub struct DecryptFileScreen {
@@ -47,6 +57,7 @@ impl DecryptFileScreen {
 #[async_trait]
 impl ScreenWidget for DecryptFileScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Decrypt File" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Decrypt File";
@@ -119,7 +130,7 @@ impl ScreenWidget for DecryptFileScreen {
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
     }
 
-    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         if let Some(t) = esc_to_back(k) { return Ok(t); }
 
         if let KeyCode::Char('q') = k.code {
@@ -146,17 +157,20 @@ impl ScreenWidget for DecryptFileScreen {
                     )));
                 }
                 let dir_path = PathBuf::from(dir);
-                fs::create_dir_all(&dir_path)
-                    .with_context(|| format!("creating directory {}", dir_path.display()))?;
-                let md = fs::metadata(&dir_path).with_context(|| format!("accessing {}", dir_path.display()))?;
-                if !md.is_dir() {
+                if let Err(e) = ctx.fs.create_dir_all(&dir_path) {
+                    return Ok(Transition::Push(Box::new(
+                        crate::screens::ConfirmOkScreen::new(format!("Error: creating directory {}: {e}", dir_path.display()))
+                            .with_after_ok(crate::screens::AfterOk::Pop)
+                    )));
+                }
+                if !ctx.fs.is_dir(&dir_path) {
                     return Ok(Transition::Push(Box::new(
                         crate::screens::ConfirmOkScreen::new("Error: Input Directory is not a directory.")
                             .with_after_ok(crate::screens::AfterOk::Pop)
                     )));
                 }
                 return Ok(Transition::Push(Box::new(
-                    crate::screens::SelectFileForDecryptionScreen::new(dir_path)
+                    crate::screens::SelectFileForDecryptionScreen::new(dir_path, ctx.fs.clone())
                 )));
             }
 
@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -9,6 +9,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 use textwrap::wrap;
+use zeroize::Zeroize;
 
 use std::fs;
 use std::path::PathBuf;
@@ -16,20 +17,35 @@ use std::path::PathBuf;
 use crate::app::{AppCtx, ScreenWidget, Transition};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
-use crate::ui::common_nav::esc_to_back;
-use crate::ui::components::{TextField, field_line_text};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{TextField, field_line_text, kdf_progress_lines, RevealBriefly, field_line_password, show_password_line};
 use crate::screens::{ConfirmOkScreen, AfterOk};
-use crate::commands::decrypt_auto::decrypt_auto;
+use crate::commands::decrypt_auto::{decrypt_in_memory, write_decrypted_output, DecryptedFile};
+use crate::commands::decrypt_modern::{modern_kdf_costs, modern_key_factors};
+use crate::crypto::factors::{mix_factors, KeyFactors};
+use crate::crypto::kdf_progress::{estimate, KdfJob};
+use crate::crypto::container::parse_container;
+use crate::crypto::detect::{detect_format, Detection, EncryptionFormat};
 use crate::defaults::Defaults;
 
 
 pub struct DecryptFileDetailsScreen {
-    // indices: 0 password, 1 show pwd toggle, 2 out dir, 3 submit, 4 cancel
+    // indices: 0 method, 1 password, 2 show pwd toggle, 3 keyfile, 4 out dir, 5 submit, 6 cancel
     field_index: usize,
     input_path: PathBuf,
+    /// What the file's first bytes say; the method can only be chosen when this is ambiguous.
+    detection: Detection,
+    /// Decryption method; `None` tries Modern, then OpenPGP.
+    method: Option<EncryptionFormat>,
     password: TextField,
+    keyfile: TextField,
+    /// What the file's header says it needs besides the password.
+    factors: KeyFactors,
     out_dir: TextField,
     show_password: bool,
+    reveal: RevealBriefly,
+    /// Decryption running on a worker thread, with the output directory it writes to.
+    decrypting: Option<(KdfJob<DecryptedFile>, PathBuf)>,
 }
 
 impl DecryptFileDetailsScreen {
@@ -37,46 +53,99 @@ impl DecryptFileDetailsScreen {
         // Instead of deriving from the input path, always start from the central default.
         let default_out_dir = Defaults::DECRYPT_OUTPUT_DIR.to_string();
 
+        let buf = fs::read(&input_path).unwrap_or_default();
+        let detection = detect_format(&buf);
+
         Self {
-            field_index: 0,
-            input_path,
+            // The method row is skipped when the format was detected
+            field_index: if detection.format().is_some() { 1 } else { 0 },
+            detection,
+            method: detection.format(),
             password: TextField::with(""),
+            keyfile: TextField::with(""),
+            factors: modern_key_factors(&buf),
             out_dir: TextField::with(&default_out_dir),
             show_password: false,
+            reveal: RevealBriefly::default(),
+            decrypting: None,
+            input_path,
         }
     }
 
 
-    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 2) }
+    fn is_text(&self) -> bool { matches!(self.field_index, 1 | 3 | 4) }
+
+    fn tf_mut(&mut self) -> &mut TextField {
+        match self.field_index {
+            1 => &mut self.password,
+            3 => &mut self.keyfile,
+            _ => &mut self.out_dir,
+        }
+    }
 
-    fn field_line_password(label: &str, tf: &TextField, selected: bool, show: bool) -> Line<'static> {
-        let render = if show { tf.text.clone() } else { "•".repeat(tf.text.chars().count()) };
+    /// Next focusable field in direction `step` (1 or 6, i.e. -1 mod 7).
+    fn step_field(&mut self, step: usize) {
+        self.field_index = (self.field_index + step) % 7;
+        if self.field_index == 0 && self.detection.format().is_some() {
+            self.field_index = step % 7;
+        }
+    }
 
-        let mut tmp = TextField::with(&render);
-        let cursor_chars = tf.cursor.min(render.chars().count());
-        let cursor_bytes = if cursor_chars == 0 {
-            0
-        } else {
-            render
-                .char_indices()
-                .nth(cursor_chars)
-                .map(|(i, _)| i)
-                .unwrap_or_else(|| render.len())
+    /// Ambiguous files cycle Try Both → Modern → OpenPGP.
+    fn cycle_method(&mut self) {
+        self.method = match self.method {
+            None => Some(EncryptionFormat::Modern),
+            Some(EncryptionFormat::Modern) => Some(EncryptionFormat::OpenPgp),
+            Some(EncryptionFormat::OpenPgp) => None,
         };
-        tmp.cursor = cursor_bytes;
-
-        field_line_text(label, &tmp, selected)
     }
 
-    fn show_password_line(&self, selected: bool) -> Line<'static> {
-        let label_span = Span::styled("Show Password: ", Style::default().fg(Color::Yellow));
-        let val = if self.show_password { "On" } else { "Off" };
+    fn method_line(&self, selected: bool) -> Line<'static> {
+        let label_span = Span::styled("Method: ", Style::default().fg(Color::Yellow));
+        let val = match (self.detection, self.method) {
+            (Detection::Detected(f), _) => format!("{} (detected)", f.label()),
+            (_, Some(f)) => f.label().to_string(),
+            (_, None) => "Try Both (Argon2id + XChaCha20-Poly1305, then OpenPGP)".to_string(),
+        };
         let val_style = if selected {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::White)
         };
-        Line::from(vec![label_span, Span::styled(val.to_string(), val_style)])
+        Line::from(vec![label_span, Span::styled(val, val_style)])
+    }
+
+    /// Write out (or hand to the key picker) what the worker thread decrypted.
+    fn decrypted(&self, decrypted: DecryptedFile, out_dir_path: PathBuf) -> Transition {
+        // Multi-key container -> let the user pick which key(s) to write out
+        if let Some(records) = parse_container(&decrypted.plaintext) {
+            return Transition::Push(Box::new(
+                crate::screens::SelectContainerKeyScreen::new(
+                    self.input_path.clone(),
+                    out_dir_path,
+                    decrypted.method_label.clone(),
+                    records,
+                    decrypted.plaintext.to_vec(),
+                )
+            ));
+        }
+
+        match write_decrypted_output(&self.input_path, &out_dir_path, None, &decrypted.plaintext) {
+            Ok(out_path) => {
+                let lines = vec![
+                    format!("Decryption successful ({}).", decrypted.method_label),
+                    "".to_string(),
+                    "Wrote decrypted output to:".to_string(),
+                    out_path.display().to_string(),
+                ];
+                Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::PopToMainMenu)
+                ))
+            }
+            Err(e) => Transition::Push(Box::new(
+                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+            )),
+        }
     }
 
     fn buttons_line(submit_selected: bool, cancel_selected: bool) -> Line<'static> {
@@ -91,25 +160,43 @@ impl DecryptFileDetailsScreen {
 #[async_trait]
 impl ScreenWidget for DecryptFileDetailsScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "File Details" }
+    fn shows_secrets(&self) -> bool { true }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Decrypt File";
-        let explanation_paras = [
-            "Confirm the file and enter the decryption parameters.",
-            &format!("Input File Path: {}", self.input_path.display()),
+        let mut explanation_paras = vec![
+            "Confirm the file and enter the decryption parameters.".to_string(),
+            format!("Input File Path: {}", self.input_path.display()),
         ];
+        match self.detection {
+            Detection::Detected(_) => {}
+            Detection::Both => explanation_paras.push(
+                "The file's first bytes fit both formats; choose the method if you know it.".to_string()
+            ),
+            Detection::Neither => explanation_paras.push(
+                "The format could not be detected (damaged or unknown file); choose the method if you know it.".to_string()
+            ),
+        }
+        if !self.factors.is_empty() {
+            explanation_paras.push(format!(
+                "This file also needs {}{}.",
+                self.factors.describe(),
+                if self.factors.fido2.is_some() { " (touch it when it blinks)" } else { "" },
+            ));
+        }
 
         // TOP sizing
         let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
         let header_lines = wrap(header_text, top_inner_width).len() as u16;
 
         let mut exp_lines = 0usize;
-        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
         let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
         let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
 
-        // Middle rows: spacer + password + show + outdir + spacer + buttons
-        let middle_rows: u16 = 5 + 1;
+        // Middle rows: spacer + method + password + show + keyfile + outdir + spacer + buttons
+        let middle_rows: u16 = 7 + 1;
         let middle_needed = 2 + 2 + middle_rows;
 
         let footer_height = 3;
@@ -142,11 +229,19 @@ impl ScreenWidget for DecryptFileDetailsScreen {
         f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from(""));
-        lines.push(Self::field_line_password("Password", &self.password, self.field_index == 0, self.show_password));
-        lines.push(self.show_password_line(self.field_index == 1));
-        lines.push(field_line_text("Output Directory", &self.out_dir, self.field_index == 2));
-        lines.push(Line::from(""));
-        lines.push(Self::buttons_line(self.field_index == 3, self.field_index == 4));
+        lines.push(self.method_line(self.field_index == 0));
+        lines.push(field_line_password("Password", &self.password, self.field_index == 1, self.show_password || self.reveal.shows(1, self.field_index)));
+        lines.push(show_password_line(self.show_password, self.field_index == 2));
+        lines.push(field_line_text("Keyfile (If Required)", &self.keyfile, self.field_index == 3));
+        lines.push(field_line_text("Output Directory", &self.out_dir, self.field_index == 4));
+        match &self.decrypting {
+            // Progress replaces spacer + buttons while the key is being derived
+            Some((job, _)) => lines.extend(kdf_progress_lines(job)),
+            None => {
+                lines.push(Line::from(""));
+                lines.push(Self::buttons_line(self.field_index == 5, self.field_index == 6));
+            }
+        }
 
         f.render_widget(Paragraph::new(lines), regions.middle_inner);
 
@@ -155,6 +250,7 @@ impl ScreenWidget for DecryptFileDetailsScreen {
         let footer_line = Line::from(vec![
             span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
             span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
             span_key("Enter"),   span_text(" Select"), span_sep(),
             span_key("Esc"),     span_text(" Back"), span_sep(),
             span_key("Ctrl+Q"),  span_text(" Quit"),
@@ -162,7 +258,28 @@ impl ScreenWidget for DecryptFileDetailsScreen {
         f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
     }
 
+    fn busy(&self) -> Option<String> {
+        self.decrypting.as_ref().map(|(job, _)| format!("{} is still running.", job.label))
+    }
+
+    async fn tick(&mut self, _ctx: &mut AppCtx) -> Result<Transition> {
+        let Some(result) = self.decrypting.as_ref().and_then(|(job, _)| job.poll()) else { return Ok(Transition::Stay) };
+        let Some((_, out_dir_path)) = self.decrypting.take() else { return Ok(Transition::Stay) };
+        Ok(match result {
+            Ok(decrypted) => self.decrypted(decrypted, out_dir_path),
+            Err(e) => Transition::Push(Box::new(
+                ConfirmOkScreen::with_lines(e.to_string().lines().map(String::from).collect())
+                    .with_after_ok(AfterOk::Pop)
+            )),
+        })
+    }
+
     async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        // The worker thread owns the password until it returns.
+        if self.decrypting.is_some() {
+            return Ok(Transition::Stay);
+        }
+
         if let Some(t) = esc_to_back(k) { return Ok(t); }
 
         if let KeyCode::Char('q') = k.code {
@@ -171,23 +288,33 @@ impl ScreenWidget for DecryptFileDetailsScreen {
             }
         }
 
+        // Reveal only the focused secret, and only briefly
+        if is_reveal_key(&k) {
+            if self.field_index == 1 { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
         match k.code {
             // Navigation
-            KeyCode::Up => {
-                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
-            }
-            KeyCode::Down | KeyCode::Tab => {
-                self.field_index = (self.field_index + 1) % 5;
-            }
+            KeyCode::Up => self.step_field(6),
+            KeyCode::Down | KeyCode::Tab => self.step_field(1),
 
             // Enter on Decrypt
-            KeyCode::Enter if self.field_index == 3 => {
+            KeyCode::Enter if self.field_index == 5 => {
+                // A keyfile or security key can stand in for the password
                 let pwd = self.password.text.clone();
-                if pwd.is_empty() {
+                if pwd.is_empty() && self.factors.is_empty() {
                     return Ok(Transition::Push(Box::new(
                         ConfirmOkScreen::new("Error: Password cannot be empty.").with_after_ok(AfterOk::Pop)
                     )));
                 }
+                let keyfile = self.keyfile.text.trim();
+                if self.factors.keyfile && keyfile.is_empty() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: This file also needs its keyfile; enter the keyfile path.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                let keyfile = (!keyfile.is_empty()).then(|| PathBuf::from(keyfile));
 
                 let out_dir = self.out_dir.text.trim();
                 if out_dir.is_empty() {
@@ -199,59 +326,77 @@ impl ScreenWidget for DecryptFileDetailsScreen {
                 fs::create_dir_all(&out_dir_path)
                     .with_context(|| format!("creating directory {}", out_dir_path.display()))?;
 
-                // Call the auto-decrypt orchestrator (tries Modern, then OpenPGP)
+                // Decrypt with the chosen method (or Modern, then OpenPGP) on a worker thread;
+                // Argon2id takes seconds, so `tick` reports progress until it returns.
+                // Only the modern header carries the KDF costs an estimate needs.
+                let method = self.method;
+                let expected = fs::read(&self.input_path).ok()
+                    .filter(|_| method != Some(EncryptionFormat::OpenPgp))
+                    .and_then(|buf| modern_kdf_costs(&buf))
+                    .map(|(m_cost_kib, t_cost)| estimate(m_cost_kib, t_cost));
+                // Touches take as long as the operator does, so no estimate with a security key
+                let (label, expected) = if self.factors.fido2.is_some() {
+                    ("Touch the security key, then deriving the decryption key (Argon2id)".to_string(), None)
+                } else if expected.is_some() {
+                    ("Deriving the decryption key (Argon2id)".to_string(), expected)
+                } else {
+                    ("Decrypting the file".to_string(), None)
+                };
+                let input_path = self.input_path.clone();
+                let factors = self.factors.clone();
                 let mut password_utf8 = pwd.into_bytes();
-                match decrypt_auto(&self.input_path, &mut password_utf8, &out_dir_path) {
-                    Ok((method_label, out_path)) => {
-                        let lines = vec![
-                            format!("Decryption successful ({}).", method_label),
-                            "".to_string(),
-                            "Wrote decrypted output to:".to_string(),
-                            out_path.display().to_string(),
-                        ];
-                        return Ok(Transition::Push(Box::new(
-                            ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::PopToMainMenu)
-                        )));
-                    }
-                    Err(e) => {
-                        return Ok(Transition::Push(Box::new(
-                            ConfirmOkScreen::new(&e.to_string()).with_after_ok(AfterOk::Pop)
-                        )));
-                    }
-                }
+                self.decrypting = Some((
+                    KdfJob::spawn(label, expected, move || {
+                        if let Err(e) = mix_factors(&mut password_utf8, &factors, keyfile.as_deref()) {
+                            password_utf8.zeroize();
+                            return Err(e);
+                        }
+                        decrypt_in_memory(&input_path, &mut password_utf8, method).map_err(|e| {
+                            if factors.is_empty() { return e; }
+                            anyhow!("{e}\nThe file was created with {}; a different one gives this error too.", factors.describe())
+                        })
+                    }),
+                    out_dir_path,
+                ));
+                return Ok(Transition::Stay);
             }
 
             // Enter on Cancel
-            KeyCode::Enter if self.field_index == 4 => {
+            KeyCode::Enter if self.field_index == 6 => {
                 return Ok(Transition::Pop);
             }
 
+            // Cycle the method (only reachable when detection was ambiguous)
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 0 => {
+                self.cycle_method();
+            }
+
             // Toggle Show Password
-            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 1 => {
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 2 => {
                 self.show_password = !self.show_password;
             }
 
             // Text cursor/editing
             KeyCode::Left if self.is_text() => {
-                if self.field_index == 0 { self.password.move_left(); } else { self.out_dir.move_left(); }
+                self.tf_mut().move_left();
             }
             KeyCode::Right if self.is_text() => {
-                if self.field_index == 0 { self.password.move_right(); } else { self.out_dir.move_right(); }
+                self.tf_mut().move_right();
             }
             KeyCode::Home if self.is_text() => {
-                if self.field_index == 0 { self.password.home(); } else { self.out_dir.home(); }
+                self.tf_mut().home();
             }
             KeyCode::End if self.is_text() => {
-                if self.field_index == 0 { self.password.end(); } else { self.out_dir.end(); }
+                self.tf_mut().end();
             }
             KeyCode::Backspace if self.is_text() => {
-                if self.field_index == 0 { self.password.backspace(); } else { self.out_dir.backspace(); }
+                self.tf_mut().backspace();
             }
             KeyCode::Delete if self.is_text() => {
-                if self.field_index == 0 { self.password.delete(); } else { self.out_dir.delete(); }
+                self.tf_mut().delete();
             }
             KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
-                if self.field_index == 0 { self.password.insert_char(c); } else { self.out_dir.insert_char(c); }
+                self.tf_mut().insert_char(c);
             }
 
             _ => {}
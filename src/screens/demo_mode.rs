@@ -0,0 +1,360 @@
+//! Demo Mode: a guided run through the whole flow (generate keys, create a delegation,
+//! decode the signed file) with throwaway keys in a fresh temporary directory, so new
+//! operators can practise without real key material or touching real output directories.
+//! The directory is removed when the screen is dropped (Back, Finish or quitting).
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ethers_core::utils::to_checksum;
+use rand_core::{OsRng, RngCore};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::PathBuf;
+use textwrap::wrap;
+
+use crate::abi::load_abi;
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::call_trace::call_trace;
+use crate::commands::keygen::{self, KeyRecord};
+use crate::defaults::Defaults;
+use crate::lint;
+use crate::output_encoder::OutputEncoding;
+use crate::process::{process_item, BatchOpts};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::signing::{decode_signed_tx_and_recover, SignerCache};
+use crate::types::Item;
+use crate::ui::components::warning_banner_lines;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::units::Nonce;
+use crate::util::bytes_to_0x;
+use crate::validation::validate_gas_fields;
+use crate::vfs::FsRef;
+use crate::write_signed_transactions_to_file::{
+    build_filename_for_any_tx, signed_file_metadata, write_single_signed_transaction,
+};
+use crate::screens::registry::{Menu, MenuEntry};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Main,
+        order: 40,
+        title: "Demo Mode (Throwaway Keys)",
+        description: "Walk through key generation, signing and verification with throwaway keys.",
+        open: |_| Transition::Push(Box::new(DemoModeScreen::new())),
+    }
+}
+
+/// Name of the (unencrypted) key file written into the demo directory.
+const DEMO_KEYS_FILE: &str = "DEMO_THROWAWAY_keys.json";
+const DEMO_FUNC: &str = "createDelegationEvent";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Intro,
+    Keys,
+    Delegation,
+    Decoded,
+}
+
+impl Step {
+    fn explanation(self) -> &'static [&'static str] {
+        match self {
+            Step::Intro => &[
+                "Demo Mode walks through the whole flow with throwaway keys: generate keys, create and sign a delegation, then decode the signed file.",
+                "Everything is written to a new temporary directory that is deleted when you leave Demo Mode. Your settings, key files and output directories are not touched.",
+            ],
+            Step::Keys => &[
+                "Step 1 of 3: generate keys. Two key pairs were generated, as Advanced Tools ▸ Create Key Pair does: the delegator signs, the delegatee receives the delegation.",
+                "They were saved unencrypted to the demo directory. Real keys are exported encrypted and kept off networked machines.",
+            ],
+            Step::Delegation => &[
+                "Step 2 of 3: create a delegation. The delegator signed a createDelegationEvent transaction with nonce 0, as Advanced Tools ▸ Create Delegation does, and it was written to the demo directory.",
+                "Below is the signed file as it would be carried to the online machine.",
+            ],
+            Step::Decoded => &[
+                "Step 3 of 3: decode the result. The signed file was read back and its transaction decoded. The sender recovered from the signature must be the delegator.",
+                "That is the whole flow. Finish deletes the demo directory.",
+            ],
+        }
+    }
+
+    fn next_label(self) -> &'static str {
+        match self {
+            Step::Intro => "Start Demo",
+            Step::Keys => "Next: Create Delegation",
+            Step::Delegation => "Next: Decode Result",
+            Step::Decoded => "Finish",
+        }
+    }
+}
+
+pub struct DemoModeScreen {
+    step: Step,
+    /// Temporary directory holding everything the demo writes, and the filesystem it is on.
+    dir: Option<(PathBuf, FsRef)>,
+    /// Delegator, then delegatee.
+    keys: Vec<KeyRecord>,
+    signed_file: Option<PathBuf>,
+    /// What the last step produced.
+    output: Vec<String>,
+    /// First output line shown.
+    scroll: usize,
+    // 0 next, 1 leave
+    button_index: usize,
+}
+
+impl DemoModeScreen {
+    pub fn new() -> Self {
+        Self { step: Step::Intro, dir: None, keys: Vec::new(), signed_file: None, output: Vec::new(), scroll: 0, button_index: 0 }
+    }
+
+    fn demo_dir(&self) -> Result<PathBuf> {
+        self.dir.as_ref().map(|(d, _)| d.clone()).ok_or_else(|| anyhow!("the demo directory was not created"))
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans(self.step.next_label(), self.button_index == 0));
+        if self.step != Step::Decoded {
+            spans.push(Span::raw("   "));
+            spans.extend(button_spans("Leave Demo", self.button_index == 1));
+        }
+        Line::from(spans)
+    }
+
+    /// Create the demo directory and generate the throwaway keys into it.
+    fn generate_keys(&mut self, ctx: &AppCtx) -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("inkan-demo-{:08x}", OsRng.next_u32()));
+        // The demo's own scratch directory, outside the sandbox directories by design.
+        ctx.sandbox.grant(&dir);
+        ctx.fs.create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+        self.dir = Some((dir.clone(), ctx.fs.clone()));
+
+        let keys = keygen::generate(2)?;
+        let key_file = dir.join(DEMO_KEYS_FILE);
+        ctx.fs.write(&key_file, serde_json::to_string_pretty(&keys)?.as_bytes())
+            .with_context(|| format!("writing {}", key_file.display()))?;
+
+        self.output = vec![format!("Demo directory: {}", dir.display()), format!("Key file: {}", key_file.display())];
+        for (role, k) in ["Delegator", "Delegatee"].iter().zip(&keys) {
+            self.output.push(String::new());
+            self.output.push(format!("{role} (DEMO THROWAWAY KEY)"));
+            self.output.push(format!("  address: {}", k.address));
+            self.output.push(format!("  pubkey:  {}", k.publicKeyUncompressed0x04));
+            self.output.push(format!("  npub:    {}", k.npub));
+        }
+        self.keys = keys;
+        Ok(())
+    }
+
+    /// Sign a delegation from the delegator to the delegatee and write it to the demo directory.
+    async fn create_delegation(&mut self, ctx: &AppCtx) -> Result<()> {
+        let [delegator, delegatee] = &self.keys[..] else {
+            return Err(anyhow!("the demo keys were not generated"));
+        };
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = validate_gas_fields(
+            &ctx.settings.gas_caps,
+            ctx.settings.gas_limits.default_for(DEMO_FUNC),
+            Defaults::MAX_FEE_PER_GAS,
+            Defaults::MAX_PRIORITY_FEE_PER_GAS,
+        )?;
+        let opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: ctx.settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            // Throwaway keys: never retired, and not recorded in the key usage store
+            retired_keys: Default::default(),
+            allow_retired_key: false,
+            // The walkthrough shows and verifies the file, so it is always this tool's JSON
+            encoding: OutputEncoding::PrettyJson,
+        };
+        let item = Item {
+            function_to_call: DEMO_FUNC.to_string(),
+            nonce: Some(Nonce(0)),
+            chain_id: Some(Defaults::CHAIN_ID),
+            contract_address: Defaults::CONTRACT_ADDRESS.to_string(),
+            label: None,
+            type_a_privkey_x: Some(delegator.privateKeyHex.clone()),
+            type_a_privkey_y: Some(delegatee.privateKeyHex.clone()),
+            type_a_pubkey_y: None,
+            type_a_uint_x: Some(0),
+            type_a_uint_y: Some(0),
+            type_a_boolean: Some("false".into()),
+            type_a_nonce16: None,
+            type_b_privkey_x: None,
+            type_b_privkey_y: None,
+            type_b_pubkey_y: None,
+            type_b_uint_x: None,
+            type_b_uint_y: None,
+            type_b_nonce16: None,
+            type_c_privkey_x: None,
+            type_c_nonce16: None,
+        };
+
+        let abi = load_abi()?;
+        let entry = process_item(&abi, &opts, &item).await.context("failed to sign the demo delegation")?;
+        lint::check_entries(std::slice::from_ref(&entry))?;
+
+        let out_path = self.demo_dir()?.join(build_filename_for_any_tx(&entry, &ctx.settings.filename_templates));
+        let metadata = signed_file_metadata(&ctx.settings, None, Some(DEMO_FUNC));
+        let written = write_single_signed_transaction(&*ctx.fs, &out_path, &entry, metadata.as_ref(), opts.encoding.encoder())?;
+
+        self.output = vec![format!("Wrote: {}", written.display()), String::new()];
+        self.output.extend(ctx.fs.read_to_string(&written)?.lines().map(str::to_string));
+        self.signed_file = Some(written);
+        Ok(())
+    }
+
+    /// Read the signed file back, decode its transaction and recover the sender.
+    fn decode_result(&mut self, ctx: &AppCtx) -> Result<()> {
+        let path = self.signed_file.clone().ok_or_else(|| anyhow!("no signed file was written"))?;
+        let json: serde_json::Value = serde_json::from_str(&ctx.fs.read_to_string(&path)?)
+            .with_context(|| format!("parsing {}", path.display()))?;
+        let entries = json.get("entries").unwrap_or(&json);
+        let signed_tx = entries[0]["signedTx"].as_str().ok_or_else(|| anyhow!("{} has no signedTx", path.display()))?;
+        let (chain_id, nonce, _prio, max_fee, gas, to, _value, data, from) = decode_signed_tx_and_recover(signed_tx)?;
+
+        let delegator = self.keys.first().map(|k| k.address.as_str()).unwrap_or_default();
+        let matches = format!("{from:#x}").eq_ignore_ascii_case(delegator);
+        self.output = vec![
+            format!("File: {}", path.display()),
+            String::new(),
+            format!("Chain ID:         {chain_id}"),
+            format!("Nonce:            {nonce}"),
+            format!("To (contract):    {}", to_checksum(&to, None)),
+            format!("Gas limit:        {}", ctx.settings.display.integer(gas)),
+            format!("Max fee per gas:  {}", ctx.settings.display.fee(max_fee)),
+            format!("From (recovered): {}", to_checksum(&from, None)),
+            format!("Is the delegator: {}", if matches { "yes" } else { "NO" }),
+            String::new(),
+            "Contract call:".to_string(),
+        ];
+        let abi = load_abi()?;
+        self.output.extend(call_trace(&abi, &bytes_to_0x(&data), &ctx.settings.display)?.lines().map(str::to_string));
+        Ok(())
+    }
+}
+
+impl Default for DemoModeScreen {
+    fn default() -> Self { Self::new() }
+}
+
+impl Drop for DemoModeScreen {
+    fn drop(&mut self) {
+        if let Some((dir, fs)) = self.dir.take() {
+            let _ = fs.remove_dir_all(&dir);
+        }
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for DemoModeScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Demo Mode" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Demo Mode";
+        let explanation_paras = self.step.explanation();
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let banner = warning_banner_lines(
+            "DEMO MODE: throwaway keys only. Never fund them or use them for anything real.",
+            top_inner_width,
+        );
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + 1 + banner.len() as u16;
+
+        // output + spacer + buttons; long output scrolls rather than squeezing the explanation
+        let middle_needed = 2 + 2 + (self.output.len() as u16).min(8) + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = banner;
+        expl_lines.push(Line::from(""));
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: scrollable step output above the buttons
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let lines: Vec<Line> = self.output.iter().skip(self.scroll).map(|l| Line::from(l.clone())).collect();
+        f.render_widget(Paragraph::new(lines), mid_chunks[0]);
+        f.render_widget(Paragraph::new(self.buttons_line()), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),     span_text(" Scroll"), span_sep(),
+            span_key("←/→/Tab"), span_text(" Choose"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Leave Demo"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if k.code == KeyCode::Esc {
+            return Ok(Transition::Pop);
+        }
+        if k.modifiers.contains(KeyModifiers::CONTROL) && matches!(k.code, KeyCode::Char('q' | 'Q')) {
+            return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+        }
+
+        match k.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if self.scroll + 1 < self.output.len() => self.scroll += 1,
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab if self.step != Step::Decoded => {
+                self.button_index = 1 - self.button_index;
+            }
+            KeyCode::Enter if self.button_index == 1 => return Ok(Transition::Pop),
+            KeyCode::Enter => {
+                let (done, next) = match self.step {
+                    Step::Intro => (self.generate_keys(ctx), Step::Keys),
+                    Step::Keys => (self.create_delegation(ctx).await, Step::Delegation),
+                    Step::Delegation => (self.decode_result(ctx), Step::Decoded),
+                    Step::Decoded => return Ok(Transition::Pop),
+                };
+                if let Err(e) = done {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Demo step failed: {e:#}")).with_after_ok(AfterOk::Pop),
+                    )));
+                }
+                self.step = next;
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,216 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::defaults::Defaults;
+use crate::dir_manifest::{verify_dir_manifest, write_dir_manifest, MANIFEST_FILE, MANIFEST_SIG_FILE};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, RevealBriefly, TextField, field_line_password};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Files),
+        order: 20,
+        title: "Directory Checksum Manifest",
+        description: "Write or check SHA-256 manifests for an output directory.",
+        open: |_| Transition::Push(Box::new(DirManifestScreen::new())),
+    }
+}
+
+/// Write a checksum manifest for an output directory (optionally signed), or check a
+/// directory against its manifest after transfer (see `dir_manifest.rs`).
+pub struct DirManifestScreen {
+    // indices: 0 directory, 1 signing key, 2 expected signer, 3 write, 4 verify, 5 back
+    field_index: usize,
+    directory: TextField,
+    signing_key: TextField,
+    expected_signer: TextField,
+    reveal: RevealBriefly,
+}
+
+impl DirManifestScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            directory: TextField::with(Defaults::BATCH_OUT_DIR),
+            signing_key: TextField::default(),
+            expected_signer: TextField::default(),
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0..=2) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.directory,
+            1 => &mut self.signing_key,
+            2 => &mut self.expected_signer,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Write Manifest", self.field_index == 3));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Verify Manifest", self.field_index == 4));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 5));
+        Line::from(spans)
+    }
+
+    async fn write(&mut self, ctx: &AppCtx) -> Result<Vec<String>> {
+        let dir = self.directory.text.trim().to_string();
+        let key = Some(self.signing_key.text.as_str()).filter(|k| !k.trim().is_empty());
+        let res = write_dir_manifest(&*ctx.fs, Path::new(&dir), key).await;
+        self.signing_key.text.zeroize();
+        self.signing_key.cursor = 0;
+        let mut lines = res?;
+        lines.push("Copy the whole directory, then run Verify Manifest (or `sha256sum -c`) on the other machine.".to_string());
+        Ok(lines)
+    }
+
+    fn verify(&mut self, ctx: &AppCtx) -> Result<Vec<String>> {
+        let dir = self.directory.text.trim().to_string();
+        let expected = self.expected_signer.text.trim();
+        let mut lines = verify_dir_manifest(&*ctx.fs, Path::new(&dir), Some(expected))?;
+        if expected.is_empty() && lines.iter().any(|l| l.starts_with("Signature valid")) {
+            lines.push("No Expected Signer was given: compare the signer above with the writing machine.".to_string());
+        }
+        Ok(lines)
+    }
+}
+
+impl Default for DirManifestScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for DirManifestScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Directory Manifest" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Directory Checksum Manifest";
+        let explanation_paras = [
+            format!("Write Manifest hashes every file below the directory into {MANIFEST_FILE}, in sha256sum format. With a Signing Key (hex or nsec; the key is not stored) it also writes the detached signature {MANIFEST_SIG_FILE}."),
+            "After the transfer, Verify Manifest re-hashes the files on the receiving machine and reports changed, missing and unlisted ones. It checks the signature when present, and the signer against Expected Signer when given.".to_string(),
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 3 fields + spacer + buttons
+        let middle_needed = 2 + 2 + 6;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Directory", &self.directory, self.field_index == 0),
+            field_line_password("Signing Key (write, optional)", &self.signing_key, self.field_index == 1, self.reveal.shows(1, self.field_index)),
+            field_line_text("Expected Signer (verify, optional)", &self.expected_signer, self.field_index == 2),
+            Line::from(""),
+            self.buttons_line(),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        if is_reveal_key(&k) {
+            if self.field_index == 1 { self.reveal.start(1); }
+            return Ok(Transition::Stay);
+        }
+
+        let outcome = |res: Result<Vec<String>>| {
+            Transition::Push(Box::new(match res {
+                Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+            }))
+        };
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 5; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 6;
+            }
+            KeyCode::Enter if self.field_index == 3 => return Ok(outcome(self.write(ctx).await)),
+            KeyCode::Enter if self.field_index == 4 => return Ok(outcome(self.verify(ctx))),
+            KeyCode::Enter if self.field_index == 5 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,516 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{TextField, field_line_text};
+use crate::units::{GasLimit, Wei};
+use crate::validation::validate_gas_fields;
+use crate::defaults::Defaults;
+
+// Generic OK-only modal
+use crate::screens::{ConfirmOkScreen, AfterOk};
+
+use crate::abi::load_abi;
+use crate::commands::batch::BatchRun;
+use crate::commands::emergency_revoke::{
+    build_emergency_items, find_delegations, search_dirs, CompromisedKey, EmergencyRevoke,
+};
+use crate::process::{normalize_pubkey_to_uncompressed_0x04, BatchOpts, KeyReuse, RetiredKey};
+use crate::key_usage::KeyUsage;
+use crate::signing::{open_signer, SignerCache};
+use crate::settings::{GasLimits, Settings};
+use crate::vfs::Fs;
+use crate::write_signed_transactions_to_file::BATCH_MANIFEST_FILE;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Transactions),
+        order: 55,
+        title: "Emergency Revoke",
+        description: "Revoke every local delegation to a compromised key in one run.",
+        open: |ctx| Transition::Push(Box::new(EmergencyRevokeScreen::new(&ctx.settings.gas_limits))),
+    }
+}
+
+/// A prepared run: the transactions to sign and the plan shown before signing them.
+struct Plan {
+    run: BatchRun,
+    lines: Vec<String>,
+    first_nonce: u64,
+}
+
+/// Wizard: find the delegations to a compromised key in the local signed files and revoke
+/// (or re-delegate) all of them in one pass.
+pub struct EmergencyRevokeScreen {
+    // 0 compromised_key, 1 delegator_priv, 2 replacement_pubkey, 3 chain_id,
+    // 4 start_nonce, 5 gas_limit, 6 max_fee_per_gas, 7 max_priority_fee_per_gas,
+    // 8 out_dir, 9 submit, 10 back
+    field_index: usize,
+    compromised_key: TextField,
+    delegator_priv: TextField,
+    replacement_pubkey: TextField,
+    chain_id: TextField,
+    start_nonce: TextField,
+    gas_limit: TextField,
+    max_fee_per_gas: TextField,
+    max_priority_fee_per_gas: TextField,
+    out_dir: TextField,
+    /// Set while signing (with the first nonce, for the summary), one item per tick.
+    run: Option<(BatchRun, u64)>,
+    /// Plan the operator was shown; the next submit with the same plan signs it.
+    plan_ack: Option<Vec<String>>,
+    /// Key reuse the operator was warned about; the next submit with the same keys signs anyway.
+    key_reuse_ack: Option<KeyReuse>,
+    /// Retired keys the operator was warned about; the next submit with the same keys signs anyway.
+    retired_ack: Option<RetiredKey>,
+}
+
+impl EmergencyRevokeScreen {
+    pub fn new(gas_limits: &GasLimits) -> Self {
+        Self {
+            field_index: 0,
+            compromised_key: TextField::with(""),
+            delegator_priv: TextField::with(""),
+            replacement_pubkey: TextField::with(""),
+            chain_id: TextField::with(""),
+            start_nonce: TextField::with(""),
+            gas_limit: TextField::with(gas_limits.default_for("createRevocationEventFollowedByDelegationEvent")),
+            max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
+            max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
+            out_dir: TextField::with(Defaults::BATCH_OUT_DIR),
+            run: None,
+            plan_ack: None,
+            key_reuse_ack: None,
+            retired_ack: None,
+        }
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self.field_index, 0..=8)
+    }
+
+    fn tf_ref(&self, idx: usize) -> &TextField {
+        match idx {
+            0 => &self.compromised_key,
+            1 => &self.delegator_priv,
+            2 => &self.replacement_pubkey,
+            3 => &self.chain_id,
+            4 => &self.start_nonce,
+            5 => &self.gas_limit,
+            6 => &self.max_fee_per_gas,
+            7 => &self.max_priority_fee_per_gas,
+            8 => &self.out_dir,
+            _ => unreachable!("tf_ref called on non-text field"),
+        }
+    }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.compromised_key,
+            1 => &mut self.delegator_priv,
+            2 => &mut self.replacement_pubkey,
+            3 => &mut self.chain_id,
+            4 => &mut self.start_nonce,
+            5 => &mut self.gas_limit,
+            6 => &mut self.max_fee_per_gas,
+            7 => &mut self.max_priority_fee_per_gas,
+            8 => &mut self.out_dir,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    // < Find And Sign >   < Back >
+    fn buttons_line(submit_selected: bool, back_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Find And Sign", submit_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+
+    /// Find the delegator's delegations to the compromised key, build one transaction per
+    /// delegation and prepare a run; `tick` signs them.
+    fn plan(&self, fs: &dyn Fs, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<Plan> {
+        let compromised = CompromisedKey::parse(&self.compromised_key.text)?;
+        let pk_delegator = self.delegator_priv.text.trim();
+        if pk_delegator.is_empty() {
+            anyhow::bail!("Delegator PrivKey cannot be empty.");
+        }
+        let delegator = open_signer(pk_delegator).context("Delegator PrivKey")?.pubkey_0x04().to_lowercase();
+        if delegator == compromised.pubkey {
+            anyhow::bail!("Delegator PrivKey is the compromised key.");
+        }
+        let replacement = match self.replacement_pubkey.text.trim() {
+            "" => None,
+            s => Some(normalize_pubkey_to_uncompressed_0x04(s).context("Replacement Delegatee PubKey")?),
+        };
+        if replacement.as_deref() == Some(compromised.pubkey.as_str()) {
+            anyhow::bail!("Replacement Delegatee PubKey is the compromised key.");
+        }
+        let chain_filter: Option<u64> = match self.chain_id.text.trim() {
+            "" => None,
+            s => Some(s.parse().context("Chain ID must be an integer")?),
+        };
+        let out_dir = self.out_dir.text.trim();
+        if out_dir.is_empty() {
+            anyhow::bail!("Output Directory cannot be empty.");
+        }
+        let start_nonce: u64 = self.start_nonce.text.trim()
+            .parse()
+            .context("Starting Nonce must be an integer")?;
+
+        let all = find_delegations(fs, &search_dirs(fs), &compromised.pubkey)?;
+        if all.is_empty() {
+            anyhow::bail!("No signed file in the output directories delegates to {}.", compromised.pubkey);
+        }
+        let (mine, others): (Vec<_>, Vec<_>) = all.into_iter()
+            .filter(|f| chain_filter.is_none_or(|c| c == f.chain_id))
+            .partition(|f| f.delegator == delegator);
+        if mine.is_empty() {
+            anyhow::bail!(
+                "None of the delegations to the compromised key found here is from this delegator{}.",
+                if others.is_empty() { " on that chain" } else { "" }
+            );
+        }
+        let items = build_emergency_items(
+            &EmergencyRevoke {
+                compromised: &compromised,
+                delegator_priv: pk_delegator,
+                replacement_pubkey: replacement.as_deref(),
+                start_nonce,
+            },
+            &mine,
+        )?;
+
+        let action = if replacement.is_some() { "re-delegation" } else { "revocation" };
+        let mut lines = vec![
+            format!("Found {} delegation(s) from this delegator to the compromised key:", mine.len()),
+            "".to_string(),
+        ];
+        for (i, f) in mine.iter().enumerate() {
+            lines.push(format!(
+                "nonce {}: {action} on chain {} at {}{}",
+                start_nonce + i as u64, f.chain_id, f.contract,
+                if f.revoked_on_file { "  (a revocation is already on file)" } else { "" }
+            ));
+            for source in &f.sources {
+                lines.push(format!("    from {}", source.display()));
+            }
+            if let Some(w) = settings.networks.unknown_warning(f.chain_id, &f.contract) {
+                lines.push(format!("    {w}"));
+            }
+        }
+        if let Some(r) = &replacement {
+            lines.extend(["".to_string(), format!("New delegatee: {r}")]);
+        }
+        if !others.is_empty() {
+            lines.extend([
+                "".to_string(),
+                format!("{} other delegator(s) also delegated to it; revoke those with their own keys.", others.len()),
+            ]);
+        }
+        lines.extend(["".to_string(), "Select Find And Sign again to sign these transactions.".to_string()]);
+
+        // Create it now, so an unusable (or sandbox-refused) directory shows up before signing.
+        fs.create_dir_all(Path::new(out_dir)).with_context(|| format!("creating output directory {out_dir}"))?;
+
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
+        };
+        let abi = load_abi()?;
+        // The run is named after the plan, as a batch run is after its input file.
+        let input = lines.join("\n").into_bytes();
+
+        Ok(Plan { run: BatchRun::new(abi, opts, items, input, PathBuf::from(out_dir)), lines, first_nonce: start_nonce })
+    }
+}
+
+impl Default for EmergencyRevokeScreen {
+    fn default() -> Self { Self::new(&GasLimits::default()) }
+}
+
+#[async_trait]
+impl ScreenWidget for EmergencyRevokeScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Emergency Revoke" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
+        let header_text = "Emergency Revoke";
+        let explanation_paras = [
+            "Undo every delegation to a compromised delegate key. The signed files in the output directories are",
+            "searched for delegations from the delegator to that key, and one revocation per chain and contract is",
+            "signed, with nonces counting up from the starting nonce. With a replacement pubkey, each becomes a",
+            "re-delegation to it instead. The plan is shown first; selecting Find And Sign again signs it.",
+            "Enter the compromised private key if a delegation requires the delegatee's signature to revoke.",
+        ];
+
+        // === TOP BOX ===
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: 11 focusable positions (0..=10) plus top spacer and button spacer
+        let middle_rows: u16 = 11 + 2;
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header_lines.max(1)),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // === MIDDLE BOX ===
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut lines: Vec<Line> = vec![
+            Line::from(""), // spacer above first field
+            field_line_text("Compromised Key (pubkey, or privkey)", self.tf_ref(0), self.field_index == 0),
+            field_line_text("Delegator PrivKey", self.tf_ref(1), self.field_index == 1),
+            field_line_text("Replacement Delegatee PubKey (optional)", self.tf_ref(2), self.field_index == 2),
+            field_line_text("Chain ID (optional; needed if several)", self.tf_ref(3), self.field_index == 3),
+            field_line_text("Starting Transaction Nonce", self.tf_ref(4), self.field_index == 4),
+        ];
+
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(5), self.field_index == 5));
+
+        let mfg_label = format!(
+            "Maximum Fee Per Gas (maximum {} wei)",
+            ctx.settings.gas_caps.max_fee_per_gas()
+        );
+        lines.push(field_line_text(&mfg_label, self.tf_ref(6), self.field_index == 6));
+
+        let mpfg_label = format!(
+            "Maximum Priority Fee Per Gas (maximum {} wei)",
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
+        );
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(7), self.field_index == 7));
+
+        lines.push(field_line_text("Output Directory", self.tf_ref(8), self.field_index == 8));
+
+        lines.push(Line::from("")); // spacer
+        match &self.run {
+            Some((run, _)) => lines.push(Line::from(format!(
+                "Signing transaction {} of {}…  (Ctrl+Q stops after the current item)",
+                run.signed() + 1, run.total()
+            ))),
+            None => lines.push(Self::buttons_line(self.field_index == 9, self.field_index == 10)),
+        }
+
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // === BOTTOM BOX (legend) ===
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"),   span_text(" Select"),  span_sep(),
+            span_key("Esc"),     span_text(" Back"),    span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.run.as_ref().map(|(run, _)| format!(
+            "An emergency revoke is in progress ({} of {} signed).", run.signed(), run.total()
+        ))
+    }
+
+    async fn tick(&mut self, ctx: &mut AppCtx) -> Result<Transition> {
+        let Some((run, first_nonce)) = self.run.as_mut() else { return Ok(Transition::Stay) };
+        let first_nonce = *first_nonce;
+        if let Err(e) = run.step().await {
+            let e = e.context("failed to sign the emergency revoke");
+            let lines = run.fail(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref(), &e);
+            self.run = None;
+            return Ok(Transition::Push(Box::new(
+                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+            )));
+        }
+        if !run.is_done() {
+            return Ok(Transition::Stay);
+        }
+        let count = run.total();
+        let written = run.finish(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref());
+        self.run = None;
+        self.plan_ack = None;
+        match written {
+            Ok((run_dir, recorded)) => {
+                let mut lines = vec![
+                    format!(
+                        "Signed {count} transaction(s), nonces {first_nonce}..={}, into run directory:",
+                        first_nonce + count as u64 - 1
+                    ),
+                    "".to_string(),
+                    run_dir.display().to_string(),
+                    "".to_string(),
+                    format!("See {BATCH_MANIFEST_FILE} in that directory for the file list."),
+                    "Broadcast them as soon as possible; until then the compromised key stays delegated.".to_string(),
+                ];
+                lines.extend(recorded);
+                Ok(Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                )))
+            }
+            Err(e) => Ok(Transition::Push(Box::new(
+                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+            ))),
+        }
+    }
+
+    fn finish_for_quit(&mut self, ctx: &AppCtx) -> Option<String> {
+        let (run, _) = self.run.take()?;
+        Some(run.flush_for_quit(&*ctx.fs, &ctx.settings))
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        // While signing, only the global hotkeys (Ctrl+Q, F12) apply.
+        if self.run.is_some() { return Ok(Transition::Stay); }
+
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 10; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 11;
+            }
+
+            // Enter on [Find And Sign]: show the plan, then sign it on the next press
+            KeyCode::Enter if self.field_index == 9 => {
+                let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+                    Ok(gas) => gas,
+                    Err(e) => return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e}")).with_after_ok(AfterOk::Pop)
+                    ))),
+                };
+
+                match self.plan(&*ctx.fs, &ctx.settings, &ctx.key_usage, gas) {
+                    Ok(Plan { run, lines, first_nonce }) => {
+                        if self.plan_ack.as_ref() != Some(&lines) {
+                            self.plan_ack = Some(lines.clone());
+                            return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                        let mut run = run.with_events(ctx.events.clone());
+                        if let Some(reuse) = run.key_reuse() {
+                            if self.key_reuse_ack.as_ref() != Some(&reuse) {
+                                let lines = reuse.confirm_lines("Find And Sign");
+                                self.key_reuse_ack = Some(reuse);
+                                return Ok(Transition::Push(Box::new(
+                                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                                )));
+                            }
+                            run.allow_key_reuse();
+                        }
+                        if let Some(retired) = run.retired_keys() {
+                            if self.retired_ack.as_ref() != Some(&retired) {
+                                let lines = retired.confirm_lines("Find And Sign");
+                                self.retired_ack = Some(retired);
+                                return Ok(Transition::Push(Box::new(
+                                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                                )));
+                            }
+                            run.allow_retired_keys();
+                        }
+                        self.run = Some((run, first_nonce));
+                    }
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::new(format!("Error: {e:#}"))
+                                .with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                }
+            }
+
+            // Enter on [Back]
+            KeyCode::Enter if self.field_index == 10 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Cursor movement in text fields
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+
+            // Editing
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::hardening::{check, report_lines};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_line, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Configuration),
+        order: 40,
+        title: "Hardening Report",
+        description: "Check this machine for session recording, unencrypted swap, open output directories and core dumps.",
+        open: |_| Transition::Push(Box::new(HardeningReportScreen::new())),
+    }
+}
+
+/// The startup environment checks of `hardening.rs`, with what to do about each warning.
+pub struct HardeningReportScreen {
+    lines: Vec<String>,
+    /// First report line shown.
+    scroll: usize,
+    /// Opened by the app at launch rather than from the menu.
+    at_startup: bool,
+}
+
+impl HardeningReportScreen {
+    pub fn new() -> Self {
+        Self { lines: report_lines(&check()), scroll: 0, at_startup: false }
+    }
+
+    /// Shown at launch because a check warned; `lines` is the report already made.
+    pub fn at_startup(lines: Vec<String>) -> Self {
+        Self { lines, scroll: 0, at_startup: true }
+    }
+}
+
+impl Default for HardeningReportScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for HardeningReportScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Hardening Report" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Hardening Report";
+        let mut explanation_paras = vec![
+            "These checks look at the machine around the tool: anything that records the terminal, writes memory to disk, or lets other users read what the tool saves. Nothing is changed; each warning says what to do.",
+        ];
+        if self.at_startup {
+            explanation_paras.push("Shown at launch because a check warned. Fix what you can, then restart the tool; Continue goes on as things are. The report is also under Advanced Tools → Configuration.");
+        }
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // report + spacer + button
+        let middle_needed = 2 + 2 + self.lines.len() as u16 + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: scrollable report above the button, warnings in yellow
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let lines: Vec<Line> = self.lines.iter().skip(self.scroll).map(|l| {
+            if l.starts_with("[WARN]") {
+                Line::styled(l.clone(), Style::default().fg(Color::Yellow))
+            } else {
+                Line::from(l.clone())
+            }
+        }).collect();
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), mid_chunks[0]);
+        let button = if self.at_startup { "Continue" } else { "Back" };
+        f.render_widget(Paragraph::new(button_line(button, true)), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),    span_text(" Scroll"), span_sep(),
+            span_key("Enter"),  span_text(if self.at_startup { " Continue" } else { " Back" }), span_sep(),
+            span_key("Esc"),    span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"), span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if self.scroll + 1 < self.lines.len() => self.scroll += 1,
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::decrypt_modern::{annotate_modern_header, HeaderAnnotation};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text};
+use crate::ui::common_nav::esc_to_back;
+
+const BYTES_PER_ROW: usize = 16;
+const PAGE_ROWS: usize = 16;
+
+// One color per annotated field, cycled in order.
+const FIELD_COLORS: [Color; 6] = [
+    Color::Yellow,
+    Color::Cyan,
+    Color::Green,
+    Color::Magenta,
+    Color::LightBlue,
+    Color::LightRed,
+];
+
+/// Read-only hex dump (offset | hex | ASCII) of a file, with modern-format
+/// header fields highlighted when a header is detected.
+pub struct HexViewerScreen {
+    path: PathBuf,
+    data: Vec<u8>,
+    annotation: Option<HeaderAnnotation>,
+    top_row: usize,
+}
+
+impl HexViewerScreen {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let data = fs::read(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let annotation = annotate_modern_header(&data);
+        Ok(Self { path, data, annotation, top_row: 0 })
+    }
+
+    fn total_rows(&self) -> usize {
+        self.data.len().div_ceil(BYTES_PER_ROW).max(1)
+    }
+
+    fn field_color(&self, offset: usize) -> Option<Color> {
+        let ann = self.annotation.as_ref()?;
+        ann.fields
+            .iter()
+            .position(|fld| fld.range.contains(&offset))
+            .map(|i| FIELD_COLORS[i % FIELD_COLORS.len()])
+    }
+
+    fn row_line(&self, row: usize) -> Line<'static> {
+        let start = row * BYTES_PER_ROW;
+        let end = (start + BYTES_PER_ROW).min(self.data.len());
+        let bytes = &self.data[start..end];
+
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.push(Span::styled(format!("{start:08x}  "), Style::default().fg(Color::DarkGray)));
+
+        for i in 0..BYTES_PER_ROW {
+            let sep = if i == BYTES_PER_ROW / 2 - 1 { "  " } else { " " };
+            match bytes.get(i) {
+                Some(b) => {
+                    let style = match self.field_color(start + i) {
+                        Some(c) => Style::default().fg(c),
+                        None => Style::default(),
+                    };
+                    spans.push(Span::styled(format!("{b:02x}"), style));
+                }
+                None => spans.push(Span::raw("  ")),
+            }
+            spans.push(Span::raw(sep));
+        }
+
+        spans.push(Span::styled(" |", Style::default().fg(Color::DarkGray)));
+        for (i, b) in bytes.iter().enumerate() {
+            let ch = if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '.' };
+            let style = match self.field_color(start + i) {
+                Some(c) => Style::default().fg(c),
+                None => Style::default(),
+            };
+            spans.push(Span::styled(ch.to_string(), style));
+        }
+        spans.push(Span::styled("|", Style::default().fg(Color::DarkGray)));
+
+        Line::from(spans)
+    }
+
+    fn legend_lines(&self) -> Vec<Line<'static>> {
+        let Some(ann) = self.annotation.as_ref() else {
+            return vec![Line::from(
+                "No modern-format header detected (PGP, corrupted, or another format).",
+            )];
+        };
+
+        let mut lines = vec![Line::from(ann.summary.clone())];
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (i, fld) in ann.fields.iter().enumerate() {
+            if i > 0 { spans.push(Span::raw("  ")); }
+            spans.push(Span::styled(
+                format!("■ {} @{:#x}+{}", fld.label, fld.range.start, fld.range.len()),
+                Style::default().fg(FIELD_COLORS[i % FIELD_COLORS.len()]),
+            ));
+        }
+        lines.push(Line::from(spans));
+        lines
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for HexViewerScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Hex Viewer" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Hex Viewer";
+        let file_para = format!("File: {} ({} bytes)", self.path.display(), self.data.len());
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let file_lines = wrap(&file_para, top_inner_width).len() as u16;
+        let legend = self.legend_lines();
+        // Legend lines are wrapped by the Paragraph; budget a few extra rows for that.
+        let legend_rows = legend.len() as u16 + 2;
+        let top_needed = 2 + 2 + header_lines + 1 + file_lines + 1 + legend_rows;
+
+        // Middle: as many hex rows as fit
+        let middle_needed = 2 + 2 + PAGE_ROWS as u16;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for seg in wrap(&file_para, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+        expl_lines.push(Line::from(""));
+        expl_lines.extend(legend);
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE (hex rows from top_row)
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let visible = regions.middle_inner.height as usize;
+        let last = (self.top_row + visible).min(self.total_rows());
+        let lines: Vec<Line> = if self.data.is_empty() {
+            vec![Line::from("(empty file)")]
+        } else {
+            (self.top_row..last).map(|r| self.row_line(r)).collect()
+        };
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),       span_text(" Scroll"), span_sep(),
+            span_key("PgUp/PgDn"), span_text(" Page"),   span_sep(),
+            span_key("Home/End"),  span_text(" Jump"),   span_sep(),
+            span_key("Esc"),       span_text(" Back"),   span_sep(),
+            span_key("Ctrl+Q"),    span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let max_top = self.total_rows().saturating_sub(1);
+        match k.code {
+            KeyCode::Up => self.top_row = self.top_row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => self.top_row = (self.top_row + 1).min(max_top),
+            KeyCode::PageUp => self.top_row = self.top_row.saturating_sub(PAGE_ROWS),
+            KeyCode::PageDown => self.top_row = (self.top_row + PAGE_ROWS).min(max_top),
+            KeyCode::Home => self.top_row = 0,
+            KeyCode::End => self.top_row = max_top,
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
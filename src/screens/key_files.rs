@@ -0,0 +1,298 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::keystore::{archive, list_key_files, nickname, restore, shred, ARCHIVE_DIR};
+use crate::defaults::Defaults;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{TextField, field_line_text};
+use crate::ui::typed_confirm::{TypedConfirm, TypedConfirmChoice};
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 50,
+        title: "Manage Key Files (Archive / Delete)",
+        description: "Archive key files or delete them for good.",
+        open: |ctx| Transition::Push(Box::new(KeyFilesScreen::new(ctx))),
+    }
+}
+
+/// Key files of a directory, with archive/restore and permanent delete (see
+/// `commands/keystore.rs`). Archived files are listed from the directory's archive.
+pub struct KeyFilesScreen {
+    dir: TextField,
+    /// Listing the archive instead of the key files themselves.
+    archived: bool,
+    entries: Vec<PathBuf>,
+    field_index: usize, // 0 = directory, 1 = list, 2 = Show Archived / Show Key Files, 3 = Back
+    list_index: usize,
+    /// Open while a permanent delete waits for the key's nickname to be typed.
+    delete: Option<(PathBuf, TypedConfirm)>,
+}
+
+impl KeyFilesScreen {
+    pub fn new(ctx: &AppCtx) -> Self {
+        let mut s = Self {
+            dir: TextField::with(Defaults::CREATE_KEYPAIR_OUT_DIR),
+            archived: false,
+            entries: Vec::new(),
+            field_index: 1,
+            list_index: 0,
+            delete: None,
+        };
+        s.reload(ctx);
+        s
+    }
+
+    fn reload(&mut self, ctx: &AppCtx) {
+        self.entries = list_key_files(&*ctx.fs, Path::new(self.dir.text.trim()), self.archived);
+        self.list_index = self.list_index.min(self.entries.len().saturating_sub(1));
+        if self.entries.is_empty() && self.field_index == 1 {
+            self.field_index = 2;
+        }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let toggle = if self.archived { "Show Key Files" } else { "Show Archived" };
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans(toggle, self.field_index == 2));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 3));
+        Line::from(spans)
+    }
+
+    fn selected(&self) -> Option<&PathBuf> {
+        if self.field_index == 1 { self.entries.get(self.list_index) } else { None }
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for KeyFilesScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Manage Key Files" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = if self.archived { "Archived Key Files" } else { "Manage Key Files" };
+        let explanation_paras = [
+            format!(
+                "Archive hides a key file from the file pickers by moving it into {ARCHIVE_DIR} in the same directory; it stays on disk and can be restored. Delete overwrites the file with random bytes and removes it, after you type the key's nickname."
+            ),
+            "Enter on the directory lists it again.".to_string(),
+        ];
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: spacer + directory + spacer + files (or a placeholder) + spacer + buttons
+        let middle_rows: u16 = (self.entries.len().max(1) as u16).saturating_add(5);
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut items: Vec<ListItem> = vec![
+            ListItem::new(Line::from("")),
+            ListItem::new(field_line_text("Directory", &self.dir, self.field_index == 0)),
+            ListItem::new(Line::from("")),
+        ];
+        if self.entries.is_empty() {
+            let empty = if self.archived { "  No archived key files." } else { "  No key files in this directory." };
+            items.push(ListItem::new(Line::from(empty)));
+        }
+        for (i, path) in self.entries.iter().enumerate() {
+            let selected = self.field_index == 1 && self.list_index == i;
+            let prefix = if selected { "▶ " } else { "  " };
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::raw(name),
+            ])));
+        }
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(self.buttons_line()));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        f.render_widget(list, regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key(if self.archived { "R" } else { "A" }),
+            span_text(if self.archived { " Restore" } else { " Archive" }), span_sep(),
+            span_key("D"), span_text(" Delete"), span_sep(),
+            span_key("Esc"),   span_text(" Back"),     span_sep(),
+            span_key("Ctrl+Q"),span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some((_, prompt)) = &self.delete {
+            prompt.draw(f, size);
+        }
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        // An open delete confirmation takes every key
+        if let Some((path, prompt)) = self.delete.as_mut() {
+            let Some(choice) = prompt.on_key(k) else { return Ok(Transition::Stay) };
+            let typed = prompt.typed().to_string();
+            let path = path.clone();
+            self.delete = None;
+            if let TypedConfirmChoice::Cancelled = choice {
+                return Ok(Transition::Stay);
+            }
+            let deleted = shred(&*ctx.fs, &path, &typed);
+            self.reload(ctx);
+            return Ok(Transition::Push(Box::new(match deleted {
+                Ok(()) => ConfirmOkScreen::with_lines(vec![
+                    "Key file overwritten and deleted:".to_string(),
+                    path.display().to_string(),
+                ]),
+                Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")),
+            }.with_after_ok(AfterOk::Pop))));
+        }
+
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let len = self.entries.len();
+        match k.code {
+            // DOWN: Directory -> List (top to bottom) -> toggle -> Back -> Directory
+            KeyCode::Down | KeyCode::Tab => match self.field_index {
+                0 if len > 0 => { self.field_index = 1; self.list_index = 0; }
+                0 => self.field_index = 2,
+                1 if self.list_index + 1 < len => self.list_index += 1,
+                1 => self.field_index = 2,
+                2 => self.field_index = 3,
+                _ => self.field_index = 0,
+            },
+
+            // UP reverses
+            KeyCode::Up => match self.field_index {
+                0 => self.field_index = 3,
+                1 if self.list_index > 0 => self.list_index -= 1,
+                1 => self.field_index = 0,
+                2 if len > 0 => { self.field_index = 1; self.list_index = len - 1; }
+                2 => self.field_index = 0,
+                _ => self.field_index = 2,
+            },
+
+            KeyCode::Enter if self.field_index == 0 => {
+                self.field_index = 1;
+                self.list_index = 0;
+                self.reload(ctx);
+            }
+
+            KeyCode::Char('a') | KeyCode::Char('A') | KeyCode::Char('r') | KeyCode::Char('R')
+                if self.field_index == 1 =>
+            {
+                let Some(path) = self.selected().cloned() else { return Ok(Transition::Stay) };
+                let restoring = matches!(k.code, KeyCode::Char('r') | KeyCode::Char('R'));
+                if restoring != self.archived {
+                    return Ok(Transition::Stay);
+                }
+                let moved = if restoring { restore(&*ctx.fs, &path) } else { archive(&*ctx.fs, &path) };
+                self.reload(ctx);
+                if let Err(e) = moved {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                    )));
+                }
+            }
+
+            KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete if self.field_index == 1 => {
+                let Some(path) = self.selected().cloned() else { return Ok(Transition::Stay) };
+                let prompt = TypedConfirm::new(
+                    "Delete Key File Permanently",
+                    vec![
+                        format!("File: {}", path.display()),
+                        "The file is overwritten with random bytes and then removed. This cannot".to_string(),
+                        "be undone: without another backup, the key is gone. To only hide the".to_string(),
+                        "file, cancel and archive it instead.".to_string(),
+                    ],
+                    &nickname(&path),
+                    "Delete Permanently",
+                );
+                self.delete = Some((path, prompt));
+            }
+
+            KeyCode::Enter if self.field_index == 2 => {
+                self.archived = !self.archived;
+                self.list_index = 0;
+                self.reload(ctx);
+            }
+
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Text editing on the directory field
+            KeyCode::Left if self.field_index == 0 => self.dir.move_left(),
+            KeyCode::Right if self.field_index == 0 => self.dir.move_right(),
+            KeyCode::Home if self.field_index == 0 => self.dir.home(),
+            KeyCode::End if self.field_index == 0 => self.dir.end(),
+            KeyCode::Backspace if self.field_index == 0 => self.dir.backspace(),
+            KeyCode::Delete if self.field_index == 0 => self.dir.delete(),
+            KeyCode::Char(c) if self.field_index == 0 && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.dir.insert_char(c)
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ethers_core::types::Address;
+use ethers_core::utils::to_checksum;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::AppEvent;
+use crate::display::DisplaySettings;
+use crate::key_usage::{save_key_usage, KeyRecord};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 40,
+        title: "Key Usage",
+        description: "List recorded keys by address; retire them or make them active again.",
+        open: |_| Transition::Push(Box::new(KeyUsageScreen::new())),
+    }
+}
+
+/// Signing statistics per key from the key usage store; Enter retires a key or makes it
+/// active again.
+#[derive(Default)]
+pub struct KeyUsageScreen {
+    field_index: usize, // 0 = list, 1 = Back
+    list_index: usize,
+}
+
+impl KeyUsageScreen {
+    pub fn new() -> Self { Self::default() }
+
+    fn buttons_line(back_selected: bool) -> Line<'static> {
+        Line::from(button_spans("Back", back_selected))
+    }
+
+    fn row(addr: &str, rec: &KeyRecord, display: &DisplaySettings) -> String {
+        let shown = addr.parse::<Address>().map(|a| to_checksum(&a, None)).unwrap_or_else(|_| addr.to_string());
+        let last = rec.last_used.as_deref().map(|ts| display.rfc3339(ts)).unwrap_or_else(|| "never".to_string());
+        let mut row = format!("{shown}  {:>5} signed  last {last}", display.integer(rec.signed));
+        if let Some(when) = rec.retired.as_deref() {
+            row.push_str(&format!("  RETIRED {}", display.rfc3339(when)));
+        }
+        row
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for KeyUsageScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Key Usage" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
+        let header_text = "Key Usage";
+        let explanation_paras = [
+            "Signing keys by sender address: transactions signed and written to a signed file, and when each key last signed. A key appears after its first signed transaction.",
+            "Select a key and press Enter to retire it (or make it active again). Selecting a retired key for signing shows a warning that must be confirmed.",
+        ];
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: spacer + keys (or a placeholder) + spacer + buttons
+        let keys = &ctx.key_usage.keys;
+        let middle_rows: u16 = (keys.len().max(1) as u16).saturating_add(3);
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut items: Vec<ListItem> = vec![ListItem::new(Line::from(""))];
+        if keys.is_empty() {
+            items.push(ListItem::new(Line::from("  No signing recorded yet.")));
+        }
+        for (i, (addr, rec)) in keys.iter().enumerate() {
+            let selected = self.field_index == 0 && self.list_index == i;
+            let prefix = if selected { "▶ " } else { "  " };
+            let style = if rec.retired.is_some() { Style::default().fg(Color::Yellow) } else { Style::default() };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::styled(Self::row(addr, rec, &ctx.settings.display), style),
+            ])));
+        }
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(Self::buttons_line(self.field_index == 1 || keys.is_empty())));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        f.render_widget(list, regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"), span_text(" Retire / Reactivate"), span_sep(),
+            span_key("Esc"),   span_text(" Back"),     span_sep(),
+            span_key("Ctrl+Q"),span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let len = ctx.key_usage.keys.len();
+        match k.code {
+            // DOWN cycles: List -> Back -> (top of) List; an empty list is skipped
+            KeyCode::Down | KeyCode::Tab => {
+                if self.field_index == 0 && self.list_index + 1 < len {
+                    self.list_index += 1;
+                } else if self.field_index == 0 {
+                    self.field_index = 1;
+                } else if len > 0 {
+                    self.field_index = 0;
+                    self.list_index = 0;
+                }
+            }
+
+            // UP cycles reverse: List (top) -> Back -> (bottom of) List
+            KeyCode::Up => {
+                if self.field_index == 0 && self.list_index > 0 {
+                    self.list_index -= 1;
+                } else if self.field_index == 0 {
+                    self.field_index = 1;
+                } else if len > 0 {
+                    self.field_index = 0;
+                    self.list_index = len - 1;
+                }
+            }
+
+            KeyCode::Enter if self.field_index == 0 && len > 0 => {
+                let Some((addr, rec)) = ctx.key_usage.keys.iter().nth(self.list_index) else {
+                    return Ok(Transition::Stay);
+                };
+                let (addr, retire) = (addr.clone(), rec.retired.is_none());
+                let mut usage = ctx.key_usage.clone();
+                usage.set_retired(&addr, retire);
+                if let Err(e) = save_key_usage(&usage, ctx.profile_key.as_ref()) {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                ctx.dispatch(self.crumb(), AppEvent::KeyUsageChanged(usage));
+            }
+
+            KeyCode::Enter if self.field_index == 1 || len == 0 => {
+                return Ok(Transition::Pop);
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,401 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use serde::Deserialize;
+use textwrap::wrap;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, LoadedSigningKey, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form};
+use crate::commands::decrypt_auto::{decrypt_auto_bytes, DecryptedFile};
+use crate::commands::decrypt_modern::{modern_kdf_costs, modern_key_factors};
+use crate::commands::keystore::list_key_files;
+use crate::crypto::container::{parse_container, ContainerRecord};
+use crate::crypto::factors::mix_factors;
+use crate::crypto::kdf_progress::{estimate, KdfJob};
+use crate::defaults::Defaults;
+use crate::process::fingerprint_from_privkey_input;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, kdf_progress_lines, RevealBriefly, TextField, field_line_password, show_password_line};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::vfs::FsRef;
+
+/// The private key of a decrypted key file, or of one key of a container.
+#[derive(Deserialize, Zeroize, ZeroizeOnDrop)]
+struct KeyPayloadIn {
+    private_key_hex: String,
+}
+
+/// Opened with Ctrl+K on a private-key field of a Create form: pick a key file made by Create
+/// Key Pair (or Create Key Container), enter its password, and the key goes straight into that
+/// field. It is decrypted in memory, never shown and never written; the form shows where it
+/// came from instead.
+pub struct LoadSigningKeyScreen {
+    form: Form,
+    /// Index and label of the form field the key is for.
+    field: usize,
+    field_label: &'static str,
+    fs: FsRef,
+    // indices: 0 key directory, 1 file list, 2 password, 3 show pwd toggle, 4 keyfile, 5 load, 6 cancel
+    field_index: usize,
+    dir: TextField,
+    files: Vec<PathBuf>,
+    list_index: usize,
+    password: TextField,
+    show_password: bool,
+    reveal: RevealBriefly,
+    keyfile: TextField,
+    /// Decryption running on a worker thread, with the file being decrypted.
+    decrypting: Option<(KdfJob<DecryptedFile>, PathBuf)>,
+    /// A decrypted container and its keys; the list then picks one of them.
+    container: Option<(PathBuf, Vec<ContainerRecord>)>,
+}
+
+impl LoadSigningKeyScreen {
+    pub fn new(form: Form, field: usize, field_label: &'static str, fs: FsRef) -> Self {
+        let mut s = Self {
+            form,
+            field,
+            field_label,
+            fs,
+            field_index: 1,
+            dir: TextField::with(Defaults::CREATE_KEYPAIR_OUT_DIR),
+            files: Vec::new(),
+            list_index: 0,
+            password: TextField::with(""),
+            show_password: false,
+            reveal: RevealBriefly::default(),
+            keyfile: TextField::with(""),
+            decrypting: None,
+            container: None,
+        };
+        s.refresh();
+        s
+    }
+
+    fn refresh(&mut self) {
+        self.files = list_key_files(&*self.fs, Path::new(self.dir.text.trim()), false);
+        self.list_index = 0;
+        self.container = None;
+    }
+
+    /// Rows of the list: key files, or the keys of a decrypted container.
+    fn list_len(&self) -> usize {
+        match &self.container {
+            Some((_, records)) => records.len(),
+            None => self.files.len(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 2 | 4) }
+
+    fn tf_mut(&mut self) -> &mut TextField {
+        match self.field_index {
+            0 => &mut self.dir,
+            2 => &mut self.password,
+            4 => &mut self.keyfile,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn list_lines(&self) -> Vec<Line<'static>> {
+        let names: Vec<String> = match &self.container {
+            Some((_, records)) => records.iter().map(|r| r.label.clone()).collect(),
+            None => self.files.iter().map(|p| p.file_name().unwrap_or_default().to_string_lossy().to_string()).collect(),
+        };
+        let mut lines = vec![match &self.container {
+            Some((path, _)) => Line::from(format!("Keys in {} (choose one):", file_name(path))),
+            None => Line::from("Key Files:"),
+        }];
+        if names.is_empty() {
+            lines.push(Line::from("  No files found in this directory."));
+        }
+        for (i, name) in names.into_iter().enumerate() {
+            let selected = self.field_index == 1 && self.list_index == i;
+            let prefix = if selected { "▶ " } else { "  " };
+            let style = if selected { Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD) } else { Style::default() };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::styled(name, style),
+            ]));
+        }
+        lines
+    }
+
+    fn buttons_line(load_selected: bool, cancel_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Load Key", load_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Cancel", cancel_selected));
+        Line::from(spans)
+    }
+
+    fn step(&mut self, down: bool) {
+        let len = self.list_len();
+        self.field_index = match (self.field_index, down) {
+            (1, true) if self.list_index + 1 < len => { self.list_index += 1; 1 }
+            (1, false) if self.list_index > 0 => { self.list_index -= 1; 1 }
+            (i, true) => (i + 1) % 7,
+            (i, false) => (i + 6) % 7,
+        };
+        // Entering the list from below lands on its last row
+        if self.field_index == 1 && !down && len > 0 && self.list_index == 0 {
+            self.list_index = len - 1;
+        }
+    }
+
+    /// Start decrypting the highlighted key file on a worker thread.
+    fn start_decrypt(&mut self) -> Result<()> {
+        let path = self.files.get(self.list_index).cloned().ok_or_else(|| anyhow!("Choose a key file first."))?;
+        let buf = self.fs.read(&path).with_context(|| format!("reading {}", path.display()))?;
+        let factors = modern_key_factors(&buf);
+        if self.password.text.is_empty() && factors.is_empty() {
+            return Err(anyhow!("Password cannot be empty."));
+        }
+        let keyfile = self.keyfile.text.trim();
+        if factors.keyfile && keyfile.is_empty() {
+            return Err(anyhow!("This file also needs its keyfile; enter the keyfile path."));
+        }
+        let keyfile = (!keyfile.is_empty()).then(|| PathBuf::from(keyfile));
+
+        let (label, expected) = if factors.fido2.is_some() {
+            ("Touch the security key, then deriving the decryption key (Argon2id)".to_string(), None)
+        } else if let Some((m_cost_kib, t_cost)) = modern_kdf_costs(&buf) {
+            ("Deriving the decryption key (Argon2id)".to_string(), Some(estimate(m_cost_kib, t_cost)))
+        } else {
+            ("Decrypting the key file".to_string(), None)
+        };
+        let mut password_utf8 = std::mem::take(&mut self.password.text).into_bytes();
+        self.password.cursor = 0;
+        self.decrypting = Some((
+            KdfJob::spawn(label, expected, move || {
+                if let Err(e) = mix_factors(&mut password_utf8, &factors, keyfile.as_deref()) {
+                    password_utf8.zeroize();
+                    return Err(e);
+                }
+                decrypt_auto_bytes(&buf, &mut password_utf8)
+            }),
+            path,
+        ));
+        Ok(())
+    }
+
+    /// Hand the key in `json` (a key file payload or container record) to the form.
+    fn hand_over(&self, json: &[u8], source: String, ctx: &mut AppCtx) -> Result<Transition> {
+        let payload: KeyPayloadIn = serde_json::from_slice(json).map_err(|_| anyhow!("The file holds no private key."))?;
+        let fingerprint = fingerprint_from_privkey_input(&payload.private_key_hex)
+            .ok_or_else(|| anyhow!("The file's private key is not a valid secp256k1 key."))?;
+        let key = LoadedSigningKey {
+            field: self.field,
+            privkey_hex: payload.private_key_hex.clone(),
+            source: format!("{source}, key {fingerprint}"),
+        };
+        ctx.dispatch(self.crumb(), AppEvent::SigningKeyLoaded { form: self.form, key });
+        Ok(Transition::Pop)
+    }
+
+    /// What the worker thread decrypted: one key for the form, or a container to pick from.
+    fn decrypted(&mut self, decrypted: DecryptedFile, path: PathBuf, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(records) = parse_container(&decrypted.plaintext) {
+            self.container = Some((path, records));
+            self.field_index = 1;
+            self.list_index = 0;
+            return Ok(Transition::Stay);
+        }
+        self.hand_over(&decrypted.plaintext, file_name(&path), ctx)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().to_string()
+}
+
+fn error_modal(e: anyhow::Error) -> Transition {
+    Transition::Push(Box::new(
+        ConfirmOkScreen::with_lines(format!("Error: {e:#}").lines().map(String::from).collect()).with_after_ok(AfterOk::Pop)
+    ))
+}
+
+#[async_trait]
+impl ScreenWidget for LoadSigningKeyScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Load Key File" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Load Key From Encrypted File";
+        let target = format!("For {} on {}.", self.field_label, self.form.label());
+        let explanation_paras = [
+            target.as_str(),
+            "Choose a key file (.enc or .pgp, as made by Create Key Pair) and enter its password. The key is decrypted in memory and put into the field without being shown or written anywhere; the form shows the file and key fingerprint instead. A container asks which of its keys to use.",
+        ];
+
+        // TOP sizing
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // MIDDLE: spacer + dir + spacer + list + spacer + password + show + keyfile + spacer + buttons (+ progress)
+        let list = self.list_lines();
+        let mut lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Key Directory", &self.dir, self.field_index == 0),
+            Line::from(""),
+        ];
+        lines.extend(list);
+        lines.push(Line::from(""));
+        lines.push(field_line_password(
+            "Password",
+            &self.password,
+            self.field_index == 2,
+            self.show_password || self.reveal.shows(2, self.field_index),
+        ));
+        lines.push(show_password_line(self.show_password, self.field_index == 3));
+        lines.push(field_line_text("Keyfile (If Required)", &self.keyfile, self.field_index == 4));
+        lines.push(Line::from(""));
+        lines.push(Self::buttons_line(self.field_index == 5, self.field_index == 6));
+        if let Some((job, _)) = &self.decrypting {
+            lines.push(Line::from(""));
+            lines.extend(kdf_progress_lines(job));
+        }
+        let middle_needed = 2 + 2 + lines.len() as u16;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal"), span_sep(),
+            span_key("Esc"),     span_text(" Cancel"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.decrypting.as_ref().map(|(job, _)| format!("{} is still running.", job.label))
+    }
+
+    async fn tick(&mut self, ctx: &mut AppCtx) -> Result<Transition> {
+        let Some(result) = self.decrypting.as_ref().and_then(|(job, _)| job.poll()) else { return Ok(Transition::Stay) };
+        let Some((_, path)) = self.decrypting.take() else { return Ok(Transition::Stay) };
+        Ok(match result.and_then(|decrypted| self.decrypted(decrypted, path, ctx)) {
+            Ok(t) => t,
+            Err(e) => error_modal(e),
+        })
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        // The worker thread owns the password until it returns.
+        if self.decrypting.is_some() {
+            return Ok(Transition::Stay);
+        }
+
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        // Reveal only the focused secret, and only briefly
+        if is_reveal_key(&k) {
+            if self.field_index == 2 { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
+        match k.code {
+            KeyCode::Up => self.step(false),
+            KeyCode::Down | KeyCode::Tab => self.step(true),
+
+            // Enter on the directory re-lists it
+            KeyCode::Enter if self.field_index == 0 => {
+                self.refresh();
+                self.field_index = 1;
+            }
+
+            // Enter on a container key, or on Load Key with one highlighted, hands it over
+            KeyCode::Enter if self.container.is_some() && matches!(self.field_index, 1 | 5) => {
+                let Some((path, records)) = &self.container else { return Ok(Transition::Stay) };
+                let Some(record) = records.get(self.list_index) else { return Ok(Transition::Stay) };
+                let source = format!("{} \"{}\"", file_name(path), record.label);
+                return Ok(match self.hand_over(record.json_pretty.as_bytes(), source, ctx) {
+                    Ok(t) => t,
+                    Err(e) => error_modal(e),
+                });
+            }
+
+            // Enter on a key file moves on to its password; Enter there or on Load Key decrypts
+            KeyCode::Enter if self.field_index == 1 => self.field_index = 2,
+            KeyCode::Enter if matches!(self.field_index, 2 | 4 | 5) => {
+                if let Err(e) = self.start_decrypt() {
+                    return Ok(error_modal(e));
+                }
+            }
+
+            KeyCode::Enter if self.field_index == 6 => return Ok(Transition::Pop),
+
+            // Toggle Show Password
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 3 => {
+                self.show_password = !self.show_password;
+            }
+
+            // Text cursor/editing
+            KeyCode::Left if self.is_text() => self.tf_mut().move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut().move_right(),
+            KeyCode::Home if self.is_text() => self.tf_mut().home(),
+            KeyCode::End if self.is_text() => self.tf_mut().end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut().backspace(),
+            KeyCode::Delete if self.is_text() => self.tf_mut().delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut().insert_char(c);
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -11,35 +11,32 @@ use ratatui::{
 use textwrap::wrap;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::screens::registry::{self, Menu, MenuEntry};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text};
+use crate::ui::scrub::scrub_limitation;
+use crate::memory_hygiene::lock_warning;
 
 #[derive(Default)]
 pub struct MainMenuScreen {
     menu_index: usize,
 }
 
-#[derive(Copy, Clone, Debug)]
+/// The registered Main Menu entries, then Quit.
+#[derive(Copy, Clone)]
 enum MenuItem {
-    CreateIdentity,
-    RecoverIdentity,
-    AdvancedTools,
+    Entry(&'static MenuEntry),
     Quit,
 }
 impl MenuItem {
     fn all() -> Vec<MenuItem> {
-        vec![
-            MenuItem::CreateIdentity,
-            MenuItem::RecoverIdentity,
-            MenuItem::AdvancedTools,
-            MenuItem::Quit,
-        ]
+        let mut items: Vec<MenuItem> = registry::entries(Menu::Main).into_iter().map(MenuItem::Entry).collect();
+        items.push(MenuItem::Quit);
+        items
     }
     fn label(&self) -> &'static str {
         match self {
-            MenuItem::CreateIdentity => "Create Inkan Identity",
-            MenuItem::RecoverIdentity => "Recover Inkan Identity",
-            MenuItem::AdvancedTools => "Advanced Tools",
+            MenuItem::Entry(e) => e.title,
             MenuItem::Quit => "Quit",
         }
     }
@@ -48,22 +45,30 @@ impl MenuItem {
 #[async_trait]
 impl ScreenWidget for MainMenuScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Main Menu" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         // content
         let header_text = "Inkan Management Utility — Main Menu";
-        let explanation_paras = [
+        let mut explanation_paras = vec![
             "Welcome to the Inkan Management Utility.",
             "This tool lets you generate/export keys and sign EIP-1559 calls offline.",
             "Use ↑/↓/Tab to navigate, Enter to select.",
+            "Mind cameras and screen sharing while secrets are shown: F12 blanks the screen at any time (any key restores it).",
         ];
+        if let Some(note) = scrub_limitation() {
+            explanation_paras.push(note);
+        }
+        if let Some(note) = lock_warning() {
+            explanation_paras.push(note);
+        }
 
         // estimate wrapped lines for dynamic top height
         let top_inner_width = size.width.saturating_sub(2*2 /*page*/ + 2 /*borders*/ + 2*3 /*inner*/ ) as usize;
         let header_lines = wrap(header_text, top_inner_width).len() as u16;
 
         let mut exp_lines = 0usize;
-        for p in explanation_paras {
+        for p in &explanation_paras {
             exp_lines += wrap(p, top_inner_width).len();
         }
         let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
@@ -142,13 +147,14 @@ impl ScreenWidget for MainMenuScreen {
         let footer_line = Line::from(vec![
             span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
             span_key("Enter"), span_text(" Select"), span_sep(),
+            span_key("F12"), span_text(" Blank Screen"), span_sep(),
             span_key("Ctrl+Q"), span_text(" Quit"),
         ]);
         let footer_para = Paragraph::new(footer_line).wrap(Wrap { trim: true });
         f.render_widget(footer_para, regions.bottom_inner);
     }
 
-    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
         // Main menu: Ctrl+Q exits immediately (no confirm here)
         if let KeyCode::Char('q') = k.code {
             if k.modifiers.contains(KeyModifiers::CONTROL) {
@@ -166,12 +172,7 @@ impl ScreenWidget for MainMenuScreen {
             }
             KeyCode::Enter => {
                 return Ok(match MenuItem::all()[self.menu_index] {
-                    MenuItem::CreateIdentity =>
-                        Transition::Push(Box::new(crate::screens::CreateInkanIdentityScreen::new())),
-                    MenuItem::RecoverIdentity =>
-                        Transition::Push(Box::new(crate::screens::RecoverInkanIdentityScreen::new())),
-                    MenuItem::AdvancedTools =>
-                        Transition::Push(Box::new(crate::screens::AdvancedToolsScreen::new())),
+                    MenuItem::Entry(e) => e.launch(ctx),
                     MenuItem::Quit =>
                         Transition::Quit, // ← exit immediately from main menu
                 })
@@ -0,0 +1,258 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::AppEvent;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{TextField, RevealBriefly, field_line_password, show_password_line};
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::profile::{disable_profile, enable_profile, profile_enabled, unlock_profile, PROTECTED_FILES};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 60,
+        title: "Master Password",
+        description: "Encrypt the tool's local records at rest under a password.",
+        open: |_| Transition::Push(Box::new(MasterPasswordScreen::new())),
+    }
+}
+
+/// Enable or remove the master-password profile (see `profile.rs`).
+pub struct MasterPasswordScreen {
+    // indices: 0 password, 1 confirm, 2 show pwd toggle, 3 submit, 4 back
+    field_index: usize,
+    password: TextField,
+    confirm: TextField,
+    show_password: bool,
+    reveal: RevealBriefly,
+}
+
+impl MasterPasswordScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            password: TextField::with(""),
+            confirm: TextField::with(""),
+            show_password: false,
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 1) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.password,
+            1 => &mut self.confirm,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(enabled: bool, submit_selected: bool, back_selected: bool) -> Line<'static> {
+        let submit = if enabled { "Remove Master Password" } else { "Set Master Password" };
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans(submit, submit_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+
+    fn clear_fields(&mut self) {
+        self.password.text.zeroize();
+        self.confirm.text.zeroize();
+        self.password.cursor = 0;
+        self.confirm.cursor = 0;
+    }
+
+    fn submit(&mut self, ctx: &mut AppCtx) -> Result<Vec<String>> {
+        if self.password.text.is_empty() {
+            return Err(anyhow!("Master Password cannot be empty."));
+        }
+        if self.password.text != self.confirm.text {
+            return Err(anyhow!("Master Password and Confirm Master Password do not match."));
+        }
+        let mut password_utf8 = self.password.text.clone().into_bytes();
+        self.clear_fields();
+
+        if profile_enabled() {
+            // Re-derive from the typed password so removal needs the password, not just an open session.
+            let key = unlock_profile(&mut password_utf8)?;
+            disable_profile(&key)?;
+            ctx.dispatch(self.crumb(), AppEvent::ProfileKeyChanged(None));
+            Ok(vec![
+                "Master password removed.".to_string(),
+                "Protected files were decrypted back to plaintext.".to_string(),
+            ])
+        } else {
+            let key = enable_profile(&mut password_utf8)?;
+            ctx.dispatch(self.crumb(), AppEvent::ProfileKeyChanged(Some(key)));
+            Ok(vec![
+                "Master password set.".to_string(),
+                "".to_string(),
+                format!("Protected files are now stored encrypted: {}", PROTECTED_FILES.join(", ")),
+                "You will be asked for the master password at every startup.".to_string(),
+            ])
+        }
+    }
+}
+
+impl Default for MasterPasswordScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for MasterPasswordScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Master Password" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let enabled = profile_enabled();
+        let header_text = "Master Password";
+        let status = if enabled {
+            "Status: a master password is set. Enter it twice to remove it and store protected files as plaintext again."
+        } else {
+            "Status: no master password. Set one to encrypt local records at rest; it is asked for once at startup."
+        };
+        let explanation_paras = [
+            "The master password encrypts this tool's local records (settings, key usage and the audit log, and later the address book and nonce tracker) with Argon2id + XChaCha20-Poly1305, so they can't be read from an imaged disk.",
+            "There is no recovery: if the master password is lost, the protected records are lost (key files are not affected).",
+            status,
+        ];
+
+        // TOP sizing
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + password + confirm + show + spacer + buttons
+        let middle_rows: u16 = 5 + 1;
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_password("Master Password", &self.password, self.field_index == 0, self.show_password || self.reveal.shows(0, self.field_index)),
+            field_line_password("Confirm Master Password", &self.confirm, self.field_index == 1, self.show_password || self.reveal.shows(1, self.field_index)),
+            show_password_line(self.show_password, self.field_index == 2),
+            Line::from(""),
+            Self::buttons_line(enabled, self.field_index == 3, self.field_index == 4),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        // Reveal only the focused secret, and only briefly
+        if is_reveal_key(&k) {
+            if matches!(self.field_index, 0 | 1) { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 5;
+            }
+
+            // Enter on Set / Remove
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(Transition::Push(Box::new(match self.submit(ctx) {
+                    Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+                })));
+            }
+
+            // Enter on Back
+            KeyCode::Enter if self.field_index == 4 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Toggle Show Password
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 2 => {
+                self.show_password = !self.show_password;
+            }
+
+            // Text cursor/editing
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
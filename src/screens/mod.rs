@@ -1,24 +1,60 @@
 // Core screens
+pub mod registry;                         // menu entries submitted by the screen modules
 pub mod main_menu;
 pub mod confirm_quit;
-pub mod result;
+pub mod sandbox_override;
+pub mod unlock_profile;
 
 // Intro / identity flows
 pub mod create_inkan_identity;
 pub mod recover_inkan_identity;
 pub mod advanced_tools;
+pub mod demo_mode;
+pub mod workspaces;
 
 // Advanced Tools -> Create* pages
 pub mod create_key_pair;
+pub mod create_key_container;
+pub mod key_usage;
+pub mod key_files;
+pub mod recovery;
+pub mod dir_manifest;
+pub mod copy_to_media;
+pub mod about;
 pub mod create_delegation;                // canonical Create Delegation screen (manual input)
 pub mod create_revocation;                // canonical Create Revocation screen (manual input)
 pub mod create_redelegation;              // canonical Create Re-Delegation screen (manual input)
 pub mod create_permanent_invalidation;
+pub mod sign_batch_file;
+pub mod bulk_redelegation;
+pub mod emergency_revoke;
+pub mod call_preview;
+pub mod select_signed_file;
+pub mod verify_report;
+pub mod decode_raw_tx;
+pub mod abi_selectors;
 
 // Decrypt flow
 pub mod decrypt_file;                     // already added
 pub mod select_file_for_decryption;       // NEW
 pub mod decrypt_file_details;             // NEW
+pub mod batch_decrypt;
+pub mod select_container_key;
+pub mod load_signing_key;
+pub mod hex_viewer;
+pub mod upgrade_key_file;
+pub mod master_password;
+pub mod settings;
+pub mod settings_bundle;
+pub mod audit_proof;
+pub mod address_table;
+pub mod batch_template;
+pub mod app_backup;
+pub mod remote_signer;
+pub mod hardening_report;
+pub mod usage_stats;
+pub mod staged_outputs;
+pub mod ceremony_checklist;
 
 // Load-from-file flows (delegation)
 pub mod choose_delegation_info_dir;
@@ -39,21 +75,21 @@ pub mod select_permanent_invalidation_info_file;
 // ---------------- Re-exports ----------------
 pub use main_menu::MainMenuScreen;
 pub use confirm_quit::ConfirmQuitScreen;
-pub use result::ResultScreen;
+pub use sandbox_override::SandboxOverrideScreen;
+pub use unlock_profile::UnlockProfileScreen;
 
-pub use create_inkan_identity::CreateInkanIdentityScreen;
-pub use recover_inkan_identity::RecoverInkanIdentityScreen;
-pub use advanced_tools::AdvancedToolsScreen;
+// Screens opened from a menu submit a `registry::MenuEntry` instead of being re-exported here.
+pub use recovery::RecoveryScreen;
+pub use call_preview::CallPreviewScreen;
+pub use select_signed_file::SelectSignedFileScreen;
+pub use verify_report::VerifyReportScreen;
 
-pub use create_key_pair::CreateKeyPairScreen;
-pub use create_delegation::CreateDelegationScreen;
-pub use create_revocation::CreateRevocationScreen;
-pub use create_redelegation::CreateRedelegationScreen;
-pub use create_permanent_invalidation::CreatePermanentInvalidationScreen;
-
-pub use decrypt_file::DecryptFileScreen;
 pub use select_file_for_decryption::SelectFileForDecryptionScreen;
 pub use decrypt_file_details::DecryptFileDetailsScreen;
+pub use batch_decrypt::BatchDecryptScreen;
+pub use select_container_key::SelectContainerKeyScreen;
+pub use load_signing_key::LoadSigningKeyScreen;
+pub use hex_viewer::HexViewerScreen;
 
 pub use choose_delegation_info_dir::ChooseDelegationInfoDirScreen;
 pub use select_delegation_info_file::SelectDelegationInfoFileScreen;
@@ -69,6 +105,7 @@ pub use select_permanent_invalidation_info_file::SelectPermanentInvalidationInfo
 
 // Re-export the confirmation screen type
 pub mod confirm_ok;
+pub mod confirm_secret_prefill;
 pub use confirm_ok::{ConfirmOkScreen, AfterOk};
 
 // Legacy/removed modules (Option B cleanup):
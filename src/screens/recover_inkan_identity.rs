@@ -14,6 +14,17 @@ use crate::app::{AppCtx, ScreenWidget, Transition};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text};
 use crate::ui::common_nav::esc_to_back;
+use crate::screens::registry::{Menu, MenuEntry};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Main,
+        order: 20,
+        title: "Recover Inkan Identity",
+        description: "Regain control of an existing Inkan identity.",
+        open: |_| Transition::Push(Box::new(RecoverInkanIdentityScreen::new())),
+    }
+}
 
 #[derive(Default)]
 pub struct RecoverInkanIdentityScreen {
@@ -45,6 +56,7 @@ impl MenuItem {
 #[async_trait]
 impl ScreenWidget for RecoverInkanIdentityScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Recover Inkan Identity" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Recover Inkan Identity";
@@ -0,0 +1,250 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::recovery::{clean_up, resume, Leftover};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::typed_confirm::{TypedConfirm, TypedConfirmChoice};
+use crate::screens::{ConfirmOkScreen, AfterOk, MainMenuScreen};
+
+/// Shown at startup instead of the main menu when an interrupted run left files behind
+/// (see `commands/recovery.rs`); Continue goes on to the main menu.
+pub struct RecoveryScreen {
+    leftovers: Vec<Leftover>,
+    field_index: usize, // 0 = list, 1 = Clean Up All, 2 = Continue
+    list_index: usize,
+    /// Open while the operator types DELETE: the leftover to clean up, or `None` for all.
+    clean_confirm: Option<(Option<usize>, TypedConfirm)>,
+}
+
+impl RecoveryScreen {
+    pub fn new(leftovers: Vec<Leftover>) -> Self {
+        Self { leftovers, field_index: 0, list_index: 0, clean_confirm: None }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Clean Up All", self.field_index == 1));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Continue", self.field_index == 2));
+        Line::from(spans)
+    }
+
+    /// Drop the handled leftover and keep the cursor in range.
+    fn remove(&mut self, index: usize) {
+        self.leftovers.remove(index);
+        self.list_index = self.list_index.min(self.leftovers.len().saturating_sub(1));
+        if self.leftovers.is_empty() {
+            self.field_index = 2;
+        }
+    }
+
+    /// Ask for DELETE before cleaning up leftover `index`, or every leftover when `None`.
+    fn confirm_clean_up(&mut self, index: Option<usize>) {
+        let lines = match index {
+            Some(i) => vec![
+                "This deletes:".to_string(),
+                self.leftovers[i].describe(),
+            ],
+            None => vec![
+                format!("This deletes all {} leftover(s) listed,", self.leftovers.len()),
+                "including every batch run directory among them.".to_string(),
+            ],
+        };
+        let action = if index.is_some() { "Clean Up" } else { "Clean Up All" };
+        self.clean_confirm = Some((index, TypedConfirm::new("Clean Up Leftovers", lines, "DELETE", action)));
+    }
+
+    /// Clean up leftover `index`, or every leftover when `None`.
+    fn clean(&mut self, index: Option<usize>, ctx: &AppCtx) -> Transition {
+        if let Some(i) = index {
+            if let Err(e) = clean_up(&*ctx.fs, &self.leftovers[i]) {
+                return Self::error(e);
+            }
+            self.remove(i);
+            return Transition::Stay;
+        }
+        while let Some(leftover) = self.leftovers.first() {
+            if let Err(e) = clean_up(&*ctx.fs, leftover) {
+                return Self::error(e);
+            }
+            self.remove(0);
+        }
+        Transition::Stay
+    }
+
+    fn error(e: anyhow::Error) -> Transition {
+        Transition::Push(Box::new(ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)))
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for RecoveryScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Interrupted Output" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Leftovers From an Interrupted Run";
+        let explanation_paras = [
+            "These files were left behind when the tool stopped in the middle of writing. A temp file never replaced its original, which is unchanged; it can only be cleaned up.",
+            "A batch run without a manifest can be resumed: its signed files are re-validated, half-written ones are removed, and the manifest is written marked incomplete. Cleaning up a run deletes the whole directory.",
+        ];
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: spacer + leftovers (or a placeholder) + spacer + buttons
+        let middle_rows: u16 = (self.leftovers.len().max(1) as u16).saturating_add(3);
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text)
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut items: Vec<ListItem> = vec![ListItem::new(Line::from(""))];
+        if self.leftovers.is_empty() {
+            items.push(ListItem::new(Line::from("  All leftovers handled.")));
+        }
+        for (i, leftover) in self.leftovers.iter().enumerate() {
+            let selected = self.field_index == 0 && self.list_index == i;
+            let prefix = if selected { "▶ " } else { "  " };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::raw(leftover.describe()),
+            ])));
+        }
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(self.buttons_line()));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        f.render_widget(list, regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("R"),     span_text(" Resume"),   span_sep(),
+            span_key("C"),     span_text(" Clean Up"), span_sep(),
+            span_key("Esc"),   span_text(" Continue"), span_sep(),
+            span_key("Ctrl+Q"),span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some((_, prompt)) = &self.clean_confirm {
+            prompt.draw(f, size);
+        }
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some((index, prompt)) = self.clean_confirm.as_mut() {
+            let Some(choice) = prompt.on_key(k) else { return Ok(Transition::Stay) };
+            let index = *index;
+            self.clean_confirm = None;
+            return Ok(match choice {
+                TypedConfirmChoice::Confirmed => self.clean(index, ctx),
+                TypedConfirmChoice::Cancelled => Transition::Stay,
+            });
+        }
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let len = self.leftovers.len();
+        match k.code {
+            // DOWN: List (top to bottom) -> Clean Up All -> Continue -> List
+            KeyCode::Down | KeyCode::Tab => match self.field_index {
+                0 if self.list_index + 1 < len => self.list_index += 1,
+                0 => self.field_index = 1,
+                1 => self.field_index = 2,
+                _ if len > 0 => { self.field_index = 0; self.list_index = 0; }
+                _ => self.field_index = 1,
+            },
+
+            // UP reverses
+            KeyCode::Up => match self.field_index {
+                0 if self.list_index > 0 => self.list_index -= 1,
+                0 => self.field_index = 2,
+                1 if len > 0 => { self.field_index = 0; self.list_index = len - 1; }
+                1 => self.field_index = 2,
+                _ => self.field_index = 1,
+            },
+
+            KeyCode::Char('r') | KeyCode::Char('R') | KeyCode::Enter if self.field_index == 0 && len > 0 => {
+                let leftover = &self.leftovers[self.list_index];
+                if !leftover.resumable() && k.code == KeyCode::Enter {
+                    return Ok(Transition::Stay);
+                }
+                match resume(&*ctx.fs, leftover) {
+                    Ok(lines) => {
+                        self.remove(self.list_index);
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                    Err(e) => return Ok(Self::error(e)),
+                }
+            }
+
+            KeyCode::Char('c') | KeyCode::Char('C') if self.field_index == 0 && len > 0 => {
+                self.confirm_clean_up(Some(self.list_index));
+            }
+
+            KeyCode::Enter if self.field_index == 1 && len > 0 => self.confirm_clean_up(None),
+            KeyCode::Enter if self.field_index == 1 => {}
+
+            KeyCode::Enter | KeyCode::Esc => {
+                return Ok(Transition::Replace(Box::new(MainMenuScreen::default())));
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,119 @@
+//! Menu entries registered by the screen modules themselves. A module that opens from a menu
+//! submits a [`MenuEntry`] next to its screen (`inventory::submit!`); the Main Menu and Advanced
+//! Tools list whatever was submitted, so a new flow needs its `pub mod` line and nothing else.
+
+use crate::app::{AppCtx, Transition};
+use crate::usage_stats::record_opened;
+
+/// Where an entry is listed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Menu {
+    Main,
+    Advanced(ToolCategory),
+}
+
+/// Submenus of Advanced Tools, in display order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToolCategory {
+    Transactions,
+    SignVerify,
+    Keys,
+    Files,
+    Configuration,
+}
+
+impl ToolCategory {
+    pub const ALL: [ToolCategory; 5] = [
+        ToolCategory::Transactions,
+        ToolCategory::SignVerify,
+        ToolCategory::Keys,
+        ToolCategory::Files,
+        ToolCategory::Configuration,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ToolCategory::Transactions => "Build Transactions",
+            ToolCategory::SignVerify => "Sign & Verify",
+            ToolCategory::Keys => "Keys",
+            ToolCategory::Files => "Files & Media",
+            ToolCategory::Configuration => "Configuration",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ToolCategory::Transactions => "Create delegations, revocations, re-delegations and permanent invalidations, one at a time or in bulk, or revoke a compromised key everywhere.",
+            ToolCategory::SignVerify => "Tick off the ceremony checklist, build batch files from templates, sign them, sign over a serial link, check signed files before they are broadcast, decode a raw transaction, list the contract's function selectors, and prove the audit log is complete.",
+            ToolCategory::Keys => "Create, upgrade, track and archive key files; list their addresses; set the master password.",
+            ToolCategory::Files => "Decrypt exports, check directory manifests, copy to removable media, describe this signer, and keep or shred staged outputs.",
+            ToolCategory::Configuration => "Edit the settings, move them between machines as a bundle, back up and restore all app data, see local usage statistics, or check the machine for risky surroundings.",
+        }
+    }
+
+    fn position(self) -> usize {
+        Self::ALL.iter().position(|c| *c == self).unwrap_or(0)
+    }
+}
+
+/// One menu line and what Enter on it does.
+pub struct MenuEntry {
+    pub menu: Menu,
+    /// Position within the menu; entries are listed by ascending `order`.
+    pub order: u16,
+    pub title: &'static str,
+    /// One line shown under the list while the entry is highlighted.
+    pub description: &'static str,
+    pub open: fn(&mut AppCtx) -> Transition,
+}
+
+inventory::collect!(MenuEntry);
+
+impl MenuEntry {
+    /// Incremental search: case-insensitive match on title, description or category.
+    pub fn matches(&self, query: &str) -> bool {
+        let q = query.trim().to_lowercase();
+        let category = match self.menu {
+            Menu::Advanced(c) => c.label(),
+            Menu::Main => "",
+        };
+        q.is_empty()
+            || [self.title, self.description, category]
+                .iter()
+                .any(|t| t.to_lowercase().contains(&q))
+    }
+
+    /// Open the entry, counting it in the usage statistics when they are on. A failed count is
+    /// dropped: it must not keep the tool from opening.
+    pub fn launch(&self, ctx: &mut AppCtx) -> Transition {
+        if ctx.settings.usage_stats {
+            let _ = record_opened(ctx.profile_key.as_ref(), self.title);
+        }
+        (self.open)(ctx)
+    }
+
+    fn sort_key(&self) -> (usize, u16, &'static str) {
+        let menu = match self.menu {
+            Menu::Main => 0,
+            Menu::Advanced(c) => 1 + c.position(),
+        };
+        (menu, self.order, self.title)
+    }
+}
+
+/// The entries of one menu, in display order.
+pub fn entries(menu: Menu) -> Vec<&'static MenuEntry> {
+    let mut out: Vec<_> = inventory::iter::<MenuEntry>.into_iter().filter(|e| e.menu == menu).collect();
+    out.sort_by_key(|e| e.sort_key());
+    out
+}
+
+/// Every Advanced Tools entry, by category and then order.
+pub fn tools() -> Vec<&'static MenuEntry> {
+    let mut out: Vec<_> = inventory::iter::<MenuEntry>
+        .into_iter()
+        .filter(|e| matches!(e.menu, Menu::Advanced(_)))
+        .collect();
+    out.sort_by_key(|e| e.sort_key());
+    out
+}
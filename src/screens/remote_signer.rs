@@ -0,0 +1,398 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ethers_core::utils::to_checksum;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::defaults::Defaults;
+use crate::remote_signer::{Link, Reply, Request, RequestOp};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::settings::PayloadDigest;
+use crate::signing::{open_signer, sign_payload, Signer};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, info_line_text, RevealBriefly, TextField, field_line_password};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::util::bytes_to_0x;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 20,
+        title: "Remote Signer (Serial)",
+        description: "Answer signing requests from an online machine over a serial line.",
+        open: |_| Transition::Push(Box::new(RemoteSignerScreen::new())),
+    }
+}
+
+/// Log lines kept on screen while serving.
+const LOG_LINES: usize = 6;
+
+/// A running remote-signer session.
+struct Session {
+    link: Link,
+    signer: Box<dyn Signer>,
+    device: String,
+    signed: usize,
+    log: Vec<String>,
+    /// Sign request waiting for the operator.
+    pending: Option<Request>,
+    // 0 approve, 1 reject
+    button_index: usize,
+}
+
+impl Session {
+    fn log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    /// Refuse a pending request so the online side is not left waiting.
+    fn close(&mut self) {
+        if let Some(req) = self.pending.take() {
+            let _ = self.link.reply(req.id, Reply::Error("signer stopped".to_string()));
+        }
+    }
+}
+
+/// Serve signature requests from a connected online machine over a serial port.
+pub struct RemoteSignerScreen {
+    // indices: 0 device, 1 baud, 2 signing key, 3 start, 4 back
+    field_index: usize,
+    device: TextField,
+    baud: TextField,
+    signing_key: TextField,
+    reveal: RevealBriefly,
+    session: Option<Session>,
+}
+
+impl RemoteSignerScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            device: TextField::with(Defaults::REMOTE_SIGNER_DEVICE),
+            baud: TextField::with(Defaults::REMOTE_SIGNER_BAUD),
+            signing_key: TextField::default(),
+            reveal: RevealBriefly::default(),
+            session: None,
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0..=2) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.device,
+            1 => &mut self.baud,
+            2 => &mut self.signing_key,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn setup_buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Start", self.field_index == 3));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 4));
+        Line::from(spans)
+    }
+
+    fn start(&mut self) -> Result<()> {
+        let device = self.device.text.trim().to_string();
+        if device.is_empty() {
+            return Err(anyhow!("Serial Device is required."));
+        }
+        let baud: u32 = self.baud.text.trim().parse()
+            .map_err(|_| anyhow!("Baud Rate must be a whole number, e.g. {}.", Defaults::REMOTE_SIGNER_BAUD))?;
+        if self.signing_key.text.trim().is_empty() {
+            return Err(anyhow!("Signing Key is required."));
+        }
+        let signer = open_signer(&self.signing_key.text);
+        self.signing_key.text.zeroize();
+        self.signing_key.cursor = 0;
+        let signer = signer?;
+        let link = Link::open(&device, baud)?;
+
+        let mut session = Session { link, signer, device, signed: 0, log: Vec::new(), pending: None, button_index: 1 };
+        session.log(format!("Listening as {}", to_checksum(&session.signer.address(), None)));
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Answer the pending request: sign it when approved, refuse it otherwise.
+    async fn decide(&mut self, approve: bool) -> Result<()> {
+        let Some(session) = self.session.as_mut() else { return Ok(()) };
+        let Some(req) = session.pending.take() else { return Ok(()) };
+        let RequestOp::Sign { hash, digest, .. } = req.op else { return Ok(()) };
+        if !approve {
+            session.link.reply(req.id, Reply::Error("rejected by operator".to_string()))?;
+            session.log(format!("#{} rejected", req.id));
+            return Ok(());
+        }
+        match sign_payload(&*session.signer, hash, digest).await {
+            Ok(sig) => {
+                session.link.reply(req.id, Reply::Signature(sig.to_vec()))?;
+                session.signed += 1;
+                session.log(format!("#{} signed {}", req.id, bytes_to_0x(&hash)));
+            }
+            Err(e) => {
+                session.link.reply(req.id, Reply::Error(format!("{e:#}")))?;
+                session.log(format!("#{} failed: {e:#}", req.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop serving; returns the closing summary.
+    fn stop(&mut self) -> Option<String> {
+        let mut session = self.session.take()?;
+        session.close();
+        Some(format!(
+            "Remote signer on {} stopped after {} signature(s).", session.device, session.signed
+        ))
+    }
+
+    fn session_lines(session: &Session) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(""),
+            info_line_text("Serial Device", &session.device),
+            info_line_text("Signer", &to_checksum(&session.signer.address(), None)),
+            info_line_text("Signatures", &session.signed.to_string()),
+            Line::from(""),
+        ];
+        lines.extend(session.log.iter().map(|l| Line::from(l.clone())));
+        lines.push(Line::from(""));
+        match &session.pending {
+            Some(Request { id, op: RequestOp::Sign { hash, digest, note } }) => {
+                lines.push(Line::from(format!("Signature request #{id}")));
+                lines.push(info_line_text("Hash", &bytes_to_0x(hash)));
+                lines.push(info_line_text("Digest", match digest {
+                    PayloadDigest::Eip191 => "EIP-191 personal message",
+                    PayloadDigest::RawKeccak => "raw (hash signed as-is)",
+                }));
+                if !note.is_empty() {
+                    lines.push(info_line_text("Note", note));
+                }
+                lines.push(Line::from(""));
+                let mut spans: Vec<Span<'static>> = Vec::new();
+                spans.extend(button_spans("Approve", session.button_index == 0));
+                spans.push(Span::raw("   "));
+                spans.extend(button_spans("Reject", session.button_index == 1));
+                lines.push(Line::from(spans));
+            }
+            _ => {
+                lines.push(Line::from("Waiting for requests…"));
+                lines.push(Line::from(""));
+                lines.push(Line::from(button_spans("Stop", true)));
+            }
+        }
+        lines
+    }
+}
+
+impl Default for RemoteSignerScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for RemoteSignerScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Remote Signer" }
+    fn shows_secrets(&self) -> bool { true }
+    fn signs(&self) -> bool { true }
+
+    fn busy(&self) -> Option<String> {
+        self.session.as_ref().map(|s| format!(
+            "The remote signer is serving on {} ({} signature(s) so far).", s.device, s.signed
+        ))
+    }
+
+    async fn tick(&mut self, _ctx: &mut AppCtx) -> Result<Transition> {
+        let Some(session) = self.session.as_mut() else { return Ok(Transition::Stay) };
+        if session.pending.is_some() {
+            // Waiting on the operator; don't spin.
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            return Ok(Transition::Stay);
+        }
+        match session.link.poll() {
+            Ok(None) => {}
+            Ok(Some(Request { id, op: RequestOp::Address })) => {
+                let reply = Reply::Address {
+                    address: to_checksum(&session.signer.address(), None),
+                    pubkey: crate::util::hex_to_bytes(&session.signer.pubkey_0x04())?,
+                };
+                session.link.reply(id, reply)?;
+                session.log(format!("#{id} address sent"));
+            }
+            Ok(Some(req)) => {
+                session.button_index = 1;
+                session.pending = Some(req);
+            }
+            Err(e) => {
+                let summary = self.stop().unwrap_or_default();
+                return Ok(Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(vec![format!("Error: {e:#}"), "".to_string(), summary])
+                        .with_after_ok(AfterOk::Pop),
+                )));
+            }
+        }
+        Ok(Transition::Stay)
+    }
+
+    fn finish_for_quit(&mut self, _ctx: &AppCtx) -> Option<String> {
+        self.stop()
+    }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Remote Signer (Serial)";
+        let explanation_paras = [
+            "Answer signature requests from a connected online machine over a serial link, e.g. a USB gadget port. Only 32-byte hashes come in and only signatures go out; the key stays on this machine.",
+            "Each signature request is shown here and is signed only after you approve it. Address requests are answered automatically.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        let lines: Vec<Line> = match &self.session {
+            Some(session) => Self::session_lines(session),
+            None => vec![
+                Line::from(""),
+                field_line_text("Serial Device", &self.device, self.field_index == 0),
+                field_line_text("Baud Rate", &self.baud, self.field_index == 1),
+                field_line_password("Signing Key", &self.signing_key, self.field_index == 2, self.reveal.shows(2, self.field_index)),
+                Line::from(""),
+                self.setup_buttons_line(),
+            ],
+        };
+        let middle_needed = 2 + 2 + lines.len() as u16;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = match &self.session {
+            Some(_) => Line::from(vec![
+                span_key("←/→/Tab"), span_text(" Choose"), span_sep(),
+                span_key("Enter"),   span_text(" Select"), span_sep(),
+                span_key("Esc"),     span_text(" Stop"), span_sep(),
+                span_key("Ctrl+Q"),  span_text(" Quit"),
+            ]),
+            None => Line::from(vec![
+                span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+                span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+                span_key("Enter"),   span_text(" Select"), span_sep(),
+                span_key("Esc"),     span_text(" Back"), span_sep(),
+                span_key("Ctrl+Q"),  span_text(" Quit"),
+            ]),
+        };
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        // Serving: only approve/reject and stop.
+        if let Some(session) = self.session.as_mut() {
+            let pending = session.pending.is_some();
+            match k.code {
+                KeyCode::Left | KeyCode::Right | KeyCode::Tab if pending => {
+                    session.button_index = 1 - session.button_index;
+                }
+                KeyCode::Enter if pending => {
+                    let approve = session.button_index == 0;
+                    if let Err(e) = self.decide(approve).await {
+                        let summary = self.stop().unwrap_or_default();
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::with_lines(vec![format!("Error: {e:#}"), "".to_string(), summary])
+                                .with_after_ok(AfterOk::Pop),
+                        )));
+                    }
+                }
+                KeyCode::Enter | KeyCode::Esc => {
+                    let summary = self.stop().unwrap_or_default();
+                    return Ok(Transition::Push(Box::new(ConfirmOkScreen::new(summary))));
+                }
+                _ => {}
+            }
+            return Ok(Transition::Stay);
+        }
+
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if is_reveal_key(&k) {
+            if self.field_index == 2 { self.reveal.start(2); }
+            return Ok(Transition::Stay);
+        }
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 5;
+            }
+            KeyCode::Enter if self.field_index == 3 => {
+                if let Err(e) = self.start() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                    )));
+                }
+            }
+            KeyCode::Enter if self.field_index == 4 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::layout::centered_rect_abs;
+use crate::ui::style;
+use crate::vfs::OutsideSandbox;
+
+/// Shown over a failure caused by the sandbox (see `vfs::Sandbox`): names the refused
+/// directory and the allowed ones, and lets the operator allow that directory until exit.
+/// Nothing is retried; after allowing, the operator repeats the action.
+pub struct SandboxOverrideScreen {
+    denied: OutsideSandbox,
+    roots: Vec<PathBuf>,
+    selected: usize, // 0 = Keep Blocked, 1 = Allow For This Session
+}
+
+impl SandboxOverrideScreen {
+    pub fn new(denied: OutsideSandbox, roots: Vec<PathBuf>) -> Self {
+        Self { denied, roots, selected: 0 }
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for SandboxOverrideScreen {
+    fn title(&self) -> &str { "" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let left_label = "Keep Blocked";
+        let right_label = "Allow For This Session";
+        let mut lines: Vec<String> = vec![
+            "File access outside the allowed directories was blocked:".to_string(),
+            self.denied.dir.display().to_string(),
+            "".to_string(),
+            "Allowed directories:".to_string(),
+        ];
+        lines.extend(self.roots.iter().map(|r| format!("  {}", r.display())));
+        lines.extend([
+            "".to_string(),
+            "Only allow it if you chose this location yourself; a loaded info file".to_string(),
+            "can set OUTPUT_DIRECTORY to anywhere. Repeat the action after allowing.".to_string(),
+        ]);
+
+        let btn_len = |label: &str| 4 + label.len(); // "< " + label + " >"
+        let buttons_len = btn_len(left_label) + 3 + btn_len(right_label);
+        let content_w = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0).max(buttons_len) as u16;
+        let inner_width = content_w.max(36);
+        let inner_height = lines.len() as u16 + 2; // lines + spacer + buttons
+
+        let area = centered_rect_abs(inner_width + 4, inner_height + 3, size);
+        let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(lines.len() as u16),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let text: Vec<Line> = lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| match i {
+                1 => Line::from(Span::styled(l, Style::default().fg(Color::Red))),
+                _ => Line::from(l),
+            })
+            .collect();
+
+        let mut btn_spans = Vec::new();
+        btn_spans.extend(style::button_spans(left_label, self.selected == 0));
+        btn_spans.push(Span::raw("   "));
+        btn_spans.extend(style::button_spans(right_label, self.selected == 1));
+
+        f.render_widget(Clear, area);
+        f.render_widget(Block::default().borders(Borders::ALL).title(self.title()), area);
+        f.render_widget(Paragraph::new(text), vchunks[0]);
+        f.render_widget(Paragraph::new(Line::from(btn_spans)).alignment(Alignment::Center), vchunks[2]);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        match k.code {
+            KeyCode::Esc => return Ok(Transition::Pop),
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::Char(' ') => {
+                self.selected = 1 - self.selected;
+            }
+            KeyCode::Enter => {
+                if self.selected == 1 {
+                    ctx.sandbox.grant(&self.denied.dir);
+                }
+                return Ok(Transition::Pop);
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::commands::decrypt_auto::write_decrypted_output;
+use crate::crypto::container::ContainerRecord;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::screens::{ConfirmOkScreen, AfterOk};
+
+/// Shown after a multi-key container decrypts: pick one key (or all) to write out.
+/// Decrypted records live only in this screen and are zeroized when it is dropped.
+pub struct SelectContainerKeyScreen {
+    input_path: PathBuf,
+    out_dir: PathBuf,
+    method_label: String,
+    records: Vec<ContainerRecord>,
+    plaintext: Vec<u8>,
+    field_index: usize, // 0 = list, 1 = Back
+    list_index: usize,  // 0..records.len() = single key, records.len() = all keys
+}
+
+impl SelectContainerKeyScreen {
+    pub fn new(
+        input_path: PathBuf,
+        out_dir: PathBuf,
+        method_label: String,
+        records: Vec<ContainerRecord>,
+        plaintext: Vec<u8>,
+    ) -> Self {
+        Self { input_path, out_dir, method_label, records, plaintext, field_index: 0, list_index: 0 }
+    }
+
+    fn list_len(&self) -> usize { self.records.len() + 1 }
+
+    fn buttons_line(back_selected: bool) -> Line<'static> {
+        Line::from(button_spans("Back", back_selected))
+    }
+
+    fn write_selection(&self) -> Result<(String, PathBuf)> {
+        match self.records.get(self.list_index) {
+            Some(rec) => {
+                let path = write_decrypted_output(
+                    &self.input_path, &self.out_dir, Some(&rec.label), rec.json_pretty.as_bytes(),
+                )?;
+                Ok((format!("Wrote key \"{}\" to:", rec.label), path))
+            }
+            None => {
+                let path = write_decrypted_output(&self.input_path, &self.out_dir, None, &self.plaintext)?;
+                Ok((format!("Wrote all {} keys to:", self.records.len()), path))
+            }
+        }
+    }
+}
+
+impl Drop for SelectContainerKeyScreen {
+    fn drop(&mut self) {
+        self.plaintext.zeroize();
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for SelectContainerKeyScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Select Key" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Select Key From Container";
+        let explanation_paras = [
+            &format!("Decryption successful ({}).", self.method_label),
+            &format!("File: {}", self.input_path.display()),
+            "This file holds several labeled keys. Choose one to write it out on its own, or write the whole container. Each choice writes a new unencrypted file; you can pick more than one.",
+        ];
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: spacer + keys + "all" + spacer + buttons
+        let middle_rows: u16 = (self.list_len() as u16).saturating_add(3);
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut items: Vec<ListItem> = vec![ListItem::new(Line::from(""))];
+        for i in 0..self.list_len() {
+            let selected = self.field_index == 0 && self.list_index == i;
+            let prefix = if selected { "▶ " } else { "  " };
+            let text = match self.records.get(i) {
+                Some(rec) => rec.label.clone(),
+                None => format!("All keys ({}) as one file", self.records.len()),
+            };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::raw(text),
+            ])));
+        }
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(Self::buttons_line(self.field_index == 1)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        f.render_widget(list, regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"), span_text(" Write Selected"), span_sep(),
+            span_key("Esc"),   span_text(" Back"),     span_sep(),
+            span_key("Ctrl+Q"),span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            // DOWN cycles: List -> Back -> (top of) List
+            KeyCode::Down | KeyCode::Tab => {
+                if self.field_index == 0 && self.list_index + 1 < self.list_len() {
+                    self.list_index += 1;
+                } else if self.field_index == 0 {
+                    self.field_index = 1;
+                } else {
+                    self.field_index = 0;
+                    self.list_index = 0;
+                }
+            }
+
+            // UP cycles reverse: List (top) -> Back -> (bottom of) List
+            KeyCode::Up => {
+                if self.field_index == 0 && self.list_index > 0 {
+                    self.list_index -= 1;
+                } else if self.field_index == 0 {
+                    self.field_index = 1;
+                } else {
+                    self.field_index = 0;
+                    self.list_index = self.list_len() - 1;
+                }
+            }
+
+            KeyCode::Enter if self.field_index == 0 => {
+                return Ok(Transition::Push(Box::new(match self.write_selection() {
+                    Ok((header, path)) => ConfirmOkScreen::with_lines(vec![
+                        header,
+                        path.display().to_string(),
+                    ])
+                    .with_after_ok(AfterOk::Pop),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+                })));
+            }
+
+            KeyCode::Enter if self.field_index == 1 => {
+                return Ok(Transition::Pop);
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use async_trait::async_trait;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -10,34 +10,35 @@ use ratatui::{
 };
 use textwrap::wrap;
 
-use std::fs;
-use std::path::{PathBuf, Path};
+use std::path::PathBuf;
 
 use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::vfs::{list_files, FsRef};
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
 use crate::ui::common_nav::esc_to_back;
 
 pub struct SelectFileForDecryptionScreen {
     dir: PathBuf,
+    fs: FsRef,
     entries: Vec<PathBuf>,
-    field_index: usize, // 0 = list, 1 = Refresh, 2 = Back
+    field_index: usize, // 0 = list, 1 = Decrypt All, 2 = Refresh, 3 = Back
     list_index: usize,
 }
 
 impl SelectFileForDecryptionScreen {
-    pub fn new(dir: PathBuf) -> Self {
-        let entries = read_files_only(&dir).unwrap_or_default();
-        // If no files, start on Refresh (1); otherwise start with the list (0)
-        let field_index = if entries.is_empty() { 1 } else { 0 };
-        Self { dir, entries, field_index, list_index: 0 }
+    pub fn new(dir: PathBuf, fs: FsRef) -> Self {
+        let entries = list_files(&*fs, &dir).unwrap_or_default();
+        // If no files, start on Refresh (2); otherwise start with the list (0)
+        let field_index = if entries.is_empty() { 2 } else { 0 };
+        Self { dir, fs, entries, field_index, list_index: 0 }
     }
 
     fn refresh_list(&mut self) -> Result<()> {
-        self.entries = read_files_only(&self.dir).unwrap_or_default();
+        self.entries = list_files(&*self.fs, &self.dir).unwrap_or_default();
         // If list now empty, focus Refresh; otherwise focus top of list
         if self.entries.is_empty() {
-            self.field_index = 1;
+            self.field_index = 2;
             self.list_index = 0;
         } else {
             self.field_index = 0;
@@ -46,8 +47,10 @@ impl SelectFileForDecryptionScreen {
         Ok(())
     }
 
-    fn buttons_line(refresh_selected: bool, back_selected: bool) -> Line<'static> {
+    fn buttons_line(all_selected: bool, refresh_selected: bool, back_selected: bool) -> Line<'static> {
         let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Decrypt All", all_selected));
+        spans.push(Span::raw("   "));
         spans.extend(button_spans("Refresh List", refresh_selected));
         spans.push(Span::raw("   "));
         spans.extend(button_spans("Back", back_selected));
@@ -55,28 +58,17 @@ impl SelectFileForDecryptionScreen {
     }
 }
 
-fn read_files_only(dir: &Path) -> Result<Vec<PathBuf>> {
-    let mut out = Vec::new();
-    for ent in fs::read_dir(dir).with_context(|| format!("listing {}", dir.display()))? {
-        let ent = ent?;
-        let p = ent.path();
-        if p.is_file() {
-            out.push(p);
-        }
-    }
-    out.sort();
-    Ok(out)
-}
 
 #[async_trait]
 impl ScreenWidget for SelectFileForDecryptionScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Select File" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Select File to Decrypt";
         let explanation_paras = [
             &format!("Directory: {}", self.dir.display()),
-            "Use ↑/↓ (or Tab) to move focus. Enter to select.",
+            "Use ↑/↓ (or Tab) to move focus. Enter to select. Press V to inspect the highlighted file in the hex viewer. Decrypt All decrypts every encrypted file listed here.",
         ];
 
         // --- TOP sizing ---
@@ -139,7 +131,7 @@ impl ScreenWidget for SelectFileForDecryptionScreen {
 
         // Buttons row
         items.push(ListItem::new(Line::from("")));
-        items.push(ListItem::new(Self::buttons_line(self.field_index == 1, self.field_index == 2)));
+        items.push(ListItem::new(Self::buttons_line(self.field_index == 1, self.field_index == 2, self.field_index == 3)));
 
         let list = List::new(items)
             .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
@@ -150,6 +142,7 @@ impl ScreenWidget for SelectFileForDecryptionScreen {
         let footer_line = Line::from(vec![
             span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
             span_key("Enter"), span_text(" Select"),   span_sep(),
+            span_key("V"),     span_text(" Hex View"), span_sep(),
             span_key("Esc"),   span_text(" Back"),     span_sep(),
             span_key("Ctrl+Q"),span_text(" Quit"),
         ]);
@@ -174,7 +167,7 @@ impl ScreenWidget for SelectFileForDecryptionScreen {
         let has_files = !self.entries.is_empty();
 
         match key {
-            // DOWN cycles: List -> Refresh -> Back -> (top of) List
+            // DOWN cycles: List -> Decrypt All -> Refresh -> Back -> (top of) List
             KeyCode::Down => {
                 if has_files {
                     match self.field_index {
@@ -182,20 +175,20 @@ impl ScreenWidget for SelectFileForDecryptionScreen {
                             if self.list_index + 1 < self.entries.len() {
                                 self.list_index += 1;
                             } else {
-                                self.field_index = 1; // move to Refresh
+                                self.field_index = 1; // move to Decrypt All
                             }
                         }
-                        1 => { self.field_index = 2; } // Refresh -> Back
-                        2 => { self.field_index = 0; self.list_index = 0; } // Back -> top of List
+                        1 | 2 => { self.field_index += 1; } // Decrypt All -> Refresh -> Back
+                        3 => { self.field_index = 0; self.list_index = 0; } // Back -> top of List
                         _ => {}
                     }
                 } else {
-                    // No files: toggle between Refresh (1) and Back (2)
-                    self.field_index = if self.field_index == 1 { 2 } else { 1 };
+                    // No files: toggle between Refresh (2) and Back (3)
+                    self.field_index = if self.field_index == 2 { 3 } else { 2 };
                 }
             }
 
-            // UP cycles reverse: List (top) -> Back -> Refresh -> (bottom of) List
+            // UP cycles reverse: List (top) -> Back -> Refresh -> Decrypt All -> (bottom of) List
             KeyCode::Up => {
                 if has_files {
                     match self.field_index {
@@ -203,22 +196,22 @@ impl ScreenWidget for SelectFileForDecryptionScreen {
                             if self.list_index > 0 {
                                 self.list_index -= 1;
                             } else {
-                                self.field_index = 2; // top-of-list -> Back
+                                self.field_index = 3; // top-of-list -> Back
                             }
                         }
                         1 => {
-                            // Refresh -> bottom of List
+                            // Decrypt All -> bottom of List
                             self.field_index = 0;
                             if !self.entries.is_empty() {
                                 self.list_index = self.entries.len().saturating_sub(1);
                             }
                         }
-                        2 => { self.field_index = 1; } // Back -> Refresh
+                        2 | 3 => { self.field_index -= 1; } // Back -> Refresh -> Decrypt All
                         _ => {}
                     }
                 } else {
-                    // No files: toggle between Back (2) and Refresh (1)
-                    self.field_index = if self.field_index == 2 { 1 } else { 2 };
+                    // No files: toggle between Back (3) and Refresh (2)
+                    self.field_index = if self.field_index == 3 { 2 } else { 3 };
                 }
             }
 
@@ -231,13 +224,33 @@ impl ScreenWidget for SelectFileForDecryptionScreen {
                 }
             }
 
-            // Enter on Refresh
+            // V on list selection -> hex viewer (read-only)
+            KeyCode::Char('v') | KeyCode::Char('V') if self.field_index == 0 => {
+                if let Some(sel) = self.entries.get(self.list_index).cloned() {
+                    return Ok(Transition::Push(match crate::screens::HexViewerScreen::new(sel) {
+                        Ok(viewer) => Box::new(viewer),
+                        Err(e) => Box::new(
+                            crate::screens::ConfirmOkScreen::new(format!("Error: {e:#}"))
+                                .with_after_ok(crate::screens::AfterOk::Pop)
+                        ),
+                    }));
+                }
+            }
+
+            // Enter on Decrypt All -> batch screen for every encrypted file listed
             KeyCode::Enter if self.field_index == 1 => {
+                return Ok(Transition::Push(Box::new(
+                    crate::screens::BatchDecryptScreen::new(self.dir.clone(), &self.entries)
+                )));
+            }
+
+            // Enter on Refresh
+            KeyCode::Enter if self.field_index == 2 => {
                 self.refresh_list()?;
             }
 
             // Enter on Back
-            KeyCode::Enter if self.field_index == 2 => {
+            KeyCode::Enter if self.field_index == 3 => {
                 return Ok(Transition::Pop);
             }
 
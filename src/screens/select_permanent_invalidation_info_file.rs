@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use async_trait::async_trait;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -10,31 +10,46 @@ use ratatui::{
 };
 use textwrap::wrap;
 
-use std::fs;
 use std::path::{PathBuf, Path};
 
 use crate::app::{AppCtx, ScreenWidget, Transition, DelegationPrefill};
+use crate::app_state::{AppEvent, Form};
+use crate::vfs::{list_files, FsRef};
+use crate::screens::choose_permanent_invalidation_info_dir::PICKER;
 use crate::ui::layout::{three_box_layout, Margins};
 use crate::ui::style::{span_key, span_sep, span_text, button_spans};
 use crate::ui::common_nav::esc_to_back;
 use crate::util::parse_permanent_invalidation_env;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::screens::confirm_secret_prefill::stash_prefill;
 
 pub struct SelectPermanentInvalidationInfoFileScreen {
     dir: PathBuf,
+    fs: FsRef,
     entries: Vec<PathBuf>,
     field_index: usize, // 0 = list, 1 = Refresh, 2 = Back
     list_index: usize,
 }
 
 impl SelectPermanentInvalidationInfoFileScreen {
-    pub fn new(dir: PathBuf) -> Self {
-        let entries = read_files_only(&dir).unwrap_or_default();
+    pub fn new(dir: PathBuf, fs: FsRef) -> Self {
+        let entries = list_files(&*fs, &dir).unwrap_or_default();
         let field_index = if entries.is_empty() { 1 } else { 0 };
-        Self { dir, entries, field_index, list_index: 0 }
+        Self { dir, fs, entries, field_index, list_index: 0 }
+    }
+
+
+    /// Put the cursor back on `selected` if it is still listed.
+    pub fn with_selection(mut self, selected: Option<&Path>) -> Self {
+        if let Some(i) = selected.and_then(|sel| self.entries.iter().position(|e| e == sel)) {
+            self.field_index = 0;
+            self.list_index = i;
+        }
+        self
     }
 
     fn refresh_list(&mut self) -> Result<()> {
-        self.entries = read_files_only(&self.dir).unwrap_or_default();
+        self.entries = list_files(&*self.fs, &self.dir).unwrap_or_default();
         if self.entries.is_empty() { self.field_index = 1; self.list_index = 0; }
         else { self.field_index = 0; self.list_index = 0; }
         Ok(())
@@ -49,20 +64,11 @@ impl SelectPermanentInvalidationInfoFileScreen {
     }
 }
 
-fn read_files_only(dir: &Path) -> Result<Vec<PathBuf>> {
-    let mut out = Vec::new();
-    for ent in fs::read_dir(dir).with_context(|| format!("listing {}", dir.display()))? {
-        let ent = ent?;
-        let p = ent.path();
-        if p.is_file() { out.push(p); }
-    }
-    out.sort();
-    Ok(out)
-}
 
 #[async_trait]
 impl ScreenWidget for SelectPermanentInvalidationInfoFileScreen {
     fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Select File" }
 
     fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
         let header_text = "Select Permanent Invalidation Info File";
@@ -149,6 +155,13 @@ impl ScreenWidget for SelectPermanentInvalidationInfoFileScreen {
     }
 
     async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(sel) = self.entries.get(self.list_index) {
+            if ctx.state.picker(PICKER).and_then(|m| m.selected.as_ref()) != Some(sel) {
+                let event = AppEvent::PickerFileHighlighted { picker: PICKER, path: sel.clone() };
+                ctx.dispatch(self.crumb(), event);
+            }
+        }
+
         if let Some(t) = esc_to_back(k) { return Ok(t); }
 
         if let KeyCode::Char('q') = k.code {
@@ -200,15 +213,29 @@ impl ScreenWidget for SelectPermanentInvalidationInfoFileScreen {
             // Enter on list selection -> read, parse, stash -> PopN(2) back to form
             KeyCode::Enter if self.field_index == 0 => {
                 if let Some(sel) = self.entries.get(self.list_index).cloned() {
-                    let contents = fs::read_to_string(&sel)
-                        .with_context(|| format!("reading {}", sel.display()))?;
-                    let map = parse_permanent_invalidation_env(&contents);
+                    let env = match parse_permanent_invalidation_env(&*self.fs, &sel) {
+                        Ok(env) => env,
+                        Err(e) => {
+                            return Ok(Transition::Push(Box::new(
+                                // One line per include level, outermost first.
+                                ConfirmOkScreen::with_lines(
+                                    std::iter::once(format!("Error loading {}:", sel.display()))
+                                        .chain(e.chain().map(|c| format!("  {c}")))
+                                        .collect(),
+                                ).with_after_ok(AfterOk::Pop)
+                            )));
+                        }
+                    };
 
                     // Stash for the Permanent Invalidation form to apply
-                    ctx.pending_permanent_invalidation_prefill = Some(DelegationPrefill { map });
-
+                    let prefill = DelegationPrefill {
+                            map: env.map,
+                            source_sha256: Some(env.sha256),
+                            includes: env.includes,
+                    };
                     // Jump straight back: Select File -> Choose Dir -> Create Permanent Invalidation Form
-                    return Ok(Transition::PopN(2));
+                    // (after confirming any private keys it holds)
+                    return Ok(stash_prefill(ctx, self.crumb(), Form::PermanentInvalidation, &sel, prefill));
                 }
             }
 
@@ -0,0 +1,309 @@
+// src/screens/select_signed_file.rs
+
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::{AppEvent, Form};
+use crate::clone_signed::clone_signed_file;
+use crate::verify_signed::verify_signed_file;
+use crate::vfs::{DirEntry, FsRef};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::screens::{AfterOk, ConfirmOkScreen, VerifyReportScreen};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+use crate::defaults::Defaults;
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 30,
+        title: "Verify Signed File (Read-Only)",
+        description: "Decode a signed file and check signatures, chains, nonces and fees.",
+        open: |ctx| Transition::Push(Box::new(SelectSignedFileScreen::for_verify(PathBuf::from(Defaults::VERIFY_INPUT_DIR), ctx.fs.clone()))),
+    }
+}
+
+/// What the picked file is for.
+#[derive(Clone, Copy)]
+enum Purpose {
+    /// Clone into the Create form signing this ABI function; other transactions are refused.
+    Clone(&'static str),
+    /// Read-only verification (see `verify_signed.rs`).
+    Verify,
+}
+
+/// Picks a signed transaction file, either to clone into a Create form (see `clone_signed.rs`)
+/// or to verify. Cloning starts in the form's Output Directory; subdirectories (e.g. batch run
+/// directories) can be entered and `..` goes up. The form applies the values like a loaded
+/// info file.
+pub struct SelectSignedFileScreen {
+    purpose: Purpose,
+    dir: PathBuf,
+    fs: FsRef,
+    /// Subdirectories first, then files, each sorted; `..` leads when there is a parent.
+    entries: Vec<DirEntry>,
+    field_index: usize, // 0 = list, 1 = Refresh, 2 = Back
+    list_index: usize,
+}
+
+impl SelectSignedFileScreen {
+    pub fn new(func_name: &'static str, dir: PathBuf, fs: FsRef) -> Self {
+        Self::with_purpose(Purpose::Clone(func_name), dir, fs)
+    }
+
+    /// Picker for Verify Signed File; starts in `dir`, or the working directory if it is missing.
+    pub fn for_verify(dir: PathBuf, fs: FsRef) -> Self {
+        let dir = if fs.is_dir(&dir) { dir } else { PathBuf::from(".") };
+        Self::with_purpose(Purpose::Verify, dir, fs)
+    }
+
+    fn with_purpose(purpose: Purpose, dir: PathBuf, fs: FsRef) -> Self {
+        let mut s = Self { purpose, dir: crate::vfs::resolve(&dir), fs, entries: Vec::new(), field_index: 0, list_index: 0 };
+        s.refresh_list();
+        s
+    }
+
+    fn refresh_list(&mut self) {
+        let mut entries = self.fs.read_dir(&self.dir).unwrap_or_default();
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.path.cmp(&b.path)));
+        if self.dir.parent().is_some() {
+            entries.insert(0, DirEntry { path: self.dir.join(".."), is_dir: true });
+        }
+        self.entries = entries;
+        self.list_index = 0;
+        self.field_index = if self.entries.is_empty() { 1 } else { 0 };
+    }
+
+    fn label(entry: &DirEntry) -> String {
+        let name = entry.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "..".into());
+        if entry.is_dir { format!("{name}/") } else { name }
+    }
+
+    fn buttons_line(refresh_selected: bool, back_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Refresh List", refresh_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for SelectSignedFileScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str {
+        match self.purpose {
+            Purpose::Clone(_) => "Clone Signed File",
+            Purpose::Verify => "Verify Signed File",
+        }
+    }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let (header_text, purpose_para) = match self.purpose {
+            Purpose::Clone(func_name) => (
+                "Select Signed File To Clone",
+                format!("Pick a signed {func_name} file. Its keys (public), flags and gas settings are copied into the form; private keys and the nonce are left for you to enter."),
+            ),
+            Purpose::Verify => (
+                "Select Signed File To Verify",
+                "Pick a signed transaction file received from another party. It is checked against this tool's network registry and payload signing settings; the file is never modified.".to_string(),
+            ),
+        };
+        let explanation_paras = [
+            &format!("Directory: {}", self.dir.display()),
+            &purpose_para,
+            "Use ↑/↓ (or Tab) to move focus. Enter opens a directory or selects a file.",
+        ];
+
+        // --- TOP sizing ---
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle: list + spacer + buttons
+        let middle_rows: u16 = (self.entries.len() as u16).saturating_add(3);
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        let mut items: Vec<ListItem> = Vec::new();
+        items.push(ListItem::new(Line::from(""))); // spacer on top
+
+        if self.entries.is_empty() {
+            items.push(ListItem::new(Line::from("No files found in this directory.")));
+        } else {
+            for (i, e) in self.entries.iter().enumerate() {
+                let selected = self.field_index == 0 && self.list_index == i;
+                let prefix = if selected { "▶ " } else { "  " };
+                let name_style = if e.is_dir { Style::default().fg(Color::Blue) } else { Style::default() };
+                let line = Line::from(vec![
+                    Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                    Span::styled(Self::label(e), name_style),
+                ]);
+                items.push(ListItem::new(line));
+            }
+        }
+
+        // Buttons row
+        items.push(ListItem::new(Line::from("")));
+        items.push(ListItem::new(Self::buttons_line(self.field_index == 1, self.field_index == 2)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        f.render_widget(list, regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"), span_text(" Open/Select"), span_sep(),
+            span_key("Esc"),   span_text(" Back"),     span_sep(),
+            span_key("Ctrl+Q"),span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        // Treat Tab exactly like Down
+        let key = match k.code { KeyCode::Tab => KeyCode::Down, other => other };
+
+        let has_entries = !self.entries.is_empty();
+
+        match key {
+            // DOWN cycles: List -> Refresh -> Back -> (top of) List
+            KeyCode::Down => {
+                if has_entries {
+                    match self.field_index {
+                        0 => {
+                            if self.list_index + 1 < self.entries.len() { self.list_index += 1; }
+                            else { self.field_index = 1; }
+                        }
+                        1 => { self.field_index = 2; }
+                        2 => { self.field_index = 0; self.list_index = 0; }
+                        _ => {}
+                    }
+                } else {
+                    self.field_index = if self.field_index == 1 { 2 } else { 1 };
+                }
+            }
+
+            // UP cycles reverse
+            KeyCode::Up => {
+                if has_entries {
+                    match self.field_index {
+                        0 => {
+                            if self.list_index > 0 { self.list_index -= 1; }
+                            else { self.field_index = 2; }
+                        }
+                        1 => { self.field_index = 0; self.list_index = self.entries.len().saturating_sub(1); }
+                        2 => { self.field_index = 1; }
+                        _ => {}
+                    }
+                } else {
+                    self.field_index = if self.field_index == 2 { 1 } else { 2 };
+                }
+            }
+
+            // Enter on a directory -> open it; on a file -> verify, or decode, stash for the form and go back
+            KeyCode::Enter if self.field_index == 0 => {
+                let Some(entry) = self.entries.get(self.list_index).cloned() else { return Ok(Transition::Stay) };
+                if entry.is_dir {
+                    self.dir = crate::vfs::resolve(&entry.path);
+                    self.refresh_list();
+                    return Ok(Transition::Stay);
+                }
+                let func_name = match self.purpose {
+                    Purpose::Clone(func_name) => func_name,
+                    Purpose::Verify => {
+                        return Ok(Transition::Push(match verify_signed_file(&*self.fs, &entry.path, &ctx.settings) {
+                            Ok(report) => Box::new(VerifyReportScreen::new(report)),
+                            Err(e) => Box::new(
+                                ConfirmOkScreen::with_lines(
+                                    std::iter::once(format!("Cannot verify {}:", entry.path.display()))
+                                        .chain(e.chain().map(|c| format!("  {c}")))
+                                        .collect(),
+                                ).with_after_ok(AfterOk::Pop)
+                            ),
+                        }));
+                    }
+                };
+                let prefill = match clone_signed_file(&*self.fs, &entry.path, func_name) {
+                    Ok(prefill) => prefill,
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::with_lines(
+                                std::iter::once(format!("Cannot clone {}:", entry.path.display()))
+                                    .chain(e.chain().map(|c| format!("  {c}")))
+                                    .collect(),
+                            ).with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                };
+                let form = Form::for_function(func_name);
+                ctx.dispatch(self.crumb(), AppEvent::PrefillLoaded { form, prefill });
+                return Ok(Transition::Pop);
+            }
+
+            // Enter on Refresh
+            KeyCode::Enter if self.field_index == 1 => { self.refresh_list(); }
+
+            // Enter on Back
+            KeyCode::Enter if self.field_index == 2 => { return Ok(Transition::Pop); }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,574 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::defaults::Defaults;
+use crate::settings::{save_settings, PayloadDigest, Settings, SignedOutputFormat};
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Configuration),
+        order: 10,
+        title: "Settings",
+        description: "Every setting on one page, with search.",
+        open: |_| Transition::Push(Box::new(SettingsScreen::new())),
+    }
+}
+
+/// Every tunable in `Settings`, in display order. A new setting gets a variant here.
+#[derive(Copy, Clone, Debug)]
+enum Field {
+    TemplateDelegation,
+    TemplateRevocation,
+    TemplateRedelegation,
+    TemplatePermanentInvalidation,
+    SignedOutputFormat,
+    SignedOutputEncoding,
+    OperatorLabel,
+    PayloadDigestDefault,
+    PayloadDigestOverrides,
+    BundleSigner,
+    GasDelegation,
+    GasRevocation,
+    GasRedelegation,
+    GasPermanentInvalidation,
+    CapGasLimit,
+    CapMaxFeePerGas,
+    CapMaxPriorityFeePerGas,
+    SimulationStateFile,
+    CeremonyChecklist,
+    EventStream,
+    UsageStats,
+    DisplayLocale,
+    UnitEquivalents,
+    UtcOffset,
+    PurgeScrollback,
+    ExitMemoryHygiene,
+    SandboxRoots,
+    EphemeralRunDir,
+    RefuseSecretPrefill,
+}
+
+enum Kind {
+    Text,
+    /// Enter steps through the fixed choices.
+    Cycle,
+    /// Shown for completeness; edited in the settings file.
+    ReadOnly,
+}
+
+impl Field {
+    fn all() -> [Field; 29] {
+        [
+            Field::TemplateDelegation,
+            Field::TemplateRevocation,
+            Field::TemplateRedelegation,
+            Field::TemplatePermanentInvalidation,
+            Field::SignedOutputFormat,
+            Field::SignedOutputEncoding,
+            Field::OperatorLabel,
+            Field::PayloadDigestDefault,
+            Field::PayloadDigestOverrides,
+            Field::BundleSigner,
+            Field::GasDelegation,
+            Field::GasRevocation,
+            Field::GasRedelegation,
+            Field::GasPermanentInvalidation,
+            Field::CapGasLimit,
+            Field::CapMaxFeePerGas,
+            Field::CapMaxPriorityFeePerGas,
+            Field::SimulationStateFile,
+            Field::CeremonyChecklist,
+            Field::EventStream,
+            Field::UsageStats,
+            Field::DisplayLocale,
+            Field::UnitEquivalents,
+            Field::UtcOffset,
+            Field::PurgeScrollback,
+            Field::ExitMemoryHygiene,
+            Field::SandboxRoots,
+            Field::EphemeralRunDir,
+            Field::RefuseSecretPrefill,
+        ]
+    }
+
+    fn section(self) -> &'static str {
+        match self {
+            Field::TemplateDelegation
+            | Field::TemplateRevocation
+            | Field::TemplateRedelegation
+            | Field::TemplatePermanentInvalidation
+            | Field::SignedOutputFormat
+            | Field::SignedOutputEncoding
+            | Field::OperatorLabel => "Output files",
+            Field::PayloadDigestDefault | Field::PayloadDigestOverrides | Field::BundleSigner => "Signing",
+            Field::GasDelegation
+            | Field::GasRevocation
+            | Field::GasRedelegation
+            | Field::GasPermanentInvalidation => "Gas defaults (empty = cap)",
+            Field::CapGasLimit
+            | Field::CapMaxFeePerGas
+            | Field::CapMaxPriorityFeePerGas => "Gas caps (empty = built-in)",
+            Field::SimulationStateFile | Field::CeremonyChecklist => "Checks",
+            Field::EventStream | Field::UsageStats => "Monitoring",
+            Field::DisplayLocale | Field::UnitEquivalents | Field::UtcOffset => "Display",
+            Field::PurgeScrollback | Field::ExitMemoryHygiene => "Privacy",
+            Field::SandboxRoots | Field::EphemeralRunDir | Field::RefuseSecretPrefill => "File access",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::TemplateDelegation => "Delegation filename template",
+            Field::TemplateRevocation => "Revocation filename template",
+            Field::TemplateRedelegation => "Re-delegation filename template",
+            Field::TemplatePermanentInvalidation => "Permanent invalidation filename template",
+            Field::SignedOutputFormat => "Signed file format",
+            Field::SignedOutputEncoding => "Signed file encoding",
+            Field::OperatorLabel => "Operator label",
+            Field::PayloadDigestDefault => "Payload digest (default)",
+            Field::PayloadDigestOverrides => "Payload digest per contract",
+            Field::BundleSigner => "Bundle signer (address, empty = any)",
+            Field::GasDelegation => "Delegation gas limit",
+            Field::GasRevocation => "Revocation gas limit",
+            Field::GasRedelegation => "Re-delegation gas limit",
+            Field::GasPermanentInvalidation => "Permanent invalidation gas limit",
+            Field::CapGasLimit => "Maximum gas limit",
+            Field::CapMaxFeePerGas => "Maximum max fee per gas (wei)",
+            Field::CapMaxPriorityFeePerGas => "Maximum priority fee per gas (wei)",
+            Field::SimulationStateFile => "Simulation state file",
+            Field::CeremonyChecklist => "Ceremony checklist (YAML)",
+            Field::EventStream => "Event stream (file or socket)",
+            Field::UsageStats => "Record local usage statistics",
+            Field::DisplayLocale => "Number and date format",
+            Field::UnitEquivalents => "Show gwei/ETH next to wei",
+            Field::UtcOffset => "Time zone offset (e.g. +02:00, empty = UTC)",
+            Field::PurgeScrollback => "Request scrollback purge (best effort)",
+            Field::ExitMemoryHygiene => "OS memory hygiene at exit",
+            Field::SandboxRoots => "Sandbox directories (; separated)",
+            Field::EphemeralRunDir => "Ephemeral run directory (tmpfs)",
+            Field::RefuseSecretPrefill => "Refuse info files with private keys",
+        }
+    }
+
+    fn kind(self) -> Kind {
+        match self {
+            Field::SignedOutputFormat
+            | Field::SignedOutputEncoding
+            | Field::PayloadDigestDefault
+            | Field::UsageStats
+            | Field::DisplayLocale
+            | Field::UnitEquivalents
+            | Field::PurgeScrollback
+            | Field::ExitMemoryHygiene
+            | Field::EphemeralRunDir
+            | Field::RefuseSecretPrefill => Kind::Cycle,
+            Field::PayloadDigestOverrides => Kind::ReadOnly,
+            _ => Kind::Text,
+        }
+    }
+
+    fn value(self, s: &Settings) -> String {
+        match self {
+            Field::TemplateDelegation => s.filename_templates.delegation.clone(),
+            Field::TemplateRevocation => s.filename_templates.revocation.clone(),
+            Field::TemplateRedelegation => s.filename_templates.redelegation.clone(),
+            Field::TemplatePermanentInvalidation => s.filename_templates.permanent_invalidation.clone(),
+            Field::SignedOutputFormat => match s.signed_output_format {
+                SignedOutputFormat::WithMetadata => "with metadata (v2)".into(),
+                SignedOutputFormat::LegacyArray => "legacy array (v1)".into(),
+            },
+            Field::SignedOutputEncoding => {
+                let e = s.signed_output_encoding;
+                if e.readable() { e.label().into() } else { format!("{} (not read back by this tool)", e.label()) }
+            }
+            Field::OperatorLabel => s.operator_label.clone(),
+            Field::PayloadDigestDefault => digest_label(s.payload_signing.default).into(),
+            Field::PayloadDigestOverrides => {
+                let p = &s.payload_signing;
+                format!("{} override(s), edit {}", p.by_contract.len() + p.scheme_by_contract.len(), Defaults::SETTINGS_FILE)
+            }
+            Field::BundleSigner => s.bundle_signer.clone(),
+            Field::GasDelegation => s.gas_limits.delegation.clone(),
+            Field::GasRevocation => s.gas_limits.revocation.clone(),
+            Field::GasRedelegation => s.gas_limits.redelegation.clone(),
+            Field::GasPermanentInvalidation => s.gas_limits.permanent_invalidation.clone(),
+            Field::CapGasLimit => s.gas_caps.gas_limit.clone(),
+            Field::CapMaxFeePerGas => s.gas_caps.max_fee_per_gas.clone(),
+            Field::CapMaxPriorityFeePerGas => s.gas_caps.max_priority_fee_per_gas.clone(),
+            Field::SimulationStateFile => s.simulation_state_file.clone(),
+            Field::CeremonyChecklist => s.ceremony_checklist.clone(),
+            Field::EventStream => s.event_stream.clone(),
+            Field::UsageStats => if s.usage_stats { "yes".into() } else { "no".into() },
+            Field::DisplayLocale => s.display.locale.label().into(),
+            Field::UnitEquivalents => if s.display.unit_equivalents { "yes".into() } else { "no".into() },
+            Field::UtcOffset => s.display.utc_offset.clone(),
+            Field::PurgeScrollback => if s.purge_scrollback { "yes".into() } else { "no".into() },
+            Field::ExitMemoryHygiene => if s.exit_memory_hygiene { "yes".into() } else { "no".into() },
+            Field::SandboxRoots => s.sandbox_roots.join("; "),
+            Field::EphemeralRunDir => if s.ephemeral_run_dir { "yes".into() } else { "no".into() },
+            Field::RefuseSecretPrefill => if s.refuse_secret_prefill { "yes".into() } else { "no".into() },
+        }
+    }
+
+    fn set_text(self, s: &mut Settings, v: String) {
+        match self {
+            Field::TemplateDelegation => s.filename_templates.delegation = v,
+            Field::TemplateRevocation => s.filename_templates.revocation = v,
+            Field::TemplateRedelegation => s.filename_templates.redelegation = v,
+            Field::TemplatePermanentInvalidation => s.filename_templates.permanent_invalidation = v,
+            Field::OperatorLabel => s.operator_label = v,
+            Field::BundleSigner => s.bundle_signer = v.trim().to_string(),
+            Field::GasDelegation => s.gas_limits.delegation = v.trim().to_string(),
+            Field::GasRevocation => s.gas_limits.revocation = v.trim().to_string(),
+            Field::GasRedelegation => s.gas_limits.redelegation = v.trim().to_string(),
+            Field::GasPermanentInvalidation => s.gas_limits.permanent_invalidation = v.trim().to_string(),
+            Field::CapGasLimit => s.gas_caps.gas_limit = v.trim().to_string(),
+            Field::CapMaxFeePerGas => s.gas_caps.max_fee_per_gas = v.trim().to_string(),
+            Field::CapMaxPriorityFeePerGas => s.gas_caps.max_priority_fee_per_gas = v.trim().to_string(),
+            Field::SimulationStateFile => s.simulation_state_file = v,
+            Field::CeremonyChecklist => s.ceremony_checklist = v.trim().to_string(),
+            Field::EventStream => s.event_stream = v.trim().to_string(),
+            Field::UtcOffset => s.display.utc_offset = v.trim().to_string(),
+            Field::SandboxRoots => {
+                s.sandbox_roots = v.split(';').map(str::trim).filter(|r| !r.is_empty()).map(String::from).collect();
+            }
+            _ => {}
+        }
+    }
+
+    fn cycle(self, s: &mut Settings) {
+        match self {
+            Field::SignedOutputFormat => {
+                s.signed_output_format = match s.signed_output_format {
+                    SignedOutputFormat::WithMetadata => SignedOutputFormat::LegacyArray,
+                    SignedOutputFormat::LegacyArray => SignedOutputFormat::WithMetadata,
+                }
+            }
+            Field::SignedOutputEncoding => s.signed_output_encoding = s.signed_output_encoding.next(),
+            Field::PayloadDigestDefault => {
+                s.payload_signing.default = match s.payload_signing.default {
+                    PayloadDigest::Eip191 => PayloadDigest::RawKeccak,
+                    PayloadDigest::RawKeccak => PayloadDigest::Eip191,
+                }
+            }
+            Field::UsageStats => s.usage_stats = !s.usage_stats,
+            Field::DisplayLocale => s.display.locale = s.display.locale.next(),
+            Field::UnitEquivalents => s.display.unit_equivalents = !s.display.unit_equivalents,
+            Field::PurgeScrollback => s.purge_scrollback = !s.purge_scrollback,
+            Field::ExitMemoryHygiene => s.exit_memory_hygiene = !s.exit_memory_hygiene,
+            Field::EphemeralRunDir => s.ephemeral_run_dir = !s.ephemeral_run_dir,
+            Field::RefuseSecretPrefill => s.refuse_secret_prefill = !s.refuse_secret_prefill,
+            _ => {}
+        }
+    }
+
+    /// Incremental search: case-insensitive match on section, label or current value.
+    fn matches(self, s: &Settings, query: &str) -> bool {
+        let q = query.trim().to_lowercase();
+        q.is_empty()
+            || [self.section().to_string(), self.label().to_string(), self.value(s)]
+                .iter()
+                .any(|t| t.to_lowercase().contains(&q))
+    }
+}
+
+fn digest_label(d: PayloadDigest) -> &'static str {
+    match d {
+        PayloadDigest::Eip191 => "EIP-191 personal message",
+        PayloadDigest::RawKeccak => "raw keccak256",
+    }
+}
+
+/// All settings on one page, grouped by section, with incremental search.
+/// Edits go to a draft; Ctrl+S writes it through `save_settings` (encrypted under a profile).
+#[derive(Default)]
+pub struct SettingsScreen {
+    /// Copied from `ctx.settings` on first show.
+    draft: Option<Settings>,
+    search: TextField,
+    /// Index into the filtered rows.
+    selected: usize,
+    editing: Option<TextField>,
+    dirty: bool,
+    /// Esc with unsaved edits warns once; a second Esc discards.
+    discard_armed: bool,
+    status: Option<String>,
+}
+
+impl SettingsScreen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn rows(&self) -> Vec<Field> {
+        let Some(draft) = &self.draft else { return Vec::new() };
+        Field::all().into_iter().filter(|f| f.matches(draft, &self.search.text)).collect()
+    }
+
+    fn current(&self) -> Option<Field> {
+        self.rows().get(self.selected).copied()
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for SettingsScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Settings" }
+
+    fn apply_prefill(&mut self, ctx: &mut AppCtx) {
+        if self.draft.is_none() {
+            self.draft = Some(ctx.settings.clone());
+        }
+    }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let Some(draft) = &self.draft else { return };
+        let header_text = "Settings";
+        let explanation_paras = [
+            "Every setting in one place. Type to filter, Enter to edit or switch a value, Ctrl+S to save.",
+            "Changes apply to this session once saved and are written to the settings file (encrypted while a master password is set).",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        // header + blank + explanation + blank + search + status
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines + 1 + 1 + 1;
+
+        let rows = self.rows();
+        let middle_needed = 2 + 2 + (rows.len() as u16) * 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size,
+            top_needed,
+            middle_needed,
+            footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header_lines.max(1)),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(regions.top_inner);
+
+        f.render_widget(
+            Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+            top_chunks[0],
+        );
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) {
+                expl_lines.push(Line::from(seg.to_string()));
+            }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+        f.render_widget(
+            Paragraph::new(field_line_text("Search", &self.search, self.editing.is_none())),
+            top_chunks[3],
+        );
+        if let Some(msg) = &self.status {
+            f.render_widget(
+                Paragraph::new(Span::styled(msg.clone(), Style::default().fg(Color::Green))),
+                top_chunks[4],
+            );
+        } else if self.dirty {
+            f.render_widget(
+                Paragraph::new(Span::styled("Unsaved changes", Style::default().fg(Color::Yellow))),
+                top_chunks[4],
+            );
+        }
+
+        // MIDDLE: section headings + rows
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mut lines: Vec<Line> = Vec::new();
+        let mut selected_line = 0usize;
+        let mut section = "";
+        for (i, field) in rows.iter().enumerate() {
+            if field.section() != section {
+                section = field.section();
+                if !lines.is_empty() { lines.push(Line::from("")); }
+                lines.push(Line::from(Span::styled(
+                    section,
+                    Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                )));
+            }
+            let selected = i == self.selected;
+            if selected { selected_line = lines.len(); }
+            let prefix = Span::styled(if selected { "▶ " } else { "  " }, Style::default().fg(Color::Cyan));
+            let line = match (&self.editing, selected) {
+                (Some(tf), true) => {
+                    let mut l = field_line_text(field.label(), tf, true);
+                    l.spans.insert(0, prefix);
+                    l
+                }
+                _ => {
+                    let value = field.value(draft);
+                    let value_style = match field.kind() {
+                        Kind::ReadOnly => Style::default().fg(Color::DarkGray),
+                        _ => Style::default(),
+                    };
+                    Line::from(vec![
+                        prefix,
+                        Span::styled(format!("{}: ", field.label()), Style::default().fg(Color::Yellow)),
+                        Span::styled(if value.is_empty() { "(empty)".to_string() } else { value }, value_style),
+                    ])
+                }
+            };
+            lines.push(line);
+        }
+        if rows.is_empty() {
+            lines.push(Line::from(Span::styled("No setting matches the search.", Style::default().fg(Color::DarkGray))));
+        }
+        let visible = regions.middle_inner.height as usize;
+        let skip = (selected_line + 1).saturating_sub(visible);
+        f.render_widget(Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = if self.editing.is_some() {
+            Line::from(vec![
+                span_key("Enter"), span_text(" Apply"), span_sep(),
+                span_key("Esc"), span_text(" Cancel edit"),
+            ])
+        } else {
+            Line::from(vec![
+                span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+                span_key("Type"), span_text(" Search"), span_sep(),
+                span_key("Enter"), span_text(" Edit"), span_sep(),
+                span_key("Ctrl+S"), span_text(" Save"), span_sep(),
+                span_key("Esc"), span_text(" Back"), span_sep(),
+                span_key("Ctrl+Q"), span_text(" Quit"),
+            ])
+        };
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if k.modifiers.contains(KeyModifiers::CONTROL) && matches!(k.code, KeyCode::Char('q' | 'Q')) {
+            return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+        }
+        let Some(field) = self.current() else {
+            // Nothing selectable: only search editing and leaving apply.
+            match k.code {
+                KeyCode::Esc => { self.search = TextField::default(); }
+                KeyCode::Backspace => { self.search.backspace(); self.selected = 0; }
+                KeyCode::Char(c) if !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.search.insert_char(c);
+                    self.selected = 0;
+                }
+                _ => {}
+            }
+            return Ok(Transition::Stay);
+        };
+
+        // Editing a text value
+        if let Some(tf) = self.editing.as_mut() {
+            match k.code {
+                KeyCode::Enter => {
+                    let value = tf.text.clone();
+                    if let Some(draft) = self.draft.as_mut() {
+                        field.set_text(draft, value);
+                    }
+                    self.editing = None;
+                    self.dirty = true;
+                    self.status = None;
+                }
+                KeyCode::Esc => self.editing = None,
+                KeyCode::Left => tf.move_left(),
+                KeyCode::Right => tf.move_right(),
+                KeyCode::Home => tf.home(),
+                KeyCode::End => tf.end(),
+                KeyCode::Backspace => tf.backspace(),
+                KeyCode::Delete => tf.delete(),
+                KeyCode::Char(c) if !k.modifiers.contains(KeyModifiers::CONTROL) => tf.insert_char(c),
+                _ => {}
+            }
+            return Ok(Transition::Stay);
+        }
+
+        if k.code != KeyCode::Esc {
+            self.discard_armed = false;
+        }
+        let count = self.rows().len();
+        match k.code {
+            KeyCode::Esc if !self.search.text.is_empty() => {
+                self.search = TextField::default();
+                self.selected = 0;
+            }
+            KeyCode::Esc if self.dirty && !self.discard_armed => {
+                self.discard_armed = true;
+                self.status = Some("Unsaved changes: Ctrl+S saves, Esc again discards them.".into());
+            }
+            KeyCode::Esc => return Ok(Transition::Pop),
+            KeyCode::Char('s' | 'S') if k.modifiers.contains(KeyModifiers::CONTROL) => {
+                let Some(draft) = &self.draft else { return Ok(Transition::Stay) };
+                match save_settings(draft, ctx.profile_key.as_ref()) {
+                    Ok(()) => {
+                        ctx.set_settings(draft.clone());
+                        self.dirty = false;
+                        self.status = Some(format!("Saved to {}", Defaults::SETTINGS_FILE));
+                    }
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            crate::screens::ConfirmOkScreen::new(format!("Error saving settings: {e:#}")),
+                        )));
+                    }
+                }
+            }
+            KeyCode::Up => {
+                self.selected = if self.selected == 0 { count - 1 } else { self.selected - 1 };
+            }
+            KeyCode::Down | KeyCode::Tab => self.selected = (self.selected + 1) % count,
+            KeyCode::Enter => match field.kind() {
+                Kind::Text => {
+                    let value = self.draft.as_ref().map(|d| field.value(d)).unwrap_or_default();
+                    self.editing = Some(TextField::with(&value));
+                }
+                Kind::Cycle => {
+                    if let Some(draft) = self.draft.as_mut() {
+                        field.cycle(draft);
+                    }
+                    self.dirty = true;
+                    self.status = None;
+                }
+                Kind::ReadOnly => {}
+            },
+            KeyCode::Backspace => {
+                self.search.backspace();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) if !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.insert_char(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,302 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::path::Path;
+use textwrap::wrap;
+use zeroize::Zeroize;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::defaults::Defaults;
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::settings::save_settings;
+use crate::settings_bundle::{export_networks_bundle, export_settings_bundle, import_bundle, BundleContents};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{field_line_text, RevealBriefly, TextField, field_line_password};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Configuration),
+        order: 20,
+        title: "Settings Bundle (Import/Export)",
+        description: "Export the settings or network profiles to a signed file, or import them from one.",
+        open: |_| Transition::Push(Box::new(SettingsBundleScreen::new())),
+    }
+}
+
+/// Export the current settings or network profiles as a signed bundle, or import and verify one.
+pub struct SettingsBundleScreen {
+    // indices: 0 bundle file, 1 networks only, 2 signing key, 3 expected signer, 4 export, 5 import, 6 back
+    field_index: usize,
+    bundle_file: TextField,
+    /// Export only `settings.networks`; import tells the kinds apart by itself.
+    networks_only: bool,
+    signing_key: TextField,
+    expected_signer: TextField,
+    reveal: RevealBriefly,
+}
+
+impl SettingsBundleScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            bundle_file: TextField::with(Defaults::SETTINGS_BUNDLE_FILE),
+            networks_only: false,
+            signing_key: TextField::default(),
+            expected_signer: TextField::default(),
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 2 | 3) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.bundle_file,
+            2 => &mut self.signing_key,
+            3 => &mut self.expected_signer,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Export Bundle", self.field_index == 4));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Import Bundle", self.field_index == 5));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.field_index == 6));
+        Line::from(spans)
+    }
+
+    fn contents_line(&self) -> Line<'static> {
+        let selected = self.field_index == 1;
+        let value = if self.networks_only { "network profiles only" } else { "all settings" };
+        let style = if selected {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Line::from(vec![
+            Span::styled("Export Contents: ", Style::default().fg(Color::Yellow)),
+            Span::styled(value, style),
+        ])
+    }
+
+    /// Switch what Export writes, moving the bundle file along while it is still a default path.
+    fn toggle_contents(&mut self) {
+        self.networks_only = !self.networks_only;
+        let (from, to) = if self.networks_only {
+            (Defaults::SETTINGS_BUNDLE_FILE, Defaults::NETWORKS_BUNDLE_FILE)
+        } else {
+            (Defaults::NETWORKS_BUNDLE_FILE, Defaults::SETTINGS_BUNDLE_FILE)
+        };
+        if self.bundle_file.text.trim() == from {
+            self.bundle_file = TextField::with(to);
+        }
+    }
+
+    async fn export(&mut self, ctx: &AppCtx) -> Result<Vec<String>> {
+        if self.signing_key.text.trim().is_empty() {
+            return Err(anyhow!("Signing Key is required to export a bundle."));
+        }
+        let path = self.bundle_file.text.trim().to_string();
+        let res = if self.networks_only {
+            export_networks_bundle(&ctx.settings.networks, &self.signing_key.text, Path::new(&path)).await
+        } else {
+            export_settings_bundle(&ctx.settings, &self.signing_key.text, Path::new(&path)).await
+        };
+        self.signing_key.text.zeroize();
+        self.signing_key.cursor = 0;
+        let signer = res?;
+        let what = if self.networks_only {
+            format!("Networks bundle ({} profile(s))", ctx.settings.networks.0.len())
+        } else {
+            "Settings bundle".to_string()
+        };
+        Ok(vec![
+            format!("{what} written to {path}"),
+            format!("Signed by {signer}"),
+            "On the other machine, set this address as the bundle signer in Settings, or give it as Expected Signer.".to_string(),
+        ])
+    }
+
+    /// The signer the bundle must carry: the configured bundle signer, which Expected Signer
+    /// may repeat but not replace, else Expected Signer.
+    fn expected(&self, ctx: &AppCtx) -> Result<String> {
+        let configured = ctx.settings.bundle_signer.trim();
+        let typed = self.expected_signer.text.trim();
+        if configured.is_empty() {
+            return Ok(typed.to_string());
+        }
+        if !typed.is_empty() && !typed.eq_ignore_ascii_case(configured) {
+            return Err(anyhow!(
+                "Expected Signer {typed} differs from the bundle signer configured in Settings ({configured}). Clear the field or change the setting."
+            ));
+        }
+        Ok(configured.to_string())
+    }
+
+    fn import(&mut self, ctx: &mut AppCtx) -> Result<Vec<String>> {
+        let path = self.bundle_file.text.trim().to_string();
+        let expected = self.expected(ctx)?;
+        let (contents, signer) = import_bundle(Path::new(&path), Some(&expected))?;
+        let mut lines = match contents {
+            BundleContents::Settings(settings) => {
+                save_settings(&settings, ctx.profile_key.as_ref())?;
+                ctx.set_settings(*settings);
+                vec![format!("Settings imported from {path}")]
+            }
+            BundleContents::Networks(networks) => {
+                let mut settings = ctx.settings.clone();
+                let before = settings.networks.0.len();
+                settings.networks = networks;
+                save_settings(&settings, ctx.profile_key.as_ref())?;
+                let mut lines = vec![format!(
+                    "{} network profile(s) imported from {path}, replacing {before}:",
+                    settings.networks.0.len()
+                )];
+                for n in &settings.networks.0 {
+                    lines.push(format!("  {} (chain {}, {})", n.name, n.chain_id, n.contract_address));
+                }
+                ctx.set_settings(settings);
+                lines
+            }
+        };
+        lines.push(format!("Signature valid, signed by {signer}"));
+        if expected.is_empty() {
+            lines.push("No bundle signer is configured and no Expected Signer was given: compare the address above with the exporting machine.".to_string());
+        }
+        Ok(lines)
+    }
+}
+
+impl Default for SettingsBundleScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for SettingsBundleScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Settings Bundle" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Settings Bundle";
+        let explanation_paras = [
+            "Carry this machine's settings, or only its network profiles, to another air-gapped machine so every signer in a ceremony uses the same templates, output format, digest policy and deployments.",
+            "Export signs them with the Signing Key (hex or nsec; the key is not stored). Import checks the signature, and the signer against the bundle signer set in Settings or Expected Signer, then replaces and saves this machine's settings or network profiles.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + 4 fields + spacer + buttons
+        let middle_needed = 2 + 2 + 7;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Bundle File", &self.bundle_file, self.field_index == 0),
+            self.contents_line(),
+            field_line_password("Signing Key (export)", &self.signing_key, self.field_index == 2, self.reveal.shows(2, self.field_index)),
+            field_line_text("Expected Signer (import, optional)", &self.expected_signer, self.field_index == 3),
+            Line::from(""),
+            self.buttons_line(),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        if is_reveal_key(&k) {
+            if self.field_index == 2 { self.reveal.start(2); }
+            return Ok(Transition::Stay);
+        }
+
+        let outcome = |res: Result<Vec<String>>| {
+            Transition::Push(Box::new(match res {
+                Ok(lines) => ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop),
+                Err(e) => ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop),
+            }))
+        };
+
+        match k.code {
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 6; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 7;
+            }
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 1 => self.toggle_contents(),
+            KeyCode::Enter if self.field_index == 4 => return Ok(outcome(self.export(ctx).await)),
+            KeyCode::Enter if self.field_index == 5 => return Ok(outcome(self.import(ctx))),
+            KeyCode::Enter if self.field_index == 6 => return Ok(Transition::Pop),
+
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
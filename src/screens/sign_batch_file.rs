@@ -0,0 +1,608 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use ethers_core::types::Address;
+use ethers_core::utils::to_checksum;
+use std::path::{Path, PathBuf};
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::components::{TextField, field_line_text, warning_banner_lines};
+use crate::units::{GasLimit, Nonce, Wei};
+use crate::validation::validate_gas_fields;
+use crate::defaults::Defaults;
+use crate::display::DisplaySettings;
+
+// Generic OK-only modal
+use crate::screens::{ConfirmOkScreen, AfterOk};
+
+// ABI / batch signer / writer
+use crate::abi::load_abi;
+use crate::commands::batch::{load_batch_items, BatchRun, BatchSummary, NonceFix};
+use crate::process::BatchOpts;
+use crate::key_usage::KeyUsage;
+use crate::signing::SignerCache;
+use crate::settings::Settings;
+use crate::vfs::Fs;
+use crate::write_signed_transactions_to_file::BATCH_MANIFEST_FILE;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::SignVerify),
+        order: 10,
+        title: "Sign Batch File",
+        description: "Review and sign a JSON batch of transactions into a run directory.",
+        open: |_| Transition::Push(Box::new(SignBatchFileScreen::new())),
+    }
+}
+
+pub struct SignBatchFileScreen {
+    // 0 batch_file, 1 gas_limit, 2 max_fee_per_gas, 3 max_priority_fee_per_gas,
+    // 4 out_dir, 5 submit, 6 back
+    field_index: usize,
+    batch_file: TextField,
+    gas_limit: TextField,
+    max_fee_per_gas: TextField,
+    max_priority_fee_per_gas: TextField,
+    out_dir: TextField,
+    /// Loaded batch waiting for the operator to confirm its pre-flight summary.
+    review: Option<Review>,
+    /// Set while the batch is being signed, one item per tick.
+    run: Option<BatchRun>,
+}
+
+/// A prepared run and its summary, shown before anything is signed.
+struct Review {
+    run: BatchRun,
+    lines: Vec<String>,
+    /// Unregistered network and key reuse warnings, shown above the summary and not scrolled.
+    warnings: Vec<String>,
+    /// First summary line shown.
+    scroll: usize,
+    confirm_selected: bool,
+    /// Shown in place of the summary until accepted or rejected.
+    fixup: Option<NonceFixup>,
+}
+
+impl Review {
+    fn new(run: BatchRun, summary: &BatchSummary, display: &DisplaySettings) -> Self {
+        let fixup = (!summary.nonce_fixes.is_empty()).then(|| NonceFixup::new(&summary.nonce_fixes));
+        let mut review = Review { run, lines: Vec::new(), warnings: Vec::new(), scroll: 0, confirm_selected: false, fixup };
+        review.set_summary(summary, display);
+        review
+    }
+
+    fn set_summary(&mut self, summary: &BatchSummary, display: &DisplaySettings) {
+        let mut warnings = summary.network_warnings.clone();
+        warnings.extend(summary.nonce_warning());
+        warnings.extend(summary.key_reuse_warning());
+        warnings.extend(summary.retired_warning());
+        self.lines = summary.lines(display);
+        self.warnings = warnings;
+        self.scroll = 0;
+    }
+}
+
+/// Proposed nonces for the senders whose nonces have gaps or repeats, editable per item.
+struct NonceFixup {
+    rows: Vec<FixupRow>,
+    /// A row, or `rows.len()` for the buttons.
+    selected: usize,
+    accept_selected: bool,
+}
+
+struct FixupRow {
+    index: usize,
+    sender: Address,
+    current: u64,
+    proposed: TextField,
+}
+
+impl NonceFixup {
+    fn new(fixes: &[NonceFix]) -> Self {
+        let rows = fixes.iter()
+            .flat_map(|fix| fix.items.iter().map(move |(index, current, proposed)| FixupRow {
+                index: *index,
+                sender: fix.address,
+                current: *current,
+                proposed: TextField::with(&proposed.to_string()),
+            }))
+            .collect();
+        Self { rows, selected: 0, accept_selected: true }
+    }
+
+    /// The edited nonces, or an error naming the first item whose entry is not a nonce.
+    fn nonces(&self) -> Result<Vec<(usize, Nonce)>> {
+        self.rows.iter()
+            .map(|r| Ok((r.index, r.proposed.text.parse().with_context(|| format!("item {}: nonce must be an integer", r.index))?)))
+            .collect()
+    }
+
+    fn row_line(row: &FixupRow, selected: bool) -> Line<'static> {
+        let marker = if selected { "▶ " } else { "  " };
+        let changed = if row.proposed.text.trim() == row.current.to_string() { "" } else { "  (changed)" };
+        Line::from(format!(
+            "{marker}{:>5}  {}  {:>6} → {}{changed}",
+            row.index, to_checksum(&row.sender, None), row.current, row.proposed.text
+        ))
+    }
+
+    // < Accept >   < Reject >
+    fn buttons_line(&self) -> Line<'static> {
+        let on_buttons = self.selected == self.rows.len();
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Accept", on_buttons && self.accept_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Reject", on_buttons && !self.accept_selected));
+        Line::from(spans)
+    }
+}
+
+impl SignBatchFileScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            batch_file: TextField::with(Defaults::BATCH_INPUT_FILE),
+            gas_limit: TextField::with(Defaults::GAS_LIMIT),
+            max_fee_per_gas: TextField::with(Defaults::MAX_FEE_PER_GAS),
+            max_priority_fee_per_gas: TextField::with(Defaults::MAX_PRIORITY_FEE_PER_GAS),
+            out_dir: TextField::with(Defaults::BATCH_OUT_DIR),
+            review: None,
+            run: None,
+        }
+    }
+
+    fn is_text(&self) -> bool {
+        matches!(self.field_index, 0..=4)
+    }
+
+    fn tf_ref(&self, idx: usize) -> &TextField {
+        match idx {
+            0 => &self.batch_file,
+            1 => &self.gas_limit,
+            2 => &self.max_fee_per_gas,
+            3 => &self.max_priority_fee_per_gas,
+            4 => &self.out_dir,
+            _ => unreachable!("tf_ref called on non-text field"),
+        }
+    }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.batch_file,
+            1 => &mut self.gas_limit,
+            2 => &mut self.max_fee_per_gas,
+            3 => &mut self.max_priority_fee_per_gas,
+            4 => &mut self.out_dir,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    // < Sign Batch >   < Back >
+    fn buttons_line(submit_selected: bool, back_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Sign Batch", submit_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+
+    // < Confirm & Sign >   < Cancel >
+    fn review_buttons_line(confirm_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Confirm & Sign", confirm_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Cancel", !confirm_selected));
+        Line::from(spans)
+    }
+
+    /// Load the batch file and prepare a run; items are signed after the summary is confirmed.
+    fn start_batch(&self, fs: &dyn Fs, settings: &Settings, usage: &KeyUsage, gas: (GasLimit, Wei, Wei)) -> Result<BatchRun> {
+        let batch_path = self.batch_file.text.trim();
+        if batch_path.is_empty() {
+            anyhow::bail!("Batch File cannot be empty.");
+        }
+        let out_dir = self.out_dir.text.trim();
+        if out_dir.is_empty() {
+            anyhow::bail!("Output Directory cannot be empty.");
+        }
+        // Create it now, so an unusable (or sandbox-refused) directory shows up before signing.
+        fs.create_dir_all(Path::new(out_dir)).with_context(|| format!("creating output directory {out_dir}"))?;
+
+        let (raw, items) = load_batch_items(Path::new(batch_path))?;
+
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: usage.retired(),
+            allow_retired_key: false,
+            encoding: settings.signed_output_encoding,
+        };
+        let abi = load_abi()?;
+
+        Ok(BatchRun::new(abi, opts, items, raw, PathBuf::from(out_dir)))
+    }
+}
+
+impl Default for SignBatchFileScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for SignBatchFileScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Sign Batch File" }
+    fn signs(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
+        let header_text = "Sign Batch File";
+        let explanation_paras = [
+            "Enter the path to a JSON array of batch items. Every item is signed as an EIP-1559",
+            "transaction with the gas settings below. Results are written to a new run directory",
+            "(batch_<timestamp>_<input hash>) inside the output directory: one file per item, named",
+            "from the settings templates, plus a manifest.json for selective broadcasting.",
+        ];
+
+        // === TOP BOX ===
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Warnings are wrapped to the middle box width.
+        let middle_inner_width = top_inner_width;
+        let banner: Vec<Line> = self.review.iter()
+            .flat_map(|r| r.warnings.iter())
+            .flat_map(|w| warning_banner_lines(w, middle_inner_width))
+            .collect();
+
+        // Middle: 5 fields + spacer + buttons, plus top spacer; or the pre-flight summary,
+        // or the nonce fixup before it
+        let middle_rows: u16 = match &self.review {
+            Some(Review { fixup: Some(fixup), .. }) => 3 + fixup.rows.len() as u16 + 2,
+            Some(review) => 1 + banner.len() as u16 + review.lines.len() as u16 + 2,
+            None => 5 + 3,
+        };
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header_lines.max(1)),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // === MIDDLE BOX ===
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+
+        if let Some(fixup) = self.review.as_ref().and_then(|r| r.fixup.as_ref()) {
+            let mid_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(2),
+                    Constraint::Length(1),
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .split(regions.middle_inner);
+            let intro = "Nonces with gaps or repeats. Proposed: consecutive per sender from its lowest nonce, in item order. Edit a row, then Accept, or Reject to keep the batch's nonces.";
+            f.render_widget(Paragraph::new(intro).wrap(Wrap { trim: true }), mid_chunks[0]);
+            f.render_widget(Paragraph::new(format!("  {:>5}  {:<42}  {:>6}   Proposed", "Item", "Sender", "Nonce")), mid_chunks[1]);
+            // Keep the selected row in view
+            let visible = mid_chunks[2].height.max(1) as usize;
+            let first = fixup.selected.min(fixup.rows.len().saturating_sub(1)).saturating_sub(visible - 1);
+            let rows: Vec<Line> = fixup.rows.iter().enumerate().skip(first)
+                .map(|(i, row)| NonceFixup::row_line(row, i == fixup.selected))
+                .collect();
+            f.render_widget(Paragraph::new(rows), mid_chunks[2]);
+            f.render_widget(Paragraph::new(fixup.buttons_line()), mid_chunks[4]);
+
+            f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+            let footer_line = Line::from(vec![
+                span_key("↑/↓"),       span_text(" Row"), span_sep(),
+                span_key("0-9/Bksp"),  span_text(" Edit"), span_sep(),
+                span_key("←/→"),       span_text(" Choose"), span_sep(),
+                span_key("Enter"),     span_text(" Select"), span_sep(),
+                span_key("Esc"),       span_text(" Reject"), span_sep(),
+                span_key("Ctrl+Q"),    span_text(" Quit"),
+            ]);
+            f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+            return;
+        }
+
+        if let Some(review) = &self.review {
+            let mid_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1),
+                    Constraint::Length(banner.len() as u16),
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .split(regions.middle_inner);
+            f.render_widget(Paragraph::new("Pre-flight summary: check it matches the batch you meant to sign."), mid_chunks[0]);
+            f.render_widget(Paragraph::new(banner), mid_chunks[1]);
+            let lines: Vec<Line> = review.lines.iter().skip(review.scroll).map(|l| Line::from(l.clone())).collect();
+            f.render_widget(Paragraph::new(lines), mid_chunks[2]);
+            f.render_widget(Paragraph::new(Self::review_buttons_line(review.confirm_selected)), mid_chunks[4]);
+
+            f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+            let footer_line = Line::from(vec![
+                span_key("↑/↓"),     span_text(" Scroll"), span_sep(),
+                span_key("←/→/Tab"), span_text(" Choose"), span_sep(),
+                span_key("Enter"),   span_text(" Select"), span_sep(),
+                span_key("Esc"),     span_text(" Cancel"), span_sep(),
+                span_key("Ctrl+Q"),  span_text(" Quit"),
+            ]);
+            f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+            return;
+        }
+
+        let mut lines: Vec<Line> = Vec::new();
+        lines.push(Line::from("")); // spacer above first field
+        lines.push(field_line_text("Batch File", self.tf_ref(0), self.field_index == 0));
+
+        let gas_label = format!("Gas limit (maximum {} gas)", ctx.settings.gas_caps.gas_limit());
+        lines.push(field_line_text(&gas_label, self.tf_ref(1), self.field_index == 1));
+
+        let mfg_label = format!(
+            "Maximum Fee Per Gas (maximum {} wei)",
+            ctx.settings.gas_caps.max_fee_per_gas()
+        );
+        lines.push(field_line_text(&mfg_label, self.tf_ref(2), self.field_index == 2));
+
+        let mpfg_label = format!(
+            "Maximum Priority Fee Per Gas (maximum {} wei)",
+            ctx.settings.gas_caps.max_priority_fee_per_gas()
+        );
+        lines.push(field_line_text(&mpfg_label, self.tf_ref(3), self.field_index == 3));
+
+        lines.push(field_line_text("Output Directory", self.tf_ref(4), self.field_index == 4));
+
+        lines.push(Line::from("")); // spacer
+        match &self.run {
+            Some(run) => lines.push(Line::from(format!(
+                "Signing item {} of {}…  (Ctrl+Q stops after the current item)",
+                run.signed() + 1, run.total()
+            ))),
+            None => lines.push(Self::buttons_line(self.field_index == 5, self.field_index == 6)),
+        }
+
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // === BOTTOM BOX (legend) ===
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"),   span_text(" Select"),  span_sep(),
+            span_key("Esc"),     span_text(" Back"),    span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    fn busy(&self) -> Option<String> {
+        self.run.as_ref().map(|run| format!(
+            "A signing batch is in progress ({} of {} signed).", run.signed(), run.total()
+        ))
+    }
+
+    async fn tick(&mut self, ctx: &mut AppCtx) -> Result<Transition> {
+        let Some(run) = self.run.as_mut() else { return Ok(Transition::Stay) };
+        if let Err(e) = run.step().await {
+            let e = e.context("failed to sign batch");
+            let mut lines = run.fail(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref(), &e);
+            lines.push("".to_string());
+            lines.extend(run.stats_lines());
+            self.run = None;
+            return Ok(Transition::Push(Box::new(
+                ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+            )));
+        }
+        if !run.is_done() {
+            return Ok(Transition::Stay);
+        }
+        let count = run.total();
+        let stats = run.stats_lines();
+        let written = run.finish(&*ctx.fs, &ctx.settings, &mut ctx.key_usage, ctx.profile_key.as_ref());
+        self.run = None;
+        match written {
+            Ok((run_dir, recorded)) => {
+                let mut lines = vec![
+                    format!("Signed {count} transaction(s) into run directory:"),
+                    "".to_string(),
+                    run_dir.display().to_string(),
+                    "".to_string(),
+                    format!("See {BATCH_MANIFEST_FILE} in that directory for the file list."),
+                    "".to_string(),
+                ];
+                lines.extend(stats);
+                lines.extend(recorded);
+                Ok(Transition::Push(Box::new(
+                    ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                )))
+            }
+            Err(e) => Ok(Transition::Push(Box::new(
+                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+            ))),
+        }
+    }
+
+    fn finish_for_quit(&mut self, ctx: &AppCtx) -> Option<String> {
+        let run = self.run.take()?;
+        Some(run.flush_for_quit(&*ctx.fs, &ctx.settings))
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        // While signing, only the global hotkeys (Ctrl+Q, F12) apply.
+        if self.run.is_some() { return Ok(Transition::Stay); }
+
+        // Nonce fixup: accept the (edited) proposal or keep the batch's nonces, then the summary.
+        if let Some(review) = self.review.as_mut() {
+            if let Some(fixup) = review.fixup.as_mut() {
+                let on_buttons = fixup.selected == fixup.rows.len();
+                match k.code {
+                    KeyCode::Up => fixup.selected = fixup.selected.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Tab => fixup.selected = (fixup.selected + 1).min(fixup.rows.len()),
+                    KeyCode::Left | KeyCode::Right if on_buttons => fixup.accept_selected = !fixup.accept_selected,
+                    KeyCode::Char(c) if !on_buttons && c.is_ascii_digit() => fixup.rows[fixup.selected].proposed.insert_char(c),
+                    KeyCode::Backspace if !on_buttons => fixup.rows[fixup.selected].proposed.backspace(),
+                    KeyCode::Enter if on_buttons && fixup.accept_selected => {
+                        let nonces = match fixup.nonces() {
+                            Ok(nonces) => nonces,
+                            Err(e) => return Ok(Transition::Push(Box::new(
+                                ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                            ))),
+                        };
+                        review.run.set_nonces(&nonces);
+                        match review.run.summary(&ctx.settings.networks) {
+                            Ok(summary) => review.set_summary(&summary, &ctx.settings.display),
+                            Err(e) => {
+                                self.review = None;
+                                return Ok(Transition::Push(Box::new(
+                                    ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                                )));
+                            }
+                        }
+                        review.fixup = None;
+                    }
+                    // Enter on a row moves on to the next one
+                    KeyCode::Enter if !on_buttons => fixup.selected += 1,
+                    KeyCode::Enter | KeyCode::Esc => review.fixup = None,
+                    _ => {}
+                }
+                return Ok(Transition::Stay);
+            }
+        }
+
+        // Pre-flight summary: confirm to start signing, or go back to the form.
+        if let Some(review) = self.review.as_mut() {
+            match k.code {
+                KeyCode::Up => review.scroll = review.scroll.saturating_sub(1),
+                KeyCode::Down if review.scroll + 1 < review.lines.len() => review.scroll += 1,
+                KeyCode::Left | KeyCode::Right | KeyCode::Tab => review.confirm_selected = !review.confirm_selected,
+                // Confirming also accepts any key reuse the summary listed
+                KeyCode::Enter if review.confirm_selected => {
+                    self.run = self.review.take().map(|r| {
+                        let mut run = r.run;
+                        run.allow_key_reuse();
+                        run.allow_retired_keys();
+                        run
+                    });
+                }
+                KeyCode::Enter | KeyCode::Esc => self.review = None,
+                _ => {}
+            }
+            return Ok(Transition::Stay);
+        }
+
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 6; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 7;
+            }
+
+            // Enter on [Sign Batch]
+            KeyCode::Enter if self.field_index == 5 => {
+                let gas = match validate_gas_fields(&ctx.settings.gas_caps, &self.gas_limit.text, &self.max_fee_per_gas.text, &self.max_priority_fee_per_gas.text) {
+                    Ok(gas) => gas,
+                    Err(e) => return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new(format!("Error: {e}")).with_after_ok(AfterOk::Pop)
+                    ))),
+                };
+
+                match self.start_batch(&*ctx.fs, &ctx.settings, &ctx.key_usage, gas).map(|run| run.with_events(ctx.events.clone())).and_then(|run| Ok((run.summary(&ctx.settings.networks)?, run))) {
+                    Ok((summary, run)) => self.review = Some(Review::new(run, &summary, &ctx.settings.display)),
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::new(format!("Error: {e:#}"))
+                                .with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                }
+            }
+
+            // Enter on [Back]
+            KeyCode::Enter if self.field_index == 6 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Cursor movement in text fields
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+
+            // Editing
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
+
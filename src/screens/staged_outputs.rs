@@ -0,0 +1,304 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::ui::typed_confirm::{TypedConfirm, TypedConfirmChoice};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Files),
+        order: 50,
+        title: "Staged Outputs",
+        description: "Move files staged in the ephemeral run directory to disk, or shred them.",
+        open: |ctx| Transition::Push(Box::new(StagedOutputsScreen::new(ctx))),
+    }
+}
+
+/// Files waiting in the per-run ephemeral directory (see `staging.rs`), each kept on disk or
+/// shredded. Opened from the menu, or in place of the quit confirmation while files are staged.
+pub struct StagedOutputsScreen {
+    /// Destinations of the staged files, with whether each is ticked.
+    files: Vec<(PathBuf, bool)>,
+    /// 0 = list, 1..=3 = buttons
+    field_index: usize,
+    list_index: usize,
+    /// Shown at quit: the buttons quit instead of staying.
+    at_quit: bool,
+    /// Asks for SHRED before anything is shredded.
+    shred_confirm: Option<TypedConfirm>,
+}
+
+impl StagedOutputsScreen {
+    pub fn new(ctx: &AppCtx) -> Self {
+        Self { files: Vec::new(), field_index: 0, list_index: 0, at_quit: false, shred_confirm: None }.loaded(ctx)
+    }
+
+    /// Asked at quit, in place of the quit confirmation.
+    pub fn at_quit(ctx: &AppCtx) -> Self {
+        Self { at_quit: true, ..Self::new(ctx) }
+    }
+
+    fn loaded(mut self, ctx: &AppCtx) -> Self {
+        self.reload(ctx);
+        self
+    }
+
+    /// Re-read the staged files; ticks are kept for files still staged.
+    fn reload(&mut self, ctx: &AppCtx) {
+        let ticked: Vec<PathBuf> = self.files.iter().filter(|(_, t)| *t).map(|(p, _)| p.clone()).collect();
+        self.files = ctx.staging.pending().into_iter().map(|p| { let t = ticked.contains(&p); (p, t) }).collect();
+        self.list_index = self.list_index.min(self.files.len().saturating_sub(1));
+        if self.files.is_empty() && self.field_index == 0 {
+            self.field_index = 1;
+        }
+    }
+
+    fn labels(&self) -> [&'static str; 3] {
+        if self.at_quit {
+            ["Keep Selected & Quit", "Shred All & Quit", "Don't Quit"]
+        } else {
+            ["Keep Selected", "Shred Selected", "Back"]
+        }
+    }
+
+    fn selected(&self) -> Vec<PathBuf> {
+        self.files.iter().filter(|(_, t)| *t).map(|(p, _)| p.clone()).collect()
+    }
+
+    /// Keep (or shred) the ticked files; returns a line per file that failed.
+    fn apply(&self, ctx: &AppCtx, keep: bool) -> (usize, Vec<String>) {
+        let mut done = 0;
+        let mut failed = Vec::new();
+        for dest in self.selected() {
+            let res = if keep { ctx.staging.keep(&dest) } else { ctx.staging.discard(&dest) };
+            match res {
+                Ok(()) => done += 1,
+                Err(e) => failed.push(format!("{}: {e}", dest.display())),
+            }
+        }
+        (done, failed)
+    }
+
+    fn summary(done: usize, failed: Vec<String>, verb: &str) -> ConfirmOkScreen {
+        let mut lines = vec![format!("{done} file(s) {verb}.")];
+        if !failed.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("{} file(s) failed and are still staged:", failed.len()));
+            lines.extend(failed);
+        }
+        ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+    }
+
+    /// Ask for SHRED before shredding `count` file(s).
+    fn confirm_shred(&mut self, count: usize) {
+        let what = if self.at_quit { "every staged file" } else { "the ticked files" };
+        self.shred_confirm = Some(TypedConfirm::new(
+            "Shred Staged Files",
+            vec![
+                format!("This overwrites and removes {what} ({count})."),
+                "Shredded files cannot be recovered.".to_string(),
+            ],
+            "SHRED",
+            if self.at_quit { "Shred & Quit" } else { "Shred" },
+        ));
+    }
+
+    /// Shred the ticked files, or everything when quitting.
+    fn shred(&mut self, ctx: &AppCtx) -> Transition {
+        // The exit path shreds whatever is still staged
+        if self.at_quit { return Transition::Quit; }
+        let (done, failed) = self.apply(ctx, false);
+        self.reload(ctx);
+        Transition::Push(Box::new(Self::summary(done, failed, "shredded")))
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for StagedOutputsScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Staged Outputs" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, ctx: &AppCtx) {
+        let header_text = "Staged Outputs";
+        let mut explanation_paras = vec![match ctx.staging.location() {
+            Some((dir, true)) => format!("Run directory: {} (RAM-backed).", dir.display()),
+            Some((dir, false)) => format!("Run directory: {} (NOT RAM-backed: shredding is best effort on this filesystem).", dir.display()),
+            None => match ctx.staging.error() {
+                Some(e) => format!("No run directory: {e}."),
+                None => "No run directory: turn on \"Ephemeral run directory\" in Settings to stage outputs.".to_string(),
+            },
+        }];
+        explanation_paras.push(if self.at_quit {
+            "These outputs exist only in the run directory. Tick the ones to keep; they are copied to their destination, and everything else is shredded when the tool exits.".to_string()
+        } else {
+            "Tick files, then keep them (copied to their destination, never overwriting) or shred them. Files left here are shredded when the tool exits.".to_string()
+        });
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // spacer + files (or a note) + spacer + buttons
+        let middle_needed = 2 + 2 + 1 + (self.files.len().max(1) as u16) + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: files scroll to keep the highlighted one visible above the buttons
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let visible = mid_chunks[1].height.max(1) as usize;
+        let first = self.list_index.saturating_sub(visible - 1);
+        let rows: Vec<Line> = if self.files.is_empty() {
+            vec![Line::from("Nothing is staged.")]
+        } else {
+            self.files.iter().enumerate().skip(first).map(|(i, (dest, ticked))| {
+                let focused = self.field_index == 0 && self.list_index == i;
+                Line::from(vec![
+                    Span::styled(if focused { "▶ " } else { "  " }, Style::default().fg(Color::Cyan)),
+                    Span::raw(if *ticked { "[x] " } else { "[ ] " }),
+                    Span::raw(dest.display().to_string()),
+                ])
+            }).collect()
+        };
+        f.render_widget(Paragraph::new(rows), mid_chunks[1]);
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (i, label) in self.labels().into_iter().enumerate() {
+            if i > 0 { spans.push(Span::raw("   ")); }
+            spans.extend(button_spans(label, self.field_index == i + 1));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), mid_chunks[3]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Space"),   span_text(" Tick"), span_sep(),
+            span_key("A"),       span_text(" Tick All"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+
+        if let Some(prompt) = &self.shred_confirm {
+            prompt.draw(f, size);
+        }
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(prompt) = self.shred_confirm.as_mut() {
+            let Some(choice) = prompt.on_key(k) else { return Ok(Transition::Stay) };
+            self.shred_confirm = None;
+            return Ok(match choice {
+                TypedConfirmChoice::Confirmed => self.shred(ctx),
+                TypedConfirmChoice::Cancelled => Transition::Stay,
+            });
+        }
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                // Already the quit question
+                if self.at_quit { return Ok(Transition::Stay); }
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let has_files = !self.files.is_empty();
+        match k.code {
+            KeyCode::Down | KeyCode::Tab => {
+                if self.field_index == 0 && self.list_index + 1 < self.files.len() {
+                    self.list_index += 1;
+                } else if self.field_index == 3 {
+                    self.field_index = if has_files { 0 } else { 1 };
+                    self.list_index = 0;
+                } else {
+                    self.field_index += 1;
+                }
+            }
+            KeyCode::Up => {
+                if self.field_index == 0 && self.list_index > 0 {
+                    self.list_index -= 1;
+                } else if self.field_index == 1 && has_files {
+                    self.field_index = 0;
+                    self.list_index = self.files.len() - 1;
+                } else if self.field_index <= 1 {
+                    self.field_index = 3;
+                } else {
+                    self.field_index -= 1;
+                }
+            }
+            KeyCode::Left if self.field_index > 1 => self.field_index -= 1,
+            KeyCode::Right if (1..3).contains(&self.field_index) => self.field_index += 1,
+            KeyCode::Char(' ') | KeyCode::Enter if self.field_index == 0 => {
+                if let Some((_, ticked)) = self.files.get_mut(self.list_index) { *ticked = !*ticked; }
+            }
+            KeyCode::Char('a' | 'A') if self.field_index == 0 => {
+                let all = self.files.iter().all(|(_, t)| *t);
+                for (_, ticked) in &mut self.files { *ticked = !all; }
+            }
+
+            // Keep Selected (& Quit)
+            KeyCode::Enter if self.field_index == 1 => {
+                let (done, failed) = self.apply(ctx, true);
+                self.reload(ctx);
+                if self.at_quit && failed.is_empty() {
+                    return Ok(Transition::Quit);
+                }
+                return Ok(Transition::Push(Box::new(Self::summary(done, failed, "moved to disk"))));
+            }
+            // Shred Selected / Shred All & Quit, once SHRED is typed
+            KeyCode::Enter if self.field_index == 2 => {
+                let count = if self.at_quit { self.files.len() } else { self.selected().len() };
+                if count == 0 {
+                    return Ok(self.shred(ctx));
+                }
+                self.confirm_shred(count);
+            }
+            KeyCode::Enter if self.field_index == 3 => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
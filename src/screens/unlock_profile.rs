@@ -0,0 +1,203 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::app_state::AppEvent;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::is_reveal_key;
+use crate::ui::components::{TextField, RevealBriefly, field_line_password, show_password_line};
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::migrate::migrate_stores;
+use crate::profile::unlock_profile;
+use crate::settings::load_settings;
+use crate::key_usage::load_key_usage;
+use crate::defaults::Defaults;
+
+/// Startup prompt shown when a master-password profile exists.
+/// On success the profile key and decrypted settings go into `AppCtx` and the main menu replaces this screen.
+pub struct UnlockProfileScreen {
+    // indices: 0 password, 1 show pwd toggle, 2 unlock, 3 quit
+    field_index: usize,
+    password: TextField,
+    show_password: bool,
+    reveal: RevealBriefly,
+}
+
+impl UnlockProfileScreen {
+    pub fn new() -> Self {
+        Self { field_index: 0, password: TextField::with(""), show_password: false, reveal: RevealBriefly::default() }
+    }
+
+    fn buttons_line(unlock_selected: bool, quit_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Unlock", unlock_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Quit", quit_selected));
+        Line::from(spans)
+    }
+}
+
+impl Default for UnlockProfileScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for UnlockProfileScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Unlock Profile" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Inkan Management Utility — Unlock Profile";
+        let explanation_paras = [
+            &format!(
+                "A master password protects this profile ({}). Settings and other local records are stored encrypted until it is entered.",
+                Defaults::PROFILE_FILE
+            ),
+            "Unlocking takes a few seconds (Argon2id, 256 MiB).",
+        ];
+
+        // TOP sizing
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras.iter() { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + password + show + spacer + buttons
+        let middle_rows: u16 = 4 + 1;
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_password("Master Password", &self.password, self.field_index == 0, self.show_password || self.reveal.shows(0, self.field_index)),
+            show_password_line(self.show_password, self.field_index == 1),
+            Line::from(""),
+            Self::buttons_line(self.field_index == 2, self.field_index == 3),
+        ];
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Quit"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let KeyCode::Esc = k.code {
+            return Ok(Transition::Quit);
+        }
+
+        // Reveal only the focused secret, and only briefly
+        if is_reveal_key(&k) {
+            if self.field_index == 0 { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 3; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 4;
+            }
+
+            // Enter on the password field or Unlock
+            KeyCode::Enter if matches!(self.field_index, 0 | 2) => {
+                if self.password.text.is_empty() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: Master Password cannot be empty.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                let mut password_utf8 = std::mem::take(&mut self.password.text).into_bytes();
+                self.password.cursor = 0;
+                match unlock_profile(&mut password_utf8) {
+                    Ok(key) => {
+                        let migrated = migrate_stores(Some(&key));
+                        ctx.set_settings(load_settings(Some(&key)));
+                        let usage = load_key_usage(Some(&key));
+                        ctx.dispatch(self.crumb(), AppEvent::ProfileUnlocked { key, usage });
+                        return Ok(Transition::Replace(crate::app::session_screen(ctx, migrated)));
+                    }
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                }
+            }
+
+            // Enter on Quit
+            KeyCode::Enter if self.field_index == 3 => {
+                return Ok(Transition::Quit);
+            }
+
+            // Toggle Show Password
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 1 => {
+                self.show_password = !self.show_password;
+            }
+
+            // Text cursor/editing
+            KeyCode::Left  if self.field_index == 0 => self.password.move_left(),
+            KeyCode::Right if self.field_index == 0 => self.password.move_right(),
+            KeyCode::Home  if self.field_index == 0 => self.password.home(),
+            KeyCode::End   if self.field_index == 0 => self.password.end(),
+            KeyCode::Backspace if self.field_index == 0 => self.password.backspace(),
+            KeyCode::Delete    if self.field_index == 0 => self.password.delete(),
+            KeyCode::Char(c) if self.field_index == 0 && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.password.insert_char(c)
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
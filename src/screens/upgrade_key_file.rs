@@ -0,0 +1,242 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use std::path::PathBuf;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text, button_spans};
+use crate::ui::common_nav::{esc_to_back, is_reveal_key};
+use crate::ui::components::{TextField, field_line_text, RevealBriefly, field_line_password, show_password_line};
+use crate::screens::{ConfirmOkScreen, AfterOk};
+use crate::commands::key_upgrade::upgrade_key_file_in_place;
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Keys),
+        order: 30,
+        title: "Upgrade Key File (v1 → v2)",
+        description: "Re-encrypt a v1 key file in the current format.",
+        open: |_| Transition::Push(Box::new(UpgradeKeyFileScreen::new())),
+    }
+}
+
+pub struct UpgradeKeyFileScreen {
+    // indices: 0 file path, 1 password, 2 show pwd toggle, 3 submit, 4 back
+    field_index: usize,
+    file_path: TextField,
+    password: TextField,
+    show_password: bool,
+    reveal: RevealBriefly,
+}
+
+impl UpgradeKeyFileScreen {
+    pub fn new() -> Self {
+        Self {
+            field_index: 0,
+            file_path: TextField::with(""),
+            password: TextField::with(""),
+            show_password: false,
+            reveal: RevealBriefly::default(),
+        }
+    }
+
+    fn is_text(&self) -> bool { matches!(self.field_index, 0 | 1) }
+
+    fn tf_mut(&mut self, idx: usize) -> &mut TextField {
+        match idx {
+            0 => &mut self.file_path,
+            1 => &mut self.password,
+            _ => unreachable!("tf_mut called on non-text field"),
+        }
+    }
+
+    fn buttons_line(submit_selected: bool, back_selected: bool) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Upgrade Key File", submit_selected));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", back_selected));
+        Line::from(spans)
+    }
+}
+
+impl Default for UpgradeKeyFileScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for UpgradeKeyFileScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Upgrade Key File" }
+    fn shows_secrets(&self) -> bool { true }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Upgrade Key File (v1 → v2)";
+        let explanation_paras = [
+            "Re-encrypts a modern-format v1 key file as v2 in place: same password and Argon2 costs, plus a metadata block (creation time, key fingerprint) and an outer integrity MAC over the whole file.",
+            "The upgraded file is verified by decrypting it before it replaces the original. PGP files are not affected.",
+        ];
+
+        // TOP sizing
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // Middle rows: spacer + file + password + show + spacer + buttons
+        let middle_rows: u16 = 5 + 1;
+        let middle_needed = 2 + 2 + middle_rows;
+
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        let explanation_para = Paragraph::new(expl_lines).alignment(Alignment::Left).wrap(Wrap { trim: true });
+
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(explanation_para, top_chunks[2]);
+
+        // MIDDLE
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let lines: Vec<Line> = vec![
+            Line::from(""),
+            field_line_text("Key File Path", &self.file_path, self.field_index == 0),
+            field_line_password("Password", &self.password, self.field_index == 1, self.show_password || self.reveal.shows(1, self.field_index)),
+            show_password_line(self.show_password, self.field_index == 2),
+            Line::from(""),
+            Self::buttons_line(self.field_index == 3, self.field_index == 4),
+        ];
+
+        f.render_widget(Paragraph::new(lines), regions.middle_inner);
+
+        // FOOTER legend
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("←/→/Space"), span_text(" Toggle"), span_sep(),
+            span_key("Ctrl+R"),  span_text(" Reveal 5s"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        // Reveal only the focused secret, and only briefly
+        if is_reveal_key(&k) {
+            if self.field_index == 1 { self.reveal.start(self.field_index); }
+            return Ok(Transition::Stay);
+        }
+
+        match k.code {
+            // Navigation
+            KeyCode::Up => {
+                if self.field_index == 0 { self.field_index = 4; } else { self.field_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.field_index = (self.field_index + 1) % 5;
+            }
+
+            // Enter on Upgrade
+            KeyCode::Enter if self.field_index == 3 => {
+                let path = self.file_path.text.trim();
+                if path.is_empty() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: Key File Path cannot be empty.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+                if self.password.text.is_empty() {
+                    return Ok(Transition::Push(Box::new(
+                        ConfirmOkScreen::new("Error: Password cannot be empty.").with_after_ok(AfterOk::Pop)
+                    )));
+                }
+
+                let path = PathBuf::from(path);
+                let mut password_utf8 = self.password.text.clone().into_bytes();
+                match upgrade_key_file_in_place(&path, &mut password_utf8) {
+                    Ok(meta) => {
+                        let lines = vec![
+                            "Key file upgraded to v2:".to_string(),
+                            path.display().to_string(),
+                            "".to_string(),
+                            format!("Key fingerprint: {}", meta.key_fingerprint),
+                            format!("Created at: {}", ctx.settings.display.rfc3339(&meta.created_at)),
+                        ];
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::with_lines(lines).with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                    Err(e) => {
+                        return Ok(Transition::Push(Box::new(
+                            ConfirmOkScreen::with_lines(format!("Error: {e:#}").lines().map(String::from).collect())
+                                .with_after_ok(AfterOk::Pop)
+                        )));
+                    }
+                }
+            }
+
+            // Enter on Back
+            KeyCode::Enter if self.field_index == 4 => {
+                return Ok(Transition::Pop);
+            }
+
+            // Toggle Show Password
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right if self.field_index == 2 => {
+                self.show_password = !self.show_password;
+            }
+
+            // Text cursor/editing
+            KeyCode::Left  if self.is_text() => self.tf_mut(self.field_index).move_left(),
+            KeyCode::Right if self.is_text() => self.tf_mut(self.field_index).move_right(),
+            KeyCode::Home  if self.is_text() => self.tf_mut(self.field_index).home(),
+            KeyCode::End   if self.is_text() => self.tf_mut(self.field_index).end(),
+            KeyCode::Backspace if self.is_text() => self.tf_mut(self.field_index).backspace(),
+            KeyCode::Delete    if self.is_text() => self.tf_mut(self.field_index).delete(),
+            KeyCode::Char(c) if self.is_text() && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tf_mut(self.field_index).insert_char(c)
+            }
+
+            _ => {}
+        }
+
+        Ok(Transition::Stay)
+    }
+}
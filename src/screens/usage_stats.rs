@@ -0,0 +1,178 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_line, span_key, span_sep, span_text};
+use crate::usage_stats::{load_usage_stats, MonthStats, UsageStats};
+use crate::screens::registry::{Menu, MenuEntry, ToolCategory};
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Advanced(ToolCategory::Configuration),
+        order: 50,
+        title: "Usage Statistics",
+        description: "See how many transactions were signed per month, how large the batches were and which tools were used.",
+        open: |ctx| Transition::Push(Box::new(UsageStatsScreen::new(ctx))),
+    }
+}
+
+/// Tools listed per month, most opened first.
+const TOP_TOOLS: usize = 5;
+
+/// The local counters of `usage_stats.rs`, one block per month, newest first.
+pub struct UsageStatsScreen {
+    lines: Vec<String>,
+    /// First report line shown.
+    scroll: usize,
+}
+
+impl UsageStatsScreen {
+    pub fn new(ctx: &AppCtx) -> Self {
+        let mut lines = report_lines(&load_usage_stats(ctx.profile_key.as_ref()));
+        if !ctx.settings.usage_stats {
+            lines.insert(0, "Recording is off; turn on Settings → Monitoring → Record local usage statistics.".to_string());
+            lines.insert(1, String::new());
+        }
+        Self { lines, scroll: 0 }
+    }
+}
+
+fn report_lines(stats: &UsageStats) -> Vec<String> {
+    if stats.months.is_empty() {
+        return vec!["Nothing recorded yet.".to_string()];
+    }
+    let mut lines = Vec::new();
+    for (month, m) in stats.months.iter().rev() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(month.clone());
+        lines.extend(month_lines(m));
+    }
+    lines
+}
+
+fn month_lines(m: &MonthStats) -> Vec<String> {
+    let mut lines = Vec::new();
+    let signed: Vec<String> = m.signed.iter().map(|(func, n)| format!("{func} {n}")).collect();
+    lines.push(if signed.is_empty() {
+        "  Transactions written: 0".to_string()
+    } else {
+        format!("  Transactions written: {} ({})", m.signed_total(), signed.join(", "))
+    });
+    if m.batch_runs > 0 {
+        lines.push(format!(
+            "  Batch runs: {}, {} transactions, average {:.1}, largest {}",
+            m.batch_runs,
+            m.batch_items,
+            m.batch_items as f64 / m.batch_runs as f64,
+            m.largest_batch,
+        ));
+    }
+    let mut opened: Vec<(&String, &u64)> = m.opened.iter().collect();
+    opened.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    if !opened.is_empty() {
+        let top: Vec<String> = opened.iter().take(TOP_TOOLS).map(|(title, n)| format!("{title} {n}")).collect();
+        lines.push(format!("  Tools opened: {}", top.join(", ")));
+    }
+    lines
+}
+
+#[async_trait]
+impl ScreenWidget for UsageStatsScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Usage Statistics" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Usage Statistics";
+        let explanation_paras = [
+            "Counted on this machine only, per calendar month (UTC): transactions written by contract function, batch runs and their sizes, and the tools opened from the menus. No keys, addresses or file names are kept, and nothing is sent anywhere.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in &explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // report + spacer + button
+        let middle_needed = 2 + 2 + self.lines.len() as u16 + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: scrollable report above the button, month headings in cyan
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let lines: Vec<Line> = self.lines.iter().skip(self.scroll).map(|l| {
+            if l.len() == 7 && l.as_bytes()[4] == b'-' {
+                Line::styled(l.clone(), Style::default().fg(Color::Cyan))
+            } else {
+                Line::from(l.clone())
+            }
+        }).collect();
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), mid_chunks[0]);
+        f.render_widget(Paragraph::new(button_line("Back", true)), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),    span_text(" Scroll"), span_sep(),
+            span_key("Enter"),  span_text(" Back"), span_sep(),
+            span_key("Esc"),    span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"), span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, _ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if self.scroll + 1 < self.lines.len() => self.scroll += 1,
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{AppCtx, ScreenWidget, Transition};
+use crate::ceremony_record::{ceremony_record_html, default_record_path};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::common_nav::esc_to_back;
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{button_spans, span_key, span_sep, span_text};
+use crate::verify_signed::VerifyReport;
+
+/// Acceptance report for a verified signed file. Read-only: the only actions save the report
+/// or a printable ceremony record beside the file, never over an existing one.
+pub struct VerifyReportScreen {
+    report: VerifyReport,
+    lines: Vec<String>,
+    /// First report line shown.
+    scroll: usize,
+    // 0 save report, 1 ceremony record, 2 back
+    button_index: usize,
+}
+
+impl VerifyReportScreen {
+    pub fn new(report: VerifyReport) -> Self {
+        let lines = report.lines();
+        Self { report, lines, scroll: 0, button_index: 0 }
+    }
+
+    fn buttons_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        spans.extend(button_spans("Save Report", self.button_index == 0));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Ceremony Record", self.button_index == 1));
+        spans.push(Span::raw("   "));
+        spans.extend(button_spans("Back", self.button_index == 2));
+        Line::from(spans)
+    }
+
+    fn report_line(l: &str) -> Line<'static> {
+        let color = if l.contains("[FAIL]") {
+            Some(Color::Red)
+        } else if l.contains("[WARN]") {
+            Some(Color::Yellow)
+        } else if l.contains("[PASS]") {
+            Some(Color::Green)
+        } else {
+            None
+        };
+        match color {
+            Some(c) => Line::from(Span::styled(l.to_string(), Style::default().fg(c))),
+            None => Line::from(l.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl ScreenWidget for VerifyReportScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Verification Report" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Verification Report";
+        let file_para = format!("File: {}", self.report.path.display());
+        let verdict = format!("Verdict: {}", self.report.verdict());
+        let explanation_paras = [
+            "Checked against this tool's network registry and payload signing settings. Any FAIL rejects the file; WARN findings are for the reviewer to judge.",
+            "Save Report writes the report beside the file as <name>.verification.txt. Ceremony Record writes <name>.ceremony.html, a printable record with checkboxes and sign-off lines. The signed file is not modified.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = wrap(&file_para, top_inner_width).len() + wrap(&verdict, top_inner_width).len();
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + explanation_paras.len() as u16 + 1;
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // report + spacer + buttons
+        let middle_needed = 2 + 2 + self.lines.len() as u16 + 2;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let verdict_color = if self.report.accepted() { Color::Green } else { Color::Red };
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for seg in wrap(&file_para, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+        for seg in wrap(&verdict, top_inner_width) {
+            expl_lines.push(Line::from(Span::styled(
+                seg.to_string(),
+                Style::default().fg(verdict_color).add_modifier(Modifier::BOLD),
+            )));
+        }
+        for p in explanation_paras {
+            expl_lines.push(Line::from(""));
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: scrollable report above the buttons
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(regions.middle_inner);
+        let lines: Vec<Line> = self.lines.iter().skip(self.scroll).map(|l| Self::report_line(l)).collect();
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), mid_chunks[0]);
+        f.render_widget(Paragraph::new(self.buttons_line()), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let footer_line = Line::from(vec![
+            span_key("↑/↓"),     span_text(" Scroll"), span_sep(),
+            span_key("←/→/Tab"), span_text(" Choose"), span_sep(),
+            span_key("Enter"),   span_text(" Select"), span_sep(),
+            span_key("Esc"),     span_text(" Back"), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let Some(t) = esc_to_back(k) { return Ok(t); }
+
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        match k.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if self.scroll + 1 < self.lines.len() => self.scroll += 1,
+            KeyCode::Right | KeyCode::Tab => self.button_index = (self.button_index + 1) % 3,
+            KeyCode::Left => self.button_index = (self.button_index + 2) % 3,
+            KeyCode::Enter if self.button_index == 0 => {
+                let path = self.report.default_report_path();
+                let mut text = self.lines.join("\n");
+                text.push('\n');
+                let modal = match ctx.fs.create_new(&path, text.as_bytes()) {
+                    Ok(()) => ConfirmOkScreen::with_lines(vec![
+                        "Saved verification report:".to_string(),
+                        "".to_string(),
+                        path.display().to_string(),
+                    ]),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: failed to write {}: {e}", path.display())),
+                };
+                return Ok(Transition::Push(Box::new(modal.with_after_ok(AfterOk::Pop))));
+            }
+            KeyCode::Enter if self.button_index == 1 => {
+                let path = default_record_path(&self.report.path);
+                let written = ceremony_record_html(&*ctx.fs, &self.report)
+                    .and_then(|html| ctx.fs.create_new(&path, html.as_bytes()).map_err(Into::into));
+                let modal = match written {
+                    Ok(()) => ConfirmOkScreen::with_lines(vec![
+                        "Saved ceremony record (open it in a browser to print):".to_string(),
+                        "".to_string(),
+                        path.display().to_string(),
+                    ]),
+                    Err(e) => ConfirmOkScreen::new(format!("Error: failed to write {}: {e:#}", path.display())),
+                };
+                return Ok(Transition::Push(Box::new(modal.with_after_ok(AfterOk::Pop))));
+            }
+            KeyCode::Enter => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use textwrap::wrap;
+
+use crate::app::{switch_workspace, AppCtx, ScreenWidget, Transition};
+use crate::screens::registry::{Menu, MenuEntry};
+use crate::screens::{AfterOk, ConfirmOkScreen};
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::{three_box_layout, Margins};
+use crate::ui::style::{span_key, span_sep, span_text};
+use crate::workspace;
+
+inventory::submit! {
+    MenuEntry {
+        menu: Menu::Main,
+        order: 45,
+        title: "Switch Workspace",
+        description: "Open another organization's or environment's workspace, or create one.",
+        open: |_| Transition::Push(Box::new(WorkspacesScreen::new())),
+    }
+}
+
+/// The workspaces to open, and a name field to create a new one.
+pub struct WorkspacesScreen {
+    names: Vec<String>,
+    /// Index into `names`, then one more for Back (Quit at startup).
+    menu_index: usize,
+    new_name: TextField,
+    /// Shown before any workspace is open: there is nothing to go back to.
+    at_startup: bool,
+}
+
+impl WorkspacesScreen {
+    pub fn new() -> Self {
+        let names = workspace::list();
+        let current = workspace::current();
+        let menu_index = names.iter().position(|n| *n == current).unwrap_or(0);
+        Self { names, menu_index, new_name: TextField::default(), at_startup: false }
+    }
+
+    pub fn at_startup() -> Self {
+        Self { at_startup: true, ..Self::new() }
+    }
+
+    fn rows(&self) -> usize { self.names.len() + 1 }
+
+    fn open(&self, name: &str, ctx: &mut AppCtx) -> Transition {
+        match switch_workspace(ctx, self.crumb(), name) {
+            Ok(first) => Transition::Reset(first),
+            Err(e) => Transition::Push(Box::new(ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop))),
+        }
+    }
+
+    fn create_and_open(&mut self, ctx: &mut AppCtx) -> Transition {
+        let name = self.new_name.text.trim().to_string();
+        if let Err(e) = workspace::create(&name) {
+            return Transition::Push(Box::new(ConfirmOkScreen::new(format!("Error: {e:#}")).with_after_ok(AfterOk::Pop)));
+        }
+        self.open(&name, ctx)
+    }
+}
+
+impl Default for WorkspacesScreen {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl ScreenWidget for WorkspacesScreen {
+    fn title(&self) -> &str { "" }
+    fn crumb(&self) -> &str { "Workspaces" }
+
+    fn draw(&self, f: &mut Frame<'_>, size: Rect, _ctx: &AppCtx) {
+        let header_text = "Workspaces";
+        let explanation_paras = [
+            "Each workspace is a separate directory with its own settings, master password, key usage records, key files and output. Keep one per organization or environment so their files never mix.",
+            "Select a workspace to open it, or type a name (letters, digits, '-' and '_') and press Enter to create and open a new one. Opening another workspace closes every screen and forgets the unlocked master password.",
+        ];
+
+        let top_inner_width = size.width.saturating_sub(2*2 + 2 + 2*3) as usize;
+        let header_lines = wrap(header_text, top_inner_width).len() as u16;
+        let mut exp_lines = 0usize;
+        for p in explanation_paras { exp_lines += wrap(p, top_inner_width).len(); }
+        let explanation_lines = exp_lines as u16 + (explanation_paras.len().saturating_sub(1) as u16);
+        let top_needed = 2 + 2 + header_lines + 1 + explanation_lines;
+
+        // name field + blank + rows
+        let middle_needed = 2 + 2 + 2 + self.rows() as u16;
+        let footer_height = 3;
+
+        let regions = three_box_layout(
+            size, top_needed, middle_needed, footer_height,
+            Margins { page: 2, inner_top: 3, inner_middle: 3, inner_bottom: 3 }
+        );
+
+        // TOP
+        f.render_widget(Block::default().borders(Borders::ALL), regions.top);
+        let top_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_lines.max(1)), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.top_inner);
+        let header_para = Paragraph::new(header_text).alignment(Alignment::Center).wrap(Wrap { trim: true });
+        let mut expl_lines: Vec<Line> = Vec::new();
+        for (i, p) in explanation_paras.iter().enumerate() {
+            for seg in wrap(p, top_inner_width) { expl_lines.push(Line::from(seg.to_string())); }
+            if i + 1 < explanation_paras.len() { expl_lines.push(Line::from("")); }
+        }
+        f.render_widget(header_para, top_chunks[0]);
+        f.render_widget(Paragraph::new(expl_lines).wrap(Wrap { trim: true }), top_chunks[2]);
+
+        // MIDDLE: new name, then the workspaces
+        f.render_widget(Block::default().borders(Borders::ALL), regions.middle);
+        let mid_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(1)])
+            .split(regions.middle_inner);
+        f.render_widget(Paragraph::new(field_line_text("New Workspace", &self.new_name, true)), mid_chunks[0]);
+
+        let current = workspace::current();
+        let mut list_items: Vec<ListItem> = Vec::new();
+        for i in 0..self.rows() {
+            let selected = i == self.menu_index;
+            let prefix = if selected { "▶ " } else { "  " };
+            let label = match self.names.get(i) {
+                Some(n) if *n == current && !self.at_startup => format!("{n}  (open)"),
+                Some(n) if n == workspace::DEFAULT_WORKSPACE => format!("{n}  ({})", workspace::launch_dir().display()),
+                Some(n) => n.clone(),
+                None if self.at_startup => "Quit".to_string(),
+                None => "Back To Main Menu".to_string(),
+            };
+            let style = if selected { Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD) } else { Style::default() };
+            list_items.push(ListItem::new(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(Color::Cyan)),
+                Span::styled(label, style),
+            ])));
+        }
+        f.render_widget(List::new(list_items), mid_chunks[2]);
+
+        // FOOTER
+        f.render_widget(Block::default().borders(Borders::ALL), regions.bottom);
+        let esc_text = if self.at_startup { " Quit" } else { " Back" };
+        let footer_line = Line::from(vec![
+            span_key("↑/↓/Tab"), span_text(" Navigate"), span_sep(),
+            span_key("Enter"),   span_text(" Open / Create"), span_sep(),
+            span_key("Type"),    span_text(" New name"), span_sep(),
+            span_key("Esc"),     span_text(esc_text), span_sep(),
+            span_key("Ctrl+Q"),  span_text(" Quit"),
+        ]);
+        f.render_widget(Paragraph::new(footer_line).wrap(Wrap { trim: true }), regions.bottom_inner);
+    }
+
+    async fn on_key(&mut self, k: KeyEvent, ctx: &mut AppCtx) -> Result<Transition> {
+        if let KeyCode::Char('q') = k.code {
+            if k.modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(Transition::Push(Box::new(crate::screens::ConfirmQuitScreen::new())));
+            }
+        }
+
+        let leave = if self.at_startup { Transition::Quit } else { Transition::Pop };
+        match k.code {
+            KeyCode::Esc if !self.new_name.text.is_empty() => self.new_name = TextField::default(),
+            KeyCode::Esc => return Ok(leave),
+            KeyCode::Up => {
+                if self.menu_index == 0 { self.menu_index = self.rows() - 1; } else { self.menu_index -= 1; }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.menu_index = (self.menu_index + 1) % self.rows();
+            }
+            KeyCode::Enter if !self.new_name.text.trim().is_empty() => return Ok(self.create_and_open(ctx)),
+            KeyCode::Enter => {
+                return Ok(match self.names.get(self.menu_index) {
+                    Some(name) => self.open(&name.clone(), ctx),
+                    None => leave,
+                });
+            }
+            KeyCode::Left => self.new_name.move_left(),
+            KeyCode::Right => self.new_name.move_right(),
+            KeyCode::Backspace => self.new_name.backspace(),
+            KeyCode::Char(c) if !k.modifiers.contains(KeyModifiers::CONTROL) => self.new_name.insert_char(c),
+            _ => {}
+        }
+        Ok(Transition::Stay)
+    }
+}
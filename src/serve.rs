@@ -0,0 +1,419 @@
+//! Headless local API: `inkan-management-utility serve --unix-socket <path> [--allow-signing]`
+//! answers JSON-RPC 2.0 requests on a UNIX stream socket, so other offline tools on the same
+//! machine can check batches, recompute payload hashes and decode signed transactions without
+//! driving the TUI.
+//!
+//! One request per line, one response line per request (notifications, i.e. requests without
+//! `id`, get none). Clients are served one at a time. Methods:
+//!
+//! ```text
+//! validate_batch   { "batch": [<item>…] | "path": "<file>", "gas_limit"?, "max_fee_per_gas"?, "max_priority_fee_per_gas"? }
+//! payload_hash     { "kind": "delegation" | "revocation" | "invalidation", <payload fields> }
+//! decode_tx        { "signed_tx": "0x02…" }
+//! sign_batch       { <validate_batch params>, "out_dir": "<dir>" }   only with --allow-signing
+//! ```
+//!
+//! Settings, the sandbox and retired keys are read once at startup, as the TUI would read them;
+//! a master-password profile cannot be unlocked here, so the server refuses to start with one.
+//! The socket is created with mode 0600 and is never reachable from another machine.
+
+use anyhow::{bail, Context, Result};
+use ethers_core::utils::to_checksum;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+
+use crate::abi::load_abi;
+use crate::commands::batch::{parse_batch_items, BatchRun};
+use crate::decoder::decode_signed_tx;
+use crate::defaults::Defaults;
+use crate::event_stream::EventStream;
+use crate::key_usage::{load_key_usage, record_signed, KeyUsage};
+use crate::usage_stats::Written;
+use crate::process::BatchOpts;
+use crate::migrate::migrate_stores;
+use crate::profile::profile_enabled;
+use crate::settings::{load_settings, Settings};
+use crate::signing::SignerCache;
+use crate::util::{bytes_to_0x, hex_to_bytes};
+use crate::validation::validate_gas_fields;
+use crate::vfs::{FsRef, Sandbox, SandboxFs};
+use inkan_management_utility::payload::{
+    signed_digest, DelegationPayload, InvalidationPayload, Payload, RevocationPayload,
+};
+
+/// Longest accepted request line; an inline batch of a few thousand items fits.
+const MAX_REQUEST_LEN: u64 = 8 * 1024 * 1024;
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The method ran and failed (bad batch, undecodable transaction, signing error, …).
+const CALL_FAILED: i64 = -32000;
+/// `sign_batch` on a server started without `--allow-signing`.
+const SIGNING_DISABLED: i64 = -32001;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::new(CALL_FAILED, format!("{e:#}"))
+    }
+}
+
+/// Deserialize `params` into the method's parameter type.
+fn params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+struct Server {
+    settings: Settings,
+    key_usage: KeyUsage,
+    fs: FsRef,
+    events: EventStream,
+    allow_signing: bool,
+}
+
+/// Entry point for `serve --unix-socket <path> [--allow-signing]`.
+pub async fn run(socket: &Path, allow_signing: bool) -> Result<()> {
+    if profile_enabled() {
+        bail!("A master password is set; serve mode cannot unlock the profile. Use the TUI instead.");
+    }
+
+    for line in migrate_stores(None)? {
+        eprintln!("{line}");
+    }
+    let settings = load_settings(None);
+    let sandbox = Sandbox::default();
+    sandbox.set_roots(&settings.sandbox_roots);
+    let events = EventStream::default();
+    events.set_target(&settings.event_stream);
+    let mut server = Server {
+        key_usage: load_key_usage(None),
+        fs: FsRef(std::sync::Arc::new(SandboxFs::new(FsRef::default(), sandbox))),
+        settings,
+        events,
+        allow_signing,
+    };
+
+    let listener = bind(socket)?;
+    eprintln!(
+        "Serving JSON-RPC on {} (signing {})",
+        socket.display(),
+        if allow_signing { "ENABLED" } else { "disabled" }
+    );
+    for conn in listener.incoming() {
+        match conn {
+            Ok(stream) => {
+                if let Err(e) = server.serve_client(stream).await {
+                    eprintln!("Client dropped: {e:#}");
+                }
+            }
+            Err(e) => eprintln!("Accept failed: {e}"),
+        }
+    }
+    Ok(())
+}
+
+/// Bind `path`, replacing a socket left behind by an earlier server that is no longer running.
+fn bind(path: &Path) -> Result<UnixListener> {
+    if let Ok(meta) = std::fs::symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            bail!("{} exists and is not a socket.", path.display());
+        }
+        if UnixStream::connect(path).is_ok() {
+            bail!("Another server is already listening on {}.", path.display());
+        }
+        std::fs::remove_file(path).with_context(|| format!("removing stale socket {}", path.display()))?;
+    }
+    // Create the socket 0600 rather than narrowing it after bind, so no client can connect in
+    // between. The umask is process-wide; nothing else creates files while the server starts.
+    let old_mask = unsafe { libc::umask(0o177) };
+    let bound = UnixListener::bind(path);
+    unsafe { libc::umask(old_mask) };
+    let listener = bound.with_context(|| format!("binding {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("restricting {}", path.display()))?;
+    Ok(listener)
+}
+
+impl Server {
+    async fn serve_client(&mut self, stream: UnixStream) -> Result<()> {
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+        loop {
+            let mut line = String::new();
+            let n = reader.by_ref().take(MAX_REQUEST_LEN).read_line(&mut line)?;
+            if n == 0 {
+                return Ok(());
+            }
+            if !line.ends_with('\n') && n as u64 == MAX_REQUEST_LEN {
+                let reply = error_reply(Value::Null, RpcError::new(INVALID_REQUEST, "request line too long"));
+                writeln!(writer, "{reply}")?;
+                return Ok(());
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(reply) = self.handle_line(&line).await {
+                writeln!(writer, "{reply}")?;
+            }
+        }
+    }
+
+    /// One request line to its response, or `None` for a notification.
+    async fn handle_line(&mut self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(e) => return Some(error_reply(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()))),
+        };
+        let id = request.get("id").cloned();
+        let method = request.get("method").and_then(Value::as_str);
+        let Some(method) = method.filter(|_| request.get("jsonrpc") == Some(&json!("2.0"))) else {
+            return Some(error_reply(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "expected {\"jsonrpc\": \"2.0\", \"method\": …}"),
+            ));
+        };
+        let result = self.call(method, request.get("params").cloned().unwrap_or(json!({}))).await;
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_reply(id, e),
+        })
+    }
+
+    async fn call(&mut self, method: &str, p: Value) -> Result<Value, RpcError> {
+        match method {
+            "validate_batch" => self.validate_batch(params(p)?),
+            "payload_hash" => self.payload_hash(params(p)?),
+            "decode_tx" => decode_tx(params(p)?),
+            "sign_batch" if !self.allow_signing => Err(RpcError::new(
+                SIGNING_DISABLED,
+                "signing is disabled; start the server with --allow-signing",
+            )),
+            "sign_batch" => self.sign_batch(params(p)?).await,
+            other => Err(RpcError::new(METHOD_NOT_FOUND, format!("unknown method '{other}'"))),
+        }
+    }
+
+    /// A run over the batch in `p`, as Sign Batch File would prepare it, without signing.
+    fn prepare(&self, p: &BatchParams, out_dir: &Path) -> Result<BatchRun> {
+        let (raw, source) = match (&p.batch, &p.path) {
+            (Some(batch), None) => (serde_json::to_vec(batch)?, "the inline batch".to_string()),
+            (None, Some(path)) => (
+                self.fs.read(Path::new(path)).with_context(|| format!("reading {path}"))?,
+                path.clone(),
+            ),
+            _ => bail!("Give exactly one of \"batch\" or \"path\"."),
+        };
+        let items = parse_batch_items(&raw, &source)?;
+        let gas = validate_gas_fields(
+            &self.settings.gas_caps,
+            p.gas_limit.as_deref().unwrap_or(Defaults::GAS_LIMIT),
+            p.max_fee_per_gas.as_deref().unwrap_or(Defaults::MAX_FEE_PER_GAS),
+            p.max_priority_fee_per_gas.as_deref().unwrap_or(Defaults::MAX_PRIORITY_FEE_PER_GAS),
+        )?;
+        let (gas_limit, max_fee_per_gas, max_priority_fee_per_gas) = gas;
+        let opts = BatchOpts {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            payload_signing: self.settings.payload_signing.clone(),
+            signers: SignerCache::default(),
+            allow_key_reuse: false,
+            retired_keys: self.key_usage.retired(),
+            allow_retired_key: false,
+            encoding: self.settings.signed_output_encoding,
+        };
+        Ok(BatchRun::new(load_abi()?, opts, items, raw, out_dir.to_path_buf()).with_events(self.events.clone()))
+    }
+
+    fn validate_batch(&self, p: BatchParams) -> Result<Value, RpcError> {
+        let run = self.prepare(&p, Path::new("."))?;
+        let summary = run.summary(&self.settings.networks)?;
+        let mut warnings = summary.network_warnings.clone();
+        warnings.extend(summary.nonce_warning());
+        warnings.extend(summary.key_reuse.iter().flat_map(|r| r.0.iter().cloned()));
+        warnings.extend(summary.retired.iter().flat_map(|r| r.0.iter().cloned()));
+        let senders: Vec<Value> = summary.senders.iter().map(|s| json!({
+            "address": to_checksum(&s.address, None),
+            "items": s.items,
+            "firstNonce": s.first_nonce,
+            "lastNonce": s.last_nonce,
+        })).collect();
+        Ok(json!({
+            "items": summary.items,
+            "byFunction": summary.by_function,
+            "senders": senders,
+            "chainIds": summary.chain_ids,
+            "maxCostWei": summary.max_cost_wei.to_string(),
+            "warnings": warnings,
+        }))
+    }
+
+    /// The payload hash under the contract's configured scheme, and the digest actually signed.
+    fn payload_hash(&self, p: PayloadParams) -> Result<Value, RpcError> {
+        let contract = p.contract_address().to_string();
+        let payload = p.into_payload()?;
+        let signing = &self.settings.payload_signing;
+        let hash = signing.scheme_for(&contract)?.payload_hash(&payload);
+        let digest = signing.digest_for(&contract);
+        Ok(json!({
+            "payloadHash": bytes_to_0x(&hash),
+            "payloadDigest": digest,
+            "signedDigest": bytes_to_0x(&signed_digest(hash, digest)),
+        }))
+    }
+
+    async fn sign_batch(&mut self, p: SignParams) -> Result<Value, RpcError> {
+        let out_dir = Path::new(p.out_dir.trim());
+        if out_dir.as_os_str().is_empty() {
+            return Err(RpcError::new(INVALID_PARAMS, "out_dir cannot be empty"));
+        }
+        self.fs.create_dir_all(out_dir).with_context(|| format!("creating output directory {}", out_dir.display()))?;
+        let mut run = self.prepare(&p.batch, out_dir)?;
+        while !run.is_done() {
+            if let Err(e) = run.step().await {
+                let saved = match run.write_failed(&*self.fs, &self.settings, &e) {
+                    Ok(dir) => format!(" Partial run saved to {}.", dir.display()),
+                    Err(w) => format!(" Partial run NOT saved: {w:#}"),
+                };
+                return Err(RpcError::new(CALL_FAILED, format!("{e:#}.{saved}")));
+            }
+        }
+        let dir = run.write_complete(&*self.fs, &self.settings)?;
+        let warnings = record_signed(&mut self.key_usage, None, self.settings.usage_stats, Written::Run(&run));
+        Ok(json!({
+            "dir": dir.display().to_string(),
+            "signed": run.signed(),
+            "warnings": warnings,
+        }))
+    }
+}
+
+/// The decoded, sender-recovered transaction in the signed files' `decodedTx` shape.
+fn decode_tx(p: DecodeParams) -> Result<Value, RpcError> {
+    let tx = decode_signed_tx(&p.signed_tx, &load_abi()?)?;
+    Ok(serde_json::to_value(tx).map_err(anyhow::Error::from)?)
+}
+
+fn error_reply(id: Value, e: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": e.code, "message": e.message } })
+}
+
+#[derive(Deserialize)]
+struct BatchParams {
+    /// The batch items inline, as in a batch file.
+    batch: Option<Value>,
+    /// A batch file, read through the sandbox.
+    path: Option<String>,
+    gas_limit: Option<String>,
+    max_fee_per_gas: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SignParams {
+    #[serde(flatten)]
+    batch: BatchParams,
+    out_dir: String,
+}
+
+#[derive(Deserialize)]
+struct DecodeParams {
+    signed_tx: String,
+}
+
+/// Payload fields as hex strings (pubkeys `0x04…`, a 16-byte nonce, the contract address).
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+enum PayloadParams {
+    Delegation {
+        delegator_pubkey: String,
+        delegatee_pubkey: String,
+        #[serde(default)]
+        start_time: u64,
+        #[serde(default)]
+        end_time: u64,
+        revocation_requires_delegatee_signature: bool,
+        nonce: String,
+        contract_address: String,
+    },
+    Revocation {
+        revoker_pubkey: String,
+        revokee_pubkey: String,
+        #[serde(default)]
+        start_time: u64,
+        #[serde(default)]
+        end_time: u64,
+        nonce: String,
+        contract_address: String,
+    },
+    Invalidation {
+        invalidated_pubkey: String,
+        nonce: String,
+        contract_address: String,
+    },
+}
+
+impl PayloadParams {
+    fn contract_address(&self) -> &str {
+        match self {
+            PayloadParams::Delegation { contract_address, .. }
+            | PayloadParams::Revocation { contract_address, .. }
+            | PayloadParams::Invalidation { contract_address, .. } => contract_address,
+        }
+    }
+
+    fn into_payload(self) -> Result<Payload> {
+        let nonce16 = |s: &str| -> Result<[u8; 16]> {
+            hex_to_bytes(s)?.try_into().map_err(|_| anyhow::anyhow!("nonce must be 16 bytes of hex"))
+        };
+        Ok(match self {
+            PayloadParams::Delegation {
+                delegator_pubkey, delegatee_pubkey, start_time, end_time,
+                revocation_requires_delegatee_signature, nonce, contract_address,
+            } => Payload::Delegation(DelegationPayload {
+                delegator_pubkey: hex_to_bytes(&delegator_pubkey)?,
+                delegatee_pubkey: hex_to_bytes(&delegatee_pubkey)?,
+                start_time,
+                end_time,
+                revocation_requires_delegatee_signature,
+                nonce: nonce16(&nonce)?,
+                contract_address: hex_to_bytes(&contract_address)?,
+            }),
+            PayloadParams::Revocation {
+                revoker_pubkey, revokee_pubkey, start_time, end_time, nonce, contract_address,
+            } => Payload::Revocation(RevocationPayload {
+                revoker_pubkey: hex_to_bytes(&revoker_pubkey)?,
+                revokee_pubkey: hex_to_bytes(&revokee_pubkey)?,
+                start_time,
+                end_time,
+                nonce: nonce16(&nonce)?,
+                contract_address: hex_to_bytes(&contract_address)?,
+            }),
+            PayloadParams::Invalidation { invalidated_pubkey, nonce, contract_address } => {
+                Payload::Invalidation(InvalidationPayload {
+                    invalidated_pubkey: hex_to_bytes(&invalidated_pubkey)?,
+                    nonce: nonce16(&nonce)?,
+                    contract_address: hex_to_bytes(&contract_address)?,
+                })
+            }
+        })
+    }
+}
@@ -0,0 +1,243 @@
+//! User-editable settings, persisted as pretty JSON at `Defaults::SETTINGS_FILE`
+//! (encrypted as `<file>.enc` while a master-password profile is enabled, see `profile.rs`).
+//! A missing or unreadable file simply means "use the built-in defaults".
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use zeroize::Zeroize;
+
+use crate::defaults::Defaults;
+use crate::display::DisplaySettings;
+use crate::migrate::to_versioned_json;
+use crate::profile::{read_protected, write_protected, ProfileKey};
+use crate::filename_template::FilenameTemplates;
+use crate::output_encoder::OutputEncoding;
+use crate::scheme::{scheme_named, PayloadScheme, SECP256K1_KECCAK};
+pub use inkan_management_utility::payload::PayloadDigest;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Settings {
+    /// Output filename templates, one per event type.
+    pub filename_templates: FilenameTemplates,
+    /// Shape of signed transaction files (metadata envelope or legacy bare array).
+    pub signed_output_format: SignedOutputFormat,
+    /// Encoder for signed transaction files (JSON, NDJSON, CSV, ...; see `output_encoder.rs`).
+    pub signed_output_encoding: OutputEncoding,
+    /// Free-form operator label recorded in the metadata block (empty = omitted).
+    pub operator_label: String,
+    /// Also ask the terminal to purge its scrollback (`CSI 3J`) on F12 blanking and at exit.
+    /// Best effort: terminals may ignore the request and multiplexers keep their own history.
+    pub purge_scrollback: bool,
+    /// At exit, also disable core dumps and return the freed heap to the OS (see `memory_hygiene.rs`).
+    pub exit_memory_hygiene: bool,
+    /// State snapshot JSON or contract bytecode file to simulate signed txs against
+    /// before writing them (empty = off; needs a `--features simulation` build, see `simulate.rs`).
+    pub simulation_state_file: String,
+    /// YAML ceremony checklist whose steps must all be ticked before signing screens open
+    /// (empty = off, see `ceremony_checklist.rs`).
+    pub ceremony_checklist: String,
+    /// Which digest payload signatures cover, per contract deployment.
+    pub payload_signing: PayloadSigning,
+    /// Default gas limit per event type, pre-filled on its Create screen.
+    pub gas_limits: GasLimits,
+    /// Upper bounds for entered gas limits and fees (see `validation.rs`).
+    pub gas_caps: GasCaps,
+    /// Registered (chain ID, contract address) deployments; anything else is flagged before signing.
+    pub networks: Networks,
+    /// Address that settings and networks bundles must be signed by to import (empty = any
+    /// signer, checked by eye; see `settings_bundle.rs`).
+    pub bundle_signer: String,
+    /// Directories screens may read and write under; relative entries resolve against the
+    /// working directory (empty = the working directory only, see `vfs::Sandbox`).
+    pub sandbox_roots: Vec<String>,
+    /// Stage screen writes in a per-run directory on tmpfs, kept only on confirmation and
+    /// shredded at exit otherwise (see `staging.rs`).
+    pub ephemeral_run_dir: bool,
+    /// Refuse Load From File info files that hold private keys; otherwise the keys are listed
+    /// by fingerprint for confirmation before they reach the form.
+    pub refuse_secret_prefill: bool,
+    /// File or UNIX socket receiving JSON-lines progress events (empty = off, see `event_stream.rs`).
+    pub event_stream: String,
+    /// Count signings, batch sizes and tools opened per month in a local store (see `usage_stats.rs`).
+    pub usage_stats: bool,
+    /// Number and date formatting on screen (see `display.rs`).
+    pub display: DisplaySettings,
+}
+
+/// Per-event-type default gas limits (empty = the global cap, `Defaults::GAS_LIMIT`).
+/// A single delegation needs far less than a combined re-delegation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GasLimits {
+    pub delegation: String,
+    pub revocation: String,
+    pub redelegation: String,
+    pub permanent_invalidation: String,
+}
+
+impl GasLimits {
+    /// The configured default for a given ABI function name, if one is set.
+    pub fn configured(&self, func_name: &str) -> Option<&str> {
+        let v = match func_name {
+            "createDelegationEvent" => &self.delegation,
+            "createRevocationEvent" => &self.revocation,
+            "createRevocationEventFollowedByDelegationEvent" => &self.redelegation,
+            "createPermanentInvalidationEvent" => &self.permanent_invalidation,
+            _ => return None,
+        };
+        Some(v.trim()).filter(|v| !v.is_empty())
+    }
+
+    /// What a Create screen for `func_name` starts with.
+    pub fn default_for(&self, func_name: &str) -> &str {
+        self.configured(func_name).unwrap_or(Defaults::GAS_LIMIT)
+    }
+}
+
+/// Upper bounds every entered gas limit and fee must stay within (empty = the built-in caps in
+/// `Defaults`). Part of the settings, so each master-password profile carries its own policy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GasCaps {
+    pub gas_limit: String,
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+}
+
+impl GasCaps {
+    pub fn gas_limit(&self) -> &str {
+        Some(self.gas_limit.trim()).filter(|v| !v.is_empty()).unwrap_or(Defaults::GAS_LIMIT)
+    }
+
+    pub fn max_fee_per_gas(&self) -> &str {
+        Some(self.max_fee_per_gas.trim()).filter(|v| !v.is_empty()).unwrap_or(Defaults::MAX_FEE_PER_GAS)
+    }
+
+    pub fn max_priority_fee_per_gas(&self) -> &str {
+        Some(self.max_priority_fee_per_gas.trim()).filter(|v| !v.is_empty()).unwrap_or(Defaults::MAX_PRIORITY_FEE_PER_GAS)
+    }
+}
+
+/// How signed transaction files are laid out on disk.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedOutputFormat {
+    /// `{ "formatVersion": 2, "metadata": {...}, "entries": [...] }`
+    #[default]
+    WithMetadata,
+    /// Bare JSON array of entries (format version 1).
+    LegacyArray,
+}
+
+
+/// Payload digest mode: a default plus overrides keyed by contract address,
+/// since different Inkan contract versions verify differently.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PayloadSigning {
+    pub default: PayloadDigest,
+    /// `"0x…contract": "raw_keccak"`; addresses compare case-insensitively.
+    pub by_contract: BTreeMap<String, PayloadDigest>,
+    /// `"0x…contract": "secp256k1_keccak"`; contracts not listed use secp256k1 (see `scheme.rs`).
+    /// Kept as names so a scheme missing from this build fails at signing, not at load.
+    pub scheme_by_contract: BTreeMap<String, String>,
+}
+
+impl PayloadSigning {
+    pub fn digest_for(&self, contract_address: &str) -> PayloadDigest {
+        let wanted = contract_address.trim();
+        self.by_contract
+            .iter()
+            .find(|(addr, _)| addr.trim().eq_ignore_ascii_case(wanted))
+            .map(|(_, d)| *d)
+            .unwrap_or(self.default)
+    }
+
+    /// Payload signature scheme for a contract; fails when this build lacks it.
+    pub fn scheme_for(&self, contract_address: &str) -> Result<&'static dyn PayloadScheme> {
+        let wanted = contract_address.trim();
+        let name = self
+            .scheme_by_contract
+            .iter()
+            .find(|(addr, _)| addr.trim().eq_ignore_ascii_case(wanted))
+            .map(|(_, s)| s.as_str())
+            .unwrap_or(SECP256K1_KECCAK);
+        scheme_named(name)
+    }
+}
+
+/// One registered deployment of the Inkan contract.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub name: String,
+    pub chain_id: u64,
+    pub contract_address: String,
+}
+
+/// The network registry. Defaults to the built-in chain and contract (`Defaults::CHAIN_ID`,
+/// `Defaults::CONTRACT_ADDRESS`), so an untouched install signs without a warning.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Networks(pub Vec<NetworkProfile>);
+
+impl Default for Networks {
+    fn default() -> Self {
+        Self(vec![NetworkProfile {
+            name: "Local development node".into(),
+            chain_id: Defaults::CHAIN_ID,
+            contract_address: Defaults::CONTRACT_ADDRESS.into(),
+        }])
+    }
+}
+
+impl Networks {
+    /// The profile for this pair; addresses compare case-insensitively.
+    pub fn find(&self, chain_id: u64, contract_address: &str) -> Option<&NetworkProfile> {
+        let wanted = contract_address.trim();
+        self.0
+            .iter()
+            .find(|n| n.chain_id == chain_id && n.contract_address.trim().eq_ignore_ascii_case(wanted))
+    }
+
+    /// Warning for a pair that matches no profile, or `None` when it is registered.
+    /// Signing for the wrong chain cannot be undone once the transaction is broadcast.
+    pub fn unknown_warning(&self, chain_id: u64, contract_address: &str) -> Option<String> {
+        if self.find(chain_id, contract_address).is_some() {
+            return None;
+        }
+        let mut msg = format!(
+            "UNKNOWN NETWORK: chain ID {chain_id} with contract {} is not a registered network.",
+            contract_address.trim()
+        );
+        let same_chain: Vec<&str> =
+            self.0.iter().filter(|n| n.chain_id == chain_id).map(|n| n.contract_address.trim()).collect();
+        if !same_chain.is_empty() {
+            msg.push_str(&format!(" Chain {chain_id} is registered with contract {}.", same_chain.join(", ")));
+        }
+        msg.push_str(" A transaction signed for the wrong chain cannot be recalled after broadcast.");
+        Some(msg)
+    }
+}
+
+/// Load settings from the default location, falling back to defaults on any error.
+/// Pass the profile key when a master password is enabled.
+pub fn load_settings(key: Option<&ProfileKey>) -> Settings {
+    let Ok(Some(mut bytes)) = read_protected(key, Path::new(Defaults::SETTINGS_FILE)) else {
+        return Settings::default();
+    };
+    let settings = serde_json::from_slice(&bytes).unwrap_or_default();
+    bytes.zeroize();
+    settings
+}
+
+/// Persist settings to the default location (encrypted when a profile key is given).
+pub fn save_settings(settings: &Settings, key: Option<&ProfileKey>) -> Result<()> {
+    let mut bytes = to_versioned_json(Defaults::SETTINGS_FILE, settings)?;
+    let res = write_protected(key, Path::new(Defaults::SETTINGS_FILE), &bytes);
+    bytes.zeroize();
+    res
+}
@@ -0,0 +1,185 @@
+//! Signed settings bundle, for carrying one machine's configuration to another air-gapped machine.
+//!
+//! The bundle is `{ "formatVersion", "settings", "signer", "signature" }`. The signature is an
+//! EIP-191 personal-message signature over `keccak256(<settings as canonical JSON>)`, made with a
+//! key the operator chooses, so the receiving machine can check that the bundle is unmodified and
+//! came from the expected signer. `Settings` holds no secrets; the master password and profile
+//! key never enter a bundle.
+//!
+//! A networks bundle carries only the network profiles, `{ "formatVersion", "networks", "signer",
+//! "signature" }`, signed the same way over the `networks` array. An array can never be read as a
+//! settings object, so one kind of signature cannot be passed off as the other.
+
+use anyhow::{bail, Context, Result};
+use ethers_core::types::Signature;
+use ethers_core::utils::{keccak256, to_checksum};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::canonical_json::canonical_string;
+use crate::settings::{Networks, Settings};
+use crate::signing::{open_signer, sign_message_eip191};
+use crate::util::{bytes_to_0x, hex_to_bytes, parse_addr};
+
+/// Version 1 signed the settings in this build's field order, which changed whenever a setting
+/// was added; version 2 signs the `settings` object exactly as written, canonicalized.
+pub const SETTINGS_BUNDLE_VERSION: u32 = 2;
+
+/// Layout version of a networks bundle.
+pub const NETWORKS_BUNDLE_VERSION: u32 = 1;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsBundle {
+    formatVersion: u32,
+    /// Kept as written: the signature covers these values, not this build's reading of them.
+    settings: Value,
+    /// Checksummed address of the signing key.
+    signer: String,
+    /// `0x || r || s || v` (65 bytes).
+    signature: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+struct NetworksBundle {
+    formatVersion: u32,
+    /// Kept as written, like `SettingsBundle::settings`.
+    networks: Value,
+    signer: String,
+    signature: String,
+}
+
+/// What an imported bundle carried.
+pub enum BundleContents {
+    Settings(Box<Settings>),
+    Networks(Networks),
+}
+
+/// The signed digest input of a version 2 bundle.
+fn settings_hash(settings: &Value) -> [u8; 32] {
+    keccak256(canonical_string(settings))
+}
+
+/// The signed digest input of a version 1 bundle: the settings as this build reads them,
+/// serialized compactly in field order.
+fn legacy_settings_hash(settings: &Settings) -> Result<[u8; 32]> {
+    Ok(keccak256(serde_json::to_vec(settings)?))
+}
+
+/// Sign `settings` with the key `privkey_input` refers to (hex or nsec) and write the bundle to `path`.
+/// Returns the signer address.
+pub async fn export_settings_bundle(settings: &Settings, privkey_input: &str, path: &Path) -> Result<String> {
+    let key = open_signer(privkey_input)?;
+    let signer = to_checksum(&key.address(), None);
+
+    let settings = serde_json::to_value(settings)?;
+    let sig = sign_message_eip191(&*key, settings_hash(&settings)).await?;
+    let bundle = SettingsBundle {
+        formatVersion: SETTINGS_BUNDLE_VERSION,
+        settings,
+        signer: signer.clone(),
+        signature: bytes_to_0x(&sig.to_vec()),
+    };
+    write_bundle(path, &serde_json::to_string_pretty(&bundle)?)?;
+    Ok(signer)
+}
+
+/// Sign only the network profiles and write them to `path` as a networks bundle.
+/// Returns the signer address.
+pub async fn export_networks_bundle(networks: &Networks, privkey_input: &str, path: &Path) -> Result<String> {
+    let key = open_signer(privkey_input)?;
+    let signer = to_checksum(&key.address(), None);
+
+    let networks = serde_json::to_value(networks)?;
+    let sig = sign_message_eip191(&*key, settings_hash(&networks)).await?;
+    let bundle = NetworksBundle {
+        formatVersion: NETWORKS_BUNDLE_VERSION,
+        networks,
+        signer: signer.clone(),
+        signature: bytes_to_0x(&sig.to_vec()),
+    };
+    write_bundle(path, &serde_json::to_string_pretty(&bundle)?)?;
+    Ok(signer)
+}
+
+fn write_bundle(path: &Path, json: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs::write(path, json).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Read a bundle of either kind, told apart by its `settings` or `networks` member, and verify
+/// its signature. With `expected_signer` set, the recovered address must also equal it. Returns
+/// the contents and the signer address.
+pub fn import_bundle(path: &Path, expected_signer: Option<&str>) -> Result<(BundleContents, String)> {
+    let raw = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let value: Value = serde_json::from_slice(&raw)
+        .with_context(|| format!("parsing {} as a bundle", path.display()))?;
+    if value.get("networks").is_some() && value.get("settings").is_none() {
+        let (networks, signer) = import_networks(path, value, expected_signer)?;
+        Ok((BundleContents::Networks(networks), signer))
+    } else {
+        let (settings, signer) = import_settings(path, value, expected_signer)?;
+        Ok((BundleContents::Settings(Box::new(settings)), signer))
+    }
+}
+
+fn import_settings(path: &Path, value: Value, expected_signer: Option<&str>) -> Result<(Settings, String)> {
+    let bundle: SettingsBundle = serde_json::from_value(value)
+        .with_context(|| format!("parsing {} as a settings bundle", path.display()))?;
+    let settings: Settings = serde_json::from_value(bundle.settings.clone())
+        .with_context(|| format!("reading the settings in {}", path.display()))?;
+    let digest = match bundle.formatVersion {
+        1 => legacy_settings_hash(&settings)?,
+        SETTINGS_BUNDLE_VERSION => settings_hash(&bundle.settings),
+        v => bail!("Unsupported settings bundle version {v} in {}.", path.display()),
+    };
+    let signer = verify_signer(digest, &bundle.signature, &bundle.signer, expected_signer)?;
+    Ok((settings, signer))
+}
+
+fn import_networks(path: &Path, value: Value, expected_signer: Option<&str>) -> Result<(Networks, String)> {
+    let bundle: NetworksBundle = serde_json::from_value(value)
+        .with_context(|| format!("parsing {} as a networks bundle", path.display()))?;
+    if bundle.formatVersion != NETWORKS_BUNDLE_VERSION {
+        bail!("Unsupported networks bundle version {} in {}.", bundle.formatVersion, path.display());
+    }
+    let networks: Networks = serde_json::from_value(bundle.networks.clone())
+        .with_context(|| format!("reading the network profiles in {}", path.display()))?;
+    let signer = verify_signer(settings_hash(&bundle.networks), &bundle.signature, &bundle.signer, expected_signer)?;
+    Ok((networks, signer))
+}
+
+/// Recover the signer of `digest`, check it against the bundle's stated `signer` and, when
+/// given, `expected_signer`. Returns the checksummed signer address.
+fn verify_signer(digest: [u8; 32], signature: &str, signer: &str, expected_signer: Option<&str>) -> Result<String> {
+    let sig_bytes = hex_to_bytes(signature).context("bundle signature is not hex")?;
+    let sig = Signature::try_from(sig_bytes.as_slice()).context("bundle signature must be 65 bytes")?;
+    let recovered = sig
+        .recover(digest.to_vec())
+        .context("recovering bundle signer")?;
+
+    let stated = parse_addr(signer).context("bundle signer is not an address")?;
+    if recovered != stated {
+        bail!(
+            "Bundle signature does not match: signed by {}, bundle claims {}. The contents were modified or the signer field was edited.",
+            to_checksum(&recovered, None),
+            signer
+        );
+    }
+    if let Some(want) = expected_signer.map(str::trim).filter(|s| !s.is_empty()) {
+        let want = parse_addr(want).context("expected signer is not an address")?;
+        if recovered != want {
+            bail!(
+                "Bundle was signed by {}, not the expected {}.",
+                to_checksum(&recovered, None),
+                to_checksum(&want, None)
+            );
+        }
+    }
+    Ok(to_checksum(&recovered, None))
+}
@@ -0,0 +1,170 @@
+//! Signed EIP-1559 transactions as this tool writes them (`signedTx`): decoding and sender
+//! recovery, without any signing key or file access.
+
+use anyhow::{anyhow, Result};
+use ethers_core::types::{Address, H256, U256};
+use ethers_core::utils::{keccak256, rlp};
+
+use crate::hexstr::hex_to_bytes;
+
+/// secp256k1 group order n (big-endian hex).
+const SECP256K1_N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+pub fn secp256k1_n() -> U256 {
+    U256::from_str_radix(SECP256K1_N, 16).expect("valid constant")
+}
+
+/// EIP-2: `s` must be at most n/2. High-s signatures are rejected by nodes for txs
+/// and by many contracts for payloads.
+pub fn is_low_s(s: U256) -> bool {
+    s <= secp256k1_n() / 2
+}
+
+/// Shown wherever a decoded transaction turns out to carry a high-s signature.
+pub const HIGH_S_TX_WARNING: &str = "Transaction signature has a high s value (EIP-2); nodes will reject it";
+
+/// Whether a raw signed EIP-1559 tx has a high-s signature. Such a tx still decodes and its
+/// sender is recovered (`decode_signed_tx_and_recover`); callers report it as a warning.
+pub fn signed_tx_has_high_s(raw_hex: &str) -> bool {
+    let Ok(raw) = hex_to_bytes(raw_hex) else { return false };
+    let Some(rlp_body) = raw.get(1..) else { return false };
+    match rlp::Rlp::new(rlp_body).val_at::<Vec<u8>>(11) {
+        Ok(s_bytes) => s_bytes.len() <= 32 && !is_low_s(U256::from_big_endian(&s_bytes)),
+        Err(_) => false,
+    }
+}
+
+/// Decode a raw signed EIP-1559 tx and recover sender. A high-s signature still decodes (the
+/// sender recovers the same); check it with `signed_tx_has_high_s`.
+#[allow(clippy::type_complexity)]
+pub fn decode_signed_tx_and_recover(
+    raw_hex: &str,
+) -> Result<(
+    u64,      /*chainId*/
+    u64,      /*nonce*/
+    U256,     /*maxPrio*/
+    U256,     /*maxFee*/
+    U256,     /*gas*/
+    Address,  /*to*/
+    U256,     /*value*/
+    Vec<u8>,  /*data*/
+    Address,  /*from*/
+)> {
+    // Expect 0x02-prefixed typed tx
+    let raw = hex_to_bytes(raw_hex)?;
+    if raw.first() != Some(&0x02) {
+        return Err(anyhow!("Not a type-2 (EIP-1559) tx"));
+    }
+    let rlp_body = &raw[1..];
+    let r = rlp::Rlp::new(rlp_body);
+
+    // Fields per spec: [chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, yParity, r, s]
+    let chain_id: U256 = r.at(0)?.as_val()?;
+    let nonce: U256 = r.at(1)?.as_val()?;
+    let max_prio: U256 = r.at(2)?.as_val()?;
+    let max_fee: U256 = r.at(3)?.as_val()?;
+    let gas: U256 = r.at(4)?.as_val()?;
+    let to_bytes: Vec<u8> = r.at(5)?.as_val()?;
+    let to = Address::from_slice(&to_bytes);
+    let value: U256 = r.at(6)?.as_val()?;
+    let data: Vec<u8> = r.at(7)?.as_val()?;
+    // accessList at 8 ignored for now
+    let y_parity: u8 = r.at(9)?.as_val()?;
+    let r_bytes: Vec<u8> = r.at(10)?.as_val()?;
+    let s_bytes: Vec<u8> = r.at(11)?.as_val()?;
+
+    // sighash = keccak256( 0x02 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gas, to, value, data, accessList]) )
+    let mut s = ethers_core::utils::rlp::RlpStream::new_list(9);
+    s.append(&chain_id);
+    s.append(&nonce);
+    s.append(&max_prio);
+    s.append(&max_fee);
+    s.append(&gas);
+    s.append(&to);
+    s.append(&value);
+    s.append(&data);
+    s.append(&ethers_core::types::transaction::eip2930::AccessList::default());
+
+    let mut preimage = vec![0x02u8];
+    preimage.extend_from_slice(&s.out());
+
+    let sighash = H256::from(keccak256(preimage));
+
+    let sig = ethers_core::types::Signature {
+        r: U256::from_big_endian(&r_bytes),
+        s: U256::from_big_endian(&s_bytes),
+        v: y_parity as u64, // 0/1 for type-2
+    };
+    let from_addr = sig.recover(sighash)?;
+
+    Ok((
+        chain_id.as_u64(),
+        nonce.as_u64(),
+        max_prio,
+        max_fee,
+        gas,
+        to,
+        value,
+        data,
+        from_addr,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::transaction::eip2930::AccessList;
+    use ethers_core::utils::rlp::RlpStream;
+    use k256::ecdsa::SigningKey;
+
+    /// A type-2 tx to `to` signed by `key`, and the same tx with its signature moved to the
+    /// equivalent high-s form `(r, n - s)` with flipped parity.
+    fn low_and_high_s_txs(key: &SigningKey, to: Address) -> (String, String) {
+        let fields = |s: &mut RlpStream| {
+            s.append(&U256::from(11155111u64));
+            s.append(&U256::from(7u64));
+            s.append(&U256::from(1_000_000_000u64));
+            s.append(&U256::from(30_000_000_000u64));
+            s.append(&U256::from(200_000u64));
+            s.append(&to);
+            s.append(&U256::zero());
+            s.append(&vec![0xdeu8, 0xad]);
+            s.append(&AccessList::default());
+        };
+        let mut unsigned = RlpStream::new_list(9);
+        fields(&mut unsigned);
+        let mut preimage = vec![0x02u8];
+        preimage.extend_from_slice(&unsigned.out());
+        let (sig, rec) = key.sign_prehash_recoverable(&keccak256(preimage)).unwrap();
+        let r = U256::from_big_endian(&sig.r().to_bytes());
+        let s = U256::from_big_endian(&sig.s().to_bytes());
+        assert!(is_low_s(s));
+
+        let encode = |y: u8, s: U256| {
+            let mut signed = RlpStream::new_list(12);
+            fields(&mut signed);
+            signed.append(&y);
+            signed.append(&r);
+            signed.append(&s);
+            format!("0x02{}", hex::encode(signed.out()))
+        };
+        (encode(rec.to_byte(), s), encode(rec.to_byte() ^ 1, secp256k1_n() - s))
+    }
+
+    #[test]
+    fn high_s_tx_decodes_and_is_flagged() {
+        let key = SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let to = Address::repeat_byte(0x22);
+        let (low, high) = low_and_high_s_txs(&key, to);
+
+        let (.., low_to, _, low_data, low_from) = decode_signed_tx_and_recover(&low).unwrap();
+        let (.., high_to, _, high_data, high_from) = decode_signed_tx_and_recover(&high).unwrap();
+        assert_eq!((low_to, low_data.clone()), (to, vec![0xde, 0xad]));
+        assert_eq!((high_to, high_data), (low_to, low_data));
+        assert_eq!(high_from, low_from);
+        assert_eq!(low_from, ethers_core::utils::secret_key_to_address(&key));
+
+        assert!(!signed_tx_has_high_s(&low));
+        assert!(signed_tx_has_high_s(&high));
+    }
+}
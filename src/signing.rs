@@ -1,121 +1,252 @@
-use anyhow::{anyhow, Result};
+//! Signing primitives behind the [`Signer`] trait: payload signatures (EIP-191 or raw) and
+//! EIP-1559 transactions are all built on `Signer::sign_digest`, so a new backend only has to
+//! sign a 32-byte digest. Which backend a key uses is decided by its key reference, see
+//! [`open_signer`].
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use ethers_core::types::{
      transaction::eip2718::TypedTransaction, Address,
      Eip1559TransactionRequest, H256, NameOrAddress, Signature, U256,
  };
-use ethers_core::utils::{keccak256, rlp};
-use ethers_signers::{LocalWallet, Signer};
+use ethers_core::utils::keccak256;
+use ethers_signers::{LocalWallet, Signer as _};
+use zeroize::Zeroize;
+
+use crate::key::uncompressed_pubkey_0x04;
+use crate::process::privkey_bytes_from_input;
+use crate::settings::PayloadDigest;
+use crate::units::{GasLimit, Nonce, Wei};
+use inkan_management_utility::payload::eip191_digest;
+use inkan_management_utility::signed_tx::secp256k1_n;
+pub use inkan_management_utility::signed_tx::{decode_signed_tx_and_recover, is_low_s, signed_tx_has_high_s, HIGH_S_TX_WARNING};
+
+/// A signing key, wherever it lives. Signatures come back with `v` in {27, 28}.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Short description for messages, e.g. "local key" or "remote HSM key hsm-01/3".
+    fn describe(&self) -> String;
+    fn address(&self) -> Address;
+    /// Uncompressed public key, `0x04 || X || Y`, lowercase hex.
+    fn pubkey_0x04(&self) -> String;
+    /// Sign a 32-byte digest as-is (no prefixing or hashing).
+    async fn sign_digest(&self, digest: [u8; 32]) -> Result<Signature>;
+}
+
+/// Key held in process memory (hex or nsec input, or a decrypted key file).
+pub struct LocalKeySigner {
+    wallet: LocalWallet,
+    /// Derived once; batches ask for it on every item.
+    pubkey_0x04: String,
+}
+
+impl LocalKeySigner {
+    pub fn from_bytes(sk_bytes: &[u8; 32]) -> Result<Self> {
+        let sk = k256::ecdsa::SigningKey::from_slice(sk_bytes)
+            .context("invalid secp256k1 secret key (out of range or zero)")?;
+        let wallet = LocalWallet::from(sk);
+        let pubkey_0x04 = uncompressed_pubkey_0x04(&wallet).to_ascii_lowercase();
+        Ok(Self { wallet, pubkey_0x04 })
+    }
+}
+
+#[async_trait]
+impl Signer for LocalKeySigner {
+    fn describe(&self) -> String { "local key".to_string() }
+    fn address(&self) -> Address { self.wallet.address() }
+    fn pubkey_0x04(&self) -> String { self.pubkey_0x04.clone() }
+    async fn sign_digest(&self, digest: [u8; 32]) -> Result<Signature> {
+        Ok(self.wallet.sign_hash(H256::from(digest))?)
+    }
+}
+
+/// Where a key reference points. Plain hex / nsec is a local key; a `scheme:` prefix
+/// names another backend.
+pub enum KeyRef<'a> {
+    Local(&'a str),
+    /// `hardware:<device path or id>`
+    Hardware(&'a str),
+    /// `remote-hsm:<slot or URL>`
+    RemoteHsm(&'a str),
+}
+
+impl<'a> KeyRef<'a> {
+    pub fn parse(input: &'a str) -> KeyRef<'a> {
+        let s = input.trim();
+        if let Some(rest) = s.strip_prefix("hardware:") {
+            KeyRef::Hardware(rest)
+        } else if let Some(rest) = s.strip_prefix("remote-hsm:") {
+            KeyRef::RemoteHsm(rest)
+        } else {
+            KeyRef::Local(s)
+        }
+    }
+}
+
+/// Open the signing backend a key reference selects. Only local keys are available in
+/// this build; the other schemes are reserved so key inputs can name them already.
+pub fn open_signer(input: &str) -> Result<Box<dyn Signer>> {
+    match KeyRef::parse(input) {
+        KeyRef::Local(s) => Ok(Box::new(LocalKeySigner::from_bytes(&privkey_bytes_from_input(s)?)?)),
+        KeyRef::Hardware(id) => bail!("hardware signer '{id}': no hardware backend is available in this build"),
+        KeyRef::RemoteHsm(id) => bail!("remote HSM signer '{id}': no remote HSM backend is available in this build"),
+    }
+}
+
+/// Local signers by `keccak256(secret)`.
+type SignerMap = HashMap<[u8; 32], Arc<dyn Signer>>;
+
+/// Signers opened during one batch run, so a key reused across hundreds of items is parsed
+/// and derived once. Local keys are keyed by `keccak256(secret)`; the secret itself is not
+/// kept outside the signer. Clones share the cache; it is dropped with the run's `BatchOpts`.
+#[derive(Clone, Default)]
+pub struct SignerCache {
+    local: Arc<Mutex<SignerMap>>,
+    timing: SignTiming,
+}
+
+/// Signatures made through a cache's signers and the time spent in `sign_digest`, for the
+/// throughput figures of a batch run.
+#[derive(Clone, Default)]
+struct SignTiming {
+    count: Arc<AtomicU64>,
+    nanos: Arc<AtomicU64>,
+}
+
+/// A signer that adds its `sign_digest` calls to a `SignTiming`.
+struct TimedSigner {
+    inner: Arc<dyn Signer>,
+    timing: SignTiming,
+}
 
-use crate::util::{hex_to_bytes, parse_u256_any};
+#[async_trait]
+impl Signer for TimedSigner {
+    fn describe(&self) -> String { self.inner.describe() }
+    fn address(&self) -> Address { self.inner.address() }
+    fn pubkey_0x04(&self) -> String { self.inner.pubkey_0x04() }
+    async fn sign_digest(&self, digest: [u8; 32]) -> Result<Signature> {
+        let start = Instant::now();
+        let sig = self.inner.sign_digest(digest).await;
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.timing.nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.timing.count.fetch_add(1, Ordering::Relaxed);
+        sig
+    }
+}
+
+impl SignerCache {
+    /// Signatures made so far by signers from this cache, and the time spent making them.
+    pub fn signing_time(&self) -> (u64, Duration) {
+        let t = &self.timing;
+        (t.count.load(Ordering::Relaxed), Duration::from_nanos(t.nanos.load(Ordering::Relaxed)))
+    }
+
+    /// `open_signer`, reusing an earlier signer for the same local key.
+    pub fn open(&self, input: &str) -> Result<Arc<dyn Signer>> {
+        let signer = self.open_untimed(input)?;
+        Ok(Arc::new(TimedSigner { inner: signer, timing: self.timing.clone() }))
+    }
+
+    fn open_untimed(&self, input: &str) -> Result<Arc<dyn Signer>> {
+        let KeyRef::Local(s) = KeyRef::parse(input) else {
+            return open_signer(input).map(Arc::from);
+        };
+        let mut sk = privkey_bytes_from_input(s)?;
+        let id = keccak256(sk);
+        let mut local = self.local.lock().map_err(|_| anyhow!("signer cache poisoned"))?;
+        let signer = match local.get(&id) {
+            Some(signer) => Ok(signer.clone()),
+            None => LocalKeySigner::from_bytes(&sk).map(|signer| {
+                let signer: Arc<dyn Signer> = Arc::new(signer);
+                local.insert(id, signer.clone());
+                signer
+            }),
+        };
+        sk.zeroize();
+        signer
+    }
+}
 
-/// EIP-191 signMessage semantics: given 32-byte hash, sign the bytes (prefix added internally)
-pub async fn sign_message_eip191(wallet: &LocalWallet, hash32: [u8; 32]) -> Result<Signature> {
-    let sig = wallet.sign_message(&hash32).await?; // adds prefix like ethers.js
-    Ok(sig)
+impl fmt::Debug for SignerCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.local.lock().map(|m| m.len()).unwrap_or(0);
+        write!(f, "SignerCache({n} keys)")
+    }
+}
+
+/// Rewrite a high-s signature as the equivalent `(r, n - s)` with flipped recovery parity.
+/// `v` keeps its encoding: 27/28, 0/1, or EIP-155 (`35 + 2 * chainId + parity`).
+pub fn normalize_low_s(mut sig: Signature) -> Signature {
+    if is_low_s(sig.s) {
+        return sig;
+    }
+    sig.s = secp256k1_n() - sig.s;
+    sig.v = match sig.v {
+        0 | 1 => 1 - sig.v,
+        27 | 28 => 55 - sig.v,
+        v if v >= 35 && (v - 35) % 2 == 0 => v + 1,
+        v if v >= 35 => v - 1,
+        v => v,
+    };
+    sig
+}
+
+/// EIP-191 signMessage semantics: given 32-byte hash, sign the bytes (prefix added here, like ethers.js)
+pub async fn sign_message_eip191(signer: &dyn Signer, hash32: [u8; 32]) -> Result<Signature> {
+    let sig = signer.sign_digest(eip191_digest(hash32)).await
+        .with_context(|| format!("payload signature by {}", signer.describe()))?;
+    Ok(normalize_low_s(sig))
+}
+
+/// Sign a payload hash under the configured digest mode (see `PayloadDigest`).
+pub async fn sign_payload(signer: &dyn Signer, hash32: [u8; 32], digest: PayloadDigest) -> Result<Signature> {
+    match digest {
+        PayloadDigest::Eip191 => sign_message_eip191(signer, hash32).await,
+        PayloadDigest::RawKeccak => Ok(normalize_low_s(
+            signer.sign_digest(hash32).await
+                .with_context(|| format!("payload signature by {}", signer.describe()))?,
+        )),
+    }
 }
 
 /// Build + sign EIP-1559 tx
 pub async fn sign_eip1559(
-    wallet: &LocalWallet,
+    signer: &dyn Signer,
     chain_id: u64,
     to: Address,
-    nonce: u64,
-    gas_limit: &str,
-    max_fee: &str,
-    max_priority: &str,
+    nonce: Nonce,
+    gas_limit: GasLimit,
+    max_fee: Wei,
+    max_priority: Wei,
     data: Vec<u8>,
 ) -> Result<(String /*raw hex*/, TypedTransaction)> {
     let tx = Eip1559TransactionRequest {
-        from: Some(wallet.address()),
+        from: Some(signer.address()),
         to: Some(NameOrAddress::Address(to)),
         value: Some(U256::from(0u64)),
         data: Some(data.clone().into()),
-        nonce: Some(U256::from(nonce)),
-        gas: Some(parse_u256_any(gas_limit)?),
-        max_fee_per_gas: Some(parse_u256_any(max_fee)?),
-        max_priority_fee_per_gas: Some(parse_u256_any(max_priority)?),
+        nonce: Some(U256::from(nonce.0)),
+        gas: Some(gas_limit.0),
+        max_fee_per_gas: Some(max_fee.0),
+        max_priority_fee_per_gas: Some(max_priority.0),
         chain_id: Some(chain_id.into()), // U64
         access_list: Default::default(),
     };
     let typed = TypedTransaction::Eip1559(tx);
-    let sig = wallet.sign_transaction(&typed).await?;
+    let sig = signer.sign_digest(typed.sighash().into()).await
+        .with_context(|| format!("transaction signature by {}", signer.describe()))?;
+    let mut sig = normalize_low_s(sig);
+    // Typed txs carry the y-parity (0/1) rather than 27/28.
+    sig.v = match sig.v {
+        27 | 28 => sig.v - 27,
+        0 | 1 => sig.v,
+        v => bail!("{} returned an unexpected recovery value v = {v}", signer.describe()),
+    };
     let rlp_bytes = typed.rlp_signed(&sig);
     Ok((format!("0x{}", hex::encode(rlp_bytes)), typed))
 }
-
-/// Decode a raw signed EIP-1559 tx and recover sender
-#[allow(clippy::type_complexity)]
-pub fn decode_signed_tx_and_recover(
-    raw_hex: &str,
-) -> Result<(
-    u64,      /*chainId*/
-    u64,      /*nonce*/
-    U256,     /*maxPrio*/
-    U256,     /*maxFee*/
-    U256,     /*gas*/
-    Address,  /*to*/
-    U256,     /*value*/
-    Vec<u8>,  /*data*/
-    Address,  /*from*/
-)> {
-    // Expect 0x02-prefixed typed tx
-    let raw = hex_to_bytes(raw_hex)?;
-    if raw.first() != Some(&0x02) {
-        return Err(anyhow!("Not a type-2 (EIP-1559) tx"));
-    }
-    let rlp_body = &raw[1..];
-    let r = rlp::Rlp::new(rlp_body);
-
-    // Fields per spec: [chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, yParity, r, s]
-    let chain_id: U256 = r.at(0)?.as_val()?;
-    let nonce: U256 = r.at(1)?.as_val()?;
-    let max_prio: U256 = r.at(2)?.as_val()?;
-    let max_fee: U256 = r.at(3)?.as_val()?;
-    let gas: U256 = r.at(4)?.as_val()?;
-    let to_bytes: Vec<u8> = r.at(5)?.as_val()?;
-    let to = Address::from_slice(&to_bytes);
-    let value: U256 = r.at(6)?.as_val()?;
-    let data: Vec<u8> = r.at(7)?.as_val()?;
-    // accessList at 8 ignored for now
-    let y_parity: u8 = r.at(9)?.as_val()?;
-    let r_bytes: Vec<u8> = r.at(10)?.as_val()?;
-    let s_bytes: Vec<u8> = r.at(11)?.as_val()?;
-
-    // sighash = keccak256( 0x02 || rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gas, to, value, data, accessList]) )
-    let mut s = ethers_core::utils::rlp::RlpStream::new_list(9);
-    s.append(&chain_id);
-    s.append(&nonce);
-    s.append(&max_prio);
-    s.append(&max_fee);
-    s.append(&gas);
-    s.append(&to);
-    s.append(&value);
-    s.append(&data);
-    s.append(&ethers_core::types::transaction::eip2930::AccessList::default());
-
-    let mut preimage = vec![0x02u8];
-    preimage.extend_from_slice(&s.out());
-
-    let sighash = H256::from(keccak256(preimage));
-
-    let sig = ethers_core::types::Signature {
-        r: U256::from_big_endian(&r_bytes),
-        s: U256::from_big_endian(&s_bytes),
-        v: y_parity as u64, // 0/1 for type-2
-    };
-
-    let from_addr = sig.recover(sighash)?;
-
-    Ok((
-        chain_id.as_u64(),
-        nonce.as_u64(),
-        max_prio,
-        max_fee,
-        gas,
-        to,
-        value,
-        data,
-        from_addr,
-    ))
-}
-
@@ -0,0 +1,243 @@
+//! Optional pre-flight simulation of signed transactions in an embedded EVM (`revm`).
+//!
+//! Enabled by building with `--features simulation` and pointing
+//! `Settings::simulation_state_file` at either:
+//!   * a state snapshot JSON:
+//!     `{ "block": { "number": .., "timestamp": .. },
+//!        "accounts": { "0x…": { "balance": "0x…", "nonce": 0, "code": "0x…", "storage": { "0x…": "0x…" } } } }`
+//!   * or a plain hex file holding the deployed (runtime) bytecode of the Inkan contract,
+//!     which is then placed at each transaction's `to` address.
+//!
+//! Transactions are executed in order against one in-memory state, so a batch sees the
+//! effects of its earlier entries. Nothing is written when any entry would revert.
+
+use anyhow::Result;
+
+use crate::settings::Settings;
+use crate::types::BatchEntryOut;
+
+/// Simulate `entries` if a simulation state file is configured; no-op otherwise.
+/// Fails with a per-entry report when any transaction would revert or halt.
+pub fn check_entries(settings: &Settings, entries: &[BatchEntryOut]) -> Result<()> {
+    let state_file = settings.simulation_state_file.trim();
+    if state_file.is_empty() {
+        return Ok(());
+    }
+    imp::check_entries(state_file, entries)
+}
+
+#[cfg(not(feature = "simulation"))]
+mod imp {
+    use anyhow::Result;
+
+    use crate::types::BatchEntryOut;
+
+    pub fn check_entries(_state_file: &str, _entries: &[BatchEntryOut]) -> Result<()> {
+        anyhow::bail!(
+            "A simulation state file is configured, but this build has no simulation support. \
+             Rebuild with `--features simulation` or clear the setting."
+        )
+    }
+}
+
+#[cfg(feature = "simulation")]
+mod imp {
+    use anyhow::{anyhow, Context, Result};
+    use revm::db::{CacheDB, EmptyDB};
+    use revm::primitives::{
+        AccountInfo, Address, Bytecode, Bytes, ExecutionResult, SpecId, TxKind, U256,
+    };
+    use revm::Evm;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use crate::types::BatchEntryOut;
+    use crate::util::hex_to_bytes;
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct Snapshot {
+        block: BlockSnapshot,
+        accounts: HashMap<String, AccountSnapshot>,
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct BlockSnapshot {
+        number: Option<u64>,
+        timestamp: Option<u64>,
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct AccountSnapshot {
+        balance: Option<String>,
+        nonce: u64,
+        code: Option<String>,
+        storage: HashMap<String, String>,
+    }
+
+    /// What the state file described: full snapshot, or bare bytecode to place at `to`.
+    enum State {
+        Snapshot(Snapshot),
+        Bytecode(Vec<u8>),
+    }
+
+    fn load_state(path: &Path) -> Result<State> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("reading simulation state {}", path.display()))?;
+        let t = raw.trim();
+        if t.starts_with('{') {
+            let snap: Snapshot = serde_json::from_str(t)
+                .with_context(|| format!("parsing state snapshot {}", path.display()))?;
+            return Ok(State::Snapshot(snap));
+        }
+        let code: String = t.split_whitespace().collect();
+        let bytes = hex_to_bytes(&code)
+            .with_context(|| format!("{} is neither a JSON snapshot nor hex bytecode", path.display()))?;
+        if bytes.is_empty() {
+            anyhow::bail!("Bytecode file {} is empty.", path.display());
+        }
+        Ok(State::Bytecode(bytes))
+    }
+
+    fn u256(s: &str) -> Result<U256> {
+        U256::from_str(s.trim()).map_err(|e| anyhow!("invalid integer {s:?}: {e}"))
+    }
+
+    fn address(s: &str) -> Result<Address> {
+        Address::from_str(s.trim()).map_err(|e| anyhow!("invalid address {s:?}: {e}"))
+    }
+
+    fn account(balance: U256, nonce: u64, code: Option<Bytecode>) -> AccountInfo {
+        match code {
+            Some(code) => AccountInfo::new(balance, nonce, code.hash_slow(), code),
+            None => AccountInfo { balance, nonce, ..Default::default() },
+        }
+    }
+
+    fn build_db(snap: &Snapshot) -> Result<CacheDB<EmptyDB>> {
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (addr, acct) in &snap.accounts {
+            let a = address(addr)?;
+            let code = match &acct.code {
+                Some(c) if !c.trim().is_empty() => {
+                    Some(Bytecode::new_raw(Bytes::from(hex_to_bytes(c.trim())?)))
+                }
+                _ => None,
+            };
+            let balance = acct.balance.as_deref().map(u256).transpose()?.unwrap_or_default();
+            db.insert_account_info(a, account(balance, acct.nonce, code));
+            for (slot, value) in &acct.storage {
+                db.insert_account_storage(a, u256(slot)?, u256(value)?)
+                    .map_err(|_| anyhow!("failed to seed storage for {addr}"))?;
+            }
+        }
+        Ok(db)
+    }
+
+    pub fn check_entries(state_file: &str, entries: &[BatchEntryOut]) -> Result<()> {
+        let state = load_state(Path::new(state_file))?;
+        let (snap, bytecode) = match state {
+            State::Snapshot(s) => (s, None),
+            State::Bytecode(b) => (Snapshot::default(), Some(b)),
+        };
+        let mut db = build_db(&snap)?;
+
+        let mut failures = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let tx = &entry.decoded_tx;
+            let caller = address(&tx.from)?;
+            let to = address(&tx.to)?;
+
+            if let (Some(code), false) = (&bytecode, db.accounts.contains_key(&to)) {
+                let code = Bytecode::new_raw(Bytes::from(code.clone()));
+                db.insert_account_info(to, account(U256::ZERO, 1, Some(code)));
+            }
+
+            // Make sure the sender can pay for gas; the snapshot may not include it.
+            let gas_limit: u64 = tx.gasLimit.parse().context("gasLimit")?;
+            let max_fee = u256(&tx.maxFeePerGas)?;
+            let value = u256(&tx.value)?;
+            let needed = max_fee * U256::from(gas_limit) + value;
+            let mut sender = db
+                .accounts
+                .get(&caller)
+                .map(|a| a.info.clone())
+                .unwrap_or_default();
+            if sender.balance < needed {
+                sender.balance = needed;
+            }
+            db.insert_account_info(caller, sender);
+
+            let chain_id: u64 = tx.chainId.parse().context("chainId")?;
+            let data = Bytes::from(hex_to_bytes(&tx.encodedData)?);
+            let prio = u256(&tx.maxPriorityFeePerGas)?;
+
+            let mut evm = Evm::builder()
+                .with_db(&mut db)
+                .with_spec_id(SpecId::CANCUN)
+                .modify_cfg_env(|c| c.chain_id = chain_id)
+                .modify_block_env(|b| {
+                    if let Some(n) = snap.block.number {
+                        b.number = U256::from(n);
+                    }
+                    if let Some(ts) = snap.block.timestamp {
+                        b.timestamp = U256::from(ts);
+                    }
+                })
+                .modify_tx_env(|t| {
+                    t.caller = caller;
+                    t.transact_to = TxKind::Call(to);
+                    t.value = value;
+                    t.data = data;
+                    t.gas_limit = gas_limit;
+                    t.gas_price = max_fee;
+                    t.gas_priority_fee = Some(prio);
+                    t.chain_id = Some(chain_id);
+                    // Account nonces are not what is being checked here.
+                    t.nonce = None;
+                })
+                .build();
+
+            let outcome = evm
+                .transact_commit()
+                .map_err(|e| anyhow!("entry {}: simulation error: {e:?}", i + 1))?;
+            match outcome {
+                ExecutionResult::Success { .. } => {}
+                ExecutionResult::Revert { output, .. } => failures.push(format!(
+                    "entry {} (nonce {}, {}): reverted {}",
+                    i + 1, tx.nonce, tx.funcName, describe_revert(&output)
+                )),
+                ExecutionResult::Halt { reason, .. } => failures.push(format!(
+                    "entry {} (nonce {}, {}): halted ({reason:?})",
+                    i + 1, tx.nonce, tx.funcName
+                )),
+            }
+        }
+
+        if !failures.is_empty() {
+            anyhow::bail!("Simulation failed:\n{}", failures.join("\n"));
+        }
+        Ok(())
+    }
+
+    /// Decode `Error(string)` revert data when present, otherwise show the raw bytes.
+    fn describe_revert(output: &Bytes) -> String {
+        const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+        if output.len() >= 68 && output[..4] == ERROR_SELECTOR {
+            let len = U256::from_be_slice(&output[36..68]).saturating_to::<usize>();
+            if let Some(msg) = 68usize.checked_add(len).and_then(|end| output.get(68..end)) {
+                return format!("with \"{}\"", String::from_utf8_lossy(msg));
+            }
+        }
+        if output.is_empty() {
+            "without reason".to_string()
+        } else {
+            format!("with data 0x{}", hex::encode(output))
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Per-run ephemeral working directory. With `Settings::ephemeral_run_dir` on, everything the
+//! screens write through `AppCtx::fs` (signed transaction files, batch runs and their manifests,
+//! temp files) lands in a fresh directory on a RAM-backed filesystem instead of its destination.
+//! Staged files read, list and rename as if they were at their destination. The operator moves
+//! the outputs worth keeping to disk from Staged Outputs (also offered at quit); whatever is
+//! still staged when the app exits is shredded, so an aborted signing session leaves nothing on
+//! persistent storage.
+//!
+//! A staged file sits at the destination's absolute path below the run directory
+//! (`/home/op/out/a.json` → `<run>/home/op/out/a.json`). Files the key and decrypt flows write
+//! directly with `std::fs` are not staged.
+
+use std::io::{self, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::vfs::{resolve, DirEntry, Fs, FsRef, RealFs};
+
+/// Where a run directory is created, best first: tmpfs mounts, then the system temp dir.
+fn base_dir() -> (PathBuf, bool) {
+    let candidates = [std::ffi::OsString::from("/dev/shm")].into_iter().chain(std::env::var_os("XDG_RUNTIME_DIR"));
+    for dir in candidates.map(PathBuf::from) {
+        if dir.is_dir() && ram_backed(&dir) {
+            return (dir, true);
+        }
+    }
+    let tmp = std::env::temp_dir();
+    let ram = ram_backed(&tmp);
+    (tmp, ram)
+}
+
+/// Whether `dir` is on tmpfs or ramfs, i.e. never written to a disk (swap aside).
+#[cfg(target_os = "linux")]
+fn ram_backed(dir: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(c_path) = std::ffi::CString::new(dir.as_os_str().as_bytes()) else { return false };
+    // SAFETY: statfs is all zeroes-valid plain data
+    let mut st: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: valid NUL-terminated path and a stack value to fill
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut st) } != 0 {
+        return false;
+    }
+    // ramfs has no constant in libc
+    const RAMFS_MAGIC: i64 = 0x8584_58f6;
+    // f_type's width differs between targets
+    #[allow(clippy::unnecessary_cast)]
+    let (kind, tmpfs) = (st.f_type as i64, libc::TMPFS_MAGIC as i64);
+    kind == tmpfs || kind == RAMFS_MAGIC
+}
+
+#[cfg(not(target_os = "linux"))]
+fn ram_backed(_dir: &Path) -> bool {
+    false
+}
+
+/// One run's directory. Dropping it shreds whatever is still inside and removes it.
+struct RunDir {
+    root: PathBuf,
+    ram_backed: bool,
+}
+
+impl RunDir {
+    fn create() -> io::Result<Self> {
+        use rand::RngCore;
+        let (base, ram_backed) = base_dir();
+        let root = base.join(format!("inkan-run-{}-{:08x}", std::process::id(), rand::thread_rng().next_u32()));
+        std::fs::create_dir(&root)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&root, std::fs::Permissions::from_mode(0o700))?;
+        }
+        Ok(Self { root, ram_backed })
+    }
+
+    /// Staged location of `dest`.
+    fn staged(&self, dest: &Path) -> PathBuf {
+        let mut out = self.root.clone();
+        out.extend(resolve(dest).components().filter(|c| matches!(c, Component::Normal(_))));
+        out
+    }
+
+    /// Destination of the staged file `staged`.
+    fn dest(&self, staged: &Path) -> PathBuf {
+        Path::new(std::path::MAIN_SEPARATOR_STR).join(staged.strip_prefix(&self.root).unwrap_or(staged))
+    }
+
+    /// Staged files, as (staged, destination) pairs.
+    fn files(&self) -> Vec<(PathBuf, PathBuf)> {
+        let mut out = Vec::new();
+        let mut dirs = vec![self.root.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    out.push((path.clone(), self.dest(&path)));
+                }
+            }
+        }
+        out.sort_by(|a, b| a.1.cmp(&b.1));
+        out
+    }
+}
+
+impl Drop for RunDir {
+    fn drop(&mut self) {
+        for (staged, _) in self.files() {
+            let _ = RealFs.shred(&staged);
+        }
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+#[derive(Default)]
+struct State {
+    enabled: bool,
+    run: Option<RunDir>,
+    /// Why the run directory could not be created; writes fail with it while enabled.
+    error: Option<String>,
+}
+
+/// Shared handle to the run directory; follows `Settings::ephemeral_run_dir` via
+/// `AppCtx::set_settings`. Turning the setting off stops staging new writes; files already
+/// staged stay until they are kept or the app exits.
+#[derive(Clone, Default)]
+pub struct Staging(Arc<Mutex<State>>);
+
+impl Staging {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.0.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    pub fn set_enabled(&self, on: bool) {
+        let mut state = self.state();
+        state.enabled = on;
+        if on && state.run.is_none() {
+            match RunDir::create() {
+                Ok(run) => {
+                    state.run = Some(run);
+                    state.error = None;
+                }
+                Err(e) => state.error = Some(format!("could not create the ephemeral run directory: {e}")),
+            }
+        }
+    }
+
+    /// The run directory and whether it is RAM-backed, once created.
+    pub fn location(&self) -> Option<(PathBuf, bool)> {
+        self.state().run.as_ref().map(|r| (r.root.clone(), r.ram_backed))
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.state().error.clone()
+    }
+
+    /// Destinations of the staged files, sorted.
+    pub fn pending(&self) -> Vec<PathBuf> {
+        self.state().run.as_ref().map(|r| r.files().into_iter().map(|(_, dest)| dest).collect()).unwrap_or_default()
+    }
+
+    /// Move the staged file for `dest` to `dest` on disk (never overwriting), shredding the
+    /// staged copy. The copy is flushed to the device before the staged file goes.
+    pub fn keep(&self, dest: &Path) -> io::Result<()> {
+        use std::io::Write;
+        let staged = self.staged_file(dest).ok_or_else(|| not_staged(dest))?;
+        let data = std::fs::read(&staged)?;
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut f = std::fs::OpenOptions::new().write(true).create_new(true).open(dest)?;
+        f.write_all(&data)?;
+        f.sync_all()?;
+        RealFs.shred(&staged)
+    }
+
+    /// Shred the staged file for `dest`.
+    pub fn discard(&self, dest: &Path) -> io::Result<()> {
+        let staged = self.staged_file(dest).ok_or_else(|| not_staged(dest))?;
+        RealFs.shred(&staged)
+    }
+
+    /// At exit: shred everything still staged and remove the run directory. Returns the line
+    /// for the exit notes, if there was a run directory.
+    pub fn finish(&self) -> Option<String> {
+        let run = self.state().run.take()?;
+        let left = run.files().len();
+        let root = run.root.display().to_string();
+        drop(run);
+        Some(format!("Ephemeral run directory: {left} staged file(s) shredded, {root} removed."))
+    }
+
+    /// Where a write to `dest` goes: `Ok(None)` for the destination itself.
+    fn route(&self, dest: &Path) -> io::Result<Option<PathBuf>> {
+        let state = self.state();
+        if !state.enabled {
+            return Ok(None);
+        }
+        match (&state.run, &state.error) {
+            (Some(run), _) => Ok(Some(run.staged(dest))),
+            (None, Some(e)) => Err(io::Error::other(e.clone())),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// The staged location of `dest` when something is staged there.
+    fn staged_entry(&self, dest: &Path) -> Option<PathBuf> {
+        let state = self.state();
+        let staged = state.run.as_ref()?.staged(dest);
+        staged.exists().then_some(staged)
+    }
+
+    fn staged_file(&self, dest: &Path) -> Option<PathBuf> {
+        self.staged_entry(dest).filter(|p| p.is_file())
+    }
+}
+
+fn not_staged(path: &Path) -> io::Error {
+    io::Error::new(ErrorKind::NotFound, format!("{} is not staged", path.display()))
+}
+
+fn exists_error(path: &Path) -> io::Error {
+    io::Error::new(ErrorKind::AlreadyExists, format!("{} exists", path.display()))
+}
+
+/// Another filesystem, with writes redirected into the run directory while staging is on.
+pub struct StagingFs {
+    inner: FsRef,
+    staging: Staging,
+}
+
+impl StagingFs {
+    pub fn new(inner: FsRef, staging: Staging) -> Self {
+        Self { inner, staging }
+    }
+
+    /// Staged target for a new entry at `path`: its parent must exist at the destination or
+    /// in staging, and is created in staging.
+    fn prepare(&self, path: &Path, staged: &Path) -> io::Result<()> {
+        let parent_ok = path.parent().is_none_or(|p| p.as_os_str().is_empty() || self.is_dir(p));
+        if !parent_ok {
+            return Err(io::Error::new(ErrorKind::NotFound, format!("{} not found", path.parent().unwrap_or(path).display())));
+        }
+        match staged.parent() {
+            Some(parent) => std::fs::create_dir_all(parent),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Fs for StagingFs {
+    fn read_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>> {
+        let staged = self.staging.staged_entry(dir).filter(|p| p.is_dir());
+        let mut out = match (self.inner.read_dir(dir), &staged) {
+            (Ok(entries), _) => entries,
+            (Err(_), Some(_)) => Vec::new(),
+            (Err(e), None) => return Err(e),
+        };
+        if let Some(staged) = staged {
+            for entry in RealFs.read_dir(&staged)? {
+                let path = dir.join(entry.path.file_name().unwrap_or_default());
+                if !out.iter().any(|e| e.path == path) {
+                    out.push(DirEntry { path, is_dir: entry.is_dir });
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.staging.staged_file(path) {
+            Some(staged) => std::fs::read(staged),
+            None => self.inner.read(path),
+        }
+    }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        match self.staging.route(path)? {
+            Some(staged) => {
+                self.prepare(path, &staged)?;
+                std::fs::write(staged, data)
+            }
+            None => self.inner.write(path, data),
+        }
+    }
+
+    fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        match self.staging.route(path)? {
+            Some(staged) => {
+                if self.exists(path) {
+                    return Err(exists_error(path));
+                }
+                self.prepare(path, &staged)?;
+                RealFs.create_new(&staged, data)
+            }
+            None => self.inner.create_new(path, data),
+        }
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        match self.staging.route(path)? {
+            Some(staged) => {
+                if self.exists(path) {
+                    return Err(exists_error(path));
+                }
+                self.prepare(path, &staged)?;
+                std::fs::create_dir(staged)
+            }
+            None => self.inner.create_dir(path),
+        }
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        match self.staging.route(path)? {
+            Some(staged) => std::fs::create_dir_all(staged),
+            None => self.inner.create_dir_all(path),
+        }
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        let staged = self.staging.staged_entry(path).filter(|p| p.is_dir());
+        if let Some(staged) = &staged {
+            std::fs::remove_dir_all(staged)?;
+        }
+        match (self.inner.is_dir(path), staged) {
+            (true, _) => self.inner.remove_dir_all(path),
+            (false, Some(_)) => Ok(()),
+            (false, None) => self.inner.remove_dir_all(path),
+        }
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let Some(staged_from) = self.staging.staged_file(from) else {
+            return self.inner.rename(from, to);
+        };
+        if self.exists(to) {
+            return Err(exists_error(to));
+        }
+        // A staged file stays staged under its new name, even with staging since turned off
+        let staged_to = self.staging.state().run.as_ref().map(|r| r.staged(to)).ok_or_else(|| not_staged(from))?;
+        self.prepare(to, &staged_to)?;
+        std::fs::rename(staged_from, staged_to)
+    }
+
+    fn shred(&self, path: &Path) -> io::Result<()> {
+        match self.staging.staged_file(path) {
+            Some(staged) => RealFs.shred(&staged),
+            None => self.inner.shred(path),
+        }
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.inner.is_dir(path) || self.staging.staged_entry(path).is_some_and(|p| p.is_dir())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.inner.exists(path) || self.staging.staged_entry(path).is_some()
+    }
+}
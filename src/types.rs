@@ -1,13 +1,20 @@
 use serde::{Deserialize, Serialize};
 
+use crate::units::Nonce;
+pub use inkan_management_utility::decoder::{
+    DecodedOne, DecodedTxOut, DelegationDecodedOrdered, InvalidationDecodedOrdered, RevocationDecodedOrdered,
+};
+
 /// Batch input items (verbatim field names from your examples)
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub struct Item {
     pub function_to_call: String,
-    pub nonce: Option<u64>,
+    pub nonce: Option<Nonce>,
     pub chain_id: Option<u64>,
     pub contract_address: String,
+    /// Operator's tag for the transaction, carried into the output entry, manifest and filename.
+    pub label: Option<String>,
 
     // A
     pub type_a_privkey_x: Option<String>,
@@ -16,6 +23,8 @@ pub struct Item {
     pub type_a_uint_x: Option<u64>,
     pub type_a_uint_y: Option<u64>,
     pub type_a_boolean: Option<String>,
+    /// Payload nonce (see `encoding::bytes16_or_random`); absent or empty = random.
+    pub type_a_nonce16: Option<String>,
 
     // B
     pub type_b_privkey_x: Option<String>,
@@ -23,9 +32,11 @@ pub struct Item {
     pub type_b_pubkey_y: Option<String>,
     pub type_b_uint_x: Option<u64>,
     pub type_b_uint_y: Option<u64>,
+    pub type_b_nonce16: Option<String>,
 
     // C
     pub type_c_privkey_x: Option<String>,
+    pub type_c_nonce16: Option<String>,
 }
 
 /// Output shapes
@@ -35,78 +46,94 @@ pub struct BatchEntryOut {
     pub signed_tx: String,
     #[serde(rename = "decodedTx")]
     pub decoded_tx: DecodedTxOut,
+    /// The item's `LABEL`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
+/// Manifest written into each per-run batch directory (`manifest.json`)
+#[allow(non_snake_case)]
 #[derive(Debug, Serialize)]
-pub struct DecodedTxOut {
-    pub from: String,
-    pub to: String,
-    pub value: String,
-    pub gasLimit: String,
-    pub nonce: u64,
-    pub chainId: String,
-    pub maxFeePerGas: String,
-    pub maxPriorityFeePerGas: String,
-    pub funcName: String,
-    pub encodedData: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub decodedData: Option<DecodedOne>,
+pub struct BatchManifestOut {
+    pub createdAt: String,
+    pub inputSha256: String,
+    pub itemCount: usize,
+    /// Present only when the batch stopped before every item was signed.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub decodedDataTypeA: Option<DelegationDecodedOrdered>,
+    pub incomplete: Option<BatchIncompleteOut>,
+    /// Throughput of the signing run (see `BatchRun::stats`).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub decodedDataTypeB: Option<RevocationDecodedOrdered>,
+    pub stats: Option<BatchStatsOut>,
+    pub entries: Vec<BatchManifestEntryOut>,
 }
 
-/// Ordered decoded output structs (to guarantee field order in JSON)
-#[derive(Debug, Serialize)]
-pub struct DelegationDecodedOrdered {
-    pub delegatorPubkey: String,
-    pub delegateePubkey: String,
-    pub delegationStartTime: String,
-    pub delegationEndTime: String,
-    pub doesRevocationRequireDelegateeSignature: bool,
-    pub nonce: String,
-    pub expectedAddressOfDeployedContract: String,
-    pub rDelegatorPubkeySig: String,
-    pub sDelegatorPubkeySig: String,
-    pub vDelegatorPubkeySig: String,
-    pub rDelegateePubkeySig: String,
-    pub sDelegateePubkeySig: String,
-    pub vDelegateePubkeySig: String,
+/// Throughput of a batch run. Latencies are per signed item, in milliseconds; "sign" is the time
+/// spent in the signers (payload and transaction signatures), "encode" everything else (payload
+/// hashing, ABI encoding, decoding the result).
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchStatsOut {
+    pub itemsSigned: usize,
+    pub failures: usize,
+    /// From the start of the first item to the end of the last one.
+    pub totalSeconds: f64,
+    pub signatures: u64,
+    pub signaturesPerSecond: f64,
+    pub avgItemMs: f64,
+    pub avgEncodeMs: f64,
+    pub avgSignMs: f64,
 }
 
+#[allow(non_snake_case)]
 #[derive(Debug, Serialize)]
-pub struct RevocationDecodedOrdered {
-    pub revokerPubkey: String,
-    pub revokeePubkey: String,
-    pub revocationStartTime: String,
-    pub revocationEndTime: String,
-    pub nonce: String,
-    pub expectedAddressOfDeployedContract: String,
-    pub rRevokerPubkeySig: String,
-    pub sRevokerPubkeySig: String,
-    pub vRevokerPubkeySig: String,
-    pub rRevokeePubkeySig: String,
-    pub sRevokeePubkeySig: String,
-    pub vRevokeePubkeySig: String,
+pub struct BatchIncompleteOut {
+    pub totalItems: usize,
+    pub reason: String,
 }
 
+#[allow(non_snake_case)]
 #[derive(Debug, Serialize)]
-pub struct InvalidationDecodedOrdered {
-    pub invalidatedPubkey: String,
-    pub nonce: String,
-    pub expectedAddressOfDeployedContract: String,
-    pub rInvalidatedPubkeySig: String,
-    pub sInvalidatedPubkeySig: String,
-    pub vInvalidatedPubkeySig: String,
+pub struct BatchManifestEntryOut {
+    pub index: usize,
+    pub file: String,
+    pub funcName: String,
+    pub from: String,
+    pub nonce: u64,
+    pub chainId: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
-/// Untagged enum so `decodedData` can be one of the three ordered shapes
+/// Version of the signed-transaction file envelope. A bare array is version 1.
+pub const SIGNED_FILE_FORMAT_VERSION: u32 = 2;
+
+/// Versioned signed-transaction file: metadata block alongside the entries array
+#[allow(non_snake_case)]
 #[derive(Debug, Serialize)]
-#[serde(untagged)]
-pub enum DecodedOne {
-    Delegation(DelegationDecodedOrdered),
-    Revocation(RevocationDecodedOrdered),
-    Invalidation(InvalidationDecodedOrdered),
+pub struct SignedFileOut<'a> {
+    pub formatVersion: u32,
+    pub metadata: &'a SignedFileMetadata,
+    pub entries: &'a [BatchEntryOut],
 }
 
+/// Creator metadata embedded in signed-transaction files
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedFileMetadata {
+    /// Sender of every transaction in the file (checksummed): the account to fund on-chain.
+    /// Omitted when entries have different senders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fundingAddress: Option<String>,
+    /// Most the file's transactions can cost the sender in total (sum of gas limit × max fee per gas).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxCostWei: Option<String>,
+    pub toolVersion: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operatorLabel: Option<String>,
+    pub createdAt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inputSha256: Option<String>,
+    /// Per-event-type default gas limit from the settings (omitted when none was configured).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gasLimitDefault: Option<String>,
+}
@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use crate::app::Transition;
 
 /// Return `Transition::Pop` on Esc so every screen gets "Back" for free.
@@ -10,3 +10,14 @@ pub fn esc_to_back(k: KeyEvent) -> Option<Transition> {
     }
 }
 
+
+/// Ctrl+R: reveal the focused secret field briefly (see `RevealBriefly`).
+pub fn is_reveal_key(k: &KeyEvent) -> bool {
+    k.modifiers.contains(KeyModifiers::CONTROL) && matches!(k.code, KeyCode::Char('r' | 'R'))
+}
+
+/// Ctrl+K: load the focused private-key field from an encrypted key file (see
+/// `screens::load_signing_key`).
+pub fn is_load_key_file_key(k: &KeyEvent) -> bool {
+    k.modifiers.contains(KeyModifiers::CONTROL) && matches!(k.code, KeyCode::Char('k' | 'K'))
+}
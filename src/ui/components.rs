@@ -3,13 +3,26 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders},
 };
+use std::time::{Duration, Instant};
 
+use zeroize::Zeroize;
+
+use crate::display::DisplaySettings;
+
+/// An editable line. Its text is zeroized when the field is dropped, since password and
+/// private-key fields are TextFields too.
 #[derive(Clone, Default)]
 pub struct TextField {
     pub text: String,
     pub cursor: usize,
 }
 
+impl Drop for TextField {
+    fn drop(&mut self) {
+        self.text.zeroize();
+    }
+}
+
 impl TextField {
     pub fn with(text: &str) -> Self {
         Self { text: text.into(), cursor: text.len() }
@@ -23,6 +36,25 @@ impl TextField {
     pub fn end(&mut self) { self.cursor = self.text.len(); }
 }
 
+/// "Reveal briefly" state for masked secret fields: unmasks one field for `REVEAL_FOR`
+/// without flipping the screen-wide Show Password toggle. The main loop redraws every
+/// 250 ms, so the field re-masks on its own once the time is up.
+#[derive(Clone, Copy, Default)]
+pub struct RevealBriefly {
+    active: Option<(usize, Instant)>,
+}
+
+impl RevealBriefly {
+    pub const REVEAL_FOR: Duration = Duration::from_secs(5);
+
+    pub fn start(&mut self, field_index: usize) { self.active = Some((field_index, Instant::now())); }
+
+    /// True while `field_index` is revealed and still focused (moving focus away re-masks it).
+    pub fn shows(&self, field_index: usize, focused: usize) -> bool {
+        matches!(self.active, Some((i, t)) if i == field_index && i == focused && t.elapsed() < Self::REVEAL_FOR)
+    }
+}
+
 pub fn draw_frame_title(title: &str) -> Block<'_> {
     Block::default().borders(Borders::ALL).title(title)
 }
@@ -83,3 +115,89 @@ pub fn bool_field_line<'a>(label: &str, val: bool, focused: bool) -> Line<'a> {
     ])
 }
 
+// Password field that visually matches field_line_text (yellow label and SAME cursor behavior/color),
+// masked with bullets unless `show`. The cursor is moved from the typed text's byte offset to the
+// same character in the rendered text, so multi-byte input never lands it off a UTF-8 boundary.
+pub fn field_line_password(label: &str, tf: &TextField, selected: bool, show: bool) -> Line<'static> {
+    let render = if show { tf.text.clone() } else { "•".repeat(tf.text.chars().count()) };
+    let cursor_chars = tf.text.get(..tf.cursor.min(tf.text.len())).map_or(0, |s| s.chars().count());
+
+    let mut tmp = TextField::with(&render);
+    tmp.cursor = render.char_indices().nth(cursor_chars).map_or(render.len(), |(i, _)| i);
+
+    field_line_text(label, &tmp, selected)
+}
+
+// "Show Password: On/Off" toggle shown under a form's password fields.
+pub fn show_password_line(show: bool, selected: bool) -> Line<'static> {
+    let label_span = Span::styled("Show Password: ", Style::default().fg(Color::Yellow));
+    let val = if show { "On" } else { "Off" };
+    let val_style = if selected {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    Line::from(vec![label_span, Span::styled(val.to_string(), val_style)])
+}
+
+// Read-only "Label: value" line (not focusable), e.g. for previews.
+pub fn info_line_text<'a>(label: &str, value: &str) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(format!("{label}: "), Style::default().fg(Color::Yellow)),
+        Span::styled(value.to_string(), Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+/// Two info lines for a Create form: the sender address derived from `sender_priv` (the
+/// account that pays gas and must be funded on-chain) and the most the transaction can cost,
+/// gas limit × max fee per gas.
+pub fn funding_lines(sender_priv: &str, gas_limit: &str, max_fee_per_gas: &str, display: &DisplaySettings) -> [Line<'static>; 2] {
+    let address = match crate::process::address_from_privkey_input(sender_priv) {
+        Some(a) => Span::styled(
+            ethers_core::utils::to_checksum(&a, None),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+        None => Span::styled("(enter the sender's private key)", Style::default().fg(Color::DarkGray)),
+    };
+    let cost = gas_limit.parse::<crate::units::GasLimit>()
+        .and_then(|g| Ok(g.max_cost(max_fee_per_gas.parse()?)))
+        .map(|wei| format!("{}; the sender must hold at least this", display.amount(wei.0)))
+        .unwrap_or_else(|_| "(needs a valid gas limit and max fee per gas)".to_string());
+    [
+        Line::from(vec![Span::styled("Sender Address To Fund: ", Style::default().fg(Color::Yellow)), address]),
+        info_line_text("Max Cost", &cost),
+    ]
+}
+
+/// Bold red banner wrapped to `width`, for conditions that must not be missed before signing.
+pub fn warning_banner_lines(text: &str, width: usize) -> Vec<Line<'static>> {
+    let style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    textwrap::wrap(&format!("⚠ {text}"), width.max(1))
+        .into_iter()
+        .map(|seg| Line::from(Span::styled(seg.to_string(), style)))
+        .collect()
+}
+
+/// Two lines for a running `KdfJob`: its label, then an estimated progress bar (or a spinner
+/// without an estimate) with the elapsed time. Used in place of a form's spacer and buttons.
+pub fn kdf_progress_lines<T: Send + 'static>(job: &crate::crypto::kdf_progress::KdfJob<T>) -> [Line<'static>; 2] {
+    const BAR: usize = 30;
+    let bar = match job.fraction() {
+        Some(frac) => {
+            let filled = (frac * BAR as f64).round() as usize;
+            format!("[{}{}] {:>2}%  ", "█".repeat(filled), "░".repeat(BAR - filled), (frac * 100.0) as u32)
+        }
+        None => {
+            const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+            let i = (job.elapsed().as_millis() / 250) as usize;
+            format!("[{}]  ", SPINNER[i % SPINNER.len()])
+        }
+    };
+    [
+        Line::from(Span::styled(job.label.clone(), Style::default().fg(Color::Yellow))),
+        Line::from(vec![
+            Span::styled(bar, Style::default().fg(Color::Cyan)),
+            Span::styled(job.status(), Style::default().fg(Color::DarkGray)),
+        ]),
+    ]
+}
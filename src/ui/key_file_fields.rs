@@ -0,0 +1,55 @@
+//! Private-key fields of a Create form that hold a key loaded from an encrypted key file (see
+//! `screens::load_signing_key`). Such a field shows where its key came from instead of the key,
+//! and the first edit empties it rather than changing a key the operator cannot see.
+
+use ethers_core::utils::keccak256;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use std::collections::HashMap;
+
+use crate::app::LoadedSigningKey;
+use crate::ui::components::{field_line_text, TextField};
+
+#[derive(Default)]
+pub struct KeyFileFields {
+    /// Field index -> keccak256 of the loaded text and where it came from. Only the hash is
+    /// kept, so a field edited or refilled some other way is told apart without a second copy.
+    loaded: HashMap<usize, ([u8; 32], String)>,
+}
+
+impl KeyFileFields {
+    /// The text field for `key`'s field, holding the key.
+    pub fn fill(&mut self, key: &LoadedSigningKey) -> TextField {
+        self.loaded.insert(key.field, (keccak256(key.privkey_hex.as_bytes()), key.source.clone()));
+        TextField::with(&key.privkey_hex)
+    }
+
+    /// Where the key in field `idx` came from, while `tf` still holds it unchanged.
+    pub fn source(&self, idx: usize, tf: &TextField) -> Option<&str> {
+        let (hash, source) = self.loaded.get(&idx)?;
+        (*hash == keccak256(tf.text.as_bytes())).then_some(source.as_str())
+    }
+
+    /// Stop treating field `idx` as loaded (its text is about to be cleared).
+    pub fn forget(&mut self, idx: usize) {
+        self.loaded.remove(&idx);
+    }
+
+    /// The field's line: `field_line_text`, or its source in place of a loaded key.
+    pub fn line(&self, label: &str, idx: usize, tf: &TextField, focused: bool) -> Line<'static> {
+        let Some(source) = self.source(idx, tf) else {
+            return field_line_text(label, tf, focused);
+        };
+        let mut spans = vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("[from {source}]"), Style::default().fg(Color::Green)),
+        ];
+        if focused {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(" ", Style::default().bg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        }
+        Line::from(spans)
+    }
+}
@@ -2,5 +2,10 @@ pub mod layout;
 pub mod components;
 pub mod style;
 pub mod help;
+pub mod scrub;
+pub mod out_dir_prompt;
+pub mod typed_confirm;
+pub mod prefill_summary;
+pub mod key_file_fields;
 
 pub mod common_nav;
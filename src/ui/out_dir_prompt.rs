@@ -0,0 +1,156 @@
+//! Confirmation for an Output Directory that came from a loaded info file. The Create screens
+//! hold one of these while it is open, draw it over themselves and send it keys first, so
+//! a prefill can never move signed output somewhere without the operator seeing where.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::path::{Path, PathBuf};
+
+use crate::app::AppCtx;
+use crate::ui::layout::centered_rect_abs;
+use crate::ui::style::{button_spans, button_spans_disabled};
+use crate::vfs::resolve;
+
+/// What checking the directory found.
+enum DirState {
+    Exists,
+    /// Missing; created on write under this existing directory.
+    Creatable(PathBuf),
+    /// Cannot be used, with the reason.
+    Unusable(String),
+}
+
+pub enum OutDirChoice {
+    /// Use this (absolute, normalized) directory.
+    Use(String),
+    /// Keep the directory the field had.
+    Keep,
+}
+
+pub struct OutDirPrompt {
+    raw: String,
+    resolved: PathBuf,
+    state: DirState,
+    outside_sandbox: bool,
+    current: String,
+    /// 0 = Use, 1 = Keep
+    selected: usize,
+}
+
+impl OutDirPrompt {
+    /// A prompt for `raw` replacing `current`, or `None` when the prefill leaves the
+    /// directory unchanged.
+    pub fn for_prefill(raw: &str, current: &str, ctx: &AppCtx) -> Option<Self> {
+        let raw = raw.trim();
+        if raw == current.trim() {
+            return None;
+        }
+        let resolved = resolve(Path::new(raw));
+        let state = check_dir(raw, ctx);
+        let outside_sandbox = !ctx.sandbox.allows(Path::new(raw));
+        // Keeping the current directory is the default; using the file's takes a deliberate move.
+        Some(Self { raw: raw.to_string(), resolved, state, outside_sandbox, current: current.trim().to_string(), selected: 1 })
+    }
+
+    fn usable(&self) -> bool {
+        !matches!(self.state, DirState::Unusable(_))
+    }
+
+    pub fn on_key(&mut self, k: KeyEvent) -> Option<OutDirChoice> {
+        match k.code {
+            KeyCode::Esc => Some(OutDirChoice::Keep),
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::Char(' ') if self.usable() => {
+                self.selected = 1 - self.selected;
+                None
+            }
+            KeyCode::Enter if self.selected == 0 => Some(OutDirChoice::Use(self.resolved.display().to_string())),
+            KeyCode::Enter => Some(OutDirChoice::Keep),
+            _ => None,
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame<'_>, size: Rect) {
+        let use_label = "Use This Directory";
+        let keep_label = "Keep Current";
+        let current = if self.current.is_empty() { "(empty)" } else { self.current.as_str() };
+        let mut lines: Vec<Line> = vec![
+            Line::from("The loaded file sets the Output Directory to:"),
+            Line::from(format!("  {}", self.raw)),
+            Line::from("which resolves to:"),
+            Line::from(Span::styled(format!("  {}", self.resolved.display()), Style::default().fg(Color::Yellow))),
+            Line::from(""),
+        ];
+        lines.push(match &self.state {
+            DirState::Exists => Line::from("The directory exists."),
+            DirState::Creatable(parent) => {
+                Line::from(format!("It does not exist yet and will be created under {}.", parent.display()))
+            }
+            DirState::Unusable(why) => Line::from(Span::styled(format!("Cannot be used: {why}"), Style::default().fg(Color::Red))),
+        });
+        if self.outside_sandbox {
+            lines.push(Line::from(Span::styled(
+                "It is outside the allowed sandbox directories; writing there will ask again.",
+                Style::default().fg(Color::Red),
+            )));
+        }
+        lines.push(Line::from(format!("Current Output Directory: {current}")));
+
+        let mut btn: Vec<Span> = if self.usable() {
+            button_spans(use_label, self.selected == 0)
+        } else {
+            button_spans_disabled(use_label)
+        };
+        btn.push(Span::raw("   "));
+        btn.extend(button_spans(keep_label, self.selected == 1));
+
+        let content_w = lines.iter().map(|l| l.width()).max().unwrap_or(0).max(4 + use_label.len() + 3 + 4 + keep_label.len());
+        let inner_width = (content_w as u16).max(36);
+        let inner_height = lines.len() as u16 + 2; // lines + spacer + buttons
+        let area = centered_rect_abs(inner_width + 4, inner_height + 3, size);
+        let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(lines.len() as u16), Constraint::Length(1), Constraint::Length(1)])
+            .split(inner);
+
+        f.render_widget(Clear, area);
+        f.render_widget(Block::default().borders(Borders::ALL).title(" Confirm Output Directory "), area);
+        f.render_widget(Paragraph::new(lines), vchunks[0]);
+        f.render_widget(Paragraph::new(Line::from(btn)).alignment(Alignment::Center), vchunks[2]);
+    }
+}
+
+/// Whether `raw` names a directory that exists or can be created: the nearest existing
+/// ancestor must be a directory, and nothing on the way may be a file.
+fn check_dir(raw: &str, ctx: &AppCtx) -> DirState {
+    if raw.is_empty() {
+        return DirState::Unusable("the path is empty".into());
+    }
+    if raw.contains('\0') {
+        return DirState::Unusable("the path contains a NUL byte".into());
+    }
+    let path = Path::new(raw);
+    if ctx.fs.is_dir(path) {
+        return DirState::Exists;
+    }
+    if ctx.fs.exists(path) {
+        return DirState::Unusable("it exists and is not a directory".into());
+    }
+    // "out" has the ancestors "out" and ""; treat "" as the working directory.
+    for ancestor in path.ancestors().skip(1) {
+        let dir = if ancestor.as_os_str().is_empty() { Path::new(".") } else { ancestor };
+        if ctx.fs.is_dir(dir) {
+            return DirState::Creatable(resolve(dir));
+        }
+        if ctx.fs.exists(dir) {
+            return DirState::Unusable(format!("{} exists and is not a directory", dir.display()));
+        }
+    }
+    DirState::Unusable("no parent directory exists".into())
+}
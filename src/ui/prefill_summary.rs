@@ -0,0 +1,112 @@
+//! What a loaded info file just put into a Create form: the fields it set (secrets masked),
+//! the fields left for manual entry, and any keys the form does not use. Shown over the form
+//! once after Load From File and dismissed with any key, so unexpected values get noticed.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    prelude::Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::collections::HashMap;
+
+use crate::app::DelegationPrefill;
+use crate::ui::layout::centered_rect_abs;
+use crate::ui::style::button_spans;
+
+/// Longest value shown; longer ones (public keys) are cut with "…".
+const MAX_VALUE_CHARS: usize = 48;
+
+/// One form field an info file can set.
+pub struct PrefillField {
+    /// Key in the info file, e.g. `DELEGATOR_PRIVKEY`.
+    pub key: &'static str,
+    /// Label of the form field.
+    pub label: &'static str,
+    /// Masked in the summary.
+    pub secret: bool,
+}
+
+pub struct PrefillSummary {
+    lines: Vec<Line<'static>>,
+}
+
+impl PrefillSummary {
+    pub fn new(fields: &[PrefillField], prefill: &DelegationPrefill) -> Self {
+        let map: &HashMap<String, String> = &prefill.map;
+        let heading = |s: &str| Line::from(Span::styled(s.to_string(), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+        let mut lines = Vec::new();
+        if let Some(sha) = &prefill.source_sha256 {
+            lines.push(Line::from(format!("File SHA-256: {}…", &sha[..sha.len().min(16)])));
+        }
+        for include in &prefill.includes {
+            lines.push(Line::from(format!("Included: {}", include.display())));
+        }
+        if prefill.source_sha256.is_some() || !prefill.includes.is_empty() {
+            lines.push(Line::from(""));
+        }
+
+        lines.push(heading("Set from the file:"));
+        let mut any = false;
+        for field in fields {
+            let Some(value) = map.get(field.key) else { continue };
+            any = true;
+            let shown = if field.secret {
+                "•••••••• (hidden)".to_string()
+            } else if value.trim().is_empty() {
+                "(empty)".to_string()
+            } else {
+                shorten(value.trim())
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}: ", field.label), Style::default().fg(Color::Yellow)),
+                Span::raw(shown),
+            ]));
+        }
+        if !any {
+            lines.push(Line::from(Span::styled("  nothing", Style::default().fg(Color::DarkGray))));
+        }
+
+        let manual: Vec<&PrefillField> = fields.iter().filter(|f| !map.contains_key(f.key)).collect();
+        if !manual.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(heading("Left as they were (enter manually):"));
+            lines.extend(manual.iter().map(|f| Line::from(format!("  {}", f.label))));
+        }
+
+        let mut ignored: Vec<&String> = map.keys().filter(|k| !fields.iter().any(|f| f.key == k.as_str())).collect();
+        if !ignored.is_empty() {
+            ignored.sort();
+            lines.push(Line::from(""));
+            lines.push(heading("Ignored (not used by this form):"));
+            lines.extend(ignored.iter().map(|k| Line::from(Span::styled(format!("  {k}"), Style::default().fg(Color::Red)))));
+        }
+        Self { lines }
+    }
+
+    pub fn draw(&self, f: &mut Frame<'_>, size: Rect) {
+        let content_w = self.lines.iter().map(|l| l.width()).max().unwrap_or(0);
+        let inner_width = (content_w as u16).max(36);
+        let inner_height = self.lines.len() as u16 + 2; // lines + spacer + button
+        let area = centered_rect_abs(inner_width + 4, inner_height + 3, size);
+        let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(inner);
+
+        f.render_widget(Clear, area);
+        f.render_widget(Block::default().borders(Borders::ALL).title(" Loaded From File "), area);
+        f.render_widget(Paragraph::new(self.lines.clone()), vchunks[0]);
+        f.render_widget(Paragraph::new(Line::from(button_spans("OK", true))).alignment(Alignment::Center), vchunks[2]);
+    }
+}
+
+fn shorten(value: &str) -> String {
+    if value.chars().count() <= MAX_VALUE_CHARS {
+        return value.to_string();
+    }
+    let head: String = value.chars().take(MAX_VALUE_CHARS - 1).collect();
+    format!("{head}…")
+}
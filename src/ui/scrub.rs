@@ -0,0 +1,35 @@
+//! Wiping secrets off the terminal once a screen that rendered them is gone.
+//!
+//! `CSI 2J` clears the visible (alternate) screen and `CSI 3J` purges the scrollback on
+//! xterm-compatible terminals. Multiplexers keep their own history that no escape
+//! sequence reaches, so we detect those and tell the operator instead of pretending.
+
+use crossterm::{
+    execute,
+    terminal::{Clear, ClearType},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::env;
+use std::io::{self, Stdout};
+
+/// Clear the screen and request a scrollback purge, then force a full redraw. The purge is
+/// best effort: see `scrub_limitation` for the cases we can detect.
+pub fn scrub_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+    execute!(terminal.backend_mut(), Clear(ClearType::All), Clear(ClearType::Purge))?;
+    terminal.clear()
+}
+
+/// Why scrubbing can't be guaranteed in the current terminal, if we can tell.
+pub fn scrub_limitation() -> Option<&'static str> {
+    if env::var_os("TMUX").is_some() {
+        return Some("Running inside tmux: its own history survives scrubbing. Run `tmux clear-history` after secrets were shown.");
+    }
+    if env::var_os("STY").is_some() {
+        return Some("Running inside GNU screen: its own scrollback survives scrubbing. Clear it (C-a C) or close the window after secrets were shown.");
+    }
+    match env::var("TERM").as_deref() {
+        Ok("dumb") | Err(_) => Some("Unknown terminal type: scrollback purge (CSI 3J) may be ignored."),
+        Ok(t) if t.starts_with("screen") => Some("Terminal multiplexer detected: its own history may survive scrubbing."),
+        _ => None,
+    }
+}
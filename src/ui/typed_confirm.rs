@@ -0,0 +1,130 @@
+//! Confirmation for destructive operations: the operator types a short phrase (the key's
+//! nickname, or a word such as INVALIDATE) before the action button unlocks, so a stray Enter
+//! cannot run it. Screens hold one of these while it is open, draw it over themselves and send
+//! it keys first, like `out_dir_prompt.rs`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    prelude::Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::ui::components::{field_line_text, TextField};
+use crate::ui::layout::centered_rect_abs;
+use crate::ui::style::{button_spans, button_spans_disabled};
+
+pub enum TypedConfirmChoice {
+    /// The phrase was typed and the action chosen.
+    Confirmed,
+    Cancelled,
+}
+
+pub struct TypedConfirm {
+    title: String,
+    lines: Vec<String>,
+    phrase: String,
+    /// Label of the button that runs the action.
+    action: String,
+    typed: TextField,
+    /// 0 = phrase, 1 = action, 2 = Cancel
+    selected: usize,
+}
+
+impl TypedConfirm {
+    /// Ask to type `phrase` before `action` runs; `lines` say what will happen.
+    pub fn new(title: &str, lines: Vec<String>, phrase: &str, action: &str) -> Self {
+        Self {
+            title: format!(" {title} "),
+            lines,
+            phrase: phrase.to_string(),
+            action: action.to_string(),
+            typed: TextField::default(),
+            selected: 0,
+        }
+    }
+
+    /// What has been typed so far.
+    pub fn typed(&self) -> &str {
+        &self.typed.text
+    }
+
+    /// Exact match, ignoring surrounding spaces.
+    fn matches(&self) -> bool {
+        self.typed.text.trim() == self.phrase
+    }
+
+    pub fn on_key(&mut self, k: KeyEvent) -> Option<TypedConfirmChoice> {
+        match k.code {
+            KeyCode::Esc => return Some(TypedConfirmChoice::Cancelled),
+            KeyCode::Down | KeyCode::Tab => self.selected = (self.selected + 1) % 3,
+            KeyCode::Up => self.selected = (self.selected + 2) % 3,
+            KeyCode::Left if self.selected == 2 => self.selected = 1,
+            KeyCode::Right if self.selected == 1 => self.selected = 2,
+            KeyCode::Enter if self.selected == 0 => self.selected = if self.matches() { 1 } else { 2 },
+            KeyCode::Enter if self.selected == 1 && self.matches() => return Some(TypedConfirmChoice::Confirmed),
+            KeyCode::Enter if self.selected == 2 => return Some(TypedConfirmChoice::Cancelled),
+
+            // Text editing on the phrase field
+            KeyCode::Left if self.selected == 0 => self.typed.move_left(),
+            KeyCode::Right if self.selected == 0 => self.typed.move_right(),
+            KeyCode::Home if self.selected == 0 => self.typed.home(),
+            KeyCode::End if self.selected == 0 => self.typed.end(),
+            KeyCode::Backspace if self.selected == 0 => self.typed.backspace(),
+            KeyCode::Delete if self.selected == 0 => self.typed.delete(),
+            KeyCode::Char(c) if self.selected == 0 && !k.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.typed.insert_char(c)
+            }
+            _ => {}
+        }
+        None
+    }
+
+    pub fn draw(&self, f: &mut Frame<'_>, size: Rect) {
+        let cancel_label = "Cancel";
+        let mut lines: Vec<Line> = self.lines.iter().map(|l| Line::from(l.clone())).collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Type "),
+            Span::styled(self.phrase.clone(), Style::default().fg(Color::Red)),
+            Span::raw(" to confirm."),
+        ]));
+        lines.push(field_line_text("Confirm", &self.typed, self.selected == 0));
+
+        let mut btn: Vec<Span> = if self.matches() {
+            button_spans(self.action.clone(), self.selected == 1)
+        } else {
+            button_spans_disabled(self.action.clone())
+        };
+        btn.push(Span::raw("   "));
+        btn.extend(button_spans(cancel_label, self.selected == 2));
+
+        // Room for the phrase plus the cursor on the field line
+        let field_w = "Confirm: ".len() + self.phrase.chars().count().max(self.typed.text.chars().count()) + 1;
+        let content_w = lines
+            .iter()
+            .map(|l| l.width())
+            .max()
+            .unwrap_or(0)
+            .max(field_w)
+            .max(4 + self.action.len() + 3 + 4 + cancel_label.len());
+        let inner_width = (content_w as u16).max(36);
+        let inner_height = lines.len() as u16 + 2; // lines + spacer + buttons
+        let area = centered_rect_abs(inner_width + 4, inner_height + 3, size);
+        let inner = area.inner(&Margin { horizontal: 2, vertical: 1 });
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(lines.len() as u16), Constraint::Length(1), Constraint::Length(1)])
+            .split(inner);
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Red)).title(self.title.clone()),
+            area,
+        );
+        f.render_widget(Paragraph::new(lines), vchunks[0]);
+        f.render_widget(Paragraph::new(Line::from(btn)).alignment(Alignment::Center), vchunks[2]);
+    }
+}
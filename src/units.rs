@@ -0,0 +1,171 @@
+//! Typed transaction parameters. Form fields and batch files are parsed into these once, where
+//! they enter the tool (gas fields through `validation.rs`); `BatchOpts`, `Item` and the signer
+//! only ever see checked values.
+
+use anyhow::{anyhow, bail, Result};
+use ethers_core::types::U256;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
+use uuid::Uuid;
+
+use crate::util::{hex_to_bytes, parse_u256_any};
+
+/// Gas units a transaction may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GasLimit(pub U256);
+
+/// An amount in wei (fees per gas, costs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Wei(pub U256);
+
+/// Transaction nonce of the sending account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(transparent)]
+pub struct Nonce(pub u64);
+
+/// Start or end of a delegation or revocation window, in Unix seconds; 0 leaves that side open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnixTime(pub u64);
+
+/// The `bytes16` nonce inside a signed payload; `None` has the signer draw a random UUID.
+/// A fixed one makes the payload hash and signed transaction reproducible from the inputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadNonce(pub Option<[u8; 16]>);
+
+impl GasLimit {
+    /// Most the transaction can cost the sender at `max_fee_per_gas`.
+    pub fn max_cost(self, max_fee_per_gas: Wei) -> Wei {
+        Wei(self.0.saturating_mul(max_fee_per_gas.0))
+    }
+}
+
+/// Decimal or `0x` hex, surrounding whitespace ignored.
+impl FromStr for GasLimit {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(GasLimit(parse_u256_any(s.trim())?))
+    }
+}
+
+impl FromStr for Wei {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Wei(parse_u256_any(s.trim())?))
+    }
+}
+
+impl FromStr for Nonce {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Nonce(s.trim().parse()?))
+    }
+}
+
+/// Empty (open), Unix seconds, or a date and time in UTC unless an offset is given:
+/// `2026-03-01`, `2026-03-01 14:30[:00]`, `2026-03-01T14:30:00Z`, `2026-03-01T14:30:00+02:00`.
+impl FromStr for UnixTime {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(UnixTime(0));
+        }
+        if s.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(UnixTime(s.parse()?));
+        }
+        parse_datetime(s).ok_or_else(|| {
+            anyhow!("\"{s}\" is not a Unix timestamp or a date (YYYY-MM-DD [HH:MM[:SS]], optional Z or +HH:MM)")
+        })?
+    }
+}
+
+fn parse_datetime(s: &str) -> Option<Result<UnixTime>> {
+    let (date, rest) = match s.split_once(['T', ' ']) {
+        Some((d, r)) => (d, r.trim()),
+        None => (s, ""),
+    };
+    let mut d = date.splitn(3, '-').map(str::parse::<i32>);
+    let (y, mo, day) = (d.next()?.ok()?, d.next()?.ok()?, d.next()?.ok()?);
+    let date = Date::from_calendar_date(y, Month::try_from(u8::try_from(mo).ok()?).ok()?, u8::try_from(day).ok()?).ok()?;
+
+    // Clock, then an optional zone: Z, UTC, or a signed offset
+    let (clock, zone) = match rest.find(['Z', 'z', '+', '-', 'U', 'u']) {
+        Some(i) => (rest[..i].trim(), rest[i..].trim()),
+        None => (rest, ""),
+    };
+    let time = if clock.is_empty() {
+        Time::MIDNIGHT
+    } else {
+        let mut c = clock.splitn(3, ':').map(str::parse::<u8>);
+        let (h, mi) = (c.next()?.ok()?, c.next()?.ok()?);
+        let sec = match c.next() {
+            Some(v) => v.ok()?,
+            None => 0,
+        };
+        Time::from_hms(h, mi, sec).ok()?
+    };
+    let offset = match zone {
+        "" => UtcOffset::UTC,
+        z if z.eq_ignore_ascii_case("z") || z.eq_ignore_ascii_case("utc") => UtcOffset::UTC,
+        z => {
+            let sign: i8 = if z.starts_with('-') { -1 } else { 1 };
+            let (h, m) = z[1..].split_once(':').unwrap_or((&z[1..], "0"));
+            UtcOffset::from_hms(sign * h.parse::<i8>().ok()?, sign * m.parse::<i8>().ok()?, 0).ok()?
+        }
+    };
+    let secs = PrimitiveDateTime::new(date, time).assume_offset(offset).unix_timestamp();
+    Some(if secs <= 0 {
+        Err(anyhow!("{s} is not after 1970-01-01"))
+    } else {
+        Ok(UnixTime(secs as u64))
+    })
+}
+
+impl PayloadNonce {
+    /// As `0x` hex for `Item`'s `TYPE_*_NONCE16`, `None` when random.
+    pub fn hex(self) -> Option<String> {
+        self.0.map(|b| format!("0x{}", hex::encode(b)))
+    }
+}
+
+/// Empty (random), 16 bytes as `0x` hex, or a UUID (`xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`).
+impl FromStr for PayloadNonce {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(PayloadNonce(None));
+        }
+        let bytes = if s.contains('-') {
+            Uuid::parse_str(s).map_err(|e| anyhow!("\"{s}\" is not a UUID: {e}"))?.as_bytes().to_vec()
+        } else {
+            hex_to_bytes(s)?
+        };
+        let bytes: [u8; 16] = bytes
+            .try_into()
+            .map_err(|b: Vec<u8>| anyhow!("a payload nonce is 16 bytes, got {}", b.len()))?;
+        Ok(PayloadNonce(Some(bytes)))
+    }
+}
+
+/// The window `[start, end]` of a delegation or revocation; an end, when set, must follow the start.
+pub fn check_window(what: &str, start: UnixTime, end: UnixTime) -> Result<()> {
+    if end.0 != 0 && end <= start {
+        bail!("{what} End must be after {what} Start.");
+    }
+    Ok(())
+}
+
+impl fmt::Display for GasLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.0) }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.0) }
+}
+
+impl fmt::Display for Nonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.0) }
+}
@@ -0,0 +1,118 @@
+//! Local usage statistics, for planning ceremonies: per calendar month (UTC), the transactions
+//! written per contract function, the batch runs and their sizes, and how often each tool was
+//! opened from the menus.
+//!
+//! Off unless `settings.usage_stats` is on. Nothing is ever sent anywhere: the counters live at
+//! `Defaults::USAGE_STATS_FILE`, encrypted at rest with the settings while a master password is
+//! set, and hold no keys, addresses or file names. Like key usage, recording is best effort and
+//! never undoes what was recorded for.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::commands::batch::BatchRun;
+use crate::defaults::Defaults;
+use crate::migrate::to_versioned_json;
+use crate::profile::{read_protected, write_protected, ProfileKey};
+
+/// Counters of one month.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonthStats {
+    /// Transactions written, by contract function (`createDelegationEvent`, ...).
+    pub signed: BTreeMap<String, u64>,
+    /// Batch runs that wrote at least one transaction, their transactions in total and the largest.
+    pub batch_runs: u64,
+    pub batch_items: u64,
+    pub largest_batch: u64,
+    /// Tools opened from the menus, by menu title.
+    pub opened: BTreeMap<String, u64>,
+}
+
+impl MonthStats {
+    pub fn signed_total(&self) -> u64 {
+        self.signed.values().sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    /// Keyed by `YYYY-MM`.
+    pub months: BTreeMap<String, MonthStats>,
+}
+
+/// What one signing wrote: a Create screen's single transaction or a batch run's.
+pub enum Written<'a> {
+    One { func: &'a str, from: &'a str },
+    Run(&'a BatchRun),
+}
+
+impl Written<'_> {
+    /// Sender of each transaction, for the key usage store.
+    pub fn senders(&self) -> Vec<&str> {
+        match self {
+            Written::One { from, .. } => vec![*from],
+            Written::Run(run) => run.signed_senders().collect(),
+        }
+    }
+
+    fn functions(&self) -> Vec<&str> {
+        match self {
+            Written::One { func, .. } => vec![*func],
+            Written::Run(run) => run.signed_functions().collect(),
+        }
+    }
+}
+
+fn this_month() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    format!("{:04}-{:02}", now.year(), u8::from(now.month()))
+}
+
+/// Load the statistics, empty when missing or unreadable.
+pub fn load_usage_stats(key: Option<&ProfileKey>) -> UsageStats {
+    let Ok(Some(bytes)) = read_protected(key, Path::new(Defaults::USAGE_STATS_FILE)) else {
+        return UsageStats::default();
+    };
+    serde_json::from_slice(&bytes).unwrap_or_default()
+}
+
+pub fn save_usage_stats(stats: &UsageStats, key: Option<&ProfileKey>) -> Result<()> {
+    let bytes = to_versioned_json(Defaults::USAGE_STATS_FILE, stats)?;
+    write_protected(key, Path::new(Defaults::USAGE_STATS_FILE), &bytes)
+        .with_context(|| format!("saving usage statistics to {}", Defaults::USAGE_STATS_FILE))
+}
+
+/// Apply `f` to this month's counters and save.
+fn update(key: Option<&ProfileKey>, f: impl FnOnce(&mut MonthStats)) -> Result<()> {
+    let mut stats = load_usage_stats(key);
+    f(stats.months.entry(this_month()).or_default());
+    save_usage_stats(&stats, key)
+}
+
+/// Count the transactions in `written`, and its size when it was a batch run.
+pub fn record_written(key: Option<&ProfileKey>, written: &Written<'_>) -> Result<()> {
+    let functions = written.functions();
+    if functions.is_empty() {
+        return Ok(());
+    }
+    update(key, |m| {
+        for func in &functions {
+            *m.signed.entry(func.to_string()).or_default() += 1;
+        }
+        if let Written::Run(_) = written {
+            let n = functions.len() as u64;
+            m.batch_runs += 1;
+            m.batch_items += n;
+            m.largest_batch = m.largest_batch.max(n);
+        }
+    })
+}
+
+/// Count one opening of the tool titled `title`.
+pub fn record_opened(key: Option<&ProfileKey>, title: &str) -> Result<()> {
+    update(key, |m| *m.opened.entry(title.to_string()).or_default() += 1)
+}
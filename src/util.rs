@@ -1,6 +1,13 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use ethers_core::types::{Address, U256};
+use sha2::Digest;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::process::fingerprint_from_privkey_input;
+use crate::signing::KeyRef;
+use crate::vfs::Fs;
+pub use inkan_management_utility::hexstr::{bytes_to_0x, hex_to_bytes};
 
 pub fn parse_u256_any(s: &str) -> Result<U256> {
     Ok(if let Some(x) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
@@ -14,16 +21,9 @@ pub fn parse_addr(s: &str) -> Result<Address> {
     Ok(s.parse::<Address>()?)
 }
 
-pub fn hex_to_bytes(s: &str) -> Result<Vec<u8>> {
-    // Accept both lowercase and uppercase 0x prefix
-    let t = s.strip_prefix("0x")
-        .or_else(|| s.strip_prefix("0X"))
-        .unwrap_or(s);
-    Ok(hex::decode(t)?)
-}
-
-pub fn bytes_to_0x(v: &[u8]) -> String {
-    format!("0x{}", hex::encode(v))
+/// Lowercase hex SHA-256 of `bytes` (used to fingerprint input files).
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(sha2::Sha256::digest(bytes))
 }
 
 pub fn u256_to_be32(x: U256) -> Vec<u8> {
@@ -40,56 +40,154 @@ pub fn expect_bytes<'a>(tok: &'a ethers_core::abi::Token) -> Result<&'a Vec<u8>>
     }
 }
 
-/// Internal: parse dotenv-style K=V lines into a map.
-/// - Ignores blank lines and lines starting with `#`
-/// - Splits on the first '='
-/// - Trims whitespace
-/// - Supports surrounding single or double quotes
-/// - Last duplicate key wins
-fn parse_kv_env(contents: &str) -> HashMap<String, String> {
-    let mut out = HashMap::new();
-
-    for line in contents.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
+/// Key whose value names another env file to read in place (shared fragments such as gas settings).
+pub const ENV_INCLUDE_KEY: &str = "INCLUDE";
+/// Deepest chain of nested includes accepted.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// One info file after includes: the merged values and a fingerprint of everything read.
+#[derive(Debug, Clone, Default)]
+pub struct EnvFile {
+    pub map: HashMap<String, String>,
+    /// SHA-256 (hex) of the file's bytes followed by each included file's, in the order read.
+    pub sha256: String,
+    /// Included files, in the order read.
+    pub includes: Vec<PathBuf>,
+}
 
-        let Some(eq) = trimmed.find('=') else { continue; };
-        let (k, vraw) = trimmed.split_at(eq);
-        let key = k.trim().to_string();
+/// A private key carried by an info file, named without its value.
+pub struct EnvSecret {
+    /// Key in the file, e.g. `DELEGATOR_PRIVKEY`.
+    pub key: String,
+    /// Key-file fingerprint of the key, or `None` when the value is not a valid key.
+    pub fingerprint: Option<String>,
+}
 
-        let mut val = vraw[1..].trim().to_string();
+/// The local private keys among an info file's values: non-empty `*_PRIVKEY` entries that are
+/// not `hardware:` or `remote-hsm:` references. Sorted by key.
+pub fn env_secrets(map: &HashMap<String, String>) -> Vec<EnvSecret> {
+    let mut out: Vec<EnvSecret> = map
+        .iter()
+        .filter(|(k, v)| k.ends_with("PRIVKEY") && !v.trim().is_empty())
+        .filter_map(|(k, v)| match KeyRef::parse(v) {
+            KeyRef::Local(s) => Some(EnvSecret { key: k.clone(), fingerprint: fingerprint_from_privkey_input(s) }),
+            KeyRef::Hardware(_) | KeyRef::RemoteHsm(_) => None,
+        })
+        .collect();
+    out.sort_by(|a, b| a.key.cmp(&b.key));
+    out
+}
 
-        // Remove matching quotes
-        if (val.starts_with('"') && val.ends_with('"') && val.len() >= 2)
-            || (val.starts_with('\'') && val.ends_with('\'') && val.len() >= 2)
-        {
-            val = val[1..val.len() - 1].to_string();
+/// One `KEY=value` line, or `None` for blank, comment and malformed lines.
+/// - `#` starts a comment at the start of a line, or after whitespace in an unquoted value
+/// - `"…"` keeps spaces and `#`, with `\"`, `\\`, `\n` and `\t` escapes
+/// - `'…'` keeps everything literally
+/// - text after a closing quote is ignored
+fn parse_env_line(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    let (k, vraw) = trimmed.split_once('=')?;
+    let key = k.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let vraw = vraw.trim_start();
+
+    let val = if let Some(rest) = vraw.strip_prefix('"') {
+        let mut val = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => val.push('\n'),
+                    Some('t') => val.push('\t'),
+                    Some(other) => val.push(other),
+                    None => val.push('\\'),
+                },
+                c => val.push(c),
+            }
         }
+        val
+    } else if let Some(rest) = vraw.strip_prefix('\'') {
+        rest.split('\'').next().unwrap_or("").to_string()
+    } else {
+        // Unquoted: a `#` preceded by whitespace starts a comment.
+        let end = vraw
+            .char_indices()
+            .find(|&(i, c)| c == '#' && vraw[..i].ends_with(char::is_whitespace))
+            .map_or(vraw.len(), |(i, _)| i);
+        vraw[..end].trim_end().to_string()
+    };
+    Some((key.to_string(), val))
+}
 
-        out.insert(key, val);
-    }
+/// Internal: read a dotenv-style info file through `fs` (see `parse_env_line` for the line
+/// rules; the last duplicate key wins), following `INCLUDE=path` lines in place:
+/// the included values apply at that line, so later lines in the including file override
+/// them. Include paths are relative to the including file. A file that includes itself,
+/// directly or through others, is an error.
+fn load_kv_env(fs: &dyn Fs, path: &Path) -> Result<EnvFile> {
+    let mut out = EnvFile::default();
+    let mut hasher = sha2::Sha256::new();
+    load_kv_env_into(fs, path, &mut Vec::new(), &mut hasher, &mut out)?;
+    out.sha256 = hex::encode(hasher.finalize());
+    Ok(out)
+}
 
-    out
+fn load_kv_env_into(
+    fs: &dyn Fs,
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+    hasher: &mut sha2::Sha256,
+    out: &mut EnvFile,
+) -> Result<()> {
+    let resolved = crate::vfs::resolve(path);
+    if stack.contains(&resolved) {
+        // The error context above this names each include on the way here.
+        bail!("include cycle: {} is already being read", resolved.display());
+    }
+    if stack.len() >= MAX_INCLUDE_DEPTH {
+        bail!("includes nested deeper than {MAX_INCLUDE_DEPTH} at {}", path.display());
+    }
+    let contents = fs.read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    hasher.update(contents.as_bytes());
+    if !stack.is_empty() {
+        out.includes.push(path.to_path_buf());
+    }
+    stack.push(resolved);
+    for (n, line) in contents.lines().enumerate() {
+        let Some((key, val)) = parse_env_line(line) else { continue };
+        if key == ENV_INCLUDE_KEY {
+            let base = path.parent().unwrap_or(Path::new(""));
+            load_kv_env_into(fs, &base.join(&val), stack, hasher, out)
+                .with_context(|| format!("{}:{}: {ENV_INCLUDE_KEY}={val}", path.display(), n + 1))?;
+        } else {
+            out.map.insert(key, val);
+        }
+    }
+    stack.pop();
+    Ok(())
 }
 
 /// Dotenv-style parser for delegation info files (backwards compatible).
-pub fn parse_delegation_env(contents: &str) -> HashMap<String, String> {
-    parse_kv_env(contents)
+pub fn parse_delegation_env(fs: &dyn Fs, path: &Path) -> Result<EnvFile> {
+    load_kv_env(fs, path)
 }
 
 /// Dotenv-style parser for revocation info files (same rules as delegation).
-pub fn parse_revocation_env(contents: &str) -> HashMap<String, String> {
-    parse_kv_env(contents)
+pub fn parse_revocation_env(fs: &dyn Fs, path: &Path) -> Result<EnvFile> {
+    load_kv_env(fs, path)
 }
 
 /// Dotenv-style parser for re-delegation info files (same rules as delegation).
-pub fn parse_redelegation_env(contents: &str) -> HashMap<String, String> {
-    parse_kv_env(contents)
+pub fn parse_redelegation_env(fs: &dyn Fs, path: &Path) -> Result<EnvFile> {
+    load_kv_env(fs, path)
 }
 
 /// Dotenv-style parser for permanent invalidation info files (same rules as delegation).
-pub fn parse_permanent_invalidation_env(contents: &str) -> HashMap<String, String> {
-    parse_kv_env(contents)
+pub fn parse_permanent_invalidation_env(fs: &dyn Fs, path: &Path) -> Result<EnvFile> {
+    load_kv_env(fs, path)
 }
@@ -0,0 +1,52 @@
+//! Gas and fee policy for every form that signs: one check, bounded by the caps of the active
+//! settings (`Settings::gas_caps`), so a policy change lands in one place.
+
+use anyhow::{bail, Context, Result};
+
+use crate::settings::GasCaps;
+use crate::units::{GasLimit, Wei};
+
+/// The gas fields of a form, checked against `caps`: a gas limit and max fee above zero, and a
+/// priority fee no higher than the max fee.
+pub fn validate_gas_fields(
+    caps: &GasCaps,
+    gas_limit: &str,
+    max_fee_per_gas: &str,
+    max_priority_fee_per_gas: &str,
+) -> Result<(GasLimit, Wei, Wei)> {
+    let max_gas: GasLimit = caps.gas_limit().parse().context("the gas limit cap in the settings must be an integer")?;
+    let gas: GasLimit = gas_limit.parse().context("Gas limit must be an integer")?;
+    if gas.0.is_zero() {
+        bail!("Gas limit must be greater than zero.");
+    }
+    if gas > max_gas {
+        bail!("Gas limit {gas} exceeds the maximum allowed {max_gas}.");
+    }
+
+    let max_fee_cap: Wei = caps
+        .max_fee_per_gas()
+        .parse()
+        .context("the max fee per gas cap in the settings must be an integer (wei)")?;
+    let max_fee: Wei = max_fee_per_gas.parse().context("Maximum Fee Per Gas must be an integer (wei)")?;
+    if max_fee.0.is_zero() {
+        bail!("Maximum Fee Per Gas must be greater than zero.");
+    }
+    if max_fee > max_fee_cap {
+        bail!("Maximum Fee Per Gas {max_fee} exceeds the allowed maximum {max_fee_cap} wei.");
+    }
+
+    let prio_cap: Wei = caps
+        .max_priority_fee_per_gas()
+        .parse()
+        .context("the max priority fee per gas cap in the settings must be an integer (wei)")?;
+    let prio: Wei = max_priority_fee_per_gas.parse().context("Maximum Priority Fee Per Gas must be an integer (wei)")?;
+    // The priority fee can be zero, but not above its cap or the max fee
+    if prio > prio_cap {
+        bail!("Maximum Priority Fee Per Gas {prio} exceeds the allowed maximum {prio_cap} wei.");
+    }
+    if prio > max_fee {
+        bail!("Maximum Priority Fee Per Gas cannot exceed Maximum Fee Per Gas.");
+    }
+
+    Ok((gas, max_fee, prio))
+}
@@ -0,0 +1,427 @@
+//! "Verify Signed File": a read-only acceptance check for signed transaction files received
+//! from another organization. Nothing here can sign or rewrite the file; the only output is a
+//! report, which the screen may save next to the file for the reviewer's records.
+//!
+//! Every check works from the signed transaction itself (decoded and sender-recovered), not
+//! from the informational `decodedTx` block, which is only compared against it. Per entry:
+//! the transaction signature, the calldata, the (chain ID, contract) pair against the local
+//! network registry, the calldata lint, each payload signature against its public key under
+//! the locally configured scheme and digest mode, the sender and the validity windows.
+//!
+//! A `Fail` anywhere rejects the file; `Warn` findings are listed for the reviewer but do not.
+
+use anyhow::{Context, Result};
+use ethers_core::utils::to_checksum;
+use std::path::{Path, PathBuf};
+
+use crate::abi::load_abi;
+use crate::display::DisplaySettings;
+use crate::decoder::{decode_signed_tx, PayloadSig};
+use crate::lint::lint_calldata;
+use inkan_management_utility::payload::Payload;
+use crate::scheme::PayloadScheme;
+use crate::settings::{PayloadDigest, Settings};
+use crate::signing::{decode_signed_tx_and_recover, signed_tx_has_high_s, HIGH_S_TX_WARNING};
+use crate::types::{
+    DecodedOne, DecodedTxOut, DelegationDecodedOrdered, InvalidationDecodedOrdered,
+    RevocationDecodedOrdered, SIGNED_FILE_FORMAT_VERSION,
+};
+use crate::util::{hex_to_bytes, sha256_hex};
+use crate::vfs::Fs;
+
+/// Ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    pub fn tag(self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Warn => "WARN",
+            Status::Fail => "FAIL",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub status: Status,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EntryReport {
+    /// Position in the file, counting from 1.
+    pub index: usize,
+    /// ABI function, or `"?"` when the calldata does not decode.
+    pub func_name: String,
+    /// Never empty: `verify_entry` records a finding before every return.
+    pub findings: Vec<Finding>,
+}
+
+impl EntryReport {
+    /// The entry's own verdict: its worst finding.
+    pub fn status(&self) -> Status {
+        self.findings
+            .iter()
+            .map(|f| f.status)
+            .max()
+            .expect("verify_entry records at least one finding per entry")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    pub path: PathBuf,
+    pub sha256: String,
+    /// RFC 3339, UTC.
+    pub checked_at: String,
+    pub file_findings: Vec<Finding>,
+    pub entries: Vec<EntryReport>,
+}
+
+impl VerifyReport {
+    fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.file_findings.iter().chain(self.entries.iter().flat_map(|e| e.findings.iter()))
+    }
+
+    fn count(&self, status: Status) -> usize {
+        self.findings().filter(|f| f.status == status).count()
+    }
+
+    pub fn accepted(&self) -> bool {
+        self.count(Status::Fail) == 0
+    }
+
+    /// "ACCEPT …" / "REJECT …" with the entry and finding counts.
+    pub fn verdict(&self) -> String {
+        let warns = self.count(Status::Warn);
+        if self.accepted() {
+            format!("ACCEPT ({} transaction(s), {warns} warning(s))", self.entries.len())
+        } else {
+            format!("REJECT ({} failed check(s), {warns} warning(s))", self.count(Status::Fail))
+        }
+    }
+
+    /// The full report, one line per finding; also the saved report's content.
+    pub fn lines(&self) -> Vec<String> {
+        let mut out = vec![
+            "Inkan signed file verification report".to_string(),
+            format!("File: {}", self.path.display()),
+            format!("SHA-256: {}", self.sha256),
+            format!("Checked at: {}", self.checked_at),
+            format!("Verdict: {}", self.verdict()),
+            format!(
+                "Entries: {} pass, {} warn, {} fail",
+                self.entries.iter().filter(|e| e.status() == Status::Pass).count(),
+                self.entries.iter().filter(|e| e.status() == Status::Warn).count(),
+                self.entries.iter().filter(|e| e.status() == Status::Fail).count(),
+            ),
+            String::new(),
+            "File:".to_string(),
+        ];
+        let finding = |f: &Finding| format!("  [{}] {}", f.status.tag(), f.text);
+        out.extend(self.file_findings.iter().map(finding));
+        for e in &self.entries {
+            out.push(String::new());
+            out.push(format!("Entry {}: {} [{}]", e.index, e.func_name, e.status().tag()));
+            out.extend(e.findings.iter().map(finding));
+        }
+        out
+    }
+
+    /// `<file stem>.verification.txt` beside the verified file.
+    pub fn default_report_path(&self) -> PathBuf {
+        let stem = self.path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        self.path.with_file_name(format!("{stem}.verification.txt"))
+    }
+}
+
+/// Verify every transaction in the signed file at `path` against local `settings`.
+/// Fails only when the file cannot be read or is not JSON; everything else is a finding.
+pub fn verify_signed_file(fs: &dyn Fs, path: &Path, settings: &Settings) -> Result<VerifyReport> {
+    let bytes = fs.read(path).with_context(|| format!("reading {}", path.display()))?;
+    let json: serde_json::Value = serde_json::from_slice(&bytes)
+        .with_context(|| format!("{} is not a signed transaction file (JSON)", path.display()))?;
+
+    let mut file_findings = Vec::new();
+    // Versioned envelope or legacy bare array
+    let entries: &[serde_json::Value] = match json.get("entries") {
+        Some(entries) => {
+            match json["formatVersion"].as_u64() {
+                Some(v) if v <= SIGNED_FILE_FORMAT_VERSION as u64 => {
+                    push(&mut file_findings, Status::Pass, format!("Signed file format version {v}"))
+                }
+                Some(v) => push(&mut file_findings, Status::Warn, format!(
+                    "Signed file format version {v} is newer than this tool ({SIGNED_FILE_FORMAT_VERSION}); unknown fields are ignored"
+                )),
+                None => push(&mut file_findings, Status::Warn, "Envelope has no formatVersion".into()),
+            }
+            entries.as_array().map(Vec::as_slice).unwrap_or_default()
+        }
+        None => {
+            push(&mut file_findings, Status::Pass, "Legacy signed file (bare array, format version 1)".into());
+            json.as_array().map(Vec::as_slice).unwrap_or_default()
+        }
+    };
+    if entries.is_empty() {
+        push(&mut file_findings, Status::Fail, "File holds no transactions".into());
+    }
+
+    let abi = load_abi()?;
+    let now = time::OffsetDateTime::now_utc();
+    let entries = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| verify_entry(i + 1, entry, &abi, settings, now.unix_timestamp()))
+        .collect();
+
+    Ok(VerifyReport {
+        path: crate::vfs::resolve(path),
+        sha256: sha256_hex(&bytes),
+        checked_at: now.format(&time::format_description::well_known::Rfc3339).unwrap_or_default(),
+        file_findings,
+        entries,
+    })
+}
+
+fn push(out: &mut Vec<Finding>, status: Status, text: String) {
+    out.push(Finding { status, text });
+}
+
+fn verify_entry(
+    index: usize,
+    entry: &serde_json::Value,
+    abi: &ethers_core::abi::Abi,
+    settings: &Settings,
+    now: i64,
+) -> EntryReport {
+    let mut report = EntryReport { index, func_name: "?".into(), findings: Vec::new() };
+    let out = &mut report.findings;
+
+    let Some(signed_tx) = entry["signedTx"].as_str() else {
+        push(out, Status::Fail, "Entry has no signedTx".into());
+        return report;
+    };
+    let (chain_id, nonce, prio, fee, gas, to, value, _data, from) = match decode_signed_tx_and_recover(signed_tx) {
+        Ok(t) => t,
+        Err(e) => {
+            push(out, Status::Fail, format!("signedTx does not decode: {e:#}"));
+            return report;
+        }
+    };
+    let to_hex = format!("{to:?}");
+    push(out, Status::Pass, format!(
+        "Transaction signature valid: sender {}, nonce {nonce}, chain ID {chain_id}",
+        to_checksum(&from, None)
+    ));
+    if signed_tx_has_high_s(signed_tx) {
+        push(out, Status::Warn, HIGH_S_TX_WARNING.into());
+    }
+    let display = &settings.display;
+    push(out, Status::Pass, format!(
+        "Gas limit {}, max fee per gas {}, max priority fee per gas {}",
+        display.integer(gas), display.fee(fee), display.fee(prio)
+    ));
+
+    let decoded = decode_signed_tx(signed_tx, abi);
+    let tx = match decoded {
+        Ok(tx) => tx,
+        Err(e) => {
+            push(out, Status::Fail, format!("Calldata is not an Inkan call: {e:#}"));
+            return report;
+        }
+    };
+    report.func_name = tx.funcName.clone();
+
+    match settings.networks.find(chain_id, &to_hex) {
+        Some(n) => push(out, Status::Pass, format!(
+            "Chain ID {chain_id} with contract {} is registered network \"{}\"",
+            to_checksum(&to, None), n.name
+        )),
+        None => push(out, Status::Fail, settings.networks.unknown_warning(chain_id, &to_hex).unwrap_or_default()),
+    }
+    if !value.is_zero() {
+        push(out, Status::Warn, format!("Transaction transfers {} to the contract", settings.display.amount(value)));
+    }
+
+    let issues = lint_calldata(&tx);
+    if issues.is_empty() {
+        push(out, Status::Pass, "Calldata checks passed".into());
+    }
+    for issue in issues {
+        push(out, Status::Fail, format!("Calldata: {issue}"));
+    }
+
+    let digest = settings.payload_signing.digest_for(&to_hex);
+    let scheme = match settings.payload_signing.scheme_for(&to_hex) {
+        Ok(scheme) => scheme,
+        Err(e) => {
+            push(out, Status::Fail, format!("Payload signatures cannot be checked: {e:#}"));
+            compare_decoded_block(out, entry.get("decodedTx"), &tx);
+            return report;
+        }
+    };
+    let owner = match (&tx.decodedData, &tx.decodedDataTypeA, &tx.decodedDataTypeB) {
+        (Some(DecodedOne::Delegation(d)), _, _) => {
+            check_delegation(out, "", d, scheme, digest, now, &settings.display);
+            Some(("delegator", d.delegatorPubkey.clone()))
+        }
+        (Some(DecodedOne::Revocation(r)), _, _) => {
+            check_revocation(out, "", r, scheme, digest, now, &settings.display);
+            Some(("revoker", r.revokerPubkey.clone()))
+        }
+        (Some(DecodedOne::Invalidation(p)), _, _) => {
+            check_invalidation(out, p, scheme, digest);
+            Some(("invalidated key", p.invalidatedPubkey.clone()))
+        }
+        (None, Some(a), Some(b)) => {
+            check_revocation(out, "Revocation: ", b, scheme, digest, now, &settings.display);
+            check_delegation(out, "Delegation: ", a, scheme, digest, now, &settings.display);
+            Some(("revoker", b.revokerPubkey.clone()))
+        }
+        _ => None,
+    };
+
+    // The tool signs with the owner key; another sender means a relayer or a different tool.
+    if let Some((role, pubkey)) = owner {
+        match hex_to_bytes(&pubkey).map(|b| scheme.account_address(&b)) {
+            Ok(None) => {}
+            Ok(Some(addr)) if addr == from => push(out, Status::Pass, format!("Sender is the {role}'s address")),
+            _ => push(out, Status::Warn, format!(
+                "Sender {} is not the {role}'s address; it pays gas but gains no rights",
+                to_checksum(&from, None)
+            )),
+        }
+    }
+
+    compare_decoded_block(out, entry.get("decodedTx"), &tx);
+    report
+}
+
+fn check_delegation(
+    out: &mut Vec<Finding>,
+    pre: &str,
+    d: &DelegationDecodedOrdered,
+    scheme: &dyn PayloadScheme,
+    digest: PayloadDigest,
+    now: i64,
+    display: &DisplaySettings,
+) {
+    check_payload(out, pre, d.payload(), &d.signatures(), scheme, digest);
+    check_window(out, pre, "Delegation", &d.delegationEndTime, now, display);
+}
+
+fn check_revocation(
+    out: &mut Vec<Finding>,
+    pre: &str,
+    r: &RevocationDecodedOrdered,
+    scheme: &dyn PayloadScheme,
+    digest: PayloadDigest,
+    now: i64,
+    display: &DisplaySettings,
+) {
+    check_payload(out, pre, r.payload(), &r.signatures(), scheme, digest);
+    check_window(out, pre, "Revocation", &r.revocationEndTime, now, display);
+}
+
+fn check_invalidation(out: &mut Vec<Finding>, p: &InvalidationDecodedOrdered, scheme: &dyn PayloadScheme, digest: PayloadDigest) {
+    check_payload(out, "", p.payload(), &p.signatures(), scheme, digest);
+}
+
+/// Check each payload signature against its public key under the local digest mode. A match
+/// only under the other mode is still a failure: the contract at this address would reject it.
+fn check_payload(
+    out: &mut Vec<Finding>,
+    pre: &str,
+    payload: Result<Payload>,
+    sigs: &[PayloadSig],
+    scheme: &dyn PayloadScheme,
+    digest: PayloadDigest,
+) {
+    let hash = match payload {
+        Ok(p) => scheme.payload_hash(&p),
+        Err(e) => {
+            push(out, Status::Fail, format!("{pre}cannot rebuild the signed payload: {e:#}"));
+            return;
+        }
+    };
+    for sig_ref in sigs {
+        let role = sig_ref.role;
+        if sig_ref.is_absent() {
+            push(out, Status::Warn, format!("{pre}No {role} signature (the {role} was given by public key only)"));
+            continue;
+        }
+        let (Ok(pubkey), Ok(sig)) = (hex_to_bytes(sig_ref.pubkey), sig_ref.signature()) else {
+            push(out, Status::Fail, format!("{pre}{role} public key or signature does not parse"));
+            continue;
+        };
+        let other = match digest {
+            PayloadDigest::Eip191 => PayloadDigest::RawKeccak,
+            PayloadDigest::RawKeccak => PayloadDigest::Eip191,
+        };
+        if scheme.verify_payload(hash, digest, &sig, &pubkey) {
+            push(out, Status::Pass, format!("{pre}{role} signature verified ({}, {})", scheme.name(), digest_name(digest)));
+        } else if scheme.verify_payload(hash, other, &sig, &pubkey) {
+            push(out, Status::Fail, format!(
+                "{pre}{role} signature covers the {} digest, but local policy for this contract is {}",
+                digest_name(other), digest_name(digest)
+            ));
+        } else {
+            push(out, Status::Fail, format!("{pre}{role} signature does not match the {role} public key"));
+        }
+    }
+}
+
+fn digest_name(digest: PayloadDigest) -> &'static str {
+    match digest {
+        PayloadDigest::Eip191 => "EIP-191",
+        PayloadDigest::RawKeccak => "raw keccak",
+    }
+}
+
+/// Flags a window that has already closed (0 = open-ended).
+fn check_window(out: &mut Vec<Finding>, pre: &str, what: &str, end: &str, now: i64, display: &DisplaySettings) {
+    if let Ok(end) = end.parse::<i64>() {
+        if end != 0 && end < now {
+            push(out, Status::Warn, format!("{pre}{what} window already ended (end time {end}, {})", display.unix_time(end)));
+        }
+    }
+}
+
+/// The informational `decodedTx` block must agree with the signed transaction, since
+/// reviewers and downstream tools tend to read it instead of the raw bytes.
+fn compare_decoded_block(out: &mut Vec<Finding>, block: Option<&serde_json::Value>, tx: &DecodedTxOut) {
+    let Some(block) = block else {
+        push(out, Status::Warn, "Entry has no decodedTx block".into());
+        return;
+    };
+    let differing = tx.differing_fields(block);
+    if differing.is_empty() {
+        push(out, Status::Pass, "decodedTx block matches the signed transaction".into());
+    } else {
+        push(out, Status::Fail, format!(
+            "decodedTx block differs from the signed transaction in: {}",
+            differing.join(", ")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_that_stop_early_still_have_a_failing_finding() {
+        let (abi, settings) = (load_abi().unwrap(), Settings::default());
+        for entry in [serde_json::json!({}), serde_json::json!({ "signedTx": "0x02zz" })] {
+            let report = verify_entry(1, &entry, &abi, &settings, 0);
+            assert_eq!(report.findings.len(), 1, "{entry}");
+            assert_eq!(report.status(), Status::Fail, "{entry}");
+        }
+    }
+}
@@ -0,0 +1,435 @@
+//! The filesystem as screens and writers see it. Everything goes through the [`Fs`] trait, held
+//! in `AppCtx::fs`, so the file pickers and the signed-file writer can run against [`MemFs`]
+//! (headless scripts, checks) as well as the real disk, and so file access has one place
+//! where a policy can be applied: [`SandboxFs`] confines it to the configured directories.
+
+#[cfg(any(test, feature = "script"))]
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{self, ErrorKind};
+use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+pub trait Fs: Send + Sync {
+    /// Entries of `dir`, in no particular order.
+    fn read_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>>;
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    /// Create or replace `path`; the parent directory must exist.
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()>;
+    /// Create `path` with `data`, failing with `AlreadyExists` if it exists.
+    fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()>;
+    /// Create one directory, failing with `AlreadyExists` if it exists.
+    fn create_dir(&self, path: &Path) -> io::Result<()>;
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    /// Remove a directory and everything in it.
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()>;
+    /// Move the file `from` to `to`, failing with `AlreadyExists` if `to` exists.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    /// Overwrite the file's contents in place with random bytes, flush them to the device,
+    /// then remove it.
+    fn shred(&self, path: &Path) -> io::Result<()>;
+    fn is_dir(&self, path: &Path) -> bool;
+    /// Whether `path` exists as a file or directory.
+    fn exists(&self, path: &Path) -> bool;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+/// Files (not directories) directly inside `dir`, sorted.
+pub fn list_files(fs: &dyn Fs, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut out: Vec<PathBuf> = fs.read_dir(dir)?.into_iter().filter(|e| !e.is_dir).map(|e| e.path).collect();
+    out.sort();
+    Ok(out)
+}
+
+/// `Arc<dyn Fs>` that defaults to the real filesystem, so `AppCtx` stays `Default`.
+#[derive(Clone)]
+pub struct FsRef(pub Arc<dyn Fs>);
+
+impl Default for FsRef {
+    fn default() -> Self { Self(Arc::new(RealFs)) }
+}
+
+impl Deref for FsRef {
+    type Target = dyn Fs;
+    fn deref(&self) -> &Self::Target { &*self.0 }
+}
+
+/// The local disk, through `std::fs`.
+pub struct RealFs;
+
+impl Fs for RealFs {
+    fn read_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>> {
+        std::fs::read_dir(dir)?
+            .map(|ent| {
+                let path = ent?.path();
+                Ok(DirEntry { is_dir: path.is_dir(), path })
+            })
+            .collect()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> { std::fs::read(path) }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> { std::fs::write(path, data) }
+
+    fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        use std::io::Write;
+        let mut f = std::fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+        f.write_all(data)?;
+        f.flush()
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> { std::fs::create_dir(path) }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> { std::fs::create_dir_all(path) }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> { std::fs::remove_dir_all(path) }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        // std::fs::rename replaces an existing target
+        if to.exists() {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} exists", to.display())));
+        }
+        std::fs::rename(from, to)
+    }
+
+    fn shred(&self, path: &Path) -> io::Result<()> {
+        use rand::RngCore;
+        use std::io::Write;
+        let mut f = std::fs::OpenOptions::new().write(true).open(path)?;
+        let mut left = f.metadata()?.len();
+        let mut buf = vec![0u8; 64 * 1024];
+        while left > 0 {
+            let n = left.min(buf.len() as u64) as usize;
+            rand::thread_rng().fill_bytes(&mut buf[..n]);
+            f.write_all(&buf[..n])?;
+            left -= n as u64;
+        }
+        f.sync_all()?;
+        drop(f);
+        std::fs::remove_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool { path.is_dir() }
+
+    fn exists(&self, path: &Path) -> bool { path.exists() }
+}
+
+/// In-memory filesystem. Paths are compared after dropping `.` components, so `./out/a.txt`
+/// and `out/a.txt` are the same file; the empty path is the (always present) root. Only in
+/// tests and `script` builds, for the key-script driver.
+#[cfg(any(test, feature = "script"))]
+#[derive(Default)]
+pub struct MemFs {
+    files: Mutex<BTreeMap<PathBuf, Vec<u8>>>,
+    dirs: Mutex<BTreeSet<PathBuf>>,
+}
+
+#[cfg(any(test, feature = "script"))]
+fn key(path: &Path) -> PathBuf {
+    path.components().filter(|c| !matches!(c, Component::CurDir)).collect()
+}
+
+#[cfg(any(test, feature = "script"))]
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(ErrorKind::NotFound, format!("{} not found", path.display()))
+}
+
+#[cfg(any(test, feature = "script"))]
+impl MemFs {
+    pub fn new() -> Self { Self::default() }
+
+    fn has_dir(&self, k: &Path) -> bool {
+        k.as_os_str().is_empty() || self.dirs.lock().map(|d| d.contains(k)).unwrap_or(false)
+    }
+
+    fn parent_exists(&self, k: &Path) -> bool {
+        k.parent().map_or(true, |p| self.has_dir(p))
+    }
+}
+
+#[cfg(any(test, feature = "script"))]
+impl Fs for MemFs {
+    fn read_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>> {
+        let k = key(dir);
+        if !self.has_dir(&k) {
+            return Err(not_found(dir));
+        }
+        let child = |p: &PathBuf| p.parent() == Some(k.as_path());
+        let files = self.files.lock().map_err(|_| io::Error::other("MemFs poisoned"))?;
+        let dirs = self.dirs.lock().map_err(|_| io::Error::other("MemFs poisoned"))?;
+        Ok(dirs.iter().filter(|p| child(p)).map(|p| DirEntry { path: dir.join(p.file_name().unwrap_or_default()), is_dir: true })
+            .chain(files.keys().filter(|p| child(p)).map(|p| DirEntry { path: dir.join(p.file_name().unwrap_or_default()), is_dir: false }))
+            .collect())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let files = self.files.lock().map_err(|_| io::Error::other("MemFs poisoned"))?;
+        files.get(&key(path)).cloned().ok_or_else(|| not_found(path))
+    }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let k = key(path);
+        if !self.parent_exists(&k) {
+            return Err(not_found(path.parent().unwrap_or(path)));
+        }
+        if self.has_dir(&k) {
+            return Err(io::Error::new(ErrorKind::IsADirectory, format!("{} is a directory", path.display())));
+        }
+        let mut files = self.files.lock().map_err(|_| io::Error::other("MemFs poisoned"))?;
+        files.insert(k, data.to_vec());
+        Ok(())
+    }
+
+    fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let k = key(path);
+        let exists = self.has_dir(&k) || self.files.lock().map(|f| f.contains_key(&k)).unwrap_or(false);
+        if exists {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} exists", path.display())));
+        }
+        self.write(path, data)
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let k = key(path);
+        let exists = self.has_dir(&k) || self.files.lock().map(|f| f.contains_key(&k)).unwrap_or(false);
+        if exists {
+            return Err(io::Error::new(ErrorKind::AlreadyExists, format!("{} exists", path.display())));
+        }
+        if !self.parent_exists(&k) {
+            return Err(not_found(path.parent().unwrap_or(path)));
+        }
+        self.dirs.lock().map_err(|_| io::Error::other("MemFs poisoned"))?.insert(k);
+        Ok(())
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let k = key(path);
+        let mut dirs = self.dirs.lock().map_err(|_| io::Error::other("MemFs poisoned"))?;
+        for dir in k.ancestors().filter(|a| !a.as_os_str().is_empty()) {
+            dirs.insert(dir.to_path_buf());
+        }
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        let k = key(path);
+        if k.as_os_str().is_empty() || !self.has_dir(&k) {
+            return Err(not_found(path));
+        }
+        self.files.lock().map_err(|_| io::Error::other("MemFs poisoned"))?.retain(|p, _| !p.starts_with(&k));
+        self.dirs.lock().map_err(|_| io::Error::other("MemFs poisoned"))?.retain(|p| !p.starts_with(&k));
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let data = self.read(from)?;
+        self.create_new(to, &data)?;
+        self.files.lock().map_err(|_| io::Error::other("MemFs poisoned"))?.remove(&key(from));
+        Ok(())
+    }
+
+    fn shred(&self, path: &Path) -> io::Result<()> {
+        let mut files = self.files.lock().map_err(|_| io::Error::other("MemFs poisoned"))?;
+        files.remove(&key(path)).map(|_| ()).ok_or_else(|| not_found(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool { self.has_dir(&key(path)) }
+
+    fn exists(&self, path: &Path) -> bool {
+        let k = key(path);
+        self.has_dir(&k) || self.files.lock().map(|f| f.contains_key(&k)).unwrap_or(false)
+    }
+}
+
+/// Why a [`SandboxFs`] refused an operation: `dir` is outside every allowed directory.
+/// Carried inside the `PermissionDenied` `io::Error` and kept for the override prompt.
+#[derive(Debug, Clone)]
+pub struct OutsideSandbox {
+    /// Resolved directory that would have to be allowed (the parent, for a file).
+    pub dir: PathBuf,
+}
+
+impl fmt::Display for OutsideSandbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is outside the allowed directories (see Sandbox directories in Settings)", self.dir.display())
+    }
+}
+
+impl std::error::Error for OutsideSandbox {}
+
+/// Absolute form of `path` for containment checks: symlinks are resolved in the part that
+/// exists, and `.`/`..` are applied lexically to the rest, so neither `../..` nor a link
+/// out of an allowed directory gets past the check.
+pub fn resolve(path: &Path) -> PathBuf {
+    let abs = std::env::current_dir().unwrap_or_default().join(path);
+    let comps: Vec<Component> = abs.components().collect();
+    let (mut out, rest) = (0..=comps.len())
+        .rev()
+        .find_map(|n| {
+            let prefix: PathBuf = comps[..n].iter().collect();
+            std::fs::canonicalize(prefix).ok().map(|c| (c, &comps[n..]))
+        })
+        .unwrap_or_else(|| (PathBuf::new(), &comps[..]));
+    for c in rest {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => { out.pop(); }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[derive(Default)]
+struct Policy {
+    roots: Vec<PathBuf>,
+    /// Directories the operator allowed for this session through the override prompt.
+    grants: Vec<PathBuf>,
+    /// Directories below a root that stay closed anyway (other workspaces; see `workspace.rs`).
+    excluded: Vec<PathBuf>,
+    /// Last refusal not yet shown to the operator.
+    denied: Option<OutsideSandbox>,
+}
+
+/// Shared handle to the allowed directories: `Settings::sandbox_roots`, plus any directories
+/// granted for the session. With no roots configured, only the working directory is allowed.
+#[derive(Clone, Default)]
+pub struct Sandbox(Arc<Mutex<Policy>>);
+
+impl Sandbox {
+    fn policy(&self) -> std::sync::MutexGuard<'_, Policy> {
+        self.0.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    pub fn set_roots(&self, roots: &[String]) {
+        self.policy().roots = roots.iter().map(|r| r.trim()).filter(|r| !r.is_empty()).map(PathBuf::from).collect();
+    }
+
+    /// Allow `dir` and everything below it until the app exits.
+    pub fn grant(&self, dir: &Path) {
+        self.policy().grants.push(resolve(dir));
+    }
+
+    /// Start over for another workspace: drop the session's grants and close `excluded`.
+    pub fn reset_for_workspace(&self, excluded: Vec<PathBuf>) {
+        let mut policy = self.policy();
+        policy.grants.clear();
+        policy.excluded = excluded;
+    }
+
+    /// The configured roots, resolved (the working directory when none are set).
+    pub fn roots(&self) -> Vec<PathBuf> {
+        let policy = self.policy();
+        if policy.roots.is_empty() {
+            return vec![resolve(Path::new("."))];
+        }
+        policy.roots.iter().map(|r| resolve(r)).collect()
+    }
+
+    /// The most recent refusal, once; the event loop turns it into the override prompt.
+    pub fn take_denied(&self) -> Option<OutsideSandbox> {
+        self.policy().denied.take()
+    }
+
+    /// Whether `path` is inside an allowed directory; unlike `check`, a `false` is not
+    /// recorded for the override prompt.
+    pub fn allows(&self, path: &Path) -> bool {
+        let target = resolve(path);
+        let (granted, excluded) = {
+            let policy = self.policy();
+            (
+                policy.grants.iter().any(|g| target.starts_with(g)),
+                policy.excluded.iter().any(|e| target.starts_with(e)),
+            )
+        };
+        granted || (!excluded && self.roots().iter().any(|r| target.starts_with(r)))
+    }
+
+    /// `Ok` if `path` may be touched. `is_dir` says whether `path` is the directory itself
+    /// or a file in it, which decides what the override prompt offers to allow.
+    pub fn check(&self, path: &Path, is_dir: bool) -> io::Result<()> {
+        if self.allows(path) {
+            return Ok(());
+        }
+        let target = resolve(path);
+        let dir = if is_dir { target } else { target.parent().map(Path::to_path_buf).unwrap_or(target) };
+        let denied = OutsideSandbox { dir };
+        self.policy().denied = Some(denied.clone());
+        Err(io::Error::new(ErrorKind::PermissionDenied, denied))
+    }
+}
+
+/// Another filesystem, restricted to the directories a [`Sandbox`] allows. Checking
+/// whether a path exists is not restricted; listing, reading and writing are.
+pub struct SandboxFs {
+    inner: FsRef,
+    sandbox: Sandbox,
+}
+
+impl SandboxFs {
+    pub fn new(inner: FsRef, sandbox: Sandbox) -> Self {
+        Self { inner, sandbox }
+    }
+}
+
+impl Fs for SandboxFs {
+    fn read_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>> {
+        self.sandbox.check(dir, true)?;
+        self.inner.read_dir(dir)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.sandbox.check(path, false)?;
+        self.inner.read(path)
+    }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.sandbox.check(path, false)?;
+        self.inner.write(path, data)
+    }
+
+    fn create_new(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.sandbox.check(path, false)?;
+        self.inner.create_new(path, data)
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        self.sandbox.check(path, true)?;
+        self.inner.create_dir(path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.sandbox.check(path, true)?;
+        self.inner.create_dir_all(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.sandbox.check(path, true)?;
+        self.inner.remove_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.sandbox.check(from, false)?;
+        self.sandbox.check(to, false)?;
+        self.inner.rename(from, to)
+    }
+
+    fn shred(&self, path: &Path) -> io::Result<()> {
+        self.sandbox.check(path, false)?;
+        self.inner.shred(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool { self.inner.is_dir(path) }
+
+    fn exists(&self, path: &Path) -> bool { self.inner.exists(path) }
+}
@@ -0,0 +1,131 @@
+//! JavaScript exports for a browser page on the online side (`wasm-pack build --target web`).
+//! Both functions take and return JSON text and never see a key.
+//!
+//! `verifySignedFile` checks what can be checked without this machine's settings: each
+//! transaction decodes and its sender is recovered, the calldata is an Inkan call, every payload
+//! signature verifies against its public key (and under which digest mode), and the `decodedTx`
+//! block agrees with the signed bytes. The policy checks of **Verify Signed File** (registered
+//! networks, the contract's configured digest mode, calldata lint, validity windows) need the
+//! local settings and stay in the TUI.
+
+use serde::Serialize;
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::abi::load_abi;
+use crate::decoder::decode_signed_tx;
+use crate::hexstr::hex_to_bytes;
+use crate::payload::{payload_hash, verify_secp256k1, PayloadDigest};
+use crate::signed_tx::{signed_tx_has_high_s, HIGH_S_TX_WARNING};
+
+/// The `decodedTx` block for a raw `0x02…` transaction, sender recovered.
+#[wasm_bindgen(js_name = decodeSignedTx)]
+pub fn decode_signed_tx_json(signed_tx: &str) -> Result<String, JsError> {
+    let abi = load_abi().map_err(js_error)?;
+    let tx = decode_signed_tx(signed_tx, &abi).map_err(js_error)?;
+    Ok(serde_json::to_string(&tx)?)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignatureCheck {
+    role: &'static str,
+    pubkey: String,
+    /// `eip191` or `raw_keccak` when the signature verifies; `null` when it does not (or is absent).
+    digest: Option<PayloadDigest>,
+    /// Counterparty given by public key only (all-zero signature).
+    absent: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryCheck {
+    /// 1-based, as in the TUI report.
+    index: usize,
+    /// The decoded transaction, when `signedTx` decodes.
+    decoded_tx: Option<Value>,
+    signatures: Vec<SignatureCheck>,
+    /// `decodedTx` fields that disagree with the signed bytes.
+    differing_fields: Vec<&'static str>,
+    errors: Vec<String>,
+    /// Findings that do not fail the entry, such as a high-s transaction signature.
+    warnings: Vec<String>,
+    /// Decodes, every required signature verifies and the `decodedTx` block agrees.
+    ok: bool,
+}
+
+/// Per-entry checks of a signed transaction file (versioned envelope or legacy array).
+#[wasm_bindgen(js_name = verifySignedFile)]
+pub fn verify_signed_file_json(contents: &str) -> Result<String, JsError> {
+    let json: Value = serde_json::from_str(contents)?;
+    let entries = match json.get("entries") {
+        Some(entries) => entries.as_array(),
+        None => json.as_array(),
+    };
+    let Some(entries) = entries.filter(|e| !e.is_empty()) else {
+        return Err(JsError::new("not a signed transaction file, or it holds no transactions"));
+    };
+    let abi = load_abi().map_err(js_error)?;
+    let checks: Vec<EntryCheck> = entries.iter().enumerate().map(|(i, entry)| check_entry(i + 1, entry, &abi)).collect();
+    Ok(serde_json::to_string(&serde_json::json!({
+        "formatVersion": json.get("formatVersion").and_then(Value::as_u64).unwrap_or(1),
+        "entries": checks,
+    }))?)
+}
+
+fn check_entry(index: usize, entry: &Value, abi: &ethers_core::abi::Abi) -> EntryCheck {
+    let mut check = EntryCheck {
+        index, decoded_tx: None, signatures: Vec::new(), differing_fields: Vec::new(), errors: Vec::new(),
+        warnings: Vec::new(), ok: false,
+    };
+    let Some(signed_tx) = entry["signedTx"].as_str() else {
+        check.errors.push("entry has no signedTx".into());
+        return check;
+    };
+    let tx = match decode_signed_tx(signed_tx, abi) {
+        Ok(tx) => tx,
+        Err(e) => {
+            check.errors.push(format!("signedTx does not decode: {e:#}"));
+            return check;
+        }
+    };
+    if signed_tx_has_high_s(signed_tx) {
+        check.warnings.push(HIGH_S_TX_WARNING.to_string());
+    }
+    for (payload, sigs) in tx.payloads() {
+        let hash = match payload {
+            Ok(p) => payload_hash(&p),
+            Err(e) => {
+                check.errors.push(format!("cannot rebuild the signed payload: {e:#}"));
+                continue;
+            }
+        };
+        for sig in sigs {
+            let absent = sig.is_absent();
+            let digest = match (hex_to_bytes(sig.pubkey), sig.signature()) {
+                (Ok(pubkey), Ok(s)) if !absent => [PayloadDigest::Eip191, PayloadDigest::RawKeccak]
+                    .into_iter()
+                    .find(|d| verify_secp256k1(hash, *d, &s, &pubkey)),
+                _ => None,
+            };
+            if digest.is_none() && !absent {
+                check.errors.push(format!("{} signature does not match the {} public key", sig.role, sig.role));
+            }
+            check.signatures.push(SignatureCheck { role: sig.role, pubkey: sig.pubkey.to_string(), digest, absent });
+        }
+    }
+    match entry.get("decodedTx") {
+        Some(block) => check.differing_fields = tx.differing_fields(block),
+        None => check.errors.push("entry has no decodedTx block".into()),
+    }
+    if !check.differing_fields.is_empty() {
+        check.errors.push(format!("decodedTx block differs in: {}", check.differing_fields.join(", ")));
+    }
+    check.ok = check.errors.is_empty();
+    check.decoded_tx = serde_json::to_value(&tx).ok();
+    check
+}
+
+fn js_error(e: anyhow::Error) -> JsError {
+    JsError::new(&format!("{e:#}"))
+}
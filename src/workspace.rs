@@ -0,0 +1,123 @@
+//! Identity workspaces: independent working directories, one per organization or environment,
+//! each with its own settings, master-password profile, key usage records, key files and output
+//! directories. Every path the tool uses is relative to the working directory, so a workspace is
+//! simply a directory the tool changes into.
+//!
+//! The directory the tool was started in is the `default` workspace; named workspaces are the
+//! subdirectories of its `workspaces/` directory. The sandbox of the default workspace keeps
+//! `workspaces/` closed, so no workspace's file pickers reach another's files.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use crate::defaults::Defaults;
+
+/// Name of the directory the tool was started in.
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// Longest accepted workspace name.
+const MAX_NAME_LEN: usize = 64;
+
+static LAUNCH_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Set by `--workspace`, which skips the chooser at startup.
+static CHOSEN_AT_LAUNCH: AtomicBool = AtomicBool::new(false);
+
+/// The directory the tool was started in (the `default` workspace). Fixed by the first call.
+pub fn launch_dir() -> &'static Path {
+    LAUNCH_DIR.get_or_init(|| std::env::current_dir().unwrap_or_default())
+}
+
+fn workspaces_root() -> PathBuf {
+    launch_dir().join(Defaults::WORKSPACES_DIR)
+}
+
+/// `default`, then the named workspaces in alphabetical order.
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(workspaces_root())
+        .map(|rd| {
+            rd.flatten()
+                .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|n| validate_name(n).is_ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.insert(0, DEFAULT_WORKSPACE.to_string());
+    names
+}
+
+/// Letters, digits, `-` and `_`, so a name is always a single plain directory.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        bail!("A workspace name has 1 to {MAX_NAME_LEN} characters.");
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        bail!("A workspace name may only contain letters, digits, '-' and '_'.");
+    }
+    if name == DEFAULT_WORKSPACE {
+        bail!("\"{DEFAULT_WORKSPACE}\" is the directory the tool was started in.");
+    }
+    Ok(())
+}
+
+fn dir_of(name: &str) -> Result<PathBuf> {
+    if name == DEFAULT_WORKSPACE {
+        return Ok(launch_dir().to_path_buf());
+    }
+    validate_name(name)?;
+    Ok(workspaces_root().join(name))
+}
+
+/// The workspace the working directory is in (`default` for anything else).
+pub fn current() -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    match cwd.strip_prefix(workspaces_root()) {
+        Ok(rest) => rest.to_string_lossy().into_owned(),
+        Err(_) => DEFAULT_WORKSPACE.to_string(),
+    }
+}
+
+/// Create the named workspace's directory. It must not exist yet.
+pub fn create(name: &str) -> Result<()> {
+    validate_name(name)?;
+    let dir = dir_of(name)?;
+    if dir.exists() {
+        bail!("Workspace \"{name}\" already exists.");
+    }
+    std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))
+}
+
+/// Make `name` the working directory. The session must be reloaded afterwards
+/// (`app::switch_workspace`).
+pub fn enter(name: &str) -> Result<()> {
+    let dir = dir_of(name)?;
+    if !dir.is_dir() {
+        bail!("There is no workspace \"{name}\" (looked for {}).", dir.display());
+    }
+    std::env::set_current_dir(&dir).with_context(|| format!("changing to {}", dir.display()))
+}
+
+/// `--workspace <name>`: enter `name` before anything else runs.
+pub fn enter_at_launch(name: &str) -> Result<()> {
+    launch_dir();
+    enter(name)?;
+    CHOSEN_AT_LAUNCH.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn chosen_at_launch() -> bool {
+    CHOSEN_AT_LAUNCH.load(Ordering::Relaxed)
+}
+
+/// Directories the current workspace's sandbox keeps closed: the other workspaces, when the
+/// current one is the launch directory that holds them.
+pub fn excluded_dirs() -> Vec<PathBuf> {
+    if current() == DEFAULT_WORKSPACE {
+        vec![crate::vfs::resolve(&workspaces_root())]
+    } else {
+        Vec::new()
+    }
+}
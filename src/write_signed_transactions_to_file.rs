@@ -1,146 +1,220 @@
-use anyhow::{anyhow, Context, Result};
-use serde_json;
-use std::fs::{self, File, OpenOptions};
-use std::io::{self, Write, ErrorKind};
+use anyhow::{Context, Result};
+use std::io::{self, ErrorKind};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::types::{BatchEntryOut, DecodedOne, DecodedTxOut};
+use crate::filename_template::{insert_label, render_template, vars_from_decoded, FilenameTemplates};
+use crate::output_encoder::OutputEncoder;
+use crate::settings::{Settings, SignedOutputFormat};
+use crate::types::{
+    BatchEntryOut, BatchIncompleteOut, BatchManifestEntryOut, BatchManifestOut, BatchStatsOut, SignedFileMetadata,
+};
+use crate::util::{parse_addr, parse_u256_any, sha256_hex};
+use ethers_core::types::U256;
+use ethers_core::utils::to_checksum;
+use crate::vfs::Fs;
 
-/// Write N signed transactions to a file as a JSON array.
+/// Name of the manifest written into every batch run directory.
+pub const BATCH_MANIFEST_FILE: &str = "manifest.json";
+
+/// Build the metadata block for a signed file, or None when the settings ask
+/// for the legacy bare-array format.
+/// `func` names the event type when the file holds one kind of transaction, so its
+/// configured default gas limit is recorded.
+pub fn signed_file_metadata(settings: &Settings, input_sha256: Option<String>, func: Option<&str>) -> Option<SignedFileMetadata> {
+    if settings.signed_output_format == SignedOutputFormat::LegacyArray {
+        return None;
+    }
+    let label = settings.operator_label.trim();
+    Some(SignedFileMetadata {
+        fundingAddress: None,
+        maxCostWei: None,
+        toolVersion: env!("CARGO_PKG_VERSION").to_string(),
+        operatorLabel: if label.is_empty() { None } else { Some(label.to_string()) },
+        createdAt: time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        inputSha256: input_sha256,
+        gasLimitDefault: func.and_then(|f| settings.gas_limits.configured(f)).map(str::to_string),
+    })
+}
+
+/// Write N signed transactions to a file, serialized by `encoder` (see `output_encoder.rs`).
+/// - With `metadata`, JSON files are `{ "formatVersion", "metadata", "entries": [...] }`;
+///   without it, a bare JSON array (legacy format version 1).
+/// - Encoders for other formats replace the file's extension (`.csv`, `.s.sol`, ...).
 /// - If the file already exists, creates a unique variant like "file (1).txt".
-/// - `pretty = true` → pretty printed (human-readable), but still 100% processable.
-/// - `pretty = false` → compact JSON (no extra whitespace).
 pub fn write_signed_transactions_to_file<P: AsRef<Path>>(
+    fs: &dyn Fs,
     out_path: P,
     entries: &[BatchEntryOut],
-    pretty: bool,
+    metadata: Option<&SignedFileMetadata>,
+    encoder: &dyn OutputEncoder,
 ) -> Result<PathBuf> {
-    let out_path = out_path.as_ref();
+    let out_path = match encoder.extension() {
+        Some(ext) => out_path.as_ref().with_extension(ext),
+        None => out_path.as_ref().to_path_buf(),
+    };
+    let out_path = out_path.as_path();
 
     // Ensure parent directory exists
     if let Some(parent) = out_path.parent() {
         if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)
+            fs.create_dir_all(parent)
                 .with_context(|| format!("creating parent directory {}", parent.display()))?;
         }
     }
 
-    // Pick a unique filename (avoid overwrite)
-    let (mut f, final_path) = create_unique_file(out_path)?;
-
     // Serialize once (fail early if needed)
-    let json = if pretty {
-        serde_json::to_string_pretty(entries)?
-    } else {
-        serde_json::to_string(entries)?
-    };
+    let metadata = metadata.map(|m| with_funding(m, entries));
+    let data = encoder.encode(entries, metadata.as_ref())?;
+
+    // Pick a unique filename (avoid overwrite)
+    create_unique_file(fs, out_path, &data)
+        .with_context(|| format!("writing {}", out_path.display()))
+}
 
-    f.write_all(json.as_bytes())
-        .with_context(|| format!("writing {}", final_path.display()))?;
-    f.flush()?;
-    Ok(final_path)
+/// `metadata` plus the account that pays for `entries` (when they share one sender) and their
+/// total max cost, so the online operator knows what to fund.
+fn with_funding(metadata: &SignedFileMetadata, entries: &[BatchEntryOut]) -> SignedFileMetadata {
+    let mut out = metadata.clone();
+    let sender = entries.first().map(|e| e.decoded_tx.from.to_ascii_lowercase());
+    if sender.is_some() && entries.iter().all(|e| Some(e.decoded_tx.from.to_ascii_lowercase()) == sender) {
+        out.fundingAddress = sender
+            .and_then(|s| parse_addr(&s).ok())
+            .map(|a| to_checksum(&a, None));
+        out.maxCostWei = entries
+            .iter()
+            .try_fold(U256::zero(), |total, e| -> Result<U256> {
+                let cost = parse_u256_any(&e.decoded_tx.gasLimit)?.saturating_mul(parse_u256_any(&e.decoded_tx.maxFeePerGas)?);
+                Ok(total.saturating_add(cost))
+            })
+            .ok()
+            .map(|wei| wei.to_string());
+    }
+    out
 }
 
-/// Convenience: write a single signed transaction as a one-element JSON array.
+/// Convenience: write a single signed transaction as a one-element entries array.
 /// Returns the actual path written (unique name if needed).
 pub fn write_single_signed_transaction<P: AsRef<Path>>(
+    fs: &dyn Fs,
     out_path: P,
     entry: &BatchEntryOut,
-    pretty: bool,
+    metadata: Option<&SignedFileMetadata>,
+    encoder: &dyn OutputEncoder,
 ) -> Result<PathBuf> {
-    write_signed_transactions_to_file(out_path, std::slice::from_ref(entry), pretty)
+    write_signed_transactions_to_file(fs, out_path, std::slice::from_ref(entry), metadata, encoder)
 }
 
-/// Build a generic, human-readable filename for any signed transaction.
-pub fn build_filename_for_any_tx(decoded: &DecodedTxOut) -> String {
-    // 1) Simple Delegation
-    if let Some(DecodedOne::Delegation(a)) = decoded.decodedData.as_ref() {
-        if let (Ok(delg_x), Ok(dele_x)) = (
-            x_coord_hex_from_uncompressed(&a.delegatorPubkey),
-            x_coord_hex_from_uncompressed(&a.delegateePubkey),
-        ) {
-            return format!(
-                "{}_delegates_to_{}_nonce_{}.txt",
-                abbrev_64_hex(&delg_x),
-                abbrev_64_hex(&dele_x),
-                decoded.nonce
-            );
-        }
-    }
+/// A batch run to write with `write_batch_run_directory`.
+pub struct RunWrite<'a> {
+    pub out_dir: &'a Path,
+    /// The batch input file; its SHA-256 names the run and is recorded in the manifest.
+    pub input: &'a [u8],
+    pub entries: &'a [BatchEntryOut],
+    pub templates: &'a FilenameTemplates,
+    /// Carried by each per-item file when given (see `signed_file_metadata`).
+    pub metadata: Option<&'a SignedFileMetadata>,
+    pub encoder: &'a dyn OutputEncoder,
+    /// Throughput figures recorded in the manifest.
+    pub stats: Option<BatchStatsOut>,
+    /// Set when the batch stopped early.
+    pub partial: Option<Partial<'a>>,
+}
 
-    // 2) Simple Revocation
-    if let Some(DecodedOne::Revocation(b)) = decoded.decodedData.as_ref() {
-        if let (Ok(revoker_x), Ok(revokee_x)) = (
-            x_coord_hex_from_uncompressed(&b.revokerPubkey),
-            x_coord_hex_from_uncompressed(&b.revokeePubkey),
-        ) {
-            return format!(
-                "{}_revokes_from_{}_nonce_{}.txt",
-                abbrev_64_hex(&revoker_x),
-                abbrev_64_hex(&revokee_x),
-                decoded.nonce
-            );
-        }
-    }
+/// Why a batch run stopped before `total` items were signed.
+pub struct Partial<'a> {
+    pub total: usize,
+    pub reason: &'a str,
+}
 
-    // 3) Permanent Invalidation
-    if let Some(DecodedOne::Invalidation(i)) = decoded.decodedData.as_ref() {
-        if let Ok(x) = x_coord_hex_from_uncompressed(&i.invalidatedPubkey) {
-            return format!("invalidate_{}_nonce_{}.txt", abbrev_64_hex(&x), decoded.nonce);
-        }
-    }
+/// Write a signed batch into a fresh per-run directory under `run.out_dir`:
+/// `batch_<YYYYMMDD-HHMMSS>_<sha256(input)[..8]>/`, holding one single-entry
+/// file per item (named from `run.templates`) plus `manifest.json`.
+/// Colliding names inside the run get " (1)", " (2)", ... like single writes.
+/// A partial run's directory name ends in `_partial` and its manifest's `incomplete`
+/// block records the total item count and the reason.
+/// Returns the run directory path.
+pub fn write_batch_run_directory(fs: &dyn Fs, run: &RunWrite) -> Result<PathBuf> {
+    let &RunWrite { out_dir, input, entries, templates, metadata, encoder, ref stats, ref partial } = run;
+    let incomplete = partial
+        .as_ref()
+        .map(|p| BatchIncompleteOut { totalItems: p.total, reason: p.reason.to_string() });
 
-    // 4) REDELEGATION (Revocation + Delegation combo)
-
-    if decoded.funcName == "createRevocationEventFollowedByDelegationEvent" {
-        if let (Some(a), Some(b)) = (decoded.decodedDataTypeA.as_ref(), decoded.decodedDataTypeB.as_ref()) {
-            if let (Ok(revoker_x), Ok(revokee_x), Ok(delegatee_x)) = (
-                x_coord_hex_from_uncompressed(&b.revokerPubkey),
-                x_coord_hex_from_uncompressed(&b.revokeePubkey),
-                x_coord_hex_from_uncompressed(&a.delegateePubkey),
-            ) {
-                return format!(
-                    "{}_revokes_from_{}_delegates_to_{}_nonce_{}.txt",
-                    abbrev_64_hex(&revoker_x),
-                    abbrev_64_hex(&revokee_x),
-                    abbrev_64_hex(&delegatee_x),
-                    decoded.nonce
-                );
-            }
-        }
-    }
-
-    // 5) Fallback
-    format!("{}_nonce_{}.txt", decoded.funcName, decoded.nonce)
-}
+    fs.create_dir_all(out_dir)
+        .with_context(|| format!("creating directory {}", out_dir.display()))?;
 
+    let input_sha256 = sha256_hex(input);
+    let now = time::OffsetDateTime::now_utc();
+    let run_name = format!(
+        "batch_{:04}{:02}{:02}-{:02}{:02}{:02}_{}{}",
+        now.year(), u8::from(now.month()), now.day(),
+        now.hour(), now.minute(), now.second(),
+        &input_sha256[..8],
+        if incomplete.is_some() { "_partial" } else { "" }
+    );
+    let run_dir = create_unique_dir(fs, &out_dir.join(run_name))
+        .with_context(|| format!("creating run directory under {}", out_dir.display()))?;
 
-/// Extract the 32-byte X coordinate (64 hex chars) from an uncompressed pubkey hex.
-/// Accepts "0x04..." or "04..." (hex), must be 65 bytes = 130 hex chars.
-fn x_coord_hex_from_uncompressed(uncompressed_hex: &str) -> Result<String> {
-    let h = uncompressed_hex.strip_prefix("0x").unwrap_or(uncompressed_hex);
-    if !h.starts_with("04") || h.len() != 130 {
-        return Err(anyhow!(
-            "expected uncompressed pubkey (0x04 + X(64) + Y(64)), got: {} (len={})",
-            uncompressed_hex,
-            uncompressed_hex.len()
-        ));
+    let mut manifest_entries = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let filename = build_filename_for_any_tx(entry, templates);
+        let written = write_single_signed_transaction(fs, run_dir.join(filename), entry, metadata, encoder)?;
+        let file = written
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+        manifest_entries.push(BatchManifestEntryOut {
+            index,
+            file,
+            funcName: entry.decoded_tx.funcName.clone(),
+            from: entry.decoded_tx.from.clone(),
+            nonce: entry.decoded_tx.nonce,
+            chainId: entry.decoded_tx.chainId.clone(),
+            label: entry.label.clone(),
+        });
     }
-    Ok(h[2..66].to_ascii_lowercase())
+
+    let manifest = BatchManifestOut {
+        createdAt: now
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default(),
+        inputSha256: input_sha256,
+        itemCount: entries.len(),
+        incomplete,
+        stats: stats.clone(),
+        entries: manifest_entries,
+    };
+    let manifest_path = run_dir.join(BATCH_MANIFEST_FILE);
+    fs.write(&manifest_path, serde_json::to_string_pretty(&manifest)?.as_bytes())
+        .with_context(|| format!("writing {}", manifest_path.display()))?;
+
+    Ok(run_dir)
 }
 
-/// Abbreviate a 64-char hex string as "first8..last8".
-fn abbrev_64_hex(x64: &str) -> String {
-    if x64.len() >= 16 {
-        format!("{}..{}", &x64[..8], &x64[x64.len() - 8..])
-    } else {
-        x64.to_string()
+/// Build a human-readable filename for any signed transaction from the
+/// per-event-type template in `templates`.
+/// Falls back to "[funcName]_nonce_[nonce].txt" if the template references
+/// values that are not available for this transaction.
+pub fn build_filename_for_any_tx(entry: &BatchEntryOut, templates: &FilenameTemplates) -> String {
+    let decoded = &entry.decoded_tx;
+    let mut vars = vars_from_decoded(decoded);
+    insert_label(&mut vars, entry.label.as_deref());
+    if let Some(name) = templates
+        .for_func(&decoded.funcName)
+        .and_then(|t| render_template(t, &vars))
+    {
+        return name;
     }
+
+    format!("{}_nonce_{}.txt", decoded.funcName, decoded.nonce)
 }
 
-/// Create a file with a unique name, avoiding overwrite by appending " (1)", " (2)", etc.
-fn create_unique_file(path: &Path) -> io::Result<(File, PathBuf)> {
+/// Create a file holding `data` with a unique name, avoiding overwrite by appending " (1)", " (2)", etc.
+/// Returns the path written.
+fn create_unique_file(fs: &dyn Fs, path: &Path, data: &[u8]) -> io::Result<PathBuf> {
     let dir = path.parent().unwrap_or_else(|| Path::new("."));
     let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
@@ -159,8 +233,8 @@ fn create_unique_file(path: &Path) -> io::Result<(File, PathBuf)> {
         };
         let candidate_path = dir.join(&candidate_name);
 
-        match OpenOptions::new().write(true).create_new(true).open(&candidate_path) {
-            Ok(f) => return Ok((f, candidate_path)),
+        match fs.create_new(&candidate_path, data) {
+            Ok(()) => return Ok(candidate_path),
             Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
             Err(e) => return Err(e),
         }
@@ -172,6 +246,26 @@ fn create_unique_file(path: &Path) -> io::Result<(File, PathBuf)> {
     ))
 }
 
+/// Create a directory with a unique name, appending " (1)", " (2)", etc. if taken.
+fn create_unique_dir(fs: &dyn Fs, path: &Path) -> io::Result<PathBuf> {
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("run");
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+
+    for i in 0..10_000 {
+        let candidate = if i == 0 { path.to_path_buf() } else { parent.join(format!("{name} ({i})")) };
+        match fs.create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "failed to create a unique directory after many attempts",
+    ))
+}
+
 /// Generate a sibling temporary filename for atomic writes (no longer needed but kept for reference).
 #[allow(dead_code)]
 fn sibling_tmp_path(target: &Path) -> PathBuf {